@@ -103,7 +103,7 @@ void main() {
 
         let shader = cs::Shader::load(device.clone())
             .expect("failed to create shader module");
-        ComputePipeline::new(device.clone(), &shader.main_entry_point(), &())
+        ComputePipeline::new(device.clone(), &shader.main_entry_point(), &(), None)
             .expect("failed to create compute pipeline")
     });
 
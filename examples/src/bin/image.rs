@@ -60,13 +60,15 @@ fn main() {
         dimensions = caps.current_extent.unwrap_or([1024, 768]);
         let usage = caps.supported_usage_flags;
         let alpha = caps.supported_composite_alpha.iter().next().unwrap();
-        let format = caps.supported_formats[0].0;
+        let (format, color_space) = caps.supported_formats[0];
 
         vulkano::swapchain::Swapchain::new(device.clone(), surface.clone(), caps.min_image_count,
-                                           format, dimensions, 1,
+                                           format, color_space, dimensions, 1,
                                            usage, &queue, vulkano::swapchain::SurfaceTransform::Identity,
                                            alpha,
-                                           vulkano::swapchain::PresentMode::Fifo, true, None).expect("failed to create swapchain")
+                                           vulkano::swapchain::PresentMode::Fifo,
+                                           vulkano::swapchain::FullscreenExclusive::Default,
+                                           true, None).expect("failed to create swapchain")
     };
 
 
@@ -143,7 +145,7 @@ fn main() {
 
     let mut recreate_swapchain = false;
 
-    let mut previous_frame_end = Box::new(tex_future) as Box<GpuFuture>;
+    let mut previous_frame_end = tex_future.boxed();
 
     loop {
         previous_frame_end.cleanup_finished();
@@ -215,15 +217,15 @@ fn main() {
 
         match future {
             Ok(future) => {
-                previous_frame_end = Box::new(future) as Box<_>;
+                previous_frame_end = future.boxed();
             }
             Err(vulkano::sync::FlushError::OutOfDate) => {
                 recreate_swapchain = true;
-                previous_frame_end = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame_end = vulkano::sync::now(device.clone()).boxed();
             }
             Err(e) => {
                 println!("{:?}", e);
-                previous_frame_end = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame_end = vulkano::sync::now(device.clone()).boxed();
             }
         }
 
@@ -100,7 +100,7 @@ fn main() {
         let caps = surface
             .capabilities(graphics_device.physical_device())
             .expect("failure to get surface capabilities");
-        let format = caps.supported_formats[0].0;
+        let (format, color_space) = caps.supported_formats[0];
         let dimensions = caps.current_extent.unwrap_or([1024, 768]);
         let usage = caps.supported_usage_flags;
         let present = caps.present_modes.iter().next().unwrap();
@@ -110,6 +110,7 @@ fn main() {
             surface.clone(),
             caps.min_image_count,
             format,
+            color_space,
             dimensions,
             1,
             usage,
@@ -117,6 +118,7 @@ fn main() {
             vk::swapchain::SurfaceTransform::Identity,
             vk::swapchain::CompositeAlpha::Opaque,
             present,
+            vk::swapchain::FullscreenExclusive::Default,
             true,
             None,
         ).expect("failed to create swapchain")
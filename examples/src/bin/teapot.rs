@@ -62,13 +62,15 @@ fn main() {
         dimensions = caps.current_extent.unwrap_or([1024, 768]);
 
         let usage = caps.supported_usage_flags;
-        let format = caps.supported_formats[0].0;
+        let (format, color_space) = caps.supported_formats[0];
         let alpha = caps.supported_composite_alpha.iter().next().unwrap();
 
-        vulkano::swapchain::Swapchain::new(device.clone(), surface.clone(), caps.min_image_count, format, dimensions, 1,
+        vulkano::swapchain::Swapchain::new(device.clone(), surface.clone(), caps.min_image_count, format, color_space, dimensions, 1,
                                            usage, &queue, vulkano::swapchain::SurfaceTransform::Identity,
                                            alpha,
-                                           vulkano::swapchain::PresentMode::Fifo, true, None).expect("failed to create swapchain")
+                                           vulkano::swapchain::PresentMode::Fifo,
+                                           vulkano::swapchain::FullscreenExclusive::Default,
+                                           true, None).expect("failed to create swapchain")
     };
 
 
@@ -135,7 +137,7 @@ fn main() {
 
     let mut recreate_swapchain = false;
 
-    let mut previous_frame = Box::new(vulkano::sync::now(device.clone())) as Box<GpuFuture>;
+    let mut previous_frame = vulkano::sync::now(device.clone()).boxed();
     let rotation_start = std::time::Instant::now();
 
     loop {
@@ -237,15 +239,15 @@ fn main() {
 
         match future {
             Ok(future) => {
-                previous_frame = Box::new(future) as Box<_>;
+                previous_frame = future.boxed();
             }
             Err(vulkano::sync::FlushError::OutOfDate) => {
                 recreate_swapchain = true;
-                previous_frame = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame = vulkano::sync::now(device.clone()).boxed();
             }
             Err(e) => {
                 println!("{:?}", e);
-                previous_frame = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame = vulkano::sync::now(device.clone()).boxed();
             }
         }
 
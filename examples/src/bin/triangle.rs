@@ -48,6 +48,8 @@ use vulkano::instance::Instance;
 use vulkano::pipeline::GraphicsPipeline;
 use vulkano::pipeline::viewport::Viewport;
 use vulkano::swapchain;
+use vulkano::swapchain::CompositeAlpha;
+use vulkano::swapchain::FullscreenExclusive;
 use vulkano::swapchain::PresentMode;
 use vulkano::swapchain::SurfaceTransform;
 use vulkano::swapchain::Swapchain;
@@ -176,15 +178,19 @@ fn main() {
 
         // The alpha mode indicates how the alpha value of the final image will behave. For example
         // you can choose whether the window will be opaque or transparent.
-        let alpha = caps.supported_composite_alpha.iter().next().unwrap();
+        let alpha = caps.supported_composite_alpha.choose(Some(CompositeAlpha::Opaque)).unwrap();
+
+        // The transform is applied by the presentation engine before the image is shown. Here we
+        // let it handle rotated displays if it can, otherwise we use the current transform.
+        let transform = caps.choose_transform(Some(SurfaceTransform::Identity));
 
         // Choosing the internal format that the images will have.
-        let format = caps.supported_formats[0].0;
+        let (format, color_space) = caps.supported_formats[0];
 
         // Please take a look at the docs for the meaning of the parameters we didn't mention.
         Swapchain::new(device.clone(), surface.clone(), caps.min_image_count, format,
-                       dimensions, 1, caps.supported_usage_flags, &queue,
-                       SurfaceTransform::Identity, alpha, PresentMode::Fifo, true,
+                       color_space, dimensions, 1, caps.supported_usage_flags, &queue,
+                       transform, alpha, PresentMode::Fifo, FullscreenExclusive::Default, true,
                        None).expect("failed to create swapchain")
     };
 
@@ -324,7 +330,7 @@ void main() {
     //
     // Destroying the `GpuFuture` blocks until the GPU is finished executing it. In order to avoid
     // that, we store the submission of the previous frame here.
-    let mut previous_frame_end = Box::new(now(device.clone())) as Box<GpuFuture>;
+    let mut previous_frame_end = now(device.clone()).boxed();
 
     loop {
         // It is important to call this function from time to time, otherwise resources will keep
@@ -386,6 +392,12 @@ void main() {
             Err(err) => panic!("{:?}", err)
         };
 
+        // The image can still be used if the swapchain is suboptimal, but we recreate the
+        // swapchain at the next frame.
+        if acquire_future.is_suboptimal() {
+            recreate_swapchain = true;
+        }
+
         // In order to draw, we have to build a *command buffer*. The command buffer object holds
         // the list of commands that are going to be executed.
         //
@@ -448,15 +460,15 @@ void main() {
 
         match future {
             Ok(future) => {
-                previous_frame_end = Box::new(future) as Box<_>;
+                previous_frame_end = future.boxed();
             }
             Err(vulkano::sync::FlushError::OutOfDate) => {
                 recreate_swapchain = true;
-                previous_frame_end = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame_end = vulkano::sync::now(device.clone()).boxed();
             }
             Err(e) => {
                 println!("{:?}", e);
-                previous_frame_end = Box::new(vulkano::sync::now(device.clone())) as Box<_>;
+                previous_frame_end = vulkano::sync::now(device.clone()).boxed();
             }
         }
 
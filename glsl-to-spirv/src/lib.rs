@@ -9,22 +9,33 @@
 
 extern crate tempdir;
 
+use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub type SpirvOutput = File;
 
 pub fn compile(code: &str, ty: ShaderType) -> Result<SpirvOutput, String> {
-    compile_inner(Some((code, ty)))
+    compile_inner(Some((code, ty)), &CompileOptions::default())
+}
+
+/// Same as `compile`, but with additional options such as macro definitions and include
+/// directories.
+pub fn compile_with_options(code: &str, ty: ShaderType, options: &CompileOptions)
+                            -> Result<SpirvOutput, String> {
+    compile_inner(Some((code, ty)), options)
 }
 
 // Eventually the API will look like this, with an iterator for multiple shader stages.
 // However for the moment GLSLang doesn't like that, so we only pass one shader at a time.
-fn compile_inner<'a, I>(shaders: I) -> Result<SpirvOutput, String>
+fn compile_inner<'a, I>(shaders: I, options: &CompileOptions) -> Result<SpirvOutput, String>
     where I: IntoIterator<Item = (&'a str, ShaderType)>
 {
-    let temp_dir = tempdir::TempDir::new("glslang-compile").unwrap();
+    let temp_dir = tempdir::TempDir::new("glslang-compile").map_err(|e| e.to_string())?;
     let output_file = temp_dir.path().join("compilation_output.spv");
 
     let mut command = Command::new(concat!(env!("OUT_DIR"), "/glslang_validator"));
@@ -32,6 +43,51 @@ fn compile_inner<'a, I>(shaders: I) -> Result<SpirvOutput, String>
     command.arg("-l");
     command.arg("-o").arg(&output_file);
 
+    if options.language == SourceLanguage::Hlsl {
+        command.arg("-D");
+    }
+
+    if let Some(ref entry_point) = options.entry_point {
+        command.arg("-e").arg(entry_point);
+    }
+
+    for &(ref name, ref value) in &options.macros {
+        match *value {
+            Some(ref value) => command.arg(format!("-D{}={}", name, value)),
+            None => command.arg(format!("-D{}", name)),
+        };
+    }
+
+    for dir in &options.include_dirs {
+        command.arg(format!("-I{}", dir.display()));
+    }
+
+    // The sources are written in the temporary directory, so that `#include "name"` finds the
+    // includes that are provided as strings.
+    for &(ref name, ref source) in &options.include_sources {
+        // Only plain relative paths are accepted, so that the includes can't be written outside
+        // of the temporary directory.
+        let is_plain_relative = {
+            let path = Path::new(name);
+            path.components().next().is_some() &&
+                path.components().all(|c| match c {
+                                           Component::Normal(_) => true,
+                                           _ => false,
+                                       })
+        };
+        if !is_plain_relative {
+            return Err(format!("invalid include source name: {:?}", name));
+        }
+
+        let file_path = temp_dir.path().join(name);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        File::create(&file_path)
+            .and_then(|mut file| file.write_all(source.as_bytes()))
+            .map_err(|e| e.to_string())?;
+    }
+
     for (num, (source, ty)) in shaders.into_iter().enumerate() {
         let extension = match ty {
             ShaderType::Vertex => ".vert",
@@ -44,9 +100,8 @@ fn compile_inner<'a, I>(shaders: I) -> Result<SpirvOutput, String>
 
         let file_path = temp_dir.path().join(format!("{}{}", num, extension));
         File::create(&file_path)
-            .unwrap()
-            .write_all(source.as_bytes())
-            .unwrap();
+            .and_then(|mut file| file.write_all(source.as_bytes()))
+            .map_err(|e| e.to_string())?;
         command.arg(file_path);
     }
 
@@ -64,6 +119,38 @@ fn compile_inner<'a, I>(shaders: I) -> Result<SpirvOutput, String>
     return Err(error1.into_owned() + &error2);
 }
 
+/// Language of the source code of a shader.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SourceLanguage {
+    Glsl,
+    Hlsl,
+}
+
+impl Default for SourceLanguage {
+    #[inline]
+    fn default() -> SourceLanguage {
+        SourceLanguage::Glsl
+    }
+}
+
+/// Additional options for `compile_with_options`.
+///
+/// Includes are resolved by the `#include "name"` directive of the `GL_GOOGLE_include_directive`
+/// extension, first among `include_sources` and then within `include_dirs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// Language of the source code.
+    pub language: SourceLanguage,
+    /// Name of the entry point in the source code, if it is not `main`.
+    pub entry_point: Option<String>,
+    /// Macros to define before compiling, with their optional value.
+    pub macros: Vec<(String, Option<String>)>,
+    /// Directories where included files are searched.
+    pub include_dirs: Vec<PathBuf>,
+    /// Files that can be included, as a name and the source code of the file.
+    pub include_sources: Vec<(String, String)>,
+}
+
 /// Type of shader.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ShaderType {
@@ -23,3 +23,50 @@ void main() {
 
     glsl_to_spirv::compile(shader, glsl_to_spirv::ShaderType::Fragment).unwrap();
 }
+
+#[test]
+fn macros_and_includes() {
+    let shader = r#"
+#version 450
+#extension GL_GOOGLE_include_directive : require
+
+#include "color.glsl"
+
+layout(location = 0) out vec4 f_color;
+
+void main() {
+    f_color = COLOR * INTENSITY;
+}
+"#;
+
+    let options = glsl_to_spirv::CompileOptions {
+        macros: vec![("INTENSITY".to_owned(), Some("0.5".to_owned()))],
+        include_sources: vec![("color.glsl".to_owned(),
+                               "#define COLOR vec4(1.0, 0.0, 0.0, 1.0)\n".to_owned())],
+        ..glsl_to_spirv::CompileOptions::default()
+    };
+
+    glsl_to_spirv::compile_with_options(shader, glsl_to_spirv::ShaderType::Fragment, &options)
+        .unwrap();
+}
+
+#[test]
+fn include_outside_temp_dir() {
+    let shader = r#"
+#version 450
+
+void main() {
+}
+"#;
+
+    for name in &["../color.glsl", "/tmp/color.glsl", "a/../../color.glsl", ""] {
+        let options = glsl_to_spirv::CompileOptions {
+            include_sources: vec![(name.to_string(), "\n".to_owned())],
+            ..glsl_to_spirv::CompileOptions::default()
+        };
+
+        assert!(glsl_to_spirv::compile_with_options(shader, glsl_to_spirv::ShaderType::Compute,
+                                                    &options)
+                        .is_err());
+    }
+}
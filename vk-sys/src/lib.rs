@@ -16,6 +16,7 @@ use std::ffi::CStr;
 use std::fmt;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
+use std::os::raw::c_int;
 use std::os::raw::c_ulong;
 use std::os::raw::c_double;
 
@@ -55,7 +56,11 @@ pub type SwapchainKHR = u64;
 pub type DisplayKHR = u64;
 pub type DisplayModeKHR = u64;
 pub type DebugReportCallbackEXT = u64;
+pub type DebugUtilsMessengerEXT = u64;
 pub type DescriptorUpdateTemplateKHR = u64;
+pub type SamplerYcbcrConversion = u64;
+pub type AccelerationStructureKHR = u64;
+pub type DeviceAddress = u64;
 
 pub const LOD_CLAMP_NONE: f32 = 1000.0;
 pub const REMAINING_MIP_LEVELS: u32 = 0xffffffff;
@@ -65,6 +70,7 @@ pub const ATTACHMENT_UNUSED: u32 = 0xffffffff;
 pub const TRUE: u32 = 1;
 pub const FALSE: u32 = 0;
 pub const QUEUE_FAMILY_IGNORED: u32 = 0xffffffff;
+pub const SHADER_UNUSED_KHR: u32 = 0xffffffff;
 pub const SUBPASS_EXTERNAL: u32 = 0xffffffff;
 pub const MAX_PHYSICAL_DEVICE_NAME_SIZE: u32 = 256;
 pub const UUID_SIZE: u32 = 16;
@@ -72,6 +78,7 @@ pub const MAX_MEMORY_TYPES: u32 = 32;
 pub const MAX_MEMORY_HEAPS: u32 = 16;
 pub const MAX_EXTENSION_NAME_SIZE: u32 = 256;
 pub const MAX_DESCRIPTION_SIZE: u32 = 256;
+pub const MAX_DEVICE_GROUP_SIZE: u32 = 32;
 
 pub type PipelineCacheHeaderVersion = u32;
 pub const PIPELINE_CACHE_HEADER_VERSION_ONE: u32 = 1;
@@ -102,6 +109,9 @@ pub const ERROR_INCOMPATIBLE_DISPLAY_KHR: u32 = -1000003001i32 as u32;
 pub const ERROR_VALIDATION_FAILED_EXT: u32 = -1000011001i32 as u32;
 pub const ERROR_INVALID_SHADER_NV: u32 = -1000012000i32 as u32;
 pub const ERROR_OUT_OF_POOL_MEMORY_KHR: u32 = -1000069000i32 as u32;
+pub const ERROR_FRAGMENTATION_EXT: u32 = -1000161000i32 as u32;
+pub const ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT: u32 = -1000255000i32 as u32;
+pub const ERROR_INVALID_EXTERNAL_HANDLE_KHR: u32 = -1000072003i32 as u32;
 
 pub type StructureType = u32;
 pub const STRUCTURE_TYPE_APPLICATION_INFO: u32 = 0;
@@ -153,6 +163,7 @@ pub const STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER: u32 = 45;
 pub const STRUCTURE_TYPE_MEMORY_BARRIER: u32 = 46;
 pub const STRUCTURE_TYPE_LOADER_INSTANCE_CREATE_INFO: u32 = 47;
 pub const STRUCTURE_TYPE_LOADER_DEVICE_CREATE_INFO: u32 = 48;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_2_FEATURES: u32 = 51;
 pub const STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR: u32 = 1000001000;
 pub const STRUCTURE_TYPE_PRESENT_INFO_KHR: u32 = 1000001001;
 pub const STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR: u32 = 1000002000;
@@ -167,8 +178,16 @@ pub const STRUCTURE_TYPE_WIN32_SURFACE_CREATE_INFO_KHR: u32 = 1000009000;
 #[deprecated(note = "Use STRUCTURE_TYPE_DEBUG_REPORT_CALLBACK_CREATE_INFO_EXT instead")]
 pub const STRUCTURE_TYPE_DEBUG_REPORT_CREATE_INFO_EXT: u32 = 1000011000;
 pub const STRUCTURE_TYPE_DEBUG_REPORT_CALLBACK_CREATE_INFO_EXT: u32 = 1000011000;
+pub const STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT: u32 = 1000128000;
+pub const STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_TAG_INFO_EXT: u32 = 1000128001;
+pub const STRUCTURE_TYPE_DEBUG_UTILS_LABEL_EXT: u32 = 1000128002;
+pub const STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CALLBACK_DATA_EXT: u32 = 1000128003;
+pub const STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CREATE_INFO_EXT: u32 = 1000128004;
 pub const STRUCTURE_TYPE_IOS_SURFACE_CREATE_INFO_MVK: u32 = 1000000000 + (52 * 1000);
 pub const STRUCTURE_TYPE_MACOS_SURFACE_CREATE_INFO_MVK: u32 = 1000000000 + (53 * 1000);
+pub const STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO_KHR: u32 = 1000053000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR: u32 = 1000053001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_PROPERTIES_KHR: u32 = 1000053002;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2_KHR: u32 = 1000059000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2_KHR: u32 = 1000059001;
 pub const STRUCTURE_TYPE_FORMAT_PROPERTIES_2_KHR: u32 = 1000059002;
@@ -181,17 +200,87 @@ pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SPARSE_IMAGE_FORMAT_INFO_2_KHR: u32 = 1
 pub const STRUCTURE_TYPE_VI_SURFACE_CREATE_INFO_NN: u32 = 1000062000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PUSH_DESCRIPTOR_PROPERTIES_KHR: u32 = 1000080000;
 pub const STRUCTURE_TYPE_PRESENT_REGIONS_KHR: u32 = 1000084000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CONSERVATIVE_RASTERIZATION_PROPERTIES_EXT: u32 = 1000101000;
+pub const STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_2_KHR: u32 = 1000109000;
+pub const STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2_KHR: u32 = 1000109001;
+pub const STRUCTURE_TYPE_SUBPASS_DESCRIPTION_2_KHR: u32 = 1000109002;
+pub const STRUCTURE_TYPE_SUBPASS_DEPENDENCY_2_KHR: u32 = 1000109003;
+pub const STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO_2_KHR: u32 = 1000109004;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_STENCIL_RESOLVE_PROPERTIES_KHR: u32 = 1000199000;
+pub const STRUCTURE_TYPE_SUBPASS_DESCRIPTION_DEPTH_STENCIL_RESOLVE_KHR: u32 = 1000199001;
+pub const STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT: u32 = 1000255000;
+pub const STRUCTURE_TYPE_SURFACE_CAPABILITIES_FULL_SCREEN_EXCLUSIVE_EXT: u32 = 1000255002;
+pub const STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT: u32 = 1000255001;
+pub const STRUCTURE_TYPE_HDR_METADATA_EXT: u32 = 1000105000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES_KHR: u32 = 1000207000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_PROPERTIES_KHR: u32 = 1000207001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_HOST_QUERY_RESET_FEATURES_EXT: u32 = 1000261000;
+pub const STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO: u32 = 1000060000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES: u32 = 1000070000;
+pub const STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO: u32 = 1000070001;
+pub const STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO_KHR: u32 = 1000207002;
+pub const STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO_KHR: u32 = 1000207003;
+pub const STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO_KHR: u32 = 1000207004;
+pub const STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO_KHR: u32 = 1000207005;
+pub const STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO_KHR: u32 = 1000077000;
+pub const STRUCTURE_TYPE_IMPORT_SEMAPHORE_WIN32_HANDLE_INFO_KHR: u32 = 1000078000;
+pub const STRUCTURE_TYPE_SEMAPHORE_GET_WIN32_HANDLE_INFO_KHR: u32 = 1000078003;
+pub const STRUCTURE_TYPE_IMPORT_SEMAPHORE_FD_INFO_KHR: u32 = 1000079000;
+pub const STRUCTURE_TYPE_SEMAPHORE_GET_FD_INFO_KHR: u32 = 1000079001;
+pub const STRUCTURE_TYPE_EXPORT_FENCE_CREATE_INFO_KHR: u32 = 1000113000;
+pub const STRUCTURE_TYPE_IMPORT_FENCE_WIN32_HANDLE_INFO_KHR: u32 = 1000114000;
+pub const STRUCTURE_TYPE_FENCE_GET_WIN32_HANDLE_INFO_KHR: u32 = 1000114002;
+pub const STRUCTURE_TYPE_IMPORT_FENCE_FD_INFO_KHR: u32 = 1000115000;
+pub const STRUCTURE_TYPE_FENCE_GET_FD_INFO_KHR: u32 = 1000115001;
+pub const STRUCTURE_TYPE_PIPELINE_RASTERIZATION_CONSERVATIVE_STATE_CREATE_INFO_EXT: u32 = 1000101001;
 pub const STRUCTURE_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_CREATE_INFO_KHR: u32 = 1000085000;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR: u32 = 1000127000;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO_KHR: u32 = 1000127001;
 pub const STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146000;
 pub const STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146001;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT: u32 = 1000161000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT: u32 = 1000161001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_PROPERTIES_EXT: u32 = 1000161002;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_ALLOCATE_INFO_EXT: u32 = 1000161003;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_LAYOUT_SUPPORT_EXT: u32 = 1000161004;
 pub const STRUCTURE_TYPE_IMAGE_SPARSE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146002;
 pub const STRUCTURE_TYPE_MEMORY_REQUIREMENTS_2_KHR: u32 = 1000146003;
 pub const STRUCTURE_TYPE_SPARSE_IMAGE_MEMORY_REQUIREMENTS_2_KHR: u32 = 1000146004;
 pub const STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_NAME_INFO_EXT: u32 = 1000022000;
 pub const STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_TAG_INFO_EXT: u32 = 1000022001;
 pub const STRUCTURE_TYPE_DEBUG_MARKER_MARKER_INFO_EXT: u32 = 1000022002;
+pub const STRUCTURE_TYPE_SAMPLER_CUSTOM_BORDER_COLOR_CREATE_INFO_EXT: u32 = 1000287000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_PROPERTIES_EXT: u32 = 1000287001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_FEATURES_EXT: u32 = 1000287002;
+pub const STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_CREATE_INFO: u32 = 1000156000;
+pub const STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_INFO: u32 = 1000156001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLER_YCBCR_CONVERSION_FEATURES: u32 = 1000156004;
+pub const STRUCTURE_TYPE_BUFFER_DEVICE_ADDRESS_INFO: u32 = 1000244001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_BUFFER_DEVICE_ADDRESS_FEATURES: u32 = 1000257000;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_BUILD_GEOMETRY_INFO_KHR: u32 = 1000150000;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_DEVICE_ADDRESS_INFO_KHR: u32 = 1000150002;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_AABBS_DATA_KHR: u32 = 1000150003;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_INSTANCES_DATA_KHR: u32 = 1000150004;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_TRIANGLES_DATA_KHR: u32 = 1000150005;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_KHR: u32 = 1000150006;
+pub const STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET_ACCELERATION_STRUCTURE_KHR: u32 = 1000150007;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_ACCELERATION_STRUCTURE_FEATURES_KHR: u32 = 1000150013;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_ACCELERATION_STRUCTURE_PROPERTIES_KHR: u32 = 1000150014;
+pub const STRUCTURE_TYPE_RAY_TRACING_PIPELINE_CREATE_INFO_KHR: u32 = 1000150015;
+pub const STRUCTURE_TYPE_RAY_TRACING_SHADER_GROUP_CREATE_INFO_KHR: u32 = 1000150016;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_CREATE_INFO_KHR: u32 = 1000150017;
+pub const STRUCTURE_TYPE_RAY_TRACING_PIPELINE_INTERFACE_CREATE_INFO_KHR: u32 = 1000150018;
+pub const STRUCTURE_TYPE_ACCELERATION_STRUCTURE_BUILD_SIZES_INFO_KHR: u32 = 1000150020;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_TRACING_PIPELINE_FEATURES_KHR: u32 = 1000347000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_TRACING_PIPELINE_PROPERTIES_KHR: u32 = 1000347001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_FEATURES_EXT: u32 = 1000328000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_PROPERTIES_EXT: u32 = 1000328001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_QUERY_FEATURES_KHR: u32 = 1000348013;
+pub const STRUCTURE_TYPE_COMMAND_BUFFER_INHERITANCE_CONDITIONAL_RENDERING_INFO_EXT: u32 = 1000081000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_CONDITIONAL_RENDERING_FEATURES_EXT: u32 = 1000081001;
+pub const STRUCTURE_TYPE_CONDITIONAL_RENDERING_BEGIN_INFO_EXT: u32 = 1000081002;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TRANSFORM_FEEDBACK_FEATURES_EXT: u32 = 1000028000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TRANSFORM_FEEDBACK_PROPERTIES_EXT: u32 = 1000028001;
 
 pub type SystemAllocationScope = u32;
 pub const SYSTEM_ALLOCATION_SCOPE_COMMAND: u32 = 0;
@@ -389,6 +478,40 @@ pub const FORMAT_ASTC_12x10_UNORM_BLOCK: u32 = 181;
 pub const FORMAT_ASTC_12x10_SRGB_BLOCK: u32 = 182;
 pub const FORMAT_ASTC_12x12_UNORM_BLOCK: u32 = 183;
 pub const FORMAT_ASTC_12x12_SRGB_BLOCK: u32 = 184;
+pub const FORMAT_G8B8G8R8_422_UNORM: u32 = 1000156000;
+pub const FORMAT_B8G8R8G8_422_UNORM: u32 = 1000156001;
+pub const FORMAT_G8_B8_R8_3PLANE_420_UNORM: u32 = 1000156002;
+pub const FORMAT_G8_B8R8_2PLANE_420_UNORM: u32 = 1000156003;
+pub const FORMAT_G8_B8_R8_3PLANE_422_UNORM: u32 = 1000156004;
+pub const FORMAT_G8_B8R8_2PLANE_422_UNORM: u32 = 1000156005;
+pub const FORMAT_G8_B8_R8_3PLANE_444_UNORM: u32 = 1000156006;
+pub const FORMAT_R10X6_UNORM_PACK16: u32 = 1000156007;
+pub const FORMAT_R10X6G10X6_UNORM_2PACK16: u32 = 1000156008;
+pub const FORMAT_R10X6G10X6B10X6A10X6_UNORM_4PACK16: u32 = 1000156009;
+pub const FORMAT_G10X6B10X6G10X6R10X6_422_UNORM_4PACK16: u32 = 1000156010;
+pub const FORMAT_B10X6G10X6R10X6G10X6_422_UNORM_4PACK16: u32 = 1000156011;
+pub const FORMAT_G10X6_B10X6_R10X6_3PLANE_420_UNORM_3PACK16: u32 = 1000156012;
+pub const FORMAT_G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16: u32 = 1000156013;
+pub const FORMAT_G10X6_B10X6_R10X6_3PLANE_422_UNORM_3PACK16: u32 = 1000156014;
+pub const FORMAT_G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16: u32 = 1000156015;
+pub const FORMAT_G10X6_B10X6_R10X6_3PLANE_444_UNORM_3PACK16: u32 = 1000156016;
+pub const FORMAT_R12X4_UNORM_PACK16: u32 = 1000156017;
+pub const FORMAT_R12X4G12X4_UNORM_2PACK16: u32 = 1000156018;
+pub const FORMAT_R12X4G12X4B12X4A12X4_UNORM_4PACK16: u32 = 1000156019;
+pub const FORMAT_G12X4B12X4G12X4R12X4_422_UNORM_4PACK16: u32 = 1000156020;
+pub const FORMAT_B12X4G12X4R12X4G12X4_422_UNORM_4PACK16: u32 = 1000156021;
+pub const FORMAT_G12X4_B12X4_R12X4_3PLANE_420_UNORM_3PACK16: u32 = 1000156022;
+pub const FORMAT_G12X4_B12X4R12X4_2PLANE_420_UNORM_3PACK16: u32 = 1000156023;
+pub const FORMAT_G12X4_B12X4_R12X4_3PLANE_422_UNORM_3PACK16: u32 = 1000156024;
+pub const FORMAT_G12X4_B12X4R12X4_2PLANE_422_UNORM_3PACK16: u32 = 1000156025;
+pub const FORMAT_G12X4_B12X4_R12X4_3PLANE_444_UNORM_3PACK16: u32 = 1000156026;
+pub const FORMAT_G16B16G16R16_422_UNORM: u32 = 1000156027;
+pub const FORMAT_B16G16R16G16_422_UNORM: u32 = 1000156028;
+pub const FORMAT_G16_B16_R16_3PLANE_420_UNORM: u32 = 1000156029;
+pub const FORMAT_G16_B16R16_2PLANE_420_UNORM: u32 = 1000156030;
+pub const FORMAT_G16_B16_R16_3PLANE_422_UNORM: u32 = 1000156031;
+pub const FORMAT_G16_B16R16_2PLANE_422_UNORM: u32 = 1000156032;
+pub const FORMAT_G16_B16_R16_3PLANE_444_UNORM: u32 = 1000156033;
 
 pub type ImageType = u32;
 pub const IMAGE_TYPE_1D: u32 = 0;
@@ -467,6 +590,11 @@ pub const POLYGON_MODE_FILL: u32 = 0;
 pub const POLYGON_MODE_LINE: u32 = 1;
 pub const POLYGON_MODE_POINT: u32 = 2;
 
+pub type ConservativeRasterizationModeEXT = u32;
+pub const CONSERVATIVE_RASTERIZATION_MODE_DISABLED_EXT: u32 = 0;
+pub const CONSERVATIVE_RASTERIZATION_MODE_OVERESTIMATE_EXT: u32 = 1;
+pub const CONSERVATIVE_RASTERIZATION_MODE_UNDERESTIMATE_EXT: u32 = 2;
+
 pub type FrontFace = u32;
 pub const FRONT_FACE_COUNTER_CLOCKWISE: u32 = 0;
 pub const FRONT_FACE_CLOCKWISE: u32 = 1;
@@ -563,6 +691,67 @@ pub const SAMPLER_ADDRESS_MODE_CLAMP_TO_EDGE: u32 = 2;
 pub const SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER: u32 = 3;
 pub const SAMPLER_ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE: u32 = 4;
 
+pub type SamplerYcbcrModelConversion = u32;
+pub const SAMPLER_YCBCR_MODEL_CONVERSION_RGB_IDENTITY: u32 = 0;
+pub const SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_IDENTITY: u32 = 1;
+pub const SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_709: u32 = 2;
+pub const SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_601: u32 = 3;
+pub const SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_2020: u32 = 4;
+
+pub type SamplerYcbcrRange = u32;
+pub const SAMPLER_YCBCR_RANGE_ITU_FULL: u32 = 0;
+pub const SAMPLER_YCBCR_RANGE_ITU_NARROW: u32 = 1;
+
+pub type AccelerationStructureTypeKHR = u32;
+pub const ACCELERATION_STRUCTURE_TYPE_TOP_LEVEL_KHR: u32 = 0;
+pub const ACCELERATION_STRUCTURE_TYPE_BOTTOM_LEVEL_KHR: u32 = 1;
+pub const ACCELERATION_STRUCTURE_TYPE_GENERIC_KHR: u32 = 2;
+
+pub type AccelerationStructureBuildTypeKHR = u32;
+pub const ACCELERATION_STRUCTURE_BUILD_TYPE_HOST_KHR: u32 = 0;
+pub const ACCELERATION_STRUCTURE_BUILD_TYPE_DEVICE_KHR: u32 = 1;
+pub const ACCELERATION_STRUCTURE_BUILD_TYPE_HOST_OR_DEVICE_KHR: u32 = 2;
+
+pub type BuildAccelerationStructureModeKHR = u32;
+pub const BUILD_ACCELERATION_STRUCTURE_MODE_BUILD_KHR: u32 = 0;
+pub const BUILD_ACCELERATION_STRUCTURE_MODE_UPDATE_KHR: u32 = 1;
+
+pub type BuildAccelerationStructureFlagBitsKHR = u32;
+pub const BUILD_ACCELERATION_STRUCTURE_ALLOW_UPDATE_BIT_KHR: u32 = 0x00000001;
+pub const BUILD_ACCELERATION_STRUCTURE_ALLOW_COMPACTION_BIT_KHR: u32 = 0x00000002;
+pub const BUILD_ACCELERATION_STRUCTURE_PREFER_FAST_TRACE_BIT_KHR: u32 = 0x00000004;
+pub const BUILD_ACCELERATION_STRUCTURE_PREFER_FAST_BUILD_BIT_KHR: u32 = 0x00000008;
+pub const BUILD_ACCELERATION_STRUCTURE_LOW_MEMORY_BIT_KHR: u32 = 0x00000010;
+pub type BuildAccelerationStructureFlagsKHR = Flags;
+
+pub type AccelerationStructureCreateFlagsKHR = Flags;
+
+pub type GeometryTypeKHR = u32;
+pub const GEOMETRY_TYPE_TRIANGLES_KHR: u32 = 0;
+pub const GEOMETRY_TYPE_AABBS_KHR: u32 = 1;
+pub const GEOMETRY_TYPE_INSTANCES_KHR: u32 = 2;
+
+pub type GeometryFlagBitsKHR = u32;
+pub const GEOMETRY_OPAQUE_BIT_KHR: u32 = 0x00000001;
+pub const GEOMETRY_NO_DUPLICATE_ANY_HIT_INVOCATION_BIT_KHR: u32 = 0x00000002;
+pub type GeometryFlagsKHR = Flags;
+
+pub type GeometryInstanceFlagBitsKHR = u32;
+pub const GEOMETRY_INSTANCE_TRIANGLE_FACING_CULL_DISABLE_BIT_KHR: u32 = 0x00000001;
+pub const GEOMETRY_INSTANCE_TRIANGLE_FLIP_FACING_BIT_KHR: u32 = 0x00000002;
+pub const GEOMETRY_INSTANCE_FORCE_OPAQUE_BIT_KHR: u32 = 0x00000004;
+pub const GEOMETRY_INSTANCE_FORCE_NO_OPAQUE_BIT_KHR: u32 = 0x00000008;
+pub type GeometryInstanceFlagsKHR = Flags;
+
+pub type RayTracingShaderGroupTypeKHR = u32;
+pub const RAY_TRACING_SHADER_GROUP_TYPE_GENERAL_KHR: u32 = 0;
+pub const RAY_TRACING_SHADER_GROUP_TYPE_TRIANGLES_HIT_GROUP_KHR: u32 = 1;
+pub const RAY_TRACING_SHADER_GROUP_TYPE_PROCEDURAL_HIT_GROUP_KHR: u32 = 2;
+
+pub type ChromaLocation = u32;
+pub const CHROMA_LOCATION_COSITED_EVEN: u32 = 0;
+pub const CHROMA_LOCATION_MIDPOINT: u32 = 1;
+
 pub type BorderColor = u32;
 pub const BORDER_COLOR_FLOAT_TRANSPARENT_BLACK: u32 = 0;
 pub const BORDER_COLOR_INT_TRANSPARENT_BLACK: u32 = 1;
@@ -570,6 +759,8 @@ pub const BORDER_COLOR_FLOAT_OPAQUE_BLACK: u32 = 2;
 pub const BORDER_COLOR_INT_OPAQUE_BLACK: u32 = 3;
 pub const BORDER_COLOR_FLOAT_OPAQUE_WHITE: u32 = 4;
 pub const BORDER_COLOR_INT_OPAQUE_WHITE: u32 = 5;
+pub const BORDER_COLOR_FLOAT_CUSTOM_EXT: u32 = 1000287003;
+pub const BORDER_COLOR_INT_CUSTOM_EXT: u32 = 1000287004;
 
 pub type DescriptorType = u32;
 pub const DESCRIPTOR_TYPE_SAMPLER: u32 = 0;
@@ -583,6 +774,7 @@ pub const DESCRIPTOR_TYPE_STORAGE_BUFFER: u32 = 7;
 pub const DESCRIPTOR_TYPE_UNIFORM_BUFFER_DYNAMIC: u32 = 8;
 pub const DESCRIPTOR_TYPE_STORAGE_BUFFER_DYNAMIC: u32 = 9;
 pub const DESCRIPTOR_TYPE_INPUT_ATTACHMENT: u32 = 10;
+pub const DESCRIPTOR_TYPE_ACCELERATION_STRUCTURE_KHR: u32 = 1000150000;
 
 pub type AttachmentLoadOp = u32;
 pub const ATTACHMENT_LOAD_OP_LOAD: u32 = 0;
@@ -596,6 +788,7 @@ pub const ATTACHMENT_STORE_OP_DONT_CARE: u32 = 1;
 pub type PipelineBindPoint = u32;
 pub const PIPELINE_BIND_POINT_GRAPHICS: u32 = 0;
 pub const PIPELINE_BIND_POINT_COMPUTE: u32 = 1;
+pub const PIPELINE_BIND_POINT_RAY_TRACING_KHR: u32 = 1000165000;
 
 pub type CommandBufferLevel = u32;
 pub const COMMAND_BUFFER_LEVEL_PRIMARY: u32 = 0;
@@ -604,6 +797,7 @@ pub const COMMAND_BUFFER_LEVEL_SECONDARY: u32 = 1;
 pub type IndexType = u32;
 pub const INDEX_TYPE_UINT16: u32 = 0;
 pub const INDEX_TYPE_UINT32: u32 = 1;
+pub const INDEX_TYPE_NONE_KHR: u32 = 1000165000;
 
 pub type SubpassContents = u32;
 pub const SUBPASS_CONTENTS_INLINE: u32 = 0;
@@ -627,6 +821,9 @@ pub const FORMAT_FEATURE_BLIT_DST_BIT: u32 = 0x00000800;
 pub const FORMAT_FEATURE_SAMPLED_IMAGE_FILTER_LINEAR_BIT: u32 = 0x00001000;
 pub const FORMAT_FEATURE_TRANSFER_SRC_BIT_KHR: u32 = 0x00004000;
 pub const FORMAT_FEATURE_TRANSFER_DST_BIT_KHR: u32 = 0x00008000;
+pub const FORMAT_FEATURE_MIDPOINT_CHROMA_SAMPLES_BIT: u32 = 0x00020000;
+pub const FORMAT_FEATURE_SAMPLED_IMAGE_YCBCR_CONVERSION_LINEAR_FILTER_BIT: u32 = 0x00040000;
+pub const FORMAT_FEATURE_COSITED_CHROMA_SAMPLES_BIT: u32 = 0x00800000;
 pub type FormatFeatureFlags = Flags;
 
 
@@ -652,6 +849,51 @@ pub const IMAGE_CREATE_2D_ARRAY_COMPATIBLE_BIT_KHR: u32 = 0x00000020;
 pub type ImageCreateFlags = Flags;
 
 
+pub type ResolveModeFlagBitsKHR = u32;
+pub const RESOLVE_MODE_NONE_KHR: u32 = 0;
+pub const RESOLVE_MODE_SAMPLE_ZERO_BIT_KHR: u32 = 0x00000001;
+pub const RESOLVE_MODE_AVERAGE_BIT_KHR: u32 = 0x00000002;
+pub const RESOLVE_MODE_MIN_BIT_KHR: u32 = 0x00000004;
+pub const RESOLVE_MODE_MAX_BIT_KHR: u32 = 0x00000008;
+pub type ResolveModeFlagsKHR = Flags;
+
+pub type SemaphoreTypeKHR = u32;
+pub const SEMAPHORE_TYPE_BINARY_KHR: u32 = 0;
+pub const SEMAPHORE_TYPE_TIMELINE_KHR: u32 = 1;
+
+pub type SemaphoreWaitFlagBitsKHR = u32;
+pub const SEMAPHORE_WAIT_ANY_BIT_KHR: u32 = 0x00000001;
+pub type SemaphoreWaitFlagsKHR = Flags;
+
+pub type ExternalSemaphoreHandleTypeFlagBitsKHR = u32;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT_KHR: u32 = 0x00000001;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT_KHR: u32 = 0x00000002;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT_KHR: u32 = 0x00000004;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT_KHR: u32 = 0x00000008;
+pub const EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT_KHR: u32 = 0x00000010;
+pub type ExternalSemaphoreHandleTypeFlagsKHR = Flags;
+
+pub type SemaphoreImportFlagBitsKHR = u32;
+pub const SEMAPHORE_IMPORT_TEMPORARY_BIT_KHR: u32 = 0x00000001;
+pub type SemaphoreImportFlagsKHR = Flags;
+
+pub type ExternalFenceHandleTypeFlagBitsKHR = u32;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_FD_BIT_KHR: u32 = 0x00000001;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_BIT_KHR: u32 = 0x00000002;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT_KHR: u32 = 0x00000004;
+pub const EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT_KHR: u32 = 0x00000008;
+pub type ExternalFenceHandleTypeFlagsKHR = Flags;
+
+pub type FenceImportFlagBitsKHR = u32;
+pub const FENCE_IMPORT_TEMPORARY_BIT_KHR: u32 = 0x00000001;
+pub type FenceImportFlagsKHR = Flags;
+
+pub type FullScreenExclusiveEXT = u32;
+pub const FULL_SCREEN_EXCLUSIVE_DEFAULT_EXT: u32 = 0;
+pub const FULL_SCREEN_EXCLUSIVE_ALLOWED_EXT: u32 = 1;
+pub const FULL_SCREEN_EXCLUSIVE_DISALLOWED_EXT: u32 = 2;
+pub const FULL_SCREEN_EXCLUSIVE_APPLICATION_CONTROLLED_EXT: u32 = 3;
+
 pub type SampleCountFlagBits = u32;
 pub const SAMPLE_COUNT_1_BIT: u32 = 0x00000001;
 pub const SAMPLE_COUNT_2_BIT: u32 = 0x00000002;
@@ -682,6 +924,12 @@ pub type MemoryPropertyFlags = Flags;
 
 pub type MemoryHeapFlagBits = u32;
 pub const MEMORY_HEAP_DEVICE_LOCAL_BIT: u32 = 0x00000001;
+pub const MEMORY_HEAP_MULTI_INSTANCE_BIT: u32 = 0x00000002;
+
+pub type MemoryAllocateFlagBits = u32;
+pub const MEMORY_ALLOCATE_DEVICE_MASK_BIT: u32 = 0x00000001;
+pub const MEMORY_ALLOCATE_DEVICE_ADDRESS_BIT: u32 = 0x00000002;
+pub type MemoryAllocateFlags = Flags;
 pub type MemoryHeapFlags = Flags;
 pub type DeviceCreateFlags = Flags;
 pub type DeviceQueueCreateFlags = Flags;
@@ -705,6 +953,12 @@ pub const PIPELINE_STAGE_BOTTOM_OF_PIPE_BIT: u32 = 0x00002000;
 pub const PIPELINE_STAGE_HOST_BIT: u32 = 0x00004000;
 pub const PIPELINE_STAGE_ALL_GRAPHICS_BIT: u32 = 0x00008000;
 pub const PIPELINE_STAGE_ALL_COMMANDS_BIT: u32 = 0x00010000;
+pub const PIPELINE_STAGE_CONDITIONAL_RENDERING_BIT_EXT: u32 = 0x00040000;
+pub const PIPELINE_STAGE_TASK_SHADER_BIT_EXT: u32 = 0x00080000;
+pub const PIPELINE_STAGE_MESH_SHADER_BIT_EXT: u32 = 0x00100000;
+pub const PIPELINE_STAGE_RAY_TRACING_SHADER_BIT_KHR: u32 = 0x00200000;
+pub const PIPELINE_STAGE_TRANSFORM_FEEDBACK_BIT_EXT: u32 = 0x01000000;
+pub const PIPELINE_STAGE_ACCELERATION_STRUCTURE_BUILD_BIT_KHR: u32 = 0x02000000;
 pub type PipelineStageFlags = Flags;
 pub type MemoryMapFlags = Flags;
 
@@ -777,6 +1031,13 @@ pub const BUFFER_USAGE_STORAGE_BUFFER_BIT: u32 = 0x00000020;
 pub const BUFFER_USAGE_INDEX_BUFFER_BIT: u32 = 0x00000040;
 pub const BUFFER_USAGE_VERTEX_BUFFER_BIT: u32 = 0x00000080;
 pub const BUFFER_USAGE_INDIRECT_BUFFER_BIT: u32 = 0x00000100;
+pub const BUFFER_USAGE_CONDITIONAL_RENDERING_BIT_EXT: u32 = 0x00000200;
+pub const BUFFER_USAGE_SHADER_BINDING_TABLE_BIT_KHR: u32 = 0x00000400;
+pub const BUFFER_USAGE_TRANSFORM_FEEDBACK_BUFFER_BIT_EXT: u32 = 0x00000800;
+pub const BUFFER_USAGE_TRANSFORM_FEEDBACK_COUNTER_BUFFER_BIT_EXT: u32 = 0x00001000;
+pub const BUFFER_USAGE_SHADER_DEVICE_ADDRESS_BIT: u32 = 0x00020000;
+pub const BUFFER_USAGE_ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_BIT_KHR: u32 = 0x00080000;
+pub const BUFFER_USAGE_ACCELERATION_STRUCTURE_STORAGE_BIT_KHR: u32 = 0x00100000;
 pub type BufferUsageFlags = Flags;
 pub type BufferViewCreateFlags = Flags;
 pub type ImageViewCreateFlags = Flags;
@@ -799,6 +1060,14 @@ pub const SHADER_STAGE_TESSELLATION_EVALUATION_BIT: u32 = 0x00000004;
 pub const SHADER_STAGE_GEOMETRY_BIT: u32 = 0x00000008;
 pub const SHADER_STAGE_FRAGMENT_BIT: u32 = 0x00000010;
 pub const SHADER_STAGE_COMPUTE_BIT: u32 = 0x00000020;
+pub const SHADER_STAGE_TASK_BIT_EXT: u32 = 0x00000040;
+pub const SHADER_STAGE_MESH_BIT_EXT: u32 = 0x00000080;
+pub const SHADER_STAGE_RAYGEN_BIT_KHR: u32 = 0x00000100;
+pub const SHADER_STAGE_ANY_HIT_BIT_KHR: u32 = 0x00000200;
+pub const SHADER_STAGE_CLOSEST_HIT_BIT_KHR: u32 = 0x00000400;
+pub const SHADER_STAGE_MISS_BIT_KHR: u32 = 0x00000800;
+pub const SHADER_STAGE_INTERSECTION_BIT_KHR: u32 = 0x00001000;
+pub const SHADER_STAGE_CALLABLE_BIT_KHR: u32 = 0x00002000;
 pub const SHADER_STAGE_ALL_GRAPHICS: u32 = 0x1F;
 pub const SHADER_STAGE_ALL: u32 = 0x7FFFFFFF;
 pub type PipelineVertexInputStateCreateFlags = Flags;
@@ -806,6 +1075,7 @@ pub type PipelineInputAssemblyStateCreateFlags = Flags;
 pub type PipelineTessellationStateCreateFlags = Flags;
 pub type PipelineViewportStateCreateFlags = Flags;
 pub type PipelineRasterizationStateCreateFlags = Flags;
+pub type PipelineRasterizationConservativeStateCreateFlagsEXT = Flags;
 
 
 pub type CullModeFlagBits = u32;
@@ -834,6 +1104,7 @@ pub type DescriptorSetLayoutCreateFlags = Flags;
 
 pub type DescriptorPoolCreateFlagBits = u32;
 pub const DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT: u32 = 0x00000001;
+pub const DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000002;
 pub type DescriptorPoolCreateFlags = Flags;
 pub type DescriptorPoolResetFlags = Flags;
 pub type FramebufferCreateFlags = Flags;
@@ -864,11 +1135,18 @@ pub const ACCESS_HOST_READ_BIT: u32 = 0x00002000;
 pub const ACCESS_HOST_WRITE_BIT: u32 = 0x00004000;
 pub const ACCESS_MEMORY_READ_BIT: u32 = 0x00008000;
 pub const ACCESS_MEMORY_WRITE_BIT: u32 = 0x00010000;
+pub const ACCESS_CONDITIONAL_RENDERING_READ_BIT_EXT: u32 = 0x00100000;
+pub const ACCESS_ACCELERATION_STRUCTURE_READ_BIT_KHR: u32 = 0x00200000;
+pub const ACCESS_ACCELERATION_STRUCTURE_WRITE_BIT_KHR: u32 = 0x00400000;
+pub const ACCESS_TRANSFORM_FEEDBACK_WRITE_BIT_EXT: u32 = 0x02000000;
+pub const ACCESS_TRANSFORM_FEEDBACK_COUNTER_READ_BIT_EXT: u32 = 0x04000000;
+pub const ACCESS_TRANSFORM_FEEDBACK_COUNTER_WRITE_BIT_EXT: u32 = 0x08000000;
 pub type AccessFlags = Flags;
 
 
 pub type DependencyFlagBits = u32;
 pub const DEPENDENCY_BY_REGION_BIT: u32 = 0x00000001;
+pub const DEPENDENCY_VIEW_LOCAL_BIT_KHR: u32 = 0x00000002;
 pub type DependencyFlags = Flags;
 
 
@@ -944,6 +1222,7 @@ pub const COLOR_SPACE_HDR10_HLG_EXT: u32 = 1000104010;
 pub const COLOR_SPACE_ADOBERGB_LINEAR_EXT: u32 = 1000104011;
 pub const COLOR_SPACE_ADOBERGB_NONLINEAR_EXT: u32 = 1000104012;
 pub const COLOR_SPACE_PASS_THROUGH_EXT: u32 = 1000104013;
+pub const COLOR_SPACE_EXTENDED_SRGB_NONLINEAR_EXT: u32 = 1000104014;
 
 pub type PresentModeKHR = u32;
 pub const PRESENT_MODE_IMMEDIATE_KHR: u32 = 0;
@@ -1008,6 +1287,8 @@ pub const DEBUG_REPORT_OBJECT_TYPE_OBJECT_TABLE_NVX_EXT: u32 = 31;
 pub const DEBUG_REPORT_OBJECT_TYPE_INDIRECT_COMMANDS_LAYOUT_NVX_EXT: u32 = 32;
 pub const DEBUG_REPORT_OBJECT_TYPE_VALIDATION_CACHE_EXT: u32 = 33;
 pub const DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR_EXT: u32 = 1000085000;
+pub const DEBUG_REPORT_OBJECT_TYPE_SAMPLER_YCBCR_CONVERSION_EXT: u32 = 1000156000;
+pub const DEBUG_REPORT_OBJECT_TYPE_ACCELERATION_STRUCTURE_KHR_EXT: u32 = 1000150000;
 
 pub type DebugReportErrorEXT = u32;
 pub const DEBUG_REPORT_ERROR_NONE_EXT: u32 = 0;
@@ -1021,12 +1302,36 @@ pub const DEBUG_REPORT_ERROR_BIT_EXT: u32 = 0x00000008;
 pub const DEBUG_REPORT_DEBUG_BIT_EXT: u32 = 0x00000010;
 pub type DebugReportFlagsEXT = Flags;
 
+pub type DebugUtilsMessageSeverityFlagBitsEXT = u32;
+pub const DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT: u32 = 0x00000001;
+pub const DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT: u32 = 0x00000010;
+pub const DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT: u32 = 0x00000100;
+pub const DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT: u32 = 0x00001000;
+pub type DebugUtilsMessageSeverityFlagsEXT = Flags;
+
+pub type DebugUtilsMessageTypeFlagBitsEXT = u32;
+pub const DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT: u32 = 0x00000001;
+pub const DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT: u32 = 0x00000002;
+pub const DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT: u32 = 0x00000004;
+pub type DebugUtilsMessageTypeFlagsEXT = Flags;
+
+pub type DebugUtilsMessengerCreateFlagsEXT = Flags;
+pub type DebugUtilsMessengerCallbackDataFlagsEXT = Flags;
+
 pub type MacOSSurfaceCreateFlagsMVK = u32;
 
 pub type IOSSurfaceCreateFlagsMVK = u32;
 
 pub type DescriptorSetLayoutCreateFlagBits = u32;
 pub const DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR: u32 = 0x00000001;
+pub const DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT: u32 = 0x00000002;
+
+pub type DescriptorBindingFlagBitsEXT = u32;
+pub const DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000001;
+pub const DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT_EXT: u32 = 0x00000002;
+pub const DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT: u32 = 0x00000004;
+pub const DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT_EXT: u32 = 0x00000008;
+pub type DescriptorBindingFlagsEXT = Flags;
 
 pub type DescriptorUpdateTemplateTypeKHR = u32;
 pub const DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR: u32 = 0;
@@ -1042,6 +1347,7 @@ pub type PFN_vkFreeFunction = extern "system" fn(*mut c_void, *mut c_void);
 pub type PFN_vkInternalAllocationNotification = extern "system" fn(*mut c_void, usize, InternalAllocationType, SystemAllocationScope) -> *mut c_void;
 pub type PFN_vkInternalFreeNotification = extern "system" fn(*mut c_void, usize, InternalAllocationType, SystemAllocationScope) -> *mut c_void;
 pub type PFN_vkDebugReportCallbackEXT = extern "system" fn(DebugReportFlagsEXT, DebugReportObjectTypeEXT, u64, usize, i32, *const c_char, *const c_char, *mut c_void) -> Bool32;
+pub type PFN_vkDebugUtilsMessengerCallbackEXT = extern "system" fn(DebugUtilsMessageSeverityFlagBitsEXT, DebugUtilsMessageTypeFlagsEXT, *const DebugUtilsMessengerCallbackDataEXT, *mut c_void) -> Bool32;
 
 pub type PFN_vkVoidFunction = extern "system" fn() -> ();
 
@@ -2438,6 +2744,50 @@ pub struct DebugReportCallbackCreateInfoEXT {
     pub pUserData: *mut c_void,
 }
 
+#[repr(C)]
+pub struct DebugUtilsLabelEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub pLabelName: *const c_char,
+    pub color: [f32; 4],
+}
+
+#[repr(C)]
+pub struct DebugUtilsObjectNameInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub objectType: u32,
+    pub objectHandle: u64,
+    pub pObjectName: *const c_char,
+}
+
+#[repr(C)]
+pub struct DebugUtilsMessengerCallbackDataEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: DebugUtilsMessengerCallbackDataFlagsEXT,
+    pub pMessageIdName: *const c_char,
+    pub messageIdNumber: i32,
+    pub pMessage: *const c_char,
+    pub queueLabelCount: u32,
+    pub pQueueLabels: *const DebugUtilsLabelEXT,
+    pub cmdBufLabelCount: u32,
+    pub pCmdBufLabels: *const DebugUtilsLabelEXT,
+    pub objectCount: u32,
+    pub pObjects: *const DebugUtilsObjectNameInfoEXT,
+}
+
+#[repr(C)]
+pub struct DebugUtilsMessengerCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: DebugUtilsMessengerCreateFlagsEXT,
+    pub messageSeverity: DebugUtilsMessageSeverityFlagsEXT,
+    pub messageType: DebugUtilsMessageTypeFlagsEXT,
+    pub pfnUserCallback: PFN_vkDebugUtilsMessengerCallbackEXT,
+    pub pUserData: *mut c_void,
+}
+
 #[repr(C)]
 pub struct IOSSurfaceCreateInfoMVK {
 	pub sType: StructureType,
@@ -2595,6 +2945,854 @@ pub struct DescriptorUpdateTemplateCreateInfoKHR {
     pub set: u32,
 }
 
+#[repr(C)]
+pub struct AttachmentDescription2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: AttachmentDescriptionFlags,
+    pub format: Format,
+    pub samples: SampleCountFlagBits,
+    pub loadOp: AttachmentLoadOp,
+    pub storeOp: AttachmentStoreOp,
+    pub stencilLoadOp: AttachmentLoadOp,
+    pub stencilStoreOp: AttachmentStoreOp,
+    pub initialLayout: ImageLayout,
+    pub finalLayout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct AttachmentReference2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachment: u32,
+    pub layout: ImageLayout,
+    pub aspectMask: ImageAspectFlags,
+}
+
+#[repr(C)]
+pub struct SubpassDescription2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SubpassDescriptionFlags,
+    pub pipelineBindPoint: PipelineBindPoint,
+    pub viewMask: u32,
+    pub inputAttachmentCount: u32,
+    pub pInputAttachments: *const AttachmentReference2KHR,
+    pub colorAttachmentCount: u32,
+    pub pColorAttachments: *const AttachmentReference2KHR,
+    pub pResolveAttachments: *const AttachmentReference2KHR,
+    pub pDepthStencilAttachment: *const AttachmentReference2KHR,
+    pub preserveAttachmentCount: u32,
+    pub pPreserveAttachments: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDependency2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcSubpass: u32,
+    pub dstSubpass: u32,
+    pub srcStageMask: PipelineStageFlags,
+    pub dstStageMask: PipelineStageFlags,
+    pub srcAccessMask: AccessFlags,
+    pub dstAccessMask: AccessFlags,
+    pub dependencyFlags: DependencyFlags,
+    pub viewOffset: i32,
+}
+
+#[repr(C)]
+pub struct RenderPassCreateInfo2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: RenderPassCreateFlags,
+    pub attachmentCount: u32,
+    pub pAttachments: *const AttachmentDescription2KHR,
+    pub subpassCount: u32,
+    pub pSubpasses: *const SubpassDescription2KHR,
+    pub dependencyCount: u32,
+    pub pDependencies: *const SubpassDependency2KHR,
+    pub correlatedViewMaskCount: u32,
+    pub pCorrelatedViewMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDescriptionDepthStencilResolveKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub depthResolveMode: ResolveModeFlagBitsKHR,
+    pub stencilResolveMode: ResolveModeFlagBitsKHR,
+    pub pDepthStencilResolveAttachment: *const AttachmentReference2KHR,
+}
+
+#[repr(C)]
+pub struct SurfaceFullScreenExclusiveInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub fullScreenExclusive: FullScreenExclusiveEXT,
+}
+
+#[repr(C)]
+pub struct SurfaceCapabilitiesFullScreenExclusiveEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub fullScreenExclusiveSupported: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceGroupProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub physicalDeviceCount: u32,
+    pub physicalDevices: [PhysicalDevice; MAX_DEVICE_GROUP_SIZE as usize],
+    pub subsetAllocation: Bool32,
+}
+
+#[repr(C)]
+pub struct DeviceGroupDeviceCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub physicalDeviceCount: u32,
+    pub pPhysicalDevices: *const PhysicalDevice,
+}
+
+#[repr(C)]
+pub struct MemoryAllocateFlagsInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: MemoryAllocateFlags,
+    pub deviceMask: u32,
+}
+
+#[repr(C)]
+pub struct SurfaceFullScreenExclusiveWin32InfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub hmonitor: *mut c_void,
+}
+
+#[repr(C)]
+pub struct XYColorEXT {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[repr(C)]
+pub struct HdrMetadataEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub displayPrimaryRed: XYColorEXT,
+    pub displayPrimaryGreen: XYColorEXT,
+    pub displayPrimaryBlue: XYColorEXT,
+    pub whitePoint: XYColorEXT,
+    pub maxLuminance: f32,
+    pub minLuminance: f32,
+    pub maxContentLightLevel: f32,
+    pub maxFrameAverageLightLevel: f32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub timelineSemaphore: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceHostQueryResetFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub hostQueryReset: Bool32,
+}
+
+#[repr(C)]
+pub struct SamplerYcbcrConversionCreateInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub format: Format,
+    pub ycbcrModel: SamplerYcbcrModelConversion,
+    pub ycbcrRange: SamplerYcbcrRange,
+    pub components: ComponentMapping,
+    pub xChromaOffset: ChromaLocation,
+    pub yChromaOffset: ChromaLocation,
+    pub chromaFilter: Filter,
+    pub forceExplicitReconstruction: Bool32,
+}
+
+#[repr(C)]
+pub struct SamplerYcbcrConversionInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub conversion: SamplerYcbcrConversion,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceSamplerYcbcrConversionFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub samplerYcbcrConversion: Bool32,
+}
+
+#[repr(C)]
+pub struct BufferDeviceAddressInfo {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub buffer: Buffer,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceVulkan12Features {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub samplerMirrorClampToEdge: Bool32,
+    pub drawIndirectCount: Bool32,
+    pub storageBuffer8BitAccess: Bool32,
+    pub uniformAndStorageBuffer8BitAccess: Bool32,
+    pub storagePushConstant8: Bool32,
+    pub shaderBufferInt64Atomics: Bool32,
+    pub shaderSharedInt64Atomics: Bool32,
+    pub shaderFloat16: Bool32,
+    pub shaderInt8: Bool32,
+    pub descriptorIndexing: Bool32,
+    pub shaderInputAttachmentArrayDynamicIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderUniformBufferArrayNonUniformIndexing: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexing: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexing: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayNonUniformIndexing: Bool32,
+    pub descriptorBindingUniformBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingSampledImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUniformTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUpdateUnusedWhilePending: Bool32,
+    pub descriptorBindingPartiallyBound: Bool32,
+    pub descriptorBindingVariableDescriptorCount: Bool32,
+    pub runtimeDescriptorArray: Bool32,
+    pub samplerFilterMinmax: Bool32,
+    pub scalarBlockLayout: Bool32,
+    pub imagelessFramebuffer: Bool32,
+    pub uniformBufferStandardLayout: Bool32,
+    pub shaderSubgroupExtendedTypes: Bool32,
+    pub separateDepthStencilLayouts: Bool32,
+    pub hostQueryReset: Bool32,
+    pub timelineSemaphore: Bool32,
+    pub bufferDeviceAddress: Bool32,
+    pub bufferDeviceAddressCaptureReplay: Bool32,
+    pub bufferDeviceAddressMultiDevice: Bool32,
+    pub vulkanMemoryModel: Bool32,
+    pub vulkanMemoryModelDeviceScope: Bool32,
+    pub vulkanMemoryModelAvailabilityVisibilityChains: Bool32,
+    pub shaderOutputViewportIndex: Bool32,
+    pub shaderOutputLayer: Bool32,
+    pub subgroupBroadcastDynamicId: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceBufferDeviceAddressFeatures {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub bufferDeviceAddress: Bool32,
+    pub bufferDeviceAddressCaptureReplay: Bool32,
+    pub bufferDeviceAddressMultiDevice: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceAccelerationStructureFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub accelerationStructure: Bool32,
+    pub accelerationStructureCaptureReplay: Bool32,
+    pub accelerationStructureIndirectBuild: Bool32,
+    pub accelerationStructureHostCommands: Bool32,
+    pub descriptorBindingAccelerationStructureUpdateAfterBind: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceAccelerationStructurePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxGeometryCount: u64,
+    pub maxInstanceCount: u64,
+    pub maxPrimitiveCount: u64,
+    pub maxPerStageDescriptorAccelerationStructures: u32,
+    pub maxPerStageDescriptorUpdateAfterBindAccelerationStructures: u32,
+    pub maxDescriptorSetAccelerationStructures: u32,
+    pub maxDescriptorSetUpdateAfterBindAccelerationStructures: u32,
+    pub minAccelerationStructureScratchOffsetAlignment: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMeshShaderFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub taskShader: Bool32,
+    pub meshShader: Bool32,
+    pub multiviewMeshShader: Bool32,
+    pub primitiveFragmentShadingRateMeshShader: Bool32,
+    pub meshShaderQueries: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMeshShaderPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxTaskWorkGroupTotalCount: u32,
+    pub maxTaskWorkGroupCount: [u32; 3],
+    pub maxTaskWorkGroupInvocations: u32,
+    pub maxTaskWorkGroupSize: [u32; 3],
+    pub maxTaskPayloadSize: u32,
+    pub maxTaskSharedMemorySize: u32,
+    pub maxTaskPayloadAndSharedMemorySize: u32,
+    pub maxMeshWorkGroupTotalCount: u32,
+    pub maxMeshWorkGroupCount: [u32; 3],
+    pub maxMeshWorkGroupInvocations: u32,
+    pub maxMeshWorkGroupSize: [u32; 3],
+    pub maxMeshSharedMemorySize: u32,
+    pub maxMeshPayloadAndSharedMemorySize: u32,
+    pub maxMeshOutputMemorySize: u32,
+    pub maxMeshPayloadAndOutputMemorySize: u32,
+    pub maxMeshOutputComponents: u32,
+    pub maxMeshOutputVertices: u32,
+    pub maxMeshOutputPrimitives: u32,
+    pub maxMeshOutputLayers: u32,
+    pub maxMeshMultiviewViewCount: u32,
+    pub meshOutputPerVertexGranularity: u32,
+    pub meshOutputPerPrimitiveGranularity: u32,
+    pub maxPreferredTaskWorkGroupInvocations: u32,
+    pub maxPreferredMeshWorkGroupInvocations: u32,
+    pub prefersLocalInvocationVertexOutput: Bool32,
+    pub prefersLocalInvocationPrimitiveOutput: Bool32,
+    pub prefersCompactVertexOutput: Bool32,
+    pub prefersCompactPrimitiveOutput: Bool32,
+}
+
+pub type ConditionalRenderingFlagsEXT = Flags;
+pub const CONDITIONAL_RENDERING_INVERTED_BIT_EXT: u32 = 0x00000001;
+
+#[repr(C)]
+pub struct PhysicalDeviceConditionalRenderingFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub conditionalRendering: Bool32,
+    pub inheritedConditionalRendering: Bool32,
+}
+
+#[repr(C)]
+pub struct ConditionalRenderingBeginInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub buffer: Buffer,
+    pub offset: DeviceSize,
+    pub flags: ConditionalRenderingFlagsEXT,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTransformFeedbackFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub transformFeedback: Bool32,
+    pub geometryStreams: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTransformFeedbackPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxTransformFeedbackStreams: u32,
+    pub maxTransformFeedbackBuffers: u32,
+    pub maxTransformFeedbackBufferSize: DeviceSize,
+    pub maxTransformFeedbackStreamDataSize: u32,
+    pub maxTransformFeedbackBufferDataSize: u32,
+    pub maxTransformFeedbackBufferDataStride: u32,
+    pub transformFeedbackQueries: Bool32,
+    pub transformFeedbackStreamsLinesTriangles: Bool32,
+    pub transformFeedbackRasterizationStreamSelect: Bool32,
+    pub transformFeedbackDraw: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceRayQueryFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub rayQuery: Bool32,
+}
+
+#[repr(C)]
+pub struct WriteDescriptorSetAccelerationStructureKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub accelerationStructureCount: u32,
+    pub pAccelerationStructures: *const AccelerationStructureKHR,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceRayTracingPipelineFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub rayTracingPipeline: Bool32,
+    pub rayTracingPipelineShaderGroupHandleCaptureReplay: Bool32,
+    pub rayTracingPipelineShaderGroupHandleCaptureReplayMixed: Bool32,
+    pub rayTracingPipelineTraceRaysIndirect: Bool32,
+    pub rayTraversalPrimitiveCulling: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceRayTracingPipelinePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub shaderGroupHandleSize: u32,
+    pub maxRayRecursionDepth: u32,
+    pub maxShaderGroupStride: u32,
+    pub shaderGroupBaseAlignment: u32,
+    pub shaderGroupHandleCaptureReplaySize: u32,
+    pub maxRayDispatchInvocationCount: u32,
+    pub shaderGroupHandleAlignment: u32,
+    pub maxRayHitAttributeSize: u32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union DeviceOrHostAddressKHR {
+    pub deviceAddress: DeviceAddress,
+    pub hostAddress: *mut c_void,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union DeviceOrHostAddressConstKHR {
+    pub deviceAddress: DeviceAddress,
+    pub hostAddress: *const c_void,
+}
+
+#[repr(C)]
+pub struct AccelerationStructureCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub createFlags: AccelerationStructureCreateFlagsKHR,
+    pub buffer: Buffer,
+    pub offset: DeviceSize,
+    pub size: DeviceSize,
+    pub ty: AccelerationStructureTypeKHR,
+    pub deviceAddress: DeviceAddress,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AccelerationStructureGeometryTrianglesDataKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub vertexFormat: Format,
+    pub vertexData: DeviceOrHostAddressConstKHR,
+    pub vertexStride: DeviceSize,
+    pub maxVertex: u32,
+    pub indexType: IndexType,
+    pub indexData: DeviceOrHostAddressConstKHR,
+    pub transformData: DeviceOrHostAddressConstKHR,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AccelerationStructureGeometryAabbsDataKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub data: DeviceOrHostAddressConstKHR,
+    pub stride: DeviceSize,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AccelerationStructureGeometryInstancesDataKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub arrayOfPointers: Bool32,
+    pub data: DeviceOrHostAddressConstKHR,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union AccelerationStructureGeometryDataKHR {
+    pub triangles: AccelerationStructureGeometryTrianglesDataKHR,
+    pub aabbs: AccelerationStructureGeometryAabbsDataKHR,
+    pub instances: AccelerationStructureGeometryInstancesDataKHR,
+}
+
+#[repr(C)]
+pub struct AccelerationStructureGeometryKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub geometryType: GeometryTypeKHR,
+    pub geometry: AccelerationStructureGeometryDataKHR,
+    pub flags: GeometryFlagsKHR,
+}
+
+#[repr(C)]
+pub struct AccelerationStructureBuildGeometryInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub ty: AccelerationStructureTypeKHR,
+    pub flags: BuildAccelerationStructureFlagsKHR,
+    pub mode: BuildAccelerationStructureModeKHR,
+    pub srcAccelerationStructure: AccelerationStructureKHR,
+    pub dstAccelerationStructure: AccelerationStructureKHR,
+    pub geometryCount: u32,
+    pub pGeometries: *const AccelerationStructureGeometryKHR,
+    pub ppGeometries: *const *const AccelerationStructureGeometryKHR,
+    pub scratchData: DeviceOrHostAddressKHR,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AccelerationStructureBuildRangeInfoKHR {
+    pub primitiveCount: u32,
+    pub primitiveOffset: u32,
+    pub firstVertex: u32,
+    pub transformOffset: u32,
+}
+
+#[repr(C)]
+pub struct AccelerationStructureBuildSizesInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub accelerationStructureSize: DeviceSize,
+    pub updateScratchSize: DeviceSize,
+    pub buildScratchSize: DeviceSize,
+}
+
+#[repr(C)]
+pub struct AccelerationStructureDeviceAddressInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub accelerationStructure: AccelerationStructureKHR,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct TransformMatrixKHR {
+    pub matrix: [[f32; 4]; 3],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AabbPositionsKHR {
+    pub minX: f32,
+    pub minY: f32,
+    pub minZ: f32,
+    pub maxX: f32,
+    pub maxY: f32,
+    pub maxZ: f32,
+}
+
+// The `instanceCustomIndex` and `mask` fields, and the `instanceShaderBindingTableRecordOffset`
+// and `flags` fields, are bitfields packed into a single `u32` each.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct AccelerationStructureInstanceKHR {
+    pub transform: TransformMatrixKHR,
+    pub instanceCustomIndexAndMask: u32,
+    pub instanceShaderBindingTableRecordOffsetAndFlags: u32,
+    pub accelerationStructureReference: u64,
+}
+
+#[repr(C)]
+pub struct RayTracingShaderGroupCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub ty: RayTracingShaderGroupTypeKHR,
+    pub generalShader: u32,
+    pub closestHitShader: u32,
+    pub anyHitShader: u32,
+    pub intersectionShader: u32,
+    pub pShaderGroupCaptureReplayHandle: *const c_void,
+}
+
+#[repr(C)]
+pub struct RayTracingPipelineInterfaceCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub maxPipelineRayPayloadSize: u32,
+    pub maxPipelineRayHitAttributeSize: u32,
+}
+
+#[repr(C)]
+pub struct RayTracingPipelineCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: PipelineCreateFlags,
+    pub stageCount: u32,
+    pub pStages: *const PipelineShaderStageCreateInfo,
+    pub groupCount: u32,
+    pub pGroups: *const RayTracingShaderGroupCreateInfoKHR,
+    pub maxPipelineRayRecursionDepth: u32,
+    pub pLibraryInfo: *const c_void,
+    pub pLibraryInterface: *const RayTracingPipelineInterfaceCreateInfoKHR,
+    pub pDynamicState: *const PipelineDynamicStateCreateInfo,
+    pub layout: PipelineLayout,
+    pub basePipelineHandle: Pipeline,
+    pub basePipelineIndex: i32,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct StridedDeviceAddressRegionKHR {
+    pub deviceAddress: DeviceAddress,
+    pub stride: DeviceSize,
+    pub size: DeviceSize,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDescriptorIndexingFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub shaderInputAttachmentArrayDynamicIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderUniformBufferArrayNonUniformIndexing: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexing: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexing: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayNonUniformIndexing: Bool32,
+    pub descriptorBindingUniformBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingSampledImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUniformTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUpdateUnusedWhilePending: Bool32,
+    pub descriptorBindingPartiallyBound: Bool32,
+    pub descriptorBindingVariableDescriptorCount: Bool32,
+    pub runtimeDescriptorArray: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceCustomBorderColorFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub customBorderColors: Bool32,
+    pub customBorderColorWithoutFormat: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceCustomBorderColorPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxCustomBorderColorSamplers: u32,
+}
+
+#[repr(C)]
+pub struct SamplerCustomBorderColorCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub customBorderColor: ClearColorValue,
+    pub format: Format,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphorePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxTimelineSemaphoreValueDifference: u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreTypeCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphoreType: SemaphoreTypeKHR,
+    pub initialValue: u64,
+}
+
+#[repr(C)]
+pub struct TimelineSemaphoreSubmitInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub waitSemaphoreValueCount: u32,
+    pub pWaitSemaphoreValues: *const u64,
+    pub signalSemaphoreValueCount: u32,
+    pub pSignalSemaphoreValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreWaitInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SemaphoreWaitFlagsKHR,
+    pub semaphoreCount: u32,
+    pub pSemaphores: *const Semaphore,
+    pub pValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreSignalInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub value: u64,
+}
+
+#[repr(C)]
+pub struct ExportSemaphoreCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleTypes: ExternalSemaphoreHandleTypeFlagsKHR,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlagsKHR,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBitsKHR,
+    pub fd: c_int,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBitsKHR,
+}
+
+#[repr(C)]
+pub struct ImportSemaphoreWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub flags: SemaphoreImportFlagsKHR,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBitsKHR,
+    pub handle: *mut c_void,
+    pub name: *const u16,
+}
+
+#[repr(C)]
+pub struct SemaphoreGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub handleType: ExternalSemaphoreHandleTypeFlagBitsKHR,
+}
+
+#[repr(C)]
+pub struct ExportFenceCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub handleTypes: ExternalFenceHandleTypeFlagsKHR,
+}
+
+#[repr(C)]
+pub struct ImportFenceFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub flags: FenceImportFlagsKHR,
+    pub handleType: ExternalFenceHandleTypeFlagBitsKHR,
+    pub fd: c_int,
+}
+
+#[repr(C)]
+pub struct FenceGetFdInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub handleType: ExternalFenceHandleTypeFlagBitsKHR,
+}
+
+#[repr(C)]
+pub struct ImportFenceWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub flags: FenceImportFlagsKHR,
+    pub handleType: ExternalFenceHandleTypeFlagBitsKHR,
+    pub handle: *mut c_void,
+    pub name: *const u16,
+}
+
+#[repr(C)]
+pub struct FenceGetWin32HandleInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub fence: Fence,
+    pub handleType: ExternalFenceHandleTypeFlagBitsKHR,
+}
+
+#[repr(C)]
+pub struct RenderPassMultiviewCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub subpassCount: u32,
+    pub pViewMasks: *const u32,
+    pub dependencyCount: u32,
+    pub pViewOffsets: *const i32,
+    pub correlationMaskCount: u32,
+    pub pCorrelationMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub multiview: Bool32,
+    pub multiviewGeometryShader: Bool32,
+    pub multiviewTessellationShader: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxMultiviewViewCount: u32,
+    pub maxMultiviewInstanceIndex: u32,
+}
+
+#[repr(C)]
+pub struct DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub bindingCount: u32,
+    pub pBindingFlags: *const DescriptorBindingFlagsEXT,
+}
+
+#[repr(C)]
+pub struct DescriptorSetVariableDescriptorCountAllocateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub descriptorSetCount: u32,
+    pub pDescriptorCounts: *const u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceConservativeRasterizationPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub primitiveOverestimationSize: f32,
+    pub maxExtraPrimitiveOverestimationSize: f32,
+    pub extraPrimitiveOverestimationSizeGranularity: f32,
+    pub primitiveUnderestimation: Bool32,
+    pub conservativePointAndLineRasterization: Bool32,
+    pub degenerateTrianglesRasterized: Bool32,
+    pub degenerateLinesRasterized: Bool32,
+    pub fullyCoveredFragmentShaderInputVariable: Bool32,
+    pub conservativeRasterizationPostDepthCoverage: Bool32,
+}
+
+#[repr(C)]
+pub struct PipelineRasterizationConservativeStateCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: PipelineRasterizationConservativeStateCreateFlagsEXT,
+    pub conservativeRasterizationMode: ConservativeRasterizationModeEXT,
+    pub extraPrimitiveOverestimationSize: f32,
+}
+
 #[repr(C)]
 pub struct MemoryDedicatedRequirementsKHR {
     pub sType: StructureType,
@@ -2740,6 +3938,8 @@ ptrs!(InstancePointers, {
     DestroyInstance => (instance: Instance, pAllocator: *const AllocationCallbacks) -> (),
     GetDeviceProcAddr => (device: Device, pName: *const c_char) -> PFN_vkVoidFunction,
     EnumeratePhysicalDevices => (instance: Instance, pPhysicalDeviceCount: *mut u32, pPhysicalDevices: *mut PhysicalDevice) -> Result,
+    EnumeratePhysicalDeviceGroups => (instance: Instance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut PhysicalDeviceGroupProperties) -> Result,
+    EnumeratePhysicalDeviceGroupsKHR => (instance: Instance, pPhysicalDeviceGroupCount: *mut u32, pPhysicalDeviceGroupProperties: *mut PhysicalDeviceGroupProperties) -> Result,
     EnumerateDeviceExtensionProperties => (physicalDevice: PhysicalDevice, pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut ExtensionProperties) -> Result,
     EnumerateDeviceLayerProperties => (physicalDevice: PhysicalDevice, pPropertyCount: *mut u32, pProperties: *mut LayerProperties) -> Result,
     CreateDevice => (physicalDevice: PhysicalDevice, pCreateInfo: *const DeviceCreateInfo, pAllocator: *const AllocationCallbacks, pDevice: *mut Device) -> Result,
@@ -2776,6 +3976,8 @@ ptrs!(InstancePointers, {
     CreateDebugReportCallbackEXT => (instance: Instance, pCreateInfo: *const DebugReportCallbackCreateInfoEXT, pAllocator: *const AllocationCallbacks, pCallback: *mut DebugReportCallbackEXT) -> Result,
     DestroyDebugReportCallbackEXT => (instance: Instance, callback: DebugReportCallbackEXT, pAllocator: *const AllocationCallbacks) -> (),
     DebugReportMessageEXT => (instance: Instance, flags: DebugReportFlagsEXT, objectType: DebugReportObjectTypeEXT, object: u64, location: usize, messageCode: i32, pLayerPrefix: *const c_char, pMessage: *const c_char) -> (),
+    CreateDebugUtilsMessengerEXT => (instance: Instance, pCreateInfo: *const DebugUtilsMessengerCreateInfoEXT, pAllocator: *const AllocationCallbacks, pMessenger: *mut DebugUtilsMessengerEXT) -> Result,
+    DestroyDebugUtilsMessengerEXT => (instance: Instance, messenger: DebugUtilsMessengerEXT, pAllocator: *const AllocationCallbacks) -> (),
     CreateIOSSurfaceMVK => (instance: Instance, pCreateInfo: *const IOSSurfaceCreateInfoMVK, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     CreateMacOSSurfaceMVK => (instance: Instance, pCreateInfo: *const MacOSSurfaceCreateInfoMVK, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     ActivateMoltenVKLicenseMVK => (licenseID: *const c_char, licenseKey: *const c_char, acceptLicenseTermsAndConditions: Bool32) -> Result,
@@ -2786,7 +3988,9 @@ ptrs!(InstancePointers, {
     GetSwapchainPerformanceMVK => (device: Device, swapchain: SwapchainKHR, pSwapchainPerf: *mut MVKSwapchainPerformance) -> Result,
     CreateViSurfaceNN => (instance: Instance, pCreateInfo: *const ViSurfaceCreateInfoNN, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     GetPhysicalDeviceFeatures2KHR => (physicalDevice: PhysicalDevice, pFeatures: *mut PhysicalDeviceFeatures2KHR) -> (),
+    GetPhysicalDeviceFeatures2 => (physicalDevice: PhysicalDevice, pFeatures: *mut PhysicalDeviceFeatures2KHR) -> (),
     GetPhysicalDeviceProperties2KHR => (physicalDevice: PhysicalDevice, pProperties: *mut PhysicalDeviceProperties2KHR) -> (),
+    GetPhysicalDeviceProperties2 => (physicalDevice: PhysicalDevice, pProperties: *mut PhysicalDeviceProperties2KHR) -> (),
     GetPhysicalDeviceFormatProperties2KHR => (physicalDevice: PhysicalDevice, pFormatProperties: *mut FormatProperties2KHR) -> (),
     GetPhysicalDeviceImageFormatProperties2KHR => (physicalDevice: PhysicalDevice, pImageFormatInfo: *const PhysicalDeviceImageFormatInfo2KHR, pImageFormatProperties: *mut ImageFormatProperties2KHR) -> Result,
     GetPhysicalDeviceQueueFamilyProperties2KHR => (physicalDevice: PhysicalDevice, pQueueFamilyPropertiesCount: *mut u32, pQueueFamilyProperties: *mut QueueFamilyProperties2KHR) -> (),
@@ -2850,6 +4054,10 @@ ptrs!(DevicePointers, {
     DestroyPipelineLayout => (device: Device, pipelineLayout: PipelineLayout, pAllocator: *const AllocationCallbacks) -> (),
     CreateSampler => (device: Device, pCreateInfo: *const SamplerCreateInfo, pAllocator: *const AllocationCallbacks, pSampler: *mut Sampler) -> Result,
     DestroySampler => (device: Device, sampler: Sampler, pAllocator: *const AllocationCallbacks) -> (),
+    CreateSamplerYcbcrConversion => (device: Device, pCreateInfo: *const SamplerYcbcrConversionCreateInfo, pAllocator: *const AllocationCallbacks, pYcbcrConversion: *mut SamplerYcbcrConversion) -> Result,
+    CreateSamplerYcbcrConversionKHR => (device: Device, pCreateInfo: *const SamplerYcbcrConversionCreateInfo, pAllocator: *const AllocationCallbacks, pYcbcrConversion: *mut SamplerYcbcrConversion) -> Result,
+    DestroySamplerYcbcrConversion => (device: Device, ycbcrConversion: SamplerYcbcrConversion, pAllocator: *const AllocationCallbacks) -> (),
+    DestroySamplerYcbcrConversionKHR => (device: Device, ycbcrConversion: SamplerYcbcrConversion, pAllocator: *const AllocationCallbacks) -> (),
     CreateDescriptorSetLayout => (device: Device, pCreateInfo: *const DescriptorSetLayoutCreateInfo, pAllocator: *const AllocationCallbacks, pSetLayout: *mut DescriptorSetLayout) -> Result,
     DestroyDescriptorSetLayout => (device: Device, descriptorSetLayout: DescriptorSetLayout, pAllocator: *const AllocationCallbacks) -> (),
     CreateDescriptorPool => (device: Device, pCreateInfo: *const DescriptorPoolCreateInfo, pAllocator: *const AllocationCallbacks, pDescriptorPool: *mut DescriptorPool) -> Result,
@@ -2876,6 +4084,8 @@ ptrs!(DevicePointers, {
     CmdSetViewport => (commandBuffer: CommandBuffer, firstViewport: u32, viewportCount: u32, pViewports: *const Viewport) -> (),
     CmdSetScissor => (commandBuffer: CommandBuffer, firstScissor: u32, scissorCount: u32, pScissors: *const Rect2D) -> (),
     CmdSetLineWidth => (commandBuffer: CommandBuffer, lineWidth: f32) -> (),
+    CmdSetDeviceMask => (commandBuffer: CommandBuffer, deviceMask: u32) -> (),
+    CmdSetDeviceMaskKHR => (commandBuffer: CommandBuffer, deviceMask: u32) -> (),
     CmdSetDepthBias => (commandBuffer: CommandBuffer, depthBiasConstantFactor: f32, depthBiasClamp: f32, depthBiasSlopeFactor: f32) -> (),
     CmdSetBlendConstants => (commandBuffer: CommandBuffer, blendConstants: [f32; 4]) -> (),
     CmdSetDepthBounds => (commandBuffer: CommandBuffer, minDepthBounds: f32, maxDepthBounds: f32) -> (),
@@ -2889,6 +4099,10 @@ ptrs!(DevicePointers, {
     CmdDrawIndexed => (commandBuffer: CommandBuffer, indexCount: u32, instanceCount: u32, firstIndex: u32, vertexOffset: i32, firstInstance: u32) -> (),
     CmdDrawIndirect => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, drawCount: u32, stride: u32) -> (),
     CmdDrawIndexedIndirect => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, drawCount: u32, stride: u32) -> (),
+    CmdDrawIndirectCount => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, countBuffer: Buffer, countBufferOffset: DeviceSize, maxDrawCount: u32, stride: u32) -> (),
+    CmdDrawIndexedIndirectCount => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, countBuffer: Buffer, countBufferOffset: DeviceSize, maxDrawCount: u32, stride: u32) -> (),
+    CmdDrawIndirectCountKHR => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, countBuffer: Buffer, countBufferOffset: DeviceSize, maxDrawCount: u32, stride: u32) -> (),
+    CmdDrawIndexedIndirectCountKHR => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize, countBuffer: Buffer, countBufferOffset: DeviceSize, maxDrawCount: u32, stride: u32) -> (),
     CmdDispatch => (commandBuffer: CommandBuffer, x: u32, y: u32, z: u32) -> (),
     CmdDispatchIndirect => (commandBuffer: CommandBuffer, buffer: Buffer, offset: DeviceSize) -> (),
     CmdCopyBuffer => (commandBuffer: CommandBuffer, srcBuffer: Buffer, dstBuffer: Buffer, regionCount: u32, pRegions: *const BufferCopy) -> (),
@@ -2922,6 +4136,24 @@ ptrs!(DevicePointers, {
     AcquireNextImageKHR => (device: Device, swapchain: SwapchainKHR, timeout: u64, semaphore: Semaphore, fence: Fence, pImageIndex: *mut u32) -> Result,
     QueuePresentKHR => (queue: Queue, pPresentInfo: *const PresentInfoKHR) -> Result,
     CreateSharedSwapchainsKHR => (device: Device, swapchainCount: u32, pCreateInfos: *const SwapchainCreateInfoKHR, pAllocator: *const AllocationCallbacks, pSwapchains: *mut SwapchainKHR) -> Result,
+    AcquireFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
+    ReleaseFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
+    SetHdrMetadataEXT => (device: Device, swapchainCount: u32, pSwapchains: *const SwapchainKHR, pMetadata: *const HdrMetadataEXT) -> (),
+    GetSemaphoreCounterValueKHR => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphoresKHR => (device: Device, pWaitInfo: *const SemaphoreWaitInfoKHR, timeout: u64) -> Result,
+    SignalSemaphoreKHR => (device: Device, pSignalInfo: *const SemaphoreSignalInfoKHR) -> Result,
+    GetSemaphoreCounterValue => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphores => (device: Device, pWaitInfo: *const SemaphoreWaitInfoKHR, timeout: u64) -> Result,
+    SignalSemaphore => (device: Device, pSignalInfo: *const SemaphoreSignalInfoKHR) -> Result,
+    GetSemaphoreFdKHR => (device: Device, pGetFdInfo: *const SemaphoreGetFdInfoKHR, pFd: *mut c_int) -> Result,
+    ImportSemaphoreFdKHR => (device: Device, pImportSemaphoreFdInfo: *const ImportSemaphoreFdInfoKHR) -> Result,
+    GetSemaphoreWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const SemaphoreGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    ImportSemaphoreWin32HandleKHR => (device: Device, pImportSemaphoreWin32HandleInfo: *const ImportSemaphoreWin32HandleInfoKHR) -> Result,
+    GetFenceFdKHR => (device: Device, pGetFdInfo: *const FenceGetFdInfoKHR, pFd: *mut c_int) -> Result,
+    ImportFenceFdKHR => (device: Device, pImportFenceFdInfo: *const ImportFenceFdInfoKHR) -> Result,
+    GetFenceWin32HandleKHR => (device: Device, pGetWin32HandleInfo: *const FenceGetWin32HandleInfoKHR, pHandle: *mut *mut c_void) -> Result,
+    ImportFenceWin32HandleKHR => (device: Device, pImportFenceWin32HandleInfo: *const ImportFenceWin32HandleInfoKHR) -> Result,
+    CreateRenderPass2KHR => (device: Device, pCreateInfo: *const RenderPassCreateInfo2KHR, pAllocator: *const AllocationCallbacks, pRenderPass: *mut RenderPass) -> Result,
     CmdPushDescriptorSetKHR => (commandBuffer: CommandBuffer, pipelineBindPoint: PipelineBindPoint, layout: PipelineLayout, set: u32, descriptorWriteCount: u32, pDescriptorWrites: *const WriteDescriptorSet) -> (),
     CreateDescriptorUpdateTemplateKHR => (device: Device, pCreateInfo: *const DescriptorUpdateTemplateCreateInfoKHR, pAllocator: *const AllocationCallbacks, pDescriptorUpdateTemplate: *mut DescriptorUpdateTemplateKHR) -> Result,
     DestroyDescriptorUpdateTemplateKHR => (device: Device, descriptorUpdateTemplate: DescriptorUpdateTemplateKHR, pAllocator: *const AllocationCallbacks) -> (),
@@ -2929,9 +4161,30 @@ ptrs!(DevicePointers, {
     CmdPushDescriptorSetWithTemplateKHR => (commandBuffer: CommandBuffer, descriptorUpdateTemplate: DescriptorUpdateTemplateKHR, layout: PipelineLayout, set: u32, pData: *const c_void) -> (),
     GetImageMemoryRequirements2KHR => (device: Device, pInfo: *const ImageMemoryRequirementsInfo2KHR, pMemoryRequirements: *mut MemoryRequirements2KHR) -> (),
     GetBufferMemoryRequirements2KHR => (device: Device, pInfo: *const BufferMemoryRequirementsInfo2KHR, pMemoryRequirements: *mut MemoryRequirements2KHR) -> (),
+    GetImageMemoryRequirements2 => (device: Device, pInfo: *const ImageMemoryRequirementsInfo2KHR, pMemoryRequirements: *mut MemoryRequirements2KHR) -> (),
+    GetBufferMemoryRequirements2 => (device: Device, pInfo: *const BufferMemoryRequirementsInfo2KHR, pMemoryRequirements: *mut MemoryRequirements2KHR) -> (),
     DebugMarkerSetObjectNameEXT => (device: Device, pNameInfo: *const DebugMarkerObjectNameInfoEXT) -> Result,
+    SetDebugUtilsObjectNameEXT => (device: Device, pNameInfo: *const DebugUtilsObjectNameInfoEXT) -> Result,
     DebugMarkerSetObjectTagEXT => (device: Device, pTagInfo: *const DebugMarkerObjectTagInfoEXT) -> Result,
     CmdDebugMarkerBeginEXT => (commandBuffer: CommandBuffer, pMarkerInfo: *const DebugMarkerMarkerInfoEXT) -> (),
     CmdDebugMarkerEndEXT => (commandBuffer: CommandBuffer) -> (),
     CmdDebugMarkerInsertEXT => (commandBuffer: CommandBuffer, pMarkerInfo: *const DebugMarkerMarkerInfoEXT) -> (),
+    ResetQueryPoolEXT => (device: Device, queryPool: QueryPool, firstQuery: u32, queryCount: u32) -> (),
+    GetBufferDeviceAddress => (device: Device, pInfo: *const BufferDeviceAddressInfo) -> DeviceAddress,
+    GetBufferDeviceAddressKHR => (device: Device, pInfo: *const BufferDeviceAddressInfo) -> DeviceAddress,
+    CreateAccelerationStructureKHR => (device: Device, pCreateInfo: *const AccelerationStructureCreateInfoKHR, pAllocator: *const AllocationCallbacks, pAccelerationStructure: *mut AccelerationStructureKHR) -> Result,
+    DestroyAccelerationStructureKHR => (device: Device, accelerationStructure: AccelerationStructureKHR, pAllocator: *const AllocationCallbacks) -> (),
+    GetAccelerationStructureBuildSizesKHR => (device: Device, buildType: AccelerationStructureBuildTypeKHR, pBuildInfo: *const AccelerationStructureBuildGeometryInfoKHR, pMaxPrimitiveCounts: *const u32, pSizeInfo: *mut AccelerationStructureBuildSizesInfoKHR) -> (),
+    GetAccelerationStructureDeviceAddressKHR => (device: Device, pInfo: *const AccelerationStructureDeviceAddressInfoKHR) -> DeviceAddress,
+    CmdBuildAccelerationStructuresKHR => (commandBuffer: CommandBuffer, infoCount: u32, pInfos: *const AccelerationStructureBuildGeometryInfoKHR, ppBuildRangeInfos: *const *const AccelerationStructureBuildRangeInfoKHR) -> (),
+    CreateRayTracingPipelinesKHR => (device: Device, deferredOperation: u64, pipelineCache: PipelineCache, createInfoCount: u32, pCreateInfos: *const RayTracingPipelineCreateInfoKHR, pAllocator: *const AllocationCallbacks, pPipelines: *mut Pipeline) -> Result,
+    GetRayTracingShaderGroupHandlesKHR => (device: Device, pipeline: Pipeline, firstGroup: u32, groupCount: u32, dataSize: usize, pData: *mut c_void) -> Result,
+    CmdTraceRaysKHR => (commandBuffer: CommandBuffer, pRaygenShaderBindingTable: *const StridedDeviceAddressRegionKHR, pMissShaderBindingTable: *const StridedDeviceAddressRegionKHR, pHitShaderBindingTable: *const StridedDeviceAddressRegionKHR, pCallableShaderBindingTable: *const StridedDeviceAddressRegionKHR, width: u32, height: u32, depth: u32) -> (),
+    CmdDrawMeshTasksEXT => (commandBuffer: CommandBuffer, groupCountX: u32, groupCountY: u32, groupCountZ: u32) -> (),
+    CmdBeginConditionalRenderingEXT => (commandBuffer: CommandBuffer, pConditionalRenderingBegin: *const ConditionalRenderingBeginInfoEXT) -> (),
+    CmdEndConditionalRenderingEXT => (commandBuffer: CommandBuffer) -> (),
+    CmdBindTransformFeedbackBuffersEXT => (commandBuffer: CommandBuffer, firstBinding: u32, bindingCount: u32, pBuffers: *const Buffer, pOffsets: *const DeviceSize, pSizes: *const DeviceSize) -> (),
+    CmdBeginTransformFeedbackEXT => (commandBuffer: CommandBuffer, firstCounterBuffer: u32, counterBufferCount: u32, pCounterBuffers: *const Buffer, pCounterBufferOffsets: *const DeviceSize) -> (),
+    CmdEndTransformFeedbackEXT => (commandBuffer: CommandBuffer, firstCounterBuffer: u32, counterBufferCount: u32, pCounterBuffers: *const Buffer, pCounterBufferOffsets: *const DeviceSize) -> (),
+    CmdDrawIndirectByteCountEXT => (commandBuffer: CommandBuffer, instanceCount: u32, firstInstance: u32, counterBuffer: Buffer, counterBufferOffset: DeviceSize, counterOffset: u32, vertexStride: u32) -> (),
 });
@@ -61,6 +61,23 @@
 //!
 //! Provides the path to the GLSL source to be compiled, relative to `Cargo.toml`.
 //! Cannot be used in conjunction with the `#[src]` attribute.
+//!
+//! # Generated items
+//!
+//! The module that contains the dummy struct receives the following items:
+//!
+//! * A `Shader` struct that loads the module with `Shader::load(device)`, and that has one
+//!   `<name>_entry_point()` method per entry point.
+//! * A `Layout` type that describes the descriptor sets and push constants of the shader, and
+//!   one `Set<N>` struct per descriptor set whose fields are the resources of each binding.
+//! * A `ty` module that contains one Rust struct per struct declared in the shader, and a
+//!   `PushConstants` alias to the struct of the push constants block if there is one.
+//! * A `SpecializationConstants` struct if the shader has specialization constants.
+//! * For vertex shaders, a `MainVertex` struct (for an entry point named `main`) whose members
+//!   match the inputs of the shader and that can be used as the vertex type of a pipeline.
+//!
+//! Since all these types are derived from the shader itself, binding a resource of the wrong
+//! kind or passing push constants or vertices of the wrong type is a compile-time error.
 
 extern crate glsl_to_spirv;
 extern crate proc_macro;
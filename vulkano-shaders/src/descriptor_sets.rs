@@ -12,18 +12,13 @@ use std::cmp;
 use enums;
 use parse;
 
-pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
+use Error;
+
+pub fn write_descriptor_sets(doc: &parse::Spirv) -> Result<String, Error> {
     // TODO: not implemented correctly
 
     // Finding all the descriptors.
     let mut descriptors = Vec::new();
-    struct Descriptor {
-        set: u32,
-        binding: u32,
-        desc_ty: String,
-        array_count: u64,
-        readonly: bool,
-    }
 
     // Looping to find all the elements that have the `DescriptorSet` decoration.
     for instruction in doc.instructions.iter() {
@@ -61,24 +56,55 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
             .next()
             .expect(&format!("Uniform `{}` is missing a binding", name));
 
+        // Vulkan only supports one-dimensional arrays of descriptors.
+        let element_ty = doc.instructions.iter().filter_map(|i| match i {
+            &parse::Instruction::TypeArray { result_id, type_id, .. } |
+            &parse::Instruction::TypeRuntimeArray { result_id, type_id }
+                if result_id == pointed_ty => Some(type_id),
+            _ => None,
+        }).next();
+        if let Some(element_ty) = element_ty {
+            let nested = doc.instructions.iter().any(|i| match i {
+                &parse::Instruction::TypeArray { result_id, .. } |
+                &parse::Instruction::TypeRuntimeArray { result_id, .. } => result_id == element_ty,
+                _ => false,
+            });
+            if nested {
+                return Err(Error::DescriptorArrayOfArrays {
+                    name: name,
+                    set: descriptor_set,
+                    binding: binding,
+                });
+            }
+        }
+
         // Find informations about the kind of binding for this descriptor.
-        let (desc_ty, readonly, array_count) = descriptor_infos(doc, pointed_ty, false)
+        let (desc_ty, resource, readonly, array_count) = descriptor_infos(doc, pointed_ty, false)
             .expect(&format!("Couldn't find relevant type for uniform `{}` (type {}, maybe \
                               unimplemented)",
                              name,
                              pointed_ty));
 
+        let runtime_array = doc.instructions.iter().any(|i| match i {
+            &parse::Instruction::TypeRuntimeArray { result_id, .. } => result_id == pointed_ty,
+            _ => false,
+        });
+
         descriptors.push(Descriptor {
+                             name: name,
                              desc_ty: desc_ty,
+                             resource: resource,
                              set: descriptor_set,
                              binding: binding,
                              array_count: array_count,
+                             runtime_array: runtime_array,
                              readonly: readonly,
                          });
     }
 
     // Looping to find all the push constant structs.
     let mut push_constants_size = 0;
+    let mut push_constants_ty = None;
     for instruction in doc.instructions.iter() {
         let type_id = match instruction {
             &parse::Instruction::TypePointer {
@@ -91,11 +117,28 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
             _ => continue,
         };
 
-        let (_, size, _) = ::structs::type_from_id(doc, type_id);
+        let (ty, size, _) = ::structs::type_from_id(doc, type_id);
         let size = size.expect("Found runtime-sized push constants");
         push_constants_size = cmp::max(push_constants_size, size);
+        push_constants_ty = Some(ty);
     }
 
+    // Alias to the struct of the `ty` module that corresponds to the push constants, so that
+    // passing push constants of the wrong type is a compile-time error.
+    let push_constants_alias = match push_constants_ty {
+        Some(ty) => {
+            format!(
+                r#"
+        /// Type of the push constants of the shader.
+        #[allow(dead_code)]
+        pub type PushConstants = ty::{ty};
+        "#,
+                ty = ty
+            )
+        },
+        None => String::new(),
+    };
+
     // Writing the body of the `descriptor` method.
     let descriptor_body = descriptors
         .iter()
@@ -104,6 +147,7 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
                 "({set}, {binding}) => Some(DescriptorDesc {{
             ty: {desc_ty},
             array_count: {array_count},
+            runtime_array: {runtime_array},
             stages: self.0.clone(),
             readonly: {readonly},
         }}),",
@@ -111,6 +155,7 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
                 binding = d.binding,
                 desc_ty = d.desc_ty,
                 array_count = d.array_count,
+                runtime_array = if d.runtime_array { "true" } else { "false" },
                 readonly = if d.readonly { "true" } else { "false" }
             )
 
@@ -152,7 +197,7 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
         pc_size = push_constants_size
     );
 
-    format!(
+    Ok(format!(
         r#"
         #[derive(Debug, Clone)]
         pub struct Layout(pub ShaderStages);
@@ -191,12 +236,254 @@ pub fn write_descriptor_sets(doc: &parse::Spirv) -> String {
         descriptor_body = descriptor_body,
         num_push_constants_ranges_body = num_push_constants_ranges_body,
         push_constants_range_body = push_constants_range_body
-    )
+    ) + &push_constants_alias + &write_typed_sets(&descriptors, num_sets))
+}
+
+/// A descriptor found in the SPIR-V document.
+struct Descriptor {
+    name: String,
+    set: u32,
+    binding: u32,
+    desc_ty: String,
+    resource: Resource,
+    array_count: u64,
+    runtime_array: bool,
+    readonly: bool,
+}
+
+/// Kind of resource that must be put in a descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Resource {
+    /// A uniform or storage buffer. Contains the name of the struct in the `ty` module that
+    /// corresponds to the content of the buffer, if known and sized.
+    Buffer(Option<String>),
+    /// A uniform or storage texel buffer.
+    BufferView,
+    /// A sampled image, a storage image or an input attachment.
+    Image,
+    /// An image combined with a sampler.
+    SampledImage,
+    /// A sampler.
+    Sampler,
+}
+
+/// Writes one struct per descriptor set, whose fields are the resources to put in each binding
+/// of the set.
+///
+/// Each struct has a `build` method that builds a `PersistentDescriptorSet`. Since the type of
+/// each field is constrained by the kind of descriptor, binding the wrong kind of resource (or a
+/// buffer with the wrong content) is a compile-time error.
+fn write_typed_sets(descriptors: &[Descriptor], num_sets: u32) -> String {
+    let mut output = String::new();
+
+    for set in 0 .. num_sets {
+        let mut set_descriptors = descriptors
+            .iter()
+            .filter(|d| d.set == set)
+            .collect::<Vec<_>>();
+        set_descriptors.sort_by_key(|d| d.binding);
+
+        if set_descriptors.is_empty() {
+            continue;
+        }
+
+        // The length of a runtime-sized array is only known when the layout is created, so the
+        // resources can't be put in a struct field. We still write an uninhabited type, so that
+        // trying to use the set gives an explanation instead of a missing type.
+        if let Some(d) = set_descriptors.iter().find(|d| d.runtime_array) {
+            output.push_str(&format!(
+                r#"
+/// Descriptor set {set} can't be described with a typed struct, because binding {binding} is a
+/// runtime-sized array whose length is only known when the layout is created.
+///
+/// Use `PersistentDescriptorSet::start` to build this set instead.
+#[allow(dead_code)]
+pub enum Set{set} {{}}
+"#,
+                set = set,
+                binding = d.binding
+            ));
+            continue;
+        }
+
+        let mut generics = Vec::new();
+        let mut bounds = Vec::new();
+        let mut fields = String::new();
+        let mut ctor_params = Vec::new();
+        let mut ctor_fields = Vec::new();
+        let mut build_body = String::new();
+        let mut next_binding = 0;
+
+        for d in set_descriptors.iter() {
+            let field = field_name(d, &set_descriptors);
+            let param = format!("T{}", d.binding);
+
+            let (elem_ty, bound) = match d.resource {
+                Resource::Buffer(Some(ref content)) => {
+                    (param.clone(),
+                     Some(format!("::vulkano::buffer::TypedBufferAccess<Content = ty::{}>",
+                                  content)))
+                },
+                Resource::Buffer(None) => {
+                    (param.clone(), Some("::vulkano::buffer::BufferAccess".to_owned()))
+                },
+                Resource::BufferView => {
+                    (param.clone(), Some("::vulkano::buffer::BufferViewRef".to_owned()))
+                },
+                Resource::Image => {
+                    (param.clone(), Some("::vulkano::image::ImageViewAccess".to_owned()))
+                },
+                Resource::SampledImage => {
+                    (format!("({}, Arc<::vulkano::sampler::Sampler>)", param),
+                     Some("::vulkano::image::ImageViewAccess".to_owned()))
+                },
+                Resource::Sampler => ("Arc<::vulkano::sampler::Sampler>".to_owned(), None),
+            };
+
+            if let Some(bound) = bound {
+                generics.push(param.clone());
+                bounds.push(format!("{}: {} + Send + Sync + 'static", param, bound));
+            }
+
+            // Arrays of descriptors hold one resource per element, all of the same type.
+            let field_ty = if d.array_count == 1 {
+                elem_ty
+            } else {
+                format!("[{}; {}]", elem_ty, d.array_count)
+            };
+
+            fields.push_str(&format!("    /// Resource of binding {}.\n    pub {}: {},\n",
+                                     d.binding, field, field_ty));
+            ctor_params.push(format!("{}: {}", field, field_ty));
+            ctor_fields.push(field.clone());
+
+            for _ in next_binding .. d.binding {
+                build_body.push_str("        let builder = builder.add_empty()?;\n");
+            }
+            next_binding = d.binding + 1;
+
+            if d.array_count == 1 {
+                let add = add_resource(&d.resource, &format!("self.{}", field));
+                build_body.push_str(&format!("        let builder = builder.{}?;\n", add));
+            } else {
+                let elements = (0 .. d.array_count)
+                    .map(|n| format!("{}_{}", field, n))
+                    .collect::<Vec<_>>();
+                build_body.push_str(&format!("        let [{}] = self.{};\n",
+                                             elements.join(", "), field));
+                build_body.push_str("        let builder = builder.enter_array()?;\n");
+                for element in elements.iter() {
+                    let add = add_resource(&d.resource, element);
+                    build_body.push_str(&format!("        let builder = builder.{}?;\n", add));
+                }
+                build_body.push_str("        let builder = builder.leave_array()?;\n");
+            }
+        }
+
+        let bounds = if bounds.is_empty() {
+            String::new()
+        } else {
+            format!("\n    where {}", bounds.join(",\n          "))
+        };
+
+        let generics = if generics.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", generics.join(", "))
+        };
+
+        output.push_str(&format!(
+            r#"
+/// Resources of descriptor set {set}, as declared in the shader.
+///
+/// Since the type of each field depends on the kind of descriptor, trying to put the wrong kind
+/// of resource in a binding is a compile-time error.
+#[allow(non_snake_case)]
+pub struct Set{set}{generics} {{
+{fields}}}
+
+#[allow(non_snake_case)]
+#[allow(dead_code)]
+impl{generics} Set{set}{generics}{bounds}
+{{
+    /// Builds the list of resources of descriptor set {set}.
+    #[inline]
+    pub fn new({ctor_params}) -> Set{set}{generics} {{
+        Set{set} {{ {ctor_fields} }}
+    }}
+
+    /// Builds a `PersistentDescriptorSet` containing these resources, for the set {set} of
+    /// `layout`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `layout` doesn't have a set {set}.
+    ///
+    pub fn build<L>(self, layout: L)
+        -> Result<Arc<DescriptorSet + Send + Sync>,
+                  ::vulkano::descriptor::descriptor_set::PersistentDescriptorSetBuildError>
+        where L: ::vulkano::descriptor::pipeline_layout::PipelineLayoutAbstract
+                 + Send + Sync + 'static
+    {{
+        let builder =
+            ::vulkano::descriptor::descriptor_set::PersistentDescriptorSet::start(layout, {set});
+{build_body}        Ok(Arc::new(builder.build()?))
+    }}
+}}
+"#,
+            set = set,
+            generics = generics,
+            fields = fields,
+            bounds = bounds,
+            ctor_params = ctor_params.join(", "),
+            ctor_fields = ctor_fields.join(", "),
+            build_body = build_body
+        ));
+    }
+
+    output
+}
+
+/// Returns the call to the builder method that adds `value` as the next descriptor, or as the
+/// next element of the current array.
+fn add_resource(resource: &Resource, value: &str) -> String {
+    match *resource {
+        Resource::Buffer(_) => format!("add_buffer({})", value),
+        Resource::BufferView => format!("add_buffer_view({})", value),
+        Resource::Image => format!("add_image({})", value),
+        Resource::SampledImage => format!("add_sampled_image({v}.0, {v}.1)", v = value),
+        Resource::Sampler => format!("add_sampler({})", value),
+    }
+}
+
+/// Returns the name of the field that holds the resource of a descriptor in its typed set.
+///
+/// Uses the name of the variable in the shader, unless it is unnamed, not a valid Rust
+/// identifier, or shared with another descriptor of the same set.
+fn field_name(desc: &Descriptor, set_descriptors: &[&Descriptor]) -> String {
+    const KEYWORDS: &[&str] = &["as", "box", "break", "const", "continue", "crate", "else",
+                                "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+                                "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+                                "return", "self", "Self", "static", "struct", "super", "trait",
+                                "true", "type", "unsafe", "use", "where", "while", "builder",
+                                "layout"];
+
+    let valid = !desc.name.is_empty() && desc.name != "__unnamed" &&
+        !desc.name.starts_with(|c: char| c.is_digit(10)) &&
+        desc.name.chars().all(|c| c.is_alphanumeric() || c == '_') &&
+        !KEYWORDS.contains(&desc.name.as_str()) &&
+        set_descriptors.iter().filter(|d| d.name == desc.name).count() == 1;
+
+    if valid {
+        desc.name.clone()
+    } else {
+        format!("binding{}", desc.binding)
+    }
 }
 
 /// Assumes that `variable` is a variable with a `TypePointer` and returns the id of the pointed
 /// type.
-fn pointer_variable_ty(doc: &parse::Spirv, variable: u32) -> u32 {
+pub fn pointer_variable_ty(doc: &parse::Spirv, variable: u32) -> u32 {
     let var_ty = doc.instructions
         .iter()
         .filter_map(|i| match i {
@@ -225,12 +512,12 @@ fn pointer_variable_ty(doc: &parse::Spirv, variable: u32) -> u32 {
         .unwrap()
 }
 
-/// Returns a `DescriptorDescTy` constructor, a bool indicating whether the descriptor is
-/// read-only, and the number of array elements.
+/// Returns a `DescriptorDescTy` constructor, the kind of resource of the descriptor, a bool
+/// indicating whether the descriptor is read-only, and the number of array elements.
 ///
 /// See also section 14.5.2 of the Vulkan specs: Descriptor Set Interface
 fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sampled: bool)
-                    -> Option<(String, bool, u64)> {
+                    -> Option<(String, Resource, bool, u64)> {
     doc.instructions.iter().filter_map(|i| {
         match i {
             &parse::Instruction::TypeStruct { result_id, .. } if result_id == pointed_ty => {
@@ -262,7 +549,14 @@ fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sa
                     storage: {},
                 }})", if is_ssbo { "true" } else { "false "});
 
-                Some((desc, true, 1))
+                // The struct is written in the `ty` module only if it has a usable name. A struct
+                // that ends with a runtime-sized array is unsized and can't be the content of a
+                // `TypedBufferAccess`.
+                let name = ::name_from_id(doc, pointed_ty);
+                let sized = ::structs::type_from_id(doc, pointed_ty).1.is_some();
+                let content = if name != "__unnamed" && sized { Some(name) } else { None };
+
+                Some((desc, Resource::Buffer(content), true, 1))
             },
 
             &parse::Instruction::TypeImage { result_id, ref dim, arrayed, ms, sampled,
@@ -292,7 +586,7 @@ fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sa
                                             array_layers: {}
                                         }}", ms, arrayed);
 
-                    Some((desc, true, 1))
+                    Some((desc, Resource::Image, true, 1))
 
                 } else if let &enums::Dim::DimBuffer = dim {
                     // We are a texel buffer.
@@ -301,7 +595,7 @@ fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sa
                         format: None,       // TODO: specify format if known
                     }}", !sampled);
 
-                    Some((desc, true, 1))
+                    Some((desc, Resource::BufferView, true, 1))
 
                 } else {
                     // We are a sampled or storage image.
@@ -325,7 +619,9 @@ fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sa
                         array_layers: {},
                     }})", ty, sampled, dim, ms, arrayed);
 
-                    Some((desc, true, 1))
+                    let resource = if force_combined_image_sampled { Resource::SampledImage }
+                                   else { Resource::Image };
+                    Some((desc, resource, true, 1))
                 }
             },
 
@@ -337,23 +633,149 @@ fn descriptor_infos(doc: &parse::Spirv, pointed_ty: u32, force_combined_image_sa
 
             &parse::Instruction::TypeSampler { result_id } if result_id == pointed_ty => {
                 let desc = format!("DescriptorDescTy::Sampler");
-                Some((desc, true, 1))
+                Some((desc, Resource::Sampler, true, 1))
             },
 
             &parse::Instruction::TypeArray { result_id, type_id, length_id } if result_id == pointed_ty => {
-                let (desc, readonly, arr) = match descriptor_infos(doc, type_id, false) {
+                // Arrays of arrays are rejected by `write_descriptor_sets`.
+                let (desc, resource, readonly, _) = match descriptor_infos(doc, type_id, false) {
                     None => return None,
                     Some(v) => v,
                 };
-                assert_eq!(arr, 1);     // TODO: implement?
                 let len = doc.instructions.iter().filter_map(|e| {
                     match e { &parse::Instruction::Constant { result_id, ref data, .. } if result_id == length_id => Some(data.clone()), _ => None }
                 }).next().expect("failed to find array length");
                 let len = len.iter().rev().fold(0u64, |a, &b| (a << 32) | b as u64);
-                Some((desc, readonly, len))
+                Some((desc, resource, readonly, len))
+            },
+
+            &parse::Instruction::TypeRuntimeArray { result_id, type_id } if result_id == pointed_ty => {
+                let (desc, resource, readonly, _) = match descriptor_infos(doc, type_id, false) {
+                    None => return None,
+                    Some(v) => v,
+                };
+                // The size of a runtime-sized array is only known when the layout is created. The
+                // layout must contain at least one element.
+                Some((desc, resource, readonly, 1))
             },
 
             _ => None,      // TODO: other types
         }
     }).next()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Descriptor;
+    use super::Resource;
+    use super::write_descriptor_sets;
+    use super::write_typed_sets;
+    use parse;
+
+    use Error;
+
+    fn descriptor(binding: u32, resource: Resource, array_count: u64, runtime_array: bool)
+                  -> Descriptor {
+        Descriptor {
+            name: format!("res{}", binding),
+            set: 0,
+            binding: binding,
+            desc_ty: String::new(),
+            resource: resource,
+            array_count: array_count,
+            runtime_array: runtime_array,
+            readonly: true,
+        }
+    }
+
+    #[test]
+    fn sized_buffer_is_typed() {
+        let descriptors = [descriptor(0, Resource::Buffer(Some("Data".to_owned())), 1, false)];
+        let output = write_typed_sets(&descriptors, 1);
+        assert!(output.contains("T0: ::vulkano::buffer::TypedBufferAccess<Content = ty::Data>"));
+        assert!(output.contains("let builder = builder.add_buffer(self.res0)?;"));
+    }
+
+    #[test]
+    fn unsized_buffer_is_untyped() {
+        // buffer Data { uint v[]; } at set 0, binding 0.
+        let words: &[u32] = &[
+            0x07230203, 0x00010000, 0, 7, 0,
+            0x00040005, 2, 0x61746144, 0,           // OpName %2 "Data"
+            0x00040006, 2, 0, 0x76,                 // OpMemberName %2 0 "v"
+            0x00030047, 2, 3,                       // OpDecorate %2 BufferBlock
+            0x00050048, 2, 0, 35, 0,                // OpMemberDecorate %2 0 Offset 0
+            0x00040015, 4, 32, 0,                   // %4 = OpTypeInt 32 0
+            0x0003001d, 3, 4,                       // %3 = OpTypeRuntimeArray %4
+            0x00040047, 3, 6, 4,                    // OpDecorate %3 ArrayStride 4
+            0x0003001e, 2, 3,                       // %2 = OpTypeStruct %3
+            0x00040020, 5, 2, 2,                    // %5 = OpTypePointer Uniform %2
+            0x0004003b, 5, 6, 2,                    // %6 = OpVariable %5 Uniform
+            0x00040047, 6, 34, 0,                   // OpDecorate %6 DescriptorSet 0
+            0x00040047, 6, 33, 0,                   // OpDecorate %6 Binding 0
+        ];
+        let bytes = words
+            .iter()
+            .flat_map(|w| (0 .. 4).map(move |n| (w >> (n * 8)) as u8))
+            .collect::<Vec<_>>();
+        let doc = parse::parse_spirv(&bytes).unwrap();
+
+        let output = write_descriptor_sets(&doc).unwrap();
+        assert!(!output.contains("TypedBufferAccess"));
+        assert!(output.contains("T0: ::vulkano::buffer::BufferAccess + Send + Sync + 'static"));
+    }
+
+    #[test]
+    fn descriptor_array() {
+        let descriptors = [descriptor(0, Resource::Sampler, 1, false),
+                           descriptor(1, Resource::SampledImage, 3, false)];
+        let output = write_typed_sets(&descriptors, 1);
+        assert!(output.contains("pub struct Set0<T1>"));
+        assert!(output.contains("pub res1: [(T1, Arc<::vulkano::sampler::Sampler>); 3],"));
+        assert!(output.contains("let [res1_0, res1_1, res1_2] = self.res1;"));
+        assert!(output.contains("let builder = builder.enter_array()?;\n        \
+                                 let builder = builder.add_sampled_image(res1_0.0, res1_0.1)?;"));
+        assert!(output.contains("builder.add_sampled_image(res1_2.0, res1_2.1)?;\n        \
+                                 let builder = builder.leave_array()?;"));
+    }
+
+    #[test]
+    fn runtime_descriptor_array() {
+        let descriptors = [descriptor(0, Resource::Image, 1, false),
+                           descriptor(1, Resource::Image, 1, true)];
+        let output = write_typed_sets(&descriptors, 1);
+        assert!(output.contains("pub enum Set0 {}"));
+        assert!(output.contains("binding 1 is a\n/// runtime-sized array"));
+        assert!(!output.contains("pub struct Set0"));
+    }
+
+    #[test]
+    fn descriptor_array_of_arrays() {
+        // uniform sampler s[2][3] at set 1, binding 4.
+        let words: &[u32] = &[
+            0x07230203, 0x00010000, 0, 9, 0,
+            0x00030005, 8, 0x73,                    // OpName %8 "s"
+            0x00040047, 8, 34, 1,                   // OpDecorate %8 DescriptorSet 1
+            0x00040047, 8, 33, 4,                   // OpDecorate %8 Binding 4
+            0x0002001a, 1,                          // %1 = OpTypeSampler
+            0x00040015, 2, 32, 0,                   // %2 = OpTypeInt 32 0
+            0x0004002b, 2, 3, 3,                    // %3 = OpConstant %2 3
+            0x0004002b, 2, 4, 2,                    // %4 = OpConstant %2 2
+            0x0004001c, 5, 1, 3,                    // %5 = OpTypeArray %1 %3
+            0x0004001c, 6, 5, 4,                    // %6 = OpTypeArray %5 %4
+            0x00040020, 7, 0, 6,                    // %7 = OpTypePointer UniformConstant %6
+            0x0004003b, 7, 8, 0,                    // %8 = OpVariable %7 UniformConstant
+        ];
+        let bytes = words
+            .iter()
+            .flat_map(|w| (0 .. 4).map(move |n| (w >> (n * 8)) as u8))
+            .collect::<Vec<_>>();
+        let doc = parse::parse_spirv(&bytes).unwrap();
+
+        match write_descriptor_sets(&doc) {
+            Err(Error::DescriptorArrayOfArrays { ref name, set: 1, binding: 4 })
+                if name == "s" => (),
+            _ => panic!(),
+        }
+    }
+}
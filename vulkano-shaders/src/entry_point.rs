@@ -54,6 +54,13 @@ pub fn write_entry_point(doc: &parse::Spirv, instruction: &parse::Instruction) -
                                     _ => false,
                                 });
 
+    let interface_structs = match *execution {
+        enums::ExecutionModel::ExecutionModelVertex => {
+            interface_structs + &write_vertex_struct(doc, &capitalized_ep_name, interface)
+        },
+        _ => interface_structs,
+    };
+
     let spec_consts_struct = if ::spec_consts::has_specialization_constants(doc) {
         "SpecializationConstants"
     } else {
@@ -244,6 +251,112 @@ fn write_interface_structs(doc: &parse::Spirv, capitalized_ep_name: &str, interf
         &write_interface_struct(&format!("{}Output", capitalized_ep_name), &output_elements)
 }
 
+/// Writes a `{Ep}Vertex` struct whose members are the inputs of a vertex shader entry point,
+/// ordered by location, and that implements the `Vertex` trait.
+///
+/// Using this struct as the vertex type of a graphics pipeline guarantees that the names and the
+/// types of the members match the inputs of the shader.
+fn write_vertex_struct(doc: &parse::Spirv, capitalized_ep_name: &str, interface: &[u32])
+                       -> String {
+    let mut members = Vec::new();
+
+    for interface in interface.iter() {
+        for i in doc.instructions.iter() {
+            match i {
+                &parse::Instruction::Variable {
+                    result_id,
+                    storage_class: enums::StorageClass::StorageClassInput,
+                    ..
+                } if &result_id == interface => {
+                    if is_builtin(doc, result_id) {
+                        continue;
+                    }
+
+                    // Unnamed inputs are skipped by `write_interface_structs` as well, so leaving
+                    // them out keeps the members in sync with the shader interface.
+                    let name = name_from_id(doc, result_id);
+                    if name == "__unnamed" {
+                        continue;
+                    }
+
+                    let loc = match location_decoration(doc, result_id) {
+                        Some(l) => l,
+                        None => continue,       // Already reported by `write_interface_structs`.
+                    };
+
+                    let pointed_ty = ::descriptor_sets::pointer_variable_ty(doc, result_id);
+                    let (ty, _, _) = ::structs::type_from_id(doc, pointed_ty);
+                    members.push((loc, name, ty));
+                },
+                _ => (),
+            }
+        }
+    }
+
+    members.sort_by_key(|&(loc, _, _)| loc);
+
+    let struct_name = format!("{}Vertex", capitalized_ep_name);
+
+    let fields = members
+        .iter()
+        .map(|&(_, ref name, ref ty)| format!("pub {}: {},", name, ty))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let member_body = members
+        .iter()
+        .map(|&(_, ref name, ref ty)| {
+            format!(
+                "if name == \"{name}\" {{
+            let (ty, array_size) = <{ty} as VertexMember>::format();
+            let offset = unsafe {{
+                let dummy: *const {struct_name} = ::std::ptr::null();
+                (&(&*dummy).{name}) as *const _ as usize
+            }};
+
+            return Some(::vulkano::pipeline::vertex::VertexMemberInfo {{
+                offset: offset,
+                ty: ty,
+                array_size: array_size,
+            }});
+        }}",
+                name = name,
+                ty = ty,
+                struct_name = struct_name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("");
+
+    format!(
+        "
+        /// Vertex type whose members match the inputs of the vertex shader.
+        #[repr(C)]
+        #[derive(Debug, Copy, Clone)]
+        pub struct {name} {{
+            {fields}
+        }}
+
+        #[allow(unsafe_code)]
+        unsafe impl ::vulkano::pipeline::vertex::Vertex for {name} {{
+            #[inline(always)]
+            #[allow(unused_variables)]
+            fn member(name: &str) -> Option<::vulkano::pipeline::vertex::VertexMemberInfo> {{
+                #[allow(unused_imports)]
+                use ::vulkano::pipeline::vertex::VertexMember;
+
+                {member_body}
+
+                None
+            }}
+        }}
+        ",
+        name = struct_name,
+        fields = fields,
+        member_body = member_body
+    )
+}
+
 fn write_interface_struct(struct_name: &str, attributes: &[(u32, String, (String, usize))])
                           -> String {
     // Checking for overlapping elements.
@@ -337,3 +450,45 @@ fn write_interface_struct(struct_name: &str, attributes: &[(u32, String, (String
         len = attributes.len()
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::write_vertex_struct;
+    use parse;
+
+    #[test]
+    fn vertex_struct() {
+        // Inputs `a` at location 1, `b` at location 0, an unnamed input at location 2 and a
+        // built-in input `c`.
+        let words: &[u32] = &[
+            0x07230203, 0x00010000, 0, 7, 0,
+            0x00030005, 3, 0x61,                    // OpName %3 "a"
+            0x00030005, 4, 0x62,                    // OpName %4 "b"
+            0x00030005, 6, 0x63,                    // OpName %6 "c"
+            0x00040047, 3, 30, 1,                   // OpDecorate %3 Location 1
+            0x00040047, 4, 30, 0,                   // OpDecorate %4 Location 0
+            0x00040047, 5, 30, 2,                   // OpDecorate %5 Location 2
+            0x00040047, 6, 11, 42,                  // OpDecorate %6 BuiltIn VertexIndex
+            0x00040015, 1, 32, 0,                   // %1 = OpTypeInt 32 0
+            0x00040020, 2, 1, 1,                    // %2 = OpTypePointer Input %1
+            0x0004003b, 2, 3, 1,                    // %3 = OpVariable %2 Input
+            0x0004003b, 2, 4, 1,                    // %4 = OpVariable %2 Input
+            0x0004003b, 2, 5, 1,                    // %5 = OpVariable %2 Input
+            0x0004003b, 2, 6, 1,                    // %6 = OpVariable %2 Input
+        ];
+        let bytes = words
+            .iter()
+            .flat_map(|w| (0 .. 4).map(move |n| (w >> (n * 8)) as u8))
+            .collect::<Vec<_>>();
+        let doc = parse::parse_spirv(&bytes).unwrap();
+
+        let output = write_vertex_struct(&doc, "Main", &[3, 4, 5, 6]);
+        assert!(output.contains("pub struct MainVertex"));
+        assert!(output.contains("pub b: u32,\npub a: u32,"));
+        assert!(!output.contains("pub c:"));
+        assert!(!output.contains("__unnamed"));
+        assert!(output.contains("unsafe impl ::vulkano::pipeline::vertex::Vertex for MainVertex"));
+        assert!(output.contains("if name == \"a\""));
+        assert!(output.contains("if name == \"b\""));
+    }
+}
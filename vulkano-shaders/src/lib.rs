@@ -135,7 +135,7 @@ impl {name} {{
     #[inline]
     #[allow(unsafe_code)]
     pub fn load(device: ::std::sync::Arc<::vulkano::device::Device>)
-                -> Result<{name}, ::vulkano::OomError>
+                -> Result<{name}, ::vulkano::pipeline::shader::ShaderModuleCreationError>
     {{
 
         "#,
@@ -206,7 +206,7 @@ impl {name} {{
         output.push_str("}");
 
         // descriptor sets
-        output.push_str(&descriptor_sets::write_descriptor_sets(&doc));
+        output.push_str(&descriptor_sets::write_descriptor_sets(&doc)?);
 
         // specialization constants
         output.push_str(&spec_consts::write_specialization_constants(&doc));
@@ -219,6 +219,13 @@ impl {name} {{
 pub enum Error {
     IoError(IoError),
     ParseError(ParseError),
+    /// A descriptor is an array of arrays, which Vulkan doesn't support.
+    DescriptorArrayOfArrays {
+        /// Name of the variable of the descriptor.
+        name: String,
+        set: u32,
+        binding: u32,
+    },
 }
 
 impl From<IoError> for Error {
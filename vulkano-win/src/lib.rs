@@ -33,6 +33,10 @@ use objc::runtime::YES;
 #[cfg(target_os = "macos")]
 use std::mem;
 
+/// Returns the instance extensions that are needed to create a surface from a window, among the
+/// ones that are supported by the system.
+///
+/// Pass them when creating the instance.
 pub fn required_extensions() -> InstanceExtensions {
     let ideal = InstanceExtensions {
         khr_surface: true,
@@ -53,7 +57,17 @@ pub fn required_extensions() -> InstanceExtensions {
     }
 }
 
+/// Creates a surface from an existing window, on whatever platform the program is running.
+///
+/// The window is kept alive by the surface, and can be accessed with `Surface::window`.
+pub fn create_vk_surface(instance: Arc<Instance>, window: winit::Window)
+                         -> Result<Arc<Surface<winit::Window>>, SurfaceCreationError> {
+    unsafe { winit_to_surface(instance, window) }
+}
+
+/// Extension trait for `WindowBuilder` that builds a window and a surface at the same time.
 pub trait VkSurfaceBuild {
+    /// Builds the window, then creates a surface from it with `create_vk_surface`.
     fn build_vk_surface(self, events_loop: &EventsLoop, instance: Arc<Instance>)
                         -> Result<Arc<Surface<winit::Window>>, CreationError>;
 }
@@ -62,7 +76,7 @@ impl VkSurfaceBuild for WindowBuilder {
     fn build_vk_surface(self, events_loop: &EventsLoop, instance: Arc<Instance>)
                         -> Result<Arc<Surface<winit::Window>>, CreationError> {
         let window = self.build(events_loop)?;
-        Ok(unsafe { winit_to_surface(instance, window) }?)
+        Ok(create_vk_surface(instance, window)?)
     }
 }
 
@@ -0,0 +1,1034 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Acceleration structures that are traversed by ray tracing shaders.
+//!
+//! Ray tracing works with two levels of acceleration structures. A *bottom-level* structure is
+//! built from geometry, which is either triangles or axis-aligned bounding boxes read from
+//! buffers. A *top-level* structure is built from instances, each of which references a
+//! bottom-level structure and places it in the scene with a transformation matrix. Ray tracing
+//! shaders trace rays against a top-level structure.
+//!
+//! Building a structure requires a *scratch buffer* on the GPU, whose size depends on the
+//! geometry. `AccelerationStructure::build` creates the structure and the scratch buffer with
+//! the right sizes and submits the build. If you want to build multiple structures in the same
+//! command buffer, or reuse the scratch buffer, use `AccelerationStructure::build_sizes`,
+//! `AccelerationStructure::new` and `AutoCommandBufferBuilder::build_acceleration_structure`
+//! instead.
+//!
+//! The buffers that contain the geometry must be created with the
+//! `acceleration_structure_build_input` and `shader_device_address` usages.
+//!
+//! Acceleration structures require the `khr_acceleration_structure` extension, which itself
+//! requires the `khr_buffer_device_address` and `khr_deferred_host_operations` extensions.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::Queue;
+//! use vulkano::acceleration_structure::AccelerationStructure;
+//! use vulkano::acceleration_structure::AccelerationStructureGeometry;
+//! use vulkano::acceleration_structure::AccelerationStructureType;
+//! use vulkano::acceleration_structure::BuildAccelerationStructureFlags;
+//! use vulkano::buffer::BufferUsage;
+//! use vulkano::buffer::CpuAccessibleBuffer;
+//! use vulkano::format::Format;
+//! use vulkano::sync::GpuFuture;
+//!
+//! # let queue: Arc<Queue> = return;
+//! let usage = BufferUsage {
+//!     acceleration_structure_build_input: true,
+//!     shader_device_address: true,
+//!     .. BufferUsage::none()
+//! };
+//!
+//! let vertices = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+//! let vertex_buffer = CpuAccessibleBuffer::from_iter(queue.device().clone(), usage,
+//!                                                    vertices.iter().cloned()).unwrap();
+//!
+//! let geometry = AccelerationStructureGeometry::triangles(vertex_buffer, Format::R32G32B32Sfloat,
+//!                                                         12).opaque();
+//!
+//! let (blas, future) = AccelerationStructure::build(queue.clone(),
+//!                                                   AccelerationStructureType::BottomLevel,
+//!                                                   vec![geometry],
+//!                                                   BuildAccelerationStructureFlags::none())
+//!     .unwrap();
+//! future.then_signal_fence_and_flush().unwrap().wait(None).unwrap();
+//! ```
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use buffer::BufferUsage;
+use buffer::DeviceLocalBuffer;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BuildAccelerationStructureError;
+use command_buffer::BuildError;
+use command_buffer::CommandBuffer;
+use command_buffer::CommandBufferExecError;
+use command_buffer::CommandBufferExecFuture;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use format::Format;
+use instance::PhysicalDevice;
+use memory::DeviceMemoryAllocError;
+use pipeline::input_assembly::IndexType;
+use sync::NowFuture;
+
+use OomError;
+use VulkanObject;
+use check_errors;
+use vk;
+
+/// Future returned by `AccelerationStructure::build`.
+pub type AccelerationStructureBuildFuture = CommandBufferExecFuture<NowFuture, AutoCommandBuffer>;
+
+/// An acceleration structure, stored in a buffer of the device.
+///
+/// The content of the structure is undefined until it is built with
+/// `AutoCommandBufferBuilder::build_acceleration_structure`.
+pub struct AccelerationStructure {
+    structure: vk::AccelerationStructureKHR,
+    device: Arc<Device>,
+    ty: AccelerationStructureType,
+    buffer: Arc<DeviceLocalBuffer<[u8]>>,
+    device_address: u64,
+    // False if the structure was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+impl AccelerationStructure {
+    /// Creates an acceleration structure of `size` bytes.
+    ///
+    /// Use `build_sizes` to determine the size required by some geometry.
+    pub fn new(device: Arc<Device>, ty: AccelerationStructureType, size: usize)
+               -> Result<Arc<AccelerationStructure>, AccelerationStructureCreationError> {
+        if !device.loaded_extensions().khr_acceleration_structure {
+            return Err(AccelerationStructureCreationError::ExtensionNotEnabled);
+        }
+
+        let usage = BufferUsage {
+            acceleration_structure_storage: true,
+            shader_device_address: true,
+            ..BufferUsage::none()
+        };
+
+        let buffer = DeviceLocalBuffer::array(device.clone(),
+                                              size,
+                                              usage,
+                                              device.active_queue_families())?;
+
+        let vk = device.pointers();
+        let structure = unsafe {
+            let inner = buffer.inner();
+            let infos = vk::AccelerationStructureCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                createFlags: 0,
+                buffer: inner.buffer.internal_object(),
+                offset: inner.offset as vk::DeviceSize,
+                size: size as vk::DeviceSize,
+                ty: ty as u32,
+                deviceAddress: 0,
+            };
+
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateAccelerationStructureKHR(device.internal_object(),
+                                                           &infos,
+                                                           ptr::null(),
+                                                           &mut output))
+                .map_err(OomError::from)?;
+            output
+        };
+
+        let device_address = unsafe {
+            let infos = vk::AccelerationStructureDeviceAddressInfoKHR {
+                sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_DEVICE_ADDRESS_INFO_KHR,
+                pNext: ptr::null(),
+                accelerationStructure: structure,
+            };
+            vk.GetAccelerationStructureDeviceAddressKHR(device.internal_object(), &infos)
+        };
+
+        Ok(Arc::new(AccelerationStructure {
+                        structure: structure,
+                        device: device,
+                        ty: ty,
+                        buffer: buffer,
+                        device_address: device_address,
+                        needs_destruction: true,
+                    }))
+    }
+
+    /// Builds an `AccelerationStructure` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the structure is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid acceleration structure of type `ty`, created on the whole of
+    ///   `buffer`.
+    /// - If `owned` is true, the structure must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(buffer: Arc<DeviceLocalBuffer<[u8]>>,
+                           handle: vk::AccelerationStructureKHR, ty: AccelerationStructureType,
+                           owned: bool)
+                           -> Arc<AccelerationStructure> {
+        let device = buffer.device().clone();
+
+        let device_address = {
+            let infos = vk::AccelerationStructureDeviceAddressInfoKHR {
+                sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_DEVICE_ADDRESS_INFO_KHR,
+                pNext: ptr::null(),
+                accelerationStructure: handle,
+            };
+            let vk = device.pointers();
+            vk.GetAccelerationStructureDeviceAddressKHR(device.internal_object(), &infos)
+        };
+
+        Arc::new(AccelerationStructure {
+                     structure: handle,
+                     device: device,
+                     ty: ty,
+                     buffer: buffer,
+                     device_address: device_address,
+                     needs_destruction: owned,
+                 })
+    }
+
+    /// Returns the sizes of the structure and of the scratch buffers required to build a
+    /// structure of type `ty` from `geometries`.
+    pub fn build_sizes(device: &Device, ty: AccelerationStructureType,
+                       geometries: &[AccelerationStructureGeometry],
+                       flags: BuildAccelerationStructureFlags)
+                       -> Result<AccelerationStructureBuildSizes,
+                                 AccelerationStructureCreationError> {
+        if !device.loaded_extensions().khr_acceleration_structure {
+            return Err(AccelerationStructureCreationError::ExtensionNotEnabled);
+        }
+
+        // The addresses of the geometry are ignored when querying the sizes.
+        let (raw_geometries, ranges) = raw_geometries(geometries, false);
+        let max_primitive_counts: SmallVec<[u32; 4]> =
+            ranges.iter().map(|r| r.primitiveCount).collect();
+
+        unsafe {
+            let vk = device.pointers();
+            let infos = vk::AccelerationStructureBuildGeometryInfoKHR {
+                sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_BUILD_GEOMETRY_INFO_KHR,
+                pNext: ptr::null(),
+                ty: ty as u32,
+                flags: flags.into_vulkan_bits(),
+                mode: vk::BUILD_ACCELERATION_STRUCTURE_MODE_BUILD_KHR,
+                srcAccelerationStructure: 0,
+                dstAccelerationStructure: 0,
+                geometryCount: raw_geometries.len() as u32,
+                pGeometries: raw_geometries.as_ptr(),
+                ppGeometries: ptr::null(),
+                scratchData: vk::DeviceOrHostAddressKHR { deviceAddress: 0 },
+            };
+
+            let mut output = vk::AccelerationStructureBuildSizesInfoKHR {
+                sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_BUILD_SIZES_INFO_KHR,
+                pNext: ptr::null(),
+                accelerationStructureSize: 0,
+                updateScratchSize: 0,
+                buildScratchSize: 0,
+            };
+
+            let build_type = vk::ACCELERATION_STRUCTURE_BUILD_TYPE_DEVICE_KHR;
+            vk.GetAccelerationStructureBuildSizesKHR(device.internal_object(),
+                                                     build_type,
+                                                     &infos,
+                                                     max_primitive_counts.as_ptr(),
+                                                     &mut output);
+
+            Ok(AccelerationStructureBuildSizes {
+                   structure_size: output.accelerationStructureSize as usize,
+                   build_scratch_size: output.buildScratchSize as usize,
+                   update_scratch_size: output.updateScratchSize as usize,
+               })
+        }
+    }
+
+    /// Creates an acceleration structure of type `ty`, and submits a command buffer to `queue`
+    /// that builds it from `geometries`.
+    ///
+    /// The structure and the scratch buffer are allocated with the sizes returned by
+    /// `build_sizes`. The structure must not be used before the returned future is signalled.
+    pub fn build(queue: Arc<Queue>, ty: AccelerationStructureType,
+                 geometries: Vec<AccelerationStructureGeometry>,
+                 flags: BuildAccelerationStructureFlags)
+                 -> Result<(Arc<AccelerationStructure>, AccelerationStructureBuildFuture),
+                           AccelerationStructureBuildError> {
+        let device = queue.device().clone();
+
+        let sizes = AccelerationStructure::build_sizes(&device, ty, &geometries, flags)?;
+        let structure = AccelerationStructure::new(device.clone(), ty, sizes.structure_size)?;
+
+        // The address of the scratch buffer must be aligned, so we allocate enough room to align
+        // it ourselves.
+        let physical_device = device.physical_device();
+        let properties = AccelerationStructureProperties::from_physical_device(physical_device);
+        let alignment = properties.min_scratch_offset_alignment as usize;
+        let scratch_usage = BufferUsage {
+            storage_buffer: true,
+            shader_device_address: true,
+            ..BufferUsage::none()
+        };
+        let scratch = DeviceLocalBuffer::<[u8]>::array(device.clone(),
+                                                       sizes.build_scratch_size + alignment,
+                                                       scratch_usage,
+                                                       device.active_queue_families())
+            .map_err(AccelerationStructureCreationError::from)?;
+
+        let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                               queue.family())?
+            .build_acceleration_structure(structure.clone(), geometries, flags, scratch)?
+            .build()?;
+
+        let future = command_buffer.execute(queue)?;
+        Ok((structure, future))
+    }
+
+    /// Returns the type of the structure.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        self.ty
+    }
+
+    /// Returns the size of the structure in bytes.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.buffer.size()
+    }
+
+    /// Returns the address of the structure on the device.
+    ///
+    /// This is the value to reference the structure from an instance of a top-level structure.
+    #[inline]
+    pub fn device_address(&self) -> u64 {
+        self.device_address
+    }
+
+    /// Returns the buffer that stores the structure.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<DeviceLocalBuffer<[u8]>> {
+        &self.buffer
+    }
+}
+
+unsafe impl DeviceOwned for AccelerationStructure {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for AccelerationStructure {
+    type Object = vk::AccelerationStructureKHR;
+
+    const TYPE: vk::DebugReportObjectTypeEXT =
+        vk::DEBUG_REPORT_OBJECT_TYPE_ACCELERATION_STRUCTURE_KHR_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::AccelerationStructureKHR {
+        self.structure
+    }
+}
+
+impl fmt::Debug for AccelerationStructure {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan acceleration structure {:?}>", self.structure)
+    }
+}
+
+impl Drop for AccelerationStructure {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyAccelerationStructureKHR(self.device.internal_object(),
+                                               self.structure,
+                                               ptr::null());
+        }
+    }
+}
+
+/// The level of an acceleration structure.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum AccelerationStructureType {
+    /// The structure contains instances of bottom-level structures.
+    TopLevel = vk::ACCELERATION_STRUCTURE_TYPE_TOP_LEVEL_KHR,
+    /// The structure contains triangles or bounding boxes.
+    BottomLevel = vk::ACCELERATION_STRUCTURE_TYPE_BOTTOM_LEVEL_KHR,
+}
+
+/// Sizes required to build an acceleration structure. Returned by
+/// `AccelerationStructure::build_sizes`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AccelerationStructureBuildSizes {
+    /// Size of the acceleration structure.
+    pub structure_size: usize,
+    /// Size of the scratch buffer required to build the structure.
+    pub build_scratch_size: usize,
+    /// Size of the scratch buffer required to update the structure.
+    pub update_scratch_size: usize,
+}
+
+/// Limits of the physical device regarding acceleration structures.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AccelerationStructureProperties {
+    /// Maximum number of geometries in a bottom-level structure.
+    pub max_geometry_count: u64,
+    /// Maximum number of instances in a top-level structure.
+    pub max_instance_count: u64,
+    /// Maximum number of triangles or bounding boxes in a bottom-level structure.
+    pub max_primitive_count: u64,
+    /// Required alignment of the address of the scratch buffer.
+    pub min_scratch_offset_alignment: u32,
+}
+
+impl AccelerationStructureProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice)
+                                -> AccelerationStructureProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceAccelerationStructurePropertiesKHR = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_ACCELERATION_STRUCTURE_PROPERTIES_KHR;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            AccelerationStructureProperties {
+                max_geometry_count: output.maxGeometryCount,
+                max_instance_count: output.maxInstanceCount,
+                max_primitive_count: output.maxPrimitiveCount,
+                min_scratch_offset_alignment: output.minAccelerationStructureScratchOffsetAlignment,
+            }
+        }
+    }
+}
+
+/// Flags that control how an acceleration structure is built.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct BuildAccelerationStructureFlags {
+    /// The structure can be updated after being built.
+    pub allow_update: bool,
+    /// The structure can be compacted after being built.
+    pub allow_compaction: bool,
+    /// Favors the speed of the ray traversal over the speed of the build.
+    pub prefer_fast_trace: bool,
+    /// Favors the speed of the build over the speed of the ray traversal.
+    pub prefer_fast_build: bool,
+    /// Favors a low memory usage over the speed of the build and of the ray traversal.
+    pub low_memory: bool,
+}
+
+impl BuildAccelerationStructureFlags {
+    /// Builds a `BuildAccelerationStructureFlags` with all the flags set to false.
+    #[inline]
+    pub fn none() -> BuildAccelerationStructureFlags {
+        BuildAccelerationStructureFlags::default()
+    }
+
+    /// Builds a `BuildAccelerationStructureFlags` that favors the speed of the ray traversal.
+    #[inline]
+    pub fn prefer_fast_trace() -> BuildAccelerationStructureFlags {
+        BuildAccelerationStructureFlags {
+            prefer_fast_trace: true,
+            ..BuildAccelerationStructureFlags::none()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::BuildAccelerationStructureFlagsKHR {
+        let mut result = 0;
+        if self.allow_update {
+            result |= vk::BUILD_ACCELERATION_STRUCTURE_ALLOW_UPDATE_BIT_KHR;
+        }
+        if self.allow_compaction {
+            result |= vk::BUILD_ACCELERATION_STRUCTURE_ALLOW_COMPACTION_BIT_KHR;
+        }
+        if self.prefer_fast_trace {
+            result |= vk::BUILD_ACCELERATION_STRUCTURE_PREFER_FAST_TRACE_BIT_KHR;
+        }
+        if self.prefer_fast_build {
+            result |= vk::BUILD_ACCELERATION_STRUCTURE_PREFER_FAST_BUILD_BIT_KHR;
+        }
+        if self.low_memory {
+            result |= vk::BUILD_ACCELERATION_STRUCTURE_LOW_MEMORY_BIT_KHR;
+        }
+        result
+    }
+}
+
+/// Geometry that an acceleration structure is built from.
+///
+/// Bottom-level structures are built from triangles or bounding boxes, and top-level structures
+/// from exactly one list of instances.
+#[derive(Clone)]
+pub struct AccelerationStructureGeometry {
+    data: GeometryData,
+    opaque: bool,
+}
+
+#[derive(Clone)]
+enum GeometryData {
+    Triangles {
+        vertices: Arc<BufferAccess + Send + Sync>,
+        vertex_format: Format,
+        vertex_stride: usize,
+        indices: Option<(Arc<BufferAccess + Send + Sync>, IndexType)>,
+    },
+    Aabbs { data: Arc<BufferAccess + Send + Sync> },
+    Instances { data: Arc<BufferAccess + Send + Sync> },
+}
+
+impl AccelerationStructureGeometry {
+    /// Geometry made of a list of triangles, with three vertices per triangle.
+    ///
+    /// Each vertex is `vertex_stride` bytes apart in `vertices`, and its position is read with
+    /// `vertex_format`.
+    #[inline]
+    pub fn triangles<V>(vertices: V, vertex_format: Format, vertex_stride: usize)
+                        -> AccelerationStructureGeometry
+        where V: BufferAccess + Send + Sync + 'static
+    {
+        AccelerationStructureGeometry {
+            data: GeometryData::Triangles {
+                vertices: Arc::new(vertices),
+                vertex_format: vertex_format,
+                vertex_stride: vertex_stride,
+                indices: None,
+            },
+            opaque: false,
+        }
+    }
+
+    /// Geometry made of a list of triangles whose vertices are referenced by `indices`, with
+    /// three indices per triangle.
+    #[inline]
+    pub fn indexed_triangles<V, I>(vertices: V, vertex_format: Format, vertex_stride: usize,
+                                   indices: I, index_ty: IndexType)
+                                   -> AccelerationStructureGeometry
+        where V: BufferAccess + Send + Sync + 'static,
+              I: BufferAccess + Send + Sync + 'static
+    {
+        AccelerationStructureGeometry {
+            data: GeometryData::Triangles {
+                vertices: Arc::new(vertices),
+                vertex_format: vertex_format,
+                vertex_stride: vertex_stride,
+                indices: Some((Arc::new(indices), index_ty)),
+            },
+            opaque: false,
+        }
+    }
+
+    /// Geometry made of axis-aligned bounding boxes, to be intersected by an intersection shader.
+    ///
+    /// The buffer must contain tightly packed `AabbPositions`.
+    #[inline]
+    pub fn aabbs<B>(data: B) -> AccelerationStructureGeometry
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        AccelerationStructureGeometry {
+            data: GeometryData::Aabbs { data: Arc::new(data) },
+            opaque: false,
+        }
+    }
+
+    /// Instances of bottom-level structures, for building a top-level structure.
+    ///
+    /// The buffer must contain tightly packed `AccelerationStructureInstance`s.
+    ///
+    /// > **Note**: The bottom-level structures referenced by the instances are neither kept alive
+    /// > nor synchronized by vulkano. They must be built by an earlier submission, and must
+    /// > outlive the top-level structure.
+    #[inline]
+    pub fn instances<B>(data: B) -> AccelerationStructureGeometry
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        AccelerationStructureGeometry {
+            data: GeometryData::Instances { data: Arc::new(data) },
+            opaque: false,
+        }
+    }
+
+    /// Marks the geometry as opaque, which means that the any-hit shaders are not invoked for it.
+    #[inline]
+    pub fn opaque(mut self) -> AccelerationStructureGeometry {
+        self.opaque = true;
+        self
+    }
+
+    /// Returns true if the geometry is a list of instances.
+    #[inline]
+    pub fn is_instances(&self) -> bool {
+        match self.data {
+            GeometryData::Instances { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the number of triangles, bounding boxes or instances of the geometry.
+    pub fn primitive_count(&self) -> u32 {
+        let count = match self.data {
+            GeometryData::Triangles { ref vertices, vertex_stride, ref indices, .. } => {
+                match *indices {
+                    Some((ref indices, IndexType::U16)) => indices.size() / 2 / 3,
+                    Some((ref indices, IndexType::U32)) => indices.size() / 4 / 3,
+                    // A stride of 0 is rejected when building the structure.
+                    None => vertices.size().checked_div(vertex_stride).unwrap_or(0) / 3,
+                }
+            },
+            GeometryData::Aabbs { ref data } => data.size() / mem::size_of::<AabbPositions>(),
+            GeometryData::Instances { ref data } => {
+                data.size() / mem::size_of::<AccelerationStructureInstance>()
+            },
+        };
+
+        count as u32
+    }
+
+    /// Returns the distance in bytes between two vertices, if the geometry is made of triangles.
+    #[inline]
+    pub fn vertex_stride(&self) -> Option<usize> {
+        match self.data {
+            GeometryData::Triangles { vertex_stride, .. } => Some(vertex_stride),
+            _ => None,
+        }
+    }
+
+    /// Returns the buffers that the geometry is read from.
+    pub(crate) fn buffers(&self) -> SmallVec<[&Arc<BufferAccess + Send + Sync>; 2]> {
+        let mut output = SmallVec::new();
+        match self.data {
+            GeometryData::Triangles { ref vertices, ref indices, .. } => {
+                output.push(vertices);
+                if let Some((ref indices, _)) = *indices {
+                    output.push(indices);
+                }
+            },
+            GeometryData::Aabbs { ref data } => output.push(data),
+            GeometryData::Instances { ref data } => output.push(data),
+        }
+        output
+    }
+
+    fn to_vulkan(&self, with_addresses: bool)
+                 -> (vk::AccelerationStructureGeometryKHR,
+                     vk::AccelerationStructureBuildRangeInfoKHR) {
+        let address = |buffer: &Arc<BufferAccess + Send + Sync>| {
+            let address = if with_addresses {
+                let inner = buffer.inner();
+                inner.buffer.device_address() + inner.offset as u64
+            } else {
+                0
+            };
+            vk::DeviceOrHostAddressConstKHR { deviceAddress: address }
+        };
+
+        let (ty, geometry) = match self.data {
+            GeometryData::Triangles { ref vertices, vertex_format, vertex_stride, ref indices } => {
+                let vertex_count = vertices.size().checked_div(vertex_stride).unwrap_or(0);
+                let (index_ty, index_data) = match *indices {
+                    Some((ref indices, ty)) => (ty as u32, address(indices)),
+                    None => (vk::INDEX_TYPE_NONE_KHR,
+                             vk::DeviceOrHostAddressConstKHR { deviceAddress: 0 }),
+                };
+
+                let triangles = vk::AccelerationStructureGeometryTrianglesDataKHR {
+                    sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_TRIANGLES_DATA_KHR,
+                    pNext: ptr::null(),
+                    vertexFormat: vertex_format as u32,
+                    vertexData: address(vertices),
+                    vertexStride: vertex_stride as vk::DeviceSize,
+                    maxVertex: (vertex_count as u32).saturating_sub(1),
+                    indexType: index_ty,
+                    indexData: index_data,
+                    transformData: vk::DeviceOrHostAddressConstKHR { deviceAddress: 0 },
+                };
+                (vk::GEOMETRY_TYPE_TRIANGLES_KHR,
+                 vk::AccelerationStructureGeometryDataKHR { triangles: triangles })
+            },
+            GeometryData::Aabbs { ref data } => {
+                let aabbs = vk::AccelerationStructureGeometryAabbsDataKHR {
+                    sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_AABBS_DATA_KHR,
+                    pNext: ptr::null(),
+                    data: address(data),
+                    stride: mem::size_of::<AabbPositions>() as vk::DeviceSize,
+                };
+                (vk::GEOMETRY_TYPE_AABBS_KHR,
+                 vk::AccelerationStructureGeometryDataKHR { aabbs: aabbs })
+            },
+            GeometryData::Instances { ref data } => {
+                let instances = vk::AccelerationStructureGeometryInstancesDataKHR {
+                    sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_INSTANCES_DATA_KHR,
+                    pNext: ptr::null(),
+                    arrayOfPointers: vk::FALSE,
+                    data: address(data),
+                };
+                (vk::GEOMETRY_TYPE_INSTANCES_KHR,
+                 vk::AccelerationStructureGeometryDataKHR { instances: instances })
+            },
+        };
+
+        let geometry = vk::AccelerationStructureGeometryKHR {
+            sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_GEOMETRY_KHR,
+            pNext: ptr::null(),
+            geometryType: ty,
+            geometry: geometry,
+            flags: if self.opaque {
+                vk::GEOMETRY_OPAQUE_BIT_KHR
+            } else {
+                0
+            },
+        };
+
+        let range = vk::AccelerationStructureBuildRangeInfoKHR {
+            primitiveCount: self.primitive_count(),
+            primitiveOffset: 0,
+            firstVertex: 0,
+            transformOffset: 0,
+        };
+
+        (geometry, range)
+    }
+}
+
+impl fmt::Debug for AccelerationStructureGeometry {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let ty = match self.data {
+            GeometryData::Triangles { .. } => "triangles",
+            GeometryData::Aabbs { .. } => "aabbs",
+            GeometryData::Instances { .. } => "instances",
+        };
+        write!(fmt, "<Acceleration structure geometry of {} {}>", self.primitive_count(), ty)
+    }
+}
+
+// Builds the Vulkan structs that describe `geometries`. If `with_addresses` is false, the
+// addresses of the buffers are left to zero.
+pub(crate) fn raw_geometries(geometries: &[AccelerationStructureGeometry], with_addresses: bool)
+                             -> (SmallVec<[vk::AccelerationStructureGeometryKHR; 4]>,
+                                 SmallVec<[vk::AccelerationStructureBuildRangeInfoKHR; 4]>) {
+    let mut raw_geometries = SmallVec::new();
+    let mut ranges = SmallVec::new();
+    for geometry in geometries {
+        let (raw, range) = geometry.to_vulkan(with_addresses);
+        raw_geometries.push(raw);
+        ranges.push(range);
+    }
+    (raw_geometries, ranges)
+}
+
+/// An axis-aligned bounding box, as read by `AccelerationStructureGeometry::aabbs`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
+pub struct AabbPositions {
+    /// The minimum coordinates of the box.
+    pub min: [f32; 3],
+    /// The maximum coordinates of the box.
+    pub max: [f32; 3],
+}
+
+/// An instance of a bottom-level structure, as read by
+/// `AccelerationStructureGeometry::instances`.
+///
+/// Has the same memory layout as a `VkAccelerationStructureInstanceKHR`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(C)]
+pub struct AccelerationStructureInstance {
+    transform: [[f32; 4]; 3],
+    custom_index_and_mask: u32,
+    shader_binding_table_offset_and_flags: u32,
+    structure_reference: u64,
+}
+
+impl AccelerationStructureInstance {
+    /// Builds an instance of `structure` with an identity transform, a custom index and a
+    /// shader binding table offset of 0, and a mask that accepts all rays.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `structure` isn't a bottom-level structure.
+    ///
+    #[inline]
+    pub fn new(structure: &AccelerationStructure) -> AccelerationStructureInstance {
+        assert_eq!(structure.ty(), AccelerationStructureType::BottomLevel);
+
+        AccelerationStructureInstance {
+            transform: [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0]],
+            custom_index_and_mask: 0xff << 24,
+            shader_binding_table_offset_and_flags: 0,
+            structure_reference: structure.device_address(),
+        }
+    }
+
+    /// Sets the row-major 3x4 matrix that transforms the bottom-level structure.
+    #[inline]
+    pub fn transform(mut self, transform: [[f32; 4]; 3]) -> AccelerationStructureInstance {
+        self.transform = transform;
+        self
+    }
+
+    /// Sets the value returned by `gl_InstanceCustomIndexEXT` in the shaders.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `index` doesn't fit in 24 bits.
+    ///
+    #[inline]
+    pub fn custom_index(mut self, index: u32) -> AccelerationStructureInstance {
+        assert!(index < (1 << 24));
+        self.custom_index_and_mask = (self.custom_index_and_mask & 0xff000000) | index;
+        self
+    }
+
+    /// Sets the mask that is tested against the cull mask of the rays. The instance is only hit
+    /// by the rays for which `mask & cull_mask` is not zero.
+    #[inline]
+    pub fn mask(mut self, mask: u8) -> AccelerationStructureInstance {
+        self.custom_index_and_mask = (self.custom_index_and_mask & 0xffffff) |
+            ((mask as u32) << 24);
+        self
+    }
+
+    /// Sets the offset of the hit group of the instance in the shader binding table.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `offset` doesn't fit in 24 bits.
+    ///
+    #[inline]
+    pub fn shader_binding_table_offset(mut self, offset: u32) -> AccelerationStructureInstance {
+        assert!(offset < (1 << 24));
+        self.shader_binding_table_offset_and_flags =
+            (self.shader_binding_table_offset_and_flags & 0xff000000) | offset;
+        self
+    }
+
+    /// If true, the instance is considered opaque regardless of the flags of its geometry.
+    #[inline]
+    pub fn force_opaque(mut self, force: bool) -> AccelerationStructureInstance {
+        let bit = vk::GEOMETRY_INSTANCE_FORCE_OPAQUE_BIT_KHR << 24;
+        if force {
+            self.shader_binding_table_offset_and_flags |= bit;
+        } else {
+            self.shader_binding_table_offset_and_flags &= !bit;
+        }
+        self
+    }
+}
+
+/// Error that can happen when creating an acceleration structure.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccelerationStructureCreationError {
+    /// Error while allocating the buffer of the structure.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+
+    /// The `VK_KHR_acceleration_structure` extension must be enabled when creating the device.
+    ExtensionNotEnabled,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for AccelerationStructureCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            AccelerationStructureCreationError::DeviceMemoryAllocError(_) =>
+                "error while allocating the buffer of the structure",
+            AccelerationStructureCreationError::ExtensionNotEnabled =>
+                "the device extension `VK_KHR_acceleration_structure` is not enabled",
+            AccelerationStructureCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            AccelerationStructureCreationError::DeviceMemoryAllocError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(AccelerationStructureCreationError, DeviceMemoryAllocError {});
+
+impl From<DeviceMemoryAllocError> for AccelerationStructureCreationError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> AccelerationStructureCreationError {
+        AccelerationStructureCreationError::DeviceMemoryAllocError(err)
+    }
+}
+
+/// Error that can happen when calling `AccelerationStructure::build`.
+#[derive(Debug, Clone)]
+pub enum AccelerationStructureBuildError {
+    /// Error while creating the structure or the scratch buffer.
+    CreationError(AccelerationStructureCreationError),
+    /// Not enough memory.
+    OomError(OomError),
+    /// Error while recording the build.
+    BuildAccelerationStructureError(BuildAccelerationStructureError),
+    /// Error while building the command buffer.
+    BuildError(BuildError),
+    /// Error while submitting the command buffer.
+    CommandBufferExecError(CommandBufferExecError),
+}
+
+impl error::Error for AccelerationStructureBuildError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            AccelerationStructureBuildError::CreationError(_) => {
+                "error while creating the structure or the scratch buffer"
+            },
+            AccelerationStructureBuildError::OomError(_) => {
+                "not enough memory"
+            },
+            AccelerationStructureBuildError::BuildAccelerationStructureError(_) => {
+                "error while recording the build"
+            },
+            AccelerationStructureBuildError::BuildError(_) => {
+                "error while building the command buffer"
+            },
+            AccelerationStructureBuildError::CommandBufferExecError(_) => {
+                "error while submitting the command buffer"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            AccelerationStructureBuildError::CreationError(ref err) => Some(err),
+            AccelerationStructureBuildError::OomError(ref err) => Some(err),
+            AccelerationStructureBuildError::BuildAccelerationStructureError(ref err) => Some(err),
+            AccelerationStructureBuildError::BuildError(ref err) => Some(err),
+            AccelerationStructureBuildError::CommandBufferExecError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for AccelerationStructureBuildError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<AccelerationStructureCreationError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: AccelerationStructureCreationError) -> AccelerationStructureBuildError {
+        AccelerationStructureBuildError::CreationError(err)
+    }
+}
+
+impl From<OomError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: OomError) -> AccelerationStructureBuildError {
+        AccelerationStructureBuildError::OomError(err)
+    }
+}
+
+impl From<BuildAccelerationStructureError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: BuildAccelerationStructureError) -> AccelerationStructureBuildError {
+        AccelerationStructureBuildError::BuildAccelerationStructureError(err)
+    }
+}
+
+impl From<BuildError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: BuildError) -> AccelerationStructureBuildError {
+        AccelerationStructureBuildError::BuildError(err)
+    }
+}
+
+impl From<CommandBufferExecError> for AccelerationStructureBuildError {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> AccelerationStructureBuildError {
+        AccelerationStructureBuildError::CommandBufferExecError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use acceleration_structure::AccelerationStructure;
+    use acceleration_structure::AccelerationStructureCreationError;
+    use acceleration_structure::AccelerationStructureInstance;
+    use acceleration_structure::AccelerationStructureType;
+    use VulkanObject;
+    use vk;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match AccelerationStructure::new(device, AccelerationStructureType::BottomLevel, 256) {
+            Err(AccelerationStructureCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = AccelerationStructure::new(device, ty, 256).unwrap();
+
+        let borrowed = unsafe {
+            AccelerationStructure::from_raw(structure.buffer().clone(),
+                                            structure.internal_object(),
+                                            ty,
+                                            false)
+        };
+        assert_eq!(borrowed.internal_object(), structure.internal_object());
+        assert_eq!(borrowed.device_address(), structure.device_address());
+        assert_eq!(borrowed.size(), structure.size());
+    }
+
+    #[test]
+    fn instance_layout() {
+        assert_eq!(mem::size_of::<AccelerationStructureInstance>(),
+                   mem::size_of::<vk::AccelerationStructureInstanceKHR>());
+    }
+}
@@ -59,6 +59,35 @@ use sync::AccessError;
 use sync::Sharing;
 
 /// Buffer whose content is accessible by the CPU.
+///
+/// # Synchronization with the GPU
+///
+/// The buffer keeps track of the command buffers that use it and that haven't finished executing.
+/// Calling `read()` while the GPU may write to the buffer, or `write()` while the GPU may access
+/// it at all, returns an error instead of racing with the GPU. The buffer is only considered free
+/// again once vulkano knows that the submissions are finished, for example after calling `wait`
+/// on the `FenceSignalFuture` of the submission or `cleanup_finished` once it is signaled.
+///
+/// ```
+/// use vulkano::buffer::BufferUsage;
+/// use vulkano::buffer::CpuAccessibleBuffer;
+/// use vulkano::command_buffer::AutoCommandBuffer;
+/// use vulkano::sync::GpuFuture;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+///
+/// let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+///
+/// let future = AutoCommandBuffer::one_time_submit(queue.clone(), |builder| {
+///     builder.fill_buffer(buffer.clone(), 12)
+/// }).unwrap();
+///
+/// // The GPU may still be writing to the buffer.
+/// assert!(buffer.read().is_err());
+///
+/// future.wait(None).unwrap();
+/// assert_eq!(*buffer.read().unwrap(), 12);
+/// ```
 #[derive(Debug)]
 pub struct CpuAccessibleBuffer<T: ?Sized, A = PotentialDedicatedAllocation<StdMemoryPoolAlloc>> {
     // Inner content.
@@ -572,6 +601,9 @@ impl fmt::Display for WriteLockError {
 #[cfg(test)]
 mod tests {
     use buffer::{BufferUsage, CpuAccessibleBuffer};
+    use buffer::cpu_access::ReadLockError;
+    use buffer::cpu_access::WriteLockError;
+    use command_buffer::AutoCommandBuffer;
 
     #[test]
     fn create_empty_buffer() {
@@ -581,4 +613,23 @@ mod tests {
 
         let _ = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), EMPTY.iter());
     }
+
+    #[test]
+    fn cpu_access_denied_while_gpu_writes() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let future = AutoCommandBuffer::one_time_submit(queue.clone(), |builder| {
+            builder.fill_buffer(buffer.clone(), 12)
+        }).unwrap();
+
+        assert_eq!(buffer.read().err(), Some(ReadLockError::GpuWriteLocked));
+        assert_eq!(buffer.write().err(), Some(WriteLockError::GpuLocked));
+
+        future.wait(None).unwrap();
+        assert_eq!(*buffer.read().unwrap(), 12);
+        *buffer.write().unwrap() = 5;
+    }
 }
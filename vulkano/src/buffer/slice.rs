@@ -97,6 +97,22 @@ impl<T: ?Sized, B> BufferSlice<T, B> {
         self.size
     }
 
+    /// Changes the content type of the slice, without changing its offset or its size.
+    ///
+    /// # Safety
+    ///
+    /// The region of the buffer must be valid for the new content type when the slice is used by
+    /// the device, including its size and its alignment.
+    #[inline]
+    pub unsafe fn reinterpret<R: ?Sized>(self) -> BufferSlice<R, B> {
+        BufferSlice {
+            marker: PhantomData,
+            resource: self.resource,
+            offset: self.offset,
+            size: self.size,
+        }
+    }
+
     /// Builds a slice that contains an element from inside the buffer.
     ///
     /// This method builds an object that represents a slice of the buffer. No actual operation
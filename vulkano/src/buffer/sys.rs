@@ -39,7 +39,6 @@ use memory::DeviceMemoryAllocError;
 use memory::MemoryRequirements;
 use sync::Sharing;
 
-use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
@@ -51,6 +50,10 @@ pub struct UnsafeBuffer {
     device: Arc<Device>,
     size: usize,
     usage: vk::BufferUsageFlags,
+    // True if the buffer was created with `Sharing::Concurrent`.
+    concurrent_sharing: bool,
+    // False if the buffer was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl UnsafeBuffer {
@@ -60,8 +63,9 @@ impl UnsafeBuffer {
     ///
     /// # Panic
     ///
-    /// Panics if `sparse.sparse` is false and `sparse.sparse_residency` or
-    /// `sparse.sparse_aliased` is true.
+    /// - Panics if `sparse.sparse` is false and `sparse.sparse_residency` or
+    ///   `sparse.sparse_aliased` is true.
+    /// - Panics if `usage` contains a usage whose extension isn't enabled on the device.
     ///
     pub unsafe fn new<'a, I>(device: Arc<Device>, size: usize, usage: BufferUsage,
                              sharing: Sharing<I>, sparse: SparseLevel)
@@ -80,6 +84,29 @@ impl UnsafeBuffer {
 
         let usage_bits = usage.to_vulkan_bits();
 
+        // Checking the usages that belong to an extension.
+        assert!(!usage.shader_device_address ||
+                    device.loaded_extensions().khr_buffer_device_address,
+                "The `khr_buffer_device_address` extension must be enabled in order to use the \
+                 `shader_device_address` usage");
+        assert!(!(usage.acceleration_structure_build_input ||
+                      usage.acceleration_structure_storage) ||
+                    device.loaded_extensions().khr_acceleration_structure,
+                "The `khr_acceleration_structure` extension must be enabled in order to use the \
+                 acceleration structure usages");
+        assert!(!usage.shader_binding_table ||
+                    device.loaded_extensions().khr_ray_tracing_pipeline,
+                "The `khr_ray_tracing_pipeline` extension must be enabled in order to use the \
+                 `shader_binding_table` usage");
+        assert!(!usage.conditional_rendering ||
+                    device.loaded_extensions().ext_conditional_rendering,
+                "The `ext_conditional_rendering` extension must be enabled in order to use the \
+                 `conditional_rendering` usage");
+        assert!(!(usage.transform_feedback_buffer || usage.transform_feedback_counter_buffer) ||
+                    device.loaded_extensions().ext_transform_feedback,
+                "The `ext_transform_feedback` extension must be enabled in order to use the \
+                 transform feedback usages");
+
         // Checking sparse features.
         assert!(sparse.sparse || !sparse.sparse_residency,
                 "Can't enable sparse residency without enabling sparse binding as well");
@@ -95,6 +122,11 @@ impl UnsafeBuffer {
             return Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled);
         }
 
+        let concurrent_sharing = match sharing {
+            Sharing::Exclusive => false,
+            Sharing::Concurrent(_) => true,
+        };
+
         let buffer = {
             let (sh_mode, sh_indices) = match sharing {
                 Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
@@ -126,14 +158,14 @@ impl UnsafeBuffer {
                 al * (1 + (val - 1) / al)
             }
 
-            let mut output = if device.loaded_extensions().khr_get_memory_requirements2 {
+            let mut output = if device.supports_memory_requirements2() {
                 let infos = vk::BufferMemoryRequirementsInfo2KHR {
                     sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR,
                     pNext: ptr::null_mut(),
                     buffer: buffer,
                 };
 
-                let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+                let mut output2 = if device.supports_dedicated_allocation() {
                     Some(vk::MemoryDedicatedRequirementsKHR {
                              sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                              pNext: ptr::null(),
@@ -153,7 +185,7 @@ impl UnsafeBuffer {
                     memoryRequirements: mem::uninitialized(),
                 };
 
-                vk.GetBufferMemoryRequirements2KHR(device.internal_object(), &infos, &mut output);
+                device.get_buffer_memory_requirements2(&infos, &mut output);
                 debug_assert!(output.memoryRequirements.size >= size as u64);
                 debug_assert!(output.memoryRequirements.memoryTypeBits != 0);
 
@@ -197,11 +229,38 @@ impl UnsafeBuffer {
             device: device.clone(),
             size: size as usize,
             usage: usage_bits,
+            concurrent_sharing: concurrent_sharing,
+            needs_destruction: true,
         };
 
         Ok((obj, mem_reqs))
     }
 
+    /// Builds an `UnsafeBuffer` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the buffer is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid buffer of `device`, created with the given size and usage.
+    /// - `concurrent_sharing` must be true if and only if the buffer was created with
+    ///   `VK_SHARING_MODE_CONCURRENT`.
+    /// - If `owned` is true, the buffer must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::Buffer, size: usize,
+                           usage: BufferUsage, concurrent_sharing: bool, owned: bool)
+                           -> UnsafeBuffer {
+        UnsafeBuffer {
+            buffer: handle,
+            device: device,
+            size: size,
+            usage: usage.to_vulkan_bits(),
+            concurrent_sharing: concurrent_sharing,
+            needs_destruction: owned,
+        }
+    }
+
     pub unsafe fn bind_memory(&self, memory: &DeviceMemory, offset: usize) -> Result<(), OomError> {
         let vk = self.device.pointers();
 
@@ -243,6 +302,14 @@ impl UnsafeBuffer {
         self.size
     }
 
+    /// Returns true if the buffer was created with `Sharing::Concurrent`.
+    ///
+    /// Buffers with a concurrent sharing mode don't need any queue ownership transfer.
+    #[inline]
+    pub fn concurrent_sharing(&self) -> bool {
+        self.concurrent_sharing
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::BUFFER_USAGE_TRANSFER_SRC_BIT) != 0
@@ -288,6 +355,64 @@ impl UnsafeBuffer {
         (self.usage & vk::BUFFER_USAGE_INDIRECT_BUFFER_BIT) != 0
     }
 
+    #[inline]
+    pub fn usage_shader_device_address(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_SHADER_DEVICE_ADDRESS_BIT) != 0
+    }
+
+    #[inline]
+    pub fn usage_acceleration_structure_build_input(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_BIT_KHR) != 0
+    }
+
+    #[inline]
+    pub fn usage_acceleration_structure_storage(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_ACCELERATION_STRUCTURE_STORAGE_BIT_KHR) != 0
+    }
+
+    #[inline]
+    pub fn usage_shader_binding_table(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_SHADER_BINDING_TABLE_BIT_KHR) != 0
+    }
+
+    #[inline]
+    pub fn usage_conditional_rendering(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_CONDITIONAL_RENDERING_BIT_EXT) != 0
+    }
+
+    #[inline]
+    pub fn usage_transform_feedback_buffer(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_TRANSFORM_FEEDBACK_BUFFER_BIT_EXT) != 0
+    }
+
+    #[inline]
+    pub fn usage_transform_feedback_counter_buffer(&self) -> bool {
+        (self.usage & vk::BUFFER_USAGE_TRANSFORM_FEEDBACK_COUNTER_BUFFER_BIT_EXT) != 0
+    }
+
+    /// Returns the address of the start of the buffer in the address space of the device.
+    ///
+    /// The buffer must already be bound to memory.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the buffer wasn't created with the `shader_device_address` usage.
+    ///
+    pub fn device_address(&self) -> u64 {
+        assert!(self.usage_shader_device_address(),
+                "The buffer wasn't created with the `shader_device_address` usage");
+
+        unsafe {
+            let vk = self.device.pointers();
+            let infos = vk::BufferDeviceAddressInfo {
+                sType: vk::STRUCTURE_TYPE_BUFFER_DEVICE_ADDRESS_INFO,
+                pNext: ptr::null(),
+                buffer: self.buffer,
+            };
+            vk.GetBufferDeviceAddressKHR(self.device.internal_object(), &infos)
+        }
+    }
+
     /// Returns a key unique to each `UnsafeBuffer`. Can be used for the `conflicts_key` method.
     #[inline]
     pub fn key(&self) -> u64 {
@@ -323,6 +448,10 @@ impl fmt::Debug for UnsafeBuffer {
 impl Drop for UnsafeBuffer {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyBuffer(self.device.internal_object(), self.buffer, ptr::null());
@@ -374,6 +503,8 @@ pub enum BufferCreationError {
     SparseResidencyBufferFeatureNotEnabled,
     /// Sparse aliasing was requested but the corresponding feature wasn't enabled.
     SparseResidencyAliasedFeatureNotEnabled,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for BufferCreationError {
@@ -390,6 +521,8 @@ impl error::Error for BufferCreationError {
             BufferCreationError::SparseResidencyAliasedFeatureNotEnabled => {
                 "sparse aliasing was requested but the corresponding feature wasn't enabled"
             },
+            BufferCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -402,32 +535,7 @@ impl error::Error for BufferCreationError {
     }
 }
 
-impl fmt::Display for BufferCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for BufferCreationError {
-    #[inline]
-    fn from(err: OomError) -> BufferCreationError {
-        BufferCreationError::AllocError(err.into())
-    }
-}
-
-impl From<Error> for BufferCreationError {
-    #[inline]
-    fn from(err: Error) -> BufferCreationError {
-        match err {
-            err @ Error::OutOfHostMemory =>
-                BufferCreationError::AllocError(DeviceMemoryAllocError::from(err)),
-            err @ Error::OutOfDeviceMemory =>
-                BufferCreationError::AllocError(DeviceMemoryAllocError::from(err)),
-            _ => panic!("unexpected error: {:?}", err),
-        }
-    }
-}
+vk_error_conversions!(BufferCreationError, AllocError {});
 
 #[cfg(test)]
 mod tests {
@@ -27,6 +27,27 @@ pub struct BufferUsage {
     pub index_buffer: bool,
     pub vertex_buffer: bool,
     pub indirect_buffer: bool,
+    /// The buffer's device address can be queried with `UnsafeBuffer::device_address`. Requires
+    /// the `khr_buffer_device_address` extension.
+    pub shader_device_address: bool,
+    /// The buffer can be used as an input of an acceleration structure build, for example as a
+    /// vertex or instance buffer. Requires the `khr_acceleration_structure` extension.
+    pub acceleration_structure_build_input: bool,
+    /// The buffer can hold acceleration structures. Requires the `khr_acceleration_structure`
+    /// extension.
+    pub acceleration_structure_storage: bool,
+    /// The buffer can hold a shader binding table. Requires the `khr_ray_tracing_pipeline`
+    /// extension.
+    pub shader_binding_table: bool,
+    /// The buffer can hold the predicate of `begin_conditional_rendering`. Requires the
+    /// `ext_conditional_rendering` extension.
+    pub conditional_rendering: bool,
+    /// The buffer can be bound with `bind_transform_feedback_buffers` to capture the output of
+    /// transform feedback. Requires the `ext_transform_feedback` extension.
+    pub transform_feedback_buffer: bool,
+    /// The buffer can hold the counters of transform feedback, and be used with
+    /// `draw_indirect_byte_count`. Requires the `ext_transform_feedback` extension.
+    pub transform_feedback_counter_buffer: bool,
 }
 
 impl BufferUsage {
@@ -60,6 +81,27 @@ impl BufferUsage {
         if self.indirect_buffer {
             result |= vk::BUFFER_USAGE_INDIRECT_BUFFER_BIT;
         }
+        if self.shader_device_address {
+            result |= vk::BUFFER_USAGE_SHADER_DEVICE_ADDRESS_BIT;
+        }
+        if self.acceleration_structure_build_input {
+            result |= vk::BUFFER_USAGE_ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_BIT_KHR;
+        }
+        if self.acceleration_structure_storage {
+            result |= vk::BUFFER_USAGE_ACCELERATION_STRUCTURE_STORAGE_BIT_KHR;
+        }
+        if self.shader_binding_table {
+            result |= vk::BUFFER_USAGE_SHADER_BINDING_TABLE_BIT_KHR;
+        }
+        if self.conditional_rendering {
+            result |= vk::BUFFER_USAGE_CONDITIONAL_RENDERING_BIT_EXT;
+        }
+        if self.transform_feedback_buffer {
+            result |= vk::BUFFER_USAGE_TRANSFORM_FEEDBACK_BUFFER_BIT_EXT;
+        }
+        if self.transform_feedback_counter_buffer {
+            result |= vk::BUFFER_USAGE_TRANSFORM_FEEDBACK_COUNTER_BUFFER_BIT_EXT;
+        }
         result
     }
 
@@ -76,10 +118,18 @@ impl BufferUsage {
             index_buffer: false,
             vertex_buffer: false,
             indirect_buffer: false,
+            shader_device_address: false,
+            acceleration_structure_build_input: false,
+            acceleration_structure_storage: false,
+            shader_binding_table: false,
+            conditional_rendering: false,
+            transform_feedback_buffer: false,
+            transform_feedback_counter_buffer: false,
         }
     }
 
-    /// Builds a `BufferUsage` with all values set to true. Can be used for quick prototyping.
+    /// Builds a `BufferUsage` with all values set to true, except for the usages that require an
+    /// extension. Can be used for quick prototyping.
     #[inline]
     pub fn all() -> BufferUsage {
         BufferUsage {
@@ -92,6 +142,13 @@ impl BufferUsage {
             index_buffer: true,
             vertex_buffer: true,
             indirect_buffer: true,
+            shader_device_address: false,
+            acceleration_structure_build_input: false,
+            acceleration_structure_storage: false,
+            shader_binding_table: false,
+            conditional_rendering: false,
+            transform_feedback_buffer: false,
+            transform_feedback_counter_buffer: false,
         }
     }
 
@@ -208,6 +265,17 @@ impl BitOr for BufferUsage {
             index_buffer: self.index_buffer || rhs.index_buffer,
             vertex_buffer: self.vertex_buffer || rhs.vertex_buffer,
             indirect_buffer: self.indirect_buffer || rhs.indirect_buffer,
+            shader_device_address: self.shader_device_address || rhs.shader_device_address,
+            acceleration_structure_build_input: self.acceleration_structure_build_input ||
+                rhs.acceleration_structure_build_input,
+            acceleration_structure_storage: self.acceleration_structure_storage ||
+                rhs.acceleration_structure_storage,
+            shader_binding_table: self.shader_binding_table || rhs.shader_binding_table,
+            conditional_rendering: self.conditional_rendering || rhs.conditional_rendering,
+            transform_feedback_buffer: self.transform_feedback_buffer ||
+                rhs.transform_feedback_buffer,
+            transform_feedback_counter_buffer: self.transform_feedback_counter_buffer ||
+                rhs.transform_feedback_counter_buffer,
         }
     }
 }
@@ -51,7 +51,6 @@ use device::DeviceOwned;
 use format::FormatDesc;
 use format::StrongStorage;
 
-use Error;
 use OomError;
 use SafeDeref;
 use VulkanObject;
@@ -67,6 +66,8 @@ pub struct BufferView<F, B>
     buffer: B,
     marker: PhantomData<F>,
     atomic_accesses: bool,
+    // False if the view was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<F, B> BufferView<F, B>
@@ -121,23 +122,16 @@ impl<F, B> BufferView<F, B>
                 }
             }
 
-            let format_props = {
-                let vk_i = device.instance().pointers();
-                let mut output = mem::uninitialized();
-                vk_i.GetPhysicalDeviceFormatProperties(device.physical_device().internal_object(),
-                                                       format as u32,
-                                                       &mut output);
-                output.bufferFeatures
-            };
+            let format_props = device.physical_device().format_properties(format).buffer_features;
 
             if buffer.usage_uniform_texel_buffer() {
-                if (format_props & vk::FORMAT_FEATURE_UNIFORM_TEXEL_BUFFER_BIT) == 0 {
+                if !format_props.uniform_texel_buffer {
                     return Err(BufferViewCreationError::UnsupportedFormat);
                 }
             }
 
             if buffer.usage_storage_texel_buffer() {
-                if (format_props & vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_BIT) == 0 {
+                if !format_props.storage_texel_buffer {
                     return Err(BufferViewCreationError::UnsupportedFormat);
                 }
             }
@@ -165,12 +159,43 @@ impl<F, B> BufferView<F, B>
                view: view,
                buffer: org_buffer,
                marker: PhantomData,
-               atomic_accesses: (format_props &
-                                     vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_ATOMIC_BIT) !=
-                   0,
+               atomic_accesses: format_props.storage_texel_buffer_atomic,
+               needs_destruction: true,
            })
     }
 
+    /// Builds a `BufferView` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the view is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid view of the whole of `buffer`, with the format `format`.
+    /// - If `owned` is true, the view must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(buffer: B, format: F, handle: vk::BufferView, owned: bool)
+                           -> BufferView<F, B>
+        where F: FormatDesc
+    {
+        let atomic_accesses = buffer
+            .inner()
+            .buffer
+            .device()
+            .physical_device()
+            .format_properties(format.format())
+            .buffer_features
+            .storage_texel_buffer_atomic;
+
+        BufferView {
+            view: handle,
+            buffer: buffer,
+            marker: PhantomData,
+            atomic_accesses: atomic_accesses,
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns the buffer associated to this view.
     #[inline]
     pub fn buffer(&self) -> &B {
@@ -234,6 +259,10 @@ impl<F, B> Drop for BufferView<F, B>
 {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.buffer.inner().buffer.device().pointers();
             vk.DestroyBufferView(self.buffer.inner().buffer.device().internal_object(),
@@ -294,6 +323,9 @@ pub enum BufferViewCreationError {
 
     /// The maximum number of elements in the buffer view has been exceeded.
     MaxTexelBufferElementsExceeded,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for BufferViewCreationError {
@@ -312,6 +344,8 @@ impl error::Error for BufferViewCreationError {
             BufferViewCreationError::MaxTexelBufferElementsExceeded => {
                 "the maximum number of texel elements is exceeded"
             },
+            BufferViewCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -324,29 +358,11 @@ impl error::Error for BufferViewCreationError {
     }
 }
 
-impl fmt::Display for BufferViewCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for BufferViewCreationError {
-    #[inline]
-    fn from(err: OomError) -> BufferViewCreationError {
-        BufferViewCreationError::OomError(err)
-    }
-}
-
-impl From<Error> for BufferViewCreationError {
-    #[inline]
-    fn from(err: Error) -> BufferViewCreationError {
-        OomError::from(err).into()
-    }
-}
+vk_error_conversions!(BufferViewCreationError, OomError {});
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
     use buffer::BufferUsage;
     use buffer::BufferView;
     use buffer::immutable::ImmutableBuffer;
@@ -447,4 +463,28 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = BufferUsage {
+            uniform_texel_buffer: true,
+            ..BufferUsage::none()
+        };
+
+        let (buffer, _) = ImmutableBuffer::<[[u8; 4]]>::from_iter((0 .. 128).map(|_| [0; 4]),
+                                                                  usage,
+                                                                  queue.clone())
+            .unwrap();
+        let view = BufferView::new(buffer.clone(), format::R8G8B8A8Unorm).unwrap();
+
+        let borrowed = unsafe {
+            BufferView::from_raw(buffer, format::R8G8B8A8Unorm, view.internal_object(), false)
+        };
+        assert_eq!(borrowed.internal_object(), view.internal_object());
+        assert!(borrowed.uniform_texel_buffer());
+        drop(borrowed);
+        assert!(view.uniform_texel_buffer());
+    }
 }
@@ -17,10 +17,16 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 
 use OomError;
+use VulkanObject;
+use acceleration_structure::AccelerationStructure;
+use acceleration_structure::AccelerationStructureGeometry;
+use acceleration_structure::BuildAccelerationStructureFlags;
 use buffer::BufferAccess;
 use buffer::TypedBufferAccess;
 use command_buffer::CommandBuffer;
 use command_buffer::CommandBufferExecError;
+use command_buffer::CommandBufferExecFuture;
+use command_buffer::DrawIndexedIndirectCommand;
 use command_buffer::DrawIndirectCommand;
 use command_buffer::DynamicState;
 use command_buffer::StateCacher;
@@ -42,7 +48,10 @@ use command_buffer::sys::UnsafeCommandBufferBuilderImageAspect;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
 use command_buffer::validity::*;
+use descriptor::descriptor_set::DescriptorSet;
 use descriptor::descriptor_set::DescriptorSetsCollection;
+use descriptor::descriptor_set::PersistentDescriptorSetBuilder;
+use descriptor::descriptor_set::PersistentDescriptorSetResources;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
 use device::DeviceOwned;
@@ -50,11 +59,9 @@ use device::Queue;
 use format::AcceptsPixels;
 use format::ClearValue;
 use format::Format;
-use format::FormatTy;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::Framebuffer;
 use framebuffer::FramebufferAbstract;
-use framebuffer::LoadOp;
 use framebuffer::RenderPass;
 use framebuffer::RenderPassAbstract;
 use framebuffer::RenderPassCompatible;
@@ -66,14 +73,29 @@ use image::ImageLayout;
 use instance::QueueFamily;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
+use pipeline::PipelineBindPoint;
+use pipeline::RayTracingPipelineAbstract;
+use pipeline::ShaderBindingTable;
 use pipeline::input_assembly::Index;
 use pipeline::vertex::VertexSource;
 use query::QueryPipelineStatisticFlags;
+use query::QueryResultElement;
+use query::QueryResultFlags;
+use query::QueryType;
+use query::UnsafeQueryPool;
 use sampler::Filter;
 use sync::AccessCheckError;
 use sync::AccessFlagBits;
+use sync::BufferMemoryBarrier;
+use sync::FenceSignalFuture;
+use sync::FlushError;
 use sync::GpuFuture;
+use sync::ImageMemoryBarrier;
+use sync::MemoryBarrier;
+use sync::NowFuture;
 use sync::PipelineStages;
+use sync::SemaphoreSignalFuture;
+use vk;
 
 ///
 ///
@@ -86,12 +108,29 @@ pub struct AutoCommandBufferBuilder<P = StandardCommandPoolBuilder> {
     inner: SyncCommandBufferBuilder<P>,
     state_cacher: StateCacher,
 
+    // Identifier of the queue family the command buffer is going to be submitted to.
+    queue_family: u32,
+
     // True if the queue family supports graphics operations.
     graphics_allowed: bool,
 
     // True if the queue family supports compute operations.
     compute_allowed: bool,
 
+    // True if the queue family supports writing timestamps.
+    timestamps_allowed: bool,
+
+    // List of queries that are currently active, as the raw query pool, the index of the query
+    // and the type of the pool.
+    active_queries: Vec<(vk::QueryPool, u32, QueryType)>,
+
+    // If conditional rendering is active, contains true if it was begun inside of the current
+    // subpass and false if it was begun outside of a render pass.
+    conditional_rendering: Option<bool>,
+
+    // True if transform feedback is active. It can only be active inside of a subpass.
+    transform_feedback: bool,
+
     // If we're inside a render pass, contains the render pass and the subpass index.
     render_pass: Option<(Box<RenderPassAbstract>, u32)>,
 
@@ -385,12 +424,18 @@ impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
 
             let graphics_allowed = queue_family.supports_graphics();
             let compute_allowed = queue_family.supports_compute();
+            let timestamps_allowed = queue_family.timestamp_valid_bits().is_some();
 
             Ok(AutoCommandBufferBuilder {
                    inner: inner?,
                    state_cacher,
+                   queue_family: queue_family.id(),
                    graphics_allowed,
                    compute_allowed,
+                   timestamps_allowed,
+                   active_queries: Vec::new(),
+                   conditional_rendering: None,
+                   transform_feedback: false,
                    render_pass,
                    secondary_cb,
                    subpass_secondary: false,
@@ -459,6 +504,18 @@ impl<P> AutoCommandBufferBuilder<P> {
             return Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass.into());
         }
 
+        if !self.active_queries.is_empty() {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
+        if self.conditional_rendering.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
+        if self.transform_feedback {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+        }
+
         let submit_state = match self.flags {
             Flags::None => {
                 SubmitState::ExclusiveUse { in_use: AtomicBool::new(false) }
@@ -477,13 +534,73 @@ impl<P> AutoCommandBufferBuilder<P> {
            })
     }
 
+    /// Adds a command that acquires the ownership of a buffer from another queue family.
+    ///
+    /// This is the second half of a queue family ownership transfer, and is only needed for
+    /// buffers that were created with `Sharing::Exclusive`. The command buffer that contains the
+    /// first half, built with `release_buffer_ownership`, must have finished executing on a queue
+    /// of `source` before this command executes, for example by waiting on a semaphore.
+    ///
+    /// See also `transfer_buffer_ownership`, which takes care of both halves and of the
+    /// synchronization between the two queues.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `source` doesn't belong to the same physical device as the command buffer.
+    ///
+    #[inline]
+    pub fn acquire_buffer_ownership<B>(mut self, buffer: B, source: QueueFamily)
+                                       -> Result<Self, OwnershipTransferError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            assert_eq!(source.physical_device().internal_object(),
+                       self.device().physical_device().internal_object());
+            self.ensure_outside_render_pass()?;
+            let destination = self.queue_family;
+            check_buffer_ownership_transfer(self.device(), &buffer, source.id(), destination)?;
+            self.inner
+                .buffer_ownership_transfer(buffer, source.id(), destination, false)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that acquires the ownership of an image from another queue family.
+    ///
+    /// This is the equivalent of `acquire_buffer_ownership` but for images. The image is expected
+    /// to be in the layout returned by `initial_layout_requirement()`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `source` doesn't belong to the same physical device as the command buffer.
+    ///
+    #[inline]
+    pub fn acquire_image_ownership<I>(mut self, image: I, source: QueueFamily)
+                                      -> Result<Self, OwnershipTransferError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        unsafe {
+            assert_eq!(source.physical_device().internal_object(),
+                       self.device().physical_device().internal_object());
+            self.ensure_outside_render_pass()?;
+            let destination = self.queue_family;
+            check_image_ownership_transfer(self.device(), &image, source.id(), destination)?;
+            let layout = image.initial_layout_requirement();
+            self.inner
+                .image_ownership_transfer(image, layout, source.id(), destination, false)?;
+            Ok(self)
+        }
+    }
+
     /// Adds a command that enters a render pass.
     ///
     /// If `secondary` is true, then you will only be able to add secondary command buffers while
     /// you're inside the first subpass of the render pass. If `secondary` is false, you will only
     /// be able to add inline draw commands and not secondary command buffers.
     ///
-    /// C must contain exactly one clear value for each attachment in the framebuffer.
+    /// C must contain exactly one clear value for each attachment in the framebuffer. Attachments
+    /// whose load operation is `Clear` must receive a clear value that matches their format, and
+    /// the other attachments must receive `ClearValue::None`.
     ///
     /// You must call this before you can add draw commands.
     #[inline]
@@ -504,55 +621,7 @@ impl<P> AutoCommandBufferBuilder<P> {
 
             let clear_values = framebuffer.convert_clear_values(clear_values);
             let clear_values = clear_values.collect::<Vec<_>>().into_iter(); // TODO: necessary for Send + Sync ; needs an API rework of convert_clear_values
-            let mut clear_values_copy = clear_values.clone().enumerate(); // TODO: Proper errors for clear value errors instead of panics
-
-            for (atch_i, atch_desc) in framebuffer.attachment_descs().enumerate() {
-                match clear_values_copy.next() {
-                    Some((clear_i, clear_value)) => {
-                        if atch_desc.load == LoadOp::Clear {
-                            match clear_value {
-                                ClearValue::None => panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: None",
-                                    clear_i, atch_i, atch_desc.format.ty()),
-                                ClearValue::Float(_) => if atch_desc.format.ty() != FormatTy::Float {
-                                   panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Float",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Int(_) => if atch_desc.format.ty() != FormatTy::Sint {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Int",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Uint(_) => if atch_desc.format.ty() != FormatTy::Uint {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Uint",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Depth(_) => if atch_desc.format.ty() != FormatTy::Depth {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Depth",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::Stencil(_) => if atch_desc.format.ty() != FormatTy::Stencil {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: Stencil",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                                ClearValue::DepthStencil(_) => if atch_desc.format.ty() != FormatTy::DepthStencil {
-                                    panic!("Bad ClearValue! index: {}, attachment index: {}, expected: {:?}, got: DepthStencil",
-                                       clear_i, atch_i, atch_desc.format.ty());
-                                }
-                            }
-                        }
-                        else {
-                            if clear_value != ClearValue::None {
-                                panic!("Bad ClearValue! index: {}, attachment index: {}, expected: None, got: {:?}",
-                                   clear_i, atch_i, clear_value);
-                            }
-                        }
-                    }
-                    None => panic!("Not enough clear values")
-                }
-            }
-            
-            if clear_values_copy.count() != 0 {
-                panic!("Too many clear values")
-            }
+            check_clear_values(&framebuffer, clear_values.clone())?;
 
             let contents = if secondary {
                 SubpassContents::SecondaryCommandBuffers
@@ -567,6 +636,285 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that begins conditional rendering.
+    ///
+    /// Until `end_conditional_rendering` is called, the draw, dispatch and clear commands are
+    /// discarded by the device if the value in `buffer` is 0. If `inverted` is true, they are
+    /// discarded if the value isn't 0 instead. This allows for example skipping draws depending
+    /// on the result of an occlusion query that was copied to the buffer, without reading it back
+    /// on the CPU.
+    ///
+    /// Conditional rendering can't be nested. If it is begun inside of a subpass, it must be
+    /// ended before the end of the subpass. Secondary command buffers can't be executed while
+    /// conditional rendering is active.
+    ///
+    /// Requires the `ext_conditional_rendering` extension.
+    pub fn begin_conditional_rendering<B>(mut self, buffer: B, inverted: bool)
+                                          -> Result<Self, BeginConditionalRenderingError>
+        where B: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        unsafe {
+            if !self.graphics_allowed && !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            if self.conditional_rendering.is_some() {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive
+                               .into());
+            }
+
+            check_begin_conditional_rendering(self.device(), &buffer)?;
+
+            self.inner.begin_conditional_rendering(buffer, inverted)?;
+            self.conditional_rendering = Some(self.render_pass.is_some());
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that begins a query.
+    ///
+    /// The query must be ended with `end_query` before the command buffer is built, and only one
+    /// query of each type can be active at the same time. Timestamp queries can't be begun; use
+    /// `write_timestamp` instead.
+    ///
+    /// # Safety
+    ///
+    /// The query must be unavailable, which is ensured by resetting it with `reset_query_pool`
+    /// beforehand.
+    pub unsafe fn begin_query(mut self, query_pool: Arc<UnsafeQueryPool>, query: u32,
+                              precise: bool)
+                              -> Result<Self, BeginQueryError> {
+        check_begin_query(self.device(), &query_pool, query, precise)?;
+
+        let ty = query_pool.ty();
+        match ty {
+            QueryType::Occlusion => {
+                if !self.graphics_allowed {
+                    return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                                   .into());
+                }
+            },
+            QueryType::PipelineStatistics(_) => {
+                if !self.graphics_allowed && !self.compute_allowed {
+                    return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                                   .into());
+                }
+            },
+            QueryType::Timestamp => unreachable!(),
+        }
+
+        if self.active_queries
+            .iter()
+            .any(|&(_, _, active_ty)| same_query_type(active_ty, ty))
+        {
+            return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+        }
+
+        self.active_queries
+            .push((query_pool.internal_object(), query, ty));
+        self.inner.begin_query(query_pool, query, precise);
+        Ok(self)
+    }
+
+    /// Adds a command that begins transform feedback.
+    ///
+    /// Until `end_transform_feedback` is called, the vertices that are output by the last
+    /// pre-rasterization shader stage of the draw commands are written to the buffers that were
+    /// bound with `bind_transform_feedback_buffers`. The writes start at the beginning of each
+    /// buffer.
+    ///
+    /// Transform feedback can only be begun inside of a subpass, and must be ended before the end
+    /// of that subpass.
+    ///
+    /// Requires the `ext_transform_feedback` extension.
+    #[inline]
+    pub fn begin_transform_feedback(self) -> Result<Self, BeginTransformFeedbackError> {
+        unsafe { self.begin_transform_feedback_impl(Vec::new()) }
+    }
+
+    /// Same as `begin_transform_feedback`, but resumes writing at the byte offsets stored in
+    /// `counter_buffers` instead of at the beginning of the buffers.
+    ///
+    /// The counter buffer at index `n` corresponds to the transform feedback buffer bound at
+    /// index `n`. The counters are usually written by a previous call to
+    /// `end_transform_feedback_with_counters`.
+    #[inline]
+    pub fn begin_transform_feedback_with_counters<I, C>(self, counter_buffers: I)
+                                                        -> Result<Self, BeginTransformFeedbackError>
+        where I: IntoIterator<Item = C>,
+              C: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        let counter_buffers = counter_buffers
+            .into_iter()
+            .map(|buffer| Box::new(buffer) as Box<BufferAccess + Send + Sync>)
+            .collect::<Vec<_>>();
+
+        unsafe { self.begin_transform_feedback_impl(counter_buffers) }
+    }
+
+    unsafe fn begin_transform_feedback_impl(mut self,
+                                            counter_buffers: Vec<Box<BufferAccess + Send + Sync>>)
+                                            -> Result<Self, BeginTransformFeedbackError> {
+        if !self.graphics_allowed {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        if self.render_pass.is_none() {
+            return Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass.into());
+        }
+
+        if self.transform_feedback {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+        }
+
+        check_transform_feedback_counter_buffers(self.device(), &counter_buffers)?;
+
+        self.inner.begin_transform_feedback(counter_buffers)?;
+        self.transform_feedback = true;
+        Ok(self)
+    }
+
+    /// Adds a command that binds descriptor sets for the pipelines of `bind_point`.
+    ///
+    /// The sets are bound starting at set 0, using the layout of `pipeline_layout`. They stay
+    /// bound when drawing or dispatching with a pipeline whose layout is compatible with
+    /// `pipeline_layout` for these sets, in which case they are not bound again.
+    ///
+    /// > **Note**: The draw and dispatch commands bind the sets they receive, so you don't need
+    /// > to call this function before them.
+    pub fn bind_descriptor_sets<Pl, S>(mut self, bind_point: PipelineBindPoint,
+                                       pipeline_layout: Pl, sets: S)
+                                       -> Result<Self, BindDescriptorSetsError>
+        where Pl: PipelineLayoutAbstract + Send + Sync + Clone + 'static,
+              S: DescriptorSetsCollection
+    {
+        unsafe {
+            let allowed = match bind_point {
+                PipelineBindPoint::Graphics => self.graphics_allowed,
+                PipelineBindPoint::Compute |
+                PipelineBindPoint::RayTracing => self.compute_allowed,
+            };
+            if !allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                               .into());
+            }
+
+            check_descriptor_sets_validity(&pipeline_layout, &sets)?;
+
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            bind_point,
+                            pipeline_layout,
+                            sets)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that binds a compute pipeline.
+    ///
+    /// > **Note**: The dispatch commands bind the pipeline they receive, so you don't need to call
+    /// > this function before them.
+    pub fn bind_pipeline_compute<Cp>(mut self, pipeline: Cp)
+                                     -> Result<Self, AutoCommandBufferBuilderContextError>
+        where Cp: ComputePipelineAbstract + Send + Sync + 'static
+    {
+        unsafe {
+            if !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+            }
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_compute_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_compute(pipeline);
+            }
+
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that binds a graphics pipeline.
+    ///
+    /// > **Note**: The draw commands bind the pipeline they receive, so you don't need to call
+    /// > this function before them.
+    pub fn bind_pipeline_graphics<Gp>(mut self, pipeline: Gp)
+                                      -> Result<Self, AutoCommandBufferBuilderContextError>
+        where Gp: GraphicsPipelineAbstract + Send + Sync + 'static
+    {
+        unsafe {
+            if !self.graphics_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily);
+            }
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_graphics_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_graphics(pipeline);
+            }
+
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that binds buffers that will receive the output of transform feedback.
+    ///
+    /// The buffers are bound to the bindings starting at `first_binding`, and the whole of each
+    /// buffer is used. This can't be called while transform feedback is active.
+    ///
+    /// Requires the `ext_transform_feedback` extension.
+    pub fn bind_transform_feedback_buffers<I, B>(mut self, first_binding: u32, buffers: I)
+                                                 -> Result<Self, BindTransformFeedbackBuffersError>
+        where I: IntoIterator<Item = B>,
+              B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            if !self.graphics_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                               .into());
+            }
+
+            if self.transform_feedback {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive
+                               .into());
+            }
+
+            let buffers = buffers
+                .into_iter()
+                .map(|buffer| Box::new(buffer) as Box<BufferAccess + Send + Sync>)
+                .collect::<Vec<_>>();
+            check_transform_feedback_buffers(self.device(), first_binding, &buffers)?;
+
+            self.inner.bind_transform_feedback_buffers(first_binding, buffers)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that binds the vertex buffers of `vertices`, decoded the way `pipeline`
+    /// expects them.
+    ///
+    /// > **Note**: The draw commands bind the vertex buffers they receive, so you don't need to
+    /// > call this function before them.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the vertex buffers was not created with the same device as `pipeline`.
+    ///
+    pub fn bind_vertex_buffers<Gp, V>(mut self, pipeline: &Gp, vertices: V)
+                                      -> Result<Self, BindVertexBuffersError>
+        where Gp: GraphicsPipelineAbstract + VertexSource<V>
+    {
+        unsafe {
+            if !self.graphics_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                               .into());
+            }
+
+            let vb_infos = check_vertex_buffers(pipeline, vertices)?;
+            vertex_buffers(&mut self.inner, &mut self.state_cacher, vb_infos.vertex_buffers)?;
+            Ok(self)
+        }
+    }
+
     /// Adds a command that copies an image to another.
     ///
     /// Copy operations have several restrictions:
@@ -737,13 +1085,43 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
-    /// Adds a command that clears all the layers and mipmap levels of a color image with a
-    /// specific value.
+    /// Adds a command that builds `structure` from `geometries`.
     ///
-    /// # Panic
+    /// A top-level structure must be built from a single geometry of instances, and a
+    /// bottom-level structure from geometries of triangles or bounding boxes. `scratch` is used
+    /// as temporary memory during the build, and its size must be at least the one returned by
+    /// `AccelerationStructure::build_sizes` plus `min_scratch_offset_alignment`.
     ///
-    /// Panics if `color` is not a color value.
+    /// > **Note**: The bottom-level structures referenced by the instances of a top-level
+    /// > structure aren't kept alive or synchronized by this command.
+    pub fn build_acceleration_structure<S>(mut self, structure: Arc<AccelerationStructure>,
+                                           geometries: Vec<AccelerationStructureGeometry>,
+                                           flags: BuildAccelerationStructureFlags, scratch: S)
+                                           -> Result<Self, BuildAccelerationStructureError>
+        where S: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            if !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+            let scratch_address = check_build_acceleration_structure(self.device(),
+                                                                     &structure,
+                                                                     &geometries,
+                                                                     flags,
+                                                                     &scratch)?;
+            self.inner
+                .build_acceleration_structure(structure, geometries, flags, scratch,
+                                              scratch_address)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that clears all the layers and mipmap levels of a color image with a
+    /// specific value.
     ///
+    /// `color` must be a `Float`, `Int` or `Uint` value that matches the format of the image.
     pub fn clear_color_image<I>(self, image: I, color: ClearValue)
                                 -> Result<Self, ClearColorImageError>
         where I: ImageAccess + Send + Sync + 'static
@@ -756,10 +1134,7 @@ impl<P> AutoCommandBufferBuilder<P> {
 
     /// Adds a command that clears a color image with a specific value.
     ///
-    /// # Panic
-    ///
-    /// - Panics if `color` is not a color value.
-    ///
+    /// `color` must be a `Float`, `Int` or `Uint` value that matches the format of the image.
     pub fn clear_color_image_dimensions<I>(mut self, image: I, first_layer: u32, num_layers: u32,
                                            first_mipmap: u32, num_mipmaps: u32, color: ClearValue)
                                            -> Result<Self, ClearColorImageError>
@@ -776,14 +1151,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                                     first_layer,
                                     num_layers,
                                     first_mipmap,
-                                    num_mipmaps)?;
-
-            match color {
-                ClearValue::Float(_) |
-                ClearValue::Int(_) |
-                ClearValue::Uint(_) => {},
-                _ => panic!("The clear color is not a color value"),
-            };
+                                    num_mipmaps,
+                                    color)?;
 
             let region = UnsafeCommandBufferBuilderColorImageClear {
                 base_mip_level: first_mipmap,
@@ -946,6 +1315,36 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that copies the results of a range of queries to a buffer.
+    ///
+    /// The results are written as tightly-packed values of type `T`, which can be `u32` or `u64`.
+    /// If `flags.with_availability` is true, an additional value is written after the result of
+    /// each query.
+    pub fn copy_query_pool_results<D, T>(mut self, query_pool: Arc<UnsafeQueryPool>,
+                                         first_query: u32, query_count: u32, destination: D,
+                                         flags: QueryResultFlags)
+                                         -> Result<Self, CopyQueryPoolResultsError>
+        where D: TypedBufferAccess<Content = [T]> + Send + Sync + 'static,
+              T: QueryResultElement
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            let stride = check_copy_query_pool_results(self.device(),
+                                                       &query_pool,
+                                                       first_query,
+                                                       query_count,
+                                                       &destination,
+                                                       flags)?;
+            self.inner.copy_query_pool_results(query_pool,
+                                               first_query,
+                                               query_count,
+                                               destination,
+                                               stride,
+                                               flags)?;
+            Ok(self)
+        }
+    }
+
     #[inline]
     pub fn dispatch<Cp, S, Pc>(mut self, dimensions: [u32; 3], pipeline: Cp, sets: S, constants: Pc)
                                -> Result<Self, DispatchError>
@@ -971,7 +1370,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             push_constants(&mut self.inner, pipeline.clone(), constants);
             descriptor_sets(&mut self.inner,
                             &mut self.state_cacher,
-                            false,
+                            PipelineBindPoint::Compute,
                             pipeline.clone(),
                             sets)?;
 
@@ -991,6 +1390,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             // TODO: must check that pipeline is compatible with render pass
 
             self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
             check_dynamic_state_validity(&pipeline, &dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
             check_descriptor_sets_validity(&pipeline, &sets)?;
@@ -1008,7 +1410,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             set_state(&mut self.inner, dynamic);
             descriptor_sets(&mut self.inner,
                             &mut self.state_cacher,
-                            true,
+                            PipelineBindPoint::Graphics,
                             pipeline.clone(),
                             sets)?;
             vertex_buffers(&mut self.inner,
@@ -1038,6 +1440,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             // TODO: must check that pipeline is compatible with render pass
 
             self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
             let ib_infos = check_index_buffer(self.device(), &index_buffer)?;
             check_dynamic_state_validity(&pipeline, &dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
@@ -1062,7 +1467,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             set_state(&mut self.inner, dynamic);
             descriptor_sets(&mut self.inner,
                             &mut self.state_cacher,
-                            true,
+                            PipelineBindPoint::Graphics,
                             pipeline.clone(),
                             sets)?;
             vertex_buffers(&mut self.inner,
@@ -1078,6 +1483,83 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Same as `draw_indexed`, except that the draws are described by `indirect_buffer`, and
+    /// that the number of draws is read from `count_buffer` by the device.
+    ///
+    /// At most `indirect_buffer.len()` draws are performed, even if the value in `count_buffer` is
+    /// larger. This allows for example submitting in one command a list of draws that was culled
+    /// by a compute shader.
+    ///
+    /// Requires the `khr_draw_indirect_count` extension, or a Vulkan 1.2 device that supports the
+    /// `drawIndirectCount` feature. See `Device::supports_draw_indirect_count`.
+    #[inline]
+    pub fn draw_indexed_indirect_count<V, Gp, S, Pc, Ib, I, Inb, Cb>(
+        mut self, pipeline: Gp, dynamic: DynamicState, vertices: V, index_buffer: Ib,
+        indirect_buffer: Inb, count_buffer: Cb, sets: S, constants: Pc)
+        -> Result<Self, DrawIndexedIndirectCountError>
+        where Gp: GraphicsPipelineAbstract + VertexSource<V> + Send + Sync + 'static + Clone, // TODO: meh for Clone
+              S: DescriptorSetsCollection,
+              Ib: BufferAccess + TypedBufferAccess<Content = [I]> + Send + Sync + 'static,
+              I: Index + 'static,
+              Inb: BufferAccess
+                       + TypedBufferAccess<Content = [DrawIndexedIndirectCommand]>
+                       + Send
+                       + Sync
+                       + 'static,
+              Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
+            check_index_buffer(self.device(), &index_buffer)?;
+            check_dynamic_state_validity(&pipeline, &dynamic)?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+            let vb_infos = check_vertex_buffers(&pipeline, vertices)?;
+
+            let max_draw_count = check_indirect_count(self.device(),
+                                                      &indirect_buffer,
+                                                      indirect_buffer.len(),
+                                                      &count_buffer)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_graphics_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_graphics(pipeline.clone());
+            }
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_index_buffer(&index_buffer, I::ty())
+            {
+                self.inner.bind_index_buffer(index_buffer, I::ty())?;
+            }
+
+            let dynamic = self.state_cacher.dynamic_state(dynamic);
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            set_state(&mut self.inner, dynamic);
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            PipelineBindPoint::Graphics,
+                            pipeline.clone(),
+                            sets)?;
+            vertex_buffers(&mut self.inner,
+                           &mut self.state_cacher,
+                           vb_infos.vertex_buffers)?;
+
+            debug_assert!(self.graphics_allowed);
+
+            self.inner
+                .draw_indexed_indirect_count(indirect_buffer,
+                                             count_buffer,
+                                             max_draw_count,
+                                             mem::size_of::<DrawIndexedIndirectCommand>() as u32)?;
+            Ok(self)
+        }
+    }
+
     #[inline]
     pub fn draw_indirect<V, Gp, S, Pc, Ib>(mut self, pipeline: Gp, dynamic: DynamicState,
                                            vertices: V, indirect_buffer: Ib, sets: S, constants: Pc)
@@ -1094,12 +1576,16 @@ impl<P> AutoCommandBufferBuilder<P> {
             // TODO: must check that pipeline is compatible with render pass
 
             self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
             check_dynamic_state_validity(&pipeline, &dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
             check_descriptor_sets_validity(&pipeline, &sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertices)?;
 
             let draw_count = indirect_buffer.len() as u32;
+            check_indirect_buffer(self.device(), &indirect_buffer, draw_count)?;
 
             if let StateCacherOutcome::NeedChange =
                 self.state_cacher.bind_graphics_pipeline(&pipeline)
@@ -1113,7 +1599,7 @@ impl<P> AutoCommandBufferBuilder<P> {
             set_state(&mut self.inner, dynamic);
             descriptor_sets(&mut self.inner,
                             &mut self.state_cacher,
-                            true,
+                            PipelineBindPoint::Graphics,
                             pipeline.clone(),
                             sets)?;
             vertex_buffers(&mut self.inner,
@@ -1130,6 +1616,176 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Same as `draw_indirect`, except that the number of draws is read from `count_buffer` by
+    /// the device.
+    ///
+    /// At most `indirect_buffer.len()` draws are performed, even if the value in `count_buffer` is
+    /// larger. This allows for example submitting in one command a list of draws that was culled
+    /// by a compute shader.
+    ///
+    /// Requires the `khr_draw_indirect_count` extension, or a Vulkan 1.2 device that supports the
+    /// `drawIndirectCount` feature. See `Device::supports_draw_indirect_count`.
+    #[inline]
+    pub fn draw_indirect_count<V, Gp, S, Pc, Ib, Cb>(mut self, pipeline: Gp, dynamic: DynamicState,
+                                                     vertices: V, indirect_buffer: Ib,
+                                                     count_buffer: Cb, sets: S, constants: Pc)
+                                                     -> Result<Self, DrawIndirectCountError>
+        where Gp: GraphicsPipelineAbstract + VertexSource<V> + Send + Sync + 'static + Clone, // TODO: meh for Clone
+              S: DescriptorSetsCollection,
+              Ib: BufferAccess
+                      + TypedBufferAccess<Content = [DrawIndirectCommand]>
+                      + Send
+                      + Sync
+                      + 'static,
+              Cb: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
+            check_dynamic_state_validity(&pipeline, &dynamic)?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+            let vb_infos = check_vertex_buffers(&pipeline, vertices)?;
+
+            let max_draw_count = check_indirect_count(self.device(),
+                                                      &indirect_buffer,
+                                                      indirect_buffer.len(),
+                                                      &count_buffer)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_graphics_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_graphics(pipeline.clone());
+            }
+
+            let dynamic = self.state_cacher.dynamic_state(dynamic);
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            set_state(&mut self.inner, dynamic);
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            PipelineBindPoint::Graphics,
+                            pipeline.clone(),
+                            sets)?;
+            vertex_buffers(&mut self.inner,
+                           &mut self.state_cacher,
+                           vb_infos.vertex_buffers)?;
+
+            debug_assert!(self.graphics_allowed);
+
+            self.inner
+                .draw_indirect_count(indirect_buffer,
+                                     count_buffer,
+                                     max_draw_count,
+                                     mem::size_of::<DrawIndirectCommand>() as u32)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a draw command whose number of vertices is deduced from the number of bytes that a
+    /// previous transform feedback wrote, as stored in `counter_buffer`.
+    ///
+    /// `counter_offset` is subtracted from the byte count, and the result is divided by
+    /// `vertex_stride` to obtain the number of vertices.
+    ///
+    /// Requires the `ext_transform_feedback` extension and the `transform_feedback_draw` property
+    /// of the device.
+    #[inline]
+    pub fn draw_indirect_byte_count<V, Gp, S, Pc, C>(mut self, pipeline: Gp, dynamic: DynamicState,
+                                                     vertices: V, counter_buffer: C,
+                                                     counter_offset: u32, vertex_stride: u32,
+                                                     sets: S, constants: Pc)
+                                                     -> Result<Self, DrawIndirectByteCountError>
+        where Gp: GraphicsPipelineAbstract + VertexSource<V> + Send + Sync + 'static + Clone, // TODO: meh for Clone
+              S: DescriptorSetsCollection,
+              C: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_inside_render_pass_inline(&pipeline)?;
+            if pipeline.uses_mesh_shading() {
+                return Err(AutoCommandBufferBuilderContextError::MeshShadingPipeline.into());
+            }
+            check_dynamic_state_validity(&pipeline, &dynamic)?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+            let vb_infos = check_vertex_buffers(&pipeline, vertices)?;
+            check_draw_indirect_byte_count(self.device(), &counter_buffer, vertex_stride)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_graphics_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_graphics(pipeline.clone());
+            }
+
+            let dynamic = self.state_cacher.dynamic_state(dynamic);
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            set_state(&mut self.inner, dynamic);
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            PipelineBindPoint::Graphics,
+                            pipeline.clone(),
+                            sets)?;
+            vertex_buffers(&mut self.inner,
+                           &mut self.state_cacher,
+                           vb_infos.vertex_buffers)?;
+
+            debug_assert!(self.graphics_allowed);
+
+            self.inner
+                .draw_indirect_byte_count(vb_infos.instance_count as u32,
+                                          0,
+                                          counter_buffer,
+                                          counter_offset,
+                                          vertex_stride)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that launches `group_counts[0] * group_counts[1] * group_counts[2]`
+    /// workgroups with a pipeline that uses mesh shading.
+    ///
+    /// The workgroups are task shader workgroups if the pipeline has a task shader, and mesh
+    /// shader workgroups otherwise.
+    #[inline]
+    pub fn draw_mesh_tasks<Gp, S, Pc>(mut self, pipeline: Gp, dynamic: DynamicState,
+                                      group_counts: [u32; 3], sets: S, constants: Pc)
+                                      -> Result<Self, DrawMeshTasksError>
+        where Gp: GraphicsPipelineAbstract + Send + Sync + 'static + Clone, // TODO: meh for Clone
+              S: DescriptorSetsCollection
+    {
+        unsafe {
+            self.ensure_inside_render_pass_inline(&pipeline)?;
+            check_dynamic_state_validity(&pipeline, &dynamic)?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_draw_mesh_tasks(self.device(), &pipeline, group_counts)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_graphics_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_graphics(pipeline.clone());
+            }
+
+            let dynamic = self.state_cacher.dynamic_state(dynamic);
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            set_state(&mut self.inner, dynamic);
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            PipelineBindPoint::Graphics,
+                            pipeline.clone(),
+                            sets)?;
+
+            debug_assert!(self.graphics_allowed);
+
+            self.inner.draw_mesh_tasks(group_counts);
+            Ok(self)
+        }
+    }
+
     /// Adds a command that ends the current render pass.
     ///
     /// This must be called after you went through all the subpasses and before you can build
@@ -1154,6 +1810,14 @@ impl<P> AutoCommandBufferBuilder<P> {
                 },
             }
 
+            if self.conditional_rendering == Some(true) {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+            }
+
+            if self.transform_feedback {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+            }
+
             debug_assert!(self.graphics_allowed);
 
             self.inner.end_render_pass();
@@ -1162,8 +1826,94 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that ends conditional rendering, which was previously begun with
+    /// `begin_conditional_rendering`.
+    #[inline]
+    pub fn end_conditional_rendering(mut self)
+                                     -> Result<Self, AutoCommandBufferBuilderContextError> {
+        unsafe {
+            match self.conditional_rendering {
+                None => {
+                    return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingNotActive);
+                },
+                Some(false) if self.render_pass.is_some() => {
+                    return Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass);
+                },
+                Some(_) => (),
+            }
+
+            self.inner.end_conditional_rendering();
+            self.conditional_rendering = None;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that ends a query that was previously begun with `begin_query`.
+    pub fn end_query(mut self, query_pool: Arc<UnsafeQueryPool>, query: u32)
+                     -> Result<Self, EndQueryError> {
+        unsafe {
+            check_end_query(self.device(), &query_pool, query)?;
+
+            let handle = query_pool.internal_object();
+            let position = match self.active_queries
+                .iter()
+                .position(|&(p, q, _)| p == handle && q == query) {
+                Some(p) => p,
+                None => return Err(AutoCommandBufferBuilderContextError::QueryNotActive.into()),
+            };
+
+            self.active_queries.remove(position);
+            self.inner.end_query(query_pool, query);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that ends transform feedback, which was previously begun with
+    /// `begin_transform_feedback` or `begin_transform_feedback_with_counters`.
+    #[inline]
+    pub fn end_transform_feedback(self) -> Result<Self, EndTransformFeedbackError> {
+        unsafe { self.end_transform_feedback_impl(Vec::new()) }
+    }
+
+    /// Same as `end_transform_feedback`, but also writes to `counter_buffers` the byte offsets
+    /// at which the next transform feedback writes would have happened.
+    ///
+    /// The counter buffer at index `n` corresponds to the transform feedback buffer bound at
+    /// index `n`. The counters can later be passed to `begin_transform_feedback_with_counters`
+    /// or `draw_indirect_byte_count`.
+    #[inline]
+    pub fn end_transform_feedback_with_counters<I, C>(self, counter_buffers: I)
+                                                      -> Result<Self, EndTransformFeedbackError>
+        where I: IntoIterator<Item = C>,
+              C: TypedBufferAccess<Content = u32> + Send + Sync + 'static
+    {
+        let counter_buffers = counter_buffers
+            .into_iter()
+            .map(|buffer| Box::new(buffer) as Box<BufferAccess + Send + Sync>)
+            .collect::<Vec<_>>();
+
+        unsafe { self.end_transform_feedback_impl(counter_buffers) }
+    }
+
+    unsafe fn end_transform_feedback_impl(mut self,
+                                          counter_buffers: Vec<Box<BufferAccess + Send + Sync>>)
+                                          -> Result<Self, EndTransformFeedbackError> {
+        if !self.transform_feedback {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackNotActive.into());
+        }
+
+        check_transform_feedback_counter_buffers(self.device(), &counter_buffers)?;
+
+        self.inner.end_transform_feedback(counter_buffers)?;
+        self.transform_feedback = false;
+        Ok(self)
+    }
+
     /// Adds a command that executes a secondary command buffer.
     ///
+    /// This can only be called from a primary command buffer, either outside of a render pass or
+    /// inside a subpass that was entered with `secondary` set to true.
+    ///
     /// **This function is unsafe for now because safety checks and synchronization are not
     /// implemented.**
     // TODO: implement correctly
@@ -1171,6 +1921,22 @@ impl<P> AutoCommandBufferBuilder<P> {
                                       -> Result<Self, ExecuteCommandsError>
         where C: CommandBuffer + Send + Sync + 'static
     {
+        if self.secondary_cb {
+            return Err(AutoCommandBufferBuilderContextError::ForbiddenInSecondary.into());
+        }
+
+        if self.render_pass.is_some() {
+            self.ensure_inside_render_pass_secondary()?;
+        }
+
+        if self.conditional_rendering.is_some() {
+            return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive.into());
+        }
+
+        if self.transform_feedback {
+            return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive.into());
+        }
+
         {
             let mut builder = self.inner.execute_commands();
             builder.add(command_buffer);
@@ -1229,6 +1995,14 @@ impl<P> AutoCommandBufferBuilder<P> {
                 },
             };
 
+            if self.conditional_rendering == Some(true) {
+                return Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive);
+            }
+
+            if self.transform_feedback {
+                return Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive);
+            }
+
             self.subpass_secondary = secondary;
 
             debug_assert!(self.graphics_allowed);
@@ -1243,6 +2017,234 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a pipeline barrier that applies to a range of a buffer.
+    ///
+    /// The barriers that are needed between the commands of this builder are added
+    /// automatically. This function is only needed if you want more control over the
+    /// synchronization. If `queue_family_transfer` is `Some`, the barrier is one of the halves of
+    /// a queue family ownership transfer.
+    ///
+    /// Can only be used outside of a render pass.
+    #[inline]
+    pub fn buffer_memory_barrier<B>(mut self, barrier: BufferMemoryBarrier<B>)
+                                    -> Result<Self, PipelineBarrierError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            check_buffer_memory_barrier(self.device(), self.queue_family, &barrier)?;
+            self.inner.buffer_memory_barrier(barrier)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a pipeline barrier that applies to a range of an image.
+    ///
+    /// This is the equivalent of `buffer_memory_barrier` but for images. The image is
+    /// automatically transitioned to the layout of the barrier if necessary.
+    ///
+    /// Can only be used outside of a render pass.
+    #[inline]
+    pub fn image_memory_barrier<I>(mut self, barrier: ImageMemoryBarrier<I>)
+                                   -> Result<Self, PipelineBarrierError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            check_image_memory_barrier(self.device(), self.queue_family, &barrier)?;
+            self.inner.image_memory_barrier(barrier)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a pipeline barrier that applies to all the resources.
+    ///
+    /// Can only be used outside of a render pass.
+    #[inline]
+    pub fn memory_barrier(mut self, barrier: MemoryBarrier) -> Result<Self, PipelineBarrierError> {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            check_memory_barrier(self.device(), &barrier)?;
+            self.inner.memory_barrier(barrier);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that pushes the descriptors of `descriptors` to its set, instead of
+    /// binding a descriptor set.
+    ///
+    /// The layout of the set must have been created with
+    /// `UnsafeDescriptorSetLayout::push_descriptor`, which requires the `khr_push_descriptor`
+    /// extension. Copies from other descriptor sets can't be pushed.
+    pub fn push_descriptor_set<L, R>(mut self, bind_point: PipelineBindPoint,
+                                     descriptors: PersistentDescriptorSetBuilder<L, R>)
+                                     -> Result<Self, PushDescriptorSetError>
+        where L: PipelineLayoutAbstract + Send + Sync + 'static,
+              R: PersistentDescriptorSetResources + Send + Sync + 'static
+    {
+        unsafe {
+            let allowed = match bind_point {
+                PipelineBindPoint::Graphics => self.graphics_allowed,
+                PipelineBindPoint::Compute |
+                PipelineBindPoint::RayTracing => self.compute_allowed,
+            };
+            if !allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily
+                               .into());
+            }
+
+            check_push_descriptor_set(self.device(), &descriptors)?;
+
+            let set_num = descriptors.set_id() as u32;
+            let (layout, writes, resources) = descriptors.into_writes_and_resources();
+            self.inner
+                .push_descriptor_set(bind_point, layout, set_num, writes, resources)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that releases the ownership of a buffer to another queue family.
+    ///
+    /// This is the first half of a queue family ownership transfer, and is only needed for
+    /// buffers that were created with `Sharing::Exclusive`. Afterwards the buffer can't be used
+    /// by this queue family anymore, and must be acquired by a command buffer built with
+    /// `acquire_buffer_ownership` and executed on a queue of `destination`.
+    ///
+    /// See also `transfer_buffer_ownership`, which takes care of both halves and of the
+    /// synchronization between the two queues.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `destination` doesn't belong to the same physical device as the command
+    ///   buffer.
+    ///
+    #[inline]
+    pub fn release_buffer_ownership<B>(mut self, buffer: B, destination: QueueFamily)
+                                       -> Result<Self, OwnershipTransferError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            assert_eq!(destination.physical_device().internal_object(),
+                       self.device().physical_device().internal_object());
+            self.ensure_outside_render_pass()?;
+            let source = self.queue_family;
+            check_buffer_ownership_transfer(self.device(), &buffer, source, destination.id())?;
+            self.inner
+                .buffer_ownership_transfer(buffer, source, destination.id(), true)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that releases the ownership of an image to another queue family.
+    ///
+    /// This is the equivalent of `release_buffer_ownership` but for images. The image is
+    /// transitioned to the layout returned by `final_layout_requirement()` before being released.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `destination` doesn't belong to the same physical device as the command
+    ///   buffer.
+    ///
+    #[inline]
+    pub fn release_image_ownership<I>(mut self, image: I, destination: QueueFamily)
+                                      -> Result<Self, OwnershipTransferError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        unsafe {
+            assert_eq!(destination.physical_device().internal_object(),
+                       self.device().physical_device().internal_object());
+            self.ensure_outside_render_pass()?;
+            let source = self.queue_family;
+            check_image_ownership_transfer(self.device(), &image, source, destination.id())?;
+            let layout = image.final_layout_requirement();
+            self.inner
+                .image_ownership_transfer(image, layout, source, destination.id(), true)?;
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that resets a range of queries of a query pool.
+    ///
+    /// Queries must be reset before they can be used again. Resetting a query that is active is
+    /// forbidden.
+    pub fn reset_query_pool(mut self, query_pool: Arc<UnsafeQueryPool>, first_query: u32,
+                            query_count: u32)
+                            -> Result<Self, ResetQueryPoolError> {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            check_reset_query_pool(self.device(), &query_pool, first_query, query_count)?;
+
+            let handle = query_pool.internal_object();
+            if self.active_queries.iter().any(|&(p, q, _)| {
+                p == handle && q >= first_query && q < first_query + query_count
+            })
+            {
+                return Err(AutoCommandBufferBuilderContextError::QueryIsActive.into());
+            }
+
+            self.inner
+                .reset_query_pool(query_pool, first_query, query_count);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that sets the physical devices of the device group that execute the
+    /// following commands.
+    ///
+    /// Bit `n` of the mask corresponds to the physical device at index `n` of the group that was
+    /// passed to `Device::with_group`. The initial device mask of a command buffer contains all
+    /// the physical devices of the group. This can be used for example to render alternate frames
+    /// or different parts of the same frame on different GPUs.
+    #[inline]
+    pub fn set_device_mask(mut self, device_mask: u32) -> Result<Self, SetDeviceMaskError> {
+        unsafe {
+            check_device_mask(self.device(), device_mask)?;
+            self.inner.set_device_mask(device_mask);
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that traces `dimensions[0] * dimensions[1] * dimensions[2]` rays with a
+    /// ray tracing pipeline.
+    ///
+    /// The shaders that are invoked are determined by `shader_binding_table`, which must have
+    /// been created from `pipeline`.
+    #[inline]
+    pub fn trace_rays<Rp, S, Pc>(mut self, pipeline: Rp,
+                                 shader_binding_table: Arc<ShaderBindingTable>,
+                                 dimensions: [u32; 3], sets: S, constants: Pc)
+                                 -> Result<Self, TraceRaysError>
+        where Rp: RayTracingPipelineAbstract + Send + Sync + 'static + Clone,
+              S: DescriptorSetsCollection
+    {
+        unsafe {
+            if !self.compute_allowed {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            self.ensure_outside_render_pass()?;
+            check_push_constants_validity(&pipeline, &constants)?;
+            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_trace_rays(pipeline.device(), dimensions)?;
+
+            if let StateCacherOutcome::NeedChange =
+                self.state_cacher.bind_ray_tracing_pipeline(&pipeline)
+            {
+                self.inner.bind_pipeline_ray_tracing(pipeline.clone());
+            }
+
+            push_constants(&mut self.inner, pipeline.clone(), constants);
+            descriptor_sets(&mut self.inner,
+                            &mut self.state_cacher,
+                            PipelineBindPoint::RayTracing,
+                            pipeline.clone(),
+                            sets)?;
+
+            self.inner.trace_rays(shader_binding_table, dimensions)?;
+            Ok(self)
+        }
+    }
+
     /// Adds a command that writes data to a buffer.
     ///
     /// If `data` is larger than the buffer, only the part of `data` that fits is written. If the
@@ -1268,6 +2270,28 @@ impl<P> AutoCommandBufferBuilder<P> {
             Ok(self)
         }
     }
+
+    /// Adds a command that writes a timestamp to a query once all the previous commands have
+    /// reached the given pipeline stage.
+    ///
+    /// `stage` must contain exactly one pipeline stage, and the query pool must be a timestamp
+    /// query pool.
+    ///
+    /// # Safety
+    ///
+    /// The query must be unavailable, which is ensured by resetting it with `reset_query_pool`
+    /// beforehand.
+    pub unsafe fn write_timestamp(mut self, query_pool: Arc<UnsafeQueryPool>, query: u32,
+                                  stage: PipelineStages)
+                                  -> Result<Self, WriteTimestampError> {
+        if !self.timestamps_allowed {
+            return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+        }
+
+        check_write_timestamp(self.device(), &query_pool, query, stage)?;
+        self.inner.write_timestamp(query_pool, query, stage);
+        Ok(self)
+    }
 }
 
 unsafe impl<P> DeviceOwned for AutoCommandBufferBuilder<P> {
@@ -1277,6 +2301,18 @@ unsafe impl<P> DeviceOwned for AutoCommandBufferBuilder<P> {
     }
 }
 
+// Returns true if two queries are of the same type, in which case they can't be active at the
+// same time.
+#[inline]
+fn same_query_type(a: QueryType, b: QueryType) -> bool {
+    match (a, b) {
+        (QueryType::Occlusion, QueryType::Occlusion) => true,
+        (QueryType::PipelineStatistics(_), QueryType::PipelineStatistics(_)) => true,
+        (QueryType::Timestamp, QueryType::Timestamp) => true,
+        _ => false,
+    }
+}
+
 // Shortcut function to set the push constants.
 unsafe fn push_constants<P, Pl, Pc>(destination: &mut SyncCommandBufferBuilder<P>, pipeline: Pl,
                                     push_constants: Pc)
@@ -1350,8 +2386,8 @@ unsafe fn vertex_buffers<P>(destination: &mut SyncCommandBufferBuilder<P>,
 }
 
 unsafe fn descriptor_sets<P, Pl, S>(destination: &mut SyncCommandBufferBuilder<P>,
-                                    state_cacher: &mut StateCacher, gfx: bool, pipeline: Pl,
-                                    sets: S)
+                                    state_cacher: &mut StateCacher,
+                                    bind_point: PipelineBindPoint, pipeline: Pl, sets: S)
                                     -> Result<(), SyncCommandBufferBuilderError>
     where Pl: PipelineLayoutAbstract + Send + Sync + Clone + 'static,
           S: DescriptorSetsCollection
@@ -1359,7 +2395,7 @@ unsafe fn descriptor_sets<P, Pl, S>(destination: &mut SyncCommandBufferBuilder<P
     let sets = sets.into_vec();
 
     let first_binding = {
-        let mut compare = state_cacher.bind_descriptor_sets(gfx);
+        let mut compare = state_cacher.bind_descriptor_sets(bind_point, &pipeline);
         for set in sets.iter() {
             compare.add(set);
         }
@@ -1371,12 +2407,14 @@ unsafe fn descriptor_sets<P, Pl, S>(destination: &mut SyncCommandBufferBuilder<P
         Some(fb) => fb,
     };
 
+    let mut dynamic_offsets = Vec::new();
     let mut sets_binder = destination.bind_descriptor_sets();
     for set in sets.into_iter().skip(first_binding as usize) {
+        dynamic_offsets.extend(DescriptorSet::dynamic_offsets(&set).iter().cloned());
         sets_binder.add(set);
     }
     sets_binder
-        .submit(gfx, pipeline.clone(), first_binding, iter::empty())?;
+        .submit(bind_point, pipeline.clone(), first_binding, dynamic_offsets.into_iter())?;
     Ok(())
 }
 
@@ -1408,6 +2446,47 @@ enum SubmitState {
     },
 }
 
+impl AutoCommandBuffer<StandardCommandPoolAlloc> {
+    /// Records a one-time-submit command buffer, submits it to `queue` and signals a fence.
+    ///
+    /// The closure receives a builder for a primary command buffer of the queue's family and
+    /// must return it after adding its commands. The returned future has already been flushed,
+    /// which means that you can immediately wait on it. This is convenient for ad-hoc uploads and
+    /// initialization work.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use vulkano::buffer::BufferUsage;
+    /// # use vulkano::buffer::CpuAccessibleBuffer;
+    /// # use vulkano::command_buffer::AutoCommandBuffer;
+    /// # use vulkano::sync::GpuFuture;
+    /// # let device: std::sync::Arc<vulkano::device::Device> = return;
+    /// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+    /// let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+    ///     .unwrap();
+    ///
+    /// AutoCommandBuffer::one_time_submit(queue.clone(), |builder| {
+    ///     builder.fill_buffer(buffer.clone(), 12)
+    /// }).unwrap().wait(None).unwrap();
+    /// ```
+    pub fn one_time_submit<F, E>(queue: Arc<Queue>, record: F)
+                                 -> Result<FenceSignalFuture<CommandBufferExecFuture<NowFuture,
+                                                                                     AutoCommandBuffer>>,
+                                           OneTimeSubmitError<E>>
+        where F: FnOnce(AutoCommandBufferBuilder) -> Result<AutoCommandBufferBuilder, E>
+    {
+        let builder = AutoCommandBufferBuilder::primary_one_time_submit(queue.device().clone(),
+                                                                        queue.family())?;
+        let command_buffer = match record(builder) {
+            Ok(builder) => builder.build()?,
+            Err(err) => return Err(OneTimeSubmitError::RecordError(err)),
+        };
+
+        Ok(command_buffer.execute(queue)?.then_signal_fence_and_flush()?)
+    }
+}
+
 unsafe impl<P> CommandBuffer for AutoCommandBuffer<P> {
     type PoolAlloc = P;
 
@@ -1493,6 +2572,82 @@ unsafe impl<P> DeviceOwned for AutoCommandBuffer<P> {
     }
 }
 
+/// Future returned by `transfer_buffer_ownership` and `transfer_image_ownership`.
+pub type OwnershipTransferFuture<F> =
+    CommandBufferExecFuture<SemaphoreSignalFuture<CommandBufferExecFuture<F, AutoCommandBuffer>>,
+                            AutoCommandBuffer>;
+
+/// Transfers the ownership of a buffer from the queue family of `source` to the queue family of
+/// `destination`.
+///
+/// After `future`, a command buffer that releases the ownership of the buffer is executed on
+/// `source`. Then a semaphore is signaled, and a command buffer that acquires the ownership of the
+/// buffer is executed on `destination` once the semaphore has been signaled. The returned future
+/// represents the moment when the buffer can be used by `destination`.
+///
+/// This is only needed for buffers created with `Sharing::Exclusive`.
+///
+/// # Panic
+///
+/// - Panics if `source` and `destination` don't belong to the same device.
+///
+pub fn transfer_buffer_ownership<F, B>(future: F, buffer: B, source: Arc<Queue>,
+                                       destination: Arc<Queue>)
+                                       -> Result<OwnershipTransferFuture<F>, TransferOwnershipError>
+    where F: GpuFuture,
+          B: BufferAccess + Clone + Send + Sync + 'static
+{
+    assert_eq!(source.device().internal_object(),
+               destination.device().internal_object());
+
+    let release = AutoCommandBufferBuilder::primary_one_time_submit(source.device().clone(),
+                                                                    source.family())?
+        .release_buffer_ownership(buffer.clone(), destination.family())?
+        .build()?;
+    let acquire = AutoCommandBufferBuilder::primary_one_time_submit(destination.device().clone(),
+                                                                    destination.family())?
+        .acquire_buffer_ownership(buffer, source.family())?
+        .build()?;
+
+    Ok(future
+           .then_execute(source, release)?
+           .then_signal_semaphore()
+           .then_execute(destination, acquire)?)
+}
+
+/// Transfers the ownership of an image from the queue family of `source` to the queue family of
+/// `destination`.
+///
+/// This is the equivalent of `transfer_buffer_ownership` but for images.
+///
+/// # Panic
+///
+/// - Panics if `source` and `destination` don't belong to the same device.
+///
+pub fn transfer_image_ownership<F, I>(future: F, image: I, source: Arc<Queue>,
+                                      destination: Arc<Queue>)
+                                      -> Result<OwnershipTransferFuture<F>, TransferOwnershipError>
+    where F: GpuFuture,
+          I: ImageAccess + Clone + Send + Sync + 'static
+{
+    assert_eq!(source.device().internal_object(),
+               destination.device().internal_object());
+
+    let release = AutoCommandBufferBuilder::primary_one_time_submit(source.device().clone(),
+                                                                    source.family())?
+        .release_image_ownership(image.clone(), destination.family())?
+        .build()?;
+    let acquire = AutoCommandBufferBuilder::primary_one_time_submit(destination.device().clone(),
+                                                                    destination.family())?
+        .acquire_image_ownership(image, source.family())?
+        .build()?;
+
+    Ok(future
+           .then_execute(source, release)?
+           .then_signal_semaphore()
+           .then_execute(destination, acquire)?)
+}
+
 macro_rules! err_gen {
     ($name:ident { $($err:ident,)+ }) => (
         #[derive(Debug, Clone)]
@@ -1542,13 +2697,143 @@ macro_rules! err_gen {
     );
 }
 
+/// Error that can happen when calling `AutoCommandBuffer::one_time_submit`.
+#[derive(Debug, Clone)]
+pub enum OneTimeSubmitError<E> {
+    /// The closure returned an error while recording the commands.
+    RecordError(E),
+    /// Not enough memory.
+    OomError(OomError),
+    /// Error while building the command buffer.
+    BuildError(BuildError),
+    /// Error while executing the command buffer.
+    CommandBufferExecError(CommandBufferExecError),
+    /// Error while submitting the command buffer.
+    FlushError(FlushError),
+}
+
+impl<E> error::Error for OneTimeSubmitError<E>
+    where E: error::Error
+{
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            OneTimeSubmitError::RecordError(_) => {
+                "the closure returned an error while recording the commands"
+            },
+            OneTimeSubmitError::OomError(_) => {
+                "not enough memory"
+            },
+            OneTimeSubmitError::BuildError(_) => {
+                "error while building the command buffer"
+            },
+            OneTimeSubmitError::CommandBufferExecError(_) => {
+                "error while executing the command buffer"
+            },
+            OneTimeSubmitError::FlushError(_) => {
+                "error while submitting the command buffer"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            OneTimeSubmitError::RecordError(ref err) => Some(err),
+            OneTimeSubmitError::OomError(ref err) => Some(err),
+            OneTimeSubmitError::BuildError(ref err) => Some(err),
+            OneTimeSubmitError::CommandBufferExecError(ref err) => Some(err),
+            OneTimeSubmitError::FlushError(ref err) => Some(err),
+        }
+    }
+}
+
+impl<E> fmt::Display for OneTimeSubmitError<E>
+    where E: error::Error
+{
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl<E> From<OomError> for OneTimeSubmitError<E> {
+    #[inline]
+    fn from(err: OomError) -> OneTimeSubmitError<E> {
+        OneTimeSubmitError::OomError(err)
+    }
+}
+
+impl<E> From<BuildError> for OneTimeSubmitError<E> {
+    #[inline]
+    fn from(err: BuildError) -> OneTimeSubmitError<E> {
+        OneTimeSubmitError::BuildError(err)
+    }
+}
+
+impl<E> From<CommandBufferExecError> for OneTimeSubmitError<E> {
+    #[inline]
+    fn from(err: CommandBufferExecError) -> OneTimeSubmitError<E> {
+        OneTimeSubmitError::CommandBufferExecError(err)
+    }
+}
+
+impl<E> From<FlushError> for OneTimeSubmitError<E> {
+    #[inline]
+    fn from(err: FlushError) -> OneTimeSubmitError<E> {
+        OneTimeSubmitError::FlushError(err)
+    }
+}
+
 err_gen!(BuildError {
              AutoCommandBufferBuilderContextError,
              OomError,
          });
 
+err_gen!(BeginQueryError {
+             AutoCommandBufferBuilderContextError,
+             CheckBeginQueryError,
+         });
+
+err_gen!(BeginConditionalRenderingError {
+             AutoCommandBufferBuilderContextError,
+             CheckBeginConditionalRenderingError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(BeginTransformFeedbackError {
+             AutoCommandBufferBuilderContextError,
+             CheckTransformFeedbackCountersError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(BindDescriptorSetsError {
+             AutoCommandBufferBuilderContextError,
+             CheckDescriptorSetsValidityError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(BindTransformFeedbackBuffersError {
+             AutoCommandBufferBuilderContextError,
+             CheckTransformFeedbackBuffersError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(BindVertexBuffersError {
+             AutoCommandBufferBuilderContextError,
+             CheckVertexBufferError,
+             SyncCommandBufferBuilderError,
+         });
+
 err_gen!(BeginRenderPassError {
              AutoCommandBufferBuilderContextError,
+             CheckClearValuesError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(BuildAccelerationStructureError {
+             AutoCommandBufferBuilderContextError,
+             CheckBuildAccelerationStructureError,
              SyncCommandBufferBuilderError,
          });
 
@@ -1587,6 +2872,12 @@ err_gen!(FillBufferError {
              CheckFillBufferError,
          });
 
+err_gen!(CopyQueryPoolResultsError {
+             AutoCommandBufferBuilderContextError,
+             CheckCopyQueryPoolResultsError,
+             SyncCommandBufferBuilderError,
+         });
+
 err_gen!(DispatchError {
              AutoCommandBufferBuilderContextError,
              CheckPushConstantsValidityError,
@@ -1614,12 +2905,64 @@ err_gen!(DrawIndexedError {
              SyncCommandBufferBuilderError,
          });
 
+err_gen!(DrawIndexedIndirectCountError {
+             AutoCommandBufferBuilderContextError,
+             CheckDynamicStateValidityError,
+             CheckPushConstantsValidityError,
+             CheckDescriptorSetsValidityError,
+             CheckVertexBufferError,
+             CheckIndexBufferError,
+             CheckIndirectCountError,
+             SyncCommandBufferBuilderError,
+         });
+
 err_gen!(DrawIndirectError {
              AutoCommandBufferBuilderContextError,
              CheckDynamicStateValidityError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
              CheckVertexBufferError,
+             CheckIndirectBufferError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(DrawIndirectCountError {
+             AutoCommandBufferBuilderContextError,
+             CheckDynamicStateValidityError,
+             CheckPushConstantsValidityError,
+             CheckDescriptorSetsValidityError,
+             CheckVertexBufferError,
+             CheckIndirectCountError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(DrawIndirectByteCountError {
+             AutoCommandBufferBuilderContextError,
+             CheckDynamicStateValidityError,
+             CheckPushConstantsValidityError,
+             CheckDescriptorSetsValidityError,
+             CheckVertexBufferError,
+             CheckDrawIndirectByteCountError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(DrawMeshTasksError {
+             AutoCommandBufferBuilderContextError,
+             CheckDynamicStateValidityError,
+             CheckPushConstantsValidityError,
+             CheckDescriptorSetsValidityError,
+             CheckDrawMeshTasksError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(EndQueryError {
+             AutoCommandBufferBuilderContextError,
+             CheckEndQueryError,
+         });
+
+err_gen!(EndTransformFeedbackError {
+             AutoCommandBufferBuilderContextError,
+             CheckTransformFeedbackCountersError,
              SyncCommandBufferBuilderError,
          });
 
@@ -1628,11 +2971,58 @@ err_gen!(ExecuteCommandsError {
              SyncCommandBufferBuilderError,
          });
 
+err_gen!(OwnershipTransferError {
+             AutoCommandBufferBuilderContextError,
+             CheckOwnershipTransferError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(PipelineBarrierError {
+             AutoCommandBufferBuilderContextError,
+             CheckPipelineBarrierError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(PushDescriptorSetError {
+             AutoCommandBufferBuilderContextError,
+             CheckPushDescriptorSetError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(ResetQueryPoolError {
+             AutoCommandBufferBuilderContextError,
+             CheckResetQueryPoolError,
+         });
+
+err_gen!(SetDeviceMaskError {
+             CheckDeviceMaskError,
+         });
+
+err_gen!(TraceRaysError {
+             AutoCommandBufferBuilderContextError,
+             CheckPushConstantsValidityError,
+             CheckDescriptorSetsValidityError,
+             CheckTraceRaysError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(TransferOwnershipError {
+             OomError,
+             OwnershipTransferError,
+             BuildError,
+             CommandBufferExecError,
+         });
+
 err_gen!(UpdateBufferError {
              AutoCommandBufferBuilderContextError,
              CheckUpdateBufferError,
          });
 
+err_gen!(WriteTimestampError {
+             AutoCommandBufferBuilderContextError,
+             CheckWriteTimestampError,
+         });
+
 #[derive(Debug, Copy, Clone)]
 pub enum AutoCommandBufferBuilderContextError {
     /// Operation forbidden in a secondary command buffer.
@@ -1660,6 +3050,26 @@ pub enum AutoCommandBufferBuilderContextError {
     /// Tried to use a graphics pipeline whose render pass is incompatible with the current render
     /// pass.
     IncompatibleRenderPass,
+    /// Tried to begin a query while a query of the same type was already active, to reset an
+    /// active query, or to build the command buffer while a query was still active.
+    QueryIsActive,
+    /// Tried to end a query that wasn't active.
+    QueryNotActive,
+    /// Tried to use a graphics pipeline that uses mesh shading with a draw command other than
+    /// `draw_mesh_tasks`.
+    MeshShadingPipeline,
+    /// Tried to begin conditional rendering while it was already active, to execute secondary
+    /// command buffers while it was active, to end the subpass it was begun in without ending
+    /// it, or to build the command buffer while it was still active.
+    ConditionalRenderingIsActive,
+    /// Tried to end conditional rendering while it wasn't active.
+    ConditionalRenderingNotActive,
+    /// Tried to begin transform feedback while it was already active, to bind transform feedback
+    /// buffers or execute secondary command buffers while it was active, to end the subpass it
+    /// was begun in without ending it, or to build the command buffer while it was still active.
+    TransformFeedbackIsActive,
+    /// Tried to end transform feedback while it wasn't active.
+    TransformFeedbackNotActive,
 }
 
 impl error::Error for AutoCommandBufferBuilderContextError {
@@ -1695,6 +3105,35 @@ impl error::Error for AutoCommandBufferBuilderContextError {
                 "tried to use a graphics pipeline whose render pass is incompatible with the \
                  current render pass"
             },
+            AutoCommandBufferBuilderContextError::QueryIsActive => {
+                "tried to begin a query while a query of the same type was already active, to \
+                 reset an active query, or to build the command buffer while a query was still \
+                 active"
+            },
+            AutoCommandBufferBuilderContextError::QueryNotActive => {
+                "tried to end a query that wasn't active"
+            },
+            AutoCommandBufferBuilderContextError::MeshShadingPipeline => {
+                "tried to use a graphics pipeline that uses mesh shading with a draw command \
+                 other than `draw_mesh_tasks`"
+            },
+            AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive => {
+                "tried to begin conditional rendering while it was already active, to execute \
+                 secondary command buffers while it was active, to end the subpass it was begun \
+                 in without ending it, or to build the command buffer while it was still active"
+            },
+            AutoCommandBufferBuilderContextError::ConditionalRenderingNotActive => {
+                "tried to end conditional rendering while it wasn't active"
+            },
+            AutoCommandBufferBuilderContextError::TransformFeedbackIsActive => {
+                "tried to begin transform feedback while it was already active, to bind \
+                 transform feedback buffers or execute secondary command buffers while it was \
+                 active, to end the subpass it was begun in without ending it, or to build the \
+                 command buffer while it was still active"
+            },
+            AutoCommandBufferBuilderContextError::TransformFeedbackNotActive => {
+                "tried to end transform feedback while it wasn't active"
+            },
         }
     }
 }
@@ -1705,3 +3144,593 @@ impl fmt::Display for AutoCommandBufferBuilderContextError {
         write!(fmt, "{}", error::Error::description(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::AutoCommandBuffer;
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::AutoCommandBufferBuilderContextError;
+    use command_buffer::BeginConditionalRenderingError;
+    use command_buffer::BeginTransformFeedbackError;
+    use command_buffer::BindDescriptorSetsError;
+    use command_buffer::BindTransformFeedbackBuffersError;
+    use command_buffer::BuildError;
+    use command_buffer::CommandBuffer;
+    use command_buffer::DrawError;
+    use command_buffer::DrawIndexedError;
+    use command_buffer::DynamicState;
+    use command_buffer::EndTransformFeedbackError;
+    use command_buffer::ExecuteCommandsError;
+    use command_buffer::OwnershipTransferError;
+    use command_buffer::PushDescriptorSetError;
+    use command_buffer::validity::CheckDescriptorSetsValidityError;
+    use command_buffer::validity::CheckOwnershipTransferError;
+    use command_buffer::validity::CheckPushDescriptorSetError;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSetWithOffsets;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use device::Device;
+    use device::DeviceExtensions;
+    use framebuffer::EmptySinglePassRenderPassDesc;
+    use framebuffer::Framebuffer;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::Subpass;
+    use instance::Features;
+    use instance::PhysicalDevice;
+    use pipeline::GraphicsPipeline;
+    use pipeline::PipelineBindPoint;
+    use pipeline::shader::GraphicsShaderType;
+    use pipeline::shader::ShaderModule;
+    use pipeline::vertex::BufferlessVertices;
+    use pipeline::viewport::Viewport;
+    use std::iter;
+    use std::sync::Arc;
+    use sync::AccessFlagBits;
+    use sync::BufferMemoryBarrier;
+    use sync::GpuFuture;
+    use sync::PipelineStages;
+    use tests::FRAGMENT_MAIN;
+    use tests::MESH_MAIN;
+    use tests::VERTEX_MAIN;
+    use tests::main_entry_point;
+
+    #[test]
+    fn draw_with_mesh_pipeline() {
+        let instance = instance!();
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+        if !DeviceExtensions::supported_by_device(physical).ext_mesh_shader {
+            return;
+        }
+        let family = match physical.queue_families().find(|q| q.supports_graphics()) {
+            Some(q) => q,
+            None => return,
+        };
+        let extensions = DeviceExtensions {
+            ext_mesh_shader: true,
+            ..DeviceExtensions::none()
+        };
+        let (device, mut queues) =
+            match Device::new(physical, &Features::none(), &extensions, Some((family, 1.0))) {
+                Ok(r) => r,
+                Err(_) => return,
+            };
+        let queue = queues.next().unwrap();
+
+        let ms = unsafe { ShaderModule::from_words(device.clone(), &MESH_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc.build_render_pass(device.clone())
+                              .unwrap());
+
+        let pipeline = Arc::new(GraphicsPipeline::start()
+                                    .mesh_shader(main_entry_point(&ms, GraphicsShaderType::Mesh),
+                                                 ())
+                                    .viewports_dynamic_scissors_irrelevant(1)
+                                    .fragment_shader(main_entry_point(&fs,
+                                                                      GraphicsShaderType::Fragment),
+                                                     ())
+                                    .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+                                    .build(device.clone())
+                                    .unwrap());
+
+        let framebuffer = Arc::new(Framebuffer::with_dimensions(rp, [4, 4, 1]).build().unwrap());
+        let index_buffer =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), 0u16 .. 3).unwrap();
+
+        let in_render_pass = || {
+            AutoCommandBufferBuilder::primary(device.clone(), queue.family())
+                .unwrap()
+                .begin_render_pass(framebuffer.clone(), false, Vec::new())
+                .unwrap()
+        };
+
+        match in_render_pass().draw(pipeline.clone(),
+                                    DynamicState::none(),
+                                    BufferlessVertices { vertices: 3, instances: 1 },
+                                    (),
+                                    ()) {
+            Err(DrawError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::MeshShadingPipeline)) => (),
+            _ => panic!(),
+        }
+
+        match in_render_pass().draw_indexed(pipeline.clone(),
+                                            DynamicState::none(),
+                                            BufferlessVertices { vertices: 3, instances: 1 },
+                                            index_buffer,
+                                            (),
+                                            ()) {
+            Err(DrawIndexedError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::MeshShadingPipeline)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bind_pipeline_and_vertex_buffers() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc.build_render_pass(device.clone())
+                              .unwrap());
+
+        let pipeline = Arc::new(GraphicsPipeline::start()
+                                    .vertex_shader(main_entry_point(&vs,
+                                                                    GraphicsShaderType::Vertex),
+                                                   ())
+                                    .viewports_dynamic_scissors_irrelevant(1)
+                                    .fragment_shader(main_entry_point(&fs,
+                                                                      GraphicsShaderType::Fragment),
+                                                     ())
+                                    .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+                                    .build(device.clone())
+                                    .unwrap());
+
+        let framebuffer = Arc::new(Framebuffer::with_dimensions(rp, [4, 4, 1]).build().unwrap());
+
+        // The draw finds the pipeline and the vertex buffers already bound.
+        AutoCommandBufferBuilder::primary(device.clone(), queue.family())
+            .unwrap()
+            .bind_pipeline_graphics(pipeline.clone())
+            .unwrap()
+            .bind_vertex_buffers(&pipeline, BufferlessVertices { vertices: 3, instances: 1 })
+            .unwrap()
+            .begin_render_pass(framebuffer, false, Vec::new())
+            .unwrap()
+            .draw(pipeline.clone(),
+                  DynamicState {
+                      viewports: Some(vec![Viewport {
+                                                origin: [0.0, 0.0],
+                                                dimensions: [4.0, 4.0],
+                                                depth_range: 0.0 .. 1.0,
+                                            }]),
+                      ..DynamicState::none()
+                  },
+                  BufferlessVertices { vertices: 3, instances: 1 },
+                  (),
+                  ())
+            .unwrap()
+            .end_render_pass()
+            .unwrap()
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn bind_descriptor_sets_dynamic_offsets() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(true),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = RuntimePipelineDesc::new(iter::once(iter::once(Some(desc))), iter::empty())
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let set = PersistentDescriptorSet::start(layout.clone(), 0)
+            .add_buffer(buffer)
+            .unwrap()
+            .build()
+            .unwrap();
+        let set = Arc::new(set);
+
+        let builder = || AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+
+        match builder().bind_descriptor_sets(PipelineBindPoint::Graphics,
+                                             layout.clone(),
+                                             set.clone()) {
+            Err(BindDescriptorSetsError::CheckDescriptorSetsValidityError(
+                CheckDescriptorSetsValidityError::WrongDynamicOffsetsCount {
+                    set_num: 0,
+                    expected: 1,
+                    obtained: 0,
+                })) => (),
+            _ => panic!(),
+        }
+
+        let set = DescriptorSetWithOffsets::new(set, iter::once(0)).unwrap();
+        builder()
+            .bind_descriptor_sets(PipelineBindPoint::Graphics, layout, set)
+            .unwrap()
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn push_descriptor_set_requires_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = RuntimePipelineDesc::new(iter::once(iter::once(Some(desc))), iter::empty())
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let descriptors = PersistentDescriptorSet::start(layout, 0).add_buffer(buffer).unwrap();
+
+        let builder = AutoCommandBufferBuilder::primary(device, queue.family()).unwrap();
+        match builder.push_descriptor_set(PipelineBindPoint::Graphics, descriptors) {
+            Err(PushDescriptorSetError::CheckPushDescriptorSetError(
+                CheckPushDescriptorSetError::ExtensionNotEnabled)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn end_render_pass_outside_render_pass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let builder = AutoCommandBufferBuilder::primary(device, queue.family()).unwrap();
+        match builder.end_render_pass() {
+            Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn next_subpass_outside_render_pass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let builder = AutoCommandBufferBuilder::primary(device, queue.family()).unwrap();
+        match builder.next_subpass(false) {
+            Err(AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn render_pass_commands_forbidden_in_secondary() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let builder = AutoCommandBufferBuilder::secondary_compute(device, queue.family()).unwrap();
+        match builder.end_render_pass() {
+            Err(AutoCommandBufferBuilderContextError::ForbiddenInSecondary) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn execute_commands_forbidden_in_secondary() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let secondary = AutoCommandBufferBuilder::secondary_compute(device.clone(), queue.family())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let builder = AutoCommandBufferBuilder::secondary_compute(device, queue.family()).unwrap();
+        match unsafe { builder.execute_commands(secondary) } {
+            Err(ExecuteCommandsError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::ForbiddenInSecondary)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn execute_commands_in_inline_subpass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc.build_render_pass(device.clone()).unwrap());
+        let framebuffer = Arc::new(Framebuffer::with_dimensions(rp, [4, 4, 1]).build().unwrap());
+
+        let secondary = AutoCommandBufferBuilder::secondary_compute(device.clone(), queue.family())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let builder = AutoCommandBufferBuilder::primary(device, queue.family())
+            .unwrap()
+            .begin_render_pass(framebuffer, false, Vec::new())
+            .unwrap();
+        match unsafe { builder.execute_commands(secondary) } {
+            Err(ExecuteCommandsError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::WrongSubpassType)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn release_ownership_same_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let builder = AutoCommandBufferBuilder::primary(device, queue.family()).unwrap();
+        match builder.release_buffer_ownership(buffer, queue.family()) {
+            Err(OwnershipTransferError::CheckOwnershipTransferError(
+                CheckOwnershipTransferError::SameQueueFamily)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn one_time_submit() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        AutoCommandBuffer::one_time_submit(queue.clone(), |builder| {
+            builder.fill_buffer(buffer.clone(), 12)
+        }).unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), 12);
+    }
+
+    #[test]
+    fn buffer_memory_barrier() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let transfer_write = AccessFlagBits {
+            transfer_write: true,
+            ..AccessFlagBits::none()
+        };
+
+        let barrier = BufferMemoryBarrier::new(buffer.clone(),
+                                               transfer,
+                                               transfer_write,
+                                               transfer,
+                                               transfer_write);
+
+        AutoCommandBufferBuilder::primary_one_time_submit(device, queue.family())
+            .unwrap()
+            .fill_buffer(buffer.clone(), 5)
+            .unwrap()
+            .buffer_memory_barrier(barrier)
+            .unwrap()
+            .fill_buffer(buffer.clone(), 12)
+            .unwrap()
+            .build()
+            .unwrap()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), 12);
+    }
+
+    #[test]
+    fn resources_kept_alive_until_finished() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let cb = AutoCommandBufferBuilder::primary_one_time_submit(device, queue.family())
+            .unwrap()
+            .fill_buffer(buffer.clone(), 12)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(Arc::strong_count(&buffer) >= 2);
+
+        let future = cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        assert!(Arc::strong_count(&buffer) >= 2);
+
+        future.wait(None).unwrap();
+        drop(future);
+        assert_eq!(Arc::strong_count(&buffer), 1);
+    }
+
+    #[test]
+    fn conditional_rendering_state() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [ext_conditional_rendering]);
+
+        let usage = BufferUsage {
+            conditional_rendering: true,
+            ..BufferUsage::none()
+        };
+        let predicate = CpuAccessibleBuffer::from_data(device.clone(), usage, 1u32).unwrap();
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc.build_render_pass(device.clone())
+                              .unwrap());
+        let framebuffer = Arc::new(Framebuffer::with_dimensions(rp, [4, 4, 1]).build().unwrap());
+
+        let builder = || AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+
+        builder()
+            .begin_conditional_rendering(predicate.clone(), false)
+            .unwrap()
+            .end_conditional_rendering()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        match builder()
+            .begin_conditional_rendering(predicate.clone(), false)
+            .unwrap()
+            .begin_conditional_rendering(predicate.clone(), true)
+        {
+            Err(BeginConditionalRenderingError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive)) => (),
+            _ => panic!(),
+        }
+
+        match builder().end_conditional_rendering() {
+            Err(AutoCommandBufferBuilderContextError::ConditionalRenderingNotActive) => (),
+            _ => panic!(),
+        }
+
+        match builder()
+            .begin_conditional_rendering(predicate.clone(), false)
+            .unwrap()
+            .build()
+        {
+            Err(BuildError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive)) => (),
+            _ => panic!(),
+        }
+
+        // Begun outside of a render pass, so it can't be ended inside of one.
+        match builder()
+            .begin_conditional_rendering(predicate.clone(), false)
+            .unwrap()
+            .begin_render_pass(framebuffer.clone(), false, Vec::new())
+            .unwrap()
+            .end_conditional_rendering()
+        {
+            Err(AutoCommandBufferBuilderContextError::ForbiddenInsideRenderPass) => (),
+            _ => panic!(),
+        }
+
+        // Begun inside of a subpass, so it must be ended before the end of the subpass.
+        match builder()
+            .begin_render_pass(framebuffer.clone(), false, Vec::new())
+            .unwrap()
+            .begin_conditional_rendering(predicate.clone(), false)
+            .unwrap()
+            .end_render_pass()
+        {
+            Err(AutoCommandBufferBuilderContextError::ConditionalRenderingIsActive) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn transform_feedback_state() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [ext_transform_feedback]);
+
+        let usage = BufferUsage {
+            transform_feedback_buffer: true,
+            ..BufferUsage::none()
+        };
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(), usage, 0u32 .. 16).unwrap();
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc.build_render_pass(device.clone())
+                              .unwrap());
+        let framebuffer = Arc::new(Framebuffer::with_dimensions(rp, [4, 4, 1]).build().unwrap());
+
+        let builder = || AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap();
+        let in_render_pass = || {
+            builder()
+                .bind_transform_feedback_buffers(0, iter::once(buffer.clone()))
+                .unwrap()
+                .begin_render_pass(framebuffer.clone(), false, Vec::new())
+                .unwrap()
+        };
+
+        in_render_pass()
+            .begin_transform_feedback()
+            .unwrap()
+            .end_transform_feedback()
+            .unwrap()
+            .end_render_pass()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        match builder().begin_transform_feedback() {
+            Err(BeginTransformFeedbackError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::ForbiddenOutsideRenderPass)) => (),
+            _ => panic!(),
+        }
+
+        match in_render_pass()
+            .begin_transform_feedback()
+            .unwrap()
+            .begin_transform_feedback()
+        {
+            Err(BeginTransformFeedbackError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::TransformFeedbackIsActive)) => (),
+            _ => panic!(),
+        }
+
+        match in_render_pass().end_transform_feedback() {
+            Err(EndTransformFeedbackError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::TransformFeedbackNotActive)) => (),
+            _ => panic!(),
+        }
+
+        match in_render_pass()
+            .begin_transform_feedback()
+            .unwrap()
+            .bind_transform_feedback_buffers(0, iter::once(buffer.clone()))
+        {
+            Err(BindTransformFeedbackBuffersError::AutoCommandBufferBuilderContextError(
+                AutoCommandBufferBuilderContextError::TransformFeedbackIsActive)) => (),
+            _ => panic!(),
+        }
+
+        match in_render_pass()
+            .begin_transform_feedback()
+            .unwrap()
+            .end_render_pass()
+        {
+            Err(AutoCommandBufferBuilderContextError::TransformFeedbackIsActive) => (),
+            _ => panic!(),
+        }
+    }
+}
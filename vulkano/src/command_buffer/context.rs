@@ -0,0 +1,281 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use buffer::BufferUsage;
+use buffer::ImmutableBuffer;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::CommandBufferExecFuture;
+use command_buffer::OneTimeSubmitError;
+use device::Queue;
+use memory::DeviceMemoryAllocError;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::NowFuture;
+use sync::SemaphoreSignalFuture;
+
+/// Future returned by the upload methods of `UploadContext`.
+///
+/// The upload has already been submitted. The future can be used on any queue of the device,
+/// in which case the queue waits on a semaphore.
+pub type UploadFuture = ContextSubmitFuture<NowFuture>;
+
+/// Future returned by `UploadContext::submit` and `ComputeContext::submit`.
+pub type ContextSubmitFuture<F> =
+    SemaphoreSignalFuture<CommandBufferExecFuture<F, AutoCommandBuffer>>;
+
+/// Uploads data with a queue that is preferably dedicated to transfer operations.
+///
+/// All the operations of the context are submitted immediately and signal a semaphore, so that
+/// they run concurrently with the work of the other queues. The returned futures can then be
+/// joined with the futures of the graphics queue.
+///
+/// The buffers created by the context can be used by all the queue families that were enabled
+/// when creating the device, and don't need any ownership transfer.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::device::Queue;
+/// use vulkano::buffer::BufferUsage;
+/// use vulkano::command_buffer::UploadContext;
+///
+/// # let transfer_queue: Arc<Queue> = return;
+/// let upload = UploadContext::new(transfer_queue.clone());
+/// let (buffer, upload_future) = upload
+///     .upload_iter((0 .. 1024u32).map(|n| n * 2), BufferUsage::vertex_buffer())
+///     .unwrap();
+///
+/// // The graphics queue must now submit its work after `upload_future`.
+/// ```
+#[derive(Debug, Clone)]
+pub struct UploadContext {
+    queue: Arc<Queue>,
+}
+
+impl UploadContext {
+    /// Builds a new context that uploads data with `queue`.
+    ///
+    /// Use `PhysicalDevice::dedicated_transfer_queue_family` to find a suitable family when
+    /// creating the device. Any queue that supports transfers is accepted, but the uploads then
+    /// compete with the other work of the queue.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue doesn't support transfer operations.
+    ///
+    #[inline]
+    pub fn new(queue: Arc<Queue>) -> UploadContext {
+        assert!(queue.family().supports_transfers());
+        UploadContext { queue: queue }
+    }
+
+    /// Returns the queue used by the context.
+    #[inline]
+    pub fn queue(&self) -> &Arc<Queue> {
+        &self.queue
+    }
+
+    /// Builds an `ImmutableBuffer` containing `data` and submits the upload.
+    pub fn upload_data<T>(&self, data: T, usage: BufferUsage)
+                          -> Result<(Arc<ImmutableBuffer<T>>, UploadFuture), UploadError>
+        where T: 'static + Send + Sync + Sized
+    {
+        let (buffer, future) = ImmutableBuffer::from_data(data, usage, self.queue.clone())?;
+        Ok((buffer, future.then_signal_semaphore_and_flush()?))
+    }
+
+    /// Builds an `ImmutableBuffer` containing the elements of `data` and submits the upload.
+    pub fn upload_iter<D, T>(&self, data: D, usage: BufferUsage)
+                             -> Result<(Arc<ImmutableBuffer<[T]>>, UploadFuture), UploadError>
+        where D: ExactSizeIterator<Item = T>,
+              T: 'static + Send + Sync + Sized
+    {
+        let (buffer, future) = ImmutableBuffer::from_iter(data, usage, self.queue.clone())?;
+        Ok((buffer, future.then_signal_semaphore_and_flush()?))
+    }
+
+    /// Records a command buffer with `record` and submits it on the queue of the context after
+    /// `future`.
+    ///
+    /// This can be used to perform uploads that the other methods don't cover, for example copies
+    /// to images.
+    #[inline]
+    pub fn submit<F, R, E>(&self, future: F, record: R)
+                           -> Result<ContextSubmitFuture<F>, OneTimeSubmitError<E>>
+        where F: GpuFuture,
+              R: FnOnce(AutoCommandBufferBuilder) -> Result<AutoCommandBufferBuilder, E>
+    {
+        submit(&self.queue, future, record)
+    }
+}
+
+/// Executes compute operations with a queue that is preferably dedicated to compute operations.
+///
+/// Work submitted with `submit` is flushed immediately and signals a semaphore, so that it runs
+/// concurrently with the work of the graphics queue. The returned future can be joined with the
+/// futures of the graphics queue in order to use the results.
+#[derive(Debug, Clone)]
+pub struct ComputeContext {
+    queue: Arc<Queue>,
+}
+
+impl ComputeContext {
+    /// Builds a new context that executes compute operations with `queue`.
+    ///
+    /// Use `PhysicalDevice::async_compute_queue_family` to find a suitable family when creating
+    /// the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue doesn't support compute operations.
+    ///
+    #[inline]
+    pub fn new(queue: Arc<Queue>) -> ComputeContext {
+        assert!(queue.family().supports_compute());
+        ComputeContext { queue: queue }
+    }
+
+    /// Returns the queue used by the context.
+    #[inline]
+    pub fn queue(&self) -> &Arc<Queue> {
+        &self.queue
+    }
+
+    /// Records a command buffer with `record` and submits it on the queue of the context after
+    /// `future`.
+    ///
+    /// Pass `sync::now(device)` as `future` if the work doesn't depend on anything, or a future
+    /// of the graphics queue to wait for its results.
+    #[inline]
+    pub fn submit<F, R, E>(&self, future: F, record: R)
+                           -> Result<ContextSubmitFuture<F>, OneTimeSubmitError<E>>
+        where F: GpuFuture,
+              R: FnOnce(AutoCommandBufferBuilder) -> Result<AutoCommandBufferBuilder, E>
+    {
+        submit(&self.queue, future, record)
+    }
+}
+
+fn submit<F, R, E>(queue: &Arc<Queue>, future: F, record: R)
+                   -> Result<ContextSubmitFuture<F>, OneTimeSubmitError<E>>
+    where F: GpuFuture,
+          R: FnOnce(AutoCommandBufferBuilder) -> Result<AutoCommandBufferBuilder, E>
+{
+    let builder = AutoCommandBufferBuilder::primary_one_time_submit(queue.device().clone(),
+                                                                    queue.family())?;
+    let command_buffer = match record(builder) {
+        Ok(builder) => builder.build()?,
+        Err(err) => return Err(OneTimeSubmitError::RecordError(err)),
+    };
+
+    Ok(future
+           .then_execute(queue.clone(), command_buffer)?
+           .then_signal_semaphore_and_flush()?)
+}
+
+/// Error that can happen when uploading data with an `UploadContext`.
+#[derive(Debug, Clone)]
+pub enum UploadError {
+    /// Error while allocating the buffers.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+    /// Error while submitting the upload.
+    FlushError(FlushError),
+}
+
+impl error::Error for UploadError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            UploadError::DeviceMemoryAllocError(_) => "error while allocating the buffers",
+            UploadError::FlushError(_) => "error while submitting the upload",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            UploadError::DeviceMemoryAllocError(ref err) => Some(err),
+            UploadError::FlushError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for UploadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<DeviceMemoryAllocError> for UploadError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> UploadError {
+        UploadError::DeviceMemoryAllocError(err)
+    }
+}
+
+impl From<FlushError> for UploadError {
+    #[inline]
+    fn from(err: FlushError) -> UploadError {
+        UploadError::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::ComputeContext;
+    use command_buffer::UploadContext;
+    use sync;
+    use sync::GpuFuture;
+
+    #[test]
+    fn upload_then_use() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let upload = UploadContext::new(queue.clone());
+        let (_buffer, future) = upload
+            .upload_iter((0 .. 128u32).map(|n| n * 2), BufferUsage::all())
+            .unwrap();
+
+        future
+            .join(sync::now(device.clone()))
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn compute_submit() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let compute = ComputeContext::new(queue.clone());
+        compute
+            .submit(sync::now(device.clone()),
+                    |builder| builder.fill_buffer(buffer.clone(), 12))
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(*buffer.read().unwrap(), 12);
+    }
+}
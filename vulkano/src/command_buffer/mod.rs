@@ -60,6 +60,26 @@
 //! let _future = cb.execute(queue.clone());
 //! ```
 //!
+//! # Lifetime of the resources
+//!
+//! A command buffer holds a reference to every buffer, image, pipeline, descriptor set and
+//! framebuffer that its commands use. Once submitted, the command buffer is owned by the future
+//! returned by `execute`, and is only destroyed once the GPU has finished executing it. If the
+//! future is dropped before that, its destructor blocks until the queue is idle.
+//!
+//! In other words you can drop your own `Arc`s to the resources right after submitting, and they
+//! will be destroyed when it is safe to do so. Call `cleanup_finished` on the futures regularly
+//! so that the resources of the submissions that are finished get released.
+//!
+//! # Using multiple queues
+//!
+//! Many GPUs have queue families dedicated to transfer or compute operations, whose work runs
+//! concurrently with the work of the graphics queue. The `UploadContext` and `ComputeContext`
+//! helpers submit work on such queues and return futures that signal a semaphore, which can then
+//! be joined with the futures of the graphics queue. See
+//! `PhysicalDevice::dedicated_transfer_queue_family` and
+//! `PhysicalDevice::async_compute_queue_family` to find these families.
+//!
 //! # Internal architecture of vulkano
 //!
 //! The `commands_raw` and `commands_extra` modules contain structs that correspond to various
@@ -76,19 +96,50 @@
 pub use self::auto::AutoCommandBuffer;
 pub use self::auto::AutoCommandBufferBuilder;
 pub use self::auto::AutoCommandBufferBuilderContextError;
+pub use self::auto::BeginQueryError;
+pub use self::auto::BeginConditionalRenderingError;
 pub use self::auto::BeginRenderPassError;
+pub use self::auto::BeginTransformFeedbackError;
+pub use self::auto::BindDescriptorSetsError;
+pub use self::auto::BindTransformFeedbackBuffersError;
+pub use self::auto::BindVertexBuffersError;
 pub use self::auto::BlitImageError;
+pub use self::auto::BuildAccelerationStructureError;
 pub use self::auto::BuildError;
 pub use self::auto::ClearColorImageError;
 pub use self::auto::CopyBufferError;
 pub use self::auto::CopyBufferImageError;
+pub use self::auto::CopyQueryPoolResultsError;
 pub use self::auto::DispatchError;
 pub use self::auto::DrawError;
 pub use self::auto::DrawIndexedError;
+pub use self::auto::DrawIndexedIndirectCountError;
+pub use self::auto::DrawIndirectByteCountError;
+pub use self::auto::DrawIndirectCountError;
 pub use self::auto::DrawIndirectError;
+pub use self::auto::DrawMeshTasksError;
+pub use self::auto::EndQueryError;
+pub use self::auto::EndTransformFeedbackError;
 pub use self::auto::ExecuteCommandsError;
 pub use self::auto::FillBufferError;
+pub use self::auto::OneTimeSubmitError;
+pub use self::auto::OwnershipTransferError;
+pub use self::auto::OwnershipTransferFuture;
+pub use self::auto::PipelineBarrierError;
+pub use self::auto::PushDescriptorSetError;
+pub use self::auto::ResetQueryPoolError;
+pub use self::auto::SetDeviceMaskError;
+pub use self::auto::TraceRaysError;
+pub use self::auto::TransferOwnershipError;
 pub use self::auto::UpdateBufferError;
+pub use self::auto::WriteTimestampError;
+pub use self::auto::transfer_buffer_ownership;
+pub use self::auto::transfer_image_ownership;
+pub use self::context::ComputeContext;
+pub use self::context::ContextSubmitFuture;
+pub use self::context::UploadContext;
+pub use self::context::UploadError;
+pub use self::context::UploadFuture;
 pub use self::state_cacher::StateCacher;
 pub use self::state_cacher::StateCacherOutcome;
 pub use self::traits::CommandBuffer;
@@ -105,6 +156,7 @@ pub mod sys;
 pub mod validity;
 
 mod auto;
+mod context;
 mod state_cacher;
 mod traits;
 
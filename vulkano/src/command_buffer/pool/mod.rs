@@ -22,6 +22,7 @@ use OomError;
 use device::DeviceOwned;
 
 pub use self::standard::StandardCommandPool;
+pub use self::sys::CommandPoolCreationError;
 pub use self::sys::CommandPoolTrimError;
 pub use self::sys::UnsafeCommandPool;
 pub use self::sys::UnsafeCommandPoolAlloc;
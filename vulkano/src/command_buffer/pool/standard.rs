@@ -22,6 +22,7 @@ use std::vec::IntoIter as VecIntoIter;
 use command_buffer::pool::CommandPool;
 use command_buffer::pool::CommandPoolAlloc;
 use command_buffer::pool::CommandPoolBuilderAlloc;
+use command_buffer::pool::CommandPoolCreationError;
 use command_buffer::pool::UnsafeCommandPool;
 use command_buffer::pool::UnsafeCommandPoolAlloc;
 use instance::QueueFamily;
@@ -101,8 +102,16 @@ unsafe impl CommandPool for Arc<StandardCommandPool> {
                 entry.get().upgrade().unwrap()
             },
             Entry::Vacant(entry) => {
-                let new_pool =
-                    UnsafeCommandPool::new(self.device.clone(), self.queue_family(), false, true)?;
+                let new_pool = match UnsafeCommandPool::new(self.device.clone(),
+                                                            self.queue_family(),
+                                                            false,
+                                                            true) {
+                    Ok(pool) => pool,
+                    Err(CommandPoolCreationError::OomError(err)) => return Err(err),
+                    Err(err @ CommandPoolCreationError::UnexpectedResult(_)) => {
+                        panic!("unexpected error: {:?}", err)
+                    },
+                };
                 let pt = Arc::new(StandardCommandPoolPerThread {
                                       pool: Mutex::new(new_pool),
                                       available_primary_command_buffers: MsQueue::new(),
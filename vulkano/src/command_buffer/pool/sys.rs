@@ -41,6 +41,9 @@ pub struct UnsafeCommandPool {
     // Index of the associated queue family in the physical device.
     queue_family_index: u32,
 
+    // False if the pool was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+
     // We don't want `UnsafeCommandPool` to implement Sync.
     // This marker unimplements both Send and Sync, but we reimplement Send manually right under.
     dummy_avoid_sync: PhantomData<*const u8>,
@@ -64,7 +67,7 @@ impl UnsafeCommandPool {
     /// - Panics if the queue family doesn't belong to the same physical device as `device`.
     ///
     pub fn new(device: Arc<Device>, queue_family: QueueFamily, transient: bool, reset_cb: bool)
-               -> Result<UnsafeCommandPool, OomError> {
+               -> Result<UnsafeCommandPool, CommandPoolCreationError> {
         assert_eq!(device.physical_device().internal_object(),
                    queue_family.physical_device().internal_object(),
                    "Device doesn't match physical device when creating a command pool");
@@ -105,10 +108,41 @@ impl UnsafeCommandPool {
                pool: pool,
                device: device.clone(),
                queue_family_index: queue_family.id(),
+               needs_destruction: true,
                dummy_avoid_sync: PhantomData,
            })
     }
 
+    /// Builds an `UnsafeCommandPool` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the pool is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid command pool of `device`, created for `queue_family`.
+    /// - If `owned` is true, the pool must not be destroyed by anything else.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family doesn't belong to the same physical device as `device`.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::CommandPool, queue_family: QueueFamily,
+                           owned: bool)
+                           -> UnsafeCommandPool {
+        assert_eq!(device.physical_device().internal_object(),
+                   queue_family.physical_device().internal_object(),
+                   "Device doesn't match physical device when creating a command pool");
+
+        UnsafeCommandPool {
+            pool: handle,
+            device: device,
+            queue_family_index: queue_family.id(),
+            needs_destruction: owned,
+            dummy_avoid_sync: PhantomData,
+        }
+    }
+
     /// Resets the pool, which resets all the command buffers that were allocated from it.
     ///
     /// If `release_resources` is true, it is a hint to the implementation that it should free all
@@ -236,6 +270,10 @@ unsafe impl VulkanObject for UnsafeCommandPool {
 impl Drop for UnsafeCommandPool {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyCommandPool(self.device.internal_object(), self.pool, ptr::null());
@@ -280,6 +318,36 @@ impl Iterator for UnsafeCommandPoolAllocIter {
 impl ExactSizeIterator for UnsafeCommandPoolAllocIter {
 }
 
+/// Error that can happen when creating a command pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CommandPoolCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for CommandPoolCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CommandPoolCreationError::OomError(_) => "not enough memory available",
+            CommandPoolCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            CommandPoolCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(CommandPoolCreationError, OomError {});
+
 /// Error that can happen when trimming command pools.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum CommandPoolTrimError {
@@ -313,6 +381,7 @@ impl From<Error> for CommandPoolTrimError {
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
     use command_buffer::pool::CommandPoolTrimError;
     use command_buffer::pool::UnsafeCommandPool;
 
@@ -329,6 +398,18 @@ mod tests {
         assert_eq!(pool.queue_family().id(), queue.family().id());
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let pool = UnsafeCommandPool::new(device.clone(), queue.family(), false, false).unwrap();
+
+        let borrowed = unsafe {
+            UnsafeCommandPool::from_raw(device, pool.internal_object(), queue.family(), false)
+        };
+        assert_eq!(borrowed.internal_object(), pool.internal_object());
+        assert_eq!(borrowed.queue_family().id(), queue.family().id());
+    }
+
     #[test]
     fn panic_if_not_match_family() {
         let (device, _) = gfx_dev_and_queue!();
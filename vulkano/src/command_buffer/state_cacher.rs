@@ -11,8 +11,14 @@ use VulkanObject;
 use buffer::BufferAccess;
 use command_buffer::DynamicState;
 use descriptor::DescriptorSet;
+use descriptor::PipelineLayoutAbstract;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
+use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
+use pipeline::PipelineBindPoint;
+use pipeline::RayTracingPipelineAbstract;
 use pipeline::input_assembly::IndexType;
 use smallvec::SmallVec;
 use std::ops::Range;
@@ -30,10 +36,22 @@ pub struct StateCacher {
     compute_pipeline: vk::Pipeline,
     // The graphics pipeline currently bound. 0 if nothing bound.
     graphics_pipeline: vk::Pipeline,
+    // The ray tracing pipeline currently bound. 0 if nothing bound.
+    ray_tracing_pipeline: vk::Pipeline,
     // The descriptor sets for the compute pipeline.
     compute_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
     // The descriptor sets for the graphics pipeline.
     graphics_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
+    // The descriptor sets for the ray tracing pipeline.
+    ray_tracing_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
+    // The pipeline layout that was used to bind `compute_descriptor_sets`. `None` if nothing bound.
+    compute_descriptor_sets_layout: Option<BoundLayout>,
+    // The pipeline layout that was used to bind `graphics_descriptor_sets`. `None` if nothing
+    // bound.
+    graphics_descriptor_sets_layout: Option<BoundLayout>,
+    // The pipeline layout that was used to bind `ray_tracing_descriptor_sets`. `None` if nothing
+    // bound.
+    ray_tracing_descriptor_sets_layout: Option<BoundLayout>,
     // If the user starts comparing descriptor sets, but drops the helper struct in the middle of
     // the processing then we will end up in a weird state. This bool is true when we start
     // comparing sets, and is set to false when we end up comparing. If it was true when we start
@@ -64,8 +82,13 @@ impl StateCacher {
             dynamic_state: DynamicState::none(),
             compute_pipeline: 0,
             graphics_pipeline: 0,
+            ray_tracing_pipeline: 0,
             compute_descriptor_sets: SmallVec::new(),
             graphics_descriptor_sets: SmallVec::new(),
+            ray_tracing_descriptor_sets: SmallVec::new(),
+            compute_descriptor_sets_layout: None,
+            graphics_descriptor_sets_layout: None,
+            ray_tracing_descriptor_sets_layout: None,
             poisonned_descriptor_sets: false,
             vertex_buffers: SmallVec::new(),
             poisonned_vertex_buffers: false,
@@ -80,8 +103,13 @@ impl StateCacher {
         self.dynamic_state = DynamicState::none();
         self.compute_pipeline = 0;
         self.graphics_pipeline = 0;
+        self.ray_tracing_pipeline = 0;
         self.compute_descriptor_sets = SmallVec::new();
         self.graphics_descriptor_sets = SmallVec::new();
+        self.ray_tracing_descriptor_sets = SmallVec::new();
+        self.compute_descriptor_sets_layout = None;
+        self.graphics_descriptor_sets_layout = None;
+        self.ray_tracing_descriptor_sets_layout = None;
         self.vertex_buffers = SmallVec::new();
         self.index_buffer = None;
     }
@@ -116,24 +144,58 @@ impl StateCacher {
     /// order to get the index of the first set to bind, or `None` if the sets were identical to
     /// what is in cache.
     ///
+    /// The sets are bound with `pipeline_layout`. If the sets in cache were bound with a different
+    /// pipeline layout, only the sets for which the two layouts are compatible (as defined by
+    /// `PipelineLayoutDesc::is_compatible_for_set`) are kept. The other sets are considered as
+    /// needing to be bound again, as they are no longer valid for the new layout.
+    ///
     /// This process also updates the state cacher. The state cacher assumes that the state
     /// changes are going to be performed after the `compare` function returns.
     #[inline]
-    pub fn bind_descriptor_sets(&mut self, graphics: bool) -> StateCacherDescriptorSets {
+    pub fn bind_descriptor_sets<Pl>(&mut self, bind_point: PipelineBindPoint,
+                                    pipeline_layout: &Pl)
+                                    -> StateCacherDescriptorSets
+        where Pl: ?Sized + PipelineLayoutAbstract
+    {
         if self.poisonned_descriptor_sets {
             self.compute_descriptor_sets = SmallVec::new();
             self.graphics_descriptor_sets = SmallVec::new();
+            self.ray_tracing_descriptor_sets = SmallVec::new();
         }
 
         self.poisonned_descriptor_sets = true;
 
+        let layout = pipeline_layout.sys().internal_object();
+        let (sets, sets_layout) = match bind_point {
+            PipelineBindPoint::Graphics => {
+                (&mut self.graphics_descriptor_sets, &mut self.graphics_descriptor_sets_layout)
+            },
+            PipelineBindPoint::Compute => {
+                (&mut self.compute_descriptor_sets, &mut self.compute_descriptor_sets_layout)
+            },
+            PipelineBindPoint::RayTracing => {
+                (&mut self.ray_tracing_descriptor_sets,
+                 &mut self.ray_tracing_descriptor_sets_layout)
+            },
+        };
+
+        if sets_layout.as_ref().map(|l| l.layout) != Some(layout) {
+            let compatible = match *sets_layout {
+                Some(ref prev) => {
+                    (0 .. sets.len())
+                        .take_while(|&set| prev.is_compatible_for_set(pipeline_layout, set))
+                        .count()
+                },
+                None => 0,
+            };
+
+            sets.truncate(compatible);
+            *sets_layout = Some(BoundLayout::new(layout, pipeline_layout));
+        }
+
         StateCacherDescriptorSets {
             poisonned: &mut self.poisonned_descriptor_sets,
-            state: if graphics {
-                &mut self.graphics_descriptor_sets
-            } else {
-                &mut self.compute_descriptor_sets
-            },
+            state: sets,
             offset: 0,
             found_diff: None,
         }
@@ -175,6 +237,24 @@ impl StateCacher {
         }
     }
 
+    /// Checks whether we need to bind a ray tracing pipeline. Returns
+    /// `StateCacherOutcome::AlreadyOk` if the pipeline was already bound earlier, and
+    /// `StateCacherOutcome::NeedChange` if you need to actually bind the pipeline.
+    ///
+    /// This function also updates the state cacher. The state cacher assumes that the state
+    /// changes are going to be performed after this function returns.
+    pub fn bind_ray_tracing_pipeline<P>(&mut self, pipeline: &P) -> StateCacherOutcome
+        where P: RayTracingPipelineAbstract
+    {
+        let inner = pipeline.inner().internal_object();
+        if inner == self.ray_tracing_pipeline {
+            StateCacherOutcome::AlreadyOk
+        } else {
+            self.ray_tracing_pipeline = inner;
+            StateCacherOutcome::NeedChange
+        }
+    }
+
     /// Starts the process of comparing a list of vertex buffers to the vertex buffers currently
     /// in cache.
     ///
@@ -224,6 +304,63 @@ impl StateCacher {
     }
 }
 
+// Description of a pipeline layout that was used to bind descriptor sets. Kept in order to know
+// which sets stay valid when binding sets with another layout.
+struct BoundLayout {
+    layout: vk::PipelineLayout,
+    descriptors: SmallVec<[SmallVec<[Option<DescriptorDesc>; 5]>; 3]>,
+    push_constants: SmallVec<[PipelineLayoutDescPcRange; 6]>,
+}
+
+impl BoundLayout {
+    fn new<Pl>(layout: vk::PipelineLayout, desc: &Pl) -> BoundLayout
+        where Pl: ?Sized + PipelineLayoutDesc
+    {
+        BoundLayout {
+            layout: layout,
+            descriptors: (0 .. desc.num_sets())
+                .map(|set| {
+                         (0 .. desc.num_bindings_in_set(set).unwrap_or(0))
+                             .map(|binding| desc.descriptor(set, binding))
+                             .collect()
+                     })
+                .collect(),
+            push_constants: (0 .. desc.num_push_constants_ranges())
+                .filter_map(|num| desc.push_constants_range(num))
+                .collect(),
+        }
+    }
+}
+
+unsafe impl PipelineLayoutDesc for BoundLayout {
+    #[inline]
+    fn num_sets(&self) -> usize {
+        self.descriptors.len()
+    }
+
+    #[inline]
+    fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
+        self.descriptors.get(set).map(|s| s.len())
+    }
+
+    #[inline]
+    fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
+        self.descriptors
+            .get(set)
+            .and_then(|s| s.get(binding).cloned().unwrap_or(None))
+    }
+
+    #[inline]
+    fn num_push_constants_ranges(&self) -> usize {
+        self.push_constants.len()
+    }
+
+    #[inline]
+    fn push_constants_range(&self, num: usize) -> Option<PipelineLayoutDescPcRange> {
+        self.push_constants.get(num).cloned()
+    }
+}
+
 /// Helper struct for comparing descriptor sets.
 ///
 /// > **Note**: For reliability reasons, if you drop/leak this struct before calling `compare` then
@@ -248,7 +385,9 @@ impl<'s> StateCacherDescriptorSets<'s> {
         let raw = set.inner().internal_object();
 
         if self.offset < self.state.len() {
-            if self.state[self.offset] == raw {
+            // Dynamic offsets aren't cached, so sets that have dynamic offsets are always
+            // considered different from the ones that are bound.
+            if self.state[self.offset] == raw && set.dynamic_offsets().is_empty() {
                 self.offset += 1;
                 return;
             }
@@ -353,6 +492,15 @@ mod tests {
     use buffer::BufferUsage;
     use buffer::CpuAccessibleBuffer;
     use command_buffer::state_cacher::StateCacher;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use pipeline::PipelineBindPoint;
+    use std::sync::Arc;
 
     #[test]
     fn vb_caching_single() {
@@ -464,4 +612,91 @@ mod tests {
             assert_eq!(bind_vb.compare(), Some(0 .. 1));
         }
     }
+
+    fn buffer_desc(storage: bool) -> Option<DescriptorDesc> {
+        Some(DescriptorDesc {
+                 ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                  dynamic: Some(false),
+                                                  storage: storage,
+                                              }),
+                 array_count: 1,
+                 runtime_array: false,
+                 stages: ShaderStages::all_graphics(),
+                 readonly: true,
+             })
+    }
+
+    #[test]
+    fn descriptor_sets_kept_for_compatible_layouts() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout_a = RuntimePipelineDesc::new(vec![vec![buffer_desc(false)],
+                                                     vec![buffer_desc(false)]],
+                                                vec![])
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout_a = Arc::new(layout_a);
+        // Same first set as `layout_a`, but the second set is different.
+        let layout_b = RuntimePipelineDesc::new(vec![vec![buffer_desc(false)],
+                                                     vec![buffer_desc(true)]],
+                                                vec![])
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout_b = Arc::new(layout_b);
+        // Incompatible with `layout_a` from the first set.
+        let layout_c = RuntimePipelineDesc::new(vec![vec![buffer_desc(true)],
+                                                     vec![buffer_desc(true)]],
+                                                vec![])
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+
+        let buffer = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), 0u32).unwrap();
+        let set_a0 = PersistentDescriptorSet::start(layout_a.clone(), 0)
+            .add_buffer(buffer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        let set_a1 = PersistentDescriptorSet::start(layout_a.clone(), 1)
+            .add_buffer(buffer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        let set_b1 = PersistentDescriptorSet::start(layout_b.clone(), 1)
+            .add_buffer(buffer)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut cacher = StateCacher::new();
+
+        {
+            let mut bind_sets = cacher.bind_descriptor_sets(PipelineBindPoint::Graphics,
+                                                            &layout_a);
+            bind_sets.add(&set_a0);
+            bind_sets.add(&set_a1);
+            assert_eq!(bind_sets.compare(), Some(0));
+        }
+
+        // The first set stays bound when switching to a layout compatible for that set.
+        assert!(layout_a.is_compatible_for_set(&layout_b, 0));
+        {
+            let mut bind_sets = cacher.bind_descriptor_sets(PipelineBindPoint::Graphics,
+                                                            &layout_b);
+            bind_sets.add(&set_a0);
+            bind_sets.add(&set_b1);
+            assert_eq!(bind_sets.compare(), Some(1));
+        }
+
+        assert!(!layout_b.is_compatible_for_set(&layout_c, 0));
+        {
+            let mut bind_sets = cacher.bind_descriptor_sets(PipelineBindPoint::Graphics,
+                                                            &layout_c);
+            bind_sets.add(&set_a0);
+            bind_sets.add(&set_b1);
+            assert_eq!(bind_sets.compare(), Some(0));
+        }
+    }
 }
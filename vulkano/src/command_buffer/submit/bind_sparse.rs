@@ -141,7 +141,12 @@ impl<'a> SubmitBindSparseBuilder<'a> {
         unsafe {
             debug_assert!(queue.family().supports_sparse_binding());
 
-            let vk = queue.device().pointers();
+            let device = queue.device();
+            if device.is_lost() {
+                return Err(SubmitBindSparseError::DeviceLost);
+            }
+
+            let vk = device.pointers();
             let queue = queue.internal_object_guard();
 
             // We start by storing all the `VkSparseBufferMemoryBindInfo`s of the whole command
@@ -241,10 +246,10 @@ impl<'a> SubmitBindSparseBuilder<'a> {
             };
 
             // Finally executing the command.
-            check_errors(vk.QueueBindSparse(*queue,
-                                            bs_infos.len() as u32,
-                                            bs_infos.as_ptr(),
-                                            self.fence))?;
+            device.track_lost(check_errors(vk.QueueBindSparse(*queue,
+                                                              bs_infos.len() as u32,
+                                                              bs_infos.as_ptr(),
+                                                              self.fence)))?;
             Ok(())
         }
     }
@@ -11,8 +11,10 @@ use smallvec::SmallVec;
 use std::error;
 use std::fmt;
 use std::marker::PhantomData;
-use std::mem;
 use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 
 use device::DeviceOwned;
 use device::Queue;
@@ -35,6 +37,12 @@ pub struct SubmitPresentBuilder<'a> {
     image_indices: SmallVec<[u32; 4]>,
     present_regions: SmallVec<[vk::PresentRegionKHR; 4]>,
     rect_layers: SmallVec<[vk::RectLayerKHR; 4]>,
+    // Locks that provide the host synchronization of the swapchains required by
+    // `vkQueuePresentKHR`.
+    swapchain_locks: SmallVec<[&'a Mutex<bool>; 4]>,
+    // Whether each swapchain holds fullscreen exclusivity. Cleared if the present reports that
+    // exclusivity has been lost.
+    fullscreen_exclusive_held: SmallVec<[&'a AtomicBool; 4]>,
     marker: PhantomData<&'a ()>,
 }
 
@@ -48,6 +56,8 @@ impl<'a> SubmitPresentBuilder<'a> {
             image_indices: SmallVec::new(),
             present_regions: SmallVec::new(),
             rect_layers: SmallVec::new(),
+            swapchain_locks: SmallVec::new(),
+            fullscreen_exclusive_held: SmallVec::new(),
             marker: PhantomData,
         }
     }
@@ -123,6 +133,8 @@ impl<'a> SubmitPresentBuilder<'a> {
 
         self.swapchains.push(swapchain.internal_object());
         self.image_indices.push(image_num);
+        self.swapchain_locks.push(swapchain.host_sync());
+        self.fullscreen_exclusive_held.push(swapchain.fullscreen_exclusive_held());
     }
 
 
@@ -138,6 +150,10 @@ impl<'a> SubmitPresentBuilder<'a> {
             assert!(!self.swapchains.is_empty(),
                     "Tried to submit a present command without any swapchain");
 
+            if queue.device().is_lost() {
+                return Err(SubmitPresentError::DeviceLost);
+            }
+
             let present_regions = {
                 if !self.present_regions.is_empty() {
                     debug_assert!(queue.device().loaded_extensions().khr_incremental_present);
@@ -158,9 +174,19 @@ impl<'a> SubmitPresentBuilder<'a> {
                 }
             };
 
-            let mut results = vec![mem::uninitialized(); self.swapchains.len()]; // TODO: alloca
+            let mut results = vec![vk::SUCCESS; self.swapchains.len()]; // TODO: alloca
+
+            // The locks are always taken in the same order, so that two presents of the same
+            // swapchains from different threads can't deadlock.
+            let mut swapchain_locks = self.swapchain_locks.clone();
+            swapchain_locks.sort_by_key(|lock| *lock as *const _ as usize);
+            let _swapchain_guards = swapchain_locks
+                .iter()
+                .map(|lock| lock.lock().unwrap())
+                .collect::<SmallVec<[_; 4]>>();
 
-            let vk = queue.device().pointers();
+            let device = queue.device();
+            let vk = device.pointers();
             let queue = queue.internal_object_guard();
 
             let infos = vk::PresentInfoKHR {
@@ -177,7 +203,11 @@ impl<'a> SubmitPresentBuilder<'a> {
                 pResults: results.as_mut_ptr(),
             };
 
-            check_errors(vk.QueuePresentKHR(*queue, &infos))?;
+            let result = device.track_lost(check_errors(vk.QueuePresentKHR(*queue, &infos)));
+            if let Err(Error::FullscreenExclusiveLost) = result {
+                clear_lost_fullscreen_exclusive(&results, &self.fullscreen_exclusive_held);
+            }
+            result?;
 
             // TODO: AMD driver initially didn't write the results ; check that it's been fixed
             //for result in results {
@@ -189,6 +219,19 @@ impl<'a> SubmitPresentBuilder<'a> {
     }
 }
 
+// Marks the swapchains whose present returned `ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT` as no
+// longer holding fullscreen exclusivity. Some drivers don't write the per-swapchain results, so a
+// single swapchain is always considered to be the one that lost it.
+fn clear_lost_fullscreen_exclusive(results: &[vk::Result], held: &[&AtomicBool]) {
+    debug_assert_eq!(results.len(), held.len());
+
+    for (&result, flag) in results.iter().zip(held.iter()) {
+        if held.len() == 1 || result == vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT {
+            flag.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
 impl<'a> fmt::Debug for SubmitPresentBuilder<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         fmt.debug_struct("SubmitPresentBuilder")
@@ -215,6 +258,10 @@ pub enum SubmitPresentError {
     /// The surface has changed in a way that makes the swapchain unusable. You must query the
     /// surface's new properties and recreate a new swapchain if you want to continue drawing.
     OutOfDate,
+
+    /// The swapchain has lost or doesn't have fullscreen exclusivity, possibly for
+    /// implementation-specific reasons outside of the application's control.
+    FullscreenExclusiveLost,
 }
 
 impl error::Error for SubmitPresentError {
@@ -225,6 +272,9 @@ impl error::Error for SubmitPresentError {
             SubmitPresentError::DeviceLost => "the connection to the device has been lost",
             SubmitPresentError::SurfaceLost => "the surface of this swapchain is no longer valid",
             SubmitPresentError::OutOfDate => "the swapchain needs to be recreated",
+            SubmitPresentError::FullscreenExclusiveLost => {
+                "the swapchain no longer has fullscreen exclusivity"
+            },
         }
     }
 
@@ -253,6 +303,7 @@ impl From<Error> for SubmitPresentError {
             Error::DeviceLost => SubmitPresentError::DeviceLost,
             Error::SurfaceLost => SubmitPresentError::SurfaceLost,
             Error::OutOfDate => SubmitPresentError::OutOfDate,
+            Error::FullscreenExclusiveLost => SubmitPresentError::FullscreenExclusiveLost,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -269,4 +320,22 @@ mod tests {
             let _ = SubmitPresentBuilder::new().submit(&queue);
         });
     }
+
+    #[test]
+    fn fullscreen_exclusive_lost_single_swapchain() {
+        // The result may not have been written by the driver.
+        let held = AtomicBool::new(true);
+        clear_lost_fullscreen_exclusive(&[vk::SUCCESS], &[&held]);
+        assert!(!held.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn fullscreen_exclusive_lost_multiple_swapchains() {
+        let held = [AtomicBool::new(true), AtomicBool::new(true), AtomicBool::new(false)];
+        let results = [vk::SUCCESS, vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT, vk::SUCCESS];
+        clear_lost_fullscreen_exclusive(&results, &[&held[0], &held[1], &held[2]]);
+        assert!(held[0].load(Ordering::SeqCst));
+        assert!(!held[1].load(Ordering::SeqCst));
+        assert!(!held[2].load(Ordering::SeqCst));
+    }
 }
@@ -14,10 +14,12 @@ use std::marker::PhantomData;
 use std::ptr;
 
 use command_buffer::sys::UnsafeCommandBuffer;
+use device::DeviceOwned;
 use device::Queue;
 use sync::Fence;
 use sync::PipelineStages;
 use sync::Semaphore;
+use sync::TimelineSemaphore;
 
 use Error;
 use OomError;
@@ -27,31 +29,144 @@ use check_errors;
 use vk;
 
 /// Prototype for a submission that executes command buffers.
-// TODO: example here
+///
+/// A submission is made of one or more *batches*. All batches are sent to the implementation
+/// with a single call to `vkQueueSubmit`, which is cheaper than submitting them one by one.
+/// Within a batch, the semaphores are waited upon before the command buffers start executing, and
+/// signaled once they have all finished. A new batch is automatically started whenever a wait
+/// semaphore or a command buffer is added after a command buffer or a signal semaphore, so that
+/// the order in which the methods are called is always respected. You can also start a new batch
+/// manually with `next_batch`.
+///
+/// # Example
+///
+/// A typical frame waits for the swapchain image to be acquired before writing to it, and signals
+/// a semaphore that the presentation waits upon.
+///
+/// ```
+/// use vulkano::command_buffer::submit::SubmitCommandBufferBuilder;
+/// use vulkano::sync::Fence;
+/// use vulkano::sync::PipelineStages;
+/// use vulkano::sync::Semaphore;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+/// # let command_buffer: vulkano::command_buffer::sys::UnsafeCommandBuffer<()> = return;
+///
+/// let image_acquired = Semaphore::from_pool(device.clone()).unwrap();
+/// let rendering_finished = Semaphore::from_pool(device.clone()).unwrap();
+/// let fence = Fence::from_pool(device.clone()).unwrap();
+///
+/// unsafe {
+///     let mut builder = SubmitCommandBufferBuilder::new();
+///     builder.add_wait_semaphore(&image_acquired, PipelineStages {
+///         color_attachment_output: true,
+///         .. PipelineStages::none()
+///     });
+///     builder.add_command_buffer(&command_buffer);
+///     builder.add_signal_semaphore(&rendering_finished);
+///     builder.set_fence_signal(&fence);
+///     builder.submit(&queue).unwrap();
+/// }
+///
+/// // `rendering_finished` can now be passed to `SubmitPresentBuilder::add_wait_semaphore`.
+/// ```
+///
+/// > **Note**: The `GpuFuture` API handles the semaphores and fences automatically and should be
+/// > preferred over this builder.
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
+    batches: SmallVec<[SubmitCommandBufferBatch; 1]>,
+    fence: vk::Fence,
+    // The timeline semaphores to signal, so that they know about the pending signal operations
+    // once the batches are submitted.
+    timeline_signals: SmallVec<[(&'a TimelineSemaphore, u64); 4]>,
+    marker: PhantomData<&'a ()>,
+}
+
+// A single `VkSubmitInfo`.
+#[derive(Debug)]
+struct SubmitCommandBufferBatch {
     wait_semaphores: SmallVec<[vk::Semaphore; 16]>,
     destination_stages: SmallVec<[vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[vk::Semaphore; 16]>,
     command_buffers: SmallVec<[vk::CommandBuffer; 4]>,
-    fence: vk::Fence,
-    marker: PhantomData<&'a ()>,
+    // Values of the timeline semaphores, with one entry per semaphore. The values that correspond
+    // to binary semaphores are ignored.
+    wait_values: SmallVec<[u64; 16]>,
+    signal_values: SmallVec<[u64; 16]>,
+    // True if at least one of the semaphores is a timeline semaphore.
+    has_timeline_semaphores: bool,
 }
 
-impl<'a> SubmitCommandBufferBuilder<'a> {
-    /// Builds a new empty `SubmitCommandBufferBuilder`.
+impl SubmitCommandBufferBatch {
     #[inline]
-    pub fn new() -> SubmitCommandBufferBuilder<'a> {
-        SubmitCommandBufferBuilder {
+    fn new() -> SubmitCommandBufferBatch {
+        SubmitCommandBufferBatch {
             wait_semaphores: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
             command_buffers: SmallVec::new(),
+            wait_values: SmallVec::new(),
+            signal_values: SmallVec::new(),
+            has_timeline_semaphores: false,
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.wait_semaphores.is_empty() && self.signal_semaphores.is_empty() &&
+            self.command_buffers.is_empty()
+    }
+}
+
+impl<'a> SubmitCommandBufferBuilder<'a> {
+    /// Builds a new empty `SubmitCommandBufferBuilder`.
+    #[inline]
+    pub fn new() -> SubmitCommandBufferBuilder<'a> {
+        SubmitCommandBufferBuilder {
+            batches: SmallVec::new(),
             fence: 0,
+            timeline_signals: SmallVec::new(),
             marker: PhantomData,
         }
     }
 
+    /// Returns the number of batches that are going to be submitted.
+    #[inline]
+    pub fn num_batches(&self) -> usize {
+        self.batches.iter().filter(|b| !b.is_empty()).count()
+    }
+
+    /// Starts a new batch. The wait semaphores, command buffers and signal semaphores that are
+    /// added afterwards will belong to this new batch.
+    ///
+    /// Does nothing if the current batch is empty.
+    #[inline]
+    pub fn next_batch(&mut self) {
+        match self.batches.last() {
+            Some(b) if !b.is_empty() => (),
+            _ => return,
+        }
+
+        self.batches.push(SubmitCommandBufferBatch::new());
+    }
+
+    // Returns the batch to which wait semaphores and command buffers can be added.
+    #[inline]
+    fn current_batch(&mut self, adding_wait: bool) -> &mut SubmitCommandBufferBatch {
+        let needs_new = match self.batches.last() {
+            None => true,
+            Some(b) => !b.signal_semaphores.is_empty() ||
+                (adding_wait && !b.command_buffers.is_empty()),
+        };
+
+        if needs_new {
+            self.batches.push(SubmitCommandBufferBatch::new());
+        }
+
+        self.batches.last_mut().unwrap()
+    }
+
     /// Returns true if this builder will signal a fence when submitted.
     ///
     /// # Example
@@ -124,7 +239,9 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     /// # Safety
     ///
-    /// - The stages must be supported by the device.
+    /// - The stages must be supported by the device. The geometry shader stage requires the
+    ///   `geometry_shader` feature, and the tessellation stages require the `tessellation_shader`
+    ///   feature.
     ///
     /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
     ///   that the GPU has at least started executing the command buffers.
@@ -142,9 +259,45 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_wait_semaphore(&mut self, semaphore: &'a Semaphore, stages: PipelineStages) {
         debug_assert!(stages.into_vulkan_bits() != 0);
-        // TODO: debug assert that the device supports the stages
-        self.wait_semaphores.push(semaphore.internal_object());
-        self.destination_stages.push(stages.into_vulkan_bits());
+        debug_assert!(!stages.geometry_shader ||
+                          semaphore.device().enabled_features().geometry_shader);
+        debug_assert!(!(stages.tessellation_control_shader ||
+                            stages.tessellation_evaluation_shader) ||
+                          semaphore.device().enabled_features().tessellation_shader);
+        let batch = self.current_batch(true);
+        batch.wait_semaphores.push(semaphore.internal_object());
+        batch.destination_stages.push(stages.into_vulkan_bits());
+        batch.wait_values.push(0);
+    }
+
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed. The
+    /// command buffers added afterwards wait until the counter of the semaphore reaches `value`.
+    ///
+    /// Only the given `stages` of the command buffers added afterwards will wait upon
+    /// the semaphore. Other stages not included in `stages` can execute before waiting.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, the counter of the semaphore must reach `value`, either
+    ///   because it has already reached it or because the CPU or another submission is going to
+    ///   signal it.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(&mut self, semaphore: &'a TimelineSemaphore,
+                                              value: u64, stages: PipelineStages) {
+        debug_assert!(stages.into_vulkan_bits() != 0);
+        let batch = self.current_batch(true);
+        batch.wait_semaphores.push(semaphore.internal_object());
+        batch.destination_stages.push(stages.into_vulkan_bits());
+        batch.wait_values.push(value);
+        batch.has_timeline_semaphores = true;
     }
 
     /// Adds a command buffer that is executed as part of this command.
@@ -167,7 +320,9 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     #[inline]
     pub unsafe fn add_command_buffer<P>(&mut self, command_buffer: &'a UnsafeCommandBuffer<P>) {
-        self.command_buffers.push(command_buffer.internal_object());
+        self.current_batch(false)
+            .command_buffers
+            .push(command_buffer.internal_object());
     }
 
     /// Returns the number of semaphores to signal.
@@ -175,7 +330,10 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     /// In other words, this is the number of times `add_signal_semaphore` has been called.
     #[inline]
     pub fn num_signal_semaphores(&self) -> usize {
-        self.signal_semaphores.len()
+        self.batches
+            .iter()
+            .map(|b| b.signal_semaphores.len())
+            .sum()
     }
 
     /// Adds a semaphore that is going to be signaled at the end of the submission.
@@ -192,57 +350,137 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     ///
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
-        self.signal_semaphores.push(semaphore.internal_object());
+        if self.batches.is_empty() {
+            self.batches.push(SubmitCommandBufferBatch::new());
+        }
+
+        let batch = self.batches.last_mut().unwrap();
+        batch.signal_semaphores.push(semaphore.internal_object());
+        batch.signal_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is going to be set to `value` at the end of the
+    /// submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the value of the counter when the queue execution reaches
+    ///   this submission, and than the values of all the other pending signal operations on this
+    ///   semaphore that are executed before this one.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(&mut self, semaphore: &'a TimelineSemaphore,
+                                                value: u64) {
+        if self.batches.is_empty() {
+            self.batches.push(SubmitCommandBufferBatch::new());
+        }
+
+        let batch = self.batches.last_mut().unwrap();
+        batch.signal_semaphores.push(semaphore.internal_object());
+        batch.signal_values.push(value);
+        batch.has_timeline_semaphores = true;
+        self.timeline_signals.push((semaphore, value));
     }
 
     /// Submits the command buffer to the given queue.
     ///
+    /// All the batches are submitted with a single call to `vkQueueSubmit`.
+    ///
     /// > **Note**: This is an expensive operation, so you may want to merge as many builders as
     /// > possible together and avoid submitting them one by one.
     ///
     pub fn submit(self, queue: &Queue) -> Result<(), SubmitCommandBufferError> {
         unsafe {
-            let vk = queue.device().pointers();
+            let device = queue.device();
+            if device.is_lost() {
+                return Err(SubmitCommandBufferError::DeviceLost);
+            }
+
+            let vk = device.pointers();
             let queue = queue.internal_object_guard();
 
-            debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
-
-            let batch = vk::SubmitInfo {
-                sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
-                pNext: ptr::null(),
-                waitSemaphoreCount: self.wait_semaphores.len() as u32,
-                pWaitSemaphores: self.wait_semaphores.as_ptr(),
-                pWaitDstStageMask: self.destination_stages.as_ptr(),
-                commandBufferCount: self.command_buffers.len() as u32,
-                pCommandBuffers: self.command_buffers.as_ptr(),
-                signalSemaphoreCount: self.signal_semaphores.len() as u32,
-                pSignalSemaphores: self.signal_semaphores.as_ptr(),
-            };
-
-            check_errors(vk.QueueSubmit(*queue, 1, &batch, self.fence))?;
+            // Must be fully built before `batches`, which points to its elements.
+            let timeline_infos = self.batches
+                .iter()
+                .filter(|b| !b.is_empty())
+                .map(|batch| if batch.has_timeline_semaphores {
+                         Some(vk::TimelineSemaphoreSubmitInfoKHR {
+                                  sType: vk::STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO_KHR,
+                                  pNext: ptr::null(),
+                                  waitSemaphoreValueCount: batch.wait_values.len() as u32,
+                                  pWaitSemaphoreValues: batch.wait_values.as_ptr(),
+                                  signalSemaphoreValueCount: batch.signal_values.len() as u32,
+                                  pSignalSemaphoreValues: batch.signal_values.as_ptr(),
+                              })
+                     } else {
+                         None
+                     })
+                .collect::<SmallVec<[_; 4]>>();
+
+            let batches = self.batches
+                .iter()
+                .filter(|b| !b.is_empty())
+                .zip(timeline_infos.iter())
+                .map(|(batch, timeline_infos)| {
+                    debug_assert_eq!(batch.wait_semaphores.len(), batch.destination_stages.len());
+                    debug_assert_eq!(batch.wait_semaphores.len(), batch.wait_values.len());
+                    debug_assert_eq!(batch.signal_semaphores.len(), batch.signal_values.len());
+
+                    vk::SubmitInfo {
+                        sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
+                        pNext: timeline_infos
+                            .as_ref()
+                            .map(|i| i as *const _ as *const _)
+                            .unwrap_or(ptr::null()),
+                        waitSemaphoreCount: batch.wait_semaphores.len() as u32,
+                        pWaitSemaphores: batch.wait_semaphores.as_ptr(),
+                        pWaitDstStageMask: batch.destination_stages.as_ptr(),
+                        commandBufferCount: batch.command_buffers.len() as u32,
+                        pCommandBuffers: batch.command_buffers.as_ptr(),
+                        signalSemaphoreCount: batch.signal_semaphores.len() as u32,
+                        pSignalSemaphores: batch.signal_semaphores.as_ptr(),
+                    }
+                })
+                .collect::<SmallVec<[_; 4]>>();
+
+            device.track_lost(check_errors(vk.QueueSubmit(*queue,
+                                                          batches.len() as u32,
+                                                          batches.as_ptr(),
+                                                          self.fence)))?;
+
+            for &(semaphore, value) in self.timeline_signals.iter() {
+                semaphore.add_pending_signal(value);
+            }
+
             Ok(())
         }
     }
 
     /// Merges this builder with another builder.
     ///
+    /// The batches of `other` are submitted after the batches of `self`.
+    ///
     /// # Panic
     ///
     /// Panics if both builders have a fence already set.
-    // TODO: create multiple batches instead
     pub fn merge(mut self, other: Self) -> Self {
         assert!(self.fence == 0 || other.fence == 0,
                 "Can't merge two queue submits that both have a fence");
 
-        self.wait_semaphores.extend(other.wait_semaphores);
-        self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
-        self.signal_semaphores.extend(other.signal_semaphores);
-        self.command_buffers.extend(other.command_buffers);
+        self.batches
+            .extend(other.batches.into_iter().filter(|b| !b.is_empty()));
 
         if self.fence == 0 {
             self.fence = other.fence;
         }
 
+        self.timeline_signals.extend(other.timeline_signals.into_iter());
+
         self
     }
 }
@@ -301,6 +539,8 @@ mod tests {
     use super::*;
     use std::time::Duration;
     use sync::Fence;
+    use sync::PipelineStages;
+    use sync::Semaphore;
 
     #[test]
     fn empty_submit() {
@@ -340,6 +580,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn automatic_batches() {
+        unsafe {
+            let (device, _) = gfx_dev_and_queue!();
+
+            let semaphore1 = Semaphore::alloc(device.clone()).unwrap();
+            let semaphore2 = Semaphore::alloc(device.clone()).unwrap();
+            let semaphore3 = Semaphore::alloc(device.clone()).unwrap();
+
+            let mut builder = SubmitCommandBufferBuilder::new();
+            assert_eq!(builder.num_batches(), 0);
+            builder.add_wait_semaphore(&semaphore1, PipelineStages {
+                top_of_pipe: true,
+                ..PipelineStages::none()
+            });
+            builder.add_signal_semaphore(&semaphore2);
+            assert_eq!(builder.num_batches(), 1);
+            builder.add_wait_semaphore(&semaphore3, PipelineStages {
+                top_of_pipe: true,
+                ..PipelineStages::none()
+            });
+            assert_eq!(builder.num_batches(), 2);
+            assert_eq!(builder.num_signal_semaphores(), 1);
+        }
+    }
+
+    #[test]
+    fn merge_keeps_batches() {
+        unsafe {
+            let (device, _) = gfx_dev_and_queue!();
+
+            let semaphore1 = Semaphore::alloc(device.clone()).unwrap();
+            let semaphore2 = Semaphore::alloc(device.clone()).unwrap();
+
+            let mut builder1 = SubmitCommandBufferBuilder::new();
+            builder1.add_signal_semaphore(&semaphore1);
+            let mut builder2 = SubmitCommandBufferBuilder::new();
+            builder2.add_signal_semaphore(&semaphore2);
+
+            let merged = builder1.merge(builder2);
+            assert_eq!(merged.num_batches(), 2);
+            assert_eq!(merged.num_signal_semaphores(), 2);
+        }
+    }
+
     #[test]
     fn merge_both_have_fences() {
         unsafe {
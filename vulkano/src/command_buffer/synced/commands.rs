@@ -10,11 +10,16 @@
 use smallvec::SmallVec;
 use std::any::Any;
 use std::borrow::Cow;
+use std::marker::PhantomData;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
 
+use acceleration_structure::AccelerationStructure;
+use acceleration_structure::AccelerationStructureGeometry;
+use acceleration_structure::BuildAccelerationStructureFlags;
 use buffer::BufferAccess;
+use buffer::TypedBufferAccess;
 use command_buffer::CommandBuffer;
 use command_buffer::synced::base::Command;
 use command_buffer::synced::base::FinalCommand;
@@ -28,26 +33,213 @@ use command_buffer::sys::UnsafeCommandBufferBuilderColorImageClear;
 use command_buffer::sys::UnsafeCommandBufferBuilderExecuteCommands;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
+use command_buffer::sys::UnsafeCommandBufferBuilderPipelineBarrier;
+use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::DescriptorSet;
+use descriptor::descriptor_set::DescriptorWrite;
+use descriptor::descriptor_set::PersistentDescriptorSetResources;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use format::ClearValue;
 use framebuffer::FramebufferAbstract;
 use framebuffer::SubpassContents;
 use image::ImageAccess;
 use image::ImageLayout;
+use image::ImageViewAccess;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
+use pipeline::PipelineBindPoint;
+use pipeline::RayTracingPipelineAbstract;
+use pipeline::ShaderBindingTable;
 use pipeline::input_assembly::IndexType;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
+use query::QueryResultElement;
+use query::QueryResultFlags;
+use query::UnsafeQueryPool;
 use sampler::Filter;
 use sync::AccessFlagBits;
+use sync::BufferMemoryBarrier;
 use sync::Event;
+use sync::ImageMemoryBarrier;
+use sync::MemoryBarrier;
 use sync::PipelineStages;
 
 impl<P> SyncCommandBufferBuilder<P> {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering<B>(&mut self, buffer: B, inverted: bool)
+                                                 -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B> {
+            buffer: B,
+            inverted: bool,
+        }
+
+        impl<P, B> Command<P> for Cmd<B>
+            where B: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginConditionalRenderingEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.begin_conditional_rendering(&self.buffer, self.inverted);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                    where B: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBeginConditionalRenderingEXT"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        assert_eq!(num, 0);
+                        "predicate".into()
+                    }
+                }
+                Box::new(Fin(self.buffer))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.buffer
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                assert_eq!(num, 0);
+                "predicate".into()
+            }
+        }
+
+        self.append_command(Cmd { buffer, inverted });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               false,
+                               PipelineStages {
+                                   conditional_rendering: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   conditional_rendering_read: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    ///
+    /// The counters are read from `counter_buffers`, which can be empty.
+    #[inline]
+    pub unsafe fn begin_transform_feedback(&mut self,
+                                           counter_buffers: Vec<Box<BufferAccess + Send + Sync>>)
+                                           -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            buffers: Vec<Box<BufferAccess + Send + Sync>>,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginTransformFeedbackEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.begin_transform_feedback(self.buffers.iter().map(|b| &**b as &BufferAccess));
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Vec<Box<BufferAccess + Send + Sync>>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBeginTransformFeedbackEXT"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        &self.0[num]
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        format!("Counter buffer #{}", num).into()
+                    }
+                }
+                Box::new(Fin(self.buffers))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                &self.buffers[num]
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                format!("Counter buffer #{}", num).into()
+            }
+        }
+
+        let num_buffers = counter_buffers.len();
+        self.append_command(Cmd { buffers: counter_buffers });
+
+        for n in 0 .. num_buffers {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   false,
+                                   PipelineStages {
+                                       transform_feedback: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       transform_feedback_counter_read: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdBeginQuery` on the builder.
+    #[inline]
+    pub unsafe fn begin_query(&mut self, pool: Arc<UnsafeQueryPool>, query: u32, precise: bool) {
+        struct Cmd {
+            pool: Arc<UnsafeQueryPool>,
+            query: u32,
+            precise: bool,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBeginQuery"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.begin_query(self.pool.query(self.query).unwrap(), self.precise);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Arc<UnsafeQueryPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBeginQuery"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd {
+                                pool,
+                                query,
+                                precise,
+                            });
+    }
+
     /// Calls `vkBeginRenderPass` on the builder.
     // TODO: it shouldn't be possible to get an error if the framebuffer checked conflicts already
     // TODO: after begin_render_pass has been called, flushing should be forbidden and an error
@@ -279,6 +471,42 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { pipeline });
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing<Rp>(&mut self, pipeline: Rp)
+        where Rp: RayTracingPipelineAbstract + Send + Sync + 'static
+    {
+        struct Cmd<Rp> {
+            pipeline: Rp,
+        }
+
+        impl<P, Rp> Command<P> for Cmd<Rp>
+            where Rp: RayTracingPipelineAbstract + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdBindPipeline"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.bind_pipeline_ray_tracing(&self.pipeline);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<Rp>(Rp);
+                impl<Rp> FinalCommand for Fin<Rp>
+                    where Rp: Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBindPipeline"
+                    }
+                }
+                Box::new(Fin(self.pipeline))
+            }
+        }
+
+        self.append_command(Cmd { pipeline });
+    }
+
     /// Starts the process of binding descriptor sets. Returns an intermediate struct which can be
     /// used to add the sets.
     #[inline]
@@ -300,6 +528,73 @@ impl<P> SyncCommandBufferBuilder<P> {
         }
     }
 
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers(&mut self, first_binding: u32,
+                                                  buffers: Vec<Box<BufferAccess + Send + Sync>>)
+                                                  -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_binding: u32,
+            buffers: Vec<Box<BufferAccess + Send + Sync>>,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdBindTransformFeedbackBuffersEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let buffers = self.buffers.iter().map(|b| &**b as &BufferAccess);
+                out.bind_transform_feedback_buffers(self.first_binding, buffers);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Vec<Box<BufferAccess + Send + Sync>>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBindTransformFeedbackBuffersEXT"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        &self.0[num]
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        format!("Transform feedback buffer #{}", num).into()
+                    }
+                }
+                Box::new(Fin(self.buffers))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                &self.buffers[num]
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                format!("Transform feedback buffer #{}", num).into()
+            }
+        }
+
+        let num_buffers = buffers.len();
+        self.append_command(Cmd { first_binding, buffers });
+
+        for n in 0 .. num_buffers {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   true,
+                                   PipelineStages {
+                                       transform_feedback: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       transform_feedback_write: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        Ok(())
+    }
+
     /// Calls `vkCmdCopyImage` on the builder.
     ///
     /// Does nothing if the list of regions is empty, as it would be a no-op and isn't a valid
@@ -562,60 +857,377 @@ impl<P> SyncCommandBufferBuilder<P> {
         Ok(())
     }
 
-    /// Calls `vkCmdClearColorImage` on the builder.
-    ///
-    /// Does nothing if the list of regions is empty, as it would be a no-op and isn't a valid
-    /// usage of the command anyway.
-    pub unsafe fn clear_color_image<I, R>(&mut self, image: I, layout: ImageLayout,
-                                          color: ClearValue, regions: R)
-                                          -> Result<(), SyncCommandBufferBuilderError>
-        where I: ImageAccess + Send + Sync + 'static,
-              R: Iterator<Item = UnsafeCommandBufferBuilderColorImageClear> + Send + Sync + 'static
+    /// Adds a pipeline barrier that applies to a range of a buffer.
+    pub unsafe fn buffer_memory_barrier<B>(&mut self, barrier: BufferMemoryBarrier<B>)
+                                           -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static
     {
-        struct Cmd<I, R> {
-            image: Option<I>,
-            layout: ImageLayout,
-            color: ClearValue,
-            regions: Option<R>,
+        struct Cmd<B> {
+            barrier: BufferMemoryBarrier<B>,
         }
 
-        impl<P, I, R> Command<P> for Cmd<I, R>
-            where I: ImageAccess + Send + Sync + 'static,
-                  R: Iterator<Item = UnsafeCommandBufferBuilderColorImageClear>
-                         + Send
-                         + Sync
-                         + 'static
+        impl<P, B> Command<P> for Cmd<B>
+            where B: BufferAccess + Send + Sync + 'static
         {
             fn name(&self) -> &'static str {
-                "vkCmdClearColorImage"
+                "vkCmdPipelineBarrier"
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
-                out.clear_color_image(self.image.as_ref().unwrap(),
-                                      self.layout,
-                                      self.color,
-                                      self.regions.take().unwrap());
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_buffer_memory_barrier(&self.barrier.buffer,
+                                                  self.barrier.source_stages,
+                                                  self.barrier.source_access,
+                                                  self.barrier.destination_stages,
+                                                  self.barrier.destination_access,
+                                                  false,
+                                                  self.barrier.queue_family_transfer,
+                                                  self.barrier.offset,
+                                                  self.barrier.size);
+                out.pipeline_barrier(&barrier);
             }
 
-            fn into_final_command(mut self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
-                struct Fin<I>(I);
-                impl<I> FinalCommand for Fin<I>
-                    where I: ImageAccess + Send + Sync + 'static
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                    where B: BufferAccess + Send + Sync + 'static
                 {
                     fn name(&self) -> &'static str {
-                        "vkCmdClearColorImage"
+                        "vkCmdPipelineBarrier"
                     }
-                    fn image(&self, num: usize) -> &ImageAccess {
+                    fn buffer(&self, num: usize) -> &BufferAccess {
                         assert_eq!(num, 0);
                         &self.0
                     }
-                    fn image_name(&self, num: usize) -> Cow<'static, str> {
-                        assert_eq!(num, 0);
-                        "target".into()
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "buffer".into()
                     }
                 }
+                let cmd = *self;
+                Box::new(Fin(cmd.barrier.buffer))
+            }
 
-                // Note: borrow checker somehow doesn't accept `self.image` without using an Option.
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.barrier.buffer
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "buffer".into()
+            }
+        }
+
+        self.append_command(Cmd { barrier });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               true,
+                               PipelineStages {
+                                   all_commands: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   memory_read: true,
+                                   memory_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
+    /// Adds a pipeline barrier that releases or acquires the ownership of a buffer between two
+    /// queue families.
+    ///
+    /// If `release` is true, the barrier is the release half of the transfer and must be
+    /// submitted to a queue of `source_family`. Otherwise it is the acquire half and must be
+    /// submitted to a queue of `destination_family`.
+    pub unsafe fn buffer_ownership_transfer<B>(&mut self, buffer: B, source_family: u32,
+                                               destination_family: u32, release: bool)
+                                               -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B> {
+            buffer: B,
+            source_family: u32,
+            destination_family: u32,
+            release: bool,
+        }
+
+        impl<P, B> Command<P> for Cmd<B>
+            where B: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdPipelineBarrier"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let (source_stage, source_access, destination_stage, destination_access) =
+                    ownership_transfer_stages(self.release);
+                let size = self.buffer.size();
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_buffer_memory_barrier(&self.buffer,
+                                                  source_stage,
+                                                  source_access,
+                                                  destination_stage,
+                                                  destination_access,
+                                                  false,
+                                                  Some((self.source_family,
+                                                        self.destination_family)),
+                                                  0,
+                                                  size);
+                out.pipeline_barrier(&barrier);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                    where B: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdPipelineBarrier"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "buffer".into()
+                    }
+                }
+                Box::new(Fin(self.buffer))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.buffer
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "buffer".into()
+            }
+        }
+
+        self.append_command(Cmd {
+                                buffer,
+                                source_family,
+                                destination_family,
+                                release,
+                            });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               true,
+                               PipelineStages {
+                                   all_commands: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   memory_read: true,
+                                   memory_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    ///
+    /// `scratch_address` must be the device address of a location within `scratch` that is
+    /// suitably aligned.
+    pub unsafe fn build_acceleration_structure<S>(&mut self,
+                                                  structure: Arc<AccelerationStructure>,
+                                                  geometries: Vec<AccelerationStructureGeometry>,
+                                                  flags: BuildAccelerationStructureFlags,
+                                                  scratch: S, scratch_address: u64)
+                                                  -> Result<(), SyncCommandBufferBuilderError>
+        where S: BufferAccess + Send + Sync + 'static
+    {
+        // Buffers 0 and 1 are the storage of the structure and the scratch buffer. The buffers
+        // of the geometries come after.
+        fn buffer<'a>(structure: &'a AccelerationStructure, scratch: &'a BufferAccess,
+                      geometries: &'a [AccelerationStructureGeometry], num: usize)
+                      -> &'a BufferAccess {
+            match num {
+                0 => &**structure.buffer(),
+                1 => scratch,
+                n => {
+                    &**geometries
+                        .iter()
+                        .flat_map(|g| g.buffers().into_iter())
+                        .nth(n - 2)
+                        .expect("out of range buffer index")
+                },
+            }
+        }
+
+        fn buffer_name(num: usize) -> Cow<'static, str> {
+            match num {
+                0 => "acceleration structure".into(),
+                1 => "scratch buffer".into(),
+                n => format!("geometry buffer {}", n - 2).into(),
+            }
+        }
+
+        struct Cmd<S> {
+            structure: Arc<AccelerationStructure>,
+            geometries: Vec<AccelerationStructureGeometry>,
+            flags: BuildAccelerationStructureFlags,
+            scratch: S,
+            scratch_address: u64,
+        }
+
+        impl<P, S> Command<P> for Cmd<S>
+            where S: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdBuildAccelerationStructuresKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.build_acceleration_structure(&self.structure,
+                                                 &self.geometries,
+                                                 self.flags,
+                                                 self.scratch_address);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<S>(Arc<AccelerationStructure>, Vec<AccelerationStructureGeometry>, S);
+                impl<S> FinalCommand for Fin<S>
+                    where S: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdBuildAccelerationStructuresKHR"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        buffer(&self.0, &self.2, &self.1, num)
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        buffer_name(num)
+                    }
+                }
+                Box::new(Fin(self.structure, self.geometries, self.scratch))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                buffer(&self.structure, &self.scratch, &self.geometries, num)
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                buffer_name(num)
+            }
+        }
+
+        let num_geometry_buffers = geometries
+            .iter()
+            .map(|g| g.buffers().len())
+            .sum::<usize>();
+
+        self.append_command(Cmd {
+                                structure,
+                                geometries,
+                                flags,
+                                scratch,
+                                scratch_address,
+                            });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               true,
+                               PipelineStages {
+                                   acceleration_structure_build: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   acceleration_structure_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               1,
+                               true,
+                               PipelineStages {
+                                   acceleration_structure_build: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   acceleration_structure_read: true,
+                                   acceleration_structure_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        for num in 0 .. num_geometry_buffers {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   2 + num,
+                                   false,
+                                   PipelineStages {
+                                       acceleration_structure_build: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       shader_read: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+        Ok(())
+    }
+
+    /// Calls `vkCmdClearColorImage` on the builder.
+    ///
+    /// Does nothing if the list of regions is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    pub unsafe fn clear_color_image<I, R>(&mut self, image: I, layout: ImageLayout,
+                                          color: ClearValue, regions: R)
+                                          -> Result<(), SyncCommandBufferBuilderError>
+        where I: ImageAccess + Send + Sync + 'static,
+              R: Iterator<Item = UnsafeCommandBufferBuilderColorImageClear> + Send + Sync + 'static
+    {
+        struct Cmd<I, R> {
+            image: Option<I>,
+            layout: ImageLayout,
+            color: ClearValue,
+            regions: Option<R>,
+        }
+
+        impl<P, I, R> Command<P> for Cmd<I, R>
+            where I: ImageAccess + Send + Sync + 'static,
+                  R: Iterator<Item = UnsafeCommandBufferBuilderColorImageClear>
+                         + Send
+                         + Sync
+                         + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdClearColorImage"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.clear_color_image(self.image.as_ref().unwrap(),
+                                      self.layout,
+                                      self.color,
+                                      self.regions.take().unwrap());
+            }
+
+            fn into_final_command(mut self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<I>(I);
+                impl<I> FinalCommand for Fin<I>
+                    where I: ImageAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdClearColorImage"
+                    }
+                    fn image(&self, num: usize) -> &ImageAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn image_name(&self, num: usize) -> Cow<'static, str> {
+                        assert_eq!(num, 0);
+                        "target".into()
+                    }
+                }
+
+                // Note: borrow checker somehow doesn't accept `self.image` without using an Option.
                 Box::new(Fin(self.image.take().unwrap()))
             }
 
@@ -1013,6 +1625,95 @@ impl<P> SyncCommandBufferBuilder<P> {
         Ok(())
     }
 
+    /// Calls `vkCmdCopyQueryPoolResults` on the builder.
+    #[inline]
+    pub unsafe fn copy_query_pool_results<D, T>(&mut self, pool: Arc<UnsafeQueryPool>,
+                                                first_query: u32, query_count: u32,
+                                                destination: D, stride: usize,
+                                                flags: QueryResultFlags)
+                                                -> Result<(), SyncCommandBufferBuilderError>
+        where D: TypedBufferAccess<Content = [T]> + Send + Sync + 'static,
+              T: QueryResultElement
+    {
+        struct Cmd<D, T> {
+            pool: Arc<UnsafeQueryPool>,
+            first_query: u32,
+            query_count: u32,
+            destination: D,
+            stride: usize,
+            flags: QueryResultFlags,
+            marker: PhantomData<T>,
+        }
+
+        impl<P, D, T> Command<P> for Cmd<D, T>
+            where D: TypedBufferAccess<Content = [T]> + Send + Sync + 'static,
+                  T: QueryResultElement
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdCopyQueryPoolResults"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let range = self.pool
+                    .queries_range(self.first_query, self.query_count)
+                    .unwrap();
+                out.copy_query_pool_results(range, &self.destination, self.stride, self.flags);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<D>(Arc<UnsafeQueryPool>, D);
+                impl<D> FinalCommand for Fin<D>
+                    where D: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdCopyQueryPoolResults"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.1
+                    }
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "destination".into()
+                    }
+                }
+                Box::new(Fin(self.pool, self.destination))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.destination
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "destination".into()
+            }
+        }
+
+        self.append_command(Cmd {
+                                pool,
+                                first_query,
+                                query_count,
+                                destination,
+                                stride,
+                                flags,
+                                marker: PhantomData,
+                            });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               true,
+                               PipelineStages {
+                                   transfer: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   transfer_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
     /// Calls `vkCmdDispatch` on the builder.
     #[inline]
     pub unsafe fn dispatch(&mut self, dimensions: [u32; 3]) {
@@ -1332,11 +2033,442 @@ impl<P> SyncCommandBufferBuilder<P> {
         Ok(())
     }
 
-    /// Calls `vkCmdEndRenderPass` on the builder.
+    /// Calls `vkCmdDrawIndexedIndirectCountKHR` on the builder.
     #[inline]
-    pub unsafe fn end_render_pass(&mut self) {
-        struct Cmd;
-
+    pub unsafe fn draw_indexed_indirect_count<B, C>(&mut self, buffer: B, count_buffer: C,
+                                                    max_draw_count: u32, stride: u32)
+                                                    -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static,
+              C: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B, C> {
+            buffer: Option<B>,
+            count_buffer: Option<C>,
+            max_draw_count: u32,
+            stride: u32,
+        }
+
+        impl<P, B, C> Command<P> for Cmd<B, C>
+            where B: BufferAccess + Send + Sync + 'static,
+                  C: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawIndexedIndirectCountKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.draw_indexed_indirect_count(self.buffer.as_ref().unwrap(),
+                                                self.count_buffer.as_ref().unwrap(),
+                                                self.max_draw_count,
+                                                self.stride);
+            }
+
+            fn into_final_command(mut self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B, C>(B, C);
+                impl<B, C> FinalCommand for Fin<B, C>
+                    where B: BufferAccess + Send + Sync + 'static,
+                          C: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdDrawIndexedIndirectCountKHR"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        match num {
+                            0 => &self.0,
+                            1 => &self.1,
+                            _ => panic!(),
+                        }
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        match num {
+                            0 => "indirect buffer".into(),
+                            1 => "count buffer".into(),
+                            _ => panic!(),
+                        }
+                    }
+                }
+                Box::new(Fin(self.buffer.take().unwrap(),
+                             self.count_buffer.take().unwrap()))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                match num {
+                    0 => self.buffer.as_ref().unwrap(),
+                    1 => self.count_buffer.as_ref().unwrap(),
+                    _ => panic!(),
+                }
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                match num {
+                    0 => "indirect buffer".into(),
+                    1 => "count buffer".into(),
+                    _ => panic!(),
+                }
+            }
+        }
+
+        self.append_command(Cmd {
+                                buffer: Some(buffer),
+                                count_buffer: Some(count_buffer),
+                                max_draw_count,
+                                stride,
+                            });
+
+        for n in 0 .. 2 {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   false,
+                                   PipelineStages {
+                                       draw_indirect: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       indirect_command_read: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndirectCountKHR` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_count<B, C>(&mut self, buffer: B, count_buffer: C,
+                                            max_draw_count: u32, stride: u32)
+                                            -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static,
+              C: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B, C> {
+            buffer: Option<B>,
+            count_buffer: Option<C>,
+            max_draw_count: u32,
+            stride: u32,
+        }
+
+        impl<P, B, C> Command<P> for Cmd<B, C>
+            where B: BufferAccess + Send + Sync + 'static,
+                  C: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawIndirectCountKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.draw_indirect_count(self.buffer.as_ref().unwrap(),
+                                        self.count_buffer.as_ref().unwrap(),
+                                        self.max_draw_count,
+                                        self.stride);
+            }
+
+            fn into_final_command(mut self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B, C>(B, C);
+                impl<B, C> FinalCommand for Fin<B, C>
+                    where B: BufferAccess + Send + Sync + 'static,
+                          C: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdDrawIndirectCountKHR"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        match num {
+                            0 => &self.0,
+                            1 => &self.1,
+                            _ => panic!(),
+                        }
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        match num {
+                            0 => "indirect buffer".into(),
+                            1 => "count buffer".into(),
+                            _ => panic!(),
+                        }
+                    }
+                }
+                Box::new(Fin(self.buffer.take().unwrap(),
+                             self.count_buffer.take().unwrap()))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                match num {
+                    0 => self.buffer.as_ref().unwrap(),
+                    1 => self.count_buffer.as_ref().unwrap(),
+                    _ => panic!(),
+                }
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                match num {
+                    0 => "indirect buffer".into(),
+                    1 => "count buffer".into(),
+                    _ => panic!(),
+                }
+            }
+        }
+
+        self.append_command(Cmd {
+                                buffer: Some(buffer),
+                                count_buffer: Some(count_buffer),
+                                max_draw_count,
+                                stride,
+                            });
+
+        for n in 0 .. 2 {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   false,
+                                   PipelineStages {
+                                       draw_indirect: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       indirect_command_read: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_byte_count<B>(&mut self, instance_count: u32,
+                                              first_instance: u32, counter_buffer: B,
+                                              counter_offset: u32, vertex_stride: u32)
+                                              -> Result<(), SyncCommandBufferBuilderError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B> {
+            instance_count: u32,
+            first_instance: u32,
+            counter_buffer: B,
+            counter_offset: u32,
+            vertex_stride: u32,
+        }
+
+        impl<P, B> Command<P> for Cmd<B>
+            where B: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawIndirectByteCountEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.draw_indirect_byte_count(self.instance_count,
+                                             self.first_instance,
+                                             &self.counter_buffer,
+                                             self.counter_offset,
+                                             self.vertex_stride);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                    where B: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdDrawIndirectByteCountEXT"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        assert_eq!(num, 0);
+                        "counter buffer".into()
+                    }
+                }
+                Box::new(Fin(self.counter_buffer))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.counter_buffer
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                assert_eq!(num, 0);
+                "counter buffer".into()
+            }
+        }
+
+        self.append_command(Cmd {
+                                instance_count,
+                                first_instance,
+                                counter_buffer,
+                                counter_offset,
+                                vertex_stride,
+                            });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               false,
+                               PipelineStages {
+                                   draw_indirect: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   transform_feedback_counter_read: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
+    /// Calls `vkCmdDrawMeshTasksEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks(&mut self, group_counts: [u32; 3]) {
+        struct Cmd {
+            group_counts: [u32; 3],
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdDrawMeshTasksEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.draw_mesh_tasks(self.group_counts);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                Box::new("vkCmdDrawMeshTasksEXT")
+            }
+        }
+
+        self.append_command(Cmd { group_counts });
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd;
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndConditionalRenderingEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.end_conditional_rendering();
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                Box::new("vkCmdEndConditionalRenderingEXT")
+            }
+        }
+
+        self.append_command(Cmd);
+    }
+
+    /// Calls `vkCmdEndQuery` on the builder.
+    #[inline]
+    pub unsafe fn end_query(&mut self, pool: Arc<UnsafeQueryPool>, query: u32) {
+        struct Cmd {
+            pool: Arc<UnsafeQueryPool>,
+            query: u32,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndQuery"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.end_query(self.pool.query(self.query).unwrap());
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Arc<UnsafeQueryPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdEndQuery"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd { pool, query });
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    ///
+    /// The counters are written to `counter_buffers`, which can be empty.
+    #[inline]
+    pub unsafe fn end_transform_feedback(&mut self,
+                                         counter_buffers: Vec<Box<BufferAccess + Send + Sync>>)
+                                         -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            buffers: Vec<Box<BufferAccess + Send + Sync>>,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdEndTransformFeedbackEXT"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.end_transform_feedback(self.buffers.iter().map(|b| &**b as &BufferAccess));
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Vec<Box<BufferAccess + Send + Sync>>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdEndTransformFeedbackEXT"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        &self.0[num]
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        format!("Counter buffer #{}", num).into()
+                    }
+                }
+                Box::new(Fin(self.buffers))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                &self.buffers[num]
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                format!("Counter buffer #{}", num).into()
+            }
+        }
+
+        let num_buffers = counter_buffers.len();
+        self.append_command(Cmd { buffers: counter_buffers });
+
+        for n in 0 .. num_buffers {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   true,
+                                   PipelineStages {
+                                       transform_feedback: true,
+                                       ..PipelineStages::none()
+                                   },
+                                   AccessFlagBits {
+                                       transform_feedback_counter_write: true,
+                                       ..AccessFlagBits::none()
+                                   },
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndRenderPass` on the builder.
+    #[inline]
+    pub unsafe fn end_render_pass(&mut self) {
+        struct Cmd;
+
         impl<P> Command<P> for Cmd {
             fn name(&self) -> &'static str {
                 "vkCmdEndRenderPass"
@@ -1366,71 +2498,284 @@ impl<P> SyncCommandBufferBuilder<P> {
         }
     }
 
-    /// Calls `vkCmdFillBuffer` on the builder.
-    #[inline]
-    pub unsafe fn fill_buffer<B>(&mut self, buffer: B, data: u32)
-        where B: BufferAccess + Send + Sync + 'static
+    /// Calls `vkCmdFillBuffer` on the builder.
+    #[inline]
+    pub unsafe fn fill_buffer<B>(&mut self, buffer: B, data: u32)
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        struct Cmd<B> {
+            buffer: B,
+            data: u32,
+        }
+
+        impl<P, B> Command<P> for Cmd<B>
+            where B: BufferAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdFillBuffer"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.fill_buffer(&self.buffer, self.data);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<B>(B);
+                impl<B> FinalCommand for Fin<B>
+                    where B: BufferAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdFillBuffer"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                        "destination".into()
+                    }
+                }
+                Box::new(Fin(self.buffer))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &self.buffer
+            }
+
+            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
+                "destination".into()
+            }
+        }
+
+        self.append_command(Cmd { buffer, data });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               true,
+                               PipelineStages {
+                                   transfer: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   transfer_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)
+            .unwrap();
+    }
+
+    /// Adds a pipeline barrier that applies to a range of an image. The image must be in the
+    /// layout of the barrier, and stays in this layout.
+    pub unsafe fn image_memory_barrier<I>(&mut self, barrier: ImageMemoryBarrier<I>)
+                                          -> Result<(), SyncCommandBufferBuilderError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        struct Cmd<I> {
+            barrier: ImageMemoryBarrier<I>,
+        }
+
+        impl<P, I> Command<P> for Cmd<I>
+            where I: ImageAccess + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdPipelineBarrier"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_image_memory_barrier(&self.barrier.image,
+                                                 self.barrier.mipmaps.clone(),
+                                                 self.barrier.layers.clone(),
+                                                 self.barrier.source_stages,
+                                                 self.barrier.source_access,
+                                                 self.barrier.destination_stages,
+                                                 self.barrier.destination_access,
+                                                 false,
+                                                 self.barrier.queue_family_transfer,
+                                                 self.barrier.layout,
+                                                 self.barrier.layout);
+                out.pipeline_barrier(&barrier);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<I>(I);
+                impl<I> FinalCommand for Fin<I>
+                    where I: ImageAccess + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdPipelineBarrier"
+                    }
+                    fn image(&self, num: usize) -> &ImageAccess {
+                        assert_eq!(num, 0);
+                        &self.0
+                    }
+                    fn image_name(&self, _: usize) -> Cow<'static, str> {
+                        "image".into()
+                    }
+                }
+                let cmd = *self;
+                Box::new(Fin(cmd.barrier.image))
+            }
+
+            fn image(&self, num: usize) -> &ImageAccess {
+                assert_eq!(num, 0);
+                &self.barrier.image
+            }
+
+            fn image_name(&self, _: usize) -> Cow<'static, str> {
+                "image".into()
+            }
+        }
+
+        let layout = barrier.layout;
+        self.append_command(Cmd { barrier });
+        self.prev_cmd_resource(KeyTy::Image,
+                               0,
+                               true,
+                               PipelineStages {
+                                   all_commands: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   memory_read: true,
+                                   memory_write: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               layout,
+                               layout)?;
+        Ok(())
+    }
+
+    /// Adds a pipeline barrier that releases or acquires the ownership of an image between two
+    /// queue families. The image must be in `layout`, and stays in this layout.
+    ///
+    /// If `release` is true, the barrier is the release half of the transfer and must be
+    /// submitted to a queue of `source_family`. Otherwise it is the acquire half and must be
+    /// submitted to a queue of `destination_family`.
+    pub unsafe fn image_ownership_transfer<I>(&mut self, image: I, layout: ImageLayout,
+                                              source_family: u32, destination_family: u32,
+                                              release: bool)
+                                              -> Result<(), SyncCommandBufferBuilderError>
+        where I: ImageAccess + Send + Sync + 'static
     {
-        struct Cmd<B> {
-            buffer: B,
-            data: u32,
+        struct Cmd<I> {
+            image: I,
+            layout: ImageLayout,
+            source_family: u32,
+            destination_family: u32,
+            release: bool,
         }
 
-        impl<P, B> Command<P> for Cmd<B>
-            where B: BufferAccess + Send + Sync + 'static
+        impl<P, I> Command<P> for Cmd<I>
+            where I: ImageAccess + Send + Sync + 'static
         {
             fn name(&self) -> &'static str {
-                "vkCmdFillBuffer"
+                "vkCmdPipelineBarrier"
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
-                out.fill_buffer(&self.buffer, self.data);
+                let (source_stage, source_access, destination_stage, destination_access) =
+                    ownership_transfer_stages(self.release);
+                let mipmaps = 0 .. self.image.mipmap_levels();
+                let layers = 0 .. self.image.dimensions().array_layers();
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_image_memory_barrier(&self.image,
+                                                 mipmaps,
+                                                 layers,
+                                                 source_stage,
+                                                 source_access,
+                                                 destination_stage,
+                                                 destination_access,
+                                                 false,
+                                                 Some((self.source_family,
+                                                       self.destination_family)),
+                                                 self.layout,
+                                                 self.layout);
+                out.pipeline_barrier(&barrier);
             }
 
             fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
-                struct Fin<B>(B);
-                impl<B> FinalCommand for Fin<B>
-                    where B: BufferAccess + Send + Sync + 'static
+                struct Fin<I>(I);
+                impl<I> FinalCommand for Fin<I>
+                    where I: ImageAccess + Send + Sync + 'static
                 {
                     fn name(&self) -> &'static str {
-                        "vkCmdFillBuffer"
+                        "vkCmdPipelineBarrier"
                     }
-                    fn buffer(&self, num: usize) -> &BufferAccess {
+                    fn image(&self, num: usize) -> &ImageAccess {
                         assert_eq!(num, 0);
                         &self.0
                     }
-                    fn buffer_name(&self, _: usize) -> Cow<'static, str> {
-                        "destination".into()
+                    fn image_name(&self, _: usize) -> Cow<'static, str> {
+                        "image".into()
                     }
                 }
-                Box::new(Fin(self.buffer))
+                Box::new(Fin(self.image))
             }
 
-            fn buffer(&self, num: usize) -> &BufferAccess {
+            fn image(&self, num: usize) -> &ImageAccess {
                 assert_eq!(num, 0);
-                &self.buffer
+                &self.image
             }
 
-            fn buffer_name(&self, _: usize) -> Cow<'static, str> {
-                "destination".into()
+            fn image_name(&self, _: usize) -> Cow<'static, str> {
+                "image".into()
             }
         }
 
-        self.append_command(Cmd { buffer, data });
-        self.prev_cmd_resource(KeyTy::Buffer,
+        self.append_command(Cmd {
+                                image,
+                                layout,
+                                source_family,
+                                destination_family,
+                                release,
+                            });
+        self.prev_cmd_resource(KeyTy::Image,
                                0,
                                true,
                                PipelineStages {
-                                   transfer: true,
+                                   all_commands: true,
                                    ..PipelineStages::none()
                                },
                                AccessFlagBits {
-                                   transfer_write: true,
+                                   memory_read: true,
+                                   memory_write: true,
                                    ..AccessFlagBits::none()
                                },
-                               ImageLayout::Undefined,
-                               ImageLayout::Undefined)
-            .unwrap();
+                               layout,
+                               layout)?;
+        Ok(())
+    }
+
+    /// Adds a pipeline barrier that applies to all the resources.
+    #[inline]
+    pub unsafe fn memory_barrier(&mut self, barrier: MemoryBarrier) {
+        struct Cmd {
+            barrier: MemoryBarrier,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdPipelineBarrier"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let mut barrier = UnsafeCommandBufferBuilderPipelineBarrier::new();
+                barrier.add_memory_barrier(self.barrier.source_stages,
+                                           self.barrier.source_access,
+                                           self.barrier.destination_stages,
+                                           self.barrier.destination_access,
+                                           false);
+                out.pipeline_barrier(&barrier);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                Box::new("vkCmdPipelineBarrier")
+            }
+        }
+
+        self.append_command(Cmd { barrier });
     }
 
     /// Calls `vkCmdNextSubpass` on the builder.
@@ -1517,6 +2862,146 @@ impl<P> SyncCommandBufferBuilder<P> {
                             });
     }
 
+    /// Calls `vkCmdPushDescriptorSetKHR` on the builder.
+    ///
+    /// `resources` must contain the resources that `writes` refer to.
+    pub unsafe fn push_descriptor_set<Pl, R>(&mut self, bind_point: PipelineBindPoint,
+                                             pipeline_layout: Pl, set_num: u32,
+                                             writes: Vec<DescriptorWrite>, resources: R)
+                                             -> Result<(), SyncCommandBufferBuilderError>
+        where Pl: PipelineLayoutAbstract + Send + Sync + 'static,
+              R: PersistentDescriptorSetResources + Send + Sync + 'static
+    {
+        struct Cmd<Pl, R> {
+            bind_point: PipelineBindPoint,
+            pipeline_layout: Pl,
+            set_num: u32,
+            writes: Vec<DescriptorWrite>,
+            resources: R,
+        }
+
+        impl<P, Pl, R> Command<P> for Cmd<Pl, R>
+            where Pl: PipelineLayoutAbstract + Send + Sync + 'static,
+                  R: PersistentDescriptorSetResources + Send + Sync + 'static
+        {
+            fn name(&self) -> &'static str {
+                "vkCmdPushDescriptorSetKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.push_descriptor_set(self.bind_point,
+                                        &self.pipeline_layout,
+                                        self.set_num,
+                                        self.writes.drain(..));
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin<Pl, R>(Pl, R, u32);
+                impl<Pl, R> FinalCommand for Fin<Pl, R>
+                    where Pl: Send + Sync + 'static,
+                          R: PersistentDescriptorSetResources + Send + Sync + 'static
+                {
+                    fn name(&self) -> &'static str {
+                        "vkCmdPushDescriptorSetKHR"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        self.1.buffer(num).unwrap().0
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        format!("Buffer pushed to descriptor {} of set {}",
+                                self.1.buffer(num).unwrap().1,
+                                self.2)
+                            .into()
+                    }
+                    fn image(&self, num: usize) -> &ImageAccess {
+                        self.1.image(num).unwrap().0.parent()
+                    }
+                    fn image_name(&self, num: usize) -> Cow<'static, str> {
+                        format!("Image pushed to descriptor {} of set {}",
+                                self.1.image(num).unwrap().1,
+                                self.2)
+                            .into()
+                    }
+                }
+                Box::new(Fin(self.pipeline_layout, self.resources, self.set_num))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                self.resources.buffer(num).unwrap().0
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                format!("Buffer pushed to descriptor {} of set {}",
+                        self.resources.buffer(num).unwrap().1,
+                        self.set_num)
+                    .into()
+            }
+
+            fn image(&self, num: usize) -> &ImageAccess {
+                self.resources.image(num).unwrap().0.parent()
+            }
+
+            fn image_name(&self, num: usize) -> Cow<'static, str> {
+                format!("Image pushed to descriptor {} of set {}",
+                        self.resources.image(num).unwrap().1,
+                        self.set_num)
+                    .into()
+            }
+        }
+
+        let all_buffers = (0 .. resources.num_buffers())
+            .map(|buf_num| {
+                let binding = resources.buffer(buf_num).unwrap().1;
+                let desc = pipeline_layout
+                    .descriptor(set_num as usize, binding as usize)
+                    .unwrap();
+                let (stages, access) = desc.pipeline_stages_and_access();
+                (!desc.readonly, stages, access)
+            })
+            .collect::<Vec<_>>();
+
+        let all_images = (0 .. resources.num_images())
+            .map(|img_num| {
+                let (image_view, binding) = resources.image(img_num).unwrap();
+                let desc = pipeline_layout
+                    .descriptor(set_num as usize, binding as usize)
+                    .unwrap();
+                let (stages, access) = desc.pipeline_stages_and_access();
+                let (layout, ignore_me_hack) = descriptor_image_layout(&desc, image_view);
+                (!desc.readonly, stages, access, layout, ignore_me_hack)
+            })
+            .collect::<Vec<_>>();
+
+        self.append_command(Cmd {
+                                bind_point,
+                                pipeline_layout,
+                                set_num,
+                                writes,
+                                resources,
+                            });
+
+        for (n, (write, stages, access)) in all_buffers.into_iter().enumerate() {
+            self.prev_cmd_resource(KeyTy::Buffer,
+                                   n,
+                                   write,
+                                   stages,
+                                   access,
+                                   ImageLayout::Undefined,
+                                   ImageLayout::Undefined)?;
+        }
+
+        for (n, (write, stages, access, layout, ignore_me_hack)) in
+            all_images.into_iter().enumerate()
+        {
+            if ignore_me_hack {
+                continue;
+            }
+            self.prev_cmd_resource(KeyTy::Image, n, write, stages, access, layout, layout)?;
+        }
+
+        Ok(())
+    }
+
     /// Calls `vkCmdResetEvent` on the builder.
     #[inline]
     pub unsafe fn reset_event(&mut self, event: Arc<Event>, stages: PipelineStages) {
@@ -1548,6 +3033,46 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { event, stages });
     }
 
+    /// Calls `vkCmdResetQueryPool` on the builder.
+    #[inline]
+    pub unsafe fn reset_query_pool(&mut self, pool: Arc<UnsafeQueryPool>, first_query: u32,
+                                   query_count: u32) {
+        struct Cmd {
+            pool: Arc<UnsafeQueryPool>,
+            first_query: u32,
+            query_count: u32,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdResetQueryPool"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                let range = self.pool
+                    .queries_range(self.first_query, self.query_count)
+                    .unwrap();
+                out.reset_query_pool(range);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Arc<UnsafeQueryPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdResetQueryPool"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd {
+                                pool,
+                                first_query,
+                                query_count,
+                            });
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -1658,6 +3183,30 @@ impl<P> SyncCommandBufferBuilder<P> {
         self.append_command(Cmd { event, stages });
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        struct Cmd {
+            device_mask: u32,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdSetDeviceMask"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.set_device_mask(self.device_mask);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                Box::new("vkCmdSetDeviceMask")
+            }
+        }
+
+        self.append_command(Cmd { device_mask });
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1752,6 +3301,74 @@ impl<P> SyncCommandBufferBuilder<P> {
                             });
     }
 
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(&mut self, shader_binding_table: Arc<ShaderBindingTable>,
+                             dimensions: [u32; 3])
+                             -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            shader_binding_table: Arc<ShaderBindingTable>,
+            dimensions: [u32; 3],
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdTraceRaysKHR"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.trace_rays(&self.shader_binding_table, self.dimensions);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Arc<ShaderBindingTable>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdTraceRaysKHR"
+                    }
+                    fn buffer(&self, num: usize) -> &BufferAccess {
+                        assert_eq!(num, 0);
+                        &**self.0.buffer()
+                    }
+                    fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                        assert_eq!(num, 0);
+                        "shader binding table".into()
+                    }
+                }
+                Box::new(Fin(self.shader_binding_table))
+            }
+
+            fn buffer(&self, num: usize) -> &BufferAccess {
+                assert_eq!(num, 0);
+                &**self.shader_binding_table.buffer()
+            }
+
+            fn buffer_name(&self, num: usize) -> Cow<'static, str> {
+                assert_eq!(num, 0);
+                "shader binding table".into()
+            }
+        }
+
+        self.append_command(Cmd {
+                                shader_binding_table,
+                                dimensions,
+                            });
+        self.prev_cmd_resource(KeyTy::Buffer,
+                               0,
+                               false,
+                               PipelineStages {
+                                   ray_tracing_shader: true,
+                                   ..PipelineStages::none()
+                               },
+                               AccessFlagBits {
+                                   shader_read: true,
+                                   ..AccessFlagBits::none()
+                               },
+                               ImageLayout::Undefined,
+                               ImageLayout::Undefined)?;
+        Ok(())
+    }
+
     /// Calls `vkCmdUpdateBuffer` on the builder.
     #[inline]
     pub unsafe fn update_buffer<B, D>(&mut self, buffer: B, data: D)
@@ -1820,6 +3437,39 @@ impl<P> SyncCommandBufferBuilder<P> {
                                ImageLayout::Undefined)
             .unwrap();
     }
+
+    /// Calls `vkCmdWriteTimestamp` on the builder.
+    #[inline]
+    pub unsafe fn write_timestamp(&mut self, pool: Arc<UnsafeQueryPool>, query: u32,
+                                  stage: PipelineStages) {
+        struct Cmd {
+            pool: Arc<UnsafeQueryPool>,
+            query: u32,
+            stage: PipelineStages,
+        }
+
+        impl<P> Command<P> for Cmd {
+            fn name(&self) -> &'static str {
+                "vkCmdWriteTimestamp"
+            }
+
+            unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
+                out.write_timestamp(self.pool.query(self.query).unwrap(), self.stage);
+            }
+
+            fn into_final_command(self: Box<Self>) -> Box<FinalCommand + Send + Sync> {
+                struct Fin(Arc<UnsafeQueryPool>);
+                impl FinalCommand for Fin {
+                    fn name(&self) -> &'static str {
+                        "vkCmdWriteTimestamp"
+                    }
+                }
+                Box::new(Fin(self.pool))
+            }
+        }
+
+        self.append_command(Cmd { pool, query, stage });
+    }
 }
 
 pub struct SyncCommandBufferBuilderBindDescriptorSets<'b, P: 'b> {
@@ -1837,8 +3487,8 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
     }
 
     #[inline]
-    pub unsafe fn submit<Pl, I>(self, graphics: bool, pipeline_layout: Pl, first_binding: u32,
-                                dynamic_offsets: I)
+    pub unsafe fn submit<Pl, I>(self, bind_point: PipelineBindPoint, pipeline_layout: Pl,
+                                first_binding: u32, dynamic_offsets: I)
                                 -> Result<(), SyncCommandBufferBuilderError>
         where Pl: PipelineLayoutAbstract + Send + Sync + 'static,
               I: Iterator<Item = u32> + Send + Sync + 'static
@@ -1849,7 +3499,7 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
 
         struct Cmd<Pl, I> {
             inner: SmallVec<[Box<DescriptorSet + Send + Sync>; 12]>,
-            graphics: bool,
+            bind_point: PipelineBindPoint,
             pipeline_layout: Pl,
             first_binding: u32,
             dynamic_offsets: Option<I>,
@@ -1864,7 +3514,7 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
             }
 
             unsafe fn send(&mut self, out: &mut UnsafeCommandBufferBuilder<P>) {
-                out.bind_descriptor_sets(self.graphics,
+                out.bind_descriptor_sets(self.bind_point,
                                          &self.pipeline_layout,
                                          self.first_binding,
                                          self.inner.iter().map(|s| s.inner()),
@@ -1988,29 +3638,7 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
                     let desc = ds.descriptor(desc_num as usize).unwrap();
                     let write = !desc.readonly;
                     let (stages, access) = desc.pipeline_stages_and_access();
-                    let mut ignore_me_hack = false;
-                    let layout = match desc.ty {
-                        DescriptorDescTy::CombinedImageSampler(_) => {
-                            image_view.descriptor_set_combined_image_sampler_layout()
-                        },
-                        DescriptorDescTy::Image(ref img) => {
-                            if img.sampled {
-                                image_view.descriptor_set_sampled_image_layout()
-                            } else {
-                                image_view.descriptor_set_storage_image_layout()
-                            }
-                        },
-                        DescriptorDescTy::InputAttachment { .. } => {
-                            // FIXME: This is tricky. Since we read from the input attachment
-                            // and this input attachment is being written in an earlier pass,
-                            // vulkano will think that it needs to put a pipeline barrier and will
-                            // return a `Conflict` error. For now as a work-around we simply ignore
-                            // input attachments.
-                            ignore_me_hack = true;
-                            image_view.descriptor_set_input_attachment_layout()
-                        },
-                        _ => panic!("Tried to bind an image to a non-image descriptor"),
-                    };
+                    let (layout, ignore_me_hack) = descriptor_image_layout(&desc, image_view);
                     all_images.push((write, stages, access, layout, ignore_me_hack));
                 }
             }
@@ -2019,7 +3647,7 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
 
         self.builder.append_command(Cmd {
                                         inner: self.inner,
-                                        graphics,
+                                        bind_point,
                                         pipeline_layout,
                                         first_binding,
                                         dynamic_offsets: Some(dynamic_offsets),
@@ -2050,6 +3678,33 @@ impl<'b, P> SyncCommandBufferBuilderBindDescriptorSets<'b, P> {
     }
 }
 
+// Returns the layout that an image bound to a descriptor must be in, and whether the image
+// should be ignored by the synchronization.
+fn descriptor_image_layout(desc: &DescriptorDesc, image_view: &ImageViewAccess)
+                           -> (ImageLayout, bool) {
+    match desc.ty {
+        DescriptorDescTy::CombinedImageSampler(_) => {
+            (image_view.descriptor_set_combined_image_sampler_layout(), false)
+        },
+        DescriptorDescTy::Image(ref img) => {
+            if img.sampled {
+                (image_view.descriptor_set_sampled_image_layout(), false)
+            } else {
+                (image_view.descriptor_set_storage_image_layout(), false)
+            }
+        },
+        DescriptorDescTy::InputAttachment { .. } => {
+            // FIXME: This is tricky. Since we read from the input attachment
+            // and this input attachment is being written in an earlier pass,
+            // vulkano will think that it needs to put a pipeline barrier and will
+            // return a `Conflict` error. For now as a work-around we simply ignore
+            // input attachments.
+            (image_view.descriptor_set_input_attachment_layout(), true)
+        },
+        _ => panic!("Tried to bind an image to a non-image descriptor"),
+    }
+}
+
 /// Prototype for a `vkCmdBindVertexBuffers`.
 pub struct SyncCommandBufferBuilderBindVertexBuffer<'a, P: 'a> {
     builder: &'a mut SyncCommandBufferBuilder<P>,
@@ -2191,3 +3846,42 @@ impl<'a, P> SyncCommandBufferBuilderExecuteCommands<'a, P> {
         Ok(())
     }
 }
+
+// Returns the source stages, source accesses, destination stages and destination accesses of the
+// release or acquire half of a queue ownership transfer.
+//
+// The accesses from the other half of the transfer are ignored by the implementation, therefore
+// we only need to make the writes available in the release barrier and visible in the acquire
+// barrier.
+fn ownership_transfer_stages(release: bool)
+                             -> (PipelineStages, AccessFlagBits, PipelineStages, AccessFlagBits) {
+    let all_commands = PipelineStages {
+        all_commands: true,
+        ..PipelineStages::none()
+    };
+
+    if release {
+        (all_commands,
+         AccessFlagBits {
+             memory_write: true,
+             ..AccessFlagBits::none()
+         },
+         PipelineStages {
+             bottom_of_pipe: true,
+             ..PipelineStages::none()
+         },
+         AccessFlagBits::none())
+    } else {
+        (PipelineStages {
+             top_of_pipe: true,
+             ..PipelineStages::none()
+         },
+         AccessFlagBits::none(),
+         all_commands,
+         AccessFlagBits {
+             memory_read: true,
+             memory_write: true,
+             ..AccessFlagBits::none()
+         })
+    }
+}
@@ -17,21 +17,27 @@ use std::ffi::CStr;
 
 use OomError;
 use VulkanObject;
+use acceleration_structure::AccelerationStructure;
+use acceleration_structure::AccelerationStructureGeometry;
+use acceleration_structure::BuildAccelerationStructureFlags;
+use acceleration_structure::raw_geometries;
 use buffer::BufferAccess;
 use buffer::BufferInner;
+use buffer::TypedBufferAccess;
 use check_errors;
 use command_buffer::CommandBuffer;
 use command_buffer::pool::CommandPool;
 use command_buffer::pool::CommandPoolAlloc;
 use command_buffer::pool::CommandPoolBuilderAlloc;
 use descriptor::descriptor::ShaderStages;
+use descriptor::descriptor_set::DescriptorWrite;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
+use descriptor::descriptor_set::with_raw_writes;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
 use device::DeviceOwned;
 use format::ClearValue;
 use format::FormatTy;
-use format::PossibleCompressedFormatDesc;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::Framebuffer;
 use framebuffer::FramebufferAbstract;
@@ -44,16 +50,22 @@ use image::ImageLayout;
 use instance::QueueFamily;
 use pipeline::ComputePipelineAbstract;
 use pipeline::GraphicsPipelineAbstract;
+use pipeline::PipelineBindPoint;
+use pipeline::RayTracingPipelineAbstract;
+use pipeline::ShaderBindingTable;
 use pipeline::input_assembly::IndexType;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
 use query::QueryPipelineStatisticFlags;
+use query::QueryResultElement;
+use query::QueryResultFlags;
 use query::UnsafeQueriesRange;
 use query::UnsafeQuery;
 use sampler::Filter;
 use sync::AccessFlagBits;
 use sync::Event;
 use sync::PipelineStages;
+use version::Version;
 use vk;
 
 /// Determines the kind of command buffer that we want to create.
@@ -353,6 +365,34 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         }
     }
 
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering<B>(&mut self, buffer: &B, inverted: bool)
+        where B: ?Sized + BufferAccess
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage_conditional_rendering());
+        debug_assert_eq!(inner.offset % 4, 0);
+
+        let infos = vk::ConditionalRenderingBeginInfoEXT {
+            sType: vk::STRUCTURE_TYPE_CONDITIONAL_RENDERING_BEGIN_INFO_EXT,
+            pNext: ptr::null(),
+            buffer: inner.buffer.internal_object(),
+            offset: inner.offset as vk::DeviceSize,
+            flags: if inverted {
+                vk::CONDITIONAL_RENDERING_INVERTED_BIT_EXT
+            } else {
+                0
+            },
+        };
+
+        vk.CmdBeginConditionalRenderingEXT(cmd, &infos);
+    }
+
     /// Calls `vkCmdBeginQuery` on the builder.
     #[inline]
     pub unsafe fn begin_query(&mut self, query: UnsafeQuery, precise: bool) {
@@ -449,12 +489,37 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdBeginRenderPass(cmd, &begin, subpass_contents as u32);
     }
 
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_transform_feedback<'a, I>(&mut self, counter_buffers: I)
+        where I: IntoIterator<Item = &'a BufferAccess>
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let mut raw_buffers = SmallVec::<[_; 4]>::new();
+        let mut offsets = SmallVec::<[_; 4]>::new();
+        for buffer in counter_buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner.buffer.usage_transform_feedback_counter_buffer());
+            debug_assert_eq!(inner.offset % 4, 0);
+            raw_buffers.push(inner.buffer.internal_object());
+            offsets.push(inner.offset as vk::DeviceSize);
+        }
+
+        vk.CmdBeginTransformFeedbackEXT(cmd,
+                                        0,
+                                        raw_buffers.len() as u32,
+                                        raw_buffers.as_ptr(),
+                                        offsets.as_ptr());
+    }
+
     /// Calls `vkCmdBindDescriptorSets` on the builder.
     ///
     /// Does nothing if the list of descriptor sets is empty, as it would be a no-op and isn't a
     /// valid usage of the command anyway.
     #[inline]
-    pub unsafe fn bind_descriptor_sets<'s, Pl, S, I>(&mut self, graphics: bool,
+    pub unsafe fn bind_descriptor_sets<'s, Pl, S, I>(&mut self, bind_point: PipelineBindPoint,
                                                      pipeline_layout: &Pl, first_binding: u32,
                                                      sets: S, dynamic_offsets: I)
         where Pl: ?Sized + PipelineLayoutAbstract,
@@ -473,14 +538,8 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         let num_bindings = sets.len() as u32;
         debug_assert!(first_binding + num_bindings <= pipeline_layout.num_sets() as u32);
 
-        let bind_point = if graphics {
-            vk::PIPELINE_BIND_POINT_GRAPHICS
-        } else {
-            vk::PIPELINE_BIND_POINT_COMPUTE
-        };
-
         vk.CmdBindDescriptorSets(cmd,
-                                 bind_point,
+                                 bind_point as vk::PipelineBindPoint,
                                  pipeline_layout.sys().internal_object(),
                                  first_binding,
                                  num_bindings,
@@ -530,6 +589,48 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdBindPipeline(cmd, vk::PIPELINE_BIND_POINT_GRAPHICS, inner);
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing<Rp>(&mut self, pipeline: &Rp)
+        where Rp: ?Sized + RayTracingPipelineAbstract
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let inner = RayTracingPipelineAbstract::inner(pipeline).internal_object();
+        vk.CmdBindPipeline(cmd, vk::PIPELINE_BIND_POINT_RAY_TRACING_KHR, inner);
+    }
+
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder, in order to build `structure`
+    /// from `geometries`.
+    ///
+    /// `scratch_address` is the device address of the scratch memory, which must be aligned to
+    /// `min_acceleration_structure_scratch_offset_alignment`.
+    pub unsafe fn build_acceleration_structure(&mut self, structure: &AccelerationStructure,
+                                               geometries: &[AccelerationStructureGeometry],
+                                               flags: BuildAccelerationStructureFlags,
+                                               scratch_address: u64) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let (raw_geometries, ranges) = raw_geometries(geometries, true);
+
+        let infos = vk::AccelerationStructureBuildGeometryInfoKHR {
+            sType: vk::STRUCTURE_TYPE_ACCELERATION_STRUCTURE_BUILD_GEOMETRY_INFO_KHR,
+            pNext: ptr::null(),
+            ty: structure.ty() as u32,
+            flags: flags.into_vulkan_bits(),
+            mode: vk::BUILD_ACCELERATION_STRUCTURE_MODE_BUILD_KHR,
+            srcAccelerationStructure: 0,
+            dstAccelerationStructure: structure.internal_object(),
+            geometryCount: raw_geometries.len() as u32,
+            pGeometries: raw_geometries.as_ptr(),
+            ppGeometries: ptr::null(),
+            scratchData: vk::DeviceOrHostAddressKHR { deviceAddress: scratch_address },
+        };
+
+        vk.CmdBuildAccelerationStructuresKHR(cmd, 1, &infos, &ranges.as_ptr());
+    }
+
     /// Calls `vkCmdBindVertexBuffers` on the builder.
     ///
     /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
@@ -563,6 +664,39 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                                 params.offsets.as_ptr());
     }
 
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    ///
+    /// Does nothing if the list of buffers is empty.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers<'a, I>(&mut self, first_binding: u32, buffers: I)
+        where I: IntoIterator<Item = &'a BufferAccess>
+    {
+        let mut raw_buffers = SmallVec::<[_; 4]>::new();
+        let mut offsets = SmallVec::<[_; 4]>::new();
+        let mut sizes = SmallVec::<[_; 4]>::new();
+        for buffer in buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner.buffer.usage_transform_feedback_buffer());
+            debug_assert_eq!(inner.offset % 4, 0);
+            raw_buffers.push(inner.buffer.internal_object());
+            offsets.push(inner.offset as vk::DeviceSize);
+            sizes.push(buffer.size() as vk::DeviceSize);
+        }
+
+        if raw_buffers.is_empty() {
+            return;
+        }
+
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdBindTransformFeedbackBuffersEXT(cmd,
+                                              first_binding,
+                                              raw_buffers.len() as u32,
+                                              raw_buffers.as_ptr(),
+                                              offsets.as_ptr(),
+                                              sizes.as_ptr());
+    }
+
     /// Calls `vkCmdCopyImage` on the builder.
     ///
     /// Does nothing if the list of regions is empty, as it would be a no-op and isn't a valid
@@ -574,11 +708,7 @@ impl<P> UnsafeCommandBufferBuilder<P> {
               D: ?Sized + ImageAccess,
               R: Iterator<Item = UnsafeCommandBufferBuilderImageCopy>
     {
-        // TODO: The correct check here is that the uncompressed element size of the source is
-        // equal to the compressed element size of the destination.
-        debug_assert!(source.format().is_compressed() ||
-                      destination.format().is_compressed() ||
-                      source.format().size() == destination.format().size());
+        debug_assert_eq!(source.format().block_size(), destination.format().block_size());
 
         // Depth/Stencil formats are required to match exactly.
         debug_assert!(!source.format().ty().is_depth_and_or_stencil() ||
@@ -1028,13 +1158,17 @@ impl<P> UnsafeCommandBufferBuilder<P> {
 
     /// Calls `vkCmdCopyQueryPoolResults` on the builder.
     #[inline]
-    pub unsafe fn copy_query_pool_results(&mut self, queries: UnsafeQueriesRange,
-                                          destination: &BufferAccess, stride: usize) {
+    pub unsafe fn copy_query_pool_results<D, T>(&mut self, queries: UnsafeQueriesRange,
+                                                destination: &D, stride: usize,
+                                                flags: QueryResultFlags)
+        where D: ?Sized + TypedBufferAccess<Content = [T]>,
+              T: QueryResultElement
+    {
         let destination = destination.inner();
         debug_assert!(destination.offset < destination.buffer.size());
         debug_assert!(destination.buffer.usage_transfer_destination());
 
-        let flags = 0; // FIXME:
+        let flags = flags.into_vulkan_bits() | T::FLAG;
 
         let vk = self.device().pointers();
         let cmd = self.internal_object();
@@ -1152,6 +1286,107 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                                   stride);
     }
 
+    /// Calls `vkCmdDrawIndexedIndirectCountKHR` or `vkCmdDrawIndexedIndirectCount` on the
+    /// builder.
+    #[inline]
+    pub unsafe fn draw_indexed_indirect_count<B, C>(&mut self, buffer: &B, count_buffer: &C,
+                                                    max_draw_count: u32, stride: u32)
+        where B: ?Sized + BufferAccess,
+              C: ?Sized + BufferAccess
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        debug_assert!(self.device().supports_draw_indirect_count());
+        debug_assert!((stride % 4) == 0 &&
+                          stride as usize >= mem::size_of::<vk::DrawIndexedIndirectCommand>());
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage_indirect_buffer());
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.buffer.usage_indirect_buffer());
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device().loaded_extensions().khr_draw_indirect_count {
+            vk.CmdDrawIndexedIndirectCountKHR(cmd,
+                                              inner.buffer.internal_object(),
+                                              inner.offset as vk::DeviceSize,
+                                              count_inner.buffer.internal_object(),
+                                              count_inner.offset as vk::DeviceSize,
+                                              max_draw_count,
+                                              stride);
+        } else {
+            vk.CmdDrawIndexedIndirectCount(cmd,
+                                           inner.buffer.internal_object(),
+                                           inner.offset as vk::DeviceSize,
+                                           count_inner.buffer.internal_object(),
+                                           count_inner.offset as vk::DeviceSize,
+                                           max_draw_count,
+                                           stride);
+        }
+    }
+
+    /// Calls `vkCmdDrawIndirectCountKHR` or `vkCmdDrawIndirectCount` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_count<B, C>(&mut self, buffer: &B, count_buffer: &C,
+                                            max_draw_count: u32, stride: u32)
+        where B: ?Sized + BufferAccess,
+              C: ?Sized + BufferAccess
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        debug_assert!(self.device().supports_draw_indirect_count());
+        debug_assert!((stride % 4) == 0 &&
+                          stride as usize >= mem::size_of::<vk::DrawIndirectCommand>());
+
+        let inner = buffer.inner();
+        debug_assert!(inner.offset < inner.buffer.size());
+        debug_assert!(inner.buffer.usage_indirect_buffer());
+
+        let count_inner = count_buffer.inner();
+        debug_assert!(count_inner.offset < count_inner.buffer.size());
+        debug_assert!(count_inner.buffer.usage_indirect_buffer());
+        debug_assert_eq!(count_inner.offset % 4, 0);
+
+        if self.device().loaded_extensions().khr_draw_indirect_count {
+            vk.CmdDrawIndirectCountKHR(cmd,
+                                       inner.buffer.internal_object(),
+                                       inner.offset as vk::DeviceSize,
+                                       count_inner.buffer.internal_object(),
+                                       count_inner.offset as vk::DeviceSize,
+                                       max_draw_count,
+                                       stride);
+        } else {
+            vk.CmdDrawIndirectCount(cmd,
+                                    inner.buffer.internal_object(),
+                                    inner.offset as vk::DeviceSize,
+                                    count_inner.buffer.internal_object(),
+                                    count_inner.offset as vk::DeviceSize,
+                                    max_draw_count,
+                                    stride);
+        }
+    }
+
+    /// Calls `vkCmdDrawMeshTasksEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_mesh_tasks(&mut self, group_counts: [u32; 3]) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdDrawMeshTasksEXT(cmd, group_counts[0], group_counts[1], group_counts[2]);
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        vk.CmdEndConditionalRenderingEXT(cmd);
+    }
+
     /// Calls `vkCmdEndQuery` on the builder.
     #[inline]
     pub unsafe fn end_query(&mut self, query: UnsafeQuery) {
@@ -1160,6 +1395,29 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdEndQuery(cmd, query.pool().internal_object(), query.index());
     }
 
+    /// Calls `vkCmdDrawIndirectByteCountEXT` on the builder.
+    #[inline]
+    pub unsafe fn draw_indirect_byte_count<B>(&mut self, instance_count: u32, first_instance: u32,
+                                              counter_buffer: &B, counter_offset: u32,
+                                              vertex_stride: u32)
+        where B: ?Sized + BufferAccess
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let inner = counter_buffer.inner();
+        debug_assert!(inner.buffer.usage_transform_feedback_counter_buffer());
+        debug_assert_eq!(inner.offset % 4, 0);
+
+        vk.CmdDrawIndirectByteCountEXT(cmd,
+                                       instance_count,
+                                       first_instance,
+                                       inner.buffer.internal_object(),
+                                       inner.offset as vk::DeviceSize,
+                                       counter_offset,
+                                       vertex_stride);
+    }
+
     /// Calls `vkCmdEndRenderPass` on the builder.
     #[inline]
     pub unsafe fn end_render_pass(&mut self) {
@@ -1168,6 +1426,31 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdEndRenderPass(cmd);
     }
 
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_transform_feedback<'a, I>(&mut self, counter_buffers: I)
+        where I: IntoIterator<Item = &'a BufferAccess>
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        let mut raw_buffers = SmallVec::<[_; 4]>::new();
+        let mut offsets = SmallVec::<[_; 4]>::new();
+        for buffer in counter_buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner.buffer.usage_transform_feedback_counter_buffer());
+            debug_assert_eq!(inner.offset % 4, 0);
+            raw_buffers.push(inner.buffer.internal_object());
+            offsets.push(inner.offset as vk::DeviceSize);
+        }
+
+        vk.CmdEndTransformFeedbackEXT(cmd,
+                                      0,
+                                      raw_buffers.len() as u32,
+                                      raw_buffers.as_ptr(),
+                                      offsets.as_ptr());
+    }
+
     /// Calls `vkCmdExecuteCommands` on the builder.
     ///
     /// Does nothing if the list of command buffers is empty, as it would be a no-op and isn't a
@@ -1271,6 +1554,49 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                             data as *const D as *const _);
     }
 
+    /// Calls `vkCmdPushDescriptorSetKHR` on the builder.
+    ///
+    /// Does nothing if the list of writes is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    ///
+    /// # Safety
+    ///
+    /// - The `khr_push_descriptor` extension must be enabled on the device.
+    /// - The layout of set `set_num` of the pipeline layout must have been created with
+    ///   `UnsafeDescriptorSetLayout::push_descriptor`.
+    /// - Doesn't verify that the writes match the layout of the set.
+    /// - Doesn't keep the resources alive. You have to do that yourself.
+    ///
+    #[inline]
+    pub unsafe fn push_descriptor_set<Pl, I>(&mut self, bind_point: PipelineBindPoint,
+                                             pipeline_layout: &Pl, set_num: u32, writes: I)
+        where Pl: ?Sized + PipelineLayoutAbstract,
+              I: Iterator<Item = DescriptorWrite>
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        debug_assert!(self.device().loaded_extensions().khr_push_descriptor);
+        debug_assert!(pipeline_layout
+                          .descriptor_set_layout(set_num as usize)
+                          .map(|l| l.is_push_descriptor())
+                          .unwrap_or(false));
+
+        // The `dstSet` field of the writes is ignored by `vkCmdPushDescriptorSetKHR`.
+        with_raw_writes(0, writes, |raw_writes| {
+            if raw_writes.is_empty() {
+                return;
+            }
+
+            vk.CmdPushDescriptorSetKHR(cmd,
+                                       bind_point as vk::PipelineBindPoint,
+                                       pipeline_layout.sys().internal_object(),
+                                       set_num,
+                                       raw_writes.len() as u32,
+                                       raw_writes.as_ptr());
+        })
+    }
+
     /// Calls `vkCmdResetEvent` on the builder.
     #[inline]
     pub unsafe fn reset_event(&mut self, event: &Event, stages: PipelineStages) {
@@ -1333,6 +1659,24 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetEvent(cmd, event.internal_object(), stages.into_vulkan_bits());
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    ///
+    /// Uses `vkCmdSetDeviceMaskKHR` if the device uses Vulkan 1.0.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        debug_assert!(self.device().supports_device_masks());
+        debug_assert_ne!(device_mask, 0);
+        debug_assert_eq!((device_mask as u64) >> self.device().device_group_size(), 0);
+
+        if self.device().api_version() >= Version::V1_1 {
+            vk.CmdSetDeviceMask(cmd, device_mask);
+        } else {
+            vk.CmdSetDeviceMaskKHR(cmd, device_mask);
+        }
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1430,6 +1774,23 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                           viewports.as_ptr());
     }
 
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(&mut self, shader_binding_table: &ShaderBindingTable,
+                             dimensions: [u32; 3]) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        let regions = shader_binding_table.raw_regions();
+        vk.CmdTraceRaysKHR(cmd,
+                           &regions[0],
+                           &regions[1],
+                           &regions[2],
+                           &regions[3],
+                           dimensions[0],
+                           dimensions[1],
+                           dimensions[2]);
+    }
+
     /// Calls `vkCmdUpdateBuffer` on the builder.
     #[inline]
     pub unsafe fn update_buffer<B, D>(&mut self, buffer: &B, data: &D)
@@ -329,7 +329,7 @@ impl<F, Cb> Drop for CommandBufferExecFuture<F, Cb>
                 // TODO: handle errors?
                 self.flush().unwrap();
                 // Block until the queue finished.
-                self.queue.wait().unwrap();
+                self.queue.wait_idle().unwrap();
                 self.command_buffer.unlock();
                 self.previous.signal_finished();
             }
@@ -0,0 +1,416 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use acceleration_structure::AccelerationStructure;
+use acceleration_structure::AccelerationStructureGeometry;
+use acceleration_structure::AccelerationStructureProperties;
+use acceleration_structure::AccelerationStructureType;
+use acceleration_structure::BuildAccelerationStructureFlags;
+use buffer::BufferAccess;
+use device::Device;
+use device::DeviceOwned;
+
+/// Checks whether an acceleration structure can be built from `geometries` with `scratch` as
+/// scratch memory.
+///
+/// On success, returns the device address within `scratch` to pass to the command, which is
+/// aligned to the `min_scratch_offset_alignment` of the device.
+///
+/// # Panic
+///
+/// - Panics if the structure or the buffers were not created with `device`.
+///
+pub fn check_build_acceleration_structure<S>(device: &Device, structure: &AccelerationStructure,
+                                             geometries: &[AccelerationStructureGeometry],
+                                             flags: BuildAccelerationStructureFlags,
+                                             scratch: &S)
+                                             -> Result<u64, CheckBuildAccelerationStructureError>
+    where S: ?Sized + BufferAccess
+{
+    assert_eq!(structure.device().internal_object(),
+               device.internal_object());
+    assert_eq!(scratch.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    // A top-level structure contains exactly one geometry of instances, while a bottom-level
+    // structure contains no instances at all.
+    let types_match = match structure.ty() {
+        AccelerationStructureType::TopLevel => {
+            geometries.len() == 1 && geometries[0].is_instances()
+        },
+        AccelerationStructureType::BottomLevel => {
+            geometries.iter().all(|g| !g.is_instances())
+        },
+    };
+    if !types_match {
+        return Err(CheckBuildAccelerationStructureError::GeometryTypeMismatch);
+    }
+
+    let physical_device = device.physical_device();
+    let properties = AccelerationStructureProperties::from_physical_device(physical_device);
+
+    if geometries.len() as u64 > properties.max_geometry_count {
+        return Err(CheckBuildAccelerationStructureError::MaxGeometryCountExceeded {
+                       limit: properties.max_geometry_count,
+                       requested: geometries.len() as u64,
+                   });
+    }
+
+    for geometry in geometries.iter() {
+        if geometry.vertex_stride() == Some(0) {
+            return Err(CheckBuildAccelerationStructureError::ZeroVertexStride);
+        }
+    }
+
+    // The instances of a top-level structure and the primitives of a bottom-level structure have
+    // separate limits.
+    let primitive_count = geometries
+        .iter()
+        .map(|g| g.primitive_count() as u64)
+        .sum::<u64>();
+    match structure.ty() {
+        AccelerationStructureType::TopLevel => {
+            if primitive_count > properties.max_instance_count {
+                return Err(CheckBuildAccelerationStructureError::MaxInstanceCountExceeded {
+                               limit: properties.max_instance_count,
+                               requested: primitive_count,
+                           });
+            }
+        },
+        AccelerationStructureType::BottomLevel => {
+            if primitive_count > properties.max_primitive_count {
+                return Err(CheckBuildAccelerationStructureError::MaxPrimitiveCountExceeded {
+                               limit: properties.max_primitive_count,
+                               requested: primitive_count,
+                           });
+            }
+        },
+    }
+
+    for geometry in geometries.iter() {
+        for buffer in geometry.buffers() {
+            let inner = buffer.inner();
+            assert_eq!(inner.buffer.device().internal_object(),
+                       device.internal_object());
+
+            if !inner.buffer.usage_acceleration_structure_build_input() ||
+                !inner.buffer.usage_shader_device_address()
+            {
+                return Err(CheckBuildAccelerationStructureError::GeometryBufferMissingUsage);
+            }
+        }
+    }
+
+    if !scratch.inner().buffer.usage_storage_buffer() ||
+        !scratch.inner().buffer.usage_shader_device_address()
+    {
+        return Err(CheckBuildAccelerationStructureError::ScratchBufferMissingUsage);
+    }
+
+    // The structure exists, so the extension is necessarily enabled.
+    let sizes = AccelerationStructure::build_sizes(device, structure.ty(), geometries, flags)
+        .unwrap();
+
+    if structure.size() < sizes.structure_size {
+        return Err(CheckBuildAccelerationStructureError::StructureTooSmall {
+                       required: sizes.structure_size,
+                       actual: structure.size(),
+                   });
+    }
+
+    let alignment = properties.min_scratch_offset_alignment as u64;
+    let address = scratch.inner().buffer.device_address() + scratch.inner().offset as u64;
+    let aligned_address = if alignment == 0 {
+        address
+    } else {
+        (address + alignment - 1) / alignment * alignment
+    };
+
+    let required = sizes.build_scratch_size + (aligned_address - address) as usize;
+    if scratch.size() < required {
+        return Err(CheckBuildAccelerationStructureError::ScratchBufferTooSmall {
+                       required: required,
+                       actual: scratch.size(),
+                   });
+    }
+
+    Ok(aligned_address)
+}
+
+/// Error that can happen when attempting to build an acceleration structure.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBuildAccelerationStructureError {
+    /// A top-level structure must be built from exactly one geometry of instances, and a
+    /// bottom-level structure from geometries of triangles or bounding boxes.
+    GeometryTypeMismatch,
+    /// The number of geometries is larger than the `max_geometry_count` limit.
+    MaxGeometryCountExceeded {
+        /// The limit of the device.
+        limit: u64,
+        /// The number of geometries that was requested.
+        requested: u64,
+    },
+    /// The number of triangles and bounding boxes of a bottom-level structure is larger than the
+    /// `max_primitive_count` limit.
+    MaxPrimitiveCountExceeded {
+        /// The limit of the device.
+        limit: u64,
+        /// The number of primitives that was requested.
+        requested: u64,
+    },
+    /// The number of instances of a top-level structure is larger than the `max_instance_count`
+    /// limit.
+    MaxInstanceCountExceeded {
+        /// The limit of the device.
+        limit: u64,
+        /// The number of instances that was requested.
+        requested: u64,
+    },
+    /// The vertex stride of a geometry of triangles is 0.
+    ZeroVertexStride,
+    /// The "acceleration structure build input" and "shader device address" usages must be
+    /// enabled on the buffers of the geometries.
+    GeometryBufferMissingUsage,
+    /// The "storage buffer" and "shader device address" usages must be enabled on the scratch
+    /// buffer.
+    ScratchBufferMissingUsage,
+    /// The structure is too small to contain the result of the build.
+    StructureTooSmall {
+        /// Required size in bytes.
+        required: usize,
+        /// Actual size of the structure.
+        actual: usize,
+    },
+    /// The scratch buffer is too small for the build.
+    ScratchBufferTooSmall {
+        /// Required size in bytes, including the room needed to align the address.
+        required: usize,
+        /// Actual size of the buffer.
+        actual: usize,
+    },
+}
+
+impl error::Error for CheckBuildAccelerationStructureError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckBuildAccelerationStructureError::GeometryTypeMismatch => {
+                "the type of the geometries doesn't match the type of the structure"
+            },
+            CheckBuildAccelerationStructureError::MaxGeometryCountExceeded { .. } => {
+                "the number of geometries exceeds the `max_geometry_count` limit"
+            },
+            CheckBuildAccelerationStructureError::MaxPrimitiveCountExceeded { .. } => {
+                "the number of primitives exceeds the `max_primitive_count` limit"
+            },
+            CheckBuildAccelerationStructureError::MaxInstanceCountExceeded { .. } => {
+                "the number of instances exceeds the `max_instance_count` limit"
+            },
+            CheckBuildAccelerationStructureError::ZeroVertexStride => {
+                "the vertex stride of a geometry of triangles is 0"
+            },
+            CheckBuildAccelerationStructureError::GeometryBufferMissingUsage => {
+                "the acceleration structure build input and shader device address usages must \
+                 be enabled on the buffers of the geometries"
+            },
+            CheckBuildAccelerationStructureError::ScratchBufferMissingUsage => {
+                "the storage buffer and shader device address usages must be enabled on the \
+                 scratch buffer"
+            },
+            CheckBuildAccelerationStructureError::StructureTooSmall { .. } => {
+                "the structure is too small to contain the result of the build"
+            },
+            CheckBuildAccelerationStructureError::ScratchBufferTooSmall { .. } => {
+                "the scratch buffer is too small for the build"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckBuildAccelerationStructureError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use format::Format;
+    use std::sync::Arc;
+
+    fn input_usage() -> BufferUsage {
+        BufferUsage {
+            acceleration_structure_build_input: true,
+            shader_device_address: true,
+            ..BufferUsage::none()
+        }
+    }
+
+    fn scratch_usage() -> BufferUsage {
+        BufferUsage {
+            storage_buffer: true,
+            shader_device_address: true,
+            ..BufferUsage::none()
+        }
+    }
+
+    fn triangle(device: &Arc<Device>, usage: BufferUsage, stride: usize)
+                -> AccelerationStructureGeometry {
+        let vertices = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(), usage,
+                                                    vertices.iter().cloned())
+            .unwrap();
+        AccelerationStructureGeometry::triangles(buffer, Format::R32G32B32Sfloat, stride)
+    }
+
+    fn structure(device: &Arc<Device>, ty: AccelerationStructureType,
+                 geometries: &[AccelerationStructureGeometry])
+                 -> Arc<AccelerationStructure> {
+        let flags = BuildAccelerationStructureFlags::none();
+        let sizes = AccelerationStructure::build_sizes(device, ty, geometries, flags).unwrap();
+        AccelerationStructure::new(device.clone(), ty, sizes.structure_size).unwrap()
+    }
+
+    #[test]
+    fn valid() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let geometries = [triangle(&device, input_usage(), 12)];
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = structure(&device, ty, &geometries);
+        let scratch = CpuAccessibleBuffer::from_iter(device.clone(), scratch_usage(),
+                                                     (0 .. 1 << 20).map(|_| 0u8))
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        let address = check_build_acceleration_structure(&device, &structure, &geometries, flags,
+                                                         &scratch)
+            .unwrap();
+        assert!(address >= scratch.inner().buffer.device_address());
+    }
+
+    #[test]
+    fn geometry_type_mismatch() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let triangles = [triangle(&device, input_usage(), 12)];
+        let top_level = AccelerationStructure::new(device.clone(),
+                                                   AccelerationStructureType::TopLevel,
+                                                   1 << 16)
+            .unwrap();
+        let scratch = CpuAccessibleBuffer::from_iter(device.clone(), scratch_usage(),
+                                                     (0 .. 1 << 20).map(|_| 0u8))
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        match check_build_acceleration_structure(&device, &top_level, &triangles, flags,
+                                                 &scratch) {
+            Err(CheckBuildAccelerationStructureError::GeometryTypeMismatch) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn zero_vertex_stride() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = structure(&device, ty, &[triangle(&device, input_usage(), 12)]);
+        let geometries = [triangle(&device, input_usage(), 0)];
+        let scratch = CpuAccessibleBuffer::from_iter(device.clone(), scratch_usage(),
+                                                     (0 .. 1 << 20).map(|_| 0u8))
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        match check_build_acceleration_structure(&device, &structure, &geometries, flags,
+                                                 &scratch) {
+            Err(CheckBuildAccelerationStructureError::ZeroVertexStride) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn geometry_buffer_missing_usage() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = structure(&device, ty, &[triangle(&device, input_usage(), 12)]);
+        let geometries = [triangle(&device, BufferUsage::all(), 12)];
+        let scratch = CpuAccessibleBuffer::from_iter(device.clone(), scratch_usage(),
+                                                     (0 .. 1 << 20).map(|_| 0u8))
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        match check_build_acceleration_structure(&device, &structure, &geometries, flags,
+                                                 &scratch) {
+            Err(CheckBuildAccelerationStructureError::GeometryBufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn scratch_buffer_missing_usage() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let geometries = [triangle(&device, input_usage(), 12)];
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = structure(&device, ty, &geometries);
+        let scratch = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                                     (0 .. 1 << 20).map(|_| 0u8))
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        match check_build_acceleration_structure(&device, &structure, &geometries, flags,
+                                                 &scratch) {
+            Err(CheckBuildAccelerationStructureError::ScratchBufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn scratch_buffer_too_small() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_deferred_host_operations,
+                                                          khr_buffer_device_address,
+                                                          khr_acceleration_structure]);
+
+        let geometries = [triangle(&device, input_usage(), 12)];
+        let ty = AccelerationStructureType::BottomLevel;
+        let structure = structure(&device, ty, &geometries);
+        let scratch = CpuAccessibleBuffer::from_data(device.clone(), scratch_usage(), 0u8)
+            .unwrap();
+
+        let flags = BuildAccelerationStructureFlags::none();
+        match check_build_acceleration_structure(&device, &structure, &geometries, flags,
+                                                 &scratch) {
+            Err(CheckBuildAccelerationStructureError::ScratchBufferTooSmall {
+                    actual: 1,
+                    ..
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
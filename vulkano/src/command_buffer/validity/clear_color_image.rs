@@ -12,16 +12,21 @@ use std::fmt;
 
 use VulkanObject;
 use device::Device;
+use format::ClearValue;
+use format::FormatTy;
 use image::ImageAccess;
 
 /// Checks whether a clear color image command is valid.
 ///
+/// `color` must be a color value that matches the format of the image (see
+/// `ClearValue::is_compatible_with`).
+///
 /// # Panic
 ///
 /// - Panics if the destination was not created with `device`.
 ///
 pub fn check_clear_color_image<I>(device: &Device, image: &I, first_layer: u32, num_layers: u32,
-                                  first_mipmap: u32, num_mipmaps: u32)
+                                  first_mipmap: u32, num_mipmaps: u32, color: ClearValue)
                                   -> Result<(), CheckClearColorImageError>
     where I: ?Sized + ImageAccess
 {
@@ -32,6 +37,18 @@ pub fn check_clear_color_image<I>(device: &Device, image: &I, first_layer: u32,
         return Err(CheckClearColorImageError::MissingTransferUsage);
     }
 
+    let format = image.format();
+    if format.ty().is_depth_and_or_stencil() || format.ty() == FormatTy::Compressed {
+        return Err(CheckClearColorImageError::NotColorFormat);
+    }
+
+    if !color.is_compatible_with(format) {
+        return Err(CheckClearColorImageError::ClearValueTyMismatch {
+                       expected: format.ty(),
+                       obtained: color,
+                   });
+    }
+
     if first_layer + num_layers > image.dimensions().array_layers() {
         return Err(CheckClearColorImageError::OutOfRange);
     }
@@ -48,6 +65,15 @@ pub fn check_clear_color_image<I>(device: &Device, image: &I, first_layer: u32,
 pub enum CheckClearColorImageError {
     /// The image is missing the transfer destination usage.
     MissingTransferUsage,
+    /// The image has a depth, stencil or compressed format.
+    NotColorFormat,
+    /// The clear value doesn't match the type of the format of the image.
+    ClearValueTyMismatch {
+        /// Type of the format of the image.
+        expected: FormatTy,
+        /// Clear value that was passed.
+        obtained: ClearValue,
+    },
     /// The array layers and mipmap levels are out of range.
     OutOfRange,
 }
@@ -59,6 +85,12 @@ impl error::Error for CheckClearColorImageError {
             CheckClearColorImageError::MissingTransferUsage => {
                 "the image is missing the transfer destination usage"
             },
+            CheckClearColorImageError::NotColorFormat => {
+                "the image has a depth, stencil or compressed format"
+            },
+            CheckClearColorImageError::ClearValueTyMismatch { .. } => {
+                "the clear value doesn't match the type of the format of the image"
+            },
             CheckClearColorImageError::OutOfRange => {
                 "the array layers and mipmap levels are out of range"
             },
@@ -72,3 +104,41 @@ impl fmt::Display for CheckClearColorImageError {
         write!(fmt, "{}", error::Error::description(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::Format;
+    use image::AttachmentImage;
+    use image::ImageUsage;
+
+    #[test]
+    fn wrong_clear_value_ty() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            transfer_destination: true,
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let image = AttachmentImage::with_usage(device.clone(), [32, 32], Format::R8G8B8A8Unorm,
+                                                usage)
+            .unwrap();
+
+        check_clear_color_image(&device, &image, 0, 1, 0, 1, ClearValue::Float([0.0; 4]))
+            .unwrap();
+
+        match check_clear_color_image(&device, &image, 0, 1, 0, 1, ClearValue::Uint([0; 4])) {
+            Err(CheckClearColorImageError::ClearValueTyMismatch {
+                    expected: FormatTy::Float,
+                    ..
+                }) => (),
+            _ => panic!(),
+        }
+
+        match check_clear_color_image(&device, &image, 0, 1, 0, 1, ClearValue::Depth(1.0)) {
+            Err(CheckClearColorImageError::ClearValueTyMismatch { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
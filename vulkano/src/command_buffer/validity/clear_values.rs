@@ -0,0 +1,226 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use format::ClearValue;
+use format::FormatTy;
+use framebuffer::RenderPassDesc;
+
+/// Checks whether the clear values passed when beginning a render pass are valid.
+///
+/// There must be exactly one clear value per attachment. Attachments that have at least one aspect
+/// cleared (see `LayoutAttachmentDescription::is_cleared`) must receive a clear value that matches
+/// their format, and the other attachments must receive `ClearValue::None`.
+pub fn check_clear_values<Rp, I>(render_pass: &Rp, clear_values: I)
+                                 -> Result<(), CheckClearValuesError>
+    where Rp: ?Sized + RenderPassDesc,
+          I: IntoIterator<Item = ClearValue>
+{
+    let mut clear_values = clear_values.into_iter();
+
+    for atch_num in 0 .. render_pass.num_attachments() {
+        let atch_desc = render_pass.attachment_desc(atch_num).unwrap();
+        let clear_value = match clear_values.next() {
+            Some(v) => v,
+            None => {
+                return Err(CheckClearValuesError::WrongClearValuesCount {
+                               expected: render_pass.num_attachments(),
+                               obtained: atch_num,
+                           });
+            },
+        };
+
+        if !atch_desc.is_cleared() {
+            if clear_value != ClearValue::None {
+                return Err(CheckClearValuesError::UnexpectedClearValue {
+                               attachment: atch_num,
+                               obtained: clear_value,
+                           });
+            }
+
+            continue;
+        }
+
+        if !clear_value.is_compatible_with(atch_desc.format) {
+            return Err(CheckClearValuesError::ClearValueTyMismatch {
+                           attachment: atch_num,
+                           expected: atch_desc.format.ty(),
+                           obtained: clear_value,
+                       });
+        }
+    }
+
+    let remaining = clear_values.count();
+    if remaining != 0 {
+        return Err(CheckClearValuesError::WrongClearValuesCount {
+                       expected: render_pass.num_attachments(),
+                       obtained: render_pass.num_attachments() + remaining,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when the clear values passed when beginning a render pass are invalid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckClearValuesError {
+    /// The number of clear values is not the same as the number of attachments.
+    WrongClearValuesCount {
+        /// Number of attachments of the render pass.
+        expected: usize,
+        /// Number of clear values that were passed.
+        obtained: usize,
+    },
+
+    /// The clear value of an attachment that is cleared doesn't match the type of its format.
+    ClearValueTyMismatch {
+        /// Index of the attachment.
+        attachment: usize,
+        /// Type of the format of the attachment.
+        expected: FormatTy,
+        /// Clear value that was passed.
+        obtained: ClearValue,
+    },
+
+    /// An attachment that isn't cleared received a clear value other than `ClearValue::None`.
+    UnexpectedClearValue {
+        /// Index of the attachment.
+        attachment: usize,
+        /// Clear value that was passed.
+        obtained: ClearValue,
+    },
+}
+
+impl error::Error for CheckClearValuesError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckClearValuesError::WrongClearValuesCount { .. } => {
+                "the number of clear values is not the same as the number of attachments"
+            },
+            CheckClearValuesError::ClearValueTyMismatch { .. } => {
+                "the clear value of an attachment doesn't match the type of its format"
+            },
+            CheckClearValuesError::UnexpectedClearValue { .. } => {
+                "an attachment that isn't cleared received a clear value"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckClearValuesError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::Format;
+    use framebuffer::EmptySinglePassRenderPassDesc;
+    use framebuffer::LayoutAttachmentDescription;
+    use framebuffer::LayoutPassDependencyDescription;
+    use framebuffer::LayoutPassDescription;
+    use framebuffer::LoadOp;
+    use framebuffer::RenderPassDescClearValues;
+    use framebuffer::StoreOp;
+    use image::ImageLayout;
+
+    // Render pass description with a single color attachment, built by hand because the render
+    // pass macros aren't available in this module.
+    struct ColorDesc {
+        load: LoadOp,
+    }
+
+    unsafe impl RenderPassDesc for ColorDesc {
+        fn num_attachments(&self) -> usize {
+            1
+        }
+
+        fn attachment_desc(&self, num: usize) -> Option<LayoutAttachmentDescription> {
+            if num != 0 {
+                return None;
+            }
+
+            Some(LayoutAttachmentDescription {
+                     format: Format::R8G8B8A8Unorm,
+                     samples: 1,
+                     load: self.load,
+                     store: StoreOp::Store,
+                     stencil_load: LoadOp::DontCare,
+                     stencil_store: StoreOp::DontCare,
+                     initial_layout: ImageLayout::Undefined,
+                     final_layout: ImageLayout::ColorAttachmentOptimal,
+                 })
+        }
+
+        fn num_subpasses(&self) -> usize {
+            1
+        }
+
+        fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+            if num != 0 {
+                return None;
+            }
+
+            Some(LayoutPassDescription {
+                     color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+                     ..EmptySinglePassRenderPassDesc.subpass_desc(0).unwrap()
+                 })
+        }
+
+        fn num_dependencies(&self) -> usize {
+            0
+        }
+
+        fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+            None
+        }
+    }
+
+    unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for ColorDesc {
+        fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                -> Box<Iterator<Item = ClearValue>> {
+            Box::new(values.into_iter())
+        }
+    }
+
+    #[test]
+    fn wrong_ty() {
+        let rp = ColorDesc { load: LoadOp::Clear };
+
+        match check_clear_values(&rp, Some(ClearValue::Depth(1.0))) {
+            Err(CheckClearValuesError::ClearValueTyMismatch {
+                    attachment: 0,
+                    expected: FormatTy::Float,
+                    ..
+                }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_count() {
+        let rp = ColorDesc { load: LoadOp::DontCare };
+
+        check_clear_values(&rp, Some(ClearValue::None)).unwrap();
+
+        match check_clear_values(&rp, vec![ClearValue::None, ClearValue::None]) {
+            Err(CheckClearValuesError::WrongClearValuesCount {
+                    expected: 1,
+                    obtained: 2,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use buffer::TypedBufferAccess;
+use device::Device;
+use device::DeviceOwned;
+
+/// Checks whether a buffer can be used as the predicate of conditional rendering.
+///
+/// # Panic
+///
+/// - Panics if the buffer was not created with `device`.
+///
+pub fn check_begin_conditional_rendering<B>(device: &Device, buffer: &B)
+                                            -> Result<(), CheckBeginConditionalRenderingError>
+    where B: ?Sized + TypedBufferAccess<Content = u32>
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if !device.loaded_extensions().ext_conditional_rendering {
+        return Err(CheckBeginConditionalRenderingError::ExtensionNotEnabled);
+    }
+
+    if !buffer.inner().buffer.usage_conditional_rendering() {
+        return Err(CheckBeginConditionalRenderingError::BufferMissingUsage);
+    }
+
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckBeginConditionalRenderingError::WrongAlignment);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether conditional rendering can be begun.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBeginConditionalRenderingError {
+    /// The `ext_conditional_rendering` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The "conditional rendering" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the predicate within the buffer must be a multiple of 4.
+    WrongAlignment,
+}
+
+impl error::Error for CheckBeginConditionalRenderingError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckBeginConditionalRenderingError::ExtensionNotEnabled => {
+                "the `ext_conditional_rendering` extension must be enabled on the device"
+            },
+            CheckBeginConditionalRenderingError::BufferMissingUsage => {
+                "the conditional rendering usage must be enabled on the buffer"
+            },
+            CheckBeginConditionalRenderingError::WrongAlignment => {
+                "the offset of the predicate within the buffer must be a multiple of 4"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckBeginConditionalRenderingError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        match check_begin_conditional_rendering(&device, &buffer) {
+            Err(CheckBeginConditionalRenderingError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_usage() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_conditional_rendering]);
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        match check_begin_conditional_rendering(&device, &buffer) {
+            Err(CheckBeginConditionalRenderingError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_alignment() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_conditional_rendering]);
+        let usage = BufferUsage {
+            conditional_rendering: true,
+            ..BufferUsage::none()
+        };
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(), usage, 0u8 .. 8).unwrap();
+
+        let aligned = unsafe { buffer.slice(4 .. 8).unwrap().reinterpret::<u32>() };
+        check_begin_conditional_rendering(&device, &aligned).unwrap();
+
+        let unaligned = unsafe { buffer.slice(2 .. 6).unwrap().reinterpret::<u32>() };
+        match check_begin_conditional_rendering(&device, &unaligned) {
+            Err(CheckBeginConditionalRenderingError::WrongAlignment) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_device() {
+        let (dev1, _) = gfx_dev_and_queue!();
+        let (dev2, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(dev1, BufferUsage::all(), 1u32).unwrap();
+
+        assert_should_panic!({
+            let _ = check_begin_conditional_rendering(&dev2, &buffer);
+        });
+    }
+}
@@ -13,7 +13,6 @@ use std::fmt;
 use VulkanObject;
 use device::Device;
 use format::FormatTy;
-use format::PossibleCompressedFormatDesc;
 use image::ImageAccess;
 use image::ImageDimensions;
 
@@ -63,11 +62,9 @@ pub fn check_copy_image<S, D>(device: &Device, source: &S, source_offset: [i32;
         }
     }
 
-    // TODO: The correct check here is that the uncompressed element size of the source is
-    // equal to the compressed element size of the destination.  However, format doesn't
-    // currently expose this information, so to be safe, we simply disallow compressed formats.
-    if source.format().is_compressed() || destination.format().is_compressed() ||
-            (source.format().size() != destination.format().size()) {
+    // TODO: copies between compressed and uncompressed formats whose block sizes match are
+    // allowed by the specs, but the coordinates would then need to be converted.
+    if !source.format().is_copy_compatible_with(destination.format()) {
         return Err(CheckCopyImageError::SizeIncompatibleFormatsTypes {
                        source_format_ty: source.format().ty(),
                        destination_format_ty: destination.format().ty(),
@@ -160,9 +157,38 @@ pub fn check_copy_image<S, D>(device: &Device, source: &S, source_offset: [i32;
         ImageDimensions::Dim3d { .. } => {},
     }
 
+    // With compressed formats, the copied region must be made of whole blocks. The formats are
+    // copy-compatible, so the source and the destination have the same block dimensions.
+    let block = source.format().block_dimensions();
+    if block != [1, 1] {
+        let source_dimensions = [source_dimensions.width(), source_dimensions.height(),
+                                 source_dimensions.depth()];
+        if !is_block_aligned(block, source_offset, extent, source_dimensions) {
+            return Err(CheckCopyImageError::SourceNotAlignedToBlocks);
+        }
+
+        let destination_dimensions = [destination_dimensions.width(),
+                                      destination_dimensions.height(),
+                                      destination_dimensions.depth()];
+        if !is_block_aligned(block, destination_offset, extent, destination_dimensions) {
+            return Err(CheckCopyImageError::DestinationNotAlignedToBlocks);
+        }
+    }
+
     Ok(())
 }
 
+// Returns true if the region starts on a block boundary, and if its extent is a multiple of the
+// block dimensions unless it reaches the edge of the image. The offsets must be positive.
+fn is_block_aligned(block: [u32; 2], offset: [i32; 3], extent: [u32; 3], dimensions: [u32; 3])
+                    -> bool {
+    (0 .. 2).all(|n| {
+        let offset = offset[n] as u32;
+        offset % block[n] == 0 &&
+            (extent[n] % block[n] == 0 || offset + extent[n] == dimensions[n])
+    })
+}
+
 /// Error that can happen from `check_copy_image`.
 #[derive(Debug, Copy, Clone)]
 pub enum CheckCopyImageError {
@@ -185,6 +211,10 @@ pub enum CheckCopyImageError {
     DestinationCoordinatesOutOfRange,
     /// The offsets or extent are incompatible with the image type.
     IncompatibleRangeForImageType,
+    /// The format is compressed and the region of the source isn't made of whole blocks.
+    SourceNotAlignedToBlocks,
+    /// The format is compressed and the region of the destination isn't made of whole blocks.
+    DestinationNotAlignedToBlocks,
 }
 
 impl error::Error for CheckCopyImageError {
@@ -218,6 +248,13 @@ impl error::Error for CheckCopyImageError {
             CheckCopyImageError::IncompatibleRangeForImageType => {
                 "the offsets or extent are incompatible with the image type"
             },
+            CheckCopyImageError::SourceNotAlignedToBlocks => {
+                "the format is compressed and the region of the source isn't made of whole blocks"
+            },
+            CheckCopyImageError::DestinationNotAlignedToBlocks => {
+                "the format is compressed and the region of the destination isn't made of whole \
+                 blocks"
+            },
         }
     }
 }
@@ -228,3 +265,22 @@ impl fmt::Display for CheckCopyImageError {
         write!(fmt, "{}", error::Error::description(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::is_block_aligned;
+
+    #[test]
+    fn block_alignment() {
+        let dims = [18, 18, 1];
+
+        assert!(is_block_aligned([4, 4], [0, 0, 0], [8, 8, 1], dims));
+        assert!(is_block_aligned([4, 4], [4, 8, 0], [4, 4, 1], dims));
+        // The extent can end at the edge of the image.
+        assert!(is_block_aligned([4, 4], [16, 16, 0], [2, 2, 1], dims));
+
+        assert!(!is_block_aligned([4, 4], [2, 0, 0], [4, 4, 1], dims));
+        assert!(!is_block_aligned([4, 4], [0, 0, 0], [4, 6, 1], dims));
+        assert!(!is_block_aligned([4, 4], [8, 0, 0], [6, 4, 1], dims));
+    }
+}
@@ -10,11 +10,16 @@
 use std::error;
 use std::fmt;
 
+use descriptor::descriptor::DescriptorBufferDesc;
 use descriptor::descriptor::DescriptorDescSupersetError;
+use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor_set::DescriptorSetsCollection;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 
 /// Checks whether descriptor sets are compatible with the pipeline.
+///
+/// Also checks that each set provides one dynamic offset for each element of its dynamic buffer
+/// descriptors.
 pub fn check_descriptor_sets_validity<Pl, D>(pipeline: &Pl, descriptor_sets: &D)
                                              -> Result<(), CheckDescriptorSetsValidityError>
     where Pl: ?Sized + PipelineLayoutDesc,
@@ -48,6 +53,26 @@ pub fn check_descriptor_sets_validity<Pl, D>(pipeline: &Pl, descriptor_sets: &D)
                            });
             }
         }
+
+        let expected_offsets = (0 .. descriptor_sets.num_bindings_in_set(set_num).unwrap_or(0))
+            .filter_map(|binding_num| descriptor_sets.descriptor(set_num, binding_num))
+            .filter_map(|desc| match desc.ty {
+                            DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                         dynamic: Some(true),
+                                                         ..
+                                                     }) => Some(desc.array_count as usize),
+                            _ => None,
+                        })
+            .sum::<usize>();
+        let obtained_offsets = descriptor_sets.dynamic_offsets(set_num).len();
+
+        if expected_offsets != obtained_offsets {
+            return Err(CheckDescriptorSetsValidityError::WrongDynamicOffsetsCount {
+                           set_num: set_num,
+                           expected: expected_offsets,
+                           obtained: obtained_offsets,
+                       });
+        }
     }
 
     Ok(())
@@ -73,6 +98,17 @@ pub enum CheckDescriptorSetsValidityError {
         /// The binding number of the descriptor.
         binding_num: usize,
     },
+
+    /// A set doesn't provide one dynamic offset for each element of its dynamic buffer
+    /// descriptors.
+    WrongDynamicOffsetsCount {
+        /// The index of the set.
+        set_num: usize,
+        /// Number of dynamic offsets expected by the set.
+        expected: usize,
+        /// Number of dynamic offsets that the set provides.
+        obtained: usize,
+    },
 }
 
 impl error::Error for CheckDescriptorSetsValidityError {
@@ -85,6 +121,10 @@ impl error::Error for CheckDescriptorSetsValidityError {
             CheckDescriptorSetsValidityError::IncompatibleDescriptor { .. } => {
                 "a descriptor in the provided sets is not compatible with what is expected"
             },
+            CheckDescriptorSetsValidityError::WrongDynamicOffsetsCount { .. } => {
+                "a set doesn't provide one dynamic offset for each element of its dynamic buffer \
+                 descriptors"
+            },
         }
     }
 
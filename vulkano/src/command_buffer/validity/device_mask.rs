@@ -0,0 +1,110 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use device::Device;
+
+/// Checks whether a device mask can be used with `device`.
+pub fn check_device_mask(device: &Device, device_mask: u32) -> Result<(), CheckDeviceMaskError> {
+    if !device.supports_device_masks() {
+        return Err(CheckDeviceMaskError::DeviceGroupNotSupported);
+    }
+
+    if device_mask == 0 {
+        return Err(CheckDeviceMaskError::EmptyMask);
+    }
+
+    // The shift is done on 64 bits, as a device group can contain 32 physical devices.
+    if (device_mask as u64) >> device.device_group_size() != 0 {
+        return Err(CheckDeviceMaskError::MaskOutOfRange {
+                       device_mask: device_mask,
+                       device_group_size: device.device_group_size(),
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether a device mask is valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckDeviceMaskError {
+    /// Device masks require Vulkan 1.1 or the `khr_device_group` extension.
+    DeviceGroupNotSupported,
+    /// The device mask must contain at least one device.
+    EmptyMask,
+    /// The device mask contains devices that aren't part of the device group.
+    MaskOutOfRange {
+        /// The mask that was passed.
+        device_mask: u32,
+        /// The number of physical devices of the device.
+        device_group_size: u32,
+    },
+}
+
+impl error::Error for CheckDeviceMaskError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckDeviceMaskError::DeviceGroupNotSupported => {
+                "device masks require Vulkan 1.1 or the `khr_device_group` extension"
+            },
+            CheckDeviceMaskError::EmptyMask => {
+                "the device mask must contain at least one device"
+            },
+            CheckDeviceMaskError::MaskOutOfRange { .. } => {
+                "the device mask contains devices that aren't part of the device group"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckDeviceMaskError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_masks() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if !device.supports_device_masks() {
+            match check_device_mask(&device, 1) {
+                Err(CheckDeviceMaskError::DeviceGroupNotSupported) => (),
+                _ => panic!(),
+            }
+            return;
+        }
+
+        assert_eq!(device.device_group_size(), 1);
+        assert!(check_device_mask(&device, 1).is_ok());
+
+        match check_device_mask(&device, 0) {
+            Err(CheckDeviceMaskError::EmptyMask) => (),
+            _ => panic!(),
+        }
+
+        match check_device_mask(&device, 0b10) {
+            Err(CheckDeviceMaskError::MaskOutOfRange { device_group_size: 1, .. }) => (),
+            _ => panic!(),
+        }
+
+        match check_device_mask(&device, 0xffffffff) {
+            Err(CheckDeviceMaskError::MaskOutOfRange { device_group_size: 1, .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use device::Device;
+use pipeline::GraphicsPipelineAbstract;
+
+/// Checks whether a `draw_mesh_tasks` command can be executed with the given pipeline and number
+/// of workgroups.
+///
+/// The workgroups are task shader workgroups if the pipeline has a task shader, and mesh shader
+/// workgroups otherwise. The limits of the device are chosen accordingly.
+pub fn check_draw_mesh_tasks<Gp>(device: &Device, pipeline: &Gp, group_counts: [u32; 3])
+                                 -> Result<(), CheckDrawMeshTasksError>
+    where Gp: ?Sized + GraphicsPipelineAbstract
+{
+    if !pipeline.uses_mesh_shading() {
+        return Err(CheckDrawMeshTasksError::NotMeshPipeline);
+    }
+
+    // Mesh pipelines can only be created if the extension is enabled.
+    let properties = device
+        .mesh_shader_properties()
+        .expect("mesh pipeline on a device without the ext_mesh_shader extension");
+    let (max_count, max_total_count) = if pipeline.has_task_shader() {
+        (properties.max_task_work_group_count, properties.max_task_work_group_total_count)
+    } else {
+        (properties.max_mesh_work_group_count, properties.max_mesh_work_group_total_count)
+    };
+
+    if group_counts[0] > max_count[0] || group_counts[1] > max_count[1] ||
+        group_counts[2] > max_count[2]
+    {
+        return Err(CheckDrawMeshTasksError::MaxWorkGroupCountExceeded {
+                       requested: group_counts,
+                       max: max_count,
+                   });
+    }
+
+    let total = group_counts[0] as u64 * group_counts[1] as u64 * group_counts[2] as u64;
+    if total > max_total_count as u64 {
+        return Err(CheckDrawMeshTasksError::MaxWorkGroupTotalCountExceeded {
+                       requested: total,
+                       max: max_total_count,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking the validity of a `draw_mesh_tasks` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckDrawMeshTasksError {
+    /// The pipeline doesn't use mesh shading.
+    NotMeshPipeline,
+    /// The number of workgroups in a dimension exceeds the limit of the device.
+    MaxWorkGroupCountExceeded {
+        /// The number of workgroups that was requested.
+        requested: [u32; 3],
+        /// The limit of the device.
+        max: [u32; 3],
+    },
+    /// The total number of workgroups exceeds the limit of the device.
+    MaxWorkGroupTotalCountExceeded {
+        /// The number of workgroups that was requested.
+        requested: u64,
+        /// The limit of the device.
+        max: u32,
+    },
+}
+
+impl error::Error for CheckDrawMeshTasksError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckDrawMeshTasksError::NotMeshPipeline => {
+                "the pipeline doesn't use mesh shading"
+            },
+            CheckDrawMeshTasksError::MaxWorkGroupCountExceeded { .. } => {
+                "the number of workgroups in a dimension exceeds the limit of the device"
+            },
+            CheckDrawMeshTasksError::MaxWorkGroupTotalCountExceeded { .. } => {
+                "the total number of workgroups exceeds the limit of the device"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckDrawMeshTasksError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
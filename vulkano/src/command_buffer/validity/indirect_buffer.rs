@@ -0,0 +1,136 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use buffer::BufferAccess;
+use device::Device;
+use device::DeviceOwned;
+
+/// Checks whether an indirect buffer can be used to perform `draw_count` draws.
+///
+/// # Panic
+///
+/// - Panics if the buffer not created with `device`.
+///
+pub fn check_indirect_buffer<B>(device: &Device, buffer: &B, draw_count: u32)
+                                -> Result<(), CheckIndirectBufferError>
+    where B: ?Sized + BufferAccess
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if !buffer.inner().buffer.usage_indirect_buffer() {
+        return Err(CheckIndirectBufferError::BufferMissingUsage);
+    }
+
+    if draw_count > 1 && !device.enabled_features().multi_draw_indirect {
+        return Err(CheckIndirectBufferError::MultiDrawIndirectFeatureNotEnabled);
+    }
+
+    let limit = device.physical_device().limits().max_draw_indirect_count();
+    if draw_count > limit {
+        return Err(CheckIndirectBufferError::MaxDrawIndirectCountExceeded {
+                       limit: limit,
+                       requested: draw_count,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether an indirect buffer is valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckIndirectBufferError {
+    /// The "indirect buffer" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The `multi_draw_indirect` feature must be enabled in order to perform more than one draw
+    /// with a single command.
+    MultiDrawIndirectFeatureNotEnabled,
+    /// The number of draws is larger than the `max_draw_indirect_count` limit.
+    MaxDrawIndirectCountExceeded {
+        /// The limit of the device.
+        limit: u32,
+        /// The number of draws that was requested.
+        requested: u32,
+    },
+}
+
+impl error::Error for CheckIndirectBufferError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckIndirectBufferError::BufferMissingUsage => {
+                "the indirect buffer usage must be enabled on the buffer"
+            },
+            CheckIndirectBufferError::MultiDrawIndirectFeatureNotEnabled => {
+                "the `multi_draw_indirect` feature must be enabled in order to perform more than \
+                 one draw"
+            },
+            CheckIndirectBufferError::MaxDrawIndirectCountExceeded { .. } => {
+                "the number of draws exceeds the `max_draw_indirect_count` limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckIndirectBufferError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::DrawIndirectCommand;
+
+    fn command() -> DrawIndirectCommand {
+        DrawIndirectCommand {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance: 0,
+        }
+    }
+
+    #[test]
+    fn missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::vertex_buffer(),
+                                           Some(command()).into_iter())
+                .unwrap();
+
+        match check_indirect_buffer(&device, &buffer, 1) {
+            Err(CheckIndirectBufferError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multi_draw_without_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                           vec![command(), command()].into_iter())
+                .unwrap();
+
+        assert!(check_indirect_buffer(&device, &buffer, 1).is_ok());
+        match check_indirect_buffer(&device, &buffer, 2) {
+            Err(CheckIndirectBufferError::MultiDrawIndirectFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::u32;
+
+use VulkanObject;
+use buffer::BufferAccess;
+use buffer::TypedBufferAccess;
+use device::Device;
+use device::DeviceOwned;
+
+/// Checks whether an indirect buffer that contains `max_draw_count` commands and a count buffer
+/// can be used together to perform a number of draws that is read from the count buffer.
+///
+/// Returns the maximum number of draws, as passed to the device.
+///
+/// # Panic
+///
+/// - Panics if one of the buffers was not created with `device`.
+///
+pub fn check_indirect_count<B, C>(device: &Device, buffer: &B, max_draw_count: usize,
+                                  count_buffer: &C)
+                                  -> Result<u32, CheckIndirectCountError>
+    where B: ?Sized + BufferAccess,
+          C: ?Sized + TypedBufferAccess<Content = u32>
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+    assert_eq!(count_buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if !device.supports_draw_indirect_count() {
+        return Err(CheckIndirectCountError::ExtensionNotEnabled);
+    }
+
+    if !buffer.inner().buffer.usage_indirect_buffer() {
+        return Err(CheckIndirectCountError::BufferMissingUsage);
+    }
+
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CheckIndirectCountError::BufferWrongAlignment);
+    }
+
+    if !count_buffer.inner().buffer.usage_indirect_buffer() {
+        return Err(CheckIndirectCountError::CountBufferMissingUsage);
+    }
+
+    if count_buffer.inner().offset % 4 != 0 {
+        return Err(CheckIndirectCountError::CountBufferWrongAlignment);
+    }
+
+    if max_draw_count > u32::MAX as usize {
+        return Err(CheckIndirectCountError::MaxDrawCountOverflow { requested: max_draw_count });
+    }
+
+    Ok(max_draw_count as u32)
+}
+
+/// Error that can happen when checking whether an indirect buffer and a count buffer are valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckIndirectCountError {
+    /// The `khr_draw_indirect_count` extension must be enabled on the device, unless the device
+    /// uses Vulkan 1.2 and supports the `drawIndirectCount` feature.
+    ExtensionNotEnabled,
+    /// The "indirect buffer" usage must be enabled on the indirect buffer.
+    BufferMissingUsage,
+    /// The offset of the commands within the indirect buffer must be a multiple of 4.
+    BufferWrongAlignment,
+    /// The "indirect buffer" usage must be enabled on the count buffer.
+    CountBufferMissingUsage,
+    /// The offset of the count within the count buffer must be a multiple of 4.
+    CountBufferWrongAlignment,
+    /// The indirect buffer contains more commands than the maximum number of draws that can be
+    /// passed to the device.
+    MaxDrawCountOverflow {
+        /// The number of commands in the indirect buffer.
+        requested: usize,
+    },
+}
+
+impl error::Error for CheckIndirectCountError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckIndirectCountError::ExtensionNotEnabled => {
+                "the `khr_draw_indirect_count` extension must be enabled on the device, or the \
+                 `drawIndirectCount` feature of Vulkan 1.2 must be supported"
+            },
+            CheckIndirectCountError::BufferMissingUsage => {
+                "the indirect buffer usage must be enabled on the indirect buffer"
+            },
+            CheckIndirectCountError::BufferWrongAlignment => {
+                "the offset of the commands within the indirect buffer must be a multiple of 4"
+            },
+            CheckIndirectCountError::CountBufferMissingUsage => {
+                "the indirect buffer usage must be enabled on the count buffer"
+            },
+            CheckIndirectCountError::CountBufferWrongAlignment => {
+                "the offset of the count within the count buffer must be a multiple of 4"
+            },
+            CheckIndirectCountError::MaxDrawCountOverflow { .. } => {
+                "the indirect buffer contains more commands than the maximum number of draws"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckIndirectCountError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::DrawIndirectCommand;
+    use version::Version;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.supports_draw_indirect_count() {
+            // Vulkan 1.2 devices don't need the extension.
+            return;
+        }
+
+        let command = DrawIndirectCommand {
+            vertex_count: 3,
+            instance_count: 1,
+            first_vertex: 0,
+            first_instance: 0,
+        };
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                                    Some(command).into_iter())
+            .unwrap();
+        let count_buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        match check_indirect_count(&device, &buffer, 1, &count_buffer) {
+            Err(CheckIndirectCountError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn vulkan_1_2_without_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.api_version() < Version::V1_2 {
+            return;
+        }
+
+        let indirect = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                                      0u32 .. 4)
+            .unwrap();
+        let count = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        match check_indirect_count(&device, &indirect, 1, &count) {
+            Ok(1) => assert!(device.supports_draw_indirect_count()),
+            Err(CheckIndirectCountError::ExtensionNotEnabled) => {
+                assert!(!device.supports_draw_indirect_count())
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_usage() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_draw_indirect_count]);
+        let indirect = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                                      0u32 .. 4)
+            .unwrap();
+        let count = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+        let uniform = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::uniform_buffer(),
+                                                     1u32)
+            .unwrap();
+
+        assert_eq!(check_indirect_count(&device, &indirect, 1, &count).unwrap(), 1);
+
+        match check_indirect_count(&device, &uniform, 1, &count) {
+            Err(CheckIndirectCountError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+
+        match check_indirect_count(&device, &indirect, 1, &uniform) {
+            Err(CheckIndirectCountError::CountBufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_alignment() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_draw_indirect_count]);
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(), 0u8 .. 32)
+            .unwrap();
+        let count = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        let unaligned = buffer.slice(2 .. 18).unwrap();
+        match check_indirect_count(&device, &unaligned, 1, &count) {
+            Err(CheckIndirectCountError::BufferWrongAlignment) => (),
+            _ => panic!(),
+        }
+
+        let unaligned_count = unsafe { buffer.slice(2 .. 6).unwrap().reinterpret::<u32>() };
+        match check_indirect_count(&device, &buffer, 1, &unaligned_count) {
+            Err(CheckIndirectCountError::CountBufferWrongAlignment) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn max_draw_count_overflow() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_draw_indirect_count]);
+        let indirect = CpuAccessibleBuffer::from_iter(device.clone(), BufferUsage::all(),
+                                                      0u32 .. 4)
+            .unwrap();
+        let count = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 1u32)
+            .unwrap();
+
+        let max = u32::MAX as usize;
+        assert_eq!(check_indirect_count(&device, &indirect, max, &count).unwrap(), u32::MAX);
+
+        if let Some(requested) = max.checked_add(1) {
+            match check_indirect_count(&device, &indirect, requested, &count) {
+                Err(CheckIndirectCountError::MaxDrawCountOverflow { requested: r }) => {
+                    assert_eq!(r, requested);
+                },
+                _ => panic!(),
+            }
+        }
+    }
+}
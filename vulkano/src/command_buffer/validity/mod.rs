@@ -9,31 +9,69 @@
 
 //! Functions that check the validity of commands.
 
+pub use self::acceleration_structure::{CheckBuildAccelerationStructureError,
+                                       check_build_acceleration_structure};
 pub use self::blit_image::{CheckBlitImageError, check_blit_image};
 pub use self::clear_color_image::{CheckClearColorImageError, check_clear_color_image};
+pub use self::clear_values::{CheckClearValuesError, check_clear_values};
+pub use self::conditional_rendering::{CheckBeginConditionalRenderingError,
+                                      check_begin_conditional_rendering};
 pub use self::copy_buffer::{CheckCopyBuffer, CheckCopyBufferError, check_copy_buffer};
 pub use self::copy_image::{CheckCopyImageError, check_copy_image};
 pub use self::copy_image_buffer::{CheckCopyBufferImageError, CheckCopyBufferImageTy,
                                   check_copy_buffer_image};
 pub use self::descriptor_sets::{CheckDescriptorSetsValidityError, check_descriptor_sets_validity};
+pub use self::device_mask::{CheckDeviceMaskError, check_device_mask};
 pub use self::dispatch::{CheckDispatchError, check_dispatch};
+pub use self::draw_mesh_tasks::{CheckDrawMeshTasksError, check_draw_mesh_tasks};
 pub use self::dynamic_state::{CheckDynamicStateValidityError, check_dynamic_state_validity};
 pub use self::fill_buffer::{CheckFillBufferError, check_fill_buffer};
 pub use self::index_buffer::{CheckIndexBuffer, CheckIndexBufferError, check_index_buffer};
+pub use self::indirect_buffer::{CheckIndirectBufferError, check_indirect_buffer};
+pub use self::indirect_count::{CheckIndirectCountError, check_indirect_count};
+pub use self::ownership_transfer::{CheckOwnershipTransferError, check_buffer_ownership_transfer,
+                                   check_image_ownership_transfer};
+pub use self::pipeline_barrier::{CheckPipelineBarrierError, check_buffer_memory_barrier,
+                                 check_image_memory_barrier, check_memory_barrier};
 pub use self::push_constants::{CheckPushConstantsValidityError, check_push_constants_validity};
+pub use self::push_descriptor_set::{CheckPushDescriptorSetError, check_push_descriptor_set};
+pub use self::query::{CheckBeginQueryError, CheckCopyQueryPoolResultsError, CheckEndQueryError,
+                      CheckResetQueryPoolError, CheckWriteTimestampError, check_begin_query,
+                      check_copy_query_pool_results, check_end_query, check_reset_query_pool,
+                      check_write_timestamp};
+pub use self::trace_rays::{CheckTraceRaysError, check_trace_rays};
+pub use self::transform_feedback::{CheckDrawIndirectByteCountError,
+                                   CheckTransformFeedbackBuffersError,
+                                   CheckTransformFeedbackCountersError,
+                                   check_draw_indirect_byte_count,
+                                   check_transform_feedback_buffers,
+                                   check_transform_feedback_counter_buffers};
 pub use self::update_buffer::{CheckUpdateBufferError, check_update_buffer};
 pub use self::vertex_buffers::{CheckVertexBuffer, CheckVertexBufferError, check_vertex_buffers};
 
+mod acceleration_structure;
 mod blit_image;
 mod clear_color_image;
+mod clear_values;
+mod conditional_rendering;
 mod copy_buffer;
 mod copy_image;
 mod copy_image_buffer;
 mod descriptor_sets;
+mod device_mask;
 mod dispatch;
+mod draw_mesh_tasks;
 mod dynamic_state;
 mod fill_buffer;
 mod index_buffer;
+mod indirect_buffer;
+mod indirect_count;
+mod ownership_transfer;
+mod pipeline_barrier;
 mod push_constants;
+mod push_descriptor_set;
+mod query;
+mod trace_rays;
+mod transform_feedback;
 mod update_buffer;
 mod vertex_buffers;
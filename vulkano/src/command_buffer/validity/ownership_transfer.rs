@@ -0,0 +1,158 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use buffer::BufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageAccess;
+use VulkanObject;
+
+/// Checks whether the ownership of a buffer can be transferred between two queue families.
+///
+/// # Panic
+///
+/// - Panics if the buffer was not created with `device`.
+///
+pub fn check_buffer_ownership_transfer<B>(device: &Device, buffer: &B, source_family: u32,
+                                          destination_family: u32)
+                                          -> Result<(), CheckOwnershipTransferError>
+    where B: ?Sized + BufferAccess
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if buffer.inner().buffer.concurrent_sharing() {
+        return Err(CheckOwnershipTransferError::ConcurrentSharing);
+    }
+
+    check_families(device, source_family, destination_family)
+}
+
+/// Checks whether the ownership of an image can be transferred between two queue families.
+///
+/// # Panic
+///
+/// - Panics if the image was not created with `device`.
+///
+pub fn check_image_ownership_transfer<I>(device: &Device, image: &I, source_family: u32,
+                                         destination_family: u32)
+                                         -> Result<(), CheckOwnershipTransferError>
+    where I: ?Sized + ImageAccess
+{
+    assert_eq!(image.inner().image.device().internal_object(),
+               device.internal_object());
+
+    if image.inner().image.concurrent_sharing() {
+        return Err(CheckOwnershipTransferError::ConcurrentSharing);
+    }
+
+    check_families(device, source_family, destination_family)
+}
+
+fn check_families(device: &Device, source_family: u32, destination_family: u32)
+                  -> Result<(), CheckOwnershipTransferError> {
+    let physical_device = device.physical_device();
+
+    if physical_device.queue_family_by_id(source_family).is_none() ||
+        physical_device.queue_family_by_id(destination_family).is_none()
+    {
+        return Err(CheckOwnershipTransferError::InvalidQueueFamily);
+    }
+
+    if source_family == destination_family {
+        return Err(CheckOwnershipTransferError::SameQueueFamily);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to transfer the ownership of a resource between queue
+/// families.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckOwnershipTransferError {
+    /// The resource was created with a concurrent sharing mode and doesn't need any ownership
+    /// transfer.
+    ConcurrentSharing,
+    /// One of the queue families doesn't belong to the physical device of the resource.
+    InvalidQueueFamily,
+    /// The source and destination queue families are the same.
+    SameQueueFamily,
+}
+
+impl error::Error for CheckOwnershipTransferError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckOwnershipTransferError::ConcurrentSharing => {
+                "the resource was created with a concurrent sharing mode"
+            },
+            CheckOwnershipTransferError::InvalidQueueFamily => {
+                "one of the queue families doesn't belong to the physical device of the resource"
+            },
+            CheckOwnershipTransferError::SameQueueFamily => {
+                "the source and destination queue families are the same"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckOwnershipTransferError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn same_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let family = queue.family().id();
+        match check_buffer_ownership_transfer(&device, &buffer, family, family) {
+            Err(CheckOwnershipTransferError::SameQueueFamily) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        match check_buffer_ownership_transfer(&device, &buffer, queue.family().id(), 0xffff) {
+            Err(CheckOwnershipTransferError::InvalidQueueFamily) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_device() {
+        let (dev1, queue) = gfx_dev_and_queue!();
+        let (dev2, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(dev1, BufferUsage::all(), 0u32).unwrap();
+
+        let family = queue.family().id();
+        assert_should_panic!({
+                                 let _ = check_buffer_ownership_transfer(&dev2, &buffer, family,
+                                                                         family);
+                             });
+    }
+}
@@ -0,0 +1,343 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use buffer::BufferAccess;
+use command_buffer::validity::CheckOwnershipTransferError;
+use command_buffer::validity::check_buffer_ownership_transfer;
+use command_buffer::validity::check_image_ownership_transfer;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageAccess;
+use image::ImageLayout;
+use sync::AccessFlagBits;
+use sync::BufferMemoryBarrier;
+use sync::ImageMemoryBarrier;
+use sync::MemoryBarrier;
+use sync::PipelineStages;
+
+/// Checks whether a global memory barrier is valid.
+pub fn check_memory_barrier(device: &Device, barrier: &MemoryBarrier)
+                            -> Result<(), CheckPipelineBarrierError> {
+    check_stages_access(device,
+                        barrier.source_stages,
+                        barrier.source_access,
+                        barrier.destination_stages,
+                        barrier.destination_access)
+}
+
+/// Checks whether a buffer memory barrier is valid when added to a command buffer of the queue
+/// family `queue_family`.
+///
+/// # Panic
+///
+/// - Panics if the buffer was not created with `device`.
+///
+pub fn check_buffer_memory_barrier<B>(device: &Device, queue_family: u32,
+                                      barrier: &BufferMemoryBarrier<B>)
+                                      -> Result<(), CheckPipelineBarrierError>
+    where B: BufferAccess
+{
+    assert_eq!(barrier.buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    check_stages_access(device,
+                        barrier.source_stages,
+                        barrier.source_access,
+                        barrier.destination_stages,
+                        barrier.destination_access)?;
+
+    let end = barrier.offset.checked_add(barrier.size);
+    if barrier.size == 0 || end.map(|end| end > barrier.buffer.size()).unwrap_or(true) {
+        return Err(CheckPipelineBarrierError::BufferRangeOutOfBounds);
+    }
+
+    if let Some((source, destination)) = barrier.queue_family_transfer {
+        check_buffer_ownership_transfer(device, &barrier.buffer, source, destination)?;
+        check_transfer_family(queue_family, source, destination)?;
+    }
+
+    Ok(())
+}
+
+/// Checks whether an image memory barrier is valid when added to a command buffer of the queue
+/// family `queue_family`.
+///
+/// # Panic
+///
+/// - Panics if the image was not created with `device`.
+///
+pub fn check_image_memory_barrier<I>(device: &Device, queue_family: u32,
+                                     barrier: &ImageMemoryBarrier<I>)
+                                     -> Result<(), CheckPipelineBarrierError>
+    where I: ImageAccess
+{
+    assert_eq!(barrier.image.inner().image.device().internal_object(),
+               device.internal_object());
+
+    check_stages_access(device,
+                        barrier.source_stages,
+                        barrier.source_access,
+                        barrier.destination_stages,
+                        barrier.destination_access)?;
+
+    if barrier.mipmaps.start >= barrier.mipmaps.end ||
+        barrier.mipmaps.end > barrier.image.mipmap_levels() ||
+        barrier.layers.start >= barrier.layers.end ||
+        barrier.layers.end > barrier.image.dimensions().array_layers()
+    {
+        return Err(CheckPipelineBarrierError::ImageRangeOutOfBounds);
+    }
+
+    match barrier.layout {
+        ImageLayout::Undefined | ImageLayout::Preinitialized => {
+            return Err(CheckPipelineBarrierError::InvalidLayout);
+        },
+        _ => (),
+    }
+
+    if let Some((source, destination)) = barrier.queue_family_transfer {
+        check_image_ownership_transfer(device, &barrier.image, source, destination)?;
+        check_transfer_family(queue_family, source, destination)?;
+    }
+
+    Ok(())
+}
+
+fn check_stages_access(device: &Device, source_stages: PipelineStages,
+                       source_access: AccessFlagBits, destination_stages: PipelineStages,
+                       destination_access: AccessFlagBits)
+                       -> Result<(), CheckPipelineBarrierError> {
+    if source_stages == PipelineStages::none() || destination_stages == PipelineStages::none() {
+        return Err(CheckPipelineBarrierError::NoStage);
+    }
+
+    if !source_access.is_compatible_with(&source_stages) ||
+        !destination_access.is_compatible_with(&destination_stages)
+    {
+        return Err(CheckPipelineBarrierError::AccessNotSupportedByStages);
+    }
+
+    let stages = source_stages | destination_stages;
+
+    if stages.geometry_shader && !device.enabled_features().geometry_shader {
+        return Err(CheckPipelineBarrierError::GeometryShaderFeatureNotEnabled);
+    }
+
+    if (stages.tessellation_control_shader || stages.tessellation_evaluation_shader) &&
+        !device.enabled_features().tessellation_shader
+    {
+        return Err(CheckPipelineBarrierError::TessellationShaderFeatureNotEnabled);
+    }
+
+    Ok(())
+}
+
+fn check_transfer_family(queue_family: u32, source: u32, destination: u32)
+                         -> Result<(), CheckPipelineBarrierError> {
+    if source != queue_family && destination != queue_family {
+        return Err(CheckPipelineBarrierError::WrongQueueFamily);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a pipeline barrier.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckPipelineBarrierError {
+    /// The source or the destination stages are empty.
+    NoStage,
+    /// The source or destination access types aren't supported by the corresponding stages.
+    AccessNotSupportedByStages,
+    /// The geometry shader stage was used but the `geometry_shader` feature is not enabled.
+    GeometryShaderFeatureNotEnabled,
+    /// A tessellation stage was used but the `tessellation_shader` feature is not enabled.
+    TessellationShaderFeatureNotEnabled,
+    /// The range of the buffer is empty or out of the bounds of the buffer.
+    BufferRangeOutOfBounds,
+    /// The range of mipmap levels or array layers is empty or out of the bounds of the image.
+    ImageRangeOutOfBounds,
+    /// The layout of the image is `Undefined` or `Preinitialized`.
+    InvalidLayout,
+    /// Neither the source nor the destination queue family of the ownership transfer are the
+    /// queue family of the command buffer.
+    WrongQueueFamily,
+    /// The ownership of the resource can't be transferred.
+    OwnershipTransferError(CheckOwnershipTransferError),
+}
+
+impl error::Error for CheckPipelineBarrierError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckPipelineBarrierError::NoStage => {
+                "the source or the destination stages are empty"
+            },
+            CheckPipelineBarrierError::AccessNotSupportedByStages => {
+                "the source or destination access types aren't supported by the corresponding \
+                 stages"
+            },
+            CheckPipelineBarrierError::GeometryShaderFeatureNotEnabled => {
+                "the geometry shader stage was used but the `geometry_shader` feature is not \
+                 enabled"
+            },
+            CheckPipelineBarrierError::TessellationShaderFeatureNotEnabled => {
+                "a tessellation stage was used but the `tessellation_shader` feature is not \
+                 enabled"
+            },
+            CheckPipelineBarrierError::BufferRangeOutOfBounds => {
+                "the range of the buffer is empty or out of the bounds of the buffer"
+            },
+            CheckPipelineBarrierError::ImageRangeOutOfBounds => {
+                "the range of mipmap levels or array layers is empty or out of the bounds of the \
+                 image"
+            },
+            CheckPipelineBarrierError::InvalidLayout => {
+                "the layout of the image is `Undefined` or `Preinitialized`"
+            },
+            CheckPipelineBarrierError::WrongQueueFamily => {
+                "neither the source nor the destination queue family of the ownership transfer \
+                 are the queue family of the command buffer"
+            },
+            CheckPipelineBarrierError::OwnershipTransferError(_) => {
+                "the ownership of the resource can't be transferred"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            CheckPipelineBarrierError::OwnershipTransferError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CheckPipelineBarrierError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<CheckOwnershipTransferError> for CheckPipelineBarrierError {
+    #[inline]
+    fn from(err: CheckOwnershipTransferError) -> CheckPipelineBarrierError {
+        CheckPipelineBarrierError::OwnershipTransferError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn no_stage() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let barrier = MemoryBarrier {
+            source_stages: PipelineStages::none(),
+            source_access: AccessFlagBits::none(),
+            destination_stages: PipelineStages {
+                transfer: true,
+                ..PipelineStages::none()
+            },
+            destination_access: AccessFlagBits::none(),
+        };
+
+        match check_memory_barrier(&device, &barrier) {
+            Err(CheckPipelineBarrierError::NoStage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn access_not_supported_by_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let barrier = MemoryBarrier {
+            source_stages: PipelineStages {
+                transfer: true,
+                ..PipelineStages::none()
+            },
+            source_access: AccessFlagBits {
+                transfer_write: true,
+                ..AccessFlagBits::none()
+            },
+            destination_stages: PipelineStages {
+                transfer: true,
+                ..PipelineStages::none()
+            },
+            destination_access: AccessFlagBits {
+                shader_read: true,
+                ..AccessFlagBits::none()
+            },
+        };
+
+        match check_memory_barrier(&device, &barrier) {
+            Err(CheckPipelineBarrierError::AccessNotSupportedByStages) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn buffer_range_out_of_bounds() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let mut barrier = BufferMemoryBarrier::new(buffer,
+                                                   transfer,
+                                                   AccessFlagBits::none(),
+                                                   transfer,
+                                                   AccessFlagBits::none());
+        check_buffer_memory_barrier(&device, queue.family().id(), &barrier).unwrap();
+
+        barrier.offset = 2;
+        match check_buffer_memory_barrier(&device, queue.family().id(), &barrier) {
+            Err(CheckPipelineBarrierError::BufferRangeOutOfBounds) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn buffer_range_overflow() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        let transfer = PipelineStages {
+            transfer: true,
+            ..PipelineStages::none()
+        };
+        let mut barrier = BufferMemoryBarrier::new(buffer,
+                                                   transfer,
+                                                   AccessFlagBits::none(),
+                                                   transfer,
+                                                   AccessFlagBits::none());
+        barrier.offset = 1;
+        barrier.size = usize::max_value();
+        match check_buffer_memory_barrier(&device, queue.family().id(), &barrier) {
+            Err(CheckPipelineBarrierError::BufferRangeOutOfBounds) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use descriptor::descriptor_set::PersistentDescriptorSetBuilder;
+use descriptor::descriptor_set::PushDescriptorProperties;
+use descriptor::pipeline_layout::PipelineLayoutAbstract;
+use device::Device;
+
+/// Checks whether the descriptors of a builder can be pushed with `vkCmdPushDescriptorSetKHR`.
+pub fn check_push_descriptor_set<L, R>(device: &Device,
+                                       descriptors: &PersistentDescriptorSetBuilder<L, R>)
+                                       -> Result<(), CheckPushDescriptorSetError>
+    where L: PipelineLayoutAbstract
+{
+    if !device.loaded_extensions().khr_push_descriptor {
+        return Err(CheckPushDescriptorSetError::ExtensionNotEnabled);
+    }
+
+    let layout = descriptors.pipeline_layout();
+    let set_id = descriptors.set_id();
+
+    let is_push_descriptor = layout
+        .descriptor_set_layout(set_id)
+        .map(|l| l.is_push_descriptor())
+        .unwrap_or(false);
+    if !is_push_descriptor {
+        return Err(CheckPushDescriptorSetError::NotPushDescriptorLayout);
+    }
+
+    let expected = layout.num_bindings_in_set(set_id).unwrap_or(0);
+    if expected > descriptors.num_filled_bindings() {
+        return Err(CheckPushDescriptorSetError::MissingDescriptors {
+                       expected: expected as u32,
+                       obtained: descriptors.num_filled_bindings() as u32,
+                   });
+    }
+
+    if descriptors.has_copies() {
+        return Err(CheckPushDescriptorSetError::CopiesNotAllowed);
+    }
+
+    let obtained = (0 .. expected)
+        .filter_map(|binding| layout.descriptor(set_id, binding))
+        .fold(0u64, |total, desc| total + desc.array_count as u64);
+    let max = PushDescriptorProperties::from_physical_device(device.physical_device())
+        .max_push_descriptors;
+    if obtained > max as u64 {
+        return Err(CheckPushDescriptorSetError::TooManyDescriptors {
+                       max: max,
+                       obtained: obtained,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to push descriptors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckPushDescriptorSetError {
+    /// The `khr_push_descriptor` extension is not enabled on the device.
+    ExtensionNotEnabled,
+    /// The layout of the set wasn't created with `UnsafeDescriptorSetLayout::push_descriptor`.
+    NotPushDescriptorLayout,
+    /// Not all the bindings of the set have been filled.
+    MissingDescriptors {
+        /// Number of bindings in the set.
+        expected: u32,
+        /// Number of bindings that have been filled.
+        obtained: u32,
+    },
+    /// Descriptors can't be copied from another set when they are pushed.
+    CopiesNotAllowed,
+    /// The set contains more descriptors than the `maxPushDescriptors` limit of the device.
+    TooManyDescriptors {
+        /// The `maxPushDescriptors` limit.
+        max: u32,
+        /// Number of descriptors in the set.
+        obtained: u64,
+    },
+}
+
+impl error::Error for CheckPushDescriptorSetError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckPushDescriptorSetError::ExtensionNotEnabled => {
+                "the `khr_push_descriptor` extension is not enabled on the device"
+            },
+            CheckPushDescriptorSetError::NotPushDescriptorLayout => {
+                "the layout of the set isn't a push descriptor layout"
+            },
+            CheckPushDescriptorSetError::MissingDescriptors { .. } => {
+                "not all the bindings of the set have been filled"
+            },
+            CheckPushDescriptorSetError::CopiesNotAllowed => {
+                "descriptors can't be copied from another set when they are pushed"
+            },
+            CheckPushDescriptorSetError::TooManyDescriptors { .. } => {
+                "the set contains more descriptors than the `maxPushDescriptors` limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckPushDescriptorSetError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let layout = RuntimePipelineDesc::new(iter::once(iter::once(Some(desc))), iter::empty())
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let builder = PersistentDescriptorSet::start(layout, 0).add_buffer(buffer).unwrap();
+
+        match check_push_descriptor_set(&device, &builder) {
+            Err(CheckPushDescriptorSetError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
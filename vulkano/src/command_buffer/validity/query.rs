@@ -0,0 +1,416 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::mem;
+
+use VulkanObject;
+use buffer::TypedBufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use query::QueryResultElement;
+use query::QueryResultFlags;
+use query::QueryType;
+use query::UnsafeQueryPool;
+use sync::PipelineStages;
+
+/// Checks whether a begin query command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_begin_query(device: &Device, query_pool: &UnsafeQueryPool, query: u32,
+                         precise: bool)
+                         -> Result<(), CheckBeginQueryError> {
+    assert_eq!(query_pool.device().internal_object(),
+               device.internal_object());
+
+    if query >= query_pool.num_slots() {
+        return Err(CheckBeginQueryError::OutOfRange);
+    }
+
+    match query_pool.ty() {
+        QueryType::Occlusion => {
+            if precise && !device.enabled_features().occlusion_query_precise {
+                return Err(CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled);
+            }
+        },
+        QueryType::PipelineStatistics(_) => {
+            if precise {
+                return Err(CheckBeginQueryError::PreciseNotOcclusion);
+            }
+        },
+        QueryType::Timestamp => {
+            return Err(CheckBeginQueryError::TimestampPool);
+        },
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `begin_query` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckBeginQueryError {
+    /// The query index is out of range of the pool.
+    OutOfRange,
+    /// Timestamp queries can't be begun, use `write_timestamp` instead.
+    TimestampPool,
+    /// The `precise` flag can only be used with occlusion queries.
+    PreciseNotOcclusion,
+    /// A precise occlusion query was requested but the corresponding feature wasn't enabled.
+    OcclusionQueryPreciseFeatureNotEnabled,
+}
+
+impl error::Error for CheckBeginQueryError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckBeginQueryError::OutOfRange => {
+                "the query index is out of range of the pool"
+            },
+            CheckBeginQueryError::TimestampPool => {
+                "timestamp queries can't be begun, use write_timestamp instead"
+            },
+            CheckBeginQueryError::PreciseNotOcclusion => {
+                "the precise flag can only be used with occlusion queries"
+            },
+            CheckBeginQueryError::OcclusionQueryPreciseFeatureNotEnabled => {
+                "a precise occlusion query was requested but the corresponding feature wasn't \
+                 enabled"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckBeginQueryError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether an end query command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_end_query(device: &Device, query_pool: &UnsafeQueryPool, query: u32)
+                       -> Result<(), CheckEndQueryError> {
+    assert_eq!(query_pool.device().internal_object(),
+               device.internal_object());
+
+    if query >= query_pool.num_slots() {
+        return Err(CheckEndQueryError::OutOfRange);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add an `end_query` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckEndQueryError {
+    /// The query index is out of range of the pool.
+    OutOfRange,
+}
+
+impl error::Error for CheckEndQueryError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckEndQueryError::OutOfRange => {
+                "the query index is out of range of the pool"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckEndQueryError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether a write timestamp command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_write_timestamp(device: &Device, query_pool: &UnsafeQueryPool, query: u32,
+                             stage: PipelineStages)
+                             -> Result<(), CheckWriteTimestampError> {
+    assert_eq!(query_pool.device().internal_object(),
+               device.internal_object());
+
+    match query_pool.ty() {
+        QueryType::Timestamp => (),
+        _ => return Err(CheckWriteTimestampError::NotTimestampPool),
+    }
+
+    if query >= query_pool.num_slots() {
+        return Err(CheckWriteTimestampError::OutOfRange);
+    }
+
+    if stage.into_vulkan_bits().count_ones() != 1 {
+        return Err(CheckWriteTimestampError::NotSingleStage);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `write_timestamp` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckWriteTimestampError {
+    /// The query pool is not a timestamp query pool.
+    NotTimestampPool,
+    /// The query index is out of range of the pool.
+    OutOfRange,
+    /// Exactly one pipeline stage must be passed.
+    NotSingleStage,
+}
+
+impl error::Error for CheckWriteTimestampError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckWriteTimestampError::NotTimestampPool => {
+                "the query pool is not a timestamp query pool"
+            },
+            CheckWriteTimestampError::OutOfRange => {
+                "the query index is out of range of the pool"
+            },
+            CheckWriteTimestampError::NotSingleStage => {
+                "exactly one pipeline stage must be passed"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckWriteTimestampError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether a reset query pool command is valid.
+///
+/// # Panic
+///
+/// - Panics if the query pool was not created with `device`.
+///
+pub fn check_reset_query_pool(device: &Device, query_pool: &UnsafeQueryPool, first_query: u32,
+                              query_count: u32)
+                              -> Result<(), CheckResetQueryPoolError> {
+    assert_eq!(query_pool.device().internal_object(),
+               device.internal_object());
+
+    if query_count == 0 ||
+        first_query.checked_add(query_count).map_or(true, |end| end > query_pool.num_slots())
+    {
+        return Err(CheckResetQueryPoolError::OutOfRange);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to add a `reset_query_pool` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckResetQueryPoolError {
+    /// The range of queries is empty or out of range of the pool.
+    OutOfRange,
+}
+
+impl error::Error for CheckResetQueryPoolError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckResetQueryPoolError::OutOfRange => {
+                "the range of queries is empty or out of range of the pool"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckResetQueryPoolError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether a copy query pool results command is valid.
+///
+/// Returns the stride, in bytes, between the results of two consecutive queries.
+///
+/// # Panic
+///
+/// - Panics if the query pool or the buffer was not created with `device`.
+///
+pub fn check_copy_query_pool_results<D, T>(device: &Device, query_pool: &UnsafeQueryPool,
+                                           first_query: u32, query_count: u32, destination: &D,
+                                           flags: QueryResultFlags)
+                                           -> Result<usize, CheckCopyQueryPoolResultsError>
+    where D: ?Sized + TypedBufferAccess<Content = [T]>,
+          T: QueryResultElement
+{
+    assert_eq!(query_pool.device().internal_object(),
+               device.internal_object());
+    assert_eq!(destination.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if query_count == 0 ||
+        first_query.checked_add(query_count).map_or(true, |end| end > query_pool.num_slots())
+    {
+        return Err(CheckCopyQueryPoolResultsError::OutOfRange);
+    }
+
+    if !destination.inner().buffer.usage_transfer_destination() {
+        return Err(CheckCopyQueryPoolResultsError::BufferMissingUsage);
+    }
+
+    if destination.inner().offset % mem::size_of::<T>() != 0 {
+        return Err(CheckCopyQueryPoolResultsError::WrongAlignment);
+    }
+
+    let per_query = query_pool.ty().result_len();
+
+    let stride = (per_query + if flags.with_availability { 1 } else { 0 }) *
+        mem::size_of::<T>();
+
+    let required = stride * query_count as usize;
+    if destination.size() < required {
+        return Err(CheckCopyQueryPoolResultsError::BufferTooSmall {
+                       required: required,
+                       actual: destination.size(),
+                   });
+    }
+
+    Ok(stride)
+}
+
+/// Error that can happen when attempting to add a `copy_query_pool_results` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckCopyQueryPoolResultsError {
+    /// The range of queries is empty or out of range of the pool.
+    OutOfRange,
+    /// The "transfer destination" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the buffer must be a multiple of the size of the result type.
+    WrongAlignment,
+    /// The buffer is too small to hold the results.
+    BufferTooSmall {
+        /// Required number of bytes.
+        required: usize,
+        /// Actual size of the buffer.
+        actual: usize,
+    },
+}
+
+impl error::Error for CheckCopyQueryPoolResultsError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckCopyQueryPoolResultsError::OutOfRange => {
+                "the range of queries is empty or out of range of the pool"
+            },
+            CheckCopyQueryPoolResultsError::BufferMissingUsage => {
+                "the transfer destination usage must be enabled on the buffer"
+            },
+            CheckCopyQueryPoolResultsError::WrongAlignment => {
+                "the offset of the buffer must be a multiple of the size of the result type"
+            },
+            CheckCopyQueryPoolResultsError::BufferTooSmall { .. } => {
+                "the buffer is too small to hold the results"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckCopyQueryPoolResultsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn begin_query_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Occlusion, 4).unwrap();
+
+        match check_begin_query(&device, &pool, 4, false) {
+            Err(CheckBeginQueryError::OutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn begin_timestamp_query() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 4).unwrap();
+
+        match check_begin_query(&device, &pool, 0, false) {
+            Err(CheckBeginQueryError::TimestampPool) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_timestamp_multiple_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 4).unwrap();
+
+        let stages = PipelineStages {
+            top_of_pipe: true,
+            bottom_of_pipe: true,
+            ..PipelineStages::none()
+        };
+
+        match check_write_timestamp(&device, &pool, 0, stages) {
+            Err(CheckWriteTimestampError::NotSingleStage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn write_timestamp_wrong_pool() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Occlusion, 4).unwrap();
+
+        let stages = PipelineStages {
+            bottom_of_pipe: true,
+            ..PipelineStages::none()
+        };
+
+        match check_write_timestamp(&device, &pool, 0, stages) {
+            Err(CheckWriteTimestampError::NotTimestampPool) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn reset_out_of_range() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Occlusion, 4).unwrap();
+
+        match check_reset_query_pool(&device, &pool, 2, 3) {
+            Err(CheckResetQueryPoolError::OutOfRange) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use device::Device;
+use pipeline::RayTracingPipelineProperties;
+
+/// Checks whether the number of rays generated by a `trace_rays` command is supported by the
+/// device.
+pub fn check_trace_rays(device: &Device, dimensions: [u32; 3]) -> Result<(), CheckTraceRaysError> {
+    let max = RayTracingPipelineProperties::from_physical_device(device.physical_device())
+        .max_ray_dispatch_invocation_count;
+
+    let requested = dimensions[0] as u64 * dimensions[1] as u64 * dimensions[2] as u64;
+    if requested > max as u64 {
+        return Err(CheckTraceRaysError::MaxRayDispatchInvocationCountExceeded {
+                       requested: requested,
+                       max: max,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking the validity of a `trace_rays` command.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckTraceRaysError {
+    /// The product of the dimensions exceeds the `max_ray_dispatch_invocation_count` limit.
+    MaxRayDispatchInvocationCountExceeded {
+        /// The number of rays that was requested.
+        requested: u64,
+        /// The limit of the device.
+        max: u32,
+    },
+}
+
+impl error::Error for CheckTraceRaysError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckTraceRaysError::MaxRayDispatchInvocationCountExceeded { .. } => {
+                "the number of rays exceeds the `max_ray_dispatch_invocation_count` limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckTraceRaysError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
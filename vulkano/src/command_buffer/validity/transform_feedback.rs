@@ -0,0 +1,463 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use buffer::BufferAccess;
+use buffer::TypedBufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use pipeline::TransformFeedbackProperties;
+
+/// Checks whether buffers can be bound as transform feedback buffers, starting at
+/// `first_binding`.
+///
+/// # Panic
+///
+/// - Panics if one of the buffers was not created with `device`.
+///
+pub fn check_transform_feedback_buffers(device: &Device, first_binding: u32,
+                                        buffers: &[Box<BufferAccess + Send + Sync>])
+                                        -> Result<(), CheckTransformFeedbackBuffersError> {
+    if !device.loaded_extensions().ext_transform_feedback {
+        return Err(CheckTransformFeedbackBuffersError::ExtensionNotEnabled);
+    }
+
+    let properties = TransformFeedbackProperties::from_physical_device(device.physical_device());
+
+    let requested = first_binding as u64 + buffers.len() as u64;
+    if requested > properties.max_transform_feedback_buffers as u64 {
+        return Err(CheckTransformFeedbackBuffersError::MaxTransformFeedbackBuffersExceeded {
+                       max: properties.max_transform_feedback_buffers,
+                       requested: requested,
+                   });
+    }
+
+    for (num, buffer) in buffers.iter().enumerate() {
+        assert_eq!(buffer.inner().buffer.device().internal_object(),
+                   device.internal_object());
+
+        if !buffer.inner().buffer.usage_transform_feedback_buffer() {
+            return Err(CheckTransformFeedbackBuffersError::BufferMissingUsage { num_buffer: num });
+        }
+
+        if buffer.inner().offset % 4 != 0 {
+            return Err(CheckTransformFeedbackBuffersError::WrongAlignment { num_buffer: num });
+        }
+
+        if buffer.size() as u64 > properties.max_transform_feedback_buffer_size {
+            return Err(CheckTransformFeedbackBuffersError::MaxTransformFeedbackBufferSizeExceeded {
+                           num_buffer: num,
+                           max: properties.max_transform_feedback_buffer_size,
+                           requested: buffer.size() as u64,
+                       });
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether transform feedback buffers can be bound.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckTransformFeedbackBuffersError {
+    /// The `ext_transform_feedback` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The "transform feedback buffer" usage must be enabled on the buffer.
+    BufferMissingUsage {
+        /// Index of the buffer that is missing usage.
+        num_buffer: usize,
+    },
+    /// The offset of the buffer must be a multiple of 4.
+    WrongAlignment {
+        /// Index of the buffer that is misaligned.
+        num_buffer: usize,
+    },
+    /// The last binding exceeds the `max_transform_feedback_buffers` limit.
+    MaxTransformFeedbackBuffersExceeded {
+        /// The limit of the device.
+        max: u32,
+        /// The number of bindings that was requested.
+        requested: u64,
+    },
+    /// The size of a buffer exceeds the `max_transform_feedback_buffer_size` limit.
+    MaxTransformFeedbackBufferSizeExceeded {
+        /// Index of the buffer that is too large.
+        num_buffer: usize,
+        /// The limit of the device.
+        max: u64,
+        /// The size of the buffer.
+        requested: u64,
+    },
+}
+
+impl error::Error for CheckTransformFeedbackBuffersError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckTransformFeedbackBuffersError::ExtensionNotEnabled => {
+                "the `ext_transform_feedback` extension must be enabled on the device"
+            },
+            CheckTransformFeedbackBuffersError::BufferMissingUsage { .. } => {
+                "the transform feedback buffer usage must be enabled on the buffer"
+            },
+            CheckTransformFeedbackBuffersError::WrongAlignment { .. } => {
+                "the offset of the buffer must be a multiple of 4"
+            },
+            CheckTransformFeedbackBuffersError::MaxTransformFeedbackBuffersExceeded { .. } => {
+                "the last binding exceeds the `max_transform_feedback_buffers` limit"
+            },
+            CheckTransformFeedbackBuffersError::MaxTransformFeedbackBufferSizeExceeded { .. } => {
+                "the size of a buffer exceeds the `max_transform_feedback_buffer_size` limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckTransformFeedbackBuffersError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether buffers can be used as the counter buffers of transform feedback.
+///
+/// # Panic
+///
+/// - Panics if one of the buffers was not created with `device`.
+///
+pub fn check_transform_feedback_counter_buffers(device: &Device,
+                                                buffers: &[Box<BufferAccess + Send + Sync>])
+                                                -> Result<(), CheckTransformFeedbackCountersError> {
+    if !device.loaded_extensions().ext_transform_feedback {
+        return Err(CheckTransformFeedbackCountersError::ExtensionNotEnabled);
+    }
+
+    let max = TransformFeedbackProperties::from_physical_device(device.physical_device())
+        .max_transform_feedback_buffers;
+    if buffers.len() as u64 > max as u64 {
+        return Err(CheckTransformFeedbackCountersError::MaxTransformFeedbackBuffersExceeded {
+                       max: max,
+                       requested: buffers.len() as u64,
+                   });
+    }
+
+    for (num, buffer) in buffers.iter().enumerate() {
+        check_counter_buffer(device, &**buffer)
+            .map_err(|err| match err {
+                         CounterBufferError::BufferMissingUsage => {
+                             CheckTransformFeedbackCountersError::BufferMissingUsage {
+                                 num_buffer: num,
+                             }
+                         },
+                         CounterBufferError::WrongAlignment => {
+                             CheckTransformFeedbackCountersError::WrongAlignment {
+                                 num_buffer: num,
+                             }
+                         },
+                     })?;
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether transform feedback counter buffers are valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckTransformFeedbackCountersError {
+    /// The `ext_transform_feedback` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The "transform feedback counter buffer" usage must be enabled on the buffer.
+    BufferMissingUsage {
+        /// Index of the buffer that is missing usage.
+        num_buffer: usize,
+    },
+    /// The offset of the counter within the buffer must be a multiple of 4.
+    WrongAlignment {
+        /// Index of the buffer that is misaligned.
+        num_buffer: usize,
+    },
+    /// The number of counter buffers exceeds the `max_transform_feedback_buffers` limit.
+    MaxTransformFeedbackBuffersExceeded {
+        /// The limit of the device.
+        max: u32,
+        /// The number of counter buffers that was requested.
+        requested: u64,
+    },
+}
+
+impl error::Error for CheckTransformFeedbackCountersError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckTransformFeedbackCountersError::ExtensionNotEnabled => {
+                "the `ext_transform_feedback` extension must be enabled on the device"
+            },
+            CheckTransformFeedbackCountersError::BufferMissingUsage { .. } => {
+                "the transform feedback counter buffer usage must be enabled on the buffer"
+            },
+            CheckTransformFeedbackCountersError::WrongAlignment { .. } => {
+                "the offset of the counter within the buffer must be a multiple of 4"
+            },
+            CheckTransformFeedbackCountersError::MaxTransformFeedbackBuffersExceeded { .. } => {
+                "the number of counter buffers exceeds the `max_transform_feedback_buffers` limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckTransformFeedbackCountersError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Checks whether a `draw_indirect_byte_count` command can be executed with the given counter
+/// buffer and vertex stride.
+///
+/// # Panic
+///
+/// - Panics if the buffer was not created with `device`.
+///
+pub fn check_draw_indirect_byte_count<B>(device: &Device, counter_buffer: &B, vertex_stride: u32)
+                                         -> Result<(), CheckDrawIndirectByteCountError>
+    where B: ?Sized + TypedBufferAccess<Content = u32>
+{
+    if !device.loaded_extensions().ext_transform_feedback {
+        return Err(CheckDrawIndirectByteCountError::ExtensionNotEnabled);
+    }
+
+    let properties = TransformFeedbackProperties::from_physical_device(device.physical_device());
+    if !properties.transform_feedback_draw {
+        return Err(CheckDrawIndirectByteCountError::NotSupported);
+    }
+
+    check_counter_buffer(device, counter_buffer).map_err(|err| match err {
+        CounterBufferError::BufferMissingUsage => {
+            CheckDrawIndirectByteCountError::BufferMissingUsage
+        },
+        CounterBufferError::WrongAlignment => CheckDrawIndirectByteCountError::WrongAlignment,
+    })?;
+
+    let max = properties.max_transform_feedback_buffer_data_stride;
+    if vertex_stride == 0 || vertex_stride > max {
+        return Err(CheckDrawIndirectByteCountError::InvalidVertexStride {
+                       max: max,
+                       requested: vertex_stride,
+                   });
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether a `draw_indirect_byte_count` command is valid.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckDrawIndirectByteCountError {
+    /// The `ext_transform_feedback` extension must be enabled on the device.
+    ExtensionNotEnabled,
+    /// The device doesn't support `draw_indirect_byte_count`.
+    NotSupported,
+    /// The "transform feedback counter buffer" usage must be enabled on the buffer.
+    BufferMissingUsage,
+    /// The offset of the counter within the buffer must be a multiple of 4.
+    WrongAlignment,
+    /// The vertex stride is 0 or exceeds the `max_transform_feedback_buffer_data_stride` limit.
+    InvalidVertexStride {
+        /// The limit of the device.
+        max: u32,
+        /// The stride that was requested.
+        requested: u32,
+    },
+}
+
+impl error::Error for CheckDrawIndirectByteCountError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckDrawIndirectByteCountError::ExtensionNotEnabled => {
+                "the `ext_transform_feedback` extension must be enabled on the device"
+            },
+            CheckDrawIndirectByteCountError::NotSupported => {
+                "the device doesn't support `draw_indirect_byte_count`"
+            },
+            CheckDrawIndirectByteCountError::BufferMissingUsage => {
+                "the transform feedback counter buffer usage must be enabled on the buffer"
+            },
+            CheckDrawIndirectByteCountError::WrongAlignment => {
+                "the offset of the counter within the buffer must be a multiple of 4"
+            },
+            CheckDrawIndirectByteCountError::InvalidVertexStride { .. } => {
+                "the vertex stride is 0 or exceeds the `max_transform_feedback_buffer_data_stride` \
+                 limit"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckDrawIndirectByteCountError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+// Errors shared by the checks of the counter buffers.
+enum CounterBufferError {
+    BufferMissingUsage,
+    WrongAlignment,
+}
+
+fn check_counter_buffer<B>(device: &Device, buffer: &B) -> Result<(), CounterBufferError>
+    where B: ?Sized + BufferAccess
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    if !buffer.inner().buffer.usage_transform_feedback_counter_buffer() {
+        return Err(CounterBufferError::BufferMissingUsage);
+    }
+
+    if buffer.inner().offset % 4 != 0 {
+        return Err(CounterBufferError::WrongAlignment);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use pipeline::TransformFeedbackProperties;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        match check_transform_feedback_buffers(&device, 0, &[Box::new(buffer.clone()) as Box<_>]) {
+            Err(CheckTransformFeedbackBuffersError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        match check_draw_indirect_byte_count(&device, &buffer, 4) {
+            Err(CheckDrawIndirectByteCountError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn buffers_checks() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_transform_feedback]);
+        let usage = BufferUsage {
+            transform_feedback_buffer: true,
+            ..BufferUsage::none()
+        };
+        let valid = CpuAccessibleBuffer::from_iter(device.clone(), usage, 0u8 .. 16).unwrap();
+        let other = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        check_transform_feedback_buffers(&device, 0, &[Box::new(valid.clone()) as Box<_>])
+            .unwrap();
+
+        let buffers = [Box::new(valid.clone()) as Box<_>, Box::new(other) as Box<_>];
+        match check_transform_feedback_buffers(&device, 0, &buffers) {
+            Err(CheckTransformFeedbackBuffersError::BufferMissingUsage { num_buffer: 1 }) => (),
+            _ => panic!(),
+        }
+
+        let unaligned = valid.clone().into_buffer_slice().slice(2 .. 10).unwrap();
+        match check_transform_feedback_buffers(&device, 0, &[Box::new(unaligned) as Box<_>]) {
+            Err(CheckTransformFeedbackBuffersError::WrongAlignment { num_buffer: 0 }) => (),
+            _ => panic!(),
+        }
+
+        let max = TransformFeedbackProperties::from_physical_device(device.physical_device())
+            .max_transform_feedback_buffers;
+        match check_transform_feedback_buffers(&device, max, &[Box::new(valid) as Box<_>]) {
+            Err(CheckTransformFeedbackBuffersError::MaxTransformFeedbackBuffersExceeded {
+                    requested, ..
+                }) => assert_eq!(requested, max as u64 + 1),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn counter_buffers_checks() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_transform_feedback]);
+        let usage = BufferUsage {
+            transform_feedback_counter_buffer: true,
+            ..BufferUsage::none()
+        };
+        let valid = CpuAccessibleBuffer::from_iter(device.clone(), usage, 0u8 .. 8).unwrap();
+        let other = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        check_transform_feedback_counter_buffers(&device, &[Box::new(valid.clone()) as Box<_>])
+            .unwrap();
+
+        match check_transform_feedback_counter_buffers(&device, &[Box::new(other) as Box<_>]) {
+            Err(CheckTransformFeedbackCountersError::BufferMissingUsage { num_buffer: 0 }) => (),
+            _ => panic!(),
+        }
+
+        let unaligned = valid.into_buffer_slice().slice(2 .. 6).unwrap();
+        match check_transform_feedback_counter_buffers(&device, &[Box::new(unaligned) as Box<_>]) {
+            Err(CheckTransformFeedbackCountersError::WrongAlignment { num_buffer: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn draw_indirect_byte_count_checks() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_transform_feedback]);
+        let usage = BufferUsage {
+            transform_feedback_counter_buffer: true,
+            ..BufferUsage::none()
+        };
+        let counter = CpuAccessibleBuffer::from_data(device.clone(), usage, 0u32).unwrap();
+        let other = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        let physical = device.physical_device();
+        let properties = TransformFeedbackProperties::from_physical_device(physical);
+        if !properties.transform_feedback_draw {
+            match check_draw_indirect_byte_count(&device, &counter, 4) {
+                Err(CheckDrawIndirectByteCountError::NotSupported) => (),
+                _ => panic!(),
+            }
+            return;
+        }
+
+        check_draw_indirect_byte_count(&device, &counter, 4).unwrap();
+
+        match check_draw_indirect_byte_count(&device, &other, 4) {
+            Err(CheckDrawIndirectByteCountError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+
+        match check_draw_indirect_byte_count(&device, &counter, 0) {
+            Err(CheckDrawIndirectByteCountError::InvalidVertexStride { requested: 0, .. }) => (),
+            _ => panic!(),
+        }
+
+        let stride = properties.max_transform_feedback_buffer_data_stride + 1;
+        match check_draw_indirect_byte_count(&device, &counter, stride) {
+            Err(CheckDrawIndirectByteCountError::InvalidVertexStride { max, requested }) => {
+                assert_eq!(max + 1, requested);
+            },
+            _ => panic!(),
+        }
+    }
+}
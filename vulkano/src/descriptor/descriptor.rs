@@ -66,6 +66,14 @@ pub struct DescriptorDesc {
     /// a panic depending on the situation.
     pub array_count: u32,
 
+    /// True if the descriptor is a runtime-sized array in the shader, whose number of elements
+    /// isn't known until the layout is created. `array_count` is then the minimum number of
+    /// elements that the layout must contain.
+    ///
+    /// Creating a layout with such a descriptor requires the `runtime_descriptor_array` feature
+    /// of the `ext_descriptor_indexing` extension.
+    pub runtime_array: bool,
+
     /// Which shader stages are going to access this descriptor.
     pub stages: ShaderStages,
 
@@ -113,6 +121,7 @@ impl DescriptorDesc {
         Some(DescriptorDesc {
                  ty: self.ty.clone(),
                  array_count: cmp::max(self.array_count, other.array_count),
+                 runtime_array: self.runtime_array || other.runtime_array,
                  stages: self.stages | other.stages,
                  readonly: self.readonly && other.readonly,
              })
@@ -150,6 +159,12 @@ impl DescriptorDesc {
                     ..AccessFlagBits::none()
                 }
             },
+            DescriptorDescTy::AccelerationStructure => {
+                AccessFlagBits {
+                    acceleration_structure_read: true,
+                    ..AccessFlagBits::none()
+                }
+            },
             DescriptorDescTy::Buffer(ref buf) => {
                 if buf.storage {
                     AccessFlagBits {
@@ -192,6 +207,9 @@ pub enum DescriptorDescTy {
         array_layers: DescriptorImageDescArray,
     },
     Buffer(DescriptorBufferDesc),
+    /// An acceleration structure that shaders can trace rays against with ray queries. Requires
+    /// the `khr_acceleration_structure` extension.
+    AccelerationStructure,
 }
 
 impl DescriptorDescTy {
@@ -211,6 +229,7 @@ impl DescriptorDescTy {
                      }
                  },
                  DescriptorDescTy::InputAttachment { .. } => DescriptorType::InputAttachment,
+                 DescriptorDescTy::AccelerationStructure => DescriptorType::AccelerationStructure,
                  DescriptorDescTy::Buffer(ref desc) => {
                      let dynamic = match desc.dynamic {
                          Some(d) => d,
@@ -241,6 +260,9 @@ impl DescriptorDescTy {
         match (self, other) {
             (&DescriptorDescTy::Sampler, &DescriptorDescTy::Sampler) => Ok(()),
 
+            (&DescriptorDescTy::AccelerationStructure,
+             &DescriptorDescTy::AccelerationStructure) => Ok(()),
+
             (&DescriptorDescTy::CombinedImageSampler(ref me),
              &DescriptorDescTy::CombinedImageSampler(ref other)) => me.is_superset_of(other),
 
@@ -474,6 +496,7 @@ pub enum DescriptorType {
     UniformBufferDynamic = vk::DESCRIPTOR_TYPE_UNIFORM_BUFFER_DYNAMIC,
     StorageBufferDynamic = vk::DESCRIPTOR_TYPE_STORAGE_BUFFER_DYNAMIC,
     InputAttachment = vk::DESCRIPTOR_TYPE_INPUT_ATTACHMENT,
+    AccelerationStructure = vk::DESCRIPTOR_TYPE_ACCELERATION_STRUCTURE_KHR,
 }
 
 /// Error when checking whether a descriptor is a superset of another one.
@@ -575,10 +598,29 @@ pub struct ShaderStages {
     pub fragment: bool,
     /// `True` means that the descriptor will be used by the compute shader.
     pub compute: bool,
+    /// `True` means that the descriptor will be used by the task shader.
+    pub task: bool,
+    /// `True` means that the descriptor will be used by the mesh shader.
+    pub mesh: bool,
+    /// `True` means that the descriptor will be used by the ray generation shader.
+    pub raygen: bool,
+    /// `True` means that the descriptor will be used by the any-hit shader.
+    pub any_hit: bool,
+    /// `True` means that the descriptor will be used by the closest-hit shader.
+    pub closest_hit: bool,
+    /// `True` means that the descriptor will be used by the miss shader.
+    pub miss: bool,
+    /// `True` means that the descriptor will be used by the intersection shader.
+    pub intersection: bool,
+    /// `True` means that the descriptor will be used by the callable shader.
+    pub callable: bool,
 }
 
 impl ShaderStages {
-    /// Creates a `ShaderStages` struct will all stages set to `true`.
+    /// Creates a `ShaderStages` struct will all the graphics and compute stages set to `true`.
+    ///
+    /// The task, mesh and ray tracing stages require an extension and are left to `false`. Use
+    /// `all_mesh` and `all_ray_tracing` to get them.
     // TODO: add example
     #[inline]
     pub fn all() -> ShaderStages {
@@ -589,6 +631,7 @@ impl ShaderStages {
             geometry: true,
             fragment: true,
             compute: true,
+            ..ShaderStages::none()
         }
     }
 
@@ -603,6 +646,14 @@ impl ShaderStages {
             geometry: false,
             fragment: false,
             compute: false,
+            task: false,
+            mesh: false,
+            raygen: false,
+            any_hit: false,
+            closest_hit: false,
+            miss: false,
+            intersection: false,
+            callable: false,
         }
     }
 
@@ -616,7 +667,7 @@ impl ShaderStages {
             tessellation_evaluation: true,
             geometry: true,
             fragment: true,
-            compute: false,
+            ..ShaderStages::none()
         }
     }
 
@@ -625,15 +676,42 @@ impl ShaderStages {
     #[inline]
     pub fn compute() -> ShaderStages {
         ShaderStages {
-            vertex: false,
-            tessellation_control: false,
-            tessellation_evaluation: false,
-            geometry: false,
-            fragment: false,
             compute: true,
+            ..ShaderStages::none()
         }
     }
 
+    /// Creates a `ShaderStages` struct with the task and mesh stages set to `true`.
+    #[inline]
+    pub fn all_mesh() -> ShaderStages {
+        ShaderStages {
+            task: true,
+            mesh: true,
+            ..ShaderStages::none()
+        }
+    }
+
+    /// Creates a `ShaderStages` struct with all the ray tracing stages set to `true`.
+    #[inline]
+    pub fn all_ray_tracing() -> ShaderStages {
+        ShaderStages {
+            raygen: true,
+            any_hit: true,
+            closest_hit: true,
+            miss: true,
+            intersection: true,
+            callable: true,
+            ..ShaderStages::none()
+        }
+    }
+
+    /// Returns true if any of the ray tracing stages is set.
+    #[inline]
+    pub fn has_ray_tracing(&self) -> bool {
+        self.raygen || self.any_hit || self.closest_hit || self.miss || self.intersection ||
+            self.callable
+    }
+
     /// Checks whether we have more stages enabled than `other`.
     // TODO: add example
     #[inline]
@@ -642,7 +720,11 @@ impl ShaderStages {
             (self.tessellation_control || !other.tessellation_control) &&
             (self.tessellation_evaluation || !other.tessellation_evaluation) &&
             (self.geometry || !other.geometry) &&
-            (self.fragment || !other.fragment) && (self.compute || !other.compute)
+            (self.fragment || !other.fragment) && (self.compute || !other.compute) &&
+            (self.task || !other.task) && (self.mesh || !other.mesh) &&
+            (self.raygen || !other.raygen) && (self.any_hit || !other.any_hit) &&
+            (self.closest_hit || !other.closest_hit) && (self.miss || !other.miss) &&
+            (self.intersection || !other.intersection) && (self.callable || !other.callable)
         {
             Ok(())
         } else {
@@ -658,7 +740,11 @@ impl ShaderStages {
             (self.tessellation_control && other.tessellation_control) ||
             (self.tessellation_evaluation && other.tessellation_evaluation) ||
             (self.geometry && other.geometry) || (self.fragment && other.fragment) ||
-            (self.compute && other.compute)
+            (self.compute && other.compute) || (self.task && other.task) ||
+            (self.mesh && other.mesh) || (self.raygen && other.raygen) ||
+            (self.any_hit && other.any_hit) || (self.closest_hit && other.closest_hit) ||
+            (self.miss && other.miss) || (self.intersection && other.intersection) ||
+            (self.callable && other.callable)
     }
 
     #[inline]
@@ -682,6 +768,30 @@ impl ShaderStages {
         if self.compute {
             result |= vk::SHADER_STAGE_COMPUTE_BIT;
         }
+        if self.task {
+            result |= vk::SHADER_STAGE_TASK_BIT_EXT;
+        }
+        if self.mesh {
+            result |= vk::SHADER_STAGE_MESH_BIT_EXT;
+        }
+        if self.raygen {
+            result |= vk::SHADER_STAGE_RAYGEN_BIT_KHR;
+        }
+        if self.any_hit {
+            result |= vk::SHADER_STAGE_ANY_HIT_BIT_KHR;
+        }
+        if self.closest_hit {
+            result |= vk::SHADER_STAGE_CLOSEST_HIT_BIT_KHR;
+        }
+        if self.miss {
+            result |= vk::SHADER_STAGE_MISS_BIT_KHR;
+        }
+        if self.intersection {
+            result |= vk::SHADER_STAGE_INTERSECTION_BIT_KHR;
+        }
+        if self.callable {
+            result |= vk::SHADER_STAGE_CALLABLE_BIT_KHR;
+        }
         result
     }
 }
@@ -698,6 +808,14 @@ impl BitOr for ShaderStages {
             geometry: self.geometry || other.geometry,
             fragment: self.fragment || other.fragment,
             compute: self.compute || other.compute,
+            task: self.task || other.task,
+            mesh: self.mesh || other.mesh,
+            raygen: self.raygen || other.raygen,
+            any_hit: self.any_hit || other.any_hit,
+            closest_hit: self.closest_hit || other.closest_hit,
+            miss: self.miss || other.miss,
+            intersection: self.intersection || other.intersection,
+            callable: self.callable || other.callable,
         }
     }
 }
@@ -712,6 +830,9 @@ impl From<ShaderStages> for PipelineStages {
             geometry_shader: stages.geometry,
             fragment_shader: stages.fragment,
             compute_shader: stages.compute,
+            task_shader: stages.task,
+            mesh_shader: stages.mesh,
+            ray_tracing_shader: stages.has_ray_tracing(),
             ..PipelineStages::none()
         }
     }
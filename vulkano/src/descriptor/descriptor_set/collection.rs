@@ -26,6 +26,16 @@ pub unsafe trait DescriptorSetsCollection {
     /// Returns `None` if out of range.
     // TODO: remove ; user should just use `into_vec` instead
     fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc>;
+
+    /// Returns the dynamic offsets of the given set. See `DescriptorSet::dynamic_offsets`.
+    ///
+    /// Returns an empty list if the set is out of range. The default implementation always
+    /// returns an empty list.
+    // TODO: remove ; user should just use `into_vec` instead
+    #[inline]
+    fn dynamic_offsets(&self, _: usize) -> &[u32] {
+        &[]
+    }
 }
 
 unsafe impl DescriptorSetsCollection for () {
@@ -68,6 +78,14 @@ unsafe impl<T> DescriptorSetsCollection for T
             _ => None,
         }
     }
+
+    #[inline]
+    fn dynamic_offsets(&self, set: usize) -> &[u32] {
+        match set {
+            0 => DescriptorSet::dynamic_offsets(self),
+            _ => &[],
+        }
+    }
 }
 
 macro_rules! impl_collection {
@@ -131,6 +149,27 @@ macro_rules! impl_collection {
 
                 None
             }
+
+            #[inline]
+            fn dynamic_offsets(&self, mut set: usize) -> &[u32] {
+                #![allow(non_snake_case)]
+                #![allow(unused_mut)]       // For the `set` parameter.
+
+                if set == 0 {
+                    return DescriptorSet::dynamic_offsets(&self.0);
+                }
+
+                let &(_, $(ref $others,)*) = self;
+
+                $(
+                    set -= 1;
+                    if set == 0 {
+                        return DescriptorSet::dynamic_offsets($others);
+                    }
+                )*
+
+                &[]
+            }
         }
 
         impl_collection!($($others),+);
@@ -17,6 +17,7 @@ use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor_set::DescriptorPool;
 use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorPoolAllocError;
+use descriptor::descriptor_set::DescriptorPoolCreationError;
 use descriptor::descriptor_set::DescriptorSet;
 use descriptor::descriptor_set::DescriptorSetDesc;
 use descriptor::descriptor_set::UnsafeDescriptorPool;
@@ -236,8 +237,16 @@ unsafe impl DescriptorPool for LocalPool {
             // If we failed to grab an existing set, that means the current pool is full. Create a
             // new one of larger capacity.
             let count = *layout.descriptors_count() * self.next_capacity;
-            let mut new_pool =
-                UnsafeDescriptorPool::new(self.device.clone(), &count, self.next_capacity, false)?;
+            let mut new_pool = match UnsafeDescriptorPool::new(self.device.clone(), &count,
+                                                               self.next_capacity, false) {
+                Ok(pool) => pool,
+                Err(DescriptorPoolCreationError::OomError(err)) => return Err(err),
+                // Fragmentation can only happen with update after bind pools.
+                Err(DescriptorPoolCreationError::Fragmentation) => unreachable!(),
+                Err(err @ DescriptorPoolCreationError::UnexpectedResult(_)) => {
+                    panic!("unexpected error: {:?}", err)
+                },
+            };
             let alloc = unsafe {
                 match new_pool.alloc((0 .. self.next_capacity).map(|_| layout)) {
                     Ok(iter) => {
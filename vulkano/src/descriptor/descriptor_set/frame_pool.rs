@@ -0,0 +1,376 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crossbeam::sync::SegQueue;
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use OomError;
+use VulkanObject;
+use descriptor::descriptor_set::DescriptorPool;
+use descriptor::descriptor_set::DescriptorPoolAlloc;
+use descriptor::descriptor_set::DescriptorPoolAllocError;
+use descriptor::descriptor_set::DescriptorPoolCreationError;
+use descriptor::descriptor_set::UnsafeDescriptorPool;
+use descriptor::descriptor_set::UnsafeDescriptorSet;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use device::Device;
+use device::DeviceOwned;
+use sync::FenceSignalFuture;
+use sync::GpuFuture;
+
+/// Pool of descriptor sets that are recycled once the GPU has finished executing the frame in
+/// which they were allocated.
+///
+/// This pool is designed for the common pattern where a few frames are in flight at the same
+/// time, and where new descriptor sets are built at each frame. Call `end_frame` after submitting
+/// the commands of a frame, with the future that signals a fence at the end of the frame. The
+/// sets allocated since the previous call to `end_frame` are put back in the pool only once both
+/// the fence has been signaled and the sets have been destroyed.
+///
+/// Keeping a set alive after its frame has finished is allowed, and only delays the recycling of
+/// this set. If `end_frame` is never called, destroyed sets are never reused.
+///
+/// All the sets of the pool must use the same layout.
+///
+/// # Example
+///
+/// ```rust
+/// use vulkano::descriptor::descriptor_set::FrameDescriptorPool;
+/// use vulkano::descriptor::descriptor_set::PersistentDescriptorSet;
+/// use vulkano::descriptor::PipelineLayoutAbstract;
+/// # use vulkano::pipeline::GraphicsPipelineAbstract;
+/// # use vulkano::sync::GpuFuture;
+/// # use std::sync::Arc;
+/// # let graphics_pipeline: Arc<GraphicsPipelineAbstract> = return;
+/// # let future: Box<GpuFuture + Send> = return;
+///
+/// let layout = graphics_pipeline.descriptor_set_layout(0).unwrap().clone();
+/// let mut pool = FrameDescriptorPool::new(layout);
+///
+/// // Then at each frame:
+/// let set = PersistentDescriptorSet::start(graphics_pipeline.clone(), 0)
+///     //.add_buffer(...)
+///     .build_with_pool(&mut pool).unwrap();
+///
+/// // ... draw with `set` ...
+///
+/// let future = Arc::new(future.then_signal_fence_and_flush().unwrap());
+/// pool.end_frame(future.clone());
+/// ```
+pub struct FrameDescriptorPool {
+    // The layout of all the sets of the pool.
+    layout: Arc<UnsafeDescriptorSetLayout>,
+    // Sets that can be allocated, and the Vulkan pools they belong to.
+    shared: Arc<Shared>,
+    // The frame in which new sets are allocated.
+    current_frame: Arc<Frame>,
+    // Frames that have ended, in order, with the fence that is signaled when they finish.
+    pending_frames: VecDeque<(Box<FrameFence + Send>, Arc<Frame>)>,
+    // Number of sets of the next Vulkan pool that we create.
+    next_capacity: u32,
+}
+
+struct Shared {
+    // The actual Vulkan descriptor pools. They aren't used directly, but they must be kept alive
+    // in order to keep the descriptor sets valid.
+    pools: Mutex<Vec<UnsafeDescriptorPool>>,
+    // Sets that are ready to be allocated.
+    reserve: SegQueue<UnsafeDescriptorSet>,
+}
+
+struct Frame {
+    shared: Arc<Shared>,
+    // Sets of this frame that have been destroyed while the frame was still executing.
+    retired: SegQueue<UnsafeDescriptorSet>,
+    // True if the GPU has finished executing the frame.
+    finished: AtomicBool,
+}
+
+impl Frame {
+    #[inline]
+    fn new(shared: Arc<Shared>) -> Arc<Frame> {
+        Arc::new(Frame {
+                     shared: shared,
+                     retired: SegQueue::new(),
+                     finished: AtomicBool::new(false),
+                 })
+    }
+
+    // Moves the retired sets of the frame to the reserve.
+    fn recycle(&self) {
+        while let Some(set) = self.retired.try_pop() {
+            self.shared.reserve.push(set);
+        }
+    }
+}
+
+impl Drop for Frame {
+    #[inline]
+    fn drop(&mut self) {
+        // Sets can be retired concurrently with `finished` being set. Since the last allocation
+        // of the frame is gone, we can now recycle them safely.
+        if self.finished.load(Ordering::SeqCst) {
+            self.recycle();
+        }
+    }
+}
+
+// Object-safe wrapper around the future passed to `end_frame`.
+trait FrameFence {
+    fn is_signaled(&self) -> bool;
+}
+
+impl<F> FrameFence for Arc<FenceSignalFuture<F>>
+    where F: GpuFuture
+{
+    #[inline]
+    fn is_signaled(&self) -> bool {
+        FenceSignalFuture::is_signaled(self)
+    }
+}
+
+impl FrameDescriptorPool {
+    /// Initializes a new pool whose sets all use `layout`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `layout` was created for push descriptors.
+    ///
+    pub fn new(layout: Arc<UnsafeDescriptorSetLayout>) -> FrameDescriptorPool {
+        assert!(!layout.is_push_descriptor(),
+                "Tried to create a descriptor pool with a push descriptor layout");
+
+        let shared = Arc::new(Shared {
+                                  pools: Mutex::new(Vec::new()),
+                                  reserve: SegQueue::new(),
+                              });
+
+        FrameDescriptorPool {
+            layout: layout,
+            current_frame: Frame::new(shared.clone()),
+            shared: shared,
+            pending_frames: VecDeque::new(),
+            next_capacity: 3,
+        }
+    }
+
+    /// Returns the layout of the sets of the pool.
+    #[inline]
+    pub fn layout(&self) -> &Arc<UnsafeDescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Ends the current frame. The sets allocated since the previous call to `end_frame` will be
+    /// recycled after `future` has signaled its fence.
+    ///
+    /// `future` must include the execution of all the commands that use these sets.
+    pub fn end_frame<F>(&mut self, future: Arc<FenceSignalFuture<F>>)
+        where F: GpuFuture + Send + 'static
+    {
+        let frame = Frame::new(self.shared.clone());
+        let ended = mem::replace(&mut self.current_frame, frame);
+        self.pending_frames.push_back((Box::new(future) as Box<_>, ended));
+        self.cleanup_finished();
+    }
+
+    /// Recycles the sets of the frames that the GPU has finished executing.
+    ///
+    /// This is automatically called by `end_frame`, and before creating a new Vulkan pool.
+    pub fn cleanup_finished(&mut self) {
+        // Frames finish in order, so we can stop at the first frame that is still executing.
+        while self
+            .pending_frames
+            .front()
+            .map(|&(ref fence, _)| fence.is_signaled())
+            .unwrap_or(false)
+        {
+            let (_, frame) = self.pending_frames.pop_front().unwrap();
+            frame.finished.store(true, Ordering::SeqCst);
+            frame.recycle();
+        }
+    }
+}
+
+unsafe impl DescriptorPool for FrameDescriptorPool {
+    type Alloc = FrameDescriptorPoolAlloc;
+
+    fn alloc(&mut self, layout: &UnsafeDescriptorSetLayout)
+             -> Result<FrameDescriptorPoolAlloc, OomError> {
+        assert_eq!(layout.internal_object(),
+                   self.layout.internal_object(),
+                   "Tried to allocate from a FrameDescriptorPool with a different layout");
+
+        loop {
+            // Try to extract a set from the reserve. This is the most common case.
+            if let Some(set) = self.shared.reserve.try_pop() {
+                return Ok(FrameDescriptorPoolAlloc {
+                              frame: self.current_frame.clone(),
+                              set: Some(set),
+                          });
+            }
+
+            // Some frames may have finished since the last time we checked, in which case their
+            // sets are now in the reserve.
+            let num_pending = self.pending_frames.len();
+            self.cleanup_finished();
+            if self.pending_frames.len() != num_pending {
+                continue;
+            }
+
+            // The reserve is empty. Create a new Vulkan pool of larger capacity.
+            let count = *self.layout.descriptors_count() * self.next_capacity;
+            let mut new_pool = match UnsafeDescriptorPool::new(self.layout.device().clone(),
+                                                               &count,
+                                                               self.next_capacity,
+                                                               false) {
+                Ok(pool) => pool,
+                Err(DescriptorPoolCreationError::OomError(err)) => return Err(err),
+                // Fragmentation can only happen with update after bind pools.
+                Err(DescriptorPoolCreationError::Fragmentation) => unreachable!(),
+                Err(err @ DescriptorPoolCreationError::UnexpectedResult(_)) => {
+                    panic!("unexpected error: {:?}", err)
+                },
+            };
+            unsafe {
+                match new_pool.alloc((0 .. self.next_capacity).map(|_| &*self.layout)) {
+                    Ok(iter) => {
+                        for set in iter {
+                            self.shared.reserve.push(set);
+                        }
+                    },
+                    Err(DescriptorPoolAllocError::OutOfHostMemory) => {
+                        return Err(OomError::OutOfHostMemory);
+                    },
+                    Err(DescriptorPoolAllocError::OutOfDeviceMemory) => {
+                        return Err(OomError::OutOfDeviceMemory);
+                    },
+                    Err(DescriptorPoolAllocError::FragmentedPool) => {
+                        // This can't happen as we don't free individual sets.
+                        unreachable!()
+                    },
+                    Err(DescriptorPoolAllocError::OutOfPoolMemory) => {
+                        unreachable!()
+                    },
+                }
+            }
+
+            self.next_capacity = self.next_capacity.saturating_mul(2);
+            self.shared.pools.lock().unwrap().push(new_pool);
+        }
+    }
+}
+
+unsafe impl DeviceOwned for FrameDescriptorPool {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.layout.device()
+    }
+}
+
+/// A descriptor set allocated from a `FrameDescriptorPool`.
+pub struct FrameDescriptorPoolAlloc {
+    // The frame during which the set was allocated.
+    frame: Arc<Frame>,
+    // The set. Inside an option so that we can extract it in the destructor.
+    set: Option<UnsafeDescriptorSet>,
+}
+
+impl DescriptorPoolAlloc for FrameDescriptorPoolAlloc {
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        self.set.as_ref().unwrap()
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut UnsafeDescriptorSet {
+        self.set.as_mut().unwrap()
+    }
+}
+
+impl Drop for FrameDescriptorPoolAlloc {
+    fn drop(&mut self) {
+        let set = self.set.take().unwrap();
+
+        if self.frame.finished.load(Ordering::SeqCst) {
+            self.frame.shared.reserve.push(set);
+        } else {
+            self.frame.retired.push(set);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorPool;
+    use descriptor::descriptor_set::DescriptorPoolAlloc;
+    use descriptor::descriptor_set::FrameDescriptorPool;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use std::iter;
+    use std::sync::Arc;
+    use sync::GpuFuture;
+    use VulkanObject;
+
+    #[test]
+    fn recycled_after_frame() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc)))
+            .unwrap();
+        let layout = Arc::new(layout);
+        let mut pool = FrameDescriptorPool::new(layout.clone());
+
+        let first = pool.alloc(&layout).unwrap();
+        let first_set = first.inner().internal_object();
+        drop(first);
+
+        // The frame hasn't ended, so the set must not be reused.
+        let second = pool.alloc(&layout).unwrap();
+        assert_ne!(second.inner().internal_object(), first_set);
+        drop(second);
+
+        let future = AutoCommandBufferBuilder::new(device, queue.family())
+            .unwrap()
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        let future = Arc::new(future);
+        future.wait(None).unwrap();
+        pool.end_frame(future);
+
+        let third = pool.alloc(&layout).unwrap();
+        let third_set = third.inner().internal_object();
+        let fourth = pool.alloc(&layout).unwrap();
+        let fourth_set = fourth.inner().internal_object();
+        assert!(third_set == first_set || fourth_set == first_set);
+    }
+}
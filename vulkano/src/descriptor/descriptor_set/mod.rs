@@ -28,10 +28,14 @@
 //!   descriptor sets. However it is different from Vulkan descriptor pools in the sense that an
 //!   implementation of the `DescriptorPool` trait can manage multiple Vulkan descriptor pools.
 //! - The `StdDescriptorPool` type is a default implementation of the `DescriptorPool` trait.
+//! - The `FrameDescriptorPool` type is an implementation of the `DescriptorPool` trait that
+//!   recycles descriptor sets once the GPU has finished executing the frame they were used in.
 //! - The `DescriptorSet` trait is implemented on types that wrap around Vulkan descriptor sets in
 //!   a safe way. A Vulkan descriptor set is inherently unsafe, so we need safe wrappers around
 //!   them.
 //! - The `SimpleDescriptorSet` type is a default implementation of the `DescriptorSet` trait.
+//! - The `DescriptorSetWithOffsets` type wraps around a descriptor set and attaches to it the
+//!   dynamic offsets of its dynamic buffer descriptors.
 //! - The `DescriptorSetsCollection` trait is implemented on collections of types that implement
 //!   `DescriptorSet`. It is what you pass to the draw functions.
 
@@ -45,34 +49,58 @@ pub use self::fixed_size_pool::FixedSizeDescriptorSet;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetBuilder;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetBuilderArray;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetsPool;
+pub use self::frame_pool::FrameDescriptorPool;
+pub use self::frame_pool::FrameDescriptorPoolAlloc;
 pub use self::persistent::PersistentDescriptorSet;
+pub use self::persistent::PersistentDescriptorSetAccelerationStructure;
 pub use self::persistent::PersistentDescriptorSetBuf;
 pub use self::persistent::PersistentDescriptorSetBufView;
 pub use self::persistent::PersistentDescriptorSetBuildError;
 pub use self::persistent::PersistentDescriptorSetBuilder;
 pub use self::persistent::PersistentDescriptorSetBuilderArray;
+pub use self::persistent::PersistentDescriptorSetCopy;
 pub use self::persistent::PersistentDescriptorSetError;
 pub use self::persistent::PersistentDescriptorSetImg;
 pub use self::persistent::PersistentDescriptorSetSampler;
 pub use self::std_pool::StdDescriptorPool;
 pub use self::std_pool::StdDescriptorPoolAlloc;
+pub use self::sys::DescriptorCopy;
 pub use self::sys::DescriptorPool;
 pub use self::sys::DescriptorPoolAlloc;
 pub use self::sys::DescriptorPoolAllocError;
+pub use self::sys::DescriptorPoolCreationError;
 pub use self::sys::DescriptorWrite;
 pub use self::sys::DescriptorsCount;
 pub use self::sys::UnsafeDescriptorPool;
 pub use self::sys::UnsafeDescriptorPoolAllocIter;
 pub use self::sys::UnsafeDescriptorSet;
+pub use self::unsafe_layout::DescriptorBindingFlags;
+pub use self::unsafe_layout::DescriptorIndexingFeatures;
+pub use self::unsafe_layout::DescriptorSetLayoutCreationError;
+pub use self::unsafe_layout::PushDescriptorProperties;
 pub use self::unsafe_layout::UnsafeDescriptorSetLayout;
+pub use self::update_template::DescriptorBufferInfo;
+pub use self::update_template::DescriptorBufferViewInfo;
+pub use self::update_template::DescriptorImageInfo;
+pub use self::update_template::DescriptorUpdateTemplateCreationError;
+pub use self::update_template::DescriptorUpdateTemplateEntry;
+pub use self::update_template::UnsafeDescriptorUpdateTemplate;
+pub use self::with_offsets::DescriptorSetWithOffsets;
+pub use self::with_offsets::DescriptorSetWithOffsetsError;
+
+pub(crate) use self::persistent::PersistentDescriptorSetResources;
+pub(crate) use self::sys::with_raw_writes;
 
 pub mod collection;
 
 mod fixed_size_pool;
+mod frame_pool;
 mod persistent;
 mod std_pool;
 mod sys;
 mod unsafe_layout;
+mod update_template;
+mod with_offsets;
 
 /// Trait for objects that contain a collection of resources that will be accessible by shaders.
 ///
@@ -98,6 +126,16 @@ pub unsafe trait DescriptorSet: DescriptorSetDesc {
     ///
     /// The valid range is between 0 and `num_images()`.
     fn image(&self, index: usize) -> Option<(&ImageViewAccess, u32)>;
+
+    /// Returns the dynamic offsets to pass when binding this descriptor set. There must be one
+    /// offset for each element of each dynamic buffer descriptor, in binding order.
+    ///
+    /// The default implementation returns an empty list. Use `DescriptorSetWithOffsets` to attach
+    /// dynamic offsets to an existing descriptor set.
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        &[]
+    }
 }
 
 unsafe impl<T> DescriptorSet for T
@@ -128,6 +166,11 @@ unsafe impl<T> DescriptorSet for T
     fn image(&self, index: usize) -> Option<(&ImageViewAccess, u32)> {
         (**self).image(index)
     }
+
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        (**self).dynamic_offsets()
+    }
 }
 
 /// Trait for objects that describe the layout of the descriptors of a set.
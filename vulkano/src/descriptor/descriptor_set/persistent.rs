@@ -13,6 +13,7 @@ use std::sync::Arc;
 
 use OomError;
 use VulkanObject;
+use acceleration_structure::AccelerationStructure;
 use buffer::BufferAccess;
 use buffer::BufferViewRef;
 use descriptor::descriptor::DescriptorDesc;
@@ -21,6 +22,7 @@ use descriptor::descriptor::DescriptorImageDesc;
 use descriptor::descriptor::DescriptorImageDescArray;
 use descriptor::descriptor::DescriptorImageDescDimensions;
 use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::DescriptorCopy;
 use descriptor::descriptor_set::DescriptorPool;
 use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorSet;
@@ -81,6 +83,7 @@ impl<L> PersistentDescriptorSet<L, ()> {
             set_id: set_id,
             binding_id: 0,
             writes: Vec::with_capacity(cap),
+            copies: Vec::new(),
             resources: (),
         }
     }
@@ -157,6 +160,8 @@ pub struct PersistentDescriptorSetBuilder<L, R> {
     binding_id: usize,
     // The writes to perform on a descriptor set in order to put the resources in it.
     writes: Vec<DescriptorWrite>,
+    // The copies from other descriptor sets to perform after the writes.
+    copies: Vec<DescriptorCopy>,
     // Holds the resources alive.
     resources: R,
 }
@@ -209,7 +214,7 @@ impl<L, R> PersistentDescriptorSetBuilder<L, R>
         let set = unsafe {
             let mut set = pool.alloc(&set_layout)?;
             set.inner_mut()
-                .write(pool.device(), self.writes.into_iter());
+                .update(pool.device(), self.writes.into_iter(), self.copies.into_iter());
             set
         };
 
@@ -222,6 +227,35 @@ impl<L, R> PersistentDescriptorSetBuilder<L, R>
            })
     }
 
+    // The following functions are used to push the descriptors of the builder with
+    // `AutoCommandBufferBuilder::push_descriptor_set`, instead of allocating a set.
+
+    #[inline]
+    pub(crate) fn pipeline_layout(&self) -> &L {
+        &self.layout
+    }
+
+    #[inline]
+    pub(crate) fn set_id(&self) -> usize {
+        self.set_id
+    }
+
+    // Returns the number of bindings that have been filled.
+    #[inline]
+    pub(crate) fn num_filled_bindings(&self) -> usize {
+        self.binding_id
+    }
+
+    #[inline]
+    pub(crate) fn has_copies(&self) -> bool {
+        !self.copies.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn into_writes_and_resources(self) -> (L, Vec<DescriptorWrite>, R) {
+        (self.layout, self.writes, self.resources)
+    }
+
     /// Call this function if the next element of the set is an array in order to set the value of
     /// each element.
     ///
@@ -245,13 +279,16 @@ impl<L, R> PersistentDescriptorSetBuilder<L, R>
            })
     }
 
-    /// Skips the current descriptor if it is empty.
+    /// Skips the current descriptor if it is empty, or if it is a sampler descriptor whose
+    /// samplers are immutable samplers of the set layout.
     #[inline]
     pub fn add_empty(
         mut self)
         -> Result<PersistentDescriptorSetBuilder<L, R>, PersistentDescriptorSetError> {
         match self.layout.descriptor(self.set_id, self.binding_id) {
             None => (),
+            Some(DescriptorDesc { ty: DescriptorDescTy::Sampler, .. })
+                if self.has_immutable_samplers() => (),
             Some(desc) => return Err(PersistentDescriptorSetError::WrongDescriptorTy {
                                          expected: desc.ty.ty().unwrap(),
                                      }),
@@ -261,6 +298,15 @@ impl<L, R> PersistentDescriptorSetBuilder<L, R>
         Ok(self)
     }
 
+    // Returns true if the current descriptor has immutable samplers in the set layout.
+    #[inline]
+    fn has_immutable_samplers(&self) -> bool {
+        match self.layout.descriptor_set_layout(self.set_id) {
+            Some(layout) => !layout.immutable_samplers(self.binding_id).is_empty(),
+            None => false,
+        }
+    }
+
     /// Binds a buffer as the next descriptor.
     ///
     /// An error is returned if the buffer isn't compatible with the descriptor.
@@ -348,6 +394,82 @@ impl<L, R> PersistentDescriptorSetBuilder<L, R>
                                  PersistentDescriptorSetError> {
         self.enter_array()?.add_sampler(sampler)?.leave_array()
     }
+
+    /// Binds an acceleration structure as the next descriptor.
+    ///
+    /// An error is returned if the structure isn't compatible with the descriptor.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the structure doesn't have the same device as the pipeline layout.
+    ///
+    #[inline]
+    pub fn add_acceleration_structure(self, structure: Arc<AccelerationStructure>)
+        -> Result<PersistentDescriptorSetBuilder<L, (R, PersistentDescriptorSetAccelerationStructure)>, PersistentDescriptorSetError>
+    {
+        self.enter_array()?
+            .add_acceleration_structure(structure)?
+            .leave_array()
+    }
+
+    /// Copies the descriptors of binding `source_binding` of `source` as the next descriptor.
+    ///
+    /// The descriptor of `source` must have the same type as the next descriptor, and at least as
+    /// many array elements. The source set is kept alive by the set being built, and the resources
+    /// that it uses in that binding are reported as used by the new set.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `source` doesn't have the same device as the pipeline layout.
+    ///
+    pub fn copy_descriptor<S>(
+        mut self, source: S, source_binding: usize)
+        -> Result<PersistentDescriptorSetBuilder<L, (R, PersistentDescriptorSetCopy<S>)>,
+                  PersistentDescriptorSetError>
+        where S: DescriptorSet + DescriptorSetDesc + DeviceOwned
+    {
+        assert_eq!(self.layout.device().internal_object(),
+                   source.device().internal_object());
+
+        let desc = match self.layout.descriptor(self.set_id, self.binding_id) {
+            Some(d) => d,
+            None => return Err(PersistentDescriptorSetError::EmptyExpected),
+        };
+
+        let expected = desc.ty.ty().unwrap();
+        let source_desc = match source.descriptor(source_binding) {
+            Some(ref d) if d.ty.ty() == Some(expected) => d.clone(),
+            _ => return Err(PersistentDescriptorSetError::WrongDescriptorTy { expected }),
+        };
+
+        if source_desc.array_count < desc.array_count {
+            return Err(PersistentDescriptorSetError::MissingArrayElements {
+                           expected: desc.array_count,
+                           obtained: source_desc.array_count,
+                       });
+        }
+
+        self.copies.push(DescriptorCopy::new(source.inner(),
+                                             source_binding as u32,
+                                             0,
+                                             self.binding_id as u32,
+                                             0,
+                                             desc.array_count));
+
+        Ok(PersistentDescriptorSetBuilder {
+               layout: self.layout,
+               set_id: self.set_id,
+               binding_id: self.binding_id + 1,
+               writes: self.writes,
+               copies: self.copies,
+               resources: (self.resources,
+                           PersistentDescriptorSetCopy {
+                               source: source,
+                               source_binding: source_binding as u32,
+                               descriptor_num: self.binding_id as u32,
+                           }),
+           })
+    }
 }
 
 /// Same as `PersistentDescriptorSetBuilder`, but we're in an array.
@@ -416,15 +538,21 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                             .enabled_features()
                             .robust_buffer_access);
 
+                let binding = self.builder.binding_id as u32;
+                let array_element = self.array_element as u32;
+                let dynamic = buffer_desc.dynamic.unwrap_or(false);
+
                 if buffer_desc.storage {
                     if !buffer.inner().buffer.usage_storage_buffer() {
                         return Err(PersistentDescriptorSetError::MissingUsage);
                     }
 
                     unsafe {
-                        DescriptorWrite::storage_buffer(self.builder.binding_id as u32,
-                                                        self.array_element as u32,
-                                                        &buffer)
+                        if dynamic {
+                            DescriptorWrite::dynamic_storage_buffer(binding, array_element, &buffer)
+                        } else {
+                            DescriptorWrite::storage_buffer(binding, array_element, &buffer)
+                        }
                     }
                 } else {
                     if !buffer.inner().buffer.usage_uniform_buffer() {
@@ -432,9 +560,11 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                     }
 
                     unsafe {
-                        DescriptorWrite::uniform_buffer(self.builder.binding_id as u32,
-                                                        self.array_element as u32,
-                                                        &buffer)
+                        if dynamic {
+                            DescriptorWrite::dynamic_uniform_buffer(binding, array_element, &buffer)
+                        } else {
+                            DescriptorWrite::uniform_buffer(binding, array_element, &buffer)
+                        }
                     }
                 }
             },
@@ -451,6 +581,7 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                    set_id: self.builder.set_id,
                    binding_id: self.builder.binding_id,
                    writes: self.builder.writes,
+                   copies: self.builder.copies,
                    resources: (self.builder.resources,
                                PersistentDescriptorSetBuf {
                                    buffer: buffer,
@@ -516,6 +647,7 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                    set_id: self.builder.set_id,
                    binding_id: self.builder.binding_id,
                    writes: self.builder.writes,
+                   copies: self.builder.copies,
                    resources: (self.builder.resources,
                                PersistentDescriptorSetBufView {
                                    view: view,
@@ -621,6 +753,7 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                    set_id: self.builder.set_id,
                    binding_id: self.builder.binding_id,
                    writes: self.builder.writes,
+                   copies: self.builder.copies,
                    resources: (self.builder.resources,
                                PersistentDescriptorSetImg {
                                    image: image_view,
@@ -685,6 +818,7 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                    set_id: self.builder.set_id,
                    binding_id: self.builder.binding_id,
                    writes: self.builder.writes,
+                   copies: self.builder.copies,
                    resources: ((self.builder.resources,
                                 PersistentDescriptorSetImg {
                                     image: image_view,
@@ -722,6 +856,10 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
             None => return Err(PersistentDescriptorSetError::EmptyExpected),
         };
 
+        if self.builder.has_immutable_samplers() {
+            return Err(PersistentDescriptorSetError::ImmutableSamplers);
+        }
+
         self.builder.writes.push(match desc.ty {
             DescriptorDescTy::Sampler => {
                 DescriptorWrite::sampler(self.builder.binding_id as u32,
@@ -741,6 +879,7 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                    set_id: self.builder.set_id,
                    binding_id: self.builder.binding_id,
                    writes: self.builder.writes,
+                   copies: self.builder.copies,
                    resources: (self.builder.resources,
                                PersistentDescriptorSetSampler { sampler: sampler }),
                },
@@ -748,6 +887,55 @@ impl<L, R> PersistentDescriptorSetBuilderArray<L, R>
                array_element: self.array_element + 1,
            })
     }
+
+    /// Binds an acceleration structure as the next element in the array.
+    ///
+    /// An error is returned if the structure isn't compatible with the descriptor.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the structure doesn't have the same device as the pipeline layout.
+    ///
+    pub fn add_acceleration_structure(mut self, structure: Arc<AccelerationStructure>)
+        -> Result<PersistentDescriptorSetBuilderArray<L, (R, PersistentDescriptorSetAccelerationStructure)>, PersistentDescriptorSetError>
+    {
+        assert_eq!(self.builder.layout.device().internal_object(),
+                   structure.device().internal_object());
+
+        if self.array_element as u32 >= self.desc.array_count {
+            return Err(PersistentDescriptorSetError::ArrayOutOfBounds);
+        }
+
+        self.builder.writes.push(match self.desc.ty {
+            DescriptorDescTy::AccelerationStructure => {
+                DescriptorWrite::acceleration_structure(self.builder.binding_id as u32,
+                                                        self.array_element as u32,
+                                                        &structure)
+            },
+            ref d => {
+                return Err(PersistentDescriptorSetError::WrongDescriptorTy {
+                               expected: d.ty().unwrap(),
+                           });
+            },
+        });
+
+        Ok(PersistentDescriptorSetBuilderArray {
+               builder: PersistentDescriptorSetBuilder {
+                   layout: self.builder.layout,
+                   set_id: self.builder.set_id,
+                   binding_id: self.builder.binding_id,
+                   writes: self.builder.writes,
+                   copies: self.builder.copies,
+                   resources: (self.builder.resources,
+                               PersistentDescriptorSetAccelerationStructure {
+                                   structure: structure,
+                                   descriptor_num: self.builder.binding_id as u32,
+                               }),
+               },
+               desc: self.desc,
+               array_element: self.array_element + 1,
+           })
+    }
 }
 
 // Checks whether an image view matches the descriptor.
@@ -983,6 +1171,99 @@ unsafe impl<R> PersistentDescriptorSetResources for (R, PersistentDescriptorSetS
     }
 }
 
+/// Internal object related to the `PersistentDescriptorSet` system.
+pub struct PersistentDescriptorSetAccelerationStructure {
+    structure: Arc<AccelerationStructure>,
+    descriptor_num: u32,
+}
+
+unsafe impl<R> PersistentDescriptorSetResources for (R, PersistentDescriptorSetAccelerationStructure)
+    where R: PersistentDescriptorSetResources
+{
+    #[inline]
+    fn num_buffers(&self) -> usize {
+        self.0.num_buffers() + 1
+    }
+
+    #[inline]
+    fn buffer(&self, index: usize) -> Option<(&BufferAccess, u32)> {
+        if let Some(buf) = self.0.buffer(index) {
+            Some(buf)
+        } else if index == self.0.num_buffers() {
+            // The structure is synchronized through the buffer that stores it.
+            Some((self.1.structure.buffer(), self.1.descriptor_num))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn num_images(&self) -> usize {
+        self.0.num_images()
+    }
+
+    #[inline]
+    fn image(&self, index: usize) -> Option<(&ImageViewAccess, u32)> {
+        self.0.image(index)
+    }
+}
+
+/// Internal object related to the `PersistentDescriptorSet` system.
+pub struct PersistentDescriptorSetCopy<S> {
+    source: S,
+    source_binding: u32,
+    descriptor_num: u32,
+}
+
+unsafe impl<R, S> PersistentDescriptorSetResources for (R, PersistentDescriptorSetCopy<S>)
+    where R: PersistentDescriptorSetResources,
+          S: DescriptorSet
+{
+    #[inline]
+    fn num_buffers(&self) -> usize {
+        let copied = (0 .. self.1.source.num_buffers())
+            .filter(|&n| self.1.source.buffer(n).map(|b| b.1) == Some(self.1.source_binding))
+            .count();
+        self.0.num_buffers() + copied
+    }
+
+    #[inline]
+    fn buffer(&self, index: usize) -> Option<(&BufferAccess, u32)> {
+        if let Some(buf) = self.0.buffer(index) {
+            return Some(buf);
+        }
+
+        let index = index - self.0.num_buffers();
+        (0 .. self.1.source.num_buffers())
+            .filter_map(|n| self.1.source.buffer(n))
+            .filter(|&(_, binding)| binding == self.1.source_binding)
+            .nth(index)
+            .map(|(buf, _)| (buf, self.1.descriptor_num))
+    }
+
+    #[inline]
+    fn num_images(&self) -> usize {
+        let copied = (0 .. self.1.source.num_images())
+            .filter(|&n| self.1.source.image(n).map(|i| i.1) == Some(self.1.source_binding))
+            .count();
+        self.0.num_images() + copied
+    }
+
+    #[inline]
+    fn image(&self, index: usize) -> Option<(&ImageViewAccess, u32)> {
+        if let Some(img) = self.0.image(index) {
+            return Some(img);
+        }
+
+        let index = index - self.0.num_images();
+        (0 .. self.1.source.num_images())
+            .filter_map(|n| self.1.source.image(n))
+            .filter(|&(_, binding)| binding == self.1.source_binding)
+            .nth(index)
+            .map(|(img, _)| (img, self.1.descriptor_num))
+    }
+}
+
 /// Error related to the persistent descriptor set.
 #[derive(Debug, Clone)]
 pub enum PersistentDescriptorSetError {
@@ -1041,6 +1322,10 @@ pub enum PersistentDescriptorSetError {
         /// Type of the image view that was passed.
         obtained: DescriptorImageDescDimensions,
     },
+
+    /// Tried to write a sampler to a descriptor whose samplers are immutable samplers of the set
+    /// layout. Use `add_empty` to skip this descriptor instead.
+    ImmutableSamplers,
 }
 
 impl error::Error for PersistentDescriptorSetError {
@@ -1080,6 +1365,9 @@ impl error::Error for PersistentDescriptorSetError {
             PersistentDescriptorSetError::ImageViewTypeMismatch { .. } => {
                 "the type of an image view doesn't match what was expected"
             },
+            PersistentDescriptorSetError::ImmutableSamplers => {
+                "tried to write a sampler to a descriptor whose samplers are immutable"
+            },
         }
     }
 }
@@ -1104,6 +1392,9 @@ pub enum PersistentDescriptorSetBuildError {
         /// Number of descriptors that were added.
         obtained: u32,
     },
+
+    /// One of the resources isn't compatible with its descriptor.
+    WrongDescriptor(PersistentDescriptorSetError),
 }
 
 impl error::Error for PersistentDescriptorSetBuildError {
@@ -1116,6 +1407,18 @@ impl error::Error for PersistentDescriptorSetBuildError {
             PersistentDescriptorSetBuildError::OomError(_) => {
                 "not enough memory available"
             },
+            PersistentDescriptorSetBuildError::WrongDescriptor(_) => {
+                "one of the resources isn't compatible with its descriptor"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            PersistentDescriptorSetBuildError::OomError(ref err) => Some(err),
+            PersistentDescriptorSetBuildError::WrongDescriptor(ref err) => Some(err),
+            _ => None,
         }
     }
 }
@@ -1127,9 +1430,117 @@ impl From<OomError> for PersistentDescriptorSetBuildError {
     }
 }
 
+impl From<PersistentDescriptorSetError> for PersistentDescriptorSetBuildError {
+    #[inline]
+    fn from(err: PersistentDescriptorSetError) -> PersistentDescriptorSetBuildError {
+        PersistentDescriptorSetBuildError::WrongDescriptor(err)
+    }
+}
+
 impl fmt::Display for PersistentDescriptorSetBuildError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(fmt, "{}", error::Error::description(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSet;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::descriptor_set::PersistentDescriptorSetError;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::sync::Arc;
+    use VulkanObject;
+
+    fn buffer_desc(storage: bool) -> Option<DescriptorDesc> {
+        Some(DescriptorDesc {
+                 ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                  dynamic: Some(false),
+                                                  storage: storage,
+                                              }),
+                 array_count: 1,
+                 runtime_array: false,
+                 stages: ShaderStages::all_graphics(),
+                 readonly: true,
+             })
+    }
+
+    #[test]
+    fn copy_descriptor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = RuntimePipelineDesc::new(vec![vec![buffer_desc(false)],
+                                                   vec![buffer_desc(true), buffer_desc(false)]],
+                                              vec![])
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let other =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let source = PersistentDescriptorSet::start(layout.clone(), 0)
+            .add_buffer(buffer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+        let source = Arc::new(source);
+
+        let destination = PersistentDescriptorSet::start(layout.clone(), 1)
+            .add_buffer(other.clone())
+            .unwrap()
+            .copy_descriptor(source.clone(), 0)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The copied buffer is reported as used by the binding it was copied to.
+        assert_eq!(destination.num_buffers(), 2);
+        let (copied, binding) = destination.buffer(1).unwrap();
+        assert_eq!(copied.inner().buffer.internal_object(),
+                   buffer.inner().buffer.internal_object());
+        assert_eq!(binding, 1);
+        assert!(destination.buffer(2).is_none());
+    }
+
+    #[test]
+    fn copy_descriptor_wrong_type() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = RuntimePipelineDesc::new(vec![vec![buffer_desc(false)],
+                                                   vec![buffer_desc(true)]],
+                                              vec![])
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), 0u32).unwrap();
+        let source = PersistentDescriptorSet::start(layout.clone(), 0)
+            .add_buffer(buffer)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // The source is a uniform buffer, while a storage buffer is expected.
+        match PersistentDescriptorSet::start(layout, 1).copy_descriptor(Arc::new(source), 0) {
+            Err(PersistentDescriptorSetError::WrongDescriptorTy {
+                    expected: DescriptorType::StorageBuffer,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
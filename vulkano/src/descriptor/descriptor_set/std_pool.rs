@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::cmp;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -14,6 +15,7 @@ use OomError;
 use descriptor::descriptor_set::DescriptorPool;
 use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorPoolAllocError;
+use descriptor::descriptor_set::DescriptorPoolCreationError;
 use descriptor::descriptor_set::DescriptorsCount;
 use descriptor::descriptor_set::UnsafeDescriptorPool;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
@@ -28,7 +30,12 @@ use device::DeviceOwned;
 ///
 /// Whenever a set is allocated, this implementation will try to find a pool that has some space
 /// for it. If there is one, allocate from it. If there is none, create a new pool whose capacity
-/// is 40 sets and 40 times the requested descriptors. This number is arbitrary.
+/// is 40 sets and 40 times the requested descriptors. This number is arbitrary. Each new pool has
+/// twice the capacity of the previous one, up to 640 sets, so that programs that allocate a lot
+/// of sets per frame quickly stop creating new pools.
+///
+/// Sets that are destroyed are freed back to their pool, and the space they used is reused for
+/// the next allocations.
 pub struct StdDescriptorPool {
     device: Arc<Device>,
     pools: Mutex<Vec<Arc<Mutex<Pool>>>>,
@@ -106,11 +113,22 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
         }
 
         // No existing pool can be used. Create a new one.
-        // We use an arbitrary number of 40 sets and 40 times the requested descriptors.
-        let count = layout.descriptors_count().clone() * 40;
+        // We use an arbitrary number of 40 sets and 40 times the requested descriptors, doubled
+        // for each pool that already exists.
+        let num_sets = 40 << cmp::min(pools.len(), 4);
+        let count = layout.descriptors_count().clone() * num_sets;
         // Failure to allocate a new pool results in an error for the whole function because
         // there's no way we can recover from that.
-        let mut new_pool = UnsafeDescriptorPool::new(self.device.clone(), &count, 40, true)?;
+        let mut new_pool = match UnsafeDescriptorPool::new(self.device.clone(), &count, num_sets,
+                                                           true) {
+            Ok(pool) => pool,
+            Err(DescriptorPoolCreationError::OomError(err)) => return Err(err),
+            // Fragmentation can only happen with update after bind pools.
+            Err(DescriptorPoolCreationError::Fragmentation) => unreachable!(),
+            Err(err @ DescriptorPoolCreationError::UnexpectedResult(_)) => {
+                panic!("unexpected error: {:?}", err)
+            },
+        };
 
         let alloc = unsafe {
             match new_pool.alloc(Some(layout)) {
@@ -132,7 +150,7 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                                                pool: new_pool,
                                                remaining_capacity: count -
                                                    *layout.descriptors_count(),
-                                               remaining_sets_count: 40 - 1,
+                                               remaining_sets_count: num_sets - 1,
                                            }));
 
         pools.push(pool_obj.clone());
@@ -197,6 +215,7 @@ mod tests {
         let desc = DescriptorDesc {
             ty: DescriptorDescTy::Sampler,
             array_count: 1,
+            runtime_array: false,
             stages: ShaderStages::all(),
             readonly: false,
         };
@@ -209,4 +228,34 @@ mod tests {
         drop(pool);
         assert!(pool_weak.upgrade().is_some());
     }
+
+    #[test]
+    fn grows_and_reuses() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all(),
+            readonly: false,
+        };
+        let layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc)))
+            .unwrap();
+
+        let mut pool = Arc::new(StdDescriptorPool::new(device));
+
+        // The first pool holds 40 sets and the second one 80.
+        let allocs = (0 .. 121)
+            .map(|_| pool.alloc(&layout).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(pool.pools.lock().unwrap().len(), 3);
+
+        // Freed sets are reused instead of creating new pools.
+        drop(allocs);
+        let _allocs = (0 .. 121)
+            .map(|_| pool.alloc(&layout).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(pool.pools.lock().unwrap().len(), 3);
+    }
 }
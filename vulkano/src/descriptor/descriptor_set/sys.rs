@@ -11,17 +11,20 @@ use smallvec::SmallVec;
 use std::cmp;
 use std::error;
 use std::fmt;
+use std::iter;
 use std::mem;
 use std::ops;
 use std::ptr;
 use std::sync::Arc;
 use std::vec::IntoIter as VecIntoIter;
 
+use acceleration_structure::AccelerationStructure;
 use buffer::BufferAccess;
 use buffer::BufferInner;
 use buffer::BufferView;
 use descriptor::descriptor::DescriptorType;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use descriptor::descriptor_set::UnsafeDescriptorUpdateTemplate;
 use device::Device;
 use device::DeviceOwned;
 use image::ImageViewAccess;
@@ -104,6 +107,7 @@ macro_rules! descriptors_count {
                     DescriptorType::UniformBufferDynamic => self.uniform_buffer_dynamic += 1,
                     DescriptorType::StorageBufferDynamic => self.storage_buffer_dynamic += 1,
                     DescriptorType::InputAttachment => self.input_attachment += 1,
+                    DescriptorType::AccelerationStructure => self.acceleration_structure += 1,
                 };
             }
         }
@@ -220,6 +224,7 @@ descriptors_count! {
     sampler,
     combined_image_sampler,
     input_attachment,
+    acceleration_structure,
 }
 
 /// Pool from which descriptor sets are allocated from.
@@ -229,6 +234,10 @@ descriptors_count! {
 pub struct UnsafeDescriptorPool {
     pool: vk::DescriptorPool,
     device: Arc<Device>,
+    // True if the pool was created with `update_after_bind`.
+    update_after_bind: bool,
+    // False if the pool was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl UnsafeDescriptorPool {
@@ -245,14 +254,42 @@ impl UnsafeDescriptorPool {
     /// - Panics if all the descriptors count are 0.
     /// - Panics if `max_sets` is 0.
     ///
+    #[inline]
     pub fn new(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
                free_descriptor_set_bit: bool)
-               -> Result<UnsafeDescriptorPool, OomError> {
+               -> Result<UnsafeDescriptorPool, DescriptorPoolCreationError> {
+        UnsafeDescriptorPool::create(device, count, max_sets, free_descriptor_set_bit, false)
+    }
+
+    /// Same as `new`, but the pool can be used to allocate descriptor sets whose layout has
+    /// bindings that are updated after bind.
+    ///
+    /// Requires the `ext_descriptor_indexing` extension to be enabled on the device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if all the descriptors count are 0.
+    /// - Panics if `max_sets` is 0.
+    /// - Panics if the `ext_descriptor_indexing` extension is not enabled.
+    ///
+    #[inline]
+    pub fn update_after_bind(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
+                             free_descriptor_set_bit: bool)
+                             -> Result<UnsafeDescriptorPool, DescriptorPoolCreationError> {
+        assert!(device.loaded_extensions().ext_descriptor_indexing,
+                "The ext_descriptor_indexing extension must be enabled to create an update after \
+                 bind pool");
+        UnsafeDescriptorPool::create(device, count, max_sets, free_descriptor_set_bit, true)
+    }
+
+    fn create(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
+              free_descriptor_set_bit: bool, update_after_bind: bool)
+              -> Result<UnsafeDescriptorPool, DescriptorPoolCreationError> {
         let vk = device.pointers();
 
         assert_ne!(max_sets, 0, "The maximum number of sets can't be 0");
 
-        let mut pool_sizes: SmallVec<[_; 10]> = SmallVec::new();
+        let mut pool_sizes: SmallVec<[_; 12]> = SmallVec::new();
 
         macro_rules! elem {
             ($field:ident, $ty:expr) => (
@@ -281,6 +318,8 @@ impl UnsafeDescriptorPool {
         elem!(combined_image_sampler,
               vk::DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER);
         elem!(input_attachment, vk::DESCRIPTOR_TYPE_INPUT_ATTACHMENT);
+        elem!(acceleration_structure,
+              vk::DESCRIPTOR_TYPE_ACCELERATION_STRUCTURE_KHR);
 
         assert!(!pool_sizes.is_empty(),
                 "All the descriptors count of a pool are 0");
@@ -289,10 +328,15 @@ impl UnsafeDescriptorPool {
             let infos = vk::DescriptorPoolCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_POOL_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: if free_descriptor_set_bit {
-                    vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT
-                } else {
-                    0
+                flags: {
+                    let mut flags = 0;
+                    if free_descriptor_set_bit {
+                        flags |= vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT;
+                    }
+                    if update_after_bind {
+                        flags |= vk::DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT;
+                    }
+                    flags
                 },
                 maxSets: max_sets,
                 poolSizeCount: pool_sizes.len() as u32,
@@ -310,9 +354,36 @@ impl UnsafeDescriptorPool {
         Ok(UnsafeDescriptorPool {
                pool: pool,
                device: device.clone(),
+               update_after_bind: update_after_bind,
+               needs_destruction: true,
            })
     }
 
+    /// Builds an `UnsafeDescriptorPool` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the pool is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid descriptor pool of `device`.
+    /// - `update_after_bind` must be true if and only if the pool was created with the
+    ///   `VK_DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT` flag.
+    /// - The pool must not be used by anything else while vulkano allocates from it.
+    /// - If `owned` is true, the pool must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::DescriptorPool,
+                           update_after_bind: bool, owned: bool)
+                           -> UnsafeDescriptorPool {
+        UnsafeDescriptorPool {
+            pool: handle,
+            device: device,
+            update_after_bind: update_after_bind,
+            needs_destruction: owned,
+        }
+    }
+
     /// Allocates descriptor sets from the pool, one for each layout.
     /// Returns an iterator to the allocated sets, or an error.
     ///
@@ -322,6 +393,9 @@ impl UnsafeDescriptorPool {
     /// # Panic
     ///
     /// - Panics if one of the layouts wasn't created with the same device as the pool.
+    /// - Panics if one of the layouts was created for push descriptors.
+    /// - Panics if one of the layouts has bindings that are updated after bind, and the pool
+    ///   wasn't created with `update_after_bind`.
     ///
     /// # Safety
     ///
@@ -339,20 +413,67 @@ impl UnsafeDescriptorPool {
     {
         let layouts: SmallVec<[_; 8]> = layouts
             .into_iter()
-            .map(|l| {
-                     assert_eq!(self.device.internal_object(),
-                                l.device().internal_object(),
-                                "Tried to allocate from a pool with a set layout of a different \
-                                 device");
-                     l.internal_object()
-                 })
+            .map(|l| self.check_layout(l))
             .collect();
 
-        self.alloc_impl(&layouts)
+        self.alloc_impl(&layouts, None)
+    }
+
+    /// Same as `alloc`, but each layout is accompanied with the number of array elements of its
+    /// binding that has a variable descriptor count.
+    ///
+    /// The number is ignored for layouts that don't have such a binding.
+    ///
+    /// # Panic
+    ///
+    /// - Same panics as `alloc`.
+    /// - Panics if one of the numbers is higher than the `array_count` of the corresponding
+    ///   binding.
+    ///
+    /// # Safety
+    ///
+    /// Same as `alloc`. The total descriptors of the layouts, counted with the variable
+    /// descriptor counts, must fit in the pool.
+    ///
+    pub unsafe fn alloc_with_variable_counts<'l, I>(&mut self, layouts: I)
+                                                    -> Result<UnsafeDescriptorPoolAllocIter,
+                                                              DescriptorPoolAllocError>
+        where I: IntoIterator<Item = (&'l UnsafeDescriptorSetLayout, u32)>
+    {
+        let mut counts: SmallVec<[u32; 8]> = SmallVec::new();
+        let layouts: SmallVec<[_; 8]> = layouts
+            .into_iter()
+            .map(|(l, count)| {
+                if let Some(binding) = l.variable_descriptor_count_binding() {
+                    assert!(count <= l.descriptor(binding).unwrap().array_count,
+                            "The variable descriptor count is higher than the number of array \
+                             elements of the binding");
+                }
+                counts.push(count);
+                self.check_layout(l)
+            })
+            .collect();
+
+        self.alloc_impl(&layouts, Some(&counts))
+    }
+
+    // Checks that a layout can be used to allocate a set from this pool.
+    #[inline]
+    fn check_layout(&self, layout: &UnsafeDescriptorSetLayout) -> vk::DescriptorSetLayout {
+        assert_eq!(self.device.internal_object(),
+                   layout.device().internal_object(),
+                   "Tried to allocate from a pool with a set layout of a different device");
+        assert!(!layout.is_push_descriptor(),
+                "Tried to allocate a descriptor set with a push descriptor layout");
+        assert!(self.update_after_bind || !layout.is_update_after_bind(),
+                "Tried to allocate a descriptor set with an update after bind layout from a pool \
+                 that wasn't created with `update_after_bind`");
+        layout.internal_object()
     }
 
     // Actual implementation of `alloc`. Separated so that it is not inlined.
-    unsafe fn alloc_impl(&mut self, layouts: &SmallVec<[vk::DescriptorSetLayout; 8]>)
+    unsafe fn alloc_impl(&mut self, layouts: &SmallVec<[vk::DescriptorSetLayout; 8]>,
+                         variable_counts: Option<&SmallVec<[u32; 8]>>)
                          -> Result<UnsafeDescriptorPoolAllocIter, DescriptorPoolAllocError> {
         let num = layouts.len();
 
@@ -360,9 +481,22 @@ impl UnsafeDescriptorPool {
             return Ok(UnsafeDescriptorPoolAllocIter { sets: vec![].into_iter() });
         }
 
+        let variable_counts_infos = variable_counts.map(|counts| {
+            debug_assert_eq!(counts.len(), num);
+            vk::DescriptorSetVariableDescriptorCountAllocateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_ALLOCATE_INFO_EXT,
+                pNext: ptr::null(),
+                descriptorSetCount: counts.len() as u32,
+                pDescriptorCounts: counts.as_ptr(),
+            }
+        });
+
         let infos = vk::DescriptorSetAllocateInfo {
             sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_ALLOCATE_INFO,
-            pNext: ptr::null(),
+            pNext: variable_counts_infos
+                .as_ref()
+                .map(|i| i as *const _ as *const _)
+                .unwrap_or(ptr::null()),
             descriptorPool: self.pool,
             descriptorSetCount: layouts.len() as u32,
             pSetLayouts: layouts.as_ptr(),
@@ -464,6 +598,10 @@ impl fmt::Debug for UnsafeDescriptorPool {
 impl Drop for UnsafeDescriptorPool {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyDescriptorPool(self.device.internal_object(), self.pool, ptr::null());
@@ -511,6 +649,47 @@ impl fmt::Display for DescriptorPoolAllocError {
     }
 }
 
+/// Error that can happen when creating a descriptor pool.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorPoolCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The creation of an update after bind pool has failed because of the fragmentation of
+    /// internal resources.
+    Fragmentation,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for DescriptorPoolCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorPoolCreationError::OomError(_) => {
+                "not enough memory available"
+            },
+            DescriptorPoolCreationError::Fragmentation => {
+                "the creation of the pool has failed because of fragmentation"
+            },
+            DescriptorPoolCreationError::UnexpectedResult(_) => {
+                "the implementation returned an unexpected error code"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DescriptorPoolCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(DescriptorPoolCreationError, OomError {
+    Fragmentation => Fragmentation,
+});
+
 /// Iterator to the descriptor sets allocated from an unsafe descriptor pool.
 #[derive(Debug)]
 pub struct UnsafeDescriptorPoolAllocIter {
@@ -544,12 +723,10 @@ pub struct UnsafeDescriptorSet {
 }
 
 impl UnsafeDescriptorSet {
-    // TODO: add copying from other descriptor sets
-    //       add a `copy` method that just takes a copy, and an `update` method that takes both
-    //       writes and copies and that actually performs the operation
-
-    /// Modifies a descriptor set. Doesn't check that the writes or copies are correct, and
-    /// doesn't check whether the descriptor set is in use.
+    /// Modifies a descriptor set. Doesn't check that the writes are correct, and doesn't check
+    /// whether the descriptor set is in use.
+    ///
+    /// This is equivalent to calling `update` with an empty list of copies.
     ///
     /// **Important**: You must ensure that the `UnsafeDescriptorSetLayout` object is alive before
     /// updating a descriptor set.
@@ -564,169 +741,307 @@ impl UnsafeDescriptorSet {
     ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
     ///   to it.
     ///
+    #[inline]
     pub unsafe fn write<I>(&mut self, device: &Device, writes: I)
         where I: Iterator<Item = DescriptorWrite>
     {
+        self.update(device, writes, iter::empty())
+    }
+
+    /// Copies descriptors from other descriptor sets into this one. Doesn't check that the copies
+    /// are correct, and doesn't check whether the descriptor set is in use.
+    ///
+    /// This is equivalent to calling `update` with an empty list of writes.
+    ///
+    /// # Safety
+    ///
+    /// Same as `update`. In addition to this, the source descriptor sets must still be alive and
+    /// the descriptors being copied must have been written before.
+    ///
+    #[inline]
+    pub unsafe fn copy<I>(&mut self, device: &Device, copies: I)
+        where I: Iterator<Item = DescriptorCopy>
+    {
+        self.update(device, iter::empty(), copies)
+    }
+
+    /// Modifies a descriptor set by performing writes and then copies. Doesn't check that the
+    /// writes or copies are correct, and doesn't check whether the descriptor set is in use.
+    ///
+    /// All the writes are performed before all the copies, which means that a copy can read
+    /// descriptors written by the same call.
+    ///
+    /// **Important**: You must ensure that the `UnsafeDescriptorSetLayout` object is alive before
+    /// updating a descriptor set.
+    ///
+    /// # Safety
+    ///
+    /// - The `Device` must be the device the pool of this set was created with.
+    /// - The `UnsafeDescriptorSetLayout` object this set was created with must be alive.
+    /// - Doesn't verify that the things you write in the descriptor set match its layout.
+    /// - Doesn't verify that the source and destination of copies have the same descriptor type,
+    ///   or that the ranges of copies are within the bounds of their bindings.
+    /// - Doesn't keep the resources alive. You have to do that yourself.
+    /// - Updating a descriptor set obeys synchronization rules that aren't checked here. Once a
+    ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
+    ///   to it.
+    ///
+    pub unsafe fn update<W, C>(&mut self, device: &Device, writes: W, copies: C)
+        where W: Iterator<Item = DescriptorWrite>,
+              C: Iterator<Item = DescriptorCopy>
+    {
+        let vk = device.pointers();
+        let raw_copies: SmallVec<[_; 16]> = copies
+            .map(|copy| {
+                debug_assert_ne!(copy.count, 0);
+                vk::CopyDescriptorSet {
+                    sType: vk::STRUCTURE_TYPE_COPY_DESCRIPTOR_SET,
+                    pNext: ptr::null(),
+                    srcSet: copy.source_set,
+                    srcBinding: copy.source_binding,
+                    srcArrayElement: copy.source_first_array_element,
+                    dstSet: self.set,
+                    dstBinding: copy.destination_binding,
+                    dstArrayElement: copy.destination_first_array_element,
+                    descriptorCount: copy.count,
+                }
+            })
+            .collect();
+
+        with_raw_writes(self.set, writes, |raw_writes| {
+            // It is pointless to call `vkUpdateDescriptorSets` with 0 writes and 0 copies, so we
+            // perform this emptiness check.
+            if !raw_writes.is_empty() || !raw_copies.is_empty() {
+                vk.UpdateDescriptorSets(device.internal_object(),
+                                        raw_writes.len() as u32,
+                                        raw_writes.as_ptr(),
+                                        raw_copies.len() as u32,
+                                        raw_copies.as_ptr());
+            }
+        })
+    }
+
+    /// Modifies the descriptor set by reading the descriptors from `data`, as described by
+    /// `template`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the template was not created with `device`.
+    ///
+    /// # Safety
+    ///
+    /// - The `Device` must be the device the pool of this set was created with.
+    /// - The template must have been created with the layout this set was created with.
+    /// - `data` must contain, at the offsets and strides described by the entries of the
+    ///   template, `DescriptorImageInfo`, `DescriptorBufferInfo` or `DescriptorBufferViewInfo`
+    ///   objects that match the type of each entry.
+    /// - Doesn't keep the resources alive. You have to do that yourself.
+    /// - Same synchronization rules as `write`.
+    ///
+    pub unsafe fn update_with_template<D>(&mut self, device: &Device,
+                                          template: &UnsafeDescriptorUpdateTemplate, data: &D)
+        where D: ?Sized
+    {
+        assert_eq!(template.device().internal_object(), device.internal_object());
+
         let vk = device.pointers();
+        vk.UpdateDescriptorSetWithTemplateKHR(device.internal_object(),
+                                              self.set,
+                                              template.internal_object(),
+                                              data as *const D as *const _);
+    }
+}
 
-        // In this function, we build 4 arrays: one array of image descriptors (image_descriptors),
-        // one for buffer descriptors (buffer_descriptors), one for buffer view descriptors
-        // (buffer_views_descriptors), and one for the final list of writes (raw_writes).
-        // Only the final list is passed to Vulkan, but it will contain pointers to the first three
-        // lists in `pImageInfo`, `pBufferInfo` and `pTexelBufferView`.
-        //
-        // In order to handle that, we start by writing null pointers as placeholders in the final
-        // writes, and we store in `raw_writes_img_infos`, `raw_writes_buf_infos` and
-        // `raw_writes_buf_view_infos` the offsets of the pointers compared to the start of the
-        // list.
-        // Once we have finished iterating all the writes requested by the user, we modify
-        // `raw_writes` to point to the correct locations.
-
-        let mut buffer_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-        let mut image_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-        let mut buffer_views_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-
-        let mut raw_writes: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_img_infos: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_buf_infos: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_buf_view_infos: SmallVec<[_; 64]> = SmallVec::new();
-
-        for indiv_write in writes {
-            // Since the `DescriptorWrite` objects are built only through functions, we know for
-            // sure that it's impossible to have an empty descriptor write.
-            debug_assert!(!indiv_write.inner.is_empty());
-
-            // The whole struct that wr write here is valid, except for pImageInfo, pBufferInfo
-            // and pTexelBufferView which are placeholder values.
-            raw_writes.push(vk::WriteDescriptorSet {
-                                sType: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET,
-                                pNext: ptr::null(),
-                                dstSet: self.set,
-                                dstBinding: indiv_write.binding,
-                                dstArrayElement: indiv_write.first_array_element,
-                                descriptorCount: indiv_write.inner.len() as u32,
-                                descriptorType: indiv_write.ty() as u32,
-                                pImageInfo: ptr::null(),
-                                pBufferInfo: ptr::null(),
-                                pTexelBufferView: ptr::null(),
-                            });
-
-            match indiv_write.inner[0] {
-                DescriptorWriteInner::Sampler(_) |
-                DescriptorWriteInner::CombinedImageSampler(_, _, _) |
-                DescriptorWriteInner::SampledImage(_, _) |
-                DescriptorWriteInner::StorageImage(_, _) |
-                DescriptorWriteInner::InputAttachment(_, _) => {
-                    raw_writes_img_infos.push(Some(image_descriptors.len()));
-                    raw_writes_buf_infos.push(None);
-                    raw_writes_buf_view_infos.push(None);
+/// Builds the list of `vk::WriteDescriptorSet` that corresponds to `writes`, and calls `f` with
+/// it. The `dstSet` field of each write is set to `dst_set`.
+///
+/// The pointers contained in the writes are only valid during the call to `f`.
+pub(crate) unsafe fn with_raw_writes<I, F, R>(dst_set: vk::DescriptorSet, writes: I, f: F) -> R
+    where I: Iterator<Item = DescriptorWrite>,
+          F: FnOnce(&[vk::WriteDescriptorSet]) -> R
+{
+    // In this function, we build 4 arrays: one array of image descriptors (image_descriptors),
+    // one for buffer descriptors (buffer_descriptors), one for buffer view descriptors
+    // (buffer_views_descriptors), and one for the final list of writes (raw_writes).
+    // Only the final list is passed to Vulkan, but it will contain pointers to the first three
+    // lists in `pImageInfo`, `pBufferInfo` and `pTexelBufferView`.
+    //
+    // In order to handle that, we start by writing null pointers as placeholders in the final
+    // writes, and we store in `raw_writes_img_infos`, `raw_writes_buf_infos` and
+    // `raw_writes_buf_view_infos` the offsets of the pointers compared to the start of the
+    // list.
+    // Once we have finished iterating all the writes requested by the user, we modify
+    // `raw_writes` to point to the correct locations.
+    //
+    // Acceleration structures are handled in the same way, except that they are passed through
+    // a `vk::WriteDescriptorSetAccelerationStructureKHR` in the `pNext` chain of the write.
+
+    let mut buffer_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut image_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut buffer_views_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut acceleration_structure_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut acceleration_structure_writes: SmallVec<[_; 8]> = SmallVec::new();
+
+    let mut raw_writes: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_img_infos: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_buf_infos: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_buf_view_infos: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_as_infos: SmallVec<[_; 64]> = SmallVec::new();
+
+    for indiv_write in writes {
+        // Since the `DescriptorWrite` objects are built only through functions, we know for
+        // sure that it's impossible to have an empty descriptor write.
+        debug_assert!(!indiv_write.inner.is_empty());
+
+        // The whole struct that wr write here is valid, except for pImageInfo, pBufferInfo
+        // and pTexelBufferView which are placeholder values.
+        raw_writes.push(vk::WriteDescriptorSet {
+                            sType: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET,
+                            pNext: ptr::null(),
+                            dstSet: dst_set,
+                            dstBinding: indiv_write.binding,
+                            dstArrayElement: indiv_write.first_array_element,
+                            descriptorCount: indiv_write.inner.len() as u32,
+                            descriptorType: indiv_write.ty() as u32,
+                            pImageInfo: ptr::null(),
+                            pBufferInfo: ptr::null(),
+                            pTexelBufferView: ptr::null(),
+                        });
+
+        match indiv_write.inner[0] {
+            DescriptorWriteInner::Sampler(_) |
+            DescriptorWriteInner::CombinedImageSampler(_, _, _) |
+            DescriptorWriteInner::SampledImage(_, _) |
+            DescriptorWriteInner::StorageImage(_, _) |
+            DescriptorWriteInner::InputAttachment(_, _) => {
+                raw_writes_img_infos.push(Some(image_descriptors.len()));
+                raw_writes_buf_infos.push(None);
+                raw_writes_buf_view_infos.push(None);
+                raw_writes_as_infos.push(None);
+            },
+            DescriptorWriteInner::UniformBuffer(_, _, _) |
+            DescriptorWriteInner::StorageBuffer(_, _, _) |
+            DescriptorWriteInner::DynamicUniformBuffer(_, _, _) |
+            DescriptorWriteInner::DynamicStorageBuffer(_, _, _) => {
+                raw_writes_img_infos.push(None);
+                raw_writes_buf_infos.push(Some(buffer_descriptors.len()));
+                raw_writes_buf_view_infos.push(None);
+                raw_writes_as_infos.push(None);
+            },
+            DescriptorWriteInner::UniformTexelBuffer(_) |
+            DescriptorWriteInner::StorageTexelBuffer(_) => {
+                raw_writes_img_infos.push(None);
+                raw_writes_buf_infos.push(None);
+                raw_writes_buf_view_infos.push(Some(buffer_views_descriptors.len()));
+                raw_writes_as_infos.push(None);
+            },
+            DescriptorWriteInner::AccelerationStructure(_) => {
+                raw_writes_img_infos.push(None);
+                raw_writes_buf_infos.push(None);
+                raw_writes_buf_view_infos.push(None);
+                raw_writes_as_infos.push(Some((acceleration_structure_writes.len(),
+                                               acceleration_structure_descriptors.len())));
+                acceleration_structure_writes.push(vk::WriteDescriptorSetAccelerationStructureKHR {
+                    sType: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET_ACCELERATION_STRUCTURE_KHR,
+                    pNext: ptr::null(),
+                    accelerationStructureCount: indiv_write.inner.len() as u32,
+                    pAccelerationStructures: ptr::null(),
+                });
+            },
+        }
+
+        for elem in indiv_write.inner.iter() {
+            match *elem {
+                DescriptorWriteInner::UniformBuffer(buffer, offset, size) |
+                DescriptorWriteInner::DynamicUniformBuffer(buffer, offset, size) => {
+                    buffer_descriptors.push(vk::DescriptorBufferInfo {
+                                                buffer: buffer,
+                                                offset: offset as u64,
+                                                range: size as u64,
+                                            });
                 },
-                DescriptorWriteInner::UniformBuffer(_, _, _) |
-                DescriptorWriteInner::StorageBuffer(_, _, _) |
-                DescriptorWriteInner::DynamicUniformBuffer(_, _, _) |
-                DescriptorWriteInner::DynamicStorageBuffer(_, _, _) => {
-                    raw_writes_img_infos.push(None);
-                    raw_writes_buf_infos.push(Some(buffer_descriptors.len()));
-                    raw_writes_buf_view_infos.push(None);
+                DescriptorWriteInner::StorageBuffer(buffer, offset, size) |
+                DescriptorWriteInner::DynamicStorageBuffer(buffer, offset, size) => {
+                    buffer_descriptors.push(vk::DescriptorBufferInfo {
+                                                buffer: buffer,
+                                                offset: offset as u64,
+                                                range: size as u64,
+                                            });
                 },
-                DescriptorWriteInner::UniformTexelBuffer(_) |
-                DescriptorWriteInner::StorageTexelBuffer(_) => {
-                    raw_writes_img_infos.push(None);
-                    raw_writes_buf_infos.push(None);
-                    raw_writes_buf_view_infos.push(Some(buffer_views_descriptors.len()));
+                DescriptorWriteInner::Sampler(sampler) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: sampler,
+                                               imageView: 0,
+                                               imageLayout: 0,
+                                           });
+                },
+                DescriptorWriteInner::CombinedImageSampler(sampler, view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: sampler,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::StorageImage(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::SampledImage(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::InputAttachment(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::UniformTexelBuffer(view) |
+                DescriptorWriteInner::StorageTexelBuffer(view) => {
+                    buffer_views_descriptors.push(view);
+                },
+                DescriptorWriteInner::AccelerationStructure(structure) => {
+                    acceleration_structure_descriptors.push(structure);
                 },
-            }
-
-            for elem in indiv_write.inner.iter() {
-                match *elem {
-                    DescriptorWriteInner::UniformBuffer(buffer, offset, size) |
-                    DescriptorWriteInner::DynamicUniformBuffer(buffer, offset, size) => {
-                        buffer_descriptors.push(vk::DescriptorBufferInfo {
-                                                    buffer: buffer,
-                                                    offset: offset as u64,
-                                                    range: size as u64,
-                                                });
-                    },
-                    DescriptorWriteInner::StorageBuffer(buffer, offset, size) |
-                    DescriptorWriteInner::DynamicStorageBuffer(buffer, offset, size) => {
-                        buffer_descriptors.push(vk::DescriptorBufferInfo {
-                                                    buffer: buffer,
-                                                    offset: offset as u64,
-                                                    range: size as u64,
-                                                });
-                    },
-                    DescriptorWriteInner::Sampler(sampler) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: sampler,
-                                                   imageView: 0,
-                                                   imageLayout: 0,
-                                               });
-                    },
-                    DescriptorWriteInner::CombinedImageSampler(sampler, view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: sampler,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::StorageImage(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::SampledImage(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::InputAttachment(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::UniformTexelBuffer(view) |
-                    DescriptorWriteInner::StorageTexelBuffer(view) => {
-                        buffer_views_descriptors.push(view);
-                    },
-                }
             }
         }
+    }
 
-        // Now that `image_descriptors`, `buffer_descriptors` and `buffer_views_descriptors` are
-        // entirely filled and will never move again, we can fill the pointers in `raw_writes`.
-        for (i, write) in raw_writes.iter_mut().enumerate() {
-            write.pImageInfo = match raw_writes_img_infos[i] {
-                Some(off) => image_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
+    // Now that `image_descriptors`, `buffer_descriptors` and `buffer_views_descriptors` are
+    // entirely filled and will never move again, we can fill the pointers in `raw_writes`.
+    for (i, write) in raw_writes.iter_mut().enumerate() {
+        write.pImageInfo = match raw_writes_img_infos[i] {
+            Some(off) => image_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
 
-            write.pBufferInfo = match raw_writes_buf_infos[i] {
-                Some(off) => buffer_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
+        write.pBufferInfo = match raw_writes_buf_infos[i] {
+            Some(off) => buffer_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
 
-            write.pTexelBufferView = match raw_writes_buf_view_infos[i] {
-                Some(off) => buffer_views_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
-        }
+        write.pTexelBufferView = match raw_writes_buf_view_infos[i] {
+            Some(off) => buffer_views_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
 
-        // It is forbidden to call `vkUpdateDescriptorSets` with 0 writes, so we need to perform
-        // this emptiness check.
-        if !raw_writes.is_empty() {
-            vk.UpdateDescriptorSets(device.internal_object(),
-                                    raw_writes.len() as u32,
-                                    raw_writes.as_ptr(),
-                                    0,
-                                    ptr::null());
+        if let Some((as_write, off)) = raw_writes_as_infos[i] {
+            let as_write: &mut vk::WriteDescriptorSetAccelerationStructureKHR =
+                &mut acceleration_structure_writes[as_write];
+            as_write.pAccelerationStructures =
+                acceleration_structure_descriptors.as_ptr().offset(off as isize);
+            write.pNext = as_write as *const _ as *const _;
         }
     }
+
+    f(&raw_writes)
 }
 
 unsafe impl VulkanObject for UnsafeDescriptorSet {
@@ -746,6 +1061,68 @@ impl fmt::Debug for UnsafeDescriptorSet {
     }
 }
 
+/// Represents a single copy entry from one descriptor set to another.
+///
+/// While it is safe to build a `DescriptorCopy`, it is unsafe to actually use it to update a
+/// descriptor set. The `DescriptorCopy` doesn't hold the source descriptor set, and you must
+/// ensure that it is still alive when the copy is performed.
+#[derive(Debug, Copy, Clone)]
+pub struct DescriptorCopy {
+    source_set: vk::DescriptorSet,
+    source_binding: u32,
+    source_first_array_element: u32,
+    destination_binding: u32,
+    destination_first_array_element: u32,
+    count: u32,
+}
+
+impl DescriptorCopy {
+    /// Builds a copy of `count` descriptors from `source`, starting at the given binding and
+    /// array element, to the given binding and array element of the destination set.
+    ///
+    /// If `count` is larger than the number of elements remaining in a binding, the copy
+    /// continues with the next bindings, as described in the Vulkan specs.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `count` is 0.
+    ///
+    #[inline]
+    pub fn new(source: &UnsafeDescriptorSet, source_binding: u32,
+               source_first_array_element: u32, destination_binding: u32,
+               destination_first_array_element: u32, count: u32)
+               -> DescriptorCopy {
+        assert_ne!(count, 0);
+
+        DescriptorCopy {
+            source_set: source.set,
+            source_binding: source_binding,
+            source_first_array_element: source_first_array_element,
+            destination_binding: destination_binding,
+            destination_first_array_element: destination_first_array_element,
+            count: count,
+        }
+    }
+
+    /// Returns the binding of the source descriptor set that is read from.
+    #[inline]
+    pub fn source_binding(&self) -> u32 {
+        self.source_binding
+    }
+
+    /// Returns the binding of the destination descriptor set that is written to.
+    #[inline]
+    pub fn destination_binding(&self) -> u32 {
+        self.destination_binding
+    }
+
+    /// Returns the number of descriptors to copy.
+    #[inline]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
 /// Represents a single write entry to a descriptor set.
 ///
 /// Use the various constructors to build a `DescriptorWrite`. While it is safe to build a
@@ -770,6 +1147,7 @@ enum DescriptorWriteInner {
     DynamicUniformBuffer(vk::Buffer, usize, usize),
     DynamicStorageBuffer(vk::Buffer, usize, usize),
     InputAttachment(vk::ImageView, vk::ImageLayout),
+    AccelerationStructure(vk::AccelerationStructureKHR),
 }
 
 macro_rules! smallvec {
@@ -1013,6 +1391,20 @@ impl DescriptorWrite {
         }
     }
 
+    /// Builds a write of an acceleration structure, for shaders that perform ray queries or trace
+    /// rays.
+    #[inline]
+    pub fn acceleration_structure(binding: u32, array_element: u32,
+                                  structure: &AccelerationStructure)
+                                  -> DescriptorWrite {
+        DescriptorWrite {
+            binding: binding,
+            first_array_element: array_element,
+            inner: smallvec!(DescriptorWriteInner::AccelerationStructure(structure
+                                                                            .internal_object())),
+        }
+    }
+
     /// Returns the type corresponding to this write.
     #[inline]
     pub fn ty(&self) -> DescriptorType {
@@ -1031,20 +1423,37 @@ impl DescriptorWrite {
             DescriptorWriteInner::DynamicStorageBuffer(_, _, _) =>
                 DescriptorType::StorageBufferDynamic,
             DescriptorWriteInner::InputAttachment(_, _) => DescriptorType::InputAttachment,
+            DescriptorWriteInner::AccelerationStructure(_) =>
+                DescriptorType::AccelerationStructure,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use Error;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
     use descriptor::descriptor::DescriptorBufferDesc;
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorCopy;
+    use descriptor::descriptor_set::DescriptorPoolCreationError;
+    use descriptor::descriptor_set::DescriptorWrite;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorPool;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
     use std::iter;
+    use vk;
+
+    #[test]
+    fn pool_creation_errors() {
+        assert_eq!(DescriptorPoolCreationError::from(Error::Fragmentation),
+                   DescriptorPoolCreationError::Fragmentation);
+        assert_eq!(DescriptorPoolCreationError::from(Error::InitializationFailed),
+                   DescriptorPoolCreationError::UnexpectedResult(vk::ERROR_INITIALIZATION_FAILED));
+    }
 
     #[test]
     fn pool_create() {
@@ -1057,6 +1466,21 @@ mod tests {
         let _ = UnsafeDescriptorPool::new(device, &desc, 10, false).unwrap();
     }
 
+    #[test]
+    fn pool_from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let desc = DescriptorsCount {
+            uniform_buffer: 1,
+            ..DescriptorsCount::zero()
+        };
+
+        let pool = UnsafeDescriptorPool::new(device.clone(), &desc, 10, false).unwrap();
+        let borrowed = unsafe { UnsafeDescriptorPool::from_raw(device, pool.pool, false, false) };
+        assert_eq!(borrowed.pool, pool.pool);
+        drop(borrowed);
+        drop(pool);
+    }
+
     #[test]
     fn zero_max_set() {
         let (device, _) = gfx_dev_and_queue!();
@@ -1089,6 +1513,7 @@ mod tests {
                                              storage: false,
                                          }),
             array_count: 1,
+            runtime_array: false,
             stages: ShaderStages::all_graphics(),
             readonly: true,
         };
@@ -1119,6 +1544,7 @@ mod tests {
                                              storage: false,
                                          }),
             array_count: 1,
+            runtime_array: false,
             stages: ShaderStages::all_graphics(),
             readonly: true,
         };
@@ -1157,4 +1583,45 @@ mod tests {
             assert_eq!(sets.count(), 0);
         }
     }
+
+    #[test]
+    fn copy_between_sets() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let set_layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout)))
+            .unwrap();
+
+        let desc = DescriptorsCount {
+            uniform_buffer: 10,
+            ..DescriptorsCount::zero()
+        };
+
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32)
+            .unwrap();
+
+        let mut pool = UnsafeDescriptorPool::new(device.clone(), &desc, 10, false).unwrap();
+        unsafe {
+            let mut sets = pool.alloc(vec![&set_layout, &set_layout].into_iter()).unwrap();
+            let mut source = sets.next().unwrap();
+            let mut destination = sets.next().unwrap();
+
+            source.write(&device, iter::once(DescriptorWrite::uniform_buffer(0, 0, &buffer)));
+            let copy = DescriptorCopy::new(&source, 0, 0, 0, 0, 1);
+            assert_eq!(copy.source_binding(), 0);
+            assert_eq!(copy.destination_binding(), 0);
+            assert_eq!(copy.count(), 1);
+            destination.copy(&device, iter::once(copy));
+        }
+    }
 }
@@ -8,7 +8,9 @@
 // according to those terms.
 
 use smallvec::SmallVec;
+use std::error;
 use std::fmt;
+use std::iter;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
@@ -18,10 +20,19 @@ use VulkanObject;
 use check_errors;
 use vk;
 
+use descriptor::descriptor::DescriptorBufferDesc;
 use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor::ShaderStages;
 use descriptor::descriptor_set::DescriptorsCount;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
+use descriptor::pipeline_layout::PipelineLayoutLimitsError;
+use descriptor::pipeline_layout::RuntimePipelineDesc;
 use device::Device;
 use device::DeviceOwned;
+use instance::PhysicalDevice;
+use sampler::Sampler;
 
 /// Describes to the Vulkan implementation the layout of all descriptors within a descriptor set.
 ///
@@ -35,6 +46,16 @@ pub struct UnsafeDescriptorSetLayout {
     device: Arc<Device>,
     // Number of descriptors.
     descriptors_count: DescriptorsCount,
+    // Description of each binding of the layout.
+    descriptors: SmallVec<[Option<DescriptorDesc>; 32]>,
+    // Immutable samplers of each binding of the layout. Kept alive for as long as the layout.
+    immutable_samplers: SmallVec<[SmallVec<[Arc<Sampler>; 1]>; 32]>,
+    // True if the layout was created for push descriptors.
+    push_descriptor: bool,
+    // Flags of each binding of the layout.
+    binding_flags: SmallVec<[DescriptorBindingFlags; 32]>,
+    // False if the layout was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl UnsafeDescriptorSetLayout {
@@ -43,18 +64,278 @@ impl UnsafeDescriptorSetLayout {
     /// The descriptors must be passed in the order of the bindings. In order words, descriptor
     /// at bind point 0 first, then descriptor at bind point 1, and so on. If a binding must remain
     /// empty, you can make the iterator yield `None` for an element.
+    ///
+    /// Returns an error if the descriptors exceed the limits of the device, for example if there
+    /// are more samplers accessible from a single stage than `max_per_stage_descriptor_samplers`.
+    #[inline]
     pub fn new<I>(device: Arc<Device>, descriptors: I)
-                  -> Result<UnsafeDescriptorSetLayout, OomError>
+                  -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutCreationError>
         where I: IntoIterator<Item = Option<DescriptorDesc>>
     {
+        let descriptors = descriptors.into_iter().map(|desc| (desc, iter::empty::<Arc<Sampler>>()));
+        UnsafeDescriptorSetLayout::with_immutable_samplers(device, descriptors)
+    }
+
+    /// Same as `new`, but each descriptor is accompanied with a list of immutable samplers.
+    ///
+    /// Immutable samplers are embedded in the layout itself. Sampler descriptors that have
+    /// immutable samplers must not be written to, and the sampler of a combined image sampler
+    /// descriptor that has immutable samplers is ignored when writing to it.
+    ///
+    /// The list of immutable samplers of a descriptor must either be empty, or contain exactly
+    /// one sampler for each element of the descriptor's array. Only sampler descriptors and
+    /// combined image sampler descriptors can have immutable samplers, and samplers that use a
+    /// YCbCr conversion can only be immutable samplers of combined image sampler descriptors.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the samplers was not created with `device`.
+    ///
+    #[inline]
+    pub fn with_immutable_samplers<I, S>(device: Arc<Device>, descriptors: I)
+        -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = (Option<DescriptorDesc>, S)>,
+              S: IntoIterator<Item = Arc<Sampler>>
+    {
+        let descriptors = descriptors
+            .into_iter()
+            .map(|(desc, samplers)| (desc, samplers, DescriptorBindingFlags::none()));
+        UnsafeDescriptorSetLayout::create(device, descriptors, false)
+    }
+
+    /// Same as `with_immutable_samplers`, but each descriptor is also accompanied with flags that
+    /// describe how the binding can be used.
+    ///
+    /// Setting any flag requires the `ext_descriptor_indexing` extension to be enabled on the
+    /// device, and the corresponding feature in `Device::descriptor_indexing_features` to be
+    /// supported. Only the last binding of the layout can have a variable descriptor count, and
+    /// dynamic buffer descriptors can neither be updated after bind nor have a variable count.
+    ///
+    /// If any binding is updated after bind, descriptor sets with this layout must be allocated
+    /// from a pool created with `UnsafeDescriptorPool::update_after_bind`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the samplers was not created with `device`.
+    ///
+    #[inline]
+    pub fn with_binding_flags<I, S>(device: Arc<Device>, descriptors: I)
+                                    -> Result<UnsafeDescriptorSetLayout,
+                                              DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = (Option<DescriptorDesc>, S, DescriptorBindingFlags)>,
+              S: IntoIterator<Item = Arc<Sampler>>
+    {
+        UnsafeDescriptorSetLayout::create(device, descriptors, false)
+    }
+
+    /// Builds a new `UnsafeDescriptorSetLayout` whose descriptors are meant to be pushed directly
+    /// into a command buffer, instead of being written in a descriptor set.
+    ///
+    /// No descriptor set can be allocated from such a layout. Use the `push_descriptor_set`
+    /// method of `AutoCommandBufferBuilder` instead.
+    ///
+    /// Requires the `khr_push_descriptor` extension to be enabled on the device. Dynamic buffer
+    /// descriptors are not allowed in a push descriptor layout.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the samplers was not created with `device`.
+    ///
+    #[inline]
+    pub fn push_descriptor<I, S>(device: Arc<Device>, descriptors: I)
+                                 -> Result<UnsafeDescriptorSetLayout,
+                                           DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = (Option<DescriptorDesc>, S)>,
+              S: IntoIterator<Item = Arc<Sampler>>
+    {
+        let descriptors = descriptors
+            .into_iter()
+            .map(|(desc, samplers)| (desc, samplers, DescriptorBindingFlags::none()));
+        UnsafeDescriptorSetLayout::create(device, descriptors, true)
+    }
+
+    fn create<I, S>(device: Arc<Device>, descriptors: I, push_descriptor: bool)
+                    -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = (Option<DescriptorDesc>, S, DescriptorBindingFlags)>,
+              S: IntoIterator<Item = Arc<Sampler>>
+    {
+        if push_descriptor && !device.loaded_extensions().khr_push_descriptor {
+            return Err(DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled);
+        }
+
+        let mut immutable_samplers: SmallVec<[SmallVec<[Arc<Sampler>; 1]>; 32]> =
+            SmallVec::new();
+        let mut binding_flags: SmallVec<[DescriptorBindingFlags; 32]> = SmallVec::new();
+        let descriptors = descriptors
+            .into_iter()
+            .map(|(desc, samplers, flags)| {
+                     immutable_samplers.push(samplers.into_iter().collect());
+                     binding_flags.push(flags);
+                     desc
+                 })
+            .collect::<SmallVec<[Option<DescriptorDesc>; 32]>>();
+
+        let last_binding = descriptors.iter().rposition(|d| d.is_some());
+
+        for (binding, (desc, flags)) in descriptors.iter().zip(binding_flags.iter()).enumerate() {
+            let desc = match *desc {
+                Some(ref desc) => desc,
+                None => continue,
+            };
+
+            if *flags == DescriptorBindingFlags::none() {
+                continue;
+            }
+
+            if !device.loaded_extensions().ext_descriptor_indexing {
+                return Err(DescriptorSetLayoutCreationError::BindingFlagsExtensionNotEnabled);
+            }
+
+            if flags.variable_descriptor_count && Some(binding) != last_binding {
+                return Err(DescriptorSetLayoutCreationError::VariableDescriptorCountNotLast {
+                               binding: binding as u32,
+                           });
+            }
+
+            match desc.ty {
+                DescriptorDescTy::Buffer(DescriptorBufferDesc { dynamic: Some(true), .. })
+                    if flags.update_after_bind || flags.variable_descriptor_count => {
+                    return Err(DescriptorSetLayoutCreationError::BindingFlagsDynamicBuffer {
+                                   binding: binding as u32,
+                               });
+                },
+                _ => (),
+            }
+
+            let features = device.descriptor_indexing_features();
+            if let Some(feature) = missing_binding_flags_feature(&desc.ty, flags, features) {
+                return Err(DescriptorSetLayoutCreationError::BindingFlagsFeatureNotEnabled {
+                               binding: binding as u32,
+                               feature: feature,
+                           });
+            }
+        }
+
+        for (binding, (desc, samplers)) in
+            descriptors.iter().zip(immutable_samplers.iter()).enumerate()
+        {
+            // Runtime-sized arrays of descriptors require a feature of `ext_descriptor_indexing`.
+            match *desc {
+                Some(DescriptorDesc { runtime_array: true, .. })
+                    if !device.descriptor_indexing_features().runtime_descriptor_array => {
+                    return Err(DescriptorSetLayoutCreationError::RuntimeArrayFeatureNotEnabled {
+                                   binding: binding as u32,
+                               });
+                },
+                _ => (),
+            }
+
+            // Acceleration structures require the extension that introduces them.
+            match *desc {
+                Some(DescriptorDesc { ty: DescriptorDescTy::AccelerationStructure, .. })
+                    if !device.loaded_extensions().khr_acceleration_structure => {
+                    let binding = binding as u32;
+                    return Err(DescriptorSetLayoutCreationError::
+                                   AccelerationStructureExtensionNotEnabled { binding: binding });
+                },
+                _ => (),
+            }
+
+            // Input attachments can only be read from fragment shaders.
+            match *desc {
+                Some(DescriptorDesc { ty: DescriptorDescTy::InputAttachment { .. }, stages, .. })
+                    if stages != ShaderStages::none() &&
+                        stages != (ShaderStages { fragment: true, ..ShaderStages::none() }) => {
+                    return Err(DescriptorSetLayoutCreationError::InputAttachmentWrongStages {
+                                   binding: binding as u32,
+                               });
+                },
+                _ => (),
+            }
+
+            if push_descriptor {
+                match *desc {
+                    Some(DescriptorDesc {
+                             ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                              dynamic: Some(true), ..
+                                                          }),
+                             ..
+                         }) => {
+                        return Err(DescriptorSetLayoutCreationError::PushDescriptorDynamicBuffer {
+                                       binding: binding as u32,
+                                   });
+                    },
+                    _ => (),
+                }
+            }
+
+            if samplers.is_empty() {
+                continue;
+            }
+
+            let wrong_ty = match *desc {
+                Some(DescriptorDesc { ty: DescriptorDescTy::Sampler, .. }) |
+                Some(DescriptorDesc { ty: DescriptorDescTy::CombinedImageSampler(_), .. }) => false,
+                _ => true,
+            };
+
+            if wrong_ty {
+                return Err(DescriptorSetLayoutCreationError::ImmutableSamplersWrongDescriptorTy {
+                               binding: binding as u32,
+                           });
+            }
+
+            let array_count = desc.as_ref().unwrap().array_count;
+            if samplers.len() != array_count as usize {
+                return Err(DescriptorSetLayoutCreationError::ImmutableSamplersCountMismatch {
+                               binding: binding as u32,
+                               expected: array_count,
+                               obtained: samplers.len() as u32,
+                           });
+            }
+
+            for sampler in samplers.iter() {
+                assert_eq!(sampler.device().internal_object(), device.internal_object());
+            }
+
+            // Samplers with a YCbCr conversion can only be used through combined image samplers.
+            let is_combined = match *desc {
+                Some(DescriptorDesc { ty: DescriptorDescTy::CombinedImageSampler(_), .. }) => true,
+                _ => false,
+            };
+
+            if !is_combined && samplers.iter().any(|s| s.ycbcr_conversion().is_some()) {
+                return Err(DescriptorSetLayoutCreationError::YcbcrSamplerWrongDescriptorTy {
+                               binding: binding as u32,
+                           });
+            }
+        }
+
+        // A layout that only contains this set must fulfill the limits, otherwise no pipeline
+        // layout that uses it can be created.
+        RuntimePipelineDesc::new(iter::once(descriptors.iter().cloned()), iter::empty())
+            .expect("Descriptor sets without push constants can't conflict")
+            .check_against_limits(&device)?;
+
         let mut descriptors_count = DescriptorsCount::zero();
 
+        // The raw handles of the immutable samplers must not move until the layout is created.
+        let raw_immutable_samplers = immutable_samplers
+            .iter()
+            .map(|samplers| {
+                     samplers
+                         .iter()
+                         .map(|s| s.internal_object())
+                         .collect::<SmallVec<[vk::Sampler; 1]>>()
+                 })
+            .collect::<SmallVec<[_; 32]>>();
+
         let bindings = descriptors
-            .into_iter()
+            .iter()
             .enumerate()
             .filter_map(|(binding, desc)| {
-                let desc = match desc {
-                    Some(d) => d,
+                let desc = match *desc {
+                    Some(ref d) => d,
                     None => return None,
                 };
 
@@ -69,18 +350,57 @@ impl UnsafeDescriptorSetLayout {
                          descriptorType: ty as u32,
                          descriptorCount: desc.array_count,
                          stageFlags: desc.stages.into_vulkan_bits(),
-                         pImmutableSamplers: ptr::null(), // FIXME: not yet implemented
+                         pImmutableSamplers: if raw_immutable_samplers[binding].is_empty() {
+                             ptr::null()
+                         } else {
+                             raw_immutable_samplers[binding].as_ptr()
+                         },
                      })
             })
             .collect::<SmallVec<[_; 32]>>();
 
+        // The flags must be passed in the same order as `bindings`.
+        let raw_binding_flags = descriptors
+            .iter()
+            .zip(binding_flags.iter())
+            .filter(|&(desc, _)| desc.is_some())
+            .map(|(_, flags)| flags.into_vulkan_bits())
+            .collect::<SmallVec<[_; 32]>>();
+
+        let update_after_bind = binding_flags.iter().any(|f| f.update_after_bind);
+
         // Note that it seems legal to have no descriptor at all in the set.
 
         let layout = unsafe {
+            let binding_flags_infos = if binding_flags
+                .iter()
+                .any(|f| *f != DescriptorBindingFlags::none())
+            {
+                Some(vk::DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+                         sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT,
+                         pNext: ptr::null(),
+                         bindingCount: raw_binding_flags.len() as u32,
+                         pBindingFlags: raw_binding_flags.as_ptr(),
+                     })
+            } else {
+                None
+            };
+
+            let mut flags = 0;
+            if push_descriptor {
+                flags |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR;
+            }
+            if update_after_bind {
+                flags |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT;
+            }
+
             let infos = vk::DescriptorSetLayoutCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
+                pNext: binding_flags_infos
+                    .as_ref()
+                    .map(|i| i as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags: flags,
                 bindingCount: bindings.len() as u32,
                 pBindings: bindings.as_ptr(),
             };
@@ -98,14 +418,544 @@ impl UnsafeDescriptorSetLayout {
                layout: layout,
                device: device,
                descriptors_count: descriptors_count,
+               descriptors: descriptors,
+               immutable_samplers: immutable_samplers,
+               push_descriptor: push_descriptor,
+               binding_flags: binding_flags,
+               needs_destruction: true,
            })
     }
 
+    /// Builds an `UnsafeDescriptorSetLayout` from a raw handle that was created outside of
+    /// vulkano.
+    ///
+    /// The descriptors must be passed in the order of the bindings, like with `new`.
+    /// If `owned` is true, the layout is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid descriptor set layout of `device`, created with exactly
+    ///   `descriptors`, without immutable samplers and without binding flags.
+    /// - `push_descriptor` must be true if and only if the layout was created with the push
+    ///   descriptor flag.
+    /// - If `owned` is true, the layout must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw<I>(device: Arc<Device>, handle: vk::DescriptorSetLayout,
+                              descriptors: I, push_descriptor: bool, owned: bool)
+                              -> UnsafeDescriptorSetLayout
+        where I: IntoIterator<Item = Option<DescriptorDesc>>
+    {
+        let descriptors = descriptors
+            .into_iter()
+            .collect::<SmallVec<[Option<DescriptorDesc>; 32]>>();
+
+        let mut descriptors_count = DescriptorsCount::zero();
+        for desc in descriptors.iter().filter_map(|d| d.as_ref()) {
+            descriptors_count.add_one(desc.ty.ty().unwrap());
+        }
+
+        UnsafeDescriptorSetLayout {
+            layout: handle,
+            device: device,
+            descriptors_count: descriptors_count,
+            immutable_samplers: descriptors.iter().map(|_| SmallVec::new()).collect(),
+            binding_flags: descriptors
+                .iter()
+                .map(|_| DescriptorBindingFlags::none())
+                .collect(),
+            descriptors: descriptors,
+            push_descriptor: push_descriptor,
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns the number of descriptors of each type.
     #[inline]
     pub fn descriptors_count(&self) -> &DescriptorsCount {
         &self.descriptors_count
     }
+
+    /// Returns the number of bindings of the layout. Includes possibly empty bindings.
+    #[inline]
+    pub fn num_bindings(&self) -> usize {
+        self.descriptors.len()
+    }
+
+    /// Returns the description of a binding of the layout.
+    ///
+    /// Returns `None` if out of range or if the binding is empty.
+    #[inline]
+    pub fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
+        self.descriptors.get(binding).and_then(|d| d.clone())
+    }
+
+    /// Returns the immutable samplers of a binding of the layout.
+    ///
+    /// Returns an empty list if out of range or if the binding doesn't have immutable samplers.
+    #[inline]
+    pub fn immutable_samplers(&self, binding: usize) -> &[Arc<Sampler>] {
+        match self.immutable_samplers.get(binding) {
+            Some(samplers) => samplers,
+            None => &[],
+        }
+    }
+
+    /// Returns true if the layout was created with `push_descriptor`.
+    #[inline]
+    pub fn is_push_descriptor(&self) -> bool {
+        self.push_descriptor
+    }
+
+    /// Returns the flags of a binding of the layout.
+    ///
+    /// Returns `DescriptorBindingFlags::none()` if out of range.
+    #[inline]
+    pub fn binding_flags(&self, binding: usize) -> DescriptorBindingFlags {
+        self.binding_flags
+            .get(binding)
+            .cloned()
+            .unwrap_or(DescriptorBindingFlags::none())
+    }
+
+    /// Returns true if one of the bindings of the layout can be updated after bind. Descriptor
+    /// sets with such a layout must be allocated from a pool created with
+    /// `UnsafeDescriptorPool::update_after_bind`.
+    #[inline]
+    pub fn is_update_after_bind(&self) -> bool {
+        self.binding_flags.iter().any(|f| f.update_after_bind)
+    }
+
+    /// Returns the binding that has a variable descriptor count, if any.
+    #[inline]
+    pub fn variable_descriptor_count_binding(&self) -> Option<usize> {
+        self.binding_flags
+            .iter()
+            .position(|f| f.variable_descriptor_count)
+    }
+}
+
+/// Flags that describe how a binding of an `UnsafeDescriptorSetLayout` can be used.
+///
+/// Setting any of these flags requires the `ext_descriptor_indexing` extension. Each flag also
+/// requires the corresponding feature of the extension to be supported by the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct DescriptorBindingFlags {
+    /// The descriptors of the binding can be written after the descriptor set has been bound in a
+    /// command buffer. The command buffer then uses the descriptors as they are at submission.
+    pub update_after_bind: bool,
+    /// The descriptors of the binding that aren't used by the GPU can be written while a command
+    /// buffer that binds the descriptor set is pending execution.
+    pub update_unused_while_pending: bool,
+    /// The descriptors of the binding that aren't used by the GPU don't need to be written.
+    pub partially_bound: bool,
+    /// The number of array elements of the binding is chosen when allocating the descriptor set,
+    /// and the `array_count` of the descriptor is only an upper bound. Only allowed for the last
+    /// binding of the layout.
+    pub variable_descriptor_count: bool,
+}
+
+impl DescriptorBindingFlags {
+    /// Builds a `DescriptorBindingFlags` with all the flags set to false.
+    #[inline]
+    pub fn none() -> DescriptorBindingFlags {
+        DescriptorBindingFlags {
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
+            variable_descriptor_count: false,
+        }
+    }
+
+    /// Builds a `DescriptorBindingFlags` suitable for a large array of textures that is indexed
+    /// dynamically in shaders, also known as "bindless" texturing.
+    #[inline]
+    pub fn bindless() -> DescriptorBindingFlags {
+        DescriptorBindingFlags {
+            update_after_bind: true,
+            update_unused_while_pending: true,
+            partially_bound: true,
+            variable_descriptor_count: true,
+        }
+    }
+
+    #[inline]
+    fn into_vulkan_bits(self) -> vk::DescriptorBindingFlagsEXT {
+        let mut result = 0;
+        if self.update_after_bind {
+            result |= vk::DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT;
+        }
+        if self.update_unused_while_pending {
+            result |= vk::DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT_EXT;
+        }
+        if self.partially_bound {
+            result |= vk::DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT;
+        }
+        if self.variable_descriptor_count {
+            result |= vk::DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT_EXT;
+        }
+        result
+    }
+}
+
+// Returns the name of the feature of `ext_descriptor_indexing` that `flags` require for a
+// descriptor of type `ty`, if this feature isn't enabled.
+fn missing_binding_flags_feature(ty: &DescriptorDescTy, flags: &DescriptorBindingFlags,
+                                 features: &DescriptorIndexingFeatures)
+                                 -> Option<&'static str> {
+    if flags.update_after_bind {
+        let (feature, enabled) = match ty.ty() {
+            Some(DescriptorType::UniformBuffer) => {
+                ("descriptor_binding_uniform_buffer_update_after_bind",
+                 features.descriptor_binding_uniform_buffer_update_after_bind)
+            },
+            Some(DescriptorType::StorageBuffer) => {
+                ("descriptor_binding_storage_buffer_update_after_bind",
+                 features.descriptor_binding_storage_buffer_update_after_bind)
+            },
+            Some(DescriptorType::Sampler) |
+            Some(DescriptorType::CombinedImageSampler) |
+            Some(DescriptorType::SampledImage) => {
+                ("descriptor_binding_sampled_image_update_after_bind",
+                 features.descriptor_binding_sampled_image_update_after_bind)
+            },
+            Some(DescriptorType::StorageImage) => {
+                ("descriptor_binding_storage_image_update_after_bind",
+                 features.descriptor_binding_storage_image_update_after_bind)
+            },
+            Some(DescriptorType::UniformTexelBuffer) => {
+                ("descriptor_binding_uniform_texel_buffer_update_after_bind",
+                 features.descriptor_binding_uniform_texel_buffer_update_after_bind)
+            },
+            Some(DescriptorType::StorageTexelBuffer) => {
+                ("descriptor_binding_storage_texel_buffer_update_after_bind",
+                 features.descriptor_binding_storage_texel_buffer_update_after_bind)
+            },
+            _ => ("", true),
+        };
+
+        if !enabled {
+            return Some(feature);
+        }
+    }
+
+    if flags.update_unused_while_pending &&
+        !features.descriptor_binding_update_unused_while_pending
+    {
+        return Some("descriptor_binding_update_unused_while_pending");
+    }
+
+    if flags.partially_bound && !features.descriptor_binding_partially_bound {
+        return Some("descriptor_binding_partially_bound");
+    }
+
+    if flags.variable_descriptor_count && !features.descriptor_binding_variable_descriptor_count {
+        return Some("descriptor_binding_variable_descriptor_count");
+    }
+
+    None
+}
+
+/// Limits of the physical device regarding push descriptors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PushDescriptorProperties {
+    /// Maximum number of descriptors that can be pushed in a single push descriptor set.
+    pub max_push_descriptors: u32,
+}
+
+impl PushDescriptorProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> PushDescriptorProperties {
+        unsafe {
+            let mut output: vk::PhysicalDevicePushDescriptorPropertiesKHR = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PUSH_DESCRIPTOR_PROPERTIES_KHR;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            PushDescriptorProperties { max_push_descriptors: output.maxPushDescriptors }
+        }
+    }
+}
+
+/// Features of the `ext_descriptor_indexing` extension.
+///
+/// The fields have the same names as in the Vulkan specs. When the extension is enabled on a
+/// device, all the features that the physical device supports are enabled as well.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[allow(missing_docs)]
+pub struct DescriptorIndexingFeatures {
+    pub shader_input_attachment_array_dynamic_indexing: bool,
+    pub shader_uniform_texel_buffer_array_dynamic_indexing: bool,
+    pub shader_storage_texel_buffer_array_dynamic_indexing: bool,
+    pub shader_uniform_buffer_array_non_uniform_indexing: bool,
+    pub shader_sampled_image_array_non_uniform_indexing: bool,
+    pub shader_storage_buffer_array_non_uniform_indexing: bool,
+    pub shader_storage_image_array_non_uniform_indexing: bool,
+    pub shader_input_attachment_array_non_uniform_indexing: bool,
+    pub shader_uniform_texel_buffer_array_non_uniform_indexing: bool,
+    pub shader_storage_texel_buffer_array_non_uniform_indexing: bool,
+    pub descriptor_binding_uniform_buffer_update_after_bind: bool,
+    pub descriptor_binding_sampled_image_update_after_bind: bool,
+    pub descriptor_binding_storage_image_update_after_bind: bool,
+    pub descriptor_binding_storage_buffer_update_after_bind: bool,
+    pub descriptor_binding_uniform_texel_buffer_update_after_bind: bool,
+    pub descriptor_binding_storage_texel_buffer_update_after_bind: bool,
+    pub descriptor_binding_update_unused_while_pending: bool,
+    pub descriptor_binding_partially_bound: bool,
+    pub descriptor_binding_variable_descriptor_count: bool,
+    pub runtime_descriptor_array: bool,
+}
+
+impl DescriptorIndexingFeatures {
+    /// Builds a `DescriptorIndexingFeatures` with all the features set to false.
+    #[inline]
+    pub fn none() -> DescriptorIndexingFeatures {
+        DescriptorIndexingFeatures {
+            shader_input_attachment_array_dynamic_indexing: false,
+            shader_uniform_texel_buffer_array_dynamic_indexing: false,
+            shader_storage_texel_buffer_array_dynamic_indexing: false,
+            shader_uniform_buffer_array_non_uniform_indexing: false,
+            shader_sampled_image_array_non_uniform_indexing: false,
+            shader_storage_buffer_array_non_uniform_indexing: false,
+            shader_storage_image_array_non_uniform_indexing: false,
+            shader_input_attachment_array_non_uniform_indexing: false,
+            shader_uniform_texel_buffer_array_non_uniform_indexing: false,
+            shader_storage_texel_buffer_array_non_uniform_indexing: false,
+            descriptor_binding_uniform_buffer_update_after_bind: false,
+            descriptor_binding_sampled_image_update_after_bind: false,
+            descriptor_binding_storage_image_update_after_bind: false,
+            descriptor_binding_storage_buffer_update_after_bind: false,
+            descriptor_binding_uniform_texel_buffer_update_after_bind: false,
+            descriptor_binding_storage_texel_buffer_update_after_bind: false,
+            descriptor_binding_update_unused_while_pending: false,
+            descriptor_binding_partially_bound: false,
+            descriptor_binding_variable_descriptor_count: false,
+            runtime_descriptor_array: false,
+        }
+    }
+
+    /// Queries the features supported by a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> DescriptorIndexingFeatures {
+        unsafe {
+            let mut output: vk::PhysicalDeviceDescriptorIndexingFeaturesEXT = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT;
+            physical_device.query_features2(&mut output as *mut _ as *mut _);
+
+            DescriptorIndexingFeatures {
+                shader_input_attachment_array_dynamic_indexing:
+                    output.shaderInputAttachmentArrayDynamicIndexing != 0,
+                shader_uniform_texel_buffer_array_dynamic_indexing:
+                    output.shaderUniformTexelBufferArrayDynamicIndexing != 0,
+                shader_storage_texel_buffer_array_dynamic_indexing:
+                    output.shaderStorageTexelBufferArrayDynamicIndexing != 0,
+                shader_uniform_buffer_array_non_uniform_indexing:
+                    output.shaderUniformBufferArrayNonUniformIndexing != 0,
+                shader_sampled_image_array_non_uniform_indexing:
+                    output.shaderSampledImageArrayNonUniformIndexing != 0,
+                shader_storage_buffer_array_non_uniform_indexing:
+                    output.shaderStorageBufferArrayNonUniformIndexing != 0,
+                shader_storage_image_array_non_uniform_indexing:
+                    output.shaderStorageImageArrayNonUniformIndexing != 0,
+                shader_input_attachment_array_non_uniform_indexing:
+                    output.shaderInputAttachmentArrayNonUniformIndexing != 0,
+                shader_uniform_texel_buffer_array_non_uniform_indexing:
+                    output.shaderUniformTexelBufferArrayNonUniformIndexing != 0,
+                shader_storage_texel_buffer_array_non_uniform_indexing:
+                    output.shaderStorageTexelBufferArrayNonUniformIndexing != 0,
+                descriptor_binding_uniform_buffer_update_after_bind:
+                    output.descriptorBindingUniformBufferUpdateAfterBind != 0,
+                descriptor_binding_sampled_image_update_after_bind:
+                    output.descriptorBindingSampledImageUpdateAfterBind != 0,
+                descriptor_binding_storage_image_update_after_bind:
+                    output.descriptorBindingStorageImageUpdateAfterBind != 0,
+                descriptor_binding_storage_buffer_update_after_bind:
+                    output.descriptorBindingStorageBufferUpdateAfterBind != 0,
+                descriptor_binding_uniform_texel_buffer_update_after_bind:
+                    output.descriptorBindingUniformTexelBufferUpdateAfterBind != 0,
+                descriptor_binding_storage_texel_buffer_update_after_bind:
+                    output.descriptorBindingStorageTexelBufferUpdateAfterBind != 0,
+                descriptor_binding_update_unused_while_pending:
+                    output.descriptorBindingUpdateUnusedWhilePending != 0,
+                descriptor_binding_partially_bound:
+                    output.descriptorBindingPartiallyBound != 0,
+                descriptor_binding_variable_descriptor_count:
+                    output.descriptorBindingVariableDescriptorCount != 0,
+                runtime_descriptor_array:
+                    output.runtimeDescriptorArray != 0,
+            }
+        }
+    }
+}
+
+/// Error that can happen when creating an `UnsafeDescriptorSetLayout`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorSetLayoutCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The descriptors of the layout don't fulfill the limit requirements of the device.
+    LimitsError(PipelineLayoutLimitsError),
+    /// Immutable samplers were provided for a binding that is neither a sampler nor a combined
+    /// image sampler.
+    ImmutableSamplersWrongDescriptorTy {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// The number of immutable samplers of a binding doesn't match its number of array elements.
+    ImmutableSamplersCountMismatch {
+        /// The binding of the descriptor.
+        binding: u32,
+        /// Number of array elements of the descriptor.
+        expected: u32,
+        /// Number of immutable samplers that were provided.
+        obtained: u32,
+    },
+    /// A push descriptor layout was requested, but the `khr_push_descriptor` extension is not
+    /// enabled on the device.
+    PushDescriptorExtensionNotEnabled,
+    /// A push descriptor layout contains a dynamic buffer descriptor.
+    PushDescriptorDynamicBuffer {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// Binding flags were provided, but the `ext_descriptor_indexing` extension is not enabled
+    /// on the device.
+    BindingFlagsExtensionNotEnabled,
+    /// A binding that isn't the last binding of the layout has a variable descriptor count.
+    VariableDescriptorCountNotLast {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// A dynamic buffer descriptor is updated after bind or has a variable descriptor count.
+    BindingFlagsDynamicBuffer {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// A binding has a flag that requires a feature of the `ext_descriptor_indexing` extension,
+    /// but this feature isn't supported by the device.
+    BindingFlagsFeatureNotEnabled {
+        /// The binding of the descriptor.
+        binding: u32,
+        /// The name of the feature in `DescriptorIndexingFeatures`.
+        feature: &'static str,
+    },
+    /// A descriptor is a runtime-sized array, but the `runtime_descriptor_array` feature of the
+    /// `ext_descriptor_indexing` extension is not enabled on the device.
+    RuntimeArrayFeatureNotEnabled {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// An input attachment descriptor is accessible from a shader stage other than the fragment
+    /// shader.
+    InputAttachmentWrongStages {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// An immutable sampler that uses a YCbCr conversion was provided for a binding that isn't a
+    /// combined image sampler.
+    YcbcrSamplerWrongDescriptorTy {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// The layout contains an acceleration structure descriptor, but the
+    /// `khr_acceleration_structure` extension is not enabled on the device.
+    AccelerationStructureExtensionNotEnabled {
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for DescriptorSetLayoutCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorSetLayoutCreationError::OomError(_) => {
+                "not enough memory available"
+            },
+            DescriptorSetLayoutCreationError::LimitsError(_) => {
+                "the descriptors of the layout don't fulfill the limit requirements of the device"
+            },
+            DescriptorSetLayoutCreationError::ImmutableSamplersWrongDescriptorTy { .. } => {
+                "immutable samplers were provided for a binding that is neither a sampler nor a \
+                 combined image sampler"
+            },
+            DescriptorSetLayoutCreationError::ImmutableSamplersCountMismatch { .. } => {
+                "the number of immutable samplers of a binding doesn't match its number of array \
+                 elements"
+            },
+            DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled => {
+                "a push descriptor layout was requested, but the `khr_push_descriptor` extension \
+                 is not enabled"
+            },
+            DescriptorSetLayoutCreationError::PushDescriptorDynamicBuffer { .. } => {
+                "a push descriptor layout contains a dynamic buffer descriptor"
+            },
+            DescriptorSetLayoutCreationError::BindingFlagsExtensionNotEnabled => {
+                "binding flags were provided, but the `ext_descriptor_indexing` extension is not \
+                 enabled"
+            },
+            DescriptorSetLayoutCreationError::VariableDescriptorCountNotLast { .. } => {
+                "a binding that isn't the last binding of the layout has a variable descriptor \
+                 count"
+            },
+            DescriptorSetLayoutCreationError::BindingFlagsDynamicBuffer { .. } => {
+                "a dynamic buffer descriptor is updated after bind or has a variable descriptor \
+                 count"
+            },
+            DescriptorSetLayoutCreationError::BindingFlagsFeatureNotEnabled { .. } => {
+                "a binding has a flag that requires a feature of the `ext_descriptor_indexing` \
+                 extension that isn't supported"
+            },
+            DescriptorSetLayoutCreationError::RuntimeArrayFeatureNotEnabled { .. } => {
+                "a descriptor is a runtime-sized array, but the `runtime_descriptor_array` \
+                 feature is not enabled"
+            },
+            DescriptorSetLayoutCreationError::InputAttachmentWrongStages { .. } => {
+                "an input attachment descriptor is accessible from a shader stage other than the \
+                 fragment shader"
+            },
+            DescriptorSetLayoutCreationError::YcbcrSamplerWrongDescriptorTy { .. } => {
+                "an immutable sampler that uses a YCbCr conversion was provided for a binding \
+                 that isn't a combined image sampler"
+            },
+            DescriptorSetLayoutCreationError::AccelerationStructureExtensionNotEnabled { .. } => {
+                "the layout contains an acceleration structure descriptor, but the \
+                 `khr_acceleration_structure` extension is not enabled"
+            },
+            DescriptorSetLayoutCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DescriptorSetLayoutCreationError::OomError(ref err) => Some(err),
+            DescriptorSetLayoutCreationError::LimitsError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(DescriptorSetLayoutCreationError, OomError {});
+
+impl From<PipelineLayoutLimitsError> for DescriptorSetLayoutCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutLimitsError) -> DescriptorSetLayoutCreationError {
+        DescriptorSetLayoutCreationError::LimitsError(err)
+    }
 }
 
 unsafe impl DeviceOwned for UnsafeDescriptorSetLayout {
@@ -138,6 +988,10 @@ unsafe impl VulkanObject for UnsafeDescriptorSetLayout {
 impl Drop for UnsafeDescriptorSetLayout {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyDescriptorSetLayout(self.device.internal_object(), self.layout, ptr::null());
@@ -147,13 +1001,21 @@ impl Drop for UnsafeDescriptorSetLayout {
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
     use descriptor::descriptor::DescriptorBufferDesc;
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorImageDescArray;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorBindingFlags;
+    use descriptor::descriptor_set::DescriptorIndexingFeatures;
+    use descriptor::descriptor_set::DescriptorSetLayoutCreationError;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use sampler::Sampler;
     use std::iter;
+    use std::sync::Arc;
+    use super::missing_binding_flags_feature;
 
     #[test]
     fn empty() {
@@ -171,6 +1033,7 @@ mod tests {
                                              storage: false,
                                          }),
             array_count: 1,
+            runtime_array: false,
             stages: ShaderStages::all_graphics(),
             readonly: true,
         };
@@ -182,5 +1045,218 @@ mod tests {
                        uniform_buffer: 1,
                        ..DescriptorsCount::zero()
                    });
+        assert_eq!(sl.num_bindings(), 1);
+        assert!(sl.descriptor(0).is_some());
+        assert!(sl.descriptor(1).is_none());
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let sl = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout.clone())))
+            .unwrap();
+
+        let borrowed = unsafe {
+            UnsafeDescriptorSetLayout::from_raw(device, sl.internal_object(),
+                                                iter::once(Some(layout)), false, false)
+        };
+        assert_eq!(borrowed.internal_object(), sl.internal_object());
+        assert_eq!(borrowed.descriptors_count(), sl.descriptors_count());
+        assert_eq!(borrowed.num_bindings(), 1);
+        assert!(!borrowed.is_push_descriptor());
+    }
+
+    #[test]
+    fn samplers_limit_exceeded() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let max = device
+            .physical_device()
+            .limits()
+            .max_per_stage_descriptor_samplers();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: max + 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))) {
+            Err(DescriptorSetLayoutCreationError::LimitsError(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn immutable_samplers() {
+        let (device, _) = gfx_dev_and_queue!();
+        let sampler = Sampler::simple_repeat_linear(device.clone());
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout =
+            UnsafeDescriptorSetLayout::with_immutable_samplers(device,
+                                                               iter::once((Some(desc),
+                                                                           vec![sampler])))
+                .unwrap();
+
+        assert_eq!(layout.immutable_samplers(0).len(), 1);
+        assert!(layout.immutable_samplers(1).is_empty());
+    }
+
+    #[test]
+    fn immutable_samplers_wrong_ty() {
+        let (device, _) = gfx_dev_and_queue!();
+        let sampler = Sampler::simple_repeat_linear(device.clone());
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::with_immutable_samplers(device,
+                                                                 iter::once((Some(desc),
+                                                                             vec![sampler]))) {
+            Err(DescriptorSetLayoutCreationError::ImmutableSamplersWrongDescriptorTy {
+                    binding: 0,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn binding_flags_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 16,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let descriptors = iter::once((Some(desc), Vec::<Arc<Sampler>>::new(),
+                                      DescriptorBindingFlags::bindless()));
+        match UnsafeDescriptorSetLayout::with_binding_flags(device, descriptors) {
+            Err(DescriptorSetLayoutCreationError::BindingFlagsExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn input_attachment_wrong_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::InputAttachment {
+                multisampled: false,
+                array_layers: DescriptorImageDescArray::NonArrayed,
+            },
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))) {
+            Err(DescriptorSetLayoutCreationError::InputAttachmentWrongStages { binding: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn runtime_array_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            runtime_array: true,
+            stages: ShaderStages::compute(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))) {
+            Err(DescriptorSetLayoutCreationError::RuntimeArrayFeatureNotEnabled { binding: 0 }) => {
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn binding_flags_features() {
+        let features = DescriptorIndexingFeatures {
+            descriptor_binding_partially_bound: true,
+            ..DescriptorIndexingFeatures::none()
+        };
+
+        let flags = DescriptorBindingFlags {
+            partially_bound: true,
+            ..DescriptorBindingFlags::none()
+        };
+        assert_eq!(missing_binding_flags_feature(&DescriptorDescTy::Sampler, &flags, &features),
+                   None);
+
+        let flags = DescriptorBindingFlags::bindless();
+        assert_eq!(missing_binding_flags_feature(&DescriptorDescTy::Sampler, &flags, &features),
+                   Some("descriptor_binding_sampled_image_update_after_bind"));
+    }
+
+    #[test]
+    fn acceleration_structure_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::AccelerationStructure,
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::compute(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))) {
+            Err(DescriptorSetLayoutCreationError::AccelerationStructureExtensionNotEnabled {
+                    binding: 0,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn push_descriptor_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match UnsafeDescriptorSetLayout::push_descriptor(device,
+                                                         iter::empty::<(_, Vec<Arc<Sampler>>)>()) {
+            Err(DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
     }
 }
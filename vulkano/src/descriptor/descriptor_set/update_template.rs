@@ -0,0 +1,492 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use buffer::BufferInner;
+use buffer::BufferView;
+use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageViewAccess;
+use sampler::Sampler;
+
+use OomError;
+use VulkanObject;
+use check_errors;
+use vk;
+
+/// Describes to the Vulkan implementation where the descriptors of a descriptor set are located
+/// in memory.
+///
+/// Once a template has been created, a descriptor set can be updated with
+/// `UnsafeDescriptorSet::update_with_template` by passing a reference to a flat struct whose
+/// fields contain the descriptors. This is faster than building a list of `DescriptorWrite`s
+/// each time.
+///
+/// The fields of the struct must be `DescriptorImageInfo`, `DescriptorBufferInfo` or
+/// `DescriptorBufferViewInfo` objects depending on the type of the descriptor. Each field is
+/// referred to by its offset with a `DescriptorUpdateTemplateEntry`.
+///
+/// Requires the `khr_descriptor_update_template` extension to be enabled on the device.
+pub struct UnsafeDescriptorUpdateTemplate {
+    template: vk::DescriptorUpdateTemplateKHR,
+    device: Arc<Device>,
+    layout: Arc<UnsafeDescriptorSetLayout>,
+    // False if the template was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+impl UnsafeDescriptorUpdateTemplate {
+    /// Builds a new template that can be used to update descriptor sets created with `layout`.
+    ///
+    /// Each entry describes a range of array elements of a binding of the layout, and the
+    /// location of their descriptors in the data that is passed when updating.
+    ///
+    /// The descriptor type of each entry must match the type of its binding in the layout, and
+    /// the range of array elements of each entry must be within the array of its binding.
+    pub fn new<I>(layout: Arc<UnsafeDescriptorSetLayout>, entries: I)
+                  -> Result<UnsafeDescriptorUpdateTemplate, DescriptorUpdateTemplateCreationError>
+        where I: IntoIterator<Item = DescriptorUpdateTemplateEntry>
+    {
+        let device = layout.device().clone();
+
+        if !device.loaded_extensions().khr_descriptor_update_template {
+            return Err(DescriptorUpdateTemplateCreationError::ExtensionNotEnabled);
+        }
+
+        let entries = entries
+            .into_iter()
+            .collect::<SmallVec<[DescriptorUpdateTemplateEntry; 16]>>();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let desc = match layout.descriptor(entry.binding as usize) {
+                Some(desc) => desc,
+                None => {
+                    return Err(DescriptorUpdateTemplateCreationError::EmptyBinding {
+                                   entry: index,
+                               });
+                },
+            };
+
+            if desc.ty.ty() != Some(entry.ty) {
+                return Err(DescriptorUpdateTemplateCreationError::WrongDescriptorTy {
+                               entry: index,
+                           });
+            }
+
+            let end = entry.first_array_element.checked_add(entry.count);
+            if entry.count == 0 || end.map(|end| end > desc.array_count).unwrap_or(true) {
+                return Err(DescriptorUpdateTemplateCreationError::ArrayOutOfRange {
+                               entry: index,
+                           });
+            }
+        }
+
+        let raw_entries = entries
+            .iter()
+            .map(|entry| {
+                     vk::DescriptorUpdateTemplateEntryKHR {
+                         dstBinding: entry.binding,
+                         dstArrayElement: entry.first_array_element,
+                         descriptorCount: entry.count,
+                         descriptorType: entry.ty as u32,
+                         offset: entry.offset,
+                         stride: entry.stride,
+                     }
+                 })
+            .collect::<SmallVec<[_; 16]>>();
+
+        let template = unsafe {
+            let infos = vk::DescriptorUpdateTemplateCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+                descriptorUpdateEntryCount: raw_entries.len() as u32,
+                pDescriptorUpdateEntries: raw_entries.as_ptr(),
+                templateType: vk::DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR,
+                descriptorSetLayout: layout.internal_object(),
+                // The following fields are ignored for descriptor set templates.
+                pipelineBindPoint: 0,
+                pipelineLayout: 0,
+                set: 0,
+            };
+
+            let mut output = mem::uninitialized();
+            let vk = device.pointers();
+            check_errors(vk.CreateDescriptorUpdateTemplateKHR(device.internal_object(),
+                                                              &infos,
+                                                              ptr::null(),
+                                                              &mut output))?;
+            output
+        };
+
+        Ok(UnsafeDescriptorUpdateTemplate {
+               template: template,
+               device: device,
+               layout: layout,
+               needs_destruction: true,
+           })
+    }
+
+    /// Builds an `UnsafeDescriptorUpdateTemplate` from a raw handle that was created outside of
+    /// vulkano.
+    ///
+    /// If `owned` is true, the template is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid descriptor update template of the device of `layout`, created
+    ///   for updating descriptor sets with `layout`.
+    /// - If `owned` is true, the template must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(layout: Arc<UnsafeDescriptorSetLayout>,
+                           handle: vk::DescriptorUpdateTemplateKHR, owned: bool)
+                           -> UnsafeDescriptorUpdateTemplate {
+        UnsafeDescriptorUpdateTemplate {
+            template: handle,
+            device: layout.device().clone(),
+            layout: layout,
+            needs_destruction: owned,
+        }
+    }
+
+    /// Returns the layout of the descriptor sets that this template can update.
+    #[inline]
+    pub fn layout(&self) -> &Arc<UnsafeDescriptorSetLayout> {
+        &self.layout
+    }
+}
+
+unsafe impl DeviceOwned for UnsafeDescriptorUpdateTemplate {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl fmt::Debug for UnsafeDescriptorUpdateTemplate {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan descriptor update template {:?}>", self.template)
+    }
+}
+
+unsafe impl VulkanObject for UnsafeDescriptorUpdateTemplate {
+    type Object = vk::DescriptorUpdateTemplateKHR;
+
+    const TYPE: vk::DebugReportObjectTypeEXT =
+        vk::DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::DescriptorUpdateTemplateKHR {
+        self.template
+    }
+}
+
+impl Drop for UnsafeDescriptorUpdateTemplate {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyDescriptorUpdateTemplateKHR(self.device.internal_object(),
+                                                  self.template,
+                                                  ptr::null());
+        }
+    }
+}
+
+/// Describes a range of descriptors of a binding, and where they are located in the data passed
+/// when updating a descriptor set with a template.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DescriptorUpdateTemplateEntry {
+    /// The binding to update.
+    pub binding: u32,
+    /// The first array element of the binding to update.
+    pub first_array_element: u32,
+    /// The number of array elements to update. Must not be 0.
+    pub count: u32,
+    /// The type of the descriptors. Must match the type of the binding in the layout.
+    pub ty: DescriptorType,
+    /// Offset in bytes of the first descriptor within the data.
+    pub offset: usize,
+    /// Stride in bytes between two consecutive descriptors within the data.
+    pub stride: usize,
+}
+
+/// Location of an image or of a sampler, in the data passed to a descriptor update template.
+///
+/// Used for sampler, combined image sampler, sampled image, storage image and input attachment
+/// descriptors.
+///
+/// Like `DescriptorWrite`, this object doesn't keep the resources alive.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct DescriptorImageInfo {
+    sampler: vk::Sampler,
+    image_view: vk::ImageView,
+    image_layout: vk::ImageLayout,
+}
+
+impl DescriptorImageInfo {
+    #[inline]
+    pub fn sampler(sampler: &Arc<Sampler>) -> DescriptorImageInfo {
+        DescriptorImageInfo {
+            sampler: sampler.internal_object(),
+            image_view: 0,
+            image_layout: 0,
+        }
+    }
+
+    #[inline]
+    pub fn combined_image_sampler<I>(sampler: &Arc<Sampler>, image: &I) -> DescriptorImageInfo
+        where I: ImageViewAccess
+    {
+        DescriptorImageInfo {
+            sampler: sampler.internal_object(),
+            image_view: image.inner().internal_object(),
+            image_layout: image.descriptor_set_combined_image_sampler_layout() as u32,
+        }
+    }
+
+    #[inline]
+    pub fn sampled_image<I>(image: &I) -> DescriptorImageInfo
+        where I: ImageViewAccess
+    {
+        DescriptorImageInfo {
+            sampler: 0,
+            image_view: image.inner().internal_object(),
+            image_layout: image.descriptor_set_sampled_image_layout() as u32,
+        }
+    }
+
+    #[inline]
+    pub fn storage_image<I>(image: &I) -> DescriptorImageInfo
+        where I: ImageViewAccess
+    {
+        DescriptorImageInfo {
+            sampler: 0,
+            image_view: image.inner().internal_object(),
+            image_layout: image.descriptor_set_storage_image_layout() as u32,
+        }
+    }
+
+    #[inline]
+    pub fn input_attachment<I>(image: &I) -> DescriptorImageInfo
+        where I: ImageViewAccess
+    {
+        DescriptorImageInfo {
+            sampler: 0,
+            image_view: image.inner().internal_object(),
+            image_layout: image.descriptor_set_input_attachment_layout() as u32,
+        }
+    }
+}
+
+/// Location of a buffer, in the data passed to a descriptor update template.
+///
+/// Used for uniform buffer and storage buffer descriptors, dynamic or not.
+///
+/// Like `DescriptorWrite`, this object doesn't keep the buffer alive.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct DescriptorBufferInfo {
+    buffer: vk::Buffer,
+    offset: vk::DeviceSize,
+    range: vk::DeviceSize,
+}
+
+impl DescriptorBufferInfo {
+    /// Builds a `DescriptorBufferInfo` that covers the whole of `buffer`.
+    ///
+    /// # Safety
+    ///
+    /// Same as `DescriptorWrite::uniform_buffer` and `DescriptorWrite::storage_buffer`. The offset
+    /// and the size of the buffer must fulfill the alignment and range limits of the type of the
+    /// descriptor.
+    #[inline]
+    pub unsafe fn new<B>(buffer: &B) -> DescriptorBufferInfo
+        where B: BufferAccess
+    {
+        let size = buffer.size();
+        let BufferInner { buffer, offset } = buffer.inner();
+
+        DescriptorBufferInfo {
+            buffer: buffer.internal_object(),
+            offset: offset as vk::DeviceSize,
+            range: size as vk::DeviceSize,
+        }
+    }
+}
+
+/// Location of a buffer view, in the data passed to a descriptor update template.
+///
+/// Used for uniform texel buffer and storage texel buffer descriptors.
+///
+/// Like `DescriptorWrite`, this object doesn't keep the buffer view alive.
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct DescriptorBufferViewInfo {
+    view: vk::BufferView,
+}
+
+impl DescriptorBufferViewInfo {
+    #[inline]
+    pub fn new<F, B>(view: &BufferView<F, B>) -> DescriptorBufferViewInfo
+        where B: BufferAccess
+    {
+        DescriptorBufferViewInfo { view: view.internal_object() }
+    }
+}
+
+/// Error that can happen when creating an `UnsafeDescriptorUpdateTemplate`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorUpdateTemplateCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `khr_descriptor_update_template` extension is not enabled on the device.
+    ExtensionNotEnabled,
+    /// The binding of an entry is empty or out of range in the layout.
+    EmptyBinding {
+        /// Index of the entry.
+        entry: usize,
+    },
+    /// The descriptor type of an entry doesn't match the type of its binding in the layout.
+    WrongDescriptorTy {
+        /// Index of the entry.
+        entry: usize,
+    },
+    /// The range of array elements of an entry is empty or is out of the array of its binding.
+    ArrayOutOfRange {
+        /// Index of the entry.
+        entry: usize,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for DescriptorUpdateTemplateCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorUpdateTemplateCreationError::OomError(_) => {
+                "not enough memory available"
+            },
+            DescriptorUpdateTemplateCreationError::ExtensionNotEnabled => {
+                "the `khr_descriptor_update_template` extension is not enabled"
+            },
+            DescriptorUpdateTemplateCreationError::EmptyBinding { .. } => {
+                "the binding of an entry is empty or out of range in the layout"
+            },
+            DescriptorUpdateTemplateCreationError::WrongDescriptorTy { .. } => {
+                "the descriptor type of an entry doesn't match the type of its binding"
+            },
+            DescriptorUpdateTemplateCreationError::ArrayOutOfRange { .. } => {
+                "the range of array elements of an entry is empty or is out of the array of its \
+                 binding"
+            },
+            DescriptorUpdateTemplateCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DescriptorUpdateTemplateCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(DescriptorUpdateTemplateCreationError, OomError {});
+
+#[cfg(test)]
+mod tests {
+    use VulkanObject;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorUpdateTemplateCreationError;
+    use descriptor::descriptor_set::DescriptorUpdateTemplateEntry;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use descriptor::descriptor_set::UnsafeDescriptorUpdateTemplate;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let layout = Arc::new(UnsafeDescriptorSetLayout::new(device, iter::empty()).unwrap());
+
+        let entry = DescriptorUpdateTemplateEntry {
+            binding: 0,
+            first_array_element: 0,
+            count: 1,
+            ty: DescriptorType::UniformBuffer,
+            offset: 0,
+            stride: 0,
+        };
+
+        match UnsafeDescriptorUpdateTemplate::new(layout, iter::once(entry)) {
+            Err(DescriptorUpdateTemplateCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_descriptor_update_template]);
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let layout = Arc::new(UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc)))
+                                  .unwrap());
+
+        let entry = DescriptorUpdateTemplateEntry {
+            binding: 0,
+            first_array_element: 0,
+            count: 1,
+            ty: DescriptorType::UniformBuffer,
+            offset: 0,
+            stride: 0,
+        };
+        let template = UnsafeDescriptorUpdateTemplate::new(layout.clone(), iter::once(entry))
+            .unwrap();
+
+        let borrowed = unsafe {
+            UnsafeDescriptorUpdateTemplate::from_raw(layout, template.internal_object(), false)
+        };
+        assert_eq!(borrowed.internal_object(), template.internal_object());
+        drop(borrowed);
+        drop(template);
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use descriptor::descriptor::DescriptorBufferDesc;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor_set::DescriptorSet;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::UnsafeDescriptorSet;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageViewAccess;
+
+/// Wraps around a descriptor set and attaches to it the dynamic offsets of its dynamic buffer
+/// descriptors.
+///
+/// Dynamic uniform and storage buffers are buffer descriptors whose offset is provided when the
+/// descriptor set is bound, instead of when the descriptor set is written. This makes it possible
+/// to use a single large buffer for the data of all the objects that are drawn, and to only
+/// change the offset between each draw command.
+///
+/// # Example
+///
+/// ```ignore       // TODO: layouts with dynamic buffers can't be created from a shader yet
+/// use vulkano::descriptor::descriptor_set::DescriptorSetWithOffsets;
+///
+/// for object in 0 .. 16 {
+///     let set = DescriptorSetWithOffsets::new(set.clone(), vec![object * 256]).unwrap();
+///     builder = builder.draw(pipeline.clone(), DynamicState::none(), vertex_buffer.clone(),
+///                            set, ()).unwrap();
+/// }
+/// ```
+pub struct DescriptorSetWithOffsets<S> {
+    set: S,
+    offsets: SmallVec<[u32; 4]>,
+}
+
+impl<S> DescriptorSetWithOffsets<S>
+    where S: DescriptorSet + DeviceOwned
+{
+    /// Attaches dynamic offsets to a descriptor set.
+    ///
+    /// There must be exactly one offset for each element of each dynamic buffer descriptor of the
+    /// set, in binding order. Each offset must be a multiple of the
+    /// `min_uniform_buffer_offset_alignment` limit for dynamic uniform buffers, or of the
+    /// `min_storage_buffer_offset_alignment` limit for dynamic storage buffers.
+    ///
+    /// The offsets are added to the offsets of the buffers that were written in the descriptor
+    /// set. It is your responsibility to make sure that the resulting ranges are within the
+    /// buffers.
+    // TODO: check that the resulting ranges are within the buffers
+    pub fn new<I>(set: S, offsets: I)
+                  -> Result<DescriptorSetWithOffsets<S>, DescriptorSetWithOffsetsError>
+        where I: IntoIterator<Item = u32>
+    {
+        let offsets: SmallVec<[u32; 4]> = offsets.into_iter().collect();
+
+        let alignments = {
+            let limits = set.device().physical_device().limits();
+            let uniform_alignment = limits.min_uniform_buffer_offset_alignment();
+            let storage_alignment = limits.min_storage_buffer_offset_alignment();
+
+            let mut alignments: SmallVec<[u64; 4]> = SmallVec::new();
+            for binding in 0 .. set.num_bindings() {
+                let desc = match set.descriptor(binding) {
+                    Some(desc) => desc,
+                    None => continue,
+                };
+
+                let alignment = match desc.ty {
+                    DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                 dynamic: Some(true),
+                                                 storage: false,
+                                             }) => uniform_alignment,
+                    DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                 dynamic: Some(true),
+                                                 storage: true,
+                                             }) => storage_alignment,
+                    _ => continue,
+                };
+
+                for _ in 0 .. desc.array_count {
+                    alignments.push(alignment);
+                }
+            }
+            alignments
+        };
+
+        if offsets.len() != alignments.len() {
+            return Err(DescriptorSetWithOffsetsError::WrongOffsetsCount {
+                           expected: alignments.len(),
+                           obtained: offsets.len(),
+                       });
+        }
+
+        for (index, (&offset, &alignment)) in offsets.iter().zip(alignments.iter()).enumerate() {
+            if offset as u64 % alignment != 0 {
+                return Err(DescriptorSetWithOffsetsError::UnalignedOffset {
+                               index: index,
+                               offset: offset,
+                               alignment: alignment,
+                           });
+            }
+        }
+
+        Ok(DescriptorSetWithOffsets {
+               set: set,
+               offsets: offsets,
+           })
+    }
+}
+
+impl<S> DescriptorSetWithOffsets<S> {
+    /// Returns the wrapped descriptor set.
+    #[inline]
+    pub fn set(&self) -> &S {
+        &self.set
+    }
+
+    /// Destroys the wrapper and returns the wrapped descriptor set.
+    #[inline]
+    pub fn into_set(self) -> S {
+        self.set
+    }
+}
+
+unsafe impl<S> DescriptorSet for DescriptorSetWithOffsets<S>
+    where S: DescriptorSet
+{
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        self.set.inner()
+    }
+
+    #[inline]
+    fn num_buffers(&self) -> usize {
+        self.set.num_buffers()
+    }
+
+    #[inline]
+    fn buffer(&self, index: usize) -> Option<(&BufferAccess, u32)> {
+        self.set.buffer(index)
+    }
+
+    #[inline]
+    fn num_images(&self) -> usize {
+        self.set.num_images()
+    }
+
+    #[inline]
+    fn image(&self, index: usize) -> Option<(&ImageViewAccess, u32)> {
+        self.set.image(index)
+    }
+
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        &self.offsets
+    }
+}
+
+unsafe impl<S> DescriptorSetDesc for DescriptorSetWithOffsets<S>
+    where S: DescriptorSetDesc
+{
+    #[inline]
+    fn num_bindings(&self) -> usize {
+        self.set.num_bindings()
+    }
+
+    #[inline]
+    fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
+        self.set.descriptor(binding)
+    }
+}
+
+unsafe impl<S> DeviceOwned for DescriptorSetWithOffsets<S>
+    where S: DeviceOwned
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.set.device()
+    }
+}
+
+/// Error that can happen when attaching dynamic offsets to a descriptor set.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DescriptorSetWithOffsetsError {
+    /// The number of offsets doesn't match the number of dynamic buffer descriptors.
+    WrongOffsetsCount {
+        /// Number of offsets expected by the descriptor set.
+        expected: usize,
+        /// Number of offsets that were provided.
+        obtained: usize,
+    },
+
+    /// An offset isn't a multiple of the required alignment.
+    UnalignedOffset {
+        /// Index of the offset in the list.
+        index: usize,
+        /// Value of the offset.
+        offset: u32,
+        /// Required alignment.
+        alignment: u64,
+    },
+}
+
+impl error::Error for DescriptorSetWithOffsetsError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorSetWithOffsetsError::WrongOffsetsCount { .. } => {
+                "the number of offsets doesn't match the number of dynamic buffer descriptors"
+            },
+            DescriptorSetWithOffsetsError::UnalignedOffset { .. } => {
+                "an offset isn't a multiple of the required alignment"
+            },
+        }
+    }
+}
+
+impl fmt::Display for DescriptorSetWithOffsetsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSet;
+    use descriptor::descriptor_set::DescriptorSetWithOffsets;
+    use descriptor::descriptor_set::DescriptorSetWithOffsetsError;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn offsets_count() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(true),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = RuntimePipelineDesc::new(iter::once(iter::once(Some(desc))), iter::empty())
+            .unwrap()
+            .build(device.clone())
+            .unwrap();
+
+        let buffer = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), 0u32).unwrap();
+        let set = PersistentDescriptorSet::start(Arc::new(layout), 0)
+            .add_buffer(buffer)
+            .unwrap()
+            .build()
+            .unwrap();
+        let set = Arc::new(set);
+
+        match DescriptorSetWithOffsets::new(set.clone(), iter::empty()) {
+            Err(DescriptorSetWithOffsetsError::WrongOffsetsCount {
+                    expected: 1,
+                    obtained: 0,
+                }) => (),
+            _ => panic!(),
+        }
+
+        let set = DescriptorSetWithOffsets::new(set, iter::once(0)).unwrap();
+        assert_eq!(set.dynamic_offsets(), &[0]);
+    }
+}
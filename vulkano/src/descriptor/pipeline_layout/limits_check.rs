@@ -80,6 +80,9 @@ pub fn check_desc_against_limits<D>(desc: &D, limits: Limits)
                 DescriptorType::InputAttachment => {
                     num_input_attachments.increment(descriptor.array_count, &descriptor.stages);
                 },
+                DescriptorType::AccelerationStructure => {
+                    // TODO: check the limits of the `khr_acceleration_structure` extension
+                },
             }
         }
     }
@@ -14,7 +14,6 @@ use std::mem;
 use std::ptr;
 use std::sync::Arc;
 
-use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
@@ -22,6 +21,7 @@ use vk;
 
 use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::ShaderStages;
+use descriptor::descriptor_set::DescriptorSetLayoutCreationError;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
@@ -37,6 +37,8 @@ pub struct PipelineLayout<L> {
     layout: vk::PipelineLayout,
     layouts: SmallVec<[Arc<UnsafeDescriptorSetLayout>; 16]>,
     desc: L,
+    // False if the layout was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<L> PipelineLayout<L>
@@ -69,14 +71,21 @@ impl<L> PipelineLayout<L>
                                      let sets_iter = 0 ..
                                          desc.num_bindings_in_set(num).unwrap_or(0);
                                      let desc_iter = sets_iter.map(|d| desc.descriptor(num, d));
-                                     Arc::new(UnsafeDescriptorSetLayout::new(device.clone(),
-                                                                             desc_iter)?)
+                                     let layout = UnsafeDescriptorSetLayout::new(device.clone(),
+                                                                                 desc_iter)
+                                         .map_err(|err| set_layout_error(num, err))?;
+                                     Arc::new(layout)
                                  },
                              });
             }
             layouts
         };
 
+        // At most one of the sets can be a push descriptor set.
+        if layouts.iter().filter(|l| l.is_push_descriptor()).count() >= 2 {
+            return Err(PipelineLayoutCreationError::MultiplePushDescriptorSets);
+        }
+
         // Grab the list of `vkDescriptorSetLayout` objects from `layouts`.
         let layouts_ids = layouts
             .iter()
@@ -157,8 +166,37 @@ impl<L> PipelineLayout<L>
                layout: layout,
                layouts: layouts,
                desc: desc,
+               needs_destruction: true,
            })
     }
+
+    /// Builds a `PipelineLayout` from a raw handle that was created outside of vulkano.
+    ///
+    /// `set_layouts` are the layouts of the descriptor sets, in order, and are kept alive by the
+    /// pipeline layout.
+    /// If `owned` is true, the layout is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid pipeline layout of `device`, created with `set_layouts` and
+    ///   with the push constants ranges of `desc`.
+    /// - `desc` must describe the sets of `set_layouts`.
+    /// - If `owned` is true, the layout must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw<I>(device: Arc<Device>, handle: vk::PipelineLayout, desc: L,
+                              set_layouts: I, owned: bool)
+                              -> PipelineLayout<L>
+        where I: IntoIterator<Item = Arc<UnsafeDescriptorSetLayout>>
+    {
+        PipelineLayout {
+            device: device,
+            layout: handle,
+            layouts: set_layouts.into_iter().collect(),
+            desc: desc,
+            needs_destruction: owned,
+        }
+    }
 }
 
 impl<L> PipelineLayout<L>
@@ -203,6 +241,11 @@ unsafe impl<D> PipelineLayoutDesc for PipelineLayout<D>
         self.desc.descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        self.layouts.get(set).cloned()
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         self.desc.num_push_constants_ranges()
@@ -236,6 +279,10 @@ impl<D> fmt::Debug for PipelineLayout<D>
 impl<L> Drop for PipelineLayout<L> {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyPipelineLayout(self.device.internal_object(), self.layout, ptr::null());
@@ -271,6 +318,35 @@ pub enum PipelineLayoutCreationError {
     /// One of the push constants range didn't obey the rules. The list of stages must not be
     /// empty, the size must not be 0, and the size must be a multiple or 4.
     InvalidPushConstant,
+    /// More than one of the descriptor set layouts was created for push descriptors.
+    MultiplePushDescriptorSets,
+    /// An input attachment descriptor of one of the sets is accessible from a shader stage
+    /// other than the fragment shader.
+    InputAttachmentWrongStages {
+        /// The index of the set.
+        set: usize,
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// One of the sets contains an acceleration structure descriptor, but the
+    /// `khr_acceleration_structure` extension is not enabled on the device.
+    AccelerationStructureExtensionNotEnabled {
+        /// The index of the set.
+        set: usize,
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// One of the sets contains a runtime-sized array of descriptors, but the
+    /// `runtime_descriptor_array` feature of the `ext_descriptor_indexing` extension is not
+    /// enabled on the device.
+    RuntimeArrayFeatureNotEnabled {
+        /// The index of the set.
+        set: usize,
+        /// The binding of the descriptor.
+        binding: u32,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for PipelineLayoutCreationError {
@@ -286,6 +362,23 @@ impl error::Error for PipelineLayoutCreationError {
             PipelineLayoutCreationError::InvalidPushConstant => {
                 "one of the push constants range didn't obey the rules"
             },
+            PipelineLayoutCreationError::MultiplePushDescriptorSets => {
+                "more than one of the descriptor set layouts was created for push descriptors"
+            },
+            PipelineLayoutCreationError::InputAttachmentWrongStages { .. } => {
+                "an input attachment descriptor is accessible from a shader stage other than the \
+                 fragment shader"
+            },
+            PipelineLayoutCreationError::AccelerationStructureExtensionNotEnabled { .. } => {
+                "one of the sets contains an acceleration structure descriptor, but the \
+                 `khr_acceleration_structure` extension is not enabled on the device"
+            },
+            PipelineLayoutCreationError::RuntimeArrayFeatureNotEnabled { .. } => {
+                "one of the sets contains a runtime-sized array of descriptors, but the \
+                 `runtime_descriptor_array` feature is not enabled"
+            },
+            PipelineLayoutCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -299,17 +392,52 @@ impl error::Error for PipelineLayoutCreationError {
     }
 }
 
-impl fmt::Display for PipelineLayoutCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for PipelineLayoutCreationError {
-    #[inline]
-    fn from(err: OomError) -> PipelineLayoutCreationError {
-        PipelineLayoutCreationError::OomError(err)
+vk_error_conversions!(PipelineLayoutCreationError, OomError {});
+
+// Converts an error that happened when creating the layout of the set `set`.
+fn set_layout_error(set: usize, err: DescriptorSetLayoutCreationError)
+                    -> PipelineLayoutCreationError {
+    match err {
+        DescriptorSetLayoutCreationError::OomError(err) => {
+            PipelineLayoutCreationError::OomError(err)
+        },
+        DescriptorSetLayoutCreationError::LimitsError(err) => {
+            PipelineLayoutCreationError::LimitsError(err)
+        },
+        DescriptorSetLayoutCreationError::InputAttachmentWrongStages { binding } => {
+            PipelineLayoutCreationError::InputAttachmentWrongStages {
+                set: set,
+                binding: binding,
+            }
+        },
+        DescriptorSetLayoutCreationError::AccelerationStructureExtensionNotEnabled { binding } => {
+            PipelineLayoutCreationError::AccelerationStructureExtensionNotEnabled {
+                set: set,
+                binding: binding,
+            }
+        },
+        DescriptorSetLayoutCreationError::RuntimeArrayFeatureNotEnabled { binding } => {
+            PipelineLayoutCreationError::RuntimeArrayFeatureNotEnabled {
+                set: set,
+                binding: binding,
+            }
+        },
+        DescriptorSetLayoutCreationError::UnexpectedResult(code) => {
+            PipelineLayoutCreationError::UnexpectedResult(code)
+        },
+        // Pipeline layouts never create set layouts with immutable samplers, push
+        // descriptors or binding flags.
+        DescriptorSetLayoutCreationError::ImmutableSamplersWrongDescriptorTy { .. } |
+        DescriptorSetLayoutCreationError::ImmutableSamplersCountMismatch { .. } |
+        DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled |
+        DescriptorSetLayoutCreationError::PushDescriptorDynamicBuffer { .. } |
+        DescriptorSetLayoutCreationError::BindingFlagsExtensionNotEnabled |
+        DescriptorSetLayoutCreationError::VariableDescriptorCountNotLast { .. } |
+        DescriptorSetLayoutCreationError::BindingFlagsDynamicBuffer { .. } |
+        DescriptorSetLayoutCreationError::BindingFlagsFeatureNotEnabled { .. } |
+        DescriptorSetLayoutCreationError::YcbcrSamplerWrongDescriptorTy { .. } => {
+            unreachable!()
+        },
     }
 }
 
@@ -320,21 +448,6 @@ impl From<PipelineLayoutLimitsError> for PipelineLayoutCreationError {
     }
 }
 
-impl From<Error> for PipelineLayoutCreationError {
-    #[inline]
-    fn from(err: Error) -> PipelineLayoutCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => {
-                PipelineLayoutCreationError::OomError(OomError::from(err))
-            },
-            err @ Error::OutOfDeviceMemory => {
-                PipelineLayoutCreationError::OomError(OomError::from(err))
-            },
-            _ => panic!("unexpected error: {:?}", err),
-        }
-    }
-}
-
 /* TODO: restore
 #[cfg(test)]
 mod tests {
@@ -402,3 +515,61 @@ mod tests {
         }
     }
 }*/
+
+#[cfg(test)]
+mod creation_tests {
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorImageDescArray;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::PipelineLayout;
+    use descriptor::pipeline_layout::PipelineLayoutCreationError;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+
+    #[test]
+    fn input_attachment_wrong_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let input = DescriptorDesc {
+            ty: DescriptorDescTy::InputAttachment {
+                multisampled: false,
+                array_layers: DescriptorImageDescArray::NonArrayed,
+            },
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let desc = RuntimePipelineDesc::new(vec![vec![None, Some(input)]], vec![]).unwrap();
+
+        match PipelineLayout::new(device, desc) {
+            Err(PipelineLayoutCreationError::InputAttachmentWrongStages {
+                    set: 0,
+                    binding: 1,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn acceleration_structure_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let tlas = DescriptorDesc {
+            ty: DescriptorDescTy::AccelerationStructure,
+            array_count: 1,
+            runtime_array: false,
+            stages: ShaderStages::compute(),
+            readonly: true,
+        };
+        let desc = RuntimePipelineDesc::new(vec![vec![], vec![Some(tlas)]], vec![]).unwrap();
+
+        match PipelineLayout::new(device, desc) {
+            Err(PipelineLayoutCreationError::AccelerationStructureExtensionNotEnabled {
+                    set: 1,
+                    binding: 0,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -108,6 +108,56 @@ pub unsafe trait PipelineLayoutDesc {
         limits_check::check_desc_against_limits(self, device.physical_device().limits())
     }
 
+    /// Returns true if descriptor sets bound with a pipeline whose layout is `self` can still be
+    /// used with a pipeline whose layout is `other`, for all the sets up to and including `set`.
+    ///
+    /// This corresponds to the *pipeline layout compatibility* rules of Vulkan: the two layouts
+    /// must have identical push constant ranges, and the sets `0` to `set` must be identically
+    /// defined. In that situation, binding a pipeline whose layout is `other` doesn't disturb the
+    /// descriptor sets that are already bound for these indices.
+    fn is_compatible_for_set<T>(&self, other: &T, set: usize) -> bool
+        where Self: Sized,
+              T: ?Sized + PipelineLayoutDesc
+    {
+        if self.num_push_constants_ranges() != other.num_push_constants_ranges() {
+            return false;
+        }
+
+        for num in 0 .. self.num_push_constants_ranges() {
+            let mine = self.push_constants_range(num).unwrap();
+            let other = other.push_constants_range(num).unwrap();
+
+            if mine.offset != other.offset || mine.size != other.size ||
+                mine.stages != other.stages
+            {
+                return false;
+            }
+        }
+
+        for set_num in 0 .. set + 1 {
+            let num_bindings = self.num_bindings_in_set(set_num).unwrap_or(0);
+            if num_bindings != other.num_bindings_in_set(set_num).unwrap_or(0) {
+                return false;
+            }
+
+            for binding in 0 .. num_bindings {
+                match (self.descriptor(set_num, binding), other.descriptor(set_num, binding)) {
+                    (Some(mine), Some(other)) => {
+                        if mine.is_superset_of(&other).is_err() ||
+                            other.is_superset_of(&mine).is_err()
+                        {
+                            return false;
+                        }
+                    },
+                    (None, None) => (),
+                    _ => return false,
+                }
+            }
+        }
+
+        true
+    }
+
     /// Turns the layout description into a `PipelineLayout` object that can be used by Vulkan.
     ///
     /// > **Note**: This is just a shortcut for `PipelineLayout::new`.
@@ -151,6 +201,11 @@ unsafe impl<T> PipelineLayoutDesc for T
         (**self).descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        (**self).provided_set_layout(set)
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         (**self).num_push_constants_ranges()
@@ -211,7 +266,28 @@ unsafe impl<T: ?Sized, U: ?Sized> PipelineLayoutSuperset<U> for T
             }
         }
 
-        // FIXME: check push constants
+        for num in 0 .. other.num_push_constants_ranges() {
+            let other_range = other.push_constants_range(num).unwrap();
+
+            // The stages of the range of `other` can be spread over multiple ranges of `self`, as
+            // long as each of these ranges covers the whole range of `other`.
+            let mut covered = ShaderStages::none();
+            for self_num in 0 .. self.num_push_constants_ranges() {
+                let self_range = self.push_constants_range(self_num).unwrap();
+                if self_range.offset <= other_range.offset &&
+                    self_range.offset + self_range.size >= other_range.offset + other_range.size
+                {
+                    covered = covered | self_range.stages;
+                }
+            }
+
+            if covered.is_superset_of(&other_range.stages).is_err() {
+                return Err(PipelineLayoutNotSupersetError::PushConstantRangeNotCovered {
+                               offset: other_range.offset,
+                               size: other_range.size,
+                           });
+            }
+        }
 
         Ok(())
     }
@@ -236,6 +312,10 @@ pub enum PipelineLayoutNotSupersetError {
         set_num: u32,
         descriptor: u32,
     },
+
+    /// A push constants range of the child isn't covered by the parent layout for all of its
+    /// stages.
+    PushConstantRangeNotCovered { offset: usize, size: usize },
 }
 
 impl error::Error for PipelineLayoutNotSupersetError {
@@ -251,6 +331,9 @@ impl error::Error for PipelineLayoutNotSupersetError {
             PipelineLayoutNotSupersetError::IncompatibleDescriptors { .. } => {
                 "two descriptors are incompatible"
             },
+            PipelineLayoutNotSupersetError::PushConstantRangeNotCovered { .. } => {
+                "a push constants range of the child isn't covered by the parent layout"
+            },
         }
     }
 
@@ -326,3 +409,75 @@ unsafe impl<T: ?Sized, U: ?Sized> PipelineLayoutPushConstantsCompatible<U> for T
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use descriptor::pipeline_layout::PipelineLayoutSuperset;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+
+    fn uniform_buffer() -> Option<DescriptorDesc> {
+        Some(DescriptorDesc {
+                 ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                  dynamic: Some(false),
+                                                  storage: false,
+                                              }),
+                 array_count: 1,
+                 runtime_array: false,
+                 stages: ShaderStages::all_graphics(),
+                 readonly: true,
+             })
+    }
+
+    fn push_constants(stages: ShaderStages) -> PipelineLayoutDescPcRange {
+        PipelineLayoutDescPcRange {
+            offset: 0,
+            size: 16,
+            stages: stages,
+        }
+    }
+
+    #[test]
+    fn compatible_for_set() {
+        let a = RuntimePipelineDesc::new(vec![vec![uniform_buffer()], vec![uniform_buffer()]],
+                                         vec![push_constants(ShaderStages::all_graphics())])
+            .unwrap();
+        let b = RuntimePipelineDesc::new(vec![vec![uniform_buffer()], vec![None]],
+                                         vec![push_constants(ShaderStages::all_graphics())])
+            .unwrap();
+
+        assert!(a.is_compatible_for_set(&b, 0));
+        assert!(!a.is_compatible_for_set(&b, 1));
+    }
+
+    #[test]
+    fn push_constants_incompatible() {
+        let a = RuntimePipelineDesc::new(vec![vec![uniform_buffer()]],
+                                         vec![push_constants(ShaderStages::all_graphics())])
+            .unwrap();
+        let b = RuntimePipelineDesc::new(vec![vec![uniform_buffer()]], vec![]).unwrap();
+
+        assert!(!a.is_compatible_for_set(&b, 0));
+    }
+
+    #[test]
+    fn superset_push_constants() {
+        let parent = RuntimePipelineDesc::new(vec![vec![uniform_buffer()]],
+                                              vec![push_constants(ShaderStages::all_graphics())])
+            .unwrap();
+        let child = RuntimePipelineDesc::new(vec![vec![uniform_buffer()]],
+                                             vec![push_constants(ShaderStages {
+                                                                     vertex: true,
+                                                                     ..ShaderStages::none()
+                                                                 })])
+            .unwrap();
+
+        assert!(parent.ensure_superset_of(&child).is_ok());
+        assert!(child.ensure_superset_of(&parent).is_err());
+    }
+}
@@ -62,7 +62,10 @@
 //! Not all physical devices support all possible features and extensions. For example mobile
 //! devices tend to not support geometry shaders, because their hardware is not capable of it. You
 //! can query what is supported with respectively `PhysicalDevice::supported_features` and
-//! TODO: oops, there's no method for querying supported extensions in vulkan yet.
+//! `DeviceExtensions::supported_by_device`.
+//!
+//! Both are plain structs with one boolean per feature or extension, so that you can check
+//! whether everything you need is supported with `superset_of` instead of comparing strings.
 //!
 //! > **Note**: The fact that you need to manually enable features at initialization also means
 //! > that you don't need to worry about a capability not being supported later on in your code.
@@ -81,9 +84,32 @@
 //! each element is a tuple containing a queue family and a number between 0.0 and 1.0 indicating
 //! the priority of execution of the queue relative to the others.
 //!
-//! TODO: write better doc here
+//! A queue family can appear multiple times in the iterator, in which case multiple queues of
+//! that family are created, up to the number of queues that the family provides.
+//!
+//! The `Device::new` function returns the newly-created device, but also the list of queues, in
+//! the same order as the iterator that was passed. Each `Queue` remembers its family, its index
+//! within the family and its priority, and can tell which operations it supports.
+//!
+//! # Device loss
+//!
+//! A device can be *lost* at any time, for example after a driver crash, a GPU reset, or if a
+//! command takes too long to execute. When a submission, a present, an acquire or a wait reports
+//! that the device is lost, vulkano marks the `Device` as lost: from then on, `is_lost()` returns
+//! true and these operations fail immediately with a `DeviceLost` error instead of calling into
+//! the driver again. Functions registered with `Device::on_lost` are called once at that moment.
+//!
+//! A lost device can't be repaired. In order to recover, the application must:
+//!
+//! - Stop submitting work and drop all the objects that belong to the device, including futures,
+//!   command buffers, buffers, images, pipelines and swapchains.
+//! - Create a new `Device` from the same `PhysicalDevice`. If creation fails with
+//!   `DeviceCreationError::DeviceLost`, the physical device itself may be gone and the instance
+//!   must be recreated as well.
+//! - Recreate all the resources from their source data, and recreate the swapchain of each
+//!   surface.
 //!
-//! The `Device::new` function returns the newly-created device, but also the list of queues.
+//! Surfaces and the `Instance` remain valid and can be reused.
 //!
 //! # Extended example
 //!
@@ -91,6 +117,7 @@
 
 use fnv::FnvHasher;
 use smallvec::SmallVec;
+use std::cmp;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::error;
@@ -98,20 +125,33 @@ use std::fmt;
 use std::hash::BuildHasherDefault;
 use std::mem;
 use std::ops::Deref;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
 use std::sync::Weak;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::ffi::CStr;
+use std::ffi::CString;
 
 use command_buffer::pool::StandardCommandPool;
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use command_buffer::submit::SubmitCommandBufferError;
+use descriptor::descriptor_set::DescriptorIndexingFeatures;
 use descriptor::descriptor_set::StdDescriptorPool;
 use instance::Features;
 use instance::Instance;
 use instance::PhysicalDevice;
+use instance::PhysicalDeviceGroup;
 use instance::QueueFamily;
 use memory::pool::StdMemoryPool;
+use pipeline::MeshShaderProperties;
+use swapchain::CapabilitiesError;
+use swapchain::Surface;
+use sync::DeletionQueue;
+use version::Version;
 
 use Error;
 use OomError;
@@ -133,13 +173,40 @@ pub struct Device {
     standard_descriptor_pool: Mutex<Weak<StdDescriptorPool>>,
     standard_command_pools:
         Mutex<HashMap<u32, Weak<StandardCommandPool>, BuildHasherDefault<FnvHasher>>>,
+    api_version: Version,
+    device_group_size: u32,
+    // True if memory can be allocated on a subset of the physical devices of the group.
+    subset_allocation: bool,
     features: Features,
     extensions: DeviceExtensions,
+    // Queried when the device is created if the `ext_mesh_shader` extension is enabled, so that
+    // draw commands don't have to query it each time.
+    mesh_shader_properties: Option<MeshShaderProperties>,
+    // True if the `taskShader` feature of `VK_EXT_mesh_shader` is enabled.
+    task_shader: bool,
+    // True if the `drawIndirectCount` feature of Vulkan 1.2 is enabled.
+    draw_indirect_count: bool,
+    // True if the `timelineSemaphore` feature of Vulkan 1.2 is enabled.
+    timeline_semaphore: bool,
+    // All false if the `ext_descriptor_indexing` extension isn't enabled.
+    descriptor_indexing_features: DescriptorIndexingFeatures,
     active_queue_families: SmallVec<[u32; 8]>,
     allocation_count: Mutex<u32>,
+    custom_border_color_sampler_count: Mutex<u32>,
     fence_pool: Mutex<Vec<vk::Fence>>,
     semaphore_pool: Mutex<Vec<vk::Semaphore>>,
     event_pool: Mutex<Vec<vk::Event>>,
+    // The queues that have been produced by the `QueuesIter`. Used by `wait_idle` to synchronize
+    // with the submissions.
+    queues: Mutex<Vec<Weak<Queue>>>,
+    // The deletion queues that have been created for this device. They are only weakly referenced,
+    // as their entries keep the device alive.
+    deletion_queues: Mutex<Vec<Weak<DeletionQueue>>>,
+    // Set to true the first time a Vulkan function reports that the device has been lost.
+    lost: AtomicBool,
+    lost_callbacks: Mutex<Vec<Box<Fn() + Send + Sync>>>,
+    // False if the device was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -164,6 +231,27 @@ impl Device {
     ///   queue with a lower value. Note however that no guarantee can be made on the way the
     ///   priority value is handled by the implementation.
     ///
+    /// If the `khr_multiview` extension is enabled, the `multiview` feature of this extension is
+    /// enabled as well. Similarly, the `timelineSemaphore` feature is enabled if the
+    /// `khr_timeline_semaphore` extension is enabled, the `hostQueryReset` feature if the
+    /// `ext_host_query_reset` extension is enabled, the `customBorderColors` feature if the
+    /// `ext_custom_border_color` extension is enabled, the `samplerYcbcrConversion` feature if
+    /// the `khr_sampler_ycbcr_conversion` extension is enabled, the `bufferDeviceAddress` feature
+    /// if the `khr_buffer_device_address` extension is enabled, the `accelerationStructure`
+    /// feature if the `khr_acceleration_structure` extension is enabled, the
+    /// `rayTracingPipeline` feature if the `khr_ray_tracing_pipeline` extension is enabled, the
+    /// `rayQuery` feature if the `khr_ray_query` extension is enabled, the `meshShader` feature
+    /// and, if it is supported, the `taskShader` feature if the `ext_mesh_shader` extension is
+    /// enabled, the
+    /// `conditionalRendering` feature if the `ext_conditional_rendering` extension is enabled,
+    /// and the `transformFeedback` feature if the `ext_transform_feedback` extension is enabled.
+    /// If the `ext_descriptor_indexing` extension is enabled, all the features of this extension
+    /// that the physical device supports are enabled.
+    /// If the device uses Vulkan 1.2 or above and the `khr_draw_indirect_count` extension isn't
+    /// enabled, the `drawIndirectCount` feature is enabled if the physical device supports it.
+    /// Similarly, the `timelineSemaphore` feature is enabled if the physical device supports it
+    /// and the `khr_timeline_semaphore` extension isn't enabled.
+    ///
     /// # Panic
     ///
     /// - Panics if one of the queue families doesn't belong to the given device.
@@ -175,6 +263,40 @@ impl Device {
                            -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
         where I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
               Ext: Into<RawDeviceExtensions>
+    {
+        Device::new_impl(phys, None, requested_features, extensions.into(), queue_families)
+    }
+
+    /// Builds a new Vulkan device that spans all the physical devices of a device group.
+    ///
+    /// This is the same as `new`, except that the commands submitted to the device can run on
+    /// any of the physical devices of the group. The queue families must belong to the first
+    /// physical device of the group, and the features are checked against this device only.
+    ///
+    /// Use `DeviceMemory::alloc_with_device_mask` and `set_device_mask` on command buffers to
+    /// choose the physical devices that are used. If the group contains a single physical device,
+    /// this is equivalent to `new`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the queue families doesn't belong to the first physical device of the
+    ///   group.
+    ///
+    pub fn with_group<'a, I, Ext>(group: &PhysicalDeviceGroup, requested_features: &Features,
+                                  extensions: Ext, queue_families: I)
+                                  -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+        where I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+              Ext: Into<RawDeviceExtensions>
+    {
+        let phys = group.physical_device(0).expect("empty device group");
+        Device::new_impl(phys, Some(group), requested_features, extensions.into(), queue_families)
+    }
+
+    fn new_impl<'a, I>(phys: PhysicalDevice, group: Option<&PhysicalDeviceGroup>,
+                       requested_features: &Features, extensions: RawDeviceExtensions,
+                       queue_families: I)
+                       -> Result<(Arc<Device>, QueuesIter), DeviceCreationError>
+        where I: IntoIterator<Item = (QueueFamily<'a>, f32)>
     {
         let queue_families = queue_families.into_iter();
 
@@ -185,7 +307,7 @@ impl Device {
         let vk_i = phys.instance().pointers();
 
         // this variable will contain the queue family ID and queue ID of each requested queue
-        let mut output_queues: SmallVec<[(u32, u32); 8]> = SmallVec::new();
+        let mut output_queues: SmallVec<[(u32, u32, f32); 8]> = SmallVec::new();
 
         // Device layers were deprecated in Vulkan 1.0.13, and device layer requests should be
         // ignored by the driver. For backwards compatibility, the spec recommends passing the
@@ -201,11 +323,11 @@ impl Device {
             .map(|layer| layer.as_ptr())
             .collect::<SmallVec<[_; 16]>>();
 
-        let extensions = extensions.into();
         let extensions_list = extensions
             .iter()
             .map(|extension| extension.as_ptr())
             .collect::<SmallVec<[_; 16]>>();
+        let loaded_extensions: DeviceExtensions = (&extensions).into();
 
         // device creation
         let device = unsafe {
@@ -223,7 +345,7 @@ impl Device {
 
                 // adding to `queues` and `output_queues`
                 if let Some(q) = queues.iter_mut().find(|q| q.0 == queue_family.id()) {
-                    output_queues.push((queue_family.id(), q.1.len() as u32));
+                    output_queues.push((queue_family.id(), q.1.len() as u32, priority));
                     q.1.push(priority);
                     if q.1.len() > queue_family.queues_count() {
                         return Err(DeviceCreationError::TooManyQueuesForFamily);
@@ -231,7 +353,7 @@ impl Device {
                     continue;
                 }
                 queues.push((queue_family.id(), vec![priority]));
-                output_queues.push((queue_family.id(), 0));
+                output_queues.push((queue_family.id(), 0, priority));
             }
 
             // turning `queues` into an array of `vkDeviceQueueCreateInfo` suitable for Vulkan
@@ -271,9 +393,341 @@ impl Device {
                 features
             };
 
+            // The `multiview` feature of `VK_KHR_multiview` can't be expressed with `Features`, so
+            // we enable it whenever the extension is enabled. Device creation fails with
+            // `FeatureNotPresent` if the physical device doesn't support it.
+            // Same for the `timelineSemaphore` feature of `VK_KHR_timeline_semaphore`, the
+            // `hostQueryReset` feature of `VK_EXT_host_query_reset`, the `customBorderColors`
+            // feature of `VK_EXT_custom_border_color`, the `samplerYcbcrConversion` feature of
+            // `VK_KHR_sampler_ycbcr_conversion`, the `bufferDeviceAddress` feature of
+            // `VK_KHR_buffer_device_address`, the `accelerationStructure` feature of
+            // `VK_KHR_acceleration_structure`, the `rayTracingPipeline` feature of
+            // `VK_KHR_ray_tracing_pipeline`, the `rayQuery` feature of `VK_KHR_ray_query`, the
+            // `meshShader` feature of `VK_EXT_mesh_shader`, the `conditionalRendering` feature of
+            // `VK_EXT_conditional_rendering` and the `transformFeedback` feature of
+            // `VK_EXT_transform_feedback`.
+            //
+            // `next` always points to the head of the chain of structs built so far. The structs
+            // must not move until the device is created.
+            let mut next: *mut c_void = ptr::null_mut();
+
+            // On Vulkan 1.2, the `drawIndirectCount` and `timelineSemaphore` features can be
+            // used without the `VK_KHR_draw_indirect_count` and `VK_KHR_timeline_semaphore`
+            // extensions, but they can then only be enabled with
+            // `VkPhysicalDeviceVulkan12Features`. This struct can't be chained along with the
+            // structs of the extensions that have been promoted to Vulkan 1.2, so the features
+            // of these extensions are enabled through it instead.
+            let api_version = cmp::min(phys.instance().api_version(), phys.api_version());
+            let mut vulkan12_features = if api_version >= Version::V1_2 &&
+                !(loaded_extensions.khr_draw_indirect_count &&
+                      loaded_extensions.khr_timeline_semaphore)
+            {
+                let mut supported: vk::PhysicalDeviceVulkan12Features = mem::zeroed();
+                supported.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_2_FEATURES;
+                phys.query_features2(&mut supported as *mut _ as *mut _);
+
+                let draw_indirect_count = !loaded_extensions.khr_draw_indirect_count &&
+                    supported.drawIndirectCount != 0;
+                let timeline_semaphore = !loaded_extensions.khr_timeline_semaphore &&
+                    supported.timelineSemaphore != 0;
+
+                if draw_indirect_count || timeline_semaphore {
+                    let mut f: vk::PhysicalDeviceVulkan12Features = mem::zeroed();
+                    f.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_2_FEATURES;
+                    f.pNext = next;
+                    if draw_indirect_count || loaded_extensions.khr_draw_indirect_count {
+                        f.drawIndirectCount = vk::TRUE;
+                    }
+                    if loaded_extensions.khr_sampler_mirror_clamp_to_edge {
+                        f.samplerMirrorClampToEdge = vk::TRUE;
+                    }
+                    if loaded_extensions.khr_buffer_device_address {
+                        f.bufferDeviceAddress = vk::TRUE;
+                    }
+                    if loaded_extensions.ext_host_query_reset {
+                        f.hostQueryReset = vk::TRUE;
+                    }
+                    if timeline_semaphore || loaded_extensions.khr_timeline_semaphore {
+                        f.timelineSemaphore = vk::TRUE;
+                    }
+                    if loaded_extensions.ext_descriptor_indexing {
+                        f.descriptorIndexing = supported.descriptorIndexing;
+                        f.shaderInputAttachmentArrayDynamicIndexing =
+                            supported.shaderInputAttachmentArrayDynamicIndexing;
+                        f.shaderUniformTexelBufferArrayDynamicIndexing =
+                            supported.shaderUniformTexelBufferArrayDynamicIndexing;
+                        f.shaderStorageTexelBufferArrayDynamicIndexing =
+                            supported.shaderStorageTexelBufferArrayDynamicIndexing;
+                        f.shaderUniformBufferArrayNonUniformIndexing =
+                            supported.shaderUniformBufferArrayNonUniformIndexing;
+                        f.shaderSampledImageArrayNonUniformIndexing =
+                            supported.shaderSampledImageArrayNonUniformIndexing;
+                        f.shaderStorageBufferArrayNonUniformIndexing =
+                            supported.shaderStorageBufferArrayNonUniformIndexing;
+                        f.shaderStorageImageArrayNonUniformIndexing =
+                            supported.shaderStorageImageArrayNonUniformIndexing;
+                        f.shaderInputAttachmentArrayNonUniformIndexing =
+                            supported.shaderInputAttachmentArrayNonUniformIndexing;
+                        f.shaderUniformTexelBufferArrayNonUniformIndexing =
+                            supported.shaderUniformTexelBufferArrayNonUniformIndexing;
+                        f.shaderStorageTexelBufferArrayNonUniformIndexing =
+                            supported.shaderStorageTexelBufferArrayNonUniformIndexing;
+                        f.descriptorBindingUniformBufferUpdateAfterBind =
+                            supported.descriptorBindingUniformBufferUpdateAfterBind;
+                        f.descriptorBindingSampledImageUpdateAfterBind =
+                            supported.descriptorBindingSampledImageUpdateAfterBind;
+                        f.descriptorBindingStorageImageUpdateAfterBind =
+                            supported.descriptorBindingStorageImageUpdateAfterBind;
+                        f.descriptorBindingStorageBufferUpdateAfterBind =
+                            supported.descriptorBindingStorageBufferUpdateAfterBind;
+                        f.descriptorBindingUniformTexelBufferUpdateAfterBind =
+                            supported.descriptorBindingUniformTexelBufferUpdateAfterBind;
+                        f.descriptorBindingStorageTexelBufferUpdateAfterBind =
+                            supported.descriptorBindingStorageTexelBufferUpdateAfterBind;
+                        f.descriptorBindingUpdateUnusedWhilePending =
+                            supported.descriptorBindingUpdateUnusedWhilePending;
+                        f.descriptorBindingPartiallyBound =
+                            supported.descriptorBindingPartiallyBound;
+                        f.descriptorBindingVariableDescriptorCount =
+                            supported.descriptorBindingVariableDescriptorCount;
+                        f.runtimeDescriptorArray = supported.runtimeDescriptorArray;
+                    }
+                    Some(f)
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+            if let Some(ref mut f) = vulkan12_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut transform_feedback_features = if loaded_extensions.ext_transform_feedback {
+                Some(vk::PhysicalDeviceTransformFeedbackFeaturesEXT {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TRANSFORM_FEEDBACK_FEATURES_EXT,
+                         pNext: next,
+                         transformFeedback: vk::TRUE,
+                         geometryStreams: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = transform_feedback_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut conditional_rendering_features = if loaded_extensions
+                .ext_conditional_rendering
+            {
+                Some(vk::PhysicalDeviceConditionalRenderingFeaturesEXT {
+                         sType:
+                             vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CONDITIONAL_RENDERING_FEATURES_EXT,
+                         pNext: next,
+                         conditionalRendering: vk::TRUE,
+                         inheritedConditionalRendering: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = conditional_rendering_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            // The `taskShader` feature is optional, so we only enable it if the physical device
+            // supports it.
+            let mut mesh_shader_features = if loaded_extensions.ext_mesh_shader {
+                let mut f: vk::PhysicalDeviceMeshShaderFeaturesEXT = mem::zeroed();
+                f.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_FEATURES_EXT;
+                phys.query_features2(&mut f as *mut _ as *mut _);
+                Some(vk::PhysicalDeviceMeshShaderFeaturesEXT {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_FEATURES_EXT,
+                         pNext: next,
+                         taskShader: f.taskShader,
+                         meshShader: f.meshShader,
+                         multiviewMeshShader: vk::FALSE,
+                         primitiveFragmentShadingRateMeshShader: vk::FALSE,
+                         meshShaderQueries: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = mesh_shader_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut ray_query_features = if loaded_extensions.khr_ray_query {
+                Some(vk::PhysicalDeviceRayQueryFeaturesKHR {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_QUERY_FEATURES_KHR,
+                         pNext: next,
+                         rayQuery: vk::TRUE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = ray_query_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut ray_tracing_pipeline_features = if loaded_extensions.khr_ray_tracing_pipeline {
+                Some(vk::PhysicalDeviceRayTracingPipelineFeaturesKHR {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_TRACING_PIPELINE_FEATURES_KHR,
+                         pNext: next,
+                         rayTracingPipeline: vk::TRUE,
+                         rayTracingPipelineShaderGroupHandleCaptureReplay: vk::FALSE,
+                         rayTracingPipelineShaderGroupHandleCaptureReplayMixed: vk::FALSE,
+                         rayTracingPipelineTraceRaysIndirect: vk::FALSE,
+                         rayTraversalPrimitiveCulling: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = ray_tracing_pipeline_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut acceleration_structure_features =
+                if loaded_extensions.khr_acceleration_structure {
+                    Some(vk::PhysicalDeviceAccelerationStructureFeaturesKHR {
+                             sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_ACCELERATION_STRUCTURE_FEATURES_KHR,
+                             pNext: next,
+                             accelerationStructure: vk::TRUE,
+                             accelerationStructureCaptureReplay: vk::FALSE,
+                             accelerationStructureIndirectBuild: vk::FALSE,
+                             accelerationStructureHostCommands: vk::FALSE,
+                             descriptorBindingAccelerationStructureUpdateAfterBind: vk::FALSE,
+                         })
+                } else {
+                    None
+                };
+            if let Some(ref mut f) = acceleration_structure_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut buffer_device_address_features =
+                if loaded_extensions.khr_buffer_device_address && vulkan12_features.is_none() {
+                    Some(vk::PhysicalDeviceBufferDeviceAddressFeatures {
+                             sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_BUFFER_DEVICE_ADDRESS_FEATURES,
+                             pNext: next,
+                             bufferDeviceAddress: vk::TRUE,
+                             bufferDeviceAddressCaptureReplay: vk::FALSE,
+                             bufferDeviceAddressMultiDevice: vk::FALSE,
+                         })
+                } else {
+                    None
+                };
+            if let Some(ref mut f) = buffer_device_address_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut ycbcr_conversion_features = if loaded_extensions.khr_sampler_ycbcr_conversion {
+                Some(vk::PhysicalDeviceSamplerYcbcrConversionFeatures {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLER_YCBCR_CONVERSION_FEATURES,
+                         pNext: next,
+                         samplerYcbcrConversion: vk::TRUE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = ycbcr_conversion_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut custom_border_color_features = if loaded_extensions.ext_custom_border_color {
+                Some(vk::PhysicalDeviceCustomBorderColorFeaturesEXT {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_FEATURES_EXT,
+                         pNext: next,
+                         customBorderColors: vk::TRUE,
+                         customBorderColorWithoutFormat: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = custom_border_color_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut host_query_reset_features =
+                if loaded_extensions.ext_host_query_reset && vulkan12_features.is_none() {
+                    Some(vk::PhysicalDeviceHostQueryResetFeaturesEXT {
+                             sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_HOST_QUERY_RESET_FEATURES_EXT,
+                             pNext: next,
+                             hostQueryReset: vk::TRUE,
+                         })
+                } else {
+                    None
+                };
+            if let Some(ref mut f) = host_query_reset_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut timeline_semaphore_features =
+                if loaded_extensions.khr_timeline_semaphore && vulkan12_features.is_none() {
+                    Some(vk::PhysicalDeviceTimelineSemaphoreFeaturesKHR {
+                             sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES_KHR,
+                             pNext: next,
+                             timelineSemaphore: vk::TRUE,
+                         })
+                } else {
+                    None
+                };
+            if let Some(ref mut f) = timeline_semaphore_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            let mut multiview_features = if loaded_extensions.khr_multiview {
+                Some(vk::PhysicalDeviceMultiviewFeaturesKHR {
+                         sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR,
+                         pNext: next,
+                         multiview: vk::TRUE,
+                         multiviewGeometryShader: vk::FALSE,
+                         multiviewTessellationShader: vk::FALSE,
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut f) = multiview_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            // The features of `VK_EXT_descriptor_indexing` are optional, so we enable the ones
+            // that the physical device supports instead of all of them.
+            let mut descriptor_indexing_features =
+                if loaded_extensions.ext_descriptor_indexing && vulkan12_features.is_none() {
+                    let mut f: vk::PhysicalDeviceDescriptorIndexingFeaturesEXT = mem::zeroed();
+                    f.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT;
+                    phys.query_features2(&mut f as *mut _ as *mut _);
+                    f.pNext = next;
+                    Some(f)
+                } else {
+                    None
+                };
+            if let Some(ref mut f) = descriptor_indexing_features {
+                next = f as *mut _ as *mut _;
+            }
+
+            // A device group with a single physical device is the same as no device group at all.
+            let group_devices = group
+                .map(|g| g.physical_devices().map(|p| p.internal_object()).collect())
+                .unwrap_or(SmallVec::<[_; 4]>::new());
+
+            let mut group_infos = if group_devices.len() >= 2 {
+                Some(vk::DeviceGroupDeviceCreateInfo {
+                         sType: vk::STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO,
+                         pNext: next as *const _,
+                         physicalDeviceCount: group_devices.len() as u32,
+                         pPhysicalDevices: group_devices.as_ptr(),
+                     })
+            } else {
+                None
+            };
+            if let Some(ref mut i) = group_infos {
+                next = i as *mut _ as *mut _;
+            }
+
             let infos = vk::DeviceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DEVICE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: next as *const _,
                 flags: 0, // reserved
                 queueCreateInfoCount: queues.len() as u32,
                 pQueueCreateInfos: queues.as_ptr(),
@@ -292,8 +746,120 @@ impl Device {
             output
         };
 
-        // loading the function pointers of the newly-created device
-        let vk = vk::DevicePointers::load(|name| unsafe {
+        let features = Features {
+            // Always enabled ; see above
+            robust_buffer_access: true,
+            ..requested_features.clone()
+        };
+
+        unsafe {
+            Ok(Device::from_raw_inner(phys,
+                                      group.map(|g| g.len() as u32).unwrap_or(1),
+                                      group.map(|g| g.len() < 2 || g.supports_subset_allocation())
+                                          .unwrap_or(true),
+                                      device,
+                                      features,
+                                      loaded_extensions,
+                                      output_queues,
+                                      true))
+        }
+    }
+
+    /// Builds a `Device` from a raw handle that was created outside of vulkano, for example by
+    /// another Vulkan wrapper or by a C library.
+    ///
+    /// `features`, `extensions` and `queue_families` must describe what was passed to
+    /// `vkCreateDevice`. Each element of `queue_families` corresponds to one queue, in the same
+    /// way as for `new`, and the queues of a family are assigned the ids `0`, `1`, `2`, ... in
+    /// order. The raw handle of a `Device` can be obtained with `VulkanObject::internal_object`.
+    ///
+    /// If `owned` is true, the device is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it after all the vulkano objects
+    /// that use it have been dropped.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid device created from `phys`, which must not span a device group.
+    /// - The parameters must match the ones used to create the device.
+    /// - If the `ext_descriptor_indexing` extension is enabled, all the features of this extension
+    ///   that the physical device supports must have been enabled, like `new` does.
+    /// - The queues must not be used outside of vulkano while the returned object is alive.
+    /// - If `owned` is true, the device must not be destroyed by anything else.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the queue families doesn't belong to the given device.
+    /// - Panics if a queue family has more queues than its `queues_count`.
+    ///
+    pub unsafe fn from_raw<'a, I, Ext>(phys: PhysicalDevice, handle: vk::Device,
+                                       features: &Features, extensions: Ext, queue_families: I,
+                                       owned: bool)
+                                       -> (Arc<Device>, QueuesIter)
+        where I: IntoIterator<Item = (QueueFamily<'a>, f32)>,
+              Ext: Into<RawDeviceExtensions>
+    {
+        let mut output_queues: SmallVec<[(u32, u32, f32); 8]> = SmallVec::new();
+        for (queue_family, priority) in queue_families {
+            assert_eq!(queue_family.physical_device().internal_object(),
+                       phys.internal_object());
+            let id = output_queues
+                .iter()
+                .filter(|&&(f, _, _)| f == queue_family.id())
+                .count();
+            assert!(id < queue_family.queues_count());
+            output_queues.push((queue_family.id(), id as u32, priority));
+        }
+
+        let extensions: DeviceExtensions = (&extensions.into()).into();
+        Device::from_raw_inner(phys, 1, true, handle, features.clone(), extensions, output_queues,
+                               owned)
+    }
+
+    unsafe fn from_raw_inner(phys: PhysicalDevice, device_group_size: u32,
+                             subset_allocation: bool, device: vk::Device, features: Features,
+                             extensions: DeviceExtensions,
+                             output_queues: SmallVec<[(u32, u32, f32); 8]>, owned: bool)
+                             -> (Arc<Device>, QueuesIter) {
+        let vk_i = phys.instance().pointers();
+
+        let mesh_shader_properties = if extensions.ext_mesh_shader {
+            Some(MeshShaderProperties::from_physical_device(phys))
+        } else {
+            None
+        };
+
+        // `new` enables the `taskShader` feature if it is supported.
+        let task_shader = if extensions.ext_mesh_shader {
+            let mut f: vk::PhysicalDeviceMeshShaderFeaturesEXT = mem::zeroed();
+            f.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_FEATURES_EXT;
+            phys.query_features2(&mut f as *mut _ as *mut _);
+            f.taskShader != 0
+        } else {
+            false
+        };
+
+        // `new` enables the `drawIndirectCount` and `timelineSemaphore` features of Vulkan 1.2
+        // if they are supported and the corresponding extensions aren't enabled.
+        let (draw_indirect_count, timeline_semaphore) =
+            if cmp::min(phys.instance().api_version(), phys.api_version()) >= Version::V1_2 {
+                let mut f: vk::PhysicalDeviceVulkan12Features = mem::zeroed();
+                f.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_VULKAN_1_2_FEATURES;
+                phys.query_features2(&mut f as *mut _ as *mut _);
+                (!extensions.khr_draw_indirect_count && f.drawIndirectCount != 0,
+                 !extensions.khr_timeline_semaphore && f.timelineSemaphore != 0)
+            } else {
+                (false, false)
+            };
+
+        // `new` enables all the supported features of the extension.
+        let descriptor_indexing_features = if extensions.ext_descriptor_indexing {
+            DescriptorIndexingFeatures::from_physical_device(phys)
+        } else {
+            DescriptorIndexingFeatures::none()
+        };
+
+        // loading the function pointers of the device
+        let vk = vk::DevicePointers::load(|name| {
                                               vk_i.GetDeviceProcAddr(device, name.as_ptr()) as
                                                   *const _
                                           });
@@ -307,17 +873,27 @@ impl Device {
                          standard_pool: Mutex::new(Weak::new()),
                          standard_descriptor_pool: Mutex::new(Weak::new()),
                          standard_command_pools: Mutex::new(Default::default()),
-                         features: Features {
-                             // Always enabled ; see above
-                             robust_buffer_access: true,
-                             ..requested_features.clone()
-                         },
-                         extensions: (&extensions).into(),
-                         active_queue_families: output_queues.iter().map(|&(q, _)| q).collect(),
+                         api_version: cmp::min(phys.instance().api_version(), phys.api_version()),
+                         device_group_size: device_group_size,
+                         subset_allocation: subset_allocation,
+                         features: features,
+                         mesh_shader_properties: mesh_shader_properties,
+                         task_shader: task_shader,
+                         draw_indirect_count: draw_indirect_count,
+                         timeline_semaphore: timeline_semaphore,
+                         descriptor_indexing_features: descriptor_indexing_features,
+                         extensions: extensions,
+                         active_queue_families: output_queues.iter().map(|&(q, _, _)| q).collect(),
                          allocation_count: Mutex::new(0),
+                         custom_border_color_sampler_count: Mutex::new(0),
                          fence_pool: Mutex::new(Vec::new()),
                          semaphore_pool: Mutex::new(Vec::new()),
                          event_pool: Mutex::new(Vec::new()),
+                         queues: Mutex::new(Vec::new()),
+                         deletion_queues: Mutex::new(Vec::new()),
+                         lost: AtomicBool::new(false),
+                         lost_callbacks: Mutex::new(Vec::new()),
+                         needs_destruction: owned,
                      });
 
         // Iterator for the produced queues.
@@ -327,7 +903,7 @@ impl Device {
             families_and_ids: output_queues,
         };
 
-        Ok((device, output_queues))
+        (device, output_queues)
     }
 
     /// Grants access to the pointers to the Vulkan functions of the device.
@@ -347,11 +923,130 @@ impl Device {
     /// of the device (either explicitely or implicitely, for example with a future's destructor)
     /// while this function is waiting.
     ///
-    pub unsafe fn wait(&self) -> Result<(), OomError> {
-        check_errors(self.vk.DeviceWaitIdle(self.device))?;
+    #[deprecated(note = "use Device::wait_idle instead")]
+    pub unsafe fn wait(&self) -> Result<(), WaitIdleError> {
+        self.check_lost()?;
+        self.track_lost(check_errors(self.vk.DeviceWaitIdle(self.device)))?;
+        Ok(())
+    }
+
+    /// Waits until all work on all the queues of this device has finished.
+    ///
+    /// Contrary to `wait`, this function is safe to call at any time. The queues of the device are
+    /// locked while waiting, which blocks the submissions made by other threads.
+    ///
+    /// This is typically useful when shutting down or before recreating a swapchain. Once this
+    /// function has returned, all the submissions are finished and calling `cleanup_finished()`
+    /// on the futures that were kept around (or dropping them) releases the resources they hold
+    /// without blocking. The entries of the deletion queues of the device are destroyed.
+    pub fn wait_idle(&self) -> Result<(), WaitIdleError> {
+        self.check_lost()?;
+
+        let queues = {
+            let mut queues = self.queues.lock().unwrap();
+            queues.retain(|q| q.upgrade().is_some());
+            queues.iter().filter_map(|q| q.upgrade()).collect::<SmallVec<[_; 8]>>()
+        };
+
+        unsafe {
+            // `vkDeviceWaitIdle` requires all the queues to be externally synchronized.
+            let _guards = queues
+                .iter()
+                .map(|q| q.queue.lock().unwrap())
+                .collect::<SmallVec<[_; 8]>>();
+            self.track_lost(check_errors(self.vk.DeviceWaitIdle(self.device)))?;
+        }
+
+        self.cleanup_deletion_queues();
         Ok(())
     }
 
+    // Registers a deletion queue, so that its finished entries are destroyed by
+    // `cleanup_deletion_queues`.
+    pub(crate) fn register_deletion_queue(&self, queue: &Arc<DeletionQueue>) {
+        let mut queues = self.deletion_queues.lock().unwrap();
+        queues.retain(|q| q.upgrade().is_some());
+        queues.push(Arc::downgrade(queue));
+    }
+
+    // Destroys the finished entries of the deletion queues of the device. Never blocks, unless a
+    // deletion queue is dropped by another thread in the meantime.
+    pub(crate) fn cleanup_deletion_queues(&self) {
+        // The lock is released before the cleanup, in case the destructor of an entry creates a
+        // new deletion queue.
+        let queues = {
+            let queues = self.deletion_queues.lock().unwrap();
+            queues.iter().filter_map(|q| q.upgrade()).collect::<SmallVec<[_; 4]>>()
+        };
+
+        for queue in queues.iter() {
+            queue.cleanup();
+        }
+    }
+
+    /// Returns true if a Vulkan function has reported that the device has been lost.
+    ///
+    /// Once the device is lost, submissions, presents and waits on this device fail immediately
+    /// with a `DeviceLost` error. See the module-level documentation for how to recover.
+    #[inline]
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Acquire)
+    }
+
+    /// Returns an error if the device has been lost.
+    #[inline]
+    pub fn check_lost(&self) -> Result<(), DeviceLostError> {
+        if self.is_lost() {
+            Err(DeviceLostError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Registers a function that is called once when the device is detected as lost.
+    ///
+    /// The function is called from the thread that detected the loss, in the middle of the
+    /// operation that failed. It should only notify the rest of the application, for example by
+    /// setting a flag that makes the render loop recreate the device, and must not use the device.
+    ///
+    /// If the device is already lost, the function is called immediately.
+    pub fn on_lost<F>(&self, callback: F)
+        where F: Fn() + Send + Sync + 'static
+    {
+        let mut callbacks = self.lost_callbacks.lock().unwrap();
+        if self.is_lost() {
+            drop(callbacks);
+            callback();
+        } else {
+            callbacks.push(Box::new(callback));
+        }
+    }
+
+    /// Marks the device as lost if `result` is `Error::DeviceLost`, then returns `result`.
+    pub(crate) fn track_lost<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        if let Err(Error::DeviceLost) = result {
+            self.mark_lost();
+        }
+
+        result
+    }
+
+    fn mark_lost(&self) {
+        // The lock ensures that callbacks registered concurrently are either called here or by
+        // `on_lost`, but never twice.
+        let callbacks = {
+            let mut callbacks = self.lost_callbacks.lock().unwrap();
+            if self.lost.swap(true, Ordering::AcqRel) {
+                return;
+            }
+            mem::replace(&mut *callbacks, Vec::new())
+        };
+
+        for callback in callbacks {
+            callback();
+        }
+    }
+
     /// Returns the instance used to create this device.
     #[inline]
     pub fn instance(&self) -> &Arc<Instance> {
@@ -389,6 +1084,127 @@ impl Device {
         &self.extensions
     }
 
+    /// Returns the mesh shading limits of the physical device, or `None` if the `ext_mesh_shader`
+    /// extension isn't enabled.
+    #[inline]
+    pub fn mesh_shader_properties(&self) -> Option<&MeshShaderProperties> {
+        self.mesh_shader_properties.as_ref()
+    }
+
+    /// Returns true if task shaders can be used on this device.
+    ///
+    /// This requires the `ext_mesh_shader` extension, and the `taskShader` feature to be
+    /// supported by the physical device.
+    #[inline]
+    pub fn supports_task_shaders(&self) -> bool {
+        self.task_shader
+    }
+
+    /// Returns the features of the `ext_descriptor_indexing` extension that are enabled on the
+    /// device. All the features are false if the extension isn't enabled.
+    #[inline]
+    pub fn descriptor_indexing_features(&self) -> &DescriptorIndexingFeatures {
+        &self.descriptor_indexing_features
+    }
+
+    /// Returns the version of Vulkan used by the device.
+    ///
+    /// This is the lowest of the version of the instance and the version supported by the
+    /// physical device. Functionalities that have been promoted to core in this version can be
+    /// used without enabling the corresponding extension.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
+    /// Returns the number of physical devices the device spans.
+    ///
+    /// This is 1 unless the device was created with `with_group` from a group of more than one
+    /// physical device. Device masks can only contain the bits `0 .. device_group_size()`.
+    #[inline]
+    pub fn device_group_size(&self) -> u32 {
+        self.device_group_size
+    }
+
+    /// Returns true if memory allocations can be made on a subset of the physical devices of the
+    /// device group.
+    ///
+    /// If false, the device mask passed to `DeviceMemory::alloc_with_device_mask` must contain
+    /// all the devices of the group. See also `PhysicalDeviceGroup::supports_subset_allocation`.
+    #[inline]
+    pub fn supports_subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
+
+    /// Returns true if device masks can be used with this device.
+    ///
+    /// This is the case if the device uses Vulkan 1.1 or above, or if the `khr_device_group`
+    /// extension has been enabled.
+    #[inline]
+    pub fn supports_device_masks(&self) -> bool {
+        self.api_version >= Version::V1_1 || self.extensions.khr_device_group
+    }
+
+    /// Returns true if the number of draws of indirect draw commands can be read from a buffer.
+    ///
+    /// This is the case if the `khr_draw_indirect_count` extension has been enabled, or if the
+    /// device uses Vulkan 1.2 or above and supports the `drawIndirectCount` feature.
+    #[inline]
+    pub fn supports_draw_indirect_count(&self) -> bool {
+        self.extensions.khr_draw_indirect_count || self.draw_indirect_count
+    }
+
+    /// Returns true if timeline semaphores can be created on this device.
+    ///
+    /// This is the case if the `khr_timeline_semaphore` extension has been enabled, or if the
+    /// device uses Vulkan 1.2 or above and supports the `timelineSemaphore` feature.
+    #[inline]
+    pub fn supports_timeline_semaphores(&self) -> bool {
+        self.extensions.khr_timeline_semaphore || self.timeline_semaphore
+    }
+
+    // Returns true if `vkGetBufferMemoryRequirements2` and `vkGetImageMemoryRequirements2` can be
+    // used, either from core Vulkan 1.1 or from the `khr_get_memory_requirements2` extension.
+    #[inline]
+    pub(crate) fn supports_memory_requirements2(&self) -> bool {
+        self.api_version >= Version::V1_1 || self.extensions.khr_get_memory_requirements2
+    }
+
+    // Returns true if dedicated allocations are supported, either from core Vulkan 1.1 or from
+    // the `khr_dedicated_allocation` extension.
+    #[inline]
+    pub(crate) fn supports_dedicated_allocation(&self) -> bool {
+        self.api_version >= Version::V1_1 || self.extensions.khr_dedicated_allocation
+    }
+
+    // Calls the core or the extension version of `vkGetBufferMemoryRequirements2`.
+    //
+    // Must only be called if `supports_memory_requirements2` returns true.
+    pub(crate) unsafe fn get_buffer_memory_requirements2(
+        &self, infos: &vk::BufferMemoryRequirementsInfo2KHR,
+        output: &mut vk::MemoryRequirements2KHR) {
+        debug_assert!(self.supports_memory_requirements2());
+        if self.api_version >= Version::V1_1 {
+            self.vk.GetBufferMemoryRequirements2(self.device, infos, output);
+        } else {
+            self.vk.GetBufferMemoryRequirements2KHR(self.device, infos, output);
+        }
+    }
+
+    // Calls the core or the extension version of `vkGetImageMemoryRequirements2`.
+    //
+    // Must only be called if `supports_memory_requirements2` returns true.
+    pub(crate) unsafe fn get_image_memory_requirements2(
+        &self, infos: &vk::ImageMemoryRequirementsInfo2KHR,
+        output: &mut vk::MemoryRequirements2KHR) {
+        debug_assert!(self.supports_memory_requirements2());
+        if self.api_version >= Version::V1_1 {
+            self.vk.GetImageMemoryRequirements2(self.device, infos, output);
+        } else {
+            self.vk.GetImageMemoryRequirements2KHR(self.device, infos, output);
+        }
+    }
+
     /// Returns the standard memory pool used by default if you don't provide any other pool.
     pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
         let mut pool = me.standard_pool.lock().unwrap();
@@ -456,6 +1272,14 @@ impl Device {
         &self.allocation_count
     }
 
+    /// Used to track the number of samplers with a custom border color that are alive, which
+    /// can't exceed `maxCustomBorderColorSamplers`.
+    ///
+    /// Warning: You should never modify this value, except in the `sampler` module
+    pub(crate) fn custom_border_color_sampler_count(&self) -> &Mutex<u32> {
+        &self.custom_border_color_sampler_count
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<vk::Fence>> {
         &self.fence_pool
     }
@@ -468,10 +1292,22 @@ impl Device {
         &self.event_pool
     }
 
+    /// Returns true if objects of this device can be given a name with `set_object_name`.
+    ///
+    /// This requires either the `VK_EXT_debug_utils` instance extension or the
+    /// `VK_EXT_debug_marker` device extension.
+    #[inline]
+    pub fn supports_object_names(&self) -> bool {
+        self.instance.loaded_extensions().ext_debug_utils || self.extensions.ext_debug_marker
+    }
+
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
+    /// Uses `VK_EXT_debug_utils` if it is enabled on the instance, and `VK_EXT_debug_marker`
+    /// otherwise.
+    ///
     /// # Panics
-    /// * If the `VK_EXT_debug_marker` device extension is not loaded.
+    /// * If neither `VK_EXT_debug_utils` nor `VK_EXT_debug_marker` is loaded.
     /// * If `object` is not owned by this device.
     pub fn set_object_name<T: VulkanObject + DeviceOwned>(&self, object: &T, name: &CStr) -> Result<(), OomError> {
         assert!(object.device().internal_object() == self.internal_object());
@@ -481,23 +1317,94 @@ impl Device {
     /// Assigns a human-readable name to `object` for debugging purposes.
     ///
     /// # Panics
-    /// * If the `VK_EXT_debug_marker` device extension is not loaded.
+    /// * If neither `VK_EXT_debug_utils` nor `VK_EXT_debug_marker` is loaded.
     ///
     /// # Safety
     /// `object` must be a Vulkan handle owned by this device, and its type must be accurately described by `ty`.
     pub unsafe fn set_object_name_raw(&self, ty: vk::DebugReportObjectTypeEXT, object: u64, name: &CStr) -> Result<(), OomError> {
-        let info = vk::DebugMarkerObjectNameInfoEXT {
-            sType: vk::STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_NAME_INFO_EXT,
-            pNext: ptr::null(),
-            objectType: ty,
-            object: object,
-            name: name.as_ptr(),
-        };
-        check_errors(self.vk.DebugMarkerSetObjectNameEXT(self.device, &info))?;
+        assert!(self.supports_object_names());
+
+        if self.instance.loaded_extensions().ext_debug_utils {
+            let info = vk::DebugUtilsObjectNameInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT,
+                pNext: ptr::null(),
+                objectType: object_type(ty),
+                objectHandle: object,
+                pObjectName: name.as_ptr(),
+            };
+            check_errors(self.vk.SetDebugUtilsObjectNameEXT(self.device, &info))?;
+        } else {
+            let info = vk::DebugMarkerObjectNameInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_NAME_INFO_EXT,
+                pNext: ptr::null(),
+                objectType: ty,
+                object: object,
+                name: name.as_ptr(),
+            };
+            check_errors(self.vk.DebugMarkerSetObjectNameEXT(self.device, &info))?;
+        }
+
         Ok(())
     }
 }
 
+// Turns a `DebugReportObjectTypeEXT` into the corresponding `VkObjectType` expected by
+// `VK_EXT_debug_utils`. The values of the core object types are the same in both enums.
+fn object_type(ty: vk::DebugReportObjectTypeEXT) -> u32 {
+    match ty {
+        vk::DEBUG_REPORT_OBJECT_TYPE_SURFACE_KHR_EXT => 1000000000,
+        vk::DEBUG_REPORT_OBJECT_TYPE_SWAPCHAIN_KHR_EXT => 1000001000,
+        vk::DEBUG_REPORT_OBJECT_TYPE_DISPLAY_KHR_EXT => 1000002000,
+        vk::DEBUG_REPORT_OBJECT_TYPE_DISPLAY_MODE_KHR_EXT => 1000002001,
+        vk::DEBUG_REPORT_OBJECT_TYPE_DEBUG_REPORT_CALLBACK_EXT_EXT => 1000011000,
+        ty => ty,
+    }
+}
+
+/// Gives a human-readable name to an object, so that the messages of the validation layers and
+/// tools such as RenderDoc can refer to it.
+///
+/// This trait is implemented on all the objects that belong to a device. Contrary to
+/// `Device::set_object_name`, the name is silently ignored if neither `VK_EXT_debug_utils` nor
+/// `VK_EXT_debug_marker` is enabled, so that the names can be set unconditionally.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::buffer::CpuAccessibleBuffer;
+/// use vulkano::buffer::BufferAccess;
+/// use vulkano::device::SetDebugName;
+///
+/// # let buffer: Arc<CpuAccessibleBuffer<u32>> = return;
+/// buffer.inner().buffer.set_debug_name("vertex buffer").unwrap();
+/// ```
+pub trait SetDebugName {
+    /// Assigns `name` to the object.
+    ///
+    /// If `name` contains a null character, only the part before it is used.
+    fn set_debug_name(&self, name: &str) -> Result<(), OomError>;
+}
+
+impl<T> SetDebugName for T
+    where T: VulkanObject + DeviceOwned
+{
+    fn set_debug_name(&self, name: &str) -> Result<(), OomError> {
+        let device = self.device();
+        if !device.supports_object_names() {
+            return Ok(());
+        }
+
+        let name = match name.find('\0') {
+            Some(pos) => &name[.. pos],
+            None => name,
+        };
+        let name = CString::new(name).unwrap();
+
+        device.set_object_name(self, &name)
+    }
+}
+
 impl fmt::Debug for Device {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -529,7 +1436,9 @@ impl Drop for Device {
             for &raw_event in self.event_pool.lock().unwrap().iter() {
                 self.vk.DestroyEvent(self.device, raw_event, ptr::null());
             }
-            self.vk.DestroyDevice(self.device, ptr::null());
+            if self.needs_destruction {
+                self.vk.DestroyDevice(self.device, ptr::null());
+            }
         }
     }
 }
@@ -559,7 +1468,7 @@ unsafe impl<T> DeviceOwned for T
 pub struct QueuesIter {
     next_queue: usize,
     device: Arc<Device>,
-    families_and_ids: SmallVec<[(u32, u32); 8]>,
+    families_and_ids: SmallVec<[(u32, u32, f32); 8]>,
 }
 
 unsafe impl DeviceOwned for QueuesIter {
@@ -573,7 +1482,7 @@ impl Iterator for QueuesIter {
 
     fn next(&mut self) -> Option<Arc<Queue>> {
         unsafe {
-            let &(family, id) = match self.families_and_ids.get(self.next_queue) {
+            let &(family, id, priority) = match self.families_and_ids.get(self.next_queue) {
                 Some(a) => a,
                 None => return None,
             };
@@ -585,12 +1494,20 @@ impl Iterator for QueuesIter {
                 .vk
                 .GetDeviceQueue(self.device.device, family, id, &mut output);
 
-            Some(Arc::new(Queue {
-                              queue: Mutex::new(output),
-                              device: self.device.clone(),
-                              family: family,
-                              id: id,
-                          }))
+            let queue = Arc::new(Queue {
+                                     queue: Mutex::new(output),
+                                     device: self.device.clone(),
+                                     family: family,
+                                     id: id,
+                                     priority: priority,
+                                 });
+
+            self.device
+                .queues
+                .lock()
+                .unwrap()
+                .push(Arc::downgrade(&queue));
+            Some(queue)
         }
     }
 
@@ -626,6 +1543,8 @@ pub enum DeviceCreationError {
     OutOfHostMemory,
     /// There is no memory available on the device (ie. video memory).
     OutOfDeviceMemory,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for DeviceCreationError {
@@ -658,6 +1577,8 @@ impl error::Error for DeviceCreationError {
                 "you have reached the limit to the number of devices that can be created from the
                  same physical device"
             },
+            DeviceCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 }
@@ -680,11 +1601,75 @@ impl From<Error> for DeviceCreationError {
             Error::ExtensionNotPresent => DeviceCreationError::ExtensionNotPresent,
             Error::FeatureNotPresent => DeviceCreationError::FeatureNotPresent,
             Error::TooManyObjects => DeviceCreationError::TooManyObjects,
-            _ => panic!("Unexpected error value: {}", err as i32),
+            err => DeviceCreationError::UnexpectedResult(err.result()),
         }
     }
 }
 
+/// Error returned when the device has been lost.
+///
+/// See the module-level documentation for how to recover from a device loss.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DeviceLostError;
+
+impl error::Error for DeviceLostError {
+    #[inline]
+    fn description(&self) -> &str {
+        "the connection to the device has been lost"
+    }
+}
+
+impl fmt::Display for DeviceLostError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+/// Error that can be returned when waiting for a device or a queue to become idle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WaitIdleError {
+    /// Not enough memory to complete the wait.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for WaitIdleError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            WaitIdleError::OomError(_) => "not enough memory",
+            WaitIdleError::DeviceLost => "the connection to the device has been lost",
+            WaitIdleError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            WaitIdleError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(WaitIdleError, OomError {
+    DeviceLost => DeviceLost,
+});
+
+impl From<DeviceLostError> for WaitIdleError {
+    #[inline]
+    fn from(_: DeviceLostError) -> WaitIdleError {
+        WaitIdleError::DeviceLost
+    }
+}
+
 /// Represents a queue where commands can be submitted.
 // TODO: should use internal synchronization?
 #[derive(Debug)]
@@ -693,6 +1678,7 @@ pub struct Queue {
     device: Arc<Device>,
     family: u32,
     id: u32, // id within family
+    priority: f32,
 }
 
 impl Queue {
@@ -724,17 +1710,92 @@ impl Queue {
         self.id
     }
 
+    /// Returns the priority that was requested for this queue when creating the device.
+    #[inline]
+    pub fn priority(&self) -> f32 {
+        self.priority
+    }
+
+    /// Returns true if graphics operations can be submitted to this queue.
+    #[inline]
+    pub fn supports_graphics(&self) -> bool {
+        self.family().supports_graphics()
+    }
+
+    /// Returns true if compute operations can be submitted to this queue.
+    #[inline]
+    pub fn supports_compute(&self) -> bool {
+        self.family().supports_compute()
+    }
+
+    /// Returns true if transfer operations can be submitted to this queue.
+    ///
+    /// See `QueueFamily::supports_transfers` for more information.
+    #[inline]
+    pub fn supports_transfers(&self) -> bool {
+        self.family().supports_transfers()
+    }
+
+    /// Returns true if sparse binding operations can be submitted to this queue.
+    #[inline]
+    pub fn supports_sparse_binding(&self) -> bool {
+        self.family().supports_sparse_binding()
+    }
+
+    /// Returns true if this queue can present images to `surface`.
+    ///
+    /// Shortcut for `surface.is_supported(queue.family())`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue and the surface don't belong to the same instance.
+    ///
+    #[inline]
+    pub fn supports_surface<W>(&self, surface: &Surface<W>) -> Result<bool, CapabilitiesError> {
+        surface.is_supported(self.family())
+    }
+
+    /// Submits command buffers to this queue in a single `vkQueueSubmit` call.
+    ///
+    /// Each batch of `builder` can contain multiple command buffers, semaphores to wait upon
+    /// with their destination stages and semaphores to signal. The fence of `builder`, if any,
+    /// is signaled once all the batches have finished executing.
+    ///
+    /// This is equivalent to calling `submit` on the builder. The safety requirements have
+    /// already been checked when the builder was filled.
+    #[inline]
+    pub fn submit(&self, builder: SubmitCommandBufferBuilder)
+                  -> Result<(), SubmitCommandBufferError> {
+        builder.submit(self)
+    }
+
     /// Waits until all work on this queue has finished.
     ///
     /// Just like `Device::wait()`, you shouldn't have to call this function in a typical program.
+    #[deprecated(note = "use Queue::wait_idle instead")]
+    #[inline]
+    pub fn wait(&self) -> Result<(), WaitIdleError> {
+        self.wait_idle()
+    }
+
+    /// Waits until all work on this queue has finished.
+    ///
+    /// Once this function has returned, calling `cleanup_finished()` on the futures that were
+    /// submitted to this queue (or dropping them) releases the resources they hold without
+    /// blocking. The finished entries of the deletion queues of the device are destroyed. See also
+    /// `Device::wait_idle`.
     #[inline]
-    pub fn wait(&self) -> Result<(), OomError> {
+    pub fn wait_idle(&self) -> Result<(), WaitIdleError> {
+        self.device.check_lost()?;
+
         unsafe {
             let vk = self.device.pointers();
             let queue = self.queue.lock().unwrap();
-            check_errors(vk.QueueWaitIdle(*queue))?;
-            Ok(())
+            self.device.track_lost(check_errors(vk.QueueWaitIdle(*queue)))?;
         }
+
+        self.device.cleanup_deletion_queues();
+        Ok(())
     }
 }
 
@@ -756,12 +1817,28 @@ unsafe impl SynchronizedVulkanObject for Queue {
 
 #[cfg(test)]
 mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use command_buffer::submit::SubmitCommandBufferBuilder;
     use device::Device;
     use device::DeviceCreationError;
     use device::DeviceExtensions;
     use features::Features;
     use instance;
+    use Error;
+    use VulkanObject;
+    use device::WaitIdleError;
     use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+    use sync::Fence;
+    use sync::FenceWaitError;
+    use sync::GpuFuture;
+    use sync::PipelineStages;
+    use sync::Semaphore;
 
     #[test]
     fn one_ref() {
@@ -769,6 +1846,84 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (borrowed, mut queues) = unsafe {
+            Device::from_raw(device.physical_device(),
+                             device.internal_object(),
+                             device.enabled_features(),
+                             device.loaded_extensions(),
+                             Some((queue.family(), 0.5)),
+                             false)
+        };
+        assert_eq!(borrowed.internal_object(), device.internal_object());
+        assert_eq!(borrowed.loaded_extensions(), device.loaded_extensions());
+
+        let borrowed_queue = queues.next().unwrap();
+        assert!(queues.next().is_none());
+        assert_eq!(borrowed_queue.family().id(), queue.family().id());
+        borrowed_queue.wait().unwrap();
+
+        // The device must still be usable after the borrowed object is dropped.
+        drop(borrowed_queue);
+        drop(borrowed);
+        CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        unsafe { device.wait().unwrap(); }
+    }
+
+    #[test]
+    fn wait_idle_then_cleanup() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+
+        let cb = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+            .unwrap()
+            .fill_buffer(buffer.clone(), 12)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut future = cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        device.wait_idle().unwrap();
+        future.cleanup_finished();
+        assert_eq!(Arc::strong_count(&buffer), 1);
+
+        queue.wait_idle().unwrap();
+    }
+
+    #[test]
+    fn queue_submit_batches() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let semaphore = Semaphore::alloc(device.clone()).unwrap();
+        let fence = Fence::alloc(device.clone()).unwrap();
+
+        let mut builder = SubmitCommandBufferBuilder::new();
+        unsafe {
+            builder.add_signal_semaphore(&semaphore);
+            builder.next_batch();
+            builder.add_wait_semaphore(&semaphore,
+                                       PipelineStages {
+                                           all_commands: true,
+                                           ..PipelineStages::none()
+                                       });
+            builder.set_fence_signal(&fence);
+        }
+        assert_eq!(builder.num_batches(), 2);
+
+        queue.submit(builder).unwrap();
+        fence.wait(Some(Duration::from_secs(5))).unwrap();
+        assert!(fence.ready().unwrap());
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -840,4 +1995,114 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn debug_name_ignored_without_extension() {
+        use device::SetDebugName;
+        use sync::Fence;
+
+        let (device, _) = gfx_dev_and_queue!();
+        assert!(!device.supports_object_names());
+
+        let fence = Fence::alloc(device.clone()).unwrap();
+        fence.set_debug_name("fence").unwrap();
+    }
+
+    #[test]
+    fn api_version() {
+        let (device, _) = gfx_dev_and_queue!();
+        assert!(device.api_version() <= device.instance().api_version());
+        assert!(device.api_version() <= device.physical_device().api_version());
+    }
+
+    #[test]
+    fn multiple_queues_with_priorities() {
+        let instance = instance!();
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = match physical.queue_families().find(|q| q.queues_count() >= 2) {
+            Some(f) => f,
+            None => return,
+        };
+
+        let (_, queues) = Device::new(physical,
+                                      &Features::none(),
+                                      &DeviceExtensions::none(),
+                                      vec![(family, 1.0), (family, 0.25)])
+            .unwrap();
+        let queues = queues.collect::<Vec<_>>();
+
+        assert_eq!(queues.len(), 2);
+        assert_eq!(queues[0].family().id(), family.id());
+        assert_eq!(queues[0].id_within_family(), 0);
+        assert_eq!(queues[0].priority(), 1.0);
+        assert_eq!(queues[1].family().id(), family.id());
+        assert_eq!(queues[1].id_within_family(), 1);
+        assert_eq!(queues[1].priority(), 0.25);
+        assert_eq!(queues[0].supports_graphics(), family.supports_graphics());
+        assert_eq!(queues[0].supports_compute(), family.supports_compute());
+    }
+
+    #[test]
+    fn device_from_group() {
+        let instance = instance!();
+        if !instance::PhysicalDeviceGroup::is_supported(&instance) {
+            return;
+        }
+
+        let group = match instance::PhysicalDeviceGroup::enumerate(&instance)
+            .unwrap()
+            .into_iter()
+            .next() {
+            Some(g) => g,
+            None => return,
+        };
+
+        let family = group.physical_device(0).unwrap().queue_families().next().unwrap();
+        let (device, _) = Device::with_group(&group,
+                                             &Features::none(),
+                                             &DeviceExtensions::none(),
+                                             Some((family, 1.0)))
+            .unwrap();
+
+        assert_eq!(device.device_group_size(), group.len() as u32);
+        assert_eq!(device.supports_subset_allocation(),
+                   group.len() < 2 || group.supports_subset_allocation());
+        assert_eq!(device.physical_device().index(),
+                   group.physical_device(0).unwrap().index());
+    }
+
+    #[test]
+    fn lost_device_fails_fast() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let fence = Fence::alloc(device.clone()).unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        {
+            let calls = calls.clone();
+            device.on_lost(move || { calls.fetch_add(1, Ordering::SeqCst); });
+        }
+
+        assert!(!device.is_lost());
+        assert!(device.check_lost().is_ok());
+
+        let _ = device.track_lost::<()>(Err(Error::DeviceLost));
+        let _ = device.track_lost::<()>(Err(Error::DeviceLost));
+        assert!(device.is_lost());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        assert_eq!(device.wait_idle(), Err(WaitIdleError::DeviceLost));
+        assert_eq!(queue.wait_idle(), Err(WaitIdleError::DeviceLost));
+        assert_eq!(fence.wait(None), Err(FenceWaitError::DeviceLostError));
+
+        // Callbacks registered after the loss are called immediately.
+        {
+            let calls = calls.clone();
+            device.on_lost(move || { calls.fetch_add(1, Ordering::SeqCst); });
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 }
@@ -78,6 +78,36 @@ macro_rules! features {
                 $((self.$name == true || other.$name == false))&&+
             }
 
+            /// Starts building a `Features` object that has only the features enabled with the
+            /// methods of the builder.
+            ///
+            /// ```
+            /// use vulkano::instance::Features;
+            ///
+            /// let features = Features::builder()
+            ///     .geometry_shader()
+            ///     .wide_lines()
+            ///     .build();
+            ///
+            /// assert!(features.geometry_shader);
+            /// assert!(!features.tessellation_shader);
+            /// ```
+            #[inline]
+            pub fn builder() -> FeaturesBuilder {
+                FeaturesBuilder { features: Features::none() }
+            }
+
+            /// Builds a `Features` that is the union of `self` and another `Features` object.
+            ///
+            /// The result's field will be true if it is true in `self` or in `other`.
+            pub fn union(&self, other: &Features) -> Features {
+                Features {
+                    $(
+                        $name: self.$name || other.$name,
+                    )+
+                }
+            }
+
             /// Builds a `Features` that is the intersection of `self` and another `Features`
             /// object.
             ///
@@ -117,6 +147,31 @@ macro_rules! features {
                 }
             }
         }
+
+        /// Builder for a `Features` object. Created with `Features::builder()`.
+        ///
+        /// Each method enables the feature of the same name.
+        #[derive(Debug, Clone)]
+        #[allow(missing_docs)]
+        pub struct FeaturesBuilder {
+            features: Features,
+        }
+
+        impl FeaturesBuilder {
+            $(
+                #[inline]
+                pub fn $name(mut self) -> FeaturesBuilder {
+                    self.features.$name = true;
+                    self
+                }
+            )+
+
+            /// Builds the `Features` object.
+            #[inline]
+            pub fn build(self) -> Features {
+                self.features
+            }
+        }
     )
 }
 
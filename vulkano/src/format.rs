@@ -174,7 +174,7 @@ pub unsafe trait AcceptsPixels<T> {
 }
 
 macro_rules! formats {
-    ($($name:ident => $vk:ident [$sz:expr] [$($f_ty:tt)*] {$($d_ty:tt)*},)+) => (
+    ($($name:ident => $vk:ident [$sz:expr] [$($f_ty:tt)*] {$($d_ty:tt)*} $num:ident,)+) => (
         /// An enumeration of all the possible formats.
         #[derive(Copy, Clone, Debug, PartialEq, Eq)]
         #[repr(u32)]
@@ -221,6 +221,40 @@ macro_rules! formats {
                     )+
                 }
             }
+
+            /// Returns how the values of this format are stored and interpreted.
+            ///
+            /// For depth-stencil formats, this is the numeric type of the depth component.
+            #[inline]
+            pub fn numeric_type(&self) -> NumericType {
+                match *self {
+                    $(
+                        Format::$name => NumericType::$num,
+                    )+
+                }
+            }
+
+            /// Returns the compression scheme of this format, or `None` if the format isn't
+            /// compressed.
+            #[inline]
+            pub fn compression(&self) -> Option<FormatCompression> {
+                match *self {
+                    $(
+                        Format::$name => formats!(__inner_compression__ $($f_ty)*),
+                    )+
+                }
+            }
+
+            /// Returns the number of components of a color format, or `None` for depth, stencil
+            /// and compressed formats.
+            #[inline]
+            pub fn components(&self) -> Option<u32> {
+                match *self {
+                    $(
+                        Format::$name => formats!(__inner_components__ $($f_ty)*),
+                    )+
+                }
+            }
         }
 
         $(
@@ -396,6 +430,22 @@ macro_rules! formats {
     (__inner_ty__ $name:ident depthstencil) => { FormatTy::DepthStencil };
     (__inner_ty__ $name:ident compressed=$f:tt) => { FormatTy::Compressed };
 
+    (__inner_compression__ compressed=texture_compression_bc) => {
+        Some(FormatCompression::Bc)
+    };
+    (__inner_compression__ compressed=texture_compression_etc2) => {
+        Some(FormatCompression::Etc2)
+    };
+    (__inner_compression__ compressed=texture_compression_astc_ldr) => {
+        Some(FormatCompression::AstcLdr)
+    };
+    (__inner_compression__ $($f_ty:tt)*) => { None };
+
+    (__inner_components__ float=$num:tt) => { Some($num) };
+    (__inner_components__ uint=$num:tt) => { Some($num) };
+    (__inner_components__ sint=$num:tt) => { Some($num) };
+    (__inner_components__ $($f_ty:tt)*) => { None };
+
 
     (__inner_strongstorage__ $name:ident [$ty:ty; $dim:expr]) => {
         formats!(__inner_strongstorage_common__ $name [$ty; $dim]);
@@ -420,190 +470,369 @@ macro_rules! formats {
 }
 
 formats! {
-    R4G4UnormPack8 => FORMAT_R4G4_UNORM_PACK8 [Some(1)] [float=2] {u8},
-    R4G4B4A4UnormPack16 => FORMAT_R4G4B4A4_UNORM_PACK16 [Some(2)] [float=4] {u16},
-    B4G4R4A4UnormPack16 => FORMAT_B4G4R4A4_UNORM_PACK16 [Some(2)] [float=4] {u16},
-    R5G6B5UnormPack16 => FORMAT_R5G6B5_UNORM_PACK16 [Some(2)] [float=3] {u16},
-    B5G6R5UnormPack16 => FORMAT_B5G6R5_UNORM_PACK16 [Some(2)] [float=3] {u16},
-    R5G5B5A1UnormPack16 => FORMAT_R5G5B5A1_UNORM_PACK16 [Some(2)] [float=4] {u16},
-    B5G5R5A1UnormPack16 => FORMAT_B5G5R5A1_UNORM_PACK16 [Some(2)] [float=4] {u16},
-    A1R5G5B5UnormPack16 => FORMAT_A1R5G5B5_UNORM_PACK16 [Some(2)] [float=4] {u16},
-    R8Unorm => FORMAT_R8_UNORM [Some(1)] [float=1] {u8},
-    R8Snorm => FORMAT_R8_SNORM [Some(1)] [float=1] {i8},
-    R8Uscaled => FORMAT_R8_USCALED [Some(1)] [float=1] {u8},
-    R8Sscaled => FORMAT_R8_SSCALED [Some(1)] [float=1] {i8},
-    R8Uint => FORMAT_R8_UINT [Some(1)] [uint=1] {u8},
-    R8Sint => FORMAT_R8_SINT [Some(1)] [sint=1] {i8},
-    R8Srgb => FORMAT_R8_SRGB [Some(1)] [float=1] {u8},
-    R8G8Unorm => FORMAT_R8G8_UNORM [Some(2)] [float=2] {[u8; 2]},
-    R8G8Snorm => FORMAT_R8G8_SNORM [Some(2)] [float=2] {[i8; 2]},
-    R8G8Uscaled => FORMAT_R8G8_USCALED [Some(2)] [float=2] {[u8; 2]},
-    R8G8Sscaled => FORMAT_R8G8_SSCALED [Some(2)] [float=2] {[i8; 2]},
-    R8G8Uint => FORMAT_R8G8_UINT [Some(2)] [uint=2] {[u8; 2]},
-    R8G8Sint => FORMAT_R8G8_SINT [Some(2)] [sint=2] {[i8; 2]},
-    R8G8Srgb => FORMAT_R8G8_SRGB [Some(2)] [float=2] {[u8; 2]},
-    R8G8B8Unorm => FORMAT_R8G8B8_UNORM [Some(3)] [float=3] {[u8; 3]},
-    R8G8B8Snorm => FORMAT_R8G8B8_SNORM [Some(3)] [float=3] {[i8; 3]},
-    R8G8B8Uscaled => FORMAT_R8G8B8_USCALED [Some(3)] [float=3] {[u8; 3]},
-    R8G8B8Sscaled => FORMAT_R8G8B8_SSCALED [Some(3)] [float=3] {[i8; 3]},
-    R8G8B8Uint => FORMAT_R8G8B8_UINT [Some(3)] [uint=3] {[u8; 3]},
-    R8G8B8Sint => FORMAT_R8G8B8_SINT [Some(3)] [sint=3] {[i8; 3]},
-    R8G8B8Srgb => FORMAT_R8G8B8_SRGB [Some(3)] [float=3] {[u8; 3]},
-    B8G8R8Unorm => FORMAT_B8G8R8_UNORM [Some(3)] [float=3] {[u8; 3]},
-    B8G8R8Snorm => FORMAT_B8G8R8_SNORM [Some(3)] [float=3] {[i8; 3]},
-    B8G8R8Uscaled => FORMAT_B8G8R8_USCALED [Some(3)] [float=3] {[u8; 3]},
-    B8G8R8Sscaled => FORMAT_B8G8R8_SSCALED [Some(3)] [float=3] {[i8; 3]},
-    B8G8R8Uint => FORMAT_B8G8R8_UINT [Some(3)] [uint=3] {[u8; 3]},
-    B8G8R8Sint => FORMAT_B8G8R8_SINT [Some(3)] [sint=3] {[i8; 3]},
-    B8G8R8Srgb => FORMAT_B8G8R8_SRGB [Some(3)] [float=3] {[u8; 3]},
-    R8G8B8A8Unorm => FORMAT_R8G8B8A8_UNORM [Some(4)] [float=4] {[u8; 4]},
-    R8G8B8A8Snorm => FORMAT_R8G8B8A8_SNORM [Some(4)] [float=4] {[i8; 4]},
-    R8G8B8A8Uscaled => FORMAT_R8G8B8A8_USCALED [Some(4)] [float=4] {[u8; 4]},
-    R8G8B8A8Sscaled => FORMAT_R8G8B8A8_SSCALED [Some(4)] [float=4] {[i8; 4]},
-    R8G8B8A8Uint => FORMAT_R8G8B8A8_UINT [Some(4)] [uint=4] {[u8; 4]},
-    R8G8B8A8Sint => FORMAT_R8G8B8A8_SINT [Some(4)] [sint=4] {[i8; 4]},
-    R8G8B8A8Srgb => FORMAT_R8G8B8A8_SRGB [Some(4)] [float=4] {[u8; 4]},
-    B8G8R8A8Unorm => FORMAT_B8G8R8A8_UNORM [Some(4)] [float=4] {[u8; 4]},
-    B8G8R8A8Snorm => FORMAT_B8G8R8A8_SNORM [Some(4)] [float=4] {[i8; 4]},
-    B8G8R8A8Uscaled => FORMAT_B8G8R8A8_USCALED [Some(4)] [float=4] {[u8; 4]},
-    B8G8R8A8Sscaled => FORMAT_B8G8R8A8_SSCALED [Some(4)] [float=4] {[i8; 4]},
-    B8G8R8A8Uint => FORMAT_B8G8R8A8_UINT [Some(4)] [uint=4] {[u8; 4]},
-    B8G8R8A8Sint => FORMAT_B8G8R8A8_SINT [Some(4)] [sint=4] {[i8; 4]},
-    B8G8R8A8Srgb => FORMAT_B8G8R8A8_SRGB [Some(4)] [float=4] {[u8; 4]},
-    A8B8G8R8UnormPack32 => FORMAT_A8B8G8R8_UNORM_PACK32 [Some(4)] [float=4] {[u8; 4]},
-    A8B8G8R8SnormPack32 => FORMAT_A8B8G8R8_SNORM_PACK32 [Some(4)] [float=4] {[i8; 4]},
-    A8B8G8R8UscaledPack32 => FORMAT_A8B8G8R8_USCALED_PACK32 [Some(4)] [float=4] {[u8; 4]},
-    A8B8G8R8SscaledPack32 => FORMAT_A8B8G8R8_SSCALED_PACK32 [Some(4)] [float=4] {[i8; 4]},
-    A8B8G8R8UintPack32 => FORMAT_A8B8G8R8_UINT_PACK32 [Some(4)] [uint=4] {[u8; 4]},
-    A8B8G8R8SintPack32 => FORMAT_A8B8G8R8_SINT_PACK32 [Some(4)] [sint=4] {[i8; 4]},
-    A8B8G8R8SrgbPack32 => FORMAT_A8B8G8R8_SRGB_PACK32 [Some(4)] [float=4] {[u8; 4]},
-    A2R10G10B10UnormPack32 => FORMAT_A2R10G10B10_UNORM_PACK32 [Some(4)] [float=4] {u32},
-    A2R10G10B10SnormPack32 => FORMAT_A2R10G10B10_SNORM_PACK32 [Some(4)] [float=4] {u32},
-    A2R10G10B10UscaledPack32 => FORMAT_A2R10G10B10_USCALED_PACK32 [Some(4)] [float=4] {u32},
-    A2R10G10B10SscaledPack32 => FORMAT_A2R10G10B10_SSCALED_PACK32 [Some(4)] [float=4] {u32},
-    A2R10G10B10UintPack32 => FORMAT_A2R10G10B10_UINT_PACK32 [Some(4)] [uint=4] {u32},
-    A2R10G10B10SintPack32 => FORMAT_A2R10G10B10_SINT_PACK32 [Some(4)] [sint=4] {u32},
-    A2B10G10R10UnormPack32 => FORMAT_A2B10G10R10_UNORM_PACK32 [Some(4)] [float=4] {u32},
-    A2B10G10R10SnormPack32 => FORMAT_A2B10G10R10_SNORM_PACK32 [Some(4)] [float=4] {u32},
-    A2B10G10R10UscaledPack32 => FORMAT_A2B10G10R10_USCALED_PACK32 [Some(4)] [float=4] {u32},
-    A2B10G10R10SscaledPack32 => FORMAT_A2B10G10R10_SSCALED_PACK32 [Some(4)] [float=4] {u32},
-    A2B10G10R10UintPack32 => FORMAT_A2B10G10R10_UINT_PACK32 [Some(4)] [uint=4] {u32},
-    A2B10G10R10SintPack32 => FORMAT_A2B10G10R10_SINT_PACK32 [Some(4)] [sint=4] {u32},
-    R16Unorm => FORMAT_R16_UNORM [Some(2)] [float=1] {u16},
-    R16Snorm => FORMAT_R16_SNORM [Some(2)] [float=1] {i16},
-    R16Uscaled => FORMAT_R16_USCALED [Some(2)] [float=1] {u16},
-    R16Sscaled => FORMAT_R16_SSCALED [Some(2)] [float=1] {i16},
-    R16Uint => FORMAT_R16_UINT [Some(2)] [uint=1] {u16},
-    R16Sint => FORMAT_R16_SINT [Some(2)] [sint=1] {i16},
-    R16Sfloat => FORMAT_R16_SFLOAT [Some(2)] [float=1] {f16},
-    R16G16Unorm => FORMAT_R16G16_UNORM [Some(4)] [float=2] {[u16; 2]},
-    R16G16Snorm => FORMAT_R16G16_SNORM [Some(4)] [float=2] {[i16; 2]},
-    R16G16Uscaled => FORMAT_R16G16_USCALED [Some(4)] [float=2] {[u16; 2]},
-    R16G16Sscaled => FORMAT_R16G16_SSCALED [Some(4)] [float=2] {[i16; 2]},
-    R16G16Uint => FORMAT_R16G16_UINT [Some(4)] [uint=2] {[u16; 2]},
-    R16G16Sint => FORMAT_R16G16_SINT [Some(4)] [sint=2] {[i16; 2]},
-    R16G16Sfloat => FORMAT_R16G16_SFLOAT [Some(4)] [float=2] {[f16; 2]},
-    R16G16B16Unorm => FORMAT_R16G16B16_UNORM [Some(6)] [float=3] {[u16; 3]},
-    R16G16B16Snorm => FORMAT_R16G16B16_SNORM [Some(6)] [float=3] {[i16; 3]},
-    R16G16B16Uscaled => FORMAT_R16G16B16_USCALED [Some(6)] [float=3] {[u16; 3]},
-    R16G16B16Sscaled => FORMAT_R16G16B16_SSCALED [Some(6)] [float=3] {[i16; 3]},
-    R16G16B16Uint => FORMAT_R16G16B16_UINT [Some(6)] [uint=3] {[u16; 3]},
-    R16G16B16Sint => FORMAT_R16G16B16_SINT [Some(6)] [sint=3] {[i16; 3]},
-    R16G16B16Sfloat => FORMAT_R16G16B16_SFLOAT [Some(6)] [float=3] {[f16; 3]},
-    R16G16B16A16Unorm => FORMAT_R16G16B16A16_UNORM [Some(8)] [float=4] {[u16; 4]},
-    R16G16B16A16Snorm => FORMAT_R16G16B16A16_SNORM [Some(8)] [float=4] {[i16; 4]},
-    R16G16B16A16Uscaled => FORMAT_R16G16B16A16_USCALED [Some(8)] [float=4] {[u16; 4]},
-    R16G16B16A16Sscaled => FORMAT_R16G16B16A16_SSCALED [Some(8)] [float=4] {[i16; 4]},
-    R16G16B16A16Uint => FORMAT_R16G16B16A16_UINT [Some(8)] [uint=4] {[u16; 4]},
-    R16G16B16A16Sint => FORMAT_R16G16B16A16_SINT [Some(8)] [sint=4] {[i16; 4]},
-    R16G16B16A16Sfloat => FORMAT_R16G16B16A16_SFLOAT [Some(8)] [float=4] {[f16; 4]},
-    R32Uint => FORMAT_R32_UINT [Some(4)] [uint=1] {u32},
-    R32Sint => FORMAT_R32_SINT [Some(4)] [sint=1] {i32},
-    R32Sfloat => FORMAT_R32_SFLOAT [Some(4)] [float=1] {f32},
-    R32G32Uint => FORMAT_R32G32_UINT [Some(8)] [uint=2] {[u32; 2]},
-    R32G32Sint => FORMAT_R32G32_SINT [Some(8)] [sint=2] {[i32; 2]},
-    R32G32Sfloat => FORMAT_R32G32_SFLOAT [Some(8)] [float=2] {[f32; 2]},
-    R32G32B32Uint => FORMAT_R32G32B32_UINT [Some(12)] [uint=3] {[u32; 3]},
-    R32G32B32Sint => FORMAT_R32G32B32_SINT [Some(12)] [sint=3] {[i32; 3]},
-    R32G32B32Sfloat => FORMAT_R32G32B32_SFLOAT [Some(12)] [float=3] {[f32; 3]},
-    R32G32B32A32Uint => FORMAT_R32G32B32A32_UINT [Some(16)] [uint=4] {[u32; 4]},
-    R32G32B32A32Sint => FORMAT_R32G32B32A32_SINT [Some(16)] [sint=4] {[i32; 4]},
-    R32G32B32A32Sfloat => FORMAT_R32G32B32A32_SFLOAT [Some(16)] [float=4] {[f32; 4]},
-    R64Uint => FORMAT_R64_UINT [Some(8)] [uint=1] {u64},
-    R64Sint => FORMAT_R64_SINT [Some(8)] [sint=1] {i64},
-    R64Sfloat => FORMAT_R64_SFLOAT [Some(8)] [float=1] {f64},
-    R64G64Uint => FORMAT_R64G64_UINT [Some(16)] [uint=2] {[u64; 2]},
-    R64G64Sint => FORMAT_R64G64_SINT [Some(16)] [sint=2] {[i64; 2]},
-    R64G64Sfloat => FORMAT_R64G64_SFLOAT [Some(16)] [float=2] {[f64; 2]},
-    R64G64B64Uint => FORMAT_R64G64B64_UINT [Some(24)] [uint=3] {[u64; 3]},
-    R64G64B64Sint => FORMAT_R64G64B64_SINT [Some(24)] [sint=3] {[i64; 3]},
-    R64G64B64Sfloat => FORMAT_R64G64B64_SFLOAT [Some(24)] [float=3] {[f64; 3]},
-    R64G64B64A64Uint => FORMAT_R64G64B64A64_UINT [Some(32)] [uint=4] {[u64; 4]},
-    R64G64B64A64Sint => FORMAT_R64G64B64A64_SINT [Some(32)] [sint=4] {[i64; 4]},
-    R64G64B64A64Sfloat => FORMAT_R64G64B64A64_SFLOAT [Some(32)] [float=4] {[f64; 4]},
-    B10G11R11UfloatPack32 => FORMAT_B10G11R11_UFLOAT_PACK32 [Some(4)] [float=3] {u32},
-    E5B9G9R9UfloatPack32 => FORMAT_E5B9G9R9_UFLOAT_PACK32 [Some(4)] [float=3] {u32},
-    D16Unorm => FORMAT_D16_UNORM [Some(2)] [depth] {},
-    X8_D24UnormPack32 => FORMAT_X8_D24_UNORM_PACK32 [Some(4)] [depth] {},
-    D32Sfloat => FORMAT_D32_SFLOAT [Some(4)] [depth] {},
-    S8Uint => FORMAT_S8_UINT [Some(1)] [stencil] {},
-    D16Unorm_S8Uint => FORMAT_D16_UNORM_S8_UINT [None] [depthstencil] {},
-    D24Unorm_S8Uint => FORMAT_D24_UNORM_S8_UINT [None] [depthstencil] {},
-    D32Sfloat_S8Uint => FORMAT_D32_SFLOAT_S8_UINT [None] [depthstencil] {},
-    BC1_RGBUnormBlock => FORMAT_BC1_RGB_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC1_RGBSrgbBlock => FORMAT_BC1_RGB_SRGB_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC1_RGBAUnormBlock => FORMAT_BC1_RGBA_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC1_RGBASrgbBlock => FORMAT_BC1_RGBA_SRGB_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC2UnormBlock => FORMAT_BC2_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC2SrgbBlock => FORMAT_BC2_SRGB_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC3UnormBlock => FORMAT_BC3_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC3SrgbBlock => FORMAT_BC3_SRGB_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC4UnormBlock => FORMAT_BC4_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC4SnormBlock => FORMAT_BC4_SNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC5UnormBlock => FORMAT_BC5_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC5SnormBlock => FORMAT_BC5_SNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC6HUfloatBlock => FORMAT_BC6H_UFLOAT_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC6HSfloatBlock => FORMAT_BC6H_SFLOAT_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC7UnormBlock => FORMAT_BC7_UNORM_BLOCK [None] [compressed=texture_compression_bc] {},
-    BC7SrgbBlock => FORMAT_BC7_SRGB_BLOCK [None] [compressed=texture_compression_bc] {},
-    ETC2_R8G8B8UnormBlock => FORMAT_ETC2_R8G8B8_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ETC2_R8G8B8SrgbBlock => FORMAT_ETC2_R8G8B8_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ETC2_R8G8B8A1UnormBlock => FORMAT_ETC2_R8G8B8A1_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ETC2_R8G8B8A1SrgbBlock => FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ETC2_R8G8B8A8UnormBlock => FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ETC2_R8G8B8A8SrgbBlock => FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {},
-    EAC_R11UnormBlock => FORMAT_EAC_R11_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    EAC_R11SnormBlock => FORMAT_EAC_R11_SNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    EAC_R11G11UnormBlock => FORMAT_EAC_R11G11_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    EAC_R11G11SnormBlock => FORMAT_EAC_R11G11_SNORM_BLOCK [None] [compressed=texture_compression_etc2] {},
-    ASTC_4x4UnormBlock => FORMAT_ASTC_4x4_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_4x4SrgbBlock => FORMAT_ASTC_4x4_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_5x4UnormBlock => FORMAT_ASTC_5x4_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_5x4SrgbBlock => FORMAT_ASTC_5x4_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_5x5UnormBlock => FORMAT_ASTC_5x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_5x5SrgbBlock => FORMAT_ASTC_5x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_6x5UnormBlock => FORMAT_ASTC_6x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_6x5SrgbBlock => FORMAT_ASTC_6x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_6x6UnormBlock => FORMAT_ASTC_6x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_6x6SrgbBlock => FORMAT_ASTC_6x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x5UnormBlock => FORMAT_ASTC_8x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x5SrgbBlock => FORMAT_ASTC_8x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x6UnormBlock => FORMAT_ASTC_8x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x6SrgbBlock => FORMAT_ASTC_8x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x8UnormBlock => FORMAT_ASTC_8x8_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_8x8SrgbBlock => FORMAT_ASTC_8x8_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x5UnormBlock => FORMAT_ASTC_10x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x5SrgbBlock => FORMAT_ASTC_10x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x6UnormBlock => FORMAT_ASTC_10x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x6SrgbBlock => FORMAT_ASTC_10x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x8UnormBlock => FORMAT_ASTC_10x8_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x8SrgbBlock => FORMAT_ASTC_10x8_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x10UnormBlock => FORMAT_ASTC_10x10_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_10x10SrgbBlock => FORMAT_ASTC_10x10_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_12x10UnormBlock => FORMAT_ASTC_12x10_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_12x10SrgbBlock => FORMAT_ASTC_12x10_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_12x12UnormBlock => FORMAT_ASTC_12x12_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
-    ASTC_12x12SrgbBlock => FORMAT_ASTC_12x12_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {},
+    R4G4UnormPack8 => FORMAT_R4G4_UNORM_PACK8 [Some(1)] [float=2] {u8} Unorm,
+    R4G4B4A4UnormPack16 => FORMAT_R4G4B4A4_UNORM_PACK16 [Some(2)] [float=4] {u16} Unorm,
+    B4G4R4A4UnormPack16 => FORMAT_B4G4R4A4_UNORM_PACK16 [Some(2)] [float=4] {u16} Unorm,
+    R5G6B5UnormPack16 => FORMAT_R5G6B5_UNORM_PACK16 [Some(2)] [float=3] {u16} Unorm,
+    B5G6R5UnormPack16 => FORMAT_B5G6R5_UNORM_PACK16 [Some(2)] [float=3] {u16} Unorm,
+    R5G5B5A1UnormPack16 => FORMAT_R5G5B5A1_UNORM_PACK16 [Some(2)] [float=4] {u16} Unorm,
+    B5G5R5A1UnormPack16 => FORMAT_B5G5R5A1_UNORM_PACK16 [Some(2)] [float=4] {u16} Unorm,
+    A1R5G5B5UnormPack16 => FORMAT_A1R5G5B5_UNORM_PACK16 [Some(2)] [float=4] {u16} Unorm,
+    R8Unorm => FORMAT_R8_UNORM [Some(1)] [float=1] {u8} Unorm,
+    R8Snorm => FORMAT_R8_SNORM [Some(1)] [float=1] {i8} Snorm,
+    R8Uscaled => FORMAT_R8_USCALED [Some(1)] [float=1] {u8} Uscaled,
+    R8Sscaled => FORMAT_R8_SSCALED [Some(1)] [float=1] {i8} Sscaled,
+    R8Uint => FORMAT_R8_UINT [Some(1)] [uint=1] {u8} Uint,
+    R8Sint => FORMAT_R8_SINT [Some(1)] [sint=1] {i8} Sint,
+    R8Srgb => FORMAT_R8_SRGB [Some(1)] [float=1] {u8} Srgb,
+    R8G8Unorm => FORMAT_R8G8_UNORM [Some(2)] [float=2] {[u8; 2]} Unorm,
+    R8G8Snorm => FORMAT_R8G8_SNORM [Some(2)] [float=2] {[i8; 2]} Snorm,
+    R8G8Uscaled => FORMAT_R8G8_USCALED [Some(2)] [float=2] {[u8; 2]} Uscaled,
+    R8G8Sscaled => FORMAT_R8G8_SSCALED [Some(2)] [float=2] {[i8; 2]} Sscaled,
+    R8G8Uint => FORMAT_R8G8_UINT [Some(2)] [uint=2] {[u8; 2]} Uint,
+    R8G8Sint => FORMAT_R8G8_SINT [Some(2)] [sint=2] {[i8; 2]} Sint,
+    R8G8Srgb => FORMAT_R8G8_SRGB [Some(2)] [float=2] {[u8; 2]} Srgb,
+    R8G8B8Unorm => FORMAT_R8G8B8_UNORM [Some(3)] [float=3] {[u8; 3]} Unorm,
+    R8G8B8Snorm => FORMAT_R8G8B8_SNORM [Some(3)] [float=3] {[i8; 3]} Snorm,
+    R8G8B8Uscaled => FORMAT_R8G8B8_USCALED [Some(3)] [float=3] {[u8; 3]} Uscaled,
+    R8G8B8Sscaled => FORMAT_R8G8B8_SSCALED [Some(3)] [float=3] {[i8; 3]} Sscaled,
+    R8G8B8Uint => FORMAT_R8G8B8_UINT [Some(3)] [uint=3] {[u8; 3]} Uint,
+    R8G8B8Sint => FORMAT_R8G8B8_SINT [Some(3)] [sint=3] {[i8; 3]} Sint,
+    R8G8B8Srgb => FORMAT_R8G8B8_SRGB [Some(3)] [float=3] {[u8; 3]} Srgb,
+    B8G8R8Unorm => FORMAT_B8G8R8_UNORM [Some(3)] [float=3] {[u8; 3]} Unorm,
+    B8G8R8Snorm => FORMAT_B8G8R8_SNORM [Some(3)] [float=3] {[i8; 3]} Snorm,
+    B8G8R8Uscaled => FORMAT_B8G8R8_USCALED [Some(3)] [float=3] {[u8; 3]} Uscaled,
+    B8G8R8Sscaled => FORMAT_B8G8R8_SSCALED [Some(3)] [float=3] {[i8; 3]} Sscaled,
+    B8G8R8Uint => FORMAT_B8G8R8_UINT [Some(3)] [uint=3] {[u8; 3]} Uint,
+    B8G8R8Sint => FORMAT_B8G8R8_SINT [Some(3)] [sint=3] {[i8; 3]} Sint,
+    B8G8R8Srgb => FORMAT_B8G8R8_SRGB [Some(3)] [float=3] {[u8; 3]} Srgb,
+    R8G8B8A8Unorm => FORMAT_R8G8B8A8_UNORM [Some(4)] [float=4] {[u8; 4]} Unorm,
+    R8G8B8A8Snorm => FORMAT_R8G8B8A8_SNORM [Some(4)] [float=4] {[i8; 4]} Snorm,
+    R8G8B8A8Uscaled => FORMAT_R8G8B8A8_USCALED [Some(4)] [float=4] {[u8; 4]} Uscaled,
+    R8G8B8A8Sscaled => FORMAT_R8G8B8A8_SSCALED [Some(4)] [float=4] {[i8; 4]} Sscaled,
+    R8G8B8A8Uint => FORMAT_R8G8B8A8_UINT [Some(4)] [uint=4] {[u8; 4]} Uint,
+    R8G8B8A8Sint => FORMAT_R8G8B8A8_SINT [Some(4)] [sint=4] {[i8; 4]} Sint,
+    R8G8B8A8Srgb => FORMAT_R8G8B8A8_SRGB [Some(4)] [float=4] {[u8; 4]} Srgb,
+    B8G8R8A8Unorm => FORMAT_B8G8R8A8_UNORM [Some(4)] [float=4] {[u8; 4]} Unorm,
+    B8G8R8A8Snorm => FORMAT_B8G8R8A8_SNORM [Some(4)] [float=4] {[i8; 4]} Snorm,
+    B8G8R8A8Uscaled => FORMAT_B8G8R8A8_USCALED [Some(4)] [float=4] {[u8; 4]} Uscaled,
+    B8G8R8A8Sscaled => FORMAT_B8G8R8A8_SSCALED [Some(4)] [float=4] {[i8; 4]} Sscaled,
+    B8G8R8A8Uint => FORMAT_B8G8R8A8_UINT [Some(4)] [uint=4] {[u8; 4]} Uint,
+    B8G8R8A8Sint => FORMAT_B8G8R8A8_SINT [Some(4)] [sint=4] {[i8; 4]} Sint,
+    B8G8R8A8Srgb => FORMAT_B8G8R8A8_SRGB [Some(4)] [float=4] {[u8; 4]} Srgb,
+    A8B8G8R8UnormPack32 => FORMAT_A8B8G8R8_UNORM_PACK32 [Some(4)] [float=4] {[u8; 4]} Unorm,
+    A8B8G8R8SnormPack32 => FORMAT_A8B8G8R8_SNORM_PACK32 [Some(4)] [float=4] {[i8; 4]} Snorm,
+    A8B8G8R8UscaledPack32 => FORMAT_A8B8G8R8_USCALED_PACK32 [Some(4)] [float=4] {[u8; 4]} Uscaled,
+    A8B8G8R8SscaledPack32 => FORMAT_A8B8G8R8_SSCALED_PACK32 [Some(4)] [float=4] {[i8; 4]} Sscaled,
+    A8B8G8R8UintPack32 => FORMAT_A8B8G8R8_UINT_PACK32 [Some(4)] [uint=4] {[u8; 4]} Uint,
+    A8B8G8R8SintPack32 => FORMAT_A8B8G8R8_SINT_PACK32 [Some(4)] [sint=4] {[i8; 4]} Sint,
+    A8B8G8R8SrgbPack32 => FORMAT_A8B8G8R8_SRGB_PACK32 [Some(4)] [float=4] {[u8; 4]} Srgb,
+    A2R10G10B10UnormPack32 => FORMAT_A2R10G10B10_UNORM_PACK32 [Some(4)] [float=4] {u32} Unorm,
+    A2R10G10B10SnormPack32 => FORMAT_A2R10G10B10_SNORM_PACK32 [Some(4)] [float=4] {u32} Snorm,
+    A2R10G10B10UscaledPack32 => FORMAT_A2R10G10B10_USCALED_PACK32 [Some(4)] [float=4] {u32} Uscaled,
+    A2R10G10B10SscaledPack32 => FORMAT_A2R10G10B10_SSCALED_PACK32 [Some(4)] [float=4] {u32} Sscaled,
+    A2R10G10B10UintPack32 => FORMAT_A2R10G10B10_UINT_PACK32 [Some(4)] [uint=4] {u32} Uint,
+    A2R10G10B10SintPack32 => FORMAT_A2R10G10B10_SINT_PACK32 [Some(4)] [sint=4] {u32} Sint,
+    A2B10G10R10UnormPack32 => FORMAT_A2B10G10R10_UNORM_PACK32 [Some(4)] [float=4] {u32} Unorm,
+    A2B10G10R10SnormPack32 => FORMAT_A2B10G10R10_SNORM_PACK32 [Some(4)] [float=4] {u32} Snorm,
+    A2B10G10R10UscaledPack32 => FORMAT_A2B10G10R10_USCALED_PACK32 [Some(4)] [float=4] {u32} Uscaled,
+    A2B10G10R10SscaledPack32 => FORMAT_A2B10G10R10_SSCALED_PACK32 [Some(4)] [float=4] {u32} Sscaled,
+    A2B10G10R10UintPack32 => FORMAT_A2B10G10R10_UINT_PACK32 [Some(4)] [uint=4] {u32} Uint,
+    A2B10G10R10SintPack32 => FORMAT_A2B10G10R10_SINT_PACK32 [Some(4)] [sint=4] {u32} Sint,
+    R16Unorm => FORMAT_R16_UNORM [Some(2)] [float=1] {u16} Unorm,
+    R16Snorm => FORMAT_R16_SNORM [Some(2)] [float=1] {i16} Snorm,
+    R16Uscaled => FORMAT_R16_USCALED [Some(2)] [float=1] {u16} Uscaled,
+    R16Sscaled => FORMAT_R16_SSCALED [Some(2)] [float=1] {i16} Sscaled,
+    R16Uint => FORMAT_R16_UINT [Some(2)] [uint=1] {u16} Uint,
+    R16Sint => FORMAT_R16_SINT [Some(2)] [sint=1] {i16} Sint,
+    R16Sfloat => FORMAT_R16_SFLOAT [Some(2)] [float=1] {f16} Sfloat,
+    R16G16Unorm => FORMAT_R16G16_UNORM [Some(4)] [float=2] {[u16; 2]} Unorm,
+    R16G16Snorm => FORMAT_R16G16_SNORM [Some(4)] [float=2] {[i16; 2]} Snorm,
+    R16G16Uscaled => FORMAT_R16G16_USCALED [Some(4)] [float=2] {[u16; 2]} Uscaled,
+    R16G16Sscaled => FORMAT_R16G16_SSCALED [Some(4)] [float=2] {[i16; 2]} Sscaled,
+    R16G16Uint => FORMAT_R16G16_UINT [Some(4)] [uint=2] {[u16; 2]} Uint,
+    R16G16Sint => FORMAT_R16G16_SINT [Some(4)] [sint=2] {[i16; 2]} Sint,
+    R16G16Sfloat => FORMAT_R16G16_SFLOAT [Some(4)] [float=2] {[f16; 2]} Sfloat,
+    R16G16B16Unorm => FORMAT_R16G16B16_UNORM [Some(6)] [float=3] {[u16; 3]} Unorm,
+    R16G16B16Snorm => FORMAT_R16G16B16_SNORM [Some(6)] [float=3] {[i16; 3]} Snorm,
+    R16G16B16Uscaled => FORMAT_R16G16B16_USCALED [Some(6)] [float=3] {[u16; 3]} Uscaled,
+    R16G16B16Sscaled => FORMAT_R16G16B16_SSCALED [Some(6)] [float=3] {[i16; 3]} Sscaled,
+    R16G16B16Uint => FORMAT_R16G16B16_UINT [Some(6)] [uint=3] {[u16; 3]} Uint,
+    R16G16B16Sint => FORMAT_R16G16B16_SINT [Some(6)] [sint=3] {[i16; 3]} Sint,
+    R16G16B16Sfloat => FORMAT_R16G16B16_SFLOAT [Some(6)] [float=3] {[f16; 3]} Sfloat,
+    R16G16B16A16Unorm => FORMAT_R16G16B16A16_UNORM [Some(8)] [float=4] {[u16; 4]} Unorm,
+    R16G16B16A16Snorm => FORMAT_R16G16B16A16_SNORM [Some(8)] [float=4] {[i16; 4]} Snorm,
+    R16G16B16A16Uscaled => FORMAT_R16G16B16A16_USCALED [Some(8)] [float=4] {[u16; 4]} Uscaled,
+    R16G16B16A16Sscaled => FORMAT_R16G16B16A16_SSCALED [Some(8)] [float=4] {[i16; 4]} Sscaled,
+    R16G16B16A16Uint => FORMAT_R16G16B16A16_UINT [Some(8)] [uint=4] {[u16; 4]} Uint,
+    R16G16B16A16Sint => FORMAT_R16G16B16A16_SINT [Some(8)] [sint=4] {[i16; 4]} Sint,
+    R16G16B16A16Sfloat => FORMAT_R16G16B16A16_SFLOAT [Some(8)] [float=4] {[f16; 4]} Sfloat,
+    R32Uint => FORMAT_R32_UINT [Some(4)] [uint=1] {u32} Uint,
+    R32Sint => FORMAT_R32_SINT [Some(4)] [sint=1] {i32} Sint,
+    R32Sfloat => FORMAT_R32_SFLOAT [Some(4)] [float=1] {f32} Sfloat,
+    R32G32Uint => FORMAT_R32G32_UINT [Some(8)] [uint=2] {[u32; 2]} Uint,
+    R32G32Sint => FORMAT_R32G32_SINT [Some(8)] [sint=2] {[i32; 2]} Sint,
+    R32G32Sfloat => FORMAT_R32G32_SFLOAT [Some(8)] [float=2] {[f32; 2]} Sfloat,
+    R32G32B32Uint => FORMAT_R32G32B32_UINT [Some(12)] [uint=3] {[u32; 3]} Uint,
+    R32G32B32Sint => FORMAT_R32G32B32_SINT [Some(12)] [sint=3] {[i32; 3]} Sint,
+    R32G32B32Sfloat => FORMAT_R32G32B32_SFLOAT [Some(12)] [float=3] {[f32; 3]} Sfloat,
+    R32G32B32A32Uint => FORMAT_R32G32B32A32_UINT [Some(16)] [uint=4] {[u32; 4]} Uint,
+    R32G32B32A32Sint => FORMAT_R32G32B32A32_SINT [Some(16)] [sint=4] {[i32; 4]} Sint,
+    R32G32B32A32Sfloat => FORMAT_R32G32B32A32_SFLOAT [Some(16)] [float=4] {[f32; 4]} Sfloat,
+    R64Uint => FORMAT_R64_UINT [Some(8)] [uint=1] {u64} Uint,
+    R64Sint => FORMAT_R64_SINT [Some(8)] [sint=1] {i64} Sint,
+    R64Sfloat => FORMAT_R64_SFLOAT [Some(8)] [float=1] {f64} Sfloat,
+    R64G64Uint => FORMAT_R64G64_UINT [Some(16)] [uint=2] {[u64; 2]} Uint,
+    R64G64Sint => FORMAT_R64G64_SINT [Some(16)] [sint=2] {[i64; 2]} Sint,
+    R64G64Sfloat => FORMAT_R64G64_SFLOAT [Some(16)] [float=2] {[f64; 2]} Sfloat,
+    R64G64B64Uint => FORMAT_R64G64B64_UINT [Some(24)] [uint=3] {[u64; 3]} Uint,
+    R64G64B64Sint => FORMAT_R64G64B64_SINT [Some(24)] [sint=3] {[i64; 3]} Sint,
+    R64G64B64Sfloat => FORMAT_R64G64B64_SFLOAT [Some(24)] [float=3] {[f64; 3]} Sfloat,
+    R64G64B64A64Uint => FORMAT_R64G64B64A64_UINT [Some(32)] [uint=4] {[u64; 4]} Uint,
+    R64G64B64A64Sint => FORMAT_R64G64B64A64_SINT [Some(32)] [sint=4] {[i64; 4]} Sint,
+    R64G64B64A64Sfloat => FORMAT_R64G64B64A64_SFLOAT [Some(32)] [float=4] {[f64; 4]} Sfloat,
+    B10G11R11UfloatPack32 => FORMAT_B10G11R11_UFLOAT_PACK32 [Some(4)] [float=3] {u32} Ufloat,
+    E5B9G9R9UfloatPack32 => FORMAT_E5B9G9R9_UFLOAT_PACK32 [Some(4)] [float=3] {u32} Ufloat,
+    D16Unorm => FORMAT_D16_UNORM [Some(2)] [depth] {} Unorm,
+    X8_D24UnormPack32 => FORMAT_X8_D24_UNORM_PACK32 [Some(4)] [depth] {} Unorm,
+    D32Sfloat => FORMAT_D32_SFLOAT [Some(4)] [depth] {} Sfloat,
+    S8Uint => FORMAT_S8_UINT [Some(1)] [stencil] {} Uint,
+    D16Unorm_S8Uint => FORMAT_D16_UNORM_S8_UINT [None] [depthstencil] {} Unorm,
+    D24Unorm_S8Uint => FORMAT_D24_UNORM_S8_UINT [None] [depthstencil] {} Unorm,
+    D32Sfloat_S8Uint => FORMAT_D32_SFLOAT_S8_UINT [None] [depthstencil] {} Sfloat,
+    BC1_RGBUnormBlock => FORMAT_BC1_RGB_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC1_RGBSrgbBlock => FORMAT_BC1_RGB_SRGB_BLOCK [None] [compressed=texture_compression_bc] {} Srgb,
+    BC1_RGBAUnormBlock => FORMAT_BC1_RGBA_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC1_RGBASrgbBlock => FORMAT_BC1_RGBA_SRGB_BLOCK [None] [compressed=texture_compression_bc] {} Srgb,
+    BC2UnormBlock => FORMAT_BC2_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC2SrgbBlock => FORMAT_BC2_SRGB_BLOCK [None] [compressed=texture_compression_bc] {} Srgb,
+    BC3UnormBlock => FORMAT_BC3_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC3SrgbBlock => FORMAT_BC3_SRGB_BLOCK [None] [compressed=texture_compression_bc] {} Srgb,
+    BC4UnormBlock => FORMAT_BC4_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC4SnormBlock => FORMAT_BC4_SNORM_BLOCK [None] [compressed=texture_compression_bc] {} Snorm,
+    BC5UnormBlock => FORMAT_BC5_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC5SnormBlock => FORMAT_BC5_SNORM_BLOCK [None] [compressed=texture_compression_bc] {} Snorm,
+    BC6HUfloatBlock => FORMAT_BC6H_UFLOAT_BLOCK [None] [compressed=texture_compression_bc] {} Ufloat,
+    BC6HSfloatBlock => FORMAT_BC6H_SFLOAT_BLOCK [None] [compressed=texture_compression_bc] {} Sfloat,
+    BC7UnormBlock => FORMAT_BC7_UNORM_BLOCK [None] [compressed=texture_compression_bc] {} Unorm,
+    BC7SrgbBlock => FORMAT_BC7_SRGB_BLOCK [None] [compressed=texture_compression_bc] {} Srgb,
+    ETC2_R8G8B8UnormBlock => FORMAT_ETC2_R8G8B8_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Unorm,
+    ETC2_R8G8B8SrgbBlock => FORMAT_ETC2_R8G8B8_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {} Srgb,
+    ETC2_R8G8B8A1UnormBlock => FORMAT_ETC2_R8G8B8A1_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Unorm,
+    ETC2_R8G8B8A1SrgbBlock => FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {} Srgb,
+    ETC2_R8G8B8A8UnormBlock => FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Unorm,
+    ETC2_R8G8B8A8SrgbBlock => FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK [None] [compressed=texture_compression_etc2] {} Srgb,
+    EAC_R11UnormBlock => FORMAT_EAC_R11_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Unorm,
+    EAC_R11SnormBlock => FORMAT_EAC_R11_SNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Snorm,
+    EAC_R11G11UnormBlock => FORMAT_EAC_R11G11_UNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Unorm,
+    EAC_R11G11SnormBlock => FORMAT_EAC_R11G11_SNORM_BLOCK [None] [compressed=texture_compression_etc2] {} Snorm,
+    ASTC_4x4UnormBlock => FORMAT_ASTC_4x4_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_4x4SrgbBlock => FORMAT_ASTC_4x4_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_5x4UnormBlock => FORMAT_ASTC_5x4_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_5x4SrgbBlock => FORMAT_ASTC_5x4_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_5x5UnormBlock => FORMAT_ASTC_5x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_5x5SrgbBlock => FORMAT_ASTC_5x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_6x5UnormBlock => FORMAT_ASTC_6x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_6x5SrgbBlock => FORMAT_ASTC_6x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_6x6UnormBlock => FORMAT_ASTC_6x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_6x6SrgbBlock => FORMAT_ASTC_6x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_8x5UnormBlock => FORMAT_ASTC_8x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_8x5SrgbBlock => FORMAT_ASTC_8x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_8x6UnormBlock => FORMAT_ASTC_8x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_8x6SrgbBlock => FORMAT_ASTC_8x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_8x8UnormBlock => FORMAT_ASTC_8x8_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_8x8SrgbBlock => FORMAT_ASTC_8x8_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_10x5UnormBlock => FORMAT_ASTC_10x5_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_10x5SrgbBlock => FORMAT_ASTC_10x5_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_10x6UnormBlock => FORMAT_ASTC_10x6_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_10x6SrgbBlock => FORMAT_ASTC_10x6_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_10x8UnormBlock => FORMAT_ASTC_10x8_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_10x8SrgbBlock => FORMAT_ASTC_10x8_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_10x10UnormBlock => FORMAT_ASTC_10x10_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_10x10SrgbBlock => FORMAT_ASTC_10x10_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_12x10UnormBlock => FORMAT_ASTC_12x10_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_12x10SrgbBlock => FORMAT_ASTC_12x10_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    ASTC_12x12UnormBlock => FORMAT_ASTC_12x12_UNORM_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Unorm,
+    ASTC_12x12SrgbBlock => FORMAT_ASTC_12x12_SRGB_BLOCK [None] [compressed=texture_compression_astc_ldr] {} Srgb,
+    G8B8G8R8_422Unorm => FORMAT_G8B8G8R8_422_UNORM [None] [float=3] {} Unorm,
+    B8G8R8G8_422Unorm => FORMAT_B8G8R8G8_422_UNORM [None] [float=3] {} Unorm,
+    G8_B8_R8_3Plane420Unorm => FORMAT_G8_B8_R8_3PLANE_420_UNORM [None] [float=3] {} Unorm,
+    G8_B8R8_2Plane420Unorm => FORMAT_G8_B8R8_2PLANE_420_UNORM [None] [float=3] {} Unorm,
+    G8_B8_R8_3Plane422Unorm => FORMAT_G8_B8_R8_3PLANE_422_UNORM [None] [float=3] {} Unorm,
+    G8_B8R8_2Plane422Unorm => FORMAT_G8_B8R8_2PLANE_422_UNORM [None] [float=3] {} Unorm,
+    G8_B8_R8_3Plane444Unorm => FORMAT_G8_B8_R8_3PLANE_444_UNORM [None] [float=3] {} Unorm,
+    R10X6UnormPack16 => FORMAT_R10X6_UNORM_PACK16 [Some(2)] [float=1] {} Unorm,
+    R10X6G10X6Unorm2Pack16 => FORMAT_R10X6G10X6_UNORM_2PACK16 [Some(4)] [float=2] {} Unorm,
+    R10X6G10X6B10X6A10X6Unorm4Pack16 => FORMAT_R10X6G10X6B10X6A10X6_UNORM_4PACK16 [Some(8)] [float=4] {} Unorm,
+    G10X6B10X6G10X6R10X6_422Unorm4Pack16 => FORMAT_G10X6B10X6G10X6R10X6_422_UNORM_4PACK16 [None] [float=3] {} Unorm,
+    B10X6G10X6R10X6G10X6_422Unorm4Pack16 => FORMAT_B10X6G10X6R10X6G10X6_422_UNORM_4PACK16 [None] [float=3] {} Unorm,
+    G10X6_B10X6_R10X6_3Plane420Unorm3Pack16 => FORMAT_G10X6_B10X6_R10X6_3PLANE_420_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G10X6_B10X6R10X6_2Plane420Unorm3Pack16 => FORMAT_G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G10X6_B10X6_R10X6_3Plane422Unorm3Pack16 => FORMAT_G10X6_B10X6_R10X6_3PLANE_422_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G10X6_B10X6R10X6_2Plane422Unorm3Pack16 => FORMAT_G10X6_B10X6R10X6_2PLANE_422_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G10X6_B10X6_R10X6_3Plane444Unorm3Pack16 => FORMAT_G10X6_B10X6_R10X6_3PLANE_444_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    R12X4UnormPack16 => FORMAT_R12X4_UNORM_PACK16 [Some(2)] [float=1] {} Unorm,
+    R12X4G12X4Unorm2Pack16 => FORMAT_R12X4G12X4_UNORM_2PACK16 [Some(4)] [float=2] {} Unorm,
+    R12X4G12X4B12X4A12X4Unorm4Pack16 => FORMAT_R12X4G12X4B12X4A12X4_UNORM_4PACK16 [Some(8)] [float=4] {} Unorm,
+    G12X4B12X4G12X4R12X4_422Unorm4Pack16 => FORMAT_G12X4B12X4G12X4R12X4_422_UNORM_4PACK16 [None] [float=3] {} Unorm,
+    B12X4G12X4R12X4G12X4_422Unorm4Pack16 => FORMAT_B12X4G12X4R12X4G12X4_422_UNORM_4PACK16 [None] [float=3] {} Unorm,
+    G12X4_B12X4_R12X4_3Plane420Unorm3Pack16 => FORMAT_G12X4_B12X4_R12X4_3PLANE_420_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G12X4_B12X4R12X4_2Plane420Unorm3Pack16 => FORMAT_G12X4_B12X4R12X4_2PLANE_420_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G12X4_B12X4_R12X4_3Plane422Unorm3Pack16 => FORMAT_G12X4_B12X4_R12X4_3PLANE_422_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G12X4_B12X4R12X4_2Plane422Unorm3Pack16 => FORMAT_G12X4_B12X4R12X4_2PLANE_422_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G12X4_B12X4_R12X4_3Plane444Unorm3Pack16 => FORMAT_G12X4_B12X4_R12X4_3PLANE_444_UNORM_3PACK16 [None] [float=3] {} Unorm,
+    G16B16G16R16_422Unorm => FORMAT_G16B16G16R16_422_UNORM [None] [float=3] {} Unorm,
+    B16G16R16G16_422Unorm => FORMAT_B16G16R16G16_422_UNORM [None] [float=3] {} Unorm,
+    G16_B16_R16_3Plane420Unorm => FORMAT_G16_B16_R16_3PLANE_420_UNORM [None] [float=3] {} Unorm,
+    G16_B16R16_2Plane420Unorm => FORMAT_G16_B16R16_2PLANE_420_UNORM [None] [float=3] {} Unorm,
+    G16_B16_R16_3Plane422Unorm => FORMAT_G16_B16_R16_3PLANE_422_UNORM [None] [float=3] {} Unorm,
+    G16_B16R16_2Plane422Unorm => FORMAT_G16_B16R16_2PLANE_422_UNORM [None] [float=3] {} Unorm,
+    G16_B16_R16_3Plane444Unorm => FORMAT_G16_B16_R16_3PLANE_444_UNORM [None] [float=3] {} Unorm,
+}
+
+impl Format {
+    /// Returns the size in bytes of a texel block of this format.
+    ///
+    /// For uncompressed formats a block is a single texel, and this is the same as `size()`.
+    /// For compressed formats, this is the size of a block of `block_dimensions()` texels.
+    /// Returns `None` for combined depth-stencil formats, whose layout is
+    /// implementation-defined.
+    pub fn block_size(&self) -> Option<usize> {
+        if let Some(size) = self.size() {
+            return Some(size);
+        }
+
+        match *self {
+            Format::BC1_RGBUnormBlock |
+            Format::BC1_RGBSrgbBlock |
+            Format::BC1_RGBAUnormBlock |
+            Format::BC1_RGBASrgbBlock |
+            Format::BC4UnormBlock |
+            Format::BC4SnormBlock |
+            Format::ETC2_R8G8B8UnormBlock |
+            Format::ETC2_R8G8B8SrgbBlock |
+            Format::ETC2_R8G8B8A1UnormBlock |
+            Format::ETC2_R8G8B8A1SrgbBlock |
+            Format::EAC_R11UnormBlock |
+            Format::EAC_R11SnormBlock => Some(8),
+            _ if self.compression().is_some() => Some(16),
+            Format::G8B8G8R8_422Unorm |
+            Format::B8G8R8G8_422Unorm => Some(4),
+            Format::G10X6B10X6G10X6R10X6_422Unorm4Pack16 |
+            Format::B10X6G10X6R10X6G10X6_422Unorm4Pack16 |
+            Format::G12X4B12X4G12X4R12X4_422Unorm4Pack16 |
+            Format::B12X4G12X4R12X4G12X4_422Unorm4Pack16 |
+            Format::G16B16G16R16_422Unorm |
+            Format::B16G16R16G16_422Unorm => Some(8),
+            _ => None,
+        }
+    }
+
+    /// Returns the width and height in texels of a texel block of this format.
+    ///
+    /// This is `[1, 1]` for uncompressed formats, except for the single-plane 4:2:2 YCbCr formats
+    /// whose blocks are two texels wide.
+    pub fn block_dimensions(&self) -> [u32; 2] {
+        match *self {
+            Format::ASTC_4x4UnormBlock | Format::ASTC_4x4SrgbBlock => [4, 4],
+            Format::ASTC_5x4UnormBlock | Format::ASTC_5x4SrgbBlock => [5, 4],
+            Format::ASTC_5x5UnormBlock | Format::ASTC_5x5SrgbBlock => [5, 5],
+            Format::ASTC_6x5UnormBlock | Format::ASTC_6x5SrgbBlock => [6, 5],
+            Format::ASTC_6x6UnormBlock | Format::ASTC_6x6SrgbBlock => [6, 6],
+            Format::ASTC_8x5UnormBlock | Format::ASTC_8x5SrgbBlock => [8, 5],
+            Format::ASTC_8x6UnormBlock | Format::ASTC_8x6SrgbBlock => [8, 6],
+            Format::ASTC_8x8UnormBlock | Format::ASTC_8x8SrgbBlock => [8, 8],
+            Format::ASTC_10x5UnormBlock | Format::ASTC_10x5SrgbBlock => [10, 5],
+            Format::ASTC_10x6UnormBlock | Format::ASTC_10x6SrgbBlock => [10, 6],
+            Format::ASTC_10x8UnormBlock | Format::ASTC_10x8SrgbBlock => [10, 8],
+            Format::ASTC_10x10UnormBlock | Format::ASTC_10x10SrgbBlock => [10, 10],
+            Format::ASTC_12x10UnormBlock | Format::ASTC_12x10SrgbBlock => [12, 10],
+            Format::ASTC_12x12UnormBlock | Format::ASTC_12x12SrgbBlock => [12, 12],
+            _ if self.compression().is_some() => [4, 4],
+            Format::G8B8G8R8_422Unorm |
+            Format::B8G8R8G8_422Unorm |
+            Format::G10X6B10X6G10X6R10X6_422Unorm4Pack16 |
+            Format::B10X6G10X6R10X6G10X6_422Unorm4Pack16 |
+            Format::G12X4B12X4G12X4R12X4_422Unorm4Pack16 |
+            Format::B12X4G12X4R12X4G12X4_422Unorm4Pack16 |
+            Format::G16B16G16R16_422Unorm |
+            Format::B16G16R16G16_422Unorm => [2, 1],
+            _ => [1, 1],
+        }
+    }
+
+    /// Returns true if the format has a color aspect.
+    #[inline]
+    pub fn has_color(&self) -> bool {
+        !self.ty().is_depth_and_or_stencil()
+    }
+
+    /// Returns true if the format has a depth aspect.
+    #[inline]
+    pub fn has_depth(&self) -> bool {
+        match self.ty() {
+            FormatTy::Depth | FormatTy::DepthStencil => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the format has a stencil aspect.
+    #[inline]
+    pub fn has_stencil(&self) -> bool {
+        match self.ty() {
+            FormatTy::Stencil | FormatTy::DepthStencil => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the number of memory planes of the format.
+    ///
+    /// This is 1 except for the multi-planar YCbCr formats, whose components are stored in two
+    /// or three planes.
+    pub fn planes_count(&self) -> u32 {
+        match *self {
+            Format::G8_B8R8_2Plane420Unorm |
+            Format::G8_B8R8_2Plane422Unorm |
+            Format::G10X6_B10X6R10X6_2Plane420Unorm3Pack16 |
+            Format::G10X6_B10X6R10X6_2Plane422Unorm3Pack16 |
+            Format::G12X4_B12X4R12X4_2Plane420Unorm3Pack16 |
+            Format::G12X4_B12X4R12X4_2Plane422Unorm3Pack16 |
+            Format::G16_B16R16_2Plane420Unorm |
+            Format::G16_B16R16_2Plane422Unorm => 2,
+            Format::G8_B8_R8_3Plane420Unorm |
+            Format::G8_B8_R8_3Plane422Unorm |
+            Format::G8_B8_R8_3Plane444Unorm |
+            Format::G10X6_B10X6_R10X6_3Plane420Unorm3Pack16 |
+            Format::G10X6_B10X6_R10X6_3Plane422Unorm3Pack16 |
+            Format::G10X6_B10X6_R10X6_3Plane444Unorm3Pack16 |
+            Format::G12X4_B12X4_R12X4_3Plane420Unorm3Pack16 |
+            Format::G12X4_B12X4_R12X4_3Plane422Unorm3Pack16 |
+            Format::G12X4_B12X4_R12X4_3Plane444Unorm3Pack16 |
+            Format::G16_B16_R16_3Plane420Unorm |
+            Format::G16_B16_R16_3Plane422Unorm |
+            Format::G16_B16_R16_3Plane444Unorm => 3,
+            _ => 1,
+        }
+    }
+
+    /// Returns true if images of `self` and `other` can be copied to each other with
+    /// `copy_image`.
+    ///
+    /// Depth and stencil formats are only compatible with themselves. Multi-planar formats are
+    /// never compatible, since `copy_image` can't copy individual planes. Other formats are
+    /// compatible if their texel blocks have the same size and the same dimensions.
+    pub fn is_copy_compatible_with(&self, other: Format) -> bool {
+        if self.ty().is_depth_and_or_stencil() || other.ty().is_depth_and_or_stencil() {
+            return *self == other;
+        }
+
+        if self.planes_count() > 1 || other.planes_count() > 1 {
+            return false;
+        }
+
+        self.block_size() == other.block_size() &&
+            self.block_dimensions() == other.block_dimensions()
+    }
 }
 
 pub unsafe trait FormatDesc {
@@ -789,6 +1018,153 @@ impl FormatTy {
     }
 }
 
+/// How the values of a format are stored and interpreted.
+///
+/// See the documentation of the module for the meaning of each suffix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NumericType {
+    Unorm,
+    Snorm,
+    Uscaled,
+    Sscaled,
+    Uint,
+    Sint,
+    Ufloat,
+    Sfloat,
+    Srgb,
+}
+
+/// Compression scheme of a compressed format.
+///
+/// Each scheme requires the corresponding feature to be enabled on the device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatCompression {
+    /// BC formats. Requires the `texture_compression_bc` feature.
+    Bc,
+    /// ETC2 and EAC formats. Requires the `texture_compression_etc2` feature.
+    Etc2,
+    /// ASTC LDR formats. Requires the `texture_compression_astc_ldr` feature.
+    AstcLdr,
+}
+
+/// The properties of a format that are supported by a physical device.
+///
+/// Returned by `PhysicalDevice::format_properties`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FormatProperties {
+    /// Features available for images created with linear tiling.
+    pub linear_tiling_features: FormatFeatures,
+    /// Features available for images created with optimal tiling.
+    pub optimal_tiling_features: FormatFeatures,
+    /// Features available for buffers.
+    pub buffer_features: FormatFeatures,
+}
+
+impl FormatProperties {
+    #[inline]
+    pub(crate) fn from_vulkan_properties(props: vk::FormatProperties) -> FormatProperties {
+        FormatProperties {
+            linear_tiling_features: FormatFeatures::from_vulkan_bits(props.linearTilingFeatures),
+            optimal_tiling_features: FormatFeatures::from_vulkan_bits(props.optimalTilingFeatures),
+            buffer_features: FormatFeatures::from_vulkan_bits(props.bufferFeatures),
+        }
+    }
+
+    /// Returns true if the format can't be used for anything on the physical device.
+    #[inline]
+    pub fn is_unsupported(&self) -> bool {
+        self.linear_tiling_features == FormatFeatures::none() &&
+            self.optimal_tiling_features == FormatFeatures::none() &&
+            self.buffer_features == FormatFeatures::none()
+    }
+}
+
+/// The features supported by a format for a kind of resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FormatFeatures {
+    /// Can be used in a sampled image.
+    pub sampled_image: bool,
+    /// Can be used in a storage image.
+    pub storage_image: bool,
+    /// Can be used in a storage image with atomic operations.
+    pub storage_image_atomic: bool,
+    /// Can be used in a uniform texel buffer.
+    pub uniform_texel_buffer: bool,
+    /// Can be used in a storage texel buffer.
+    pub storage_texel_buffer: bool,
+    /// Can be used in a storage texel buffer with atomic operations.
+    pub storage_texel_buffer_atomic: bool,
+    /// Can be used as the format of a vertex attribute.
+    pub vertex_buffer: bool,
+    /// Can be used in a color attachment or an input attachment.
+    pub color_attachment: bool,
+    /// Can be used in a color attachment with blending.
+    pub color_attachment_blend: bool,
+    /// Can be used in a depth-stencil attachment or an input attachment.
+    pub depth_stencil_attachment: bool,
+    /// Can be used as the source of a blit.
+    pub blit_src: bool,
+    /// Can be used as the destination of a blit.
+    pub blit_dst: bool,
+    /// Sampled images of this format can be sampled with linear filtering, and can be the
+    /// source of a blit with linear filtering.
+    pub sampled_image_filter_linear: bool,
+    /// Can be used as the source of a transfer command.
+    ///
+    /// Only reported by devices that use Vulkan 1.1 or that have the `khr_maintenance1`
+    /// extension enabled. On other devices, all the formats support transfers.
+    pub transfer_src: bool,
+    /// Can be used as the destination of a transfer command.
+    ///
+    /// Only reported by devices that use Vulkan 1.1 or that have the `khr_maintenance1`
+    /// extension enabled. On other devices, all the formats support transfers.
+    pub transfer_dst: bool,
+    /// Can be used with a `SamplerYcbcrConversion` whose chroma offsets are
+    /// `ChromaLocation::Midpoint`.
+    pub midpoint_chroma_samples: bool,
+    /// Can be used with a `SamplerYcbcrConversion` whose chroma offsets are
+    /// `ChromaLocation::CositedEven`.
+    pub cosited_chroma_samples: bool,
+    /// Can be used with a `SamplerYcbcrConversion` whose chroma filter is `Filter::Linear`.
+    pub ycbcr_conversion_linear_filter: bool,
+}
+
+impl FormatFeatures {
+    /// Builds a `FormatFeatures` with all the features set to false.
+    #[inline]
+    pub fn none() -> FormatFeatures {
+        FormatFeatures::default()
+    }
+
+    #[inline]
+    pub(crate) fn from_vulkan_bits(bits: vk::FormatFeatureFlags) -> FormatFeatures {
+        FormatFeatures {
+            sampled_image: (bits & vk::FORMAT_FEATURE_SAMPLED_IMAGE_BIT) != 0,
+            storage_image: (bits & vk::FORMAT_FEATURE_STORAGE_IMAGE_BIT) != 0,
+            storage_image_atomic: (bits & vk::FORMAT_FEATURE_STORAGE_IMAGE_ATOMIC_BIT) != 0,
+            uniform_texel_buffer: (bits & vk::FORMAT_FEATURE_UNIFORM_TEXEL_BUFFER_BIT) != 0,
+            storage_texel_buffer: (bits & vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_BIT) != 0,
+            storage_texel_buffer_atomic:
+                (bits & vk::FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_ATOMIC_BIT) != 0,
+            vertex_buffer: (bits & vk::FORMAT_FEATURE_VERTEX_BUFFER_BIT) != 0,
+            color_attachment: (bits & vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BIT) != 0,
+            color_attachment_blend: (bits & vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BLEND_BIT) != 0,
+            depth_stencil_attachment:
+                (bits & vk::FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT) != 0,
+            blit_src: (bits & vk::FORMAT_FEATURE_BLIT_SRC_BIT) != 0,
+            blit_dst: (bits & vk::FORMAT_FEATURE_BLIT_DST_BIT) != 0,
+            sampled_image_filter_linear:
+                (bits & vk::FORMAT_FEATURE_SAMPLED_IMAGE_FILTER_LINEAR_BIT) != 0,
+            transfer_src: (bits & vk::FORMAT_FEATURE_TRANSFER_SRC_BIT_KHR) != 0,
+            transfer_dst: (bits & vk::FORMAT_FEATURE_TRANSFER_DST_BIT_KHR) != 0,
+            midpoint_chroma_samples: (bits & vk::FORMAT_FEATURE_MIDPOINT_CHROMA_SAMPLES_BIT) != 0,
+            cosited_chroma_samples: (bits & vk::FORMAT_FEATURE_COSITED_CHROMA_SAMPLES_BIT) != 0,
+            ycbcr_conversion_linear_filter:
+                (bits & vk::FORMAT_FEATURE_SAMPLED_IMAGE_YCBCR_CONVERSION_LINEAR_FILTER_BIT) != 0,
+        }
+    }
+}
+
 /// Describes a uniform value that will be used to fill an image.
 // TODO: should have the same layout as `vk::ClearValue` for performances
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -809,6 +1185,30 @@ pub enum ClearValue {
     DepthStencil((f32, u32)),
 }
 
+impl ClearValue {
+    /// Returns true if the value can be used to clear an image or an attachment of the given
+    /// format.
+    ///
+    /// `Float` values are used for the formats whose numeric type is neither `Uint` nor `Sint`,
+    /// `Int` values for `Sint` formats and `Uint` values for `Uint` formats. Depth-stencil formats
+    /// must be cleared with the value that matches their aspects. Compressed formats can't be
+    /// cleared, and `ClearValue::None` isn't compatible with any format.
+    #[inline]
+    pub fn is_compatible_with(&self, format: Format) -> bool {
+        let expected_ty = match *self {
+            ClearValue::None => return false,
+            ClearValue::Float(_) => FormatTy::Float,
+            ClearValue::Int(_) => FormatTy::Sint,
+            ClearValue::Uint(_) => FormatTy::Uint,
+            ClearValue::Depth(_) => FormatTy::Depth,
+            ClearValue::Stencil(_) => FormatTy::Stencil,
+            ClearValue::DepthStencil(_) => FormatTy::DepthStencil,
+        };
+
+        format.ty() == expected_ty
+    }
+}
+
 // TODO: remove all these From implementations once they are no longer needed
 
 impl From<[f32; 1]> for ClearValue {
@@ -956,3 +1356,83 @@ macro_rules! impl_clear_values_tuple {
 }
 
 impl_clear_values_tuple!(A B C D E F G H I J K L M N O P Q R S T U V W X Y Z);
+
+#[cfg(test)]
+mod tests {
+    use format::ClearValue;
+    use format::Format;
+    use format::FormatCompression;
+    use format::NumericType;
+    use vk;
+
+    #[test]
+    fn block_size() {
+        assert_eq!(Format::R8G8B8A8Unorm.block_size(), Some(4));
+        assert_eq!(Format::R8G8B8A8Unorm.block_dimensions(), [1, 1]);
+        assert_eq!(Format::BC1_RGBUnormBlock.block_size(), Some(8));
+        assert_eq!(Format::BC7UnormBlock.block_size(), Some(16));
+        assert_eq!(Format::BC7UnormBlock.block_dimensions(), [4, 4]);
+        assert_eq!(Format::ASTC_10x6SrgbBlock.block_dimensions(), [10, 6]);
+        assert_eq!(Format::D24Unorm_S8Uint.block_size(), None);
+    }
+
+    #[test]
+    fn numeric_type() {
+        assert_eq!(Format::R8G8B8A8Srgb.numeric_type(), NumericType::Srgb);
+        assert_eq!(Format::R32Sfloat.numeric_type(), NumericType::Sfloat);
+        assert_eq!(Format::A2B10G10R10UintPack32.numeric_type(), NumericType::Uint);
+        assert_eq!(Format::D32Sfloat_S8Uint.numeric_type(), NumericType::Sfloat);
+    }
+
+    #[test]
+    fn aspects_and_compression() {
+        assert!(Format::R8Unorm.has_color());
+        assert!(!Format::R8Unorm.has_depth());
+        assert!(Format::D24Unorm_S8Uint.has_depth());
+        assert!(Format::D24Unorm_S8Uint.has_stencil());
+        assert!(!Format::D16Unorm.has_stencil());
+        assert_eq!(Format::R8Unorm.compression(), None);
+        assert_eq!(Format::EAC_R11UnormBlock.compression(), Some(FormatCompression::Etc2));
+    }
+
+    #[test]
+    fn copy_compatibility() {
+        assert!(Format::R8G8B8A8Unorm.is_copy_compatible_with(Format::R32Uint));
+        assert!(!Format::R8G8B8A8Unorm.is_copy_compatible_with(Format::R16Uint));
+        assert!(Format::BC1_RGBUnormBlock.is_copy_compatible_with(Format::BC1_RGBASrgbBlock));
+        assert!(!Format::BC1_RGBUnormBlock.is_copy_compatible_with(Format::R32G32Uint));
+        assert!(!Format::D16Unorm.is_copy_compatible_with(Format::R16Unorm));
+        assert!(Format::G8B8G8R8_422Unorm.is_copy_compatible_with(Format::B8G8R8G8_422Unorm));
+        assert!(!Format::G8B8G8R8_422Unorm.is_copy_compatible_with(Format::R8G8B8A8Unorm));
+        assert!(!Format::G8_B8R8_2Plane420Unorm
+                     .is_copy_compatible_with(Format::G8_B8R8_2Plane420Unorm));
+    }
+
+    #[test]
+    fn ycbcr_formats() {
+        assert_eq!(Format::R8G8B8A8Unorm.planes_count(), 1);
+        assert_eq!(Format::G8B8G8R8_422Unorm.planes_count(), 1);
+        assert_eq!(Format::G8B8G8R8_422Unorm.block_size(), Some(4));
+        assert_eq!(Format::G8B8G8R8_422Unorm.block_dimensions(), [2, 1]);
+        assert_eq!(Format::G16_B16R16_2Plane422Unorm.planes_count(), 2);
+        assert_eq!(Format::G10X6_B10X6_R10X6_3Plane444Unorm3Pack16.planes_count(), 3);
+        assert_eq!(Format::G8_B8_R8_3Plane420Unorm.block_size(), None);
+        assert_eq!(Format::R10X6UnormPack16.block_size(), Some(2));
+        assert_eq!(Format::from_vulkan_num(vk::FORMAT_G16_B16_R16_3PLANE_444_UNORM),
+                   Some(Format::G16_B16_R16_3Plane444Unorm));
+    }
+
+    #[test]
+    fn clear_value_compatibility() {
+        assert!(ClearValue::Float([0.0; 4]).is_compatible_with(Format::R8G8B8A8Srgb));
+        assert!(ClearValue::Float([0.0; 4]).is_compatible_with(Format::R16Sscaled));
+        assert!(!ClearValue::Float([0.0; 4]).is_compatible_with(Format::R32Uint));
+        assert!(ClearValue::Uint([0; 4]).is_compatible_with(Format::R32Uint));
+        assert!(ClearValue::Int([0; 4]).is_compatible_with(Format::R8Sint));
+        assert!(!ClearValue::Int([0; 4]).is_compatible_with(Format::R8Uint));
+        assert!(ClearValue::DepthStencil((1.0, 0)).is_compatible_with(Format::D24Unorm_S8Uint));
+        assert!(!ClearValue::Depth(1.0).is_compatible_with(Format::D24Unorm_S8Uint));
+        assert!(!ClearValue::Float([0.0; 4]).is_compatible_with(Format::BC7UnormBlock));
+        assert!(!ClearValue::None.is_compatible_with(Format::R8Unorm));
+    }
+}
@@ -51,6 +51,12 @@ pub fn ensure_image_view_compatible<Rp, I>(render_pass: &Rp, attachment_num: usi
         return Err(IncompatibleRenderPassAttachmentError::NotIdentitySwizzled);
     }
 
+    if image.inner().mipmap_levels() != 1 {
+        return Err(IncompatibleRenderPassAttachmentError::MultipleMipmapLevels {
+                       obtained: image.inner().mipmap_levels(),
+                   });
+    }
+
     for subpass_num in 0 .. render_pass.num_subpasses() {
         let subpass = render_pass
             .subpass_desc(subpass_num)
@@ -67,6 +73,16 @@ pub fn ensure_image_view_compatible<Rp, I>(render_pass: &Rp, attachment_num: usi
             }
         }
 
+        if subpass
+            .resolve_attachments
+            .iter()
+            .any(|&(n, _)| n == attachment_num)
+        {
+            if !image.parent().inner().image.usage_color_attachment() {
+                return Err(IncompatibleRenderPassAttachmentError::MissingColorAttachmentUsage);
+            }
+        }
+
         if let Some((ds, _)) = subpass.depth_stencil {
             if ds == attachment_num {
                 // Was normally checked by the render pass.
@@ -82,6 +98,18 @@ pub fn ensure_image_view_compatible<Rp, I>(render_pass: &Rp, attachment_num: usi
             }
         }
 
+        if let Some(resolve) = render_pass.depth_stencil_resolve_desc(subpass_num) {
+            if resolve.attachment.0 == attachment_num &&
+                !image
+                    .parent()
+                    .inner()
+                    .image
+                    .usage_depth_stencil_attachment()
+            {
+                return Err(IncompatibleRenderPassAttachmentError::MissingDepthStencilAttachmentUsage);
+            }
+        }
+
         if subpass
             .input_attachments
             .iter()
@@ -124,6 +152,12 @@ pub enum IncompatibleRenderPassAttachmentError {
     /// The image view has a component swizzle that is different from identity.
     NotIdentitySwizzled,
 
+    /// The image view gives access to more than one mipmap level.
+    MultipleMipmapLevels {
+        /// Number of mipmap levels of the view.
+        obtained: u32,
+    },
+
     /// The image is used as a color attachment but is missing the color attachment usage.
     MissingColorAttachmentUsage,
 
@@ -149,6 +183,9 @@ impl error::Error for IncompatibleRenderPassAttachmentError {
             IncompatibleRenderPassAttachmentError::NotIdentitySwizzled => {
                 "the image view does not use identity swizzling"
             },
+            IncompatibleRenderPassAttachmentError::MultipleMipmapLevels { .. } => {
+                "the image view gives access to more than one mipmap level"
+            },
             IncompatibleRenderPassAttachmentError::MissingColorAttachmentUsage => {
                 "the image is used as a color attachment but is missing the color attachment usage"
             },
@@ -178,6 +215,9 @@ mod tests {
     use format::Format;
     use framebuffer::EmptySinglePassRenderPassDesc;
     use image::AttachmentImage;
+    use image::Dimensions;
+    use image::ImageUsage;
+    use image::StorageImage;
 
     #[test]
     fn basic_ok() {
@@ -245,5 +285,51 @@ mod tests {
         });
     }
 
+    #[test]
+    fn resolve_missing_color_usage() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 4,
+                },
+                resolve: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+                resolve: [resolve],
+            }
+        ).unwrap();
+
+        let usage = ImageUsage {
+            sampled: true,
+            ..ImageUsage::none()
+        };
+        let img = StorageImage::with_usage(device,
+                                           Dimensions::Dim2d {
+                                               width: 128,
+                                               height: 128,
+                                           },
+                                           Format::R8G8B8A8Unorm,
+                                           usage,
+                                           Some(queue.family()))
+            .unwrap();
+
+        match ensure_image_view_compatible(&rp, 1, &img) {
+            Err(IncompatibleRenderPassAttachmentError::MissingColorAttachmentUsage) => (),
+            e => panic!("{:?}", e),
+        }
+    }
+
     // TODO: more tests
 }
@@ -100,6 +100,26 @@ pub unsafe trait RenderPassDesc: RenderPassDescClearValues<Vec<ClearValue>> {
         }
     }
 
+    /// Returns the multiview configuration of the render pass, or `None` if the render pass
+    /// doesn't use multiview. A description whose view masks are all zero is treated like `None`.
+    ///
+    /// Using multiview requires the `khr_multiview` extension to be enabled on the device.
+    #[inline]
+    fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+        None
+    }
+
+    /// Returns the depth/stencil resolve attachment of the given subpass, or `None` if the
+    /// subpass doesn't resolve its depth/stencil attachment or if the subpass is out of range.
+    ///
+    /// Using a depth/stencil resolve attachment requires the `khr_create_renderpass2` and
+    /// `khr_depth_stencil_resolve` extensions to be enabled on the device.
+    #[inline]
+    fn depth_stencil_resolve_desc(&self, _subpass: usize)
+                                  -> Option<LayoutDepthStencilResolveDescription> {
+        None
+    }
+
     /// Returns true if this render pass is compatible with another render pass.
     ///
     /// Two render passes that contain one subpass are compatible if they are identical. Two render
@@ -343,6 +363,17 @@ unsafe impl<T> RenderPassDesc for T
     fn dependency_desc(&self, num: usize) -> Option<LayoutPassDependencyDescription> {
         (**self).dependency_desc(num)
     }
+
+    #[inline]
+    fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+        (**self).multiview_desc()
+    }
+
+    #[inline]
+    fn depth_stencil_resolve_desc(&self, subpass: usize)
+                                  -> Option<LayoutDepthStencilResolveDescription> {
+        (**self).depth_stencil_resolve_desc(subpass)
+    }
 }
 
 /// Iterator to the attachments of a `RenderPassDesc`.
@@ -429,12 +460,19 @@ pub struct LayoutAttachmentDescription {
     pub samples: u32,
 
     /// What the implementation should do with that attachment at the start of the render pass.
+    ///
+    /// For depth/stencil formats, this only applies to the depth component.
     pub load: LoadOp,
     /// What the implementation should do with that attachment at the end of the render pass.
+    ///
+    /// For depth/stencil formats, this only applies to the depth component.
     pub store: StoreOp,
 
     /// Equivalent of `load` for the stencil component of the attachment, if any. Irrelevant if
     /// there is no stencil component.
+    ///
+    /// This can be different from `load`, for example to keep the stencil component while
+    /// clearing the depth component.
     pub stencil_load: LoadOp,
     /// Equivalent of `store` for the stencil component of the attachment, if any. Irrelevant if
     /// there is no stencil component.
@@ -457,6 +495,22 @@ impl LayoutAttachmentDescription {
     pub fn is_compatible_with(&self, other: &LayoutAttachmentDescription) -> bool {
         self.format == other.format && self.samples == other.samples
     }
+
+    /// Returns true if at least one aspect of this attachment is cleared at the start of the
+    /// render pass, in which case a clear value must be passed for it.
+    ///
+    /// `load` applies to the color and depth aspects, while `stencil_load` applies to the stencil
+    /// aspect.
+    #[inline]
+    pub fn is_cleared(&self) -> bool {
+        match self.format.ty() {
+            FormatTy::Stencil => self.stencil_load == LoadOp::Clear,
+            FormatTy::DepthStencil => {
+                self.load == LoadOp::Clear || self.stencil_load == LoadOp::Clear
+            },
+            _ => self.load == LoadOp::Clear,
+        }
+    }
 }
 
 /// Describes one of the passes of a render pass.
@@ -464,8 +518,10 @@ impl LayoutAttachmentDescription {
 /// # Restrictions
 ///
 /// All these restrictions are checked when the `RenderPass` object is created.
-/// TODO: that's not the case ^
+/// TODO: that's not the case for all of them ^
 ///
+/// - The indices of the attachments must be smaller than the number of attachments of the render
+///   pass.
 /// - The number of color attachments must be less than the limit of the physical device.
 /// - All the attachments in `color_attachments` and `depth_stencil` must have the same
 ///   samples count.
@@ -503,17 +559,33 @@ pub struct LayoutPassDescription {
     pub preserve_attachments: Vec<usize>, // TODO: Vec is slow
 }
 
+/// Value that can be used as the source or destination subpass of a dependency, in order to
+/// designate the commands that are outside of the render pass.
+pub const SUBPASS_EXTERNAL: usize = vk::SUBPASS_EXTERNAL as usize;
+
 /// Describes a dependency between two passes of a render pass.
 ///
 /// The implementation is allowed to change the order of the passes within a render pass, unless
 /// you specify that there exists a dependency between two passes (ie. the result of one will be
 /// used as the input of another one).
+///
+/// # Restrictions
+///
+/// All these restrictions are checked when the `RenderPass` object is created.
+///
+/// - `source_subpass` and `destination_subpass` must be indices of subpasses of the render pass,
+///   or `SUBPASS_EXTERNAL`. They can't be both `SUBPASS_EXTERNAL`.
+/// - If neither is `SUBPASS_EXTERNAL`, then `source_subpass` must be less than or equal to
+///   `destination_subpass`.
+///
 #[derive(Debug, Clone)]
 pub struct LayoutPassDependencyDescription {
-    /// Index of the subpass that writes the data that `destination_subpass` is going to use.
+    /// Index of the subpass that writes the data that `destination_subpass` is going to use, or
+    /// `SUBPASS_EXTERNAL`.
     pub source_subpass: usize,
 
-    /// Index of the subpass that reads the data that `source_subpass` wrote.
+    /// Index of the subpass that reads the data that `source_subpass` wrote, or
+    /// `SUBPASS_EXTERNAL`.
     pub destination_subpass: usize,
 
     /// The pipeline stages that must be finished on the previous subpass before the destination
@@ -543,6 +615,83 @@ pub struct LayoutPassDependencyDescription {
     pub by_region: bool,
 }
 
+/// Describes how the subpasses of a render pass use multiview rendering.
+///
+/// With multiview, each subpass renders to several layers of its attachments at once. The
+/// `ViewIndex` built-in variable of the shaders contains the index of the view being rendered.
+///
+/// # Restrictions
+///
+/// All these restrictions are checked when the `RenderPass` object is created.
+///
+/// - There must be exactly one view mask per subpass.
+/// - The view masks must either be all zero or all non-zero.
+///
+/// If all the view masks are zero, multiview is disabled and the description is ignored.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutMultiviewDescription {
+    /// For each subpass, a bitmask of the views (ie. the layers of the attachments) that the
+    /// subpass renders to.
+    pub view_masks: Vec<u32>,
+
+    /// Bitmasks of views that are likely to be rendered concurrently. This is purely a hint for
+    /// the implementation.
+    pub correlation_masks: Vec<u32>,
+}
+
+impl LayoutMultiviewDescription {
+    /// Returns true if at least one of the view masks is non-zero, in other words if the render
+    /// pass actually uses multiview.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.view_masks.iter().any(|&m| m != 0)
+    }
+}
+
+/// Describes the attachment that the depth/stencil attachment of a subpass is resolved to at the
+/// end of the subpass.
+///
+/// # Restrictions
+///
+/// All these restrictions are checked when the `RenderPass` object is created.
+///
+/// - The subpass must have a multisampled depth/stencil attachment.
+/// - The resolve attachment must not be multisampled and must have the same format as the
+///   depth/stencil attachment.
+/// - At least one of `depth_mode` and `stencil_mode` must be `Some`, and `stencil_mode` must not
+///   be `ResolveMode::Average`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutDepthStencilResolveDescription {
+    /// The attachment to resolve to, and the layout it is in during the subpass.
+    pub attachment: (usize, ImageLayout),
+
+    /// How the depth aspect is resolved, or `None` if it isn't resolved.
+    pub depth_mode: Option<ResolveMode>,
+
+    /// How the stencil aspect is resolved, or `None` if it isn't resolved.
+    pub stencil_mode: Option<ResolveMode>,
+}
+
+/// Describes how the samples of a multisampled depth/stencil attachment are combined when it is
+/// resolved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ResolveMode {
+    /// The value of the first sample is used.
+    SampleZero = vk::RESOLVE_MODE_SAMPLE_ZERO_BIT_KHR,
+
+    /// The average of all the samples is used. Not allowed for the stencil aspect.
+    Average = vk::RESOLVE_MODE_AVERAGE_BIT_KHR,
+
+    /// The smallest of all the samples is used.
+    Min = vk::RESOLVE_MODE_MIN_BIT_KHR,
+
+    /// The largest of all the samples is used.
+    Max = vk::RESOLVE_MODE_MAX_BIT_KHR,
+}
+
 /// Describes what the implementation should do with an attachment after all the subpasses have
 /// completed.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
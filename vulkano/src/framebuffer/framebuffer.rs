@@ -23,6 +23,8 @@ use framebuffer::AttachmentsList;
 use framebuffer::FramebufferAbstract;
 use framebuffer::IncompatibleRenderPassAttachmentError;
 use framebuffer::LayoutAttachmentDescription;
+use framebuffer::LayoutDepthStencilResolveDescription;
+use framebuffer::LayoutMultiviewDescription;
 use framebuffer::LayoutPassDependencyDescription;
 use framebuffer::LayoutPassDescription;
 use framebuffer::RenderPassAbstract;
@@ -32,7 +34,6 @@ use framebuffer::RenderPassSys;
 use framebuffer::ensure_image_view_compatible;
 use image::ImageViewAccess;
 
-use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
@@ -86,6 +87,8 @@ pub struct Framebuffer<Rp, A> {
     framebuffer: vk::Framebuffer,
     dimensions: [u32; 3],
     resources: A,
+    // False if the framebuffer was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<Rp> Framebuffer<Rp, ()> {
@@ -168,6 +171,9 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
                        });
         }
 
+        assert_eq!(attachment.parent().inner().image.device().internal_object(),
+                   self.render_pass.device().internal_object());
+
         match ensure_image_view_compatible(&self.render_pass, self.raw_ids.len(), &attachment) {
             Ok(()) => (),
             Err(err) => return Err(FramebufferCreationError::IncompatibleAttachment(err)),
@@ -277,6 +283,24 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
             },
         };
 
+        // With multiview, the framebuffer must have one layer and the views are rendered to the
+        // layers of the attachments.
+        let dimensions = match self.render_pass.multiview_desc().filter(|m| m.is_enabled()) {
+            Some(multiview) => {
+                let mask = multiview.view_masks.iter().fold(0, |a, &m| a | m);
+                let required_layers = 32 - mask.leading_zeros();
+                if dimensions[2] < required_layers {
+                    return Err(FramebufferCreationError::MultiviewLayersMismatch {
+                                   required: required_layers,
+                                   obtained: dimensions[2],
+                               });
+                }
+
+                [dimensions[0], dimensions[1], 1]
+            },
+            None => dimensions,
+        };
+
         // Checking the dimensions against the limits.
         {
             let limits = device.physical_device().limits();
@@ -319,11 +343,42 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
                framebuffer: framebuffer,
                dimensions: dimensions,
                resources: self.attachments,
+               needs_destruction: true,
            })
     }
 }
 
 impl<Rp, A> Framebuffer<Rp, A> {
+    /// Builds a `Framebuffer` from a raw handle that was created outside of vulkano.
+    ///
+    /// `attachments` are kept alive by the framebuffer, and are used for the synchronization of
+    /// the command buffers that use it.
+    /// If `owned` is true, the framebuffer is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid framebuffer of the device of `render_pass`, created with a
+    ///   render pass compatible with `render_pass`, with the views of `attachments` in the same
+    ///   order and with `dimensions`.
+    /// - If `owned` is true, the framebuffer must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(render_pass: Rp, handle: vk::Framebuffer, dimensions: [u32; 3],
+                           attachments: A, owned: bool)
+                           -> Framebuffer<Rp, A>
+        where Rp: RenderPassAbstract,
+              A: AttachmentsList
+    {
+        Framebuffer {
+            device: render_pass.device().clone(),
+            render_pass: render_pass,
+            framebuffer: handle,
+            dimensions: dimensions,
+            resources: attachments,
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns the width, height and layers of this framebuffer.
     #[inline]
     pub fn dimensions(&self) -> [u32; 3] {
@@ -413,6 +468,17 @@ unsafe impl<Rp, A> RenderPassDesc for Framebuffer<Rp, A>
     fn dependency_desc(&self, num: usize) -> Option<LayoutPassDependencyDescription> {
         self.render_pass.dependency_desc(num)
     }
+
+    #[inline]
+    fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+        self.render_pass.multiview_desc()
+    }
+
+    #[inline]
+    fn depth_stencil_resolve_desc(&self, subpass: usize)
+                                  -> Option<LayoutDepthStencilResolveDescription> {
+        self.render_pass.depth_stencil_resolve_desc(subpass)
+    }
 }
 
 unsafe impl<C, Rp, A> RenderPassDescClearValues<C> for Framebuffer<Rp, A>
@@ -443,6 +509,10 @@ unsafe impl<Rp, A> DeviceOwned for Framebuffer<Rp, A> {
 impl<Rp, A> Drop for Framebuffer<Rp, A> {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyFramebuffer(self.device.internal_object(), self.framebuffer, ptr::null());
@@ -490,13 +560,16 @@ pub enum FramebufferCreationError {
     IncompatibleAttachment(IncompatibleRenderPassAttachmentError),
     /// The framebuffer has no attachment and no dimension was specified.
     CantDetermineDimensions,
-}
-
-impl From<OomError> for FramebufferCreationError {
-    #[inline]
-    fn from(err: OomError) -> FramebufferCreationError {
-        FramebufferCreationError::OomError(err)
-    }
+    /// The render pass uses multiview, but the attachments don't have enough layers for all the
+    /// views.
+    MultiviewLayersMismatch {
+        /// Minimum number of layers required by the view masks.
+        required: u32,
+        /// Number of layers of the attachments.
+        obtained: u32,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for FramebufferCreationError {
@@ -518,6 +591,11 @@ impl error::Error for FramebufferCreationError {
             FramebufferCreationError::CantDetermineDimensions => {
                 "the framebuffer has no attachment and no dimension was specified"
             },
+            FramebufferCreationError::MultiviewLayersMismatch { .. } => {
+                "the attachments don't have enough layers for all the views of the render pass"
+            },
+            FramebufferCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -531,23 +609,13 @@ impl error::Error for FramebufferCreationError {
     }
 }
 
-impl fmt::Display for FramebufferCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<Error> for FramebufferCreationError {
-    #[inline]
-    fn from(err: Error) -> FramebufferCreationError {
-        FramebufferCreationError::from(OomError::from(err))
-    }
-}
+vk_error_conversions!(FramebufferCreationError, OomError {});
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
     use format::Format;
+    use framebuffer::FramebufferAbstract;
     use framebuffer::EmptySinglePassRenderPassDesc;
     use framebuffer::Framebuffer;
     use framebuffer::FramebufferCreationError;
@@ -874,6 +942,24 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = Arc::new(EmptySinglePassRenderPassDesc
+                              .build_render_pass(device)
+                              .unwrap());
+        let fb = Framebuffer::with_dimensions(rp.clone(), [512, 256, 1])
+            .build()
+            .unwrap();
+
+        let borrowed = unsafe {
+            Framebuffer::from_raw(rp, fb.inner().internal_object(), [512, 256, 1], (), false)
+        };
+        assert_eq!(borrowed.inner().internal_object(), fb.inner().internal_object());
+        assert_eq!(borrowed.dimensions(), [512, 256, 1]);
+    }
+
     #[test]
     fn cant_determine_dimensions_auto() {
         let (device, _) = gfx_dev_and_queue!();
@@ -0,0 +1,665 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Frame graphs, which derive the structure of a frame from the list of its passes.
+//!
+//! Instead of manually working out the render passes and dependencies, you declare each pass of
+//! your frame along with the images it reads and writes, and the frame graph determines:
+//!
+//! - Which passes actually contribute to the outputs of the frame. The others are culled.
+//! - The dependencies between passes, which correspond to the barriers or subpass dependencies
+//!   that must be inserted.
+//! - Which consecutive passes can be merged into the subpasses of a single render pass.
+//! - Which transient images can share the same physical image, because their lifetimes don't
+//!   overlap.
+//!
+//! The result of `FrameGraph::compile` is only a plan. Passes are never reordered: they execute
+//! in the order in which they were added. The frame graph doesn't create any Vulkan object, and
+//! it is the responsibility of the user to create the physical images and the render passes
+//! described by the plan, and to record the commands of each pass.
+
+use format::Format;
+use image::ImageUsage;
+use std::error;
+use std::fmt;
+
+/// Declaration of the passes of a frame and of the images they use.
+///
+/// Passes are executed in the order in which they are added, and each pass sees the content
+/// written by the previous passes.
+#[derive(Debug, Clone, Default)]
+pub struct FrameGraph {
+    images: Vec<ImageEntry>,
+    passes: Vec<Vec<(FrameGraphImage, FrameGraphAccess)>>,
+}
+
+#[derive(Debug, Clone)]
+struct ImageEntry {
+    desc: FrameGraphImageDesc,
+    imported: bool,
+}
+
+/// Identifier of an image of a `FrameGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FrameGraphImage(usize);
+
+/// Identifier of a pass of a `FrameGraph`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FrameGraphPass(usize);
+
+/// Characteristics of an image of a frame graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameGraphImageDesc {
+    /// Width and height of the image.
+    pub dimensions: [u32; 2],
+    /// Format of the image.
+    pub format: Format,
+    /// Number of samples of the image.
+    pub samples: u32,
+}
+
+/// How a pass uses an image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FrameGraphAccess {
+    /// The image is written as a color attachment.
+    ColorAttachment,
+    /// The image is read and written as a depth/stencil attachment.
+    DepthStencilAttachment,
+    /// The image is read as an input attachment.
+    InputAttachment,
+    /// The image is read from a shader through a sampler. The image can't be an attachment of the
+    /// same render pass.
+    Sampled,
+}
+
+impl FrameGraphAccess {
+    /// Returns true if the pass modifies the content of the image.
+    #[inline]
+    pub fn is_write(&self) -> bool {
+        match *self {
+            FrameGraphAccess::ColorAttachment => true,
+            FrameGraphAccess::DepthStencilAttachment => true,
+            FrameGraphAccess::InputAttachment => false,
+            FrameGraphAccess::Sampled => false,
+        }
+    }
+
+    /// Returns true if the image is used as an attachment of the render pass.
+    #[inline]
+    pub fn is_attachment(&self) -> bool {
+        *self != FrameGraphAccess::Sampled
+    }
+}
+
+impl FrameGraph {
+    /// Builds a new frame graph with no image and no pass.
+    #[inline]
+    pub fn new() -> FrameGraph {
+        FrameGraph {
+            images: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Declares an image that only exists for the duration of the frame.
+    ///
+    /// Transient images must be written before they are read, and can share the same physical
+    /// image as other transient images.
+    #[inline]
+    pub fn add_transient_image(&mut self, desc: FrameGraphImageDesc) -> FrameGraphImage {
+        self.images.push(ImageEntry {
+                             desc: desc,
+                             imported: false,
+                         });
+        FrameGraphImage(self.images.len() - 1)
+    }
+
+    /// Declares an image that is provided by the user, for example an image of a swapchain or
+    /// an image whose content is kept between frames.
+    #[inline]
+    pub fn add_imported_image(&mut self, desc: FrameGraphImageDesc) -> FrameGraphImage {
+        self.images.push(ImageEntry {
+                             desc: desc,
+                             imported: true,
+                         });
+        FrameGraphImage(self.images.len() - 1)
+    }
+
+    /// Declares a pass that executes after all the passes that were previously added, and the
+    /// images that it uses.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the images doesn't belong to this frame graph.
+    ///
+    pub fn add_pass<I>(&mut self, accesses: I) -> FrameGraphPass
+        where I: IntoIterator<Item = (FrameGraphImage, FrameGraphAccess)>
+    {
+        let accesses = accesses.into_iter().collect::<Vec<_>>();
+        for &(image, _) in accesses.iter() {
+            assert!(image.0 < self.images.len());
+        }
+
+        self.passes.push(accesses);
+        FrameGraphPass(self.passes.len() - 1)
+    }
+
+    /// Returns the description of an image of the frame graph.
+    #[inline]
+    pub fn image_desc(&self, image: FrameGraphImage) -> Option<FrameGraphImageDesc> {
+        self.images.get(image.0).map(|i| i.desc)
+    }
+
+    /// Computes the plan of the frame.
+    ///
+    /// `outputs` is the list of images whose content is needed after the frame, for example the
+    /// image of the swapchain that is going to be presented. Passes that don't contribute to any
+    /// output are culled.
+    pub fn compile<I>(&self, outputs: I) -> Result<CompiledFrameGraph, FrameGraphError>
+        where I: IntoIterator<Item = FrameGraphImage>
+    {
+        let outputs = outputs.into_iter().collect::<Vec<_>>();
+
+        // Checking the passes individually, and checking that transient images are written
+        // before they are read.
+        let mut written = vec![false; self.images.len()];
+        for (pass_num, accesses) in self.passes.iter().enumerate() {
+            let pass = FrameGraphPass(pass_num);
+
+            for (n, &(image, access)) in accesses.iter().enumerate() {
+                if accesses[.. n].iter().any(|&(i, _)| i == image) {
+                    return Err(FrameGraphError::ImageAccessedTwice {
+                                   pass: pass,
+                                   image: image,
+                               });
+                }
+
+                if !access.is_write() && !self.images[image.0].imported && !written[image.0] {
+                    return Err(FrameGraphError::ReadBeforeWrite {
+                                   pass: pass,
+                                   image: image,
+                               });
+                }
+            }
+
+            let mut dimensions = accesses
+                .iter()
+                .filter(|&&(_, access)| access.is_attachment())
+                .map(|&(image, _)| self.images[image.0].desc.dimensions);
+            if let Some(first) = dimensions.next() {
+                if dimensions.any(|d| d != first) {
+                    return Err(FrameGraphError::AttachmentDimensionsMismatch { pass: pass });
+                }
+            }
+
+            for &(image, access) in accesses.iter() {
+                if access.is_write() {
+                    written[image.0] = true;
+                }
+            }
+        }
+
+        // Culling the passes that don't contribute to the outputs. Attachments writes are
+        // considered as reads as well, because the previous content may be loaded.
+        let mut needed = vec![false; self.images.len()];
+        for &output in outputs.iter() {
+            assert!(output.0 < self.images.len());
+            if !written[output.0] {
+                return Err(FrameGraphError::OutputNeverWritten { image: output });
+            }
+            needed[output.0] = true;
+        }
+
+        let mut kept = vec![false; self.passes.len()];
+        for (pass_num, accesses) in self.passes.iter().enumerate().rev() {
+            if !accesses
+                .iter()
+                .any(|&(image, access)| access.is_write() && needed[image.0])
+            {
+                continue;
+            }
+
+            kept[pass_num] = true;
+            for &(image, _) in accesses.iter() {
+                needed[image.0] = true;
+            }
+        }
+
+        let passes = (0 .. self.passes.len())
+            .filter(|&n| kept[n])
+            .map(FrameGraphPass)
+            .collect::<Vec<_>>();
+
+        // Read-after-write, write-after-read and write-after-write dependencies. A read depends on
+        // the last previous write to the same image. A write depends on all the reads since the
+        // last previous write, or on the write itself if there's no such read.
+        let mut dependencies = Vec::new();
+        for (order, &pass) in passes.iter().enumerate() {
+            for &(image, access) in self.passes[pass.0].iter() {
+                let previous = passes[.. order].iter().rev().filter_map(|&prev| {
+                    self.passes[prev.0]
+                        .iter()
+                        .find(|&&(i, _)| i == image)
+                        .map(|&(_, prev_access)| (prev, prev_access))
+                });
+
+                // The scan only stops at a write. Earlier reads never hide a write from a read,
+                // but they do hide it from a write, since the reads already depend on it.
+                let mut found_read = false;
+                for (prev, prev_access) in previous {
+                    let depends = if prev_access.is_write() {
+                        !(access.is_write() && found_read)
+                    } else {
+                        access.is_write()
+                    };
+
+                    if depends {
+                        dependencies.push(FrameGraphDependency {
+                                              source: prev,
+                                              destination: pass,
+                                              image: image,
+                                              source_access: prev_access,
+                                              destination_access: access,
+                                          });
+                    }
+
+                    if prev_access.is_write() {
+                        break;
+                    }
+                    found_read = true;
+                }
+            }
+        }
+
+        // Merging consecutive passes into render passes. A pass can become a subpass of the
+        // current render pass if its attachments have the same dimensions, and if no image is
+        // sampled by one pass and used as an attachment by another.
+        let mut render_passes: Vec<Vec<FrameGraphPass>> = Vec::new();
+        for &pass in passes.iter() {
+            let can_merge = match render_passes.last() {
+                Some(group) => self.can_merge(group, pass),
+                None => false,
+            };
+
+            if can_merge {
+                render_passes.last_mut().unwrap().push(pass);
+            } else {
+                render_passes.push(vec![pass]);
+            }
+        }
+
+        // Lifetime of each image, in render pass indices. Outputs live until the end of the frame.
+        let mut lifetimes: Vec<Option<(usize, usize)>> = vec![None; self.images.len()];
+        for (group_num, group) in render_passes.iter().enumerate() {
+            for &pass in group.iter() {
+                for &(image, _) in self.passes[pass.0].iter() {
+                    let lifetime = &mut lifetimes[image.0];
+                    *lifetime = match *lifetime {
+                        Some((first, _)) => Some((first, group_num)),
+                        None => Some((group_num, group_num)),
+                    };
+                }
+            }
+        }
+        for &output in outputs.iter() {
+            if let Some((first, _)) = lifetimes[output.0] {
+                lifetimes[output.0] = Some((first, render_passes.len()));
+            }
+        }
+
+        // Usage of each image.
+        let mut usages = vec![ImageUsage::none(); self.images.len()];
+        for &pass in passes.iter() {
+            for &(image, access) in self.passes[pass.0].iter() {
+                let usage = &mut usages[image.0];
+                match access {
+                    FrameGraphAccess::ColorAttachment => usage.color_attachment = true,
+                    FrameGraphAccess::DepthStencilAttachment => {
+                        usage.depth_stencil_attachment = true
+                    },
+                    FrameGraphAccess::InputAttachment => usage.input_attachment = true,
+                    FrameGraphAccess::Sampled => usage.sampled = true,
+                }
+            }
+        }
+
+        // Assigning physical images to the transient images, in order of first use. Two transient
+        // images can share the same physical image if they have the same description and if
+        // their lifetimes don't overlap.
+        let mut transients = (0 .. self.images.len())
+            .filter(|&n| !self.images[n].imported)
+            .filter_map(|n| lifetimes[n].map(|l| (n, l)))
+            .collect::<Vec<_>>();
+        transients.sort_by_key(|&(_, (first, _))| first);
+
+        let mut physical_images: Vec<(FrameGraphImageDesc, ImageUsage, usize)> = Vec::new();
+        let mut aliases = vec![None; self.images.len()];
+        for (image_num, (first, last)) in transients {
+            let desc = self.images[image_num].desc;
+            let slot = physical_images
+                .iter()
+                .position(|&(d, _, slot_last)| d == desc && slot_last < first);
+
+            let slot = match slot {
+                Some(slot) => {
+                    let entry = &mut physical_images[slot];
+                    entry.1 = entry.1 | usages[image_num];
+                    entry.2 = last;
+                    slot
+                },
+                None => {
+                    physical_images.push((desc, usages[image_num], last));
+                    physical_images.len() - 1
+                },
+            };
+
+            aliases[image_num] = Some(slot);
+        }
+
+        Ok(CompiledFrameGraph {
+               passes: passes,
+               render_passes: render_passes,
+               dependencies: dependencies,
+               usages: usages,
+               aliases: aliases,
+               physical_images: physical_images
+                   .into_iter()
+                   .map(|(desc, mut usage, _)| {
+                            // Images that are only used as attachments don't need to be backed by
+                            // actual memory on some implementations.
+                            usage.transient_attachment = !usage.sampled;
+                            (desc, usage)
+                        })
+                   .collect(),
+           })
+    }
+
+    // Returns true if `pass` can be added as a subpass to the render pass made of `group`.
+    fn can_merge(&self, group: &[FrameGraphPass], pass: FrameGraphPass) -> bool {
+        let dimensions = |p: FrameGraphPass| {
+            self.passes[p.0]
+                .iter()
+                .find(|&&(_, access)| access.is_attachment())
+                .map(|&(image, _)| self.images[image.0].desc.dimensions)
+        };
+
+        let pass_dimensions = match dimensions(pass) {
+            Some(d) => d,
+            None => return false,
+        };
+        if group.iter().any(|&p| dimensions(p) != Some(pass_dimensions)) {
+            return false;
+        }
+
+        let conflicts = |a: FrameGraphPass, b: FrameGraphPass| {
+            self.passes[a.0].iter().any(|&(image, access)| {
+                access == FrameGraphAccess::Sampled &&
+                    self.passes[b.0]
+                        .iter()
+                        .any(|&(i, acc)| i == image && acc.is_attachment())
+            })
+        };
+
+        !group.iter().any(|&p| conflicts(p, pass) || conflicts(pass, p))
+    }
+}
+
+/// Plan of a frame, obtained from `FrameGraph::compile`.
+#[derive(Debug, Clone)]
+pub struct CompiledFrameGraph {
+    passes: Vec<FrameGraphPass>,
+    render_passes: Vec<Vec<FrameGraphPass>>,
+    dependencies: Vec<FrameGraphDependency>,
+    usages: Vec<ImageUsage>,
+    aliases: Vec<Option<usize>>,
+    physical_images: Vec<(FrameGraphImageDesc, ImageUsage)>,
+}
+
+impl CompiledFrameGraph {
+    /// Returns the passes that weren't culled, in execution order.
+    #[inline]
+    pub fn passes(&self) -> &[FrameGraphPass] {
+        &self.passes
+    }
+
+    /// Returns the render passes of the frame, in execution order. Each render pass is a list of
+    /// passes that correspond to its subpasses.
+    #[inline]
+    pub fn render_passes(&self) -> &[Vec<FrameGraphPass>] {
+        &self.render_passes
+    }
+
+    /// Returns the dependencies between the passes.
+    ///
+    /// If both passes belong to the same render pass, the dependency corresponds to a subpass
+    /// dependency. Otherwise it corresponds to a pipeline barrier.
+    #[inline]
+    pub fn dependencies(&self) -> &[FrameGraphDependency] {
+        &self.dependencies
+    }
+
+    /// Returns the usage that an image must have for the passes that weren't culled.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the image doesn't belong to the frame graph.
+    ///
+    #[inline]
+    pub fn image_usage(&self, image: FrameGraphImage) -> ImageUsage {
+        self.usages[image.0]
+    }
+
+    /// Returns the index of the physical image that must be used for a transient image, or
+    /// `None` if the image is imported or unused.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the image doesn't belong to the frame graph.
+    ///
+    #[inline]
+    pub fn physical_image(&self, image: FrameGraphImage) -> Option<usize> {
+        self.aliases[image.0]
+    }
+
+    /// Returns the number of physical images that must be created for the transient images.
+    #[inline]
+    pub fn num_physical_images(&self) -> usize {
+        self.physical_images.len()
+    }
+
+    /// Returns the description and the usage of a physical image, or `None` if out of range.
+    #[inline]
+    pub fn physical_image_desc(&self, num: usize) -> Option<(FrameGraphImageDesc, ImageUsage)> {
+        self.physical_images.get(num).cloned()
+    }
+}
+
+/// Dependency between two passes of a compiled frame graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameGraphDependency {
+    /// Pass that must be finished accessing the image.
+    pub source: FrameGraphPass,
+    /// Pass that waits for the source pass.
+    pub destination: FrameGraphPass,
+    /// Image that both passes access.
+    pub image: FrameGraphImage,
+    /// How the source pass accesses the image.
+    pub source_access: FrameGraphAccess,
+    /// How the destination pass accesses the image.
+    pub destination_access: FrameGraphAccess,
+}
+
+/// Error that can happen when compiling a frame graph.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FrameGraphError {
+    /// A pass accesses the same image more than once.
+    ImageAccessedTwice {
+        /// The pass.
+        pass: FrameGraphPass,
+        /// The image.
+        image: FrameGraphImage,
+    },
+
+    /// The attachments of a pass don't all have the same dimensions.
+    AttachmentDimensionsMismatch {
+        /// The pass.
+        pass: FrameGraphPass,
+    },
+
+    /// A pass reads a transient image that hasn't been written by a previous pass.
+    ReadBeforeWrite {
+        /// The pass.
+        pass: FrameGraphPass,
+        /// The image.
+        image: FrameGraphImage,
+    },
+
+    /// An output of the frame graph isn't written by any pass.
+    OutputNeverWritten {
+        /// The image.
+        image: FrameGraphImage,
+    },
+}
+
+impl error::Error for FrameGraphError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            FrameGraphError::ImageAccessedTwice { .. } => {
+                "a pass accesses the same image more than once"
+            },
+            FrameGraphError::AttachmentDimensionsMismatch { .. } => {
+                "the attachments of a pass don't all have the same dimensions"
+            },
+            FrameGraphError::ReadBeforeWrite { .. } => {
+                "a pass reads a transient image that hasn't been written by a previous pass"
+            },
+            FrameGraphError::OutputNeverWritten { .. } => {
+                "an output of the frame graph isn't written by any pass"
+            },
+        }
+    }
+}
+
+impl fmt::Display for FrameGraphError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use framebuffer::FrameGraph;
+    use framebuffer::FrameGraphAccess;
+    use framebuffer::FrameGraphError;
+    use framebuffer::FrameGraphImageDesc;
+
+    fn desc(format: Format) -> FrameGraphImageDesc {
+        FrameGraphImageDesc {
+            dimensions: [1024, 768],
+            format: format,
+            samples: 1,
+        }
+    }
+
+    #[test]
+    fn culling_and_merging() {
+        let mut graph = FrameGraph::new();
+        let gbuffer = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let depth = graph.add_transient_image(desc(Format::D16Unorm));
+        let unused = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let swapchain = graph.add_imported_image(desc(Format::B8G8R8A8Srgb));
+
+        let geometry = graph.add_pass(vec![(gbuffer, FrameGraphAccess::ColorAttachment),
+                                           (depth, FrameGraphAccess::DepthStencilAttachment)]);
+        let _culled = graph.add_pass(vec![(unused, FrameGraphAccess::ColorAttachment)]);
+        let lighting = graph.add_pass(vec![(gbuffer, FrameGraphAccess::InputAttachment),
+                                           (swapchain, FrameGraphAccess::ColorAttachment)]);
+
+        let compiled = graph.compile(Some(swapchain)).unwrap();
+        assert_eq!(compiled.passes(), &[geometry, lighting]);
+        assert_eq!(compiled.render_passes(), &[vec![geometry, lighting]]);
+        assert_eq!(compiled.dependencies().len(), 1);
+        assert_eq!(compiled.dependencies()[0].source, geometry);
+        assert_eq!(compiled.dependencies()[0].image, gbuffer);
+        assert!(compiled.physical_image(unused).is_none());
+        assert!(compiled.physical_image(swapchain).is_none());
+        assert!(compiled.image_usage(gbuffer).input_attachment);
+    }
+
+    #[test]
+    fn aliasing() {
+        let mut graph = FrameGraph::new();
+        let a = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let b = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let c = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let out = graph.add_imported_image(desc(Format::R8G8B8A8Unorm));
+
+        graph.add_pass(vec![(a, FrameGraphAccess::ColorAttachment)]);
+        graph.add_pass(vec![(a, FrameGraphAccess::Sampled),
+                            (b, FrameGraphAccess::ColorAttachment)]);
+        graph.add_pass(vec![(b, FrameGraphAccess::Sampled),
+                            (c, FrameGraphAccess::ColorAttachment)]);
+        graph.add_pass(vec![(c, FrameGraphAccess::Sampled),
+                            (out, FrameGraphAccess::ColorAttachment)]);
+
+        let compiled = graph.compile(Some(out)).unwrap();
+        assert_eq!(compiled.render_passes().len(), 4);
+        assert_eq!(compiled.num_physical_images(), 2);
+        assert_eq!(compiled.physical_image(a), compiled.physical_image(c));
+        assert!(compiled.physical_image(a) != compiled.physical_image(b));
+    }
+
+    #[test]
+    fn reads_depend_on_last_write() {
+        let mut graph = FrameGraph::new();
+        let a = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let b = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let out = graph.add_imported_image(desc(Format::R8G8B8A8Unorm));
+
+        let write = graph.add_pass(vec![(a, FrameGraphAccess::ColorAttachment)]);
+        let first_read = graph.add_pass(vec![(a, FrameGraphAccess::Sampled),
+                                             (b, FrameGraphAccess::ColorAttachment)]);
+        let second_read = graph.add_pass(vec![(a, FrameGraphAccess::Sampled),
+                                              (b, FrameGraphAccess::Sampled),
+                                              (out, FrameGraphAccess::ColorAttachment)]);
+
+        let compiled = graph.compile(Some(out)).unwrap();
+        let depends = |source, destination, image| {
+            compiled
+                .dependencies()
+                .iter()
+                .any(|d| d.source == source && d.destination == destination && d.image == image)
+        };
+
+        assert_eq!(compiled.dependencies().len(), 3);
+        assert!(depends(write, first_read, a));
+        assert!(depends(write, second_read, a));
+        assert!(depends(first_read, second_read, b));
+    }
+
+    #[test]
+    fn read_before_write() {
+        let mut graph = FrameGraph::new();
+        let a = graph.add_transient_image(desc(Format::R8G8B8A8Unorm));
+        let out = graph.add_imported_image(desc(Format::R8G8B8A8Unorm));
+        let pass = graph.add_pass(vec![(a, FrameGraphAccess::Sampled),
+                                       (out, FrameGraphAccess::ColorAttachment)]);
+
+        match graph.compile(Some(out)) {
+            Err(FrameGraphError::ReadBeforeWrite { pass: p, image }) => {
+                assert_eq!(p, pass);
+                assert_eq!(image, a);
+            },
+            _ => panic!(),
+        }
+    }
+}
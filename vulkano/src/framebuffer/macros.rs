@@ -37,6 +37,18 @@ macro_rules! single_pass_renderpass {
 /// Builds a `RenderPass` object whose template parameter is of undeterminate type.
 #[macro_export]
 macro_rules! ordered_passes_renderpass {
+    // Returns the stencil operation of an attachment. The stencil operations default to the same
+    // as the depth operations, and can only be specified once.
+    (@stencil_op $ty:ident, $default:ident) => (
+        $crate::framebuffer::$ty::$default
+    );
+    (@stencil_op $ty:ident, $default:ident, $op:ident) => (
+        $crate::framebuffer::$ty::$op
+    );
+    (@stencil_op $ty:ident, $default:ident, $op:ident, $($others:ident),+) => (
+        compile_error!("`stencil_load` and `stencil_store` can only be specified once per attachment")
+    );
+
     (
         $device:expr,
         attachments: {
@@ -46,6 +58,8 @@ macro_rules! ordered_passes_renderpass {
                     store: $store:ident,
                     format: $format:expr,
                     samples: $samples:expr,
+                    $(stencil_load: $stencil_load:ident,)*
+                    $(stencil_store: $stencil_store:ident,)*
                     $(initial_layout: $init_layout:expr,)*
                     $(final_layout: $final_layout:expr,)*
                 }
@@ -151,8 +165,10 @@ macro_rules! ordered_passes_renderpass {
                             samples: desc.$atch_name.1,
                             load: $crate::framebuffer::LoadOp::$load,
                             store: $crate::framebuffer::StoreOp::$store,
-                            stencil_load: $crate::framebuffer::LoadOp::$load,
-                            stencil_store: $crate::framebuffer::StoreOp::$store,
+                            stencil_load: ordered_passes_renderpass!(@stencil_op LoadOp, $load
+                                                                     $(, $stencil_load)*),
+                            stencil_store: ordered_passes_renderpass!(@stencil_op StoreOp, $store
+                                                                      $(, $stencil_store)*),
                             initial_layout: initial_layout,
                             final_layout: final_layout,
                         });
@@ -312,13 +328,18 @@ macro_rules! ordered_passes_renderpass {
                 })*
 
                 $(if $atch_name == num {
-                    // If the clear OP is Clear or DontCare, default to the Undefined layout.
+                    let stencil_load = ordered_passes_renderpass!(@stencil_op LoadOp, $load
+                                                                  $(, $stencil_load)*);
+
+                    // If the clear OP is Clear or DontCare, default to the Undefined layout. For
+                    // depth/stencil attachments, this must be the case of both the depth and the
+                    // stencil clear OPs.
                     if initial_layout == Some(ImageLayout::DepthStencilAttachmentOptimal) ||
                         initial_layout == Some(ImageLayout::ColorAttachmentOptimal) ||
                         initial_layout == Some(ImageLayout::TransferDstOptimal)
                     {
-                        if $crate::framebuffer::LoadOp::$load == $crate::framebuffer::LoadOp::Clear ||
-                            $crate::framebuffer::LoadOp::$load == $crate::framebuffer::LoadOp::DontCare
+                        if $crate::framebuffer::LoadOp::$load != $crate::framebuffer::LoadOp::Load &&
+                            stencil_load != $crate::framebuffer::LoadOp::Load
                         {
                             initial_layout = Some(ImageLayout::Undefined);
                         }
@@ -375,4 +396,38 @@ mod tests {
             }
         ).unwrap();
     }
+
+    #[test]
+    fn separate_stencil_ops() {
+        use framebuffer::LoadOp;
+        use framebuffer::RenderPassDesc;
+        use framebuffer::StoreOp;
+        use image::ImageLayout;
+
+        let (device, _) = gfx_dev_and_queue!();
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D24Unorm_S8Uint,
+                    samples: 1,
+                    stencil_load: Load,
+                    stencil_store: Store,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth}
+            }
+        ).unwrap();
+
+        let desc = rp.attachment_desc(0).unwrap();
+        assert_eq!(desc.load, LoadOp::Clear);
+        assert_eq!(desc.store, StoreOp::DontCare);
+        assert_eq!(desc.stencil_load, LoadOp::Load);
+        assert_eq!(desc.stencil_store, StoreOp::Store);
+        // The stencil aspect is loaded, so the content of the attachment must be preserved.
+        assert_eq!(desc.initial_layout, ImageLayout::DepthStencilAttachmentOptimal);
+    }
 }
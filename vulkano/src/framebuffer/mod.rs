@@ -89,11 +89,21 @@
 //! See [the documentation of the `Framebuffer` struct](struct.Framebuffer.html) for information
 //! about how to create a framebuffer.
 //!
+//! # Frame graphs
+//!
+//! Instead of designing the render passes of a frame by hand, you can declare the passes of the
+//! frame and the images they use in a `FrameGraph`. Compiling the frame graph culls the unused
+//! passes, computes the dependencies between passes, groups the passes into render passes and
+//! lets transient images share memory. The result is a plan: creating the images and the render
+//! passes, and recording the passes in their declaration order, is left to the user.
+//!
 
 pub use self::attachments_list::AttachmentsList;
 pub use self::compat_atch::IncompatibleRenderPassAttachmentError;
 pub use self::compat_atch::ensure_image_view_compatible;
 pub use self::desc::LayoutAttachmentDescription;
+pub use self::desc::LayoutDepthStencilResolveDescription;
+pub use self::desc::LayoutMultiviewDescription;
 pub use self::desc::LayoutPassDependencyDescription;
 pub use self::desc::LayoutPassDescription;
 pub use self::desc::LoadOp;
@@ -101,15 +111,26 @@ pub use self::desc::RenderPassDesc;
 pub use self::desc::RenderPassDescAttachments;
 pub use self::desc::RenderPassDescDependencies;
 pub use self::desc::RenderPassDescSubpasses;
+pub use self::desc::ResolveMode;
+pub use self::desc::SUBPASS_EXTERNAL;
 pub use self::desc::StoreOp;
 pub use self::empty::EmptySinglePassRenderPassDesc;
 pub use self::framebuffer::Framebuffer;
 pub use self::framebuffer::FramebufferBuilder;
 pub use self::framebuffer::FramebufferCreationError;
 pub use self::framebuffer::FramebufferSys;
+pub use self::graph::CompiledFrameGraph;
+pub use self::graph::FrameGraph;
+pub use self::graph::FrameGraphAccess;
+pub use self::graph::FrameGraphDependency;
+pub use self::graph::FrameGraphError;
+pub use self::graph::FrameGraphImage;
+pub use self::graph::FrameGraphImageDesc;
+pub use self::graph::FrameGraphPass;
 pub use self::sys::RenderPass;
 pub use self::sys::RenderPassCreationError;
 pub use self::sys::RenderPassSys;
+pub use self::traits::FragmentOutputMismatchError;
 pub use self::traits::FramebufferAbstract;
 pub use self::traits::RenderPassAbstract;
 pub use self::traits::RenderPassCompatible;
@@ -126,6 +147,7 @@ mod compat_atch;
 mod desc;
 mod empty;
 mod framebuffer;
+mod graph;
 mod sys;
 mod traits;
 
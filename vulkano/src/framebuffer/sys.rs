@@ -19,16 +19,20 @@ use std::sync::Mutex;
 use device::Device;
 use device::DeviceOwned;
 use format::ClearValue;
+use format::FormatTy;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::LayoutAttachmentDescription;
+use framebuffer::LayoutDepthStencilResolveDescription;
+use framebuffer::LayoutMultiviewDescription;
 use framebuffer::LayoutPassDependencyDescription;
 use framebuffer::LayoutPassDescription;
-use framebuffer::LoadOp;
 use framebuffer::RenderPassAbstract;
 use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassDescClearValues;
+use framebuffer::ResolveMode;
+use framebuffer::SUBPASS_EXTERNAL;
+use image::ImageLayout;
 
-use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
@@ -50,6 +54,9 @@ pub struct RenderPass<D> {
 
     // Cache of the granularity of the render pass.
     granularity: Mutex<Option<[u32; 2]>>,
+
+    // False if the render pass was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<D> RenderPass<D>
@@ -57,6 +64,9 @@ impl<D> RenderPass<D>
 {
     /// Builds a new render pass.
     ///
+    /// The attachment references of each subpass are checked against the list of attachments
+    /// of the description, and an error is returned if they are invalid.
+    ///
     /// # Panic
     ///
     /// - Can panic if it detects some violations in the restrictions. Only unexpensive checks are
@@ -67,40 +77,6 @@ impl<D> RenderPass<D>
                -> Result<RenderPass<D>, RenderPassCreationError> {
         let vk = device.pointers();
 
-        // If the first use of an attachment in this render pass is as an input attachment, and
-        // the attachment is not also used as a color or depth/stencil attachment in the same
-        // subpass, then loadOp must not be VK_ATTACHMENT_LOAD_OP_CLEAR
-        debug_assert!(description.attachment_descs().enumerate().all(|(atch_num,
-          attachment)| {
-            if attachment.load != LoadOp::Clear {
-                return true;
-            }
-
-            for p in description.subpass_descs() {
-                if p.color_attachments
-                    .iter()
-                    .find(|&&(a, _)| a == atch_num)
-                    .is_some()
-                {
-                    return true;
-                }
-                if let Some((a, _)) = p.depth_stencil {
-                    if a == atch_num {
-                        return true;
-                    }
-                }
-                if p.input_attachments
-                    .iter()
-                    .find(|&&(a, _)| a == atch_num)
-                    .is_some()
-                {
-                    return false;
-                }
-            }
-
-            true
-        }));
-
         let attachments = description
             .attachment_descs()
             .map(|attachment| {
@@ -120,6 +96,164 @@ impl<D> RenderPass<D>
             })
             .collect::<SmallVec<[_; 16]>>();
 
+        // Checking the attachment references of each subpass.
+        for (pass_num, pass) in description.subpass_descs().enumerate() {
+            let depth_stencil_resolve = description.depth_stencil_resolve_desc(pass_num);
+
+            let used_attachments = pass.color_attachments
+                .iter()
+                .cloned()
+                .chain(pass.depth_stencil.clone().into_iter())
+                .chain(pass.input_attachments.iter().cloned())
+                .chain(pass.resolve_attachments.iter().cloned())
+                .chain(depth_stencil_resolve.as_ref().map(|r| r.attachment).into_iter())
+                .collect::<SmallVec<[_; 16]>>();
+
+            for &(atch_num, _) in used_attachments.iter() {
+                if atch_num >= attachments.len() {
+                    return Err(RenderPassCreationError::AttachmentOutOfRange {
+                                   subpass: pass_num,
+                                   attachment: atch_num,
+                               });
+                }
+            }
+
+            for &atch_num in pass.preserve_attachments.iter() {
+                if atch_num >= attachments.len() {
+                    return Err(RenderPassCreationError::AttachmentOutOfRange {
+                                   subpass: pass_num,
+                                   attachment: atch_num,
+                               });
+                }
+
+                if used_attachments.iter().any(|&(a, _)| a == atch_num) {
+                    return Err(RenderPassCreationError::PreserveAttachmentInUse {
+                                   subpass: pass_num,
+                                   attachment: atch_num,
+                               });
+                }
+            }
+
+            if !pass.resolve_attachments.is_empty() {
+                if pass.resolve_attachments.len() != pass.color_attachments.len() {
+                    return Err(RenderPassCreationError::ResolveAttachmentsCountMismatch {
+                                   subpass: pass_num,
+                               });
+                }
+
+                for (&(resolve, _), &(color, _)) in
+                    pass.resolve_attachments.iter().zip(pass.color_attachments.iter())
+                {
+                    if attachments[resolve].samples != 1 {
+                        return Err(RenderPassCreationError::ResolveAttachmentMultisampled {
+                                       subpass: pass_num,
+                                       attachment: resolve,
+                                   });
+                    }
+
+                    if attachments[color].samples == 1 {
+                        return Err(RenderPassCreationError::ResolveSourceNotMultisampled {
+                                       subpass: pass_num,
+                                       attachment: color,
+                                   });
+                    }
+
+                    if attachments[resolve].format != attachments[color].format {
+                        return Err(RenderPassCreationError::ResolveFormatMismatch {
+                                       subpass: pass_num,
+                                       attachment: resolve,
+                                   });
+                    }
+                }
+            }
+
+            if let Some(ref resolve) = depth_stencil_resolve {
+                if !device.loaded_extensions().khr_create_renderpass2 ||
+                    !device.loaded_extensions().khr_depth_stencil_resolve
+                {
+                    return Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled);
+                }
+
+                let (resolve_num, _) = resolve.attachment;
+                let depth_stencil = match pass.depth_stencil {
+                    Some((a, _)) => a,
+                    None => {
+                        return Err(RenderPassCreationError::DepthStencilResolveMissingSource {
+                                       subpass: pass_num,
+                                   });
+                    },
+                };
+
+                if attachments[resolve_num].samples != 1 {
+                    return Err(RenderPassCreationError::ResolveAttachmentMultisampled {
+                                   subpass: pass_num,
+                                   attachment: resolve_num,
+                               });
+                }
+
+                if attachments[depth_stencil].samples == 1 {
+                    return Err(RenderPassCreationError::ResolveSourceNotMultisampled {
+                                   subpass: pass_num,
+                                   attachment: depth_stencil,
+                               });
+                }
+
+                if attachments[resolve_num].format != attachments[depth_stencil].format {
+                    return Err(RenderPassCreationError::ResolveFormatMismatch {
+                                   subpass: pass_num,
+                                   attachment: resolve_num,
+                               });
+                }
+
+                if (resolve.depth_mode.is_none() && resolve.stencil_mode.is_none()) ||
+                    resolve.stencil_mode == Some(ResolveMode::Average)
+                {
+                    return Err(RenderPassCreationError::DepthStencilResolveInvalidModes {
+                                   subpass: pass_num,
+                               });
+                }
+            }
+
+            // If an attachment is used as both an input attachment and a color or depth/stencil
+            // attachment, then each use must use the same layout.
+            for &(atch_num, layout) in pass.input_attachments.iter() {
+                let mismatch = pass.color_attachments
+                    .iter()
+                    .chain(pass.depth_stencil.iter())
+                    .any(|&(a, l)| a == atch_num && l != layout);
+                if mismatch {
+                    return Err(RenderPassCreationError::InputAttachmentLayoutMismatch {
+                                   subpass: pass_num,
+                                   attachment: atch_num,
+                               });
+                }
+            }
+        }
+
+        // If the first use of an attachment in this render pass is as an input attachment, and
+        // the attachment is not also used as a color or depth/stencil attachment in the same
+        // subpass, then neither loadOp nor stencilLoadOp (for the aspects that the format has) must
+        // be VK_ATTACHMENT_LOAD_OP_CLEAR
+        for (atch_num, attachment) in description.attachment_descs().enumerate() {
+            if !attachment.is_cleared() {
+                continue;
+            }
+
+            for p in description.subpass_descs() {
+                if p.color_attachments.iter().any(|&(a, _)| a == atch_num) ||
+                    p.depth_stencil.map(|(a, _)| a == atch_num).unwrap_or(false)
+                {
+                    break;
+                }
+
+                if p.input_attachments.iter().any(|&(a, _)| a == atch_num) {
+                    return Err(RenderPassCreationError::InputAttachmentCleared {
+                                   attachment: atch_num,
+                               });
+                }
+            }
+        }
+
         // We need to pass pointers to vkAttachmentReference structs when creating the render pass.
         // Therefore we need to allocate them in advance.
         //
@@ -129,53 +263,9 @@ impl<D> RenderPass<D>
         let attachment_references = description
             .subpass_descs()
             .flat_map(|pass| {
-                // Performing some validation with debug asserts.
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.resolve_attachments.len() == pass.color_attachments.len());
-                debug_assert!(pass.resolve_attachments
-                                  .iter()
-                                  .all(|a| attachments[a.0].samples == 1));
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.color_attachments
-                                      .iter()
-                                      .all(|a| attachments[a.0].samples > 1));
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.resolve_attachments
-                                      .iter()
-                                      .zip(pass.color_attachments.iter())
-                                      .all(|(r, c)| {
-                                               attachments[r.0].format == attachments[c.0].format
-                                           }));
-                debug_assert!(pass.color_attachments
-                                  .iter()
-                                  .cloned()
-                                  .chain(pass.depth_stencil.clone().into_iter())
-                                  .chain(pass.input_attachments.iter().cloned())
-                                  .chain(pass.resolve_attachments.iter().cloned())
-                                  .all(|(a, _)| {
-                                           pass.preserve_attachments
-                                               .iter()
-                                               .find(|&&b| a == b)
-                                               .is_none()
-                                       }));
-                debug_assert!(
-                    pass.color_attachments
-                        .iter()
-                        .cloned()
-                        .chain(pass.depth_stencil.clone().into_iter())
-                        .all(|(atch, layout)| if let Some(r) =
-                            pass.input_attachments.iter().find(|r| r.0 == atch)
-                        {
-                            r.1 == layout
-                        } else {
-                            true
-                        })
-                );
-
                 let resolve = pass.resolve_attachments
                     .into_iter()
                     .map(|(offset, img_la)| {
-                             debug_assert!(offset < attachments.len());
                              vk::AttachmentReference {
                                  attachment: offset as u32,
                                  layout: img_la as u32,
@@ -183,7 +273,6 @@ impl<D> RenderPass<D>
                          });
 
                 let color = pass.color_attachments.into_iter().map(|(offset, img_la)| {
-                    debug_assert!(offset < attachments.len());
                     vk::AttachmentReference {
                         attachment: offset as u32,
                         layout: img_la as u32,
@@ -191,7 +280,6 @@ impl<D> RenderPass<D>
                 });
 
                 let input = pass.input_attachments.into_iter().map(|(offset, img_la)| {
-                    debug_assert!(offset < attachments.len());
                     vk::AttachmentReference {
                         attachment: offset as u32,
                         layout: img_la as u32,
@@ -296,12 +384,64 @@ impl<D> RenderPass<D>
             out
         };
 
+        for (num, dependency) in description.dependency_descs().enumerate() {
+            let valid = |subpass: usize| subpass < passes.len() || subpass == SUBPASS_EXTERNAL;
+            if !valid(dependency.source_subpass) || !valid(dependency.destination_subpass) ||
+                (dependency.source_subpass == SUBPASS_EXTERNAL &&
+                     dependency.destination_subpass == SUBPASS_EXTERNAL)
+            {
+                return Err(RenderPassCreationError::DependencySubpassOutOfRange {
+                               dependency: num,
+                           });
+            }
+
+            if dependency.source_subpass != SUBPASS_EXTERNAL &&
+                dependency.destination_subpass != SUBPASS_EXTERNAL &&
+                dependency.source_subpass > dependency.destination_subpass
+            {
+                return Err(RenderPassCreationError::DependencyBackwards { dependency: num });
+            }
+        }
+
+        let multiview = description.multiview_desc().filter(|m| m.is_enabled());
+        if let Some(ref multiview) = multiview {
+            if !device.loaded_extensions().khr_multiview {
+                return Err(RenderPassCreationError::MultiviewExtensionNotEnabled);
+            }
+
+            if multiview.view_masks.len() != passes.len() {
+                return Err(RenderPassCreationError::MultiviewViewMasksCountMismatch {
+                               expected: passes.len(),
+                               obtained: multiview.view_masks.len(),
+                           });
+            }
+
+            let num_zero = multiview.view_masks.iter().filter(|&&m| m == 0).count();
+            if num_zero != 0 && num_zero != multiview.view_masks.len() {
+                return Err(RenderPassCreationError::MultiviewViewMasksInconsistent);
+            }
+        }
+
+        let multiview_infos = multiview.as_ref().map(|multiview| {
+            vk::RenderPassMultiviewCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                subpassCount: multiview.view_masks.len() as u32,
+                pViewMasks: multiview.view_masks.as_ptr(),
+                dependencyCount: 0,
+                pViewOffsets: ptr::null(),
+                correlationMaskCount: multiview.correlation_masks.len() as u32,
+                pCorrelationMasks: if multiview.correlation_masks.is_empty() {
+                    ptr::null()
+                } else {
+                    multiview.correlation_masks.as_ptr()
+                },
+            }
+        });
+
         let dependencies = description
             .dependency_descs()
             .map(|dependency| {
-                debug_assert!(dependency.source_subpass < passes.len());
-                debug_assert!(dependency.destination_subpass < passes.len());
-
                 vk::SubpassDependency {
                     srcSubpass: dependency.source_subpass as u32,
                     dstSubpass: dependency.destination_subpass as u32,
@@ -318,37 +458,54 @@ impl<D> RenderPass<D>
             })
             .collect::<SmallVec<[_; 16]>>();
 
-        let render_pass = unsafe {
-            let infos = vk::RenderPassCreateInfo {
-                sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
-                attachmentCount: attachments.len() as u32,
-                pAttachments: if attachments.is_empty() {
-                    ptr::null()
-                } else {
-                    attachments.as_ptr()
-                },
-                subpassCount: passes.len() as u32,
-                pSubpasses: if passes.is_empty() {
-                    ptr::null()
-                } else {
-                    passes.as_ptr()
-                },
-                dependencyCount: dependencies.len() as u32,
-                pDependencies: if dependencies.is_empty() {
-                    ptr::null()
-                } else {
-                    dependencies.as_ptr()
-                },
-            };
-
-            let mut output = mem::uninitialized();
-            check_errors(vk.CreateRenderPass(device.internal_object(),
-                                             &infos,
-                                             ptr::null(),
-                                             &mut output))?;
-            output
+        // Depth/stencil resolve attachments can only be passed to `vkCreateRenderPass2KHR`.
+        let needs_create_render_pass2 = (0 .. passes.len())
+            .any(|n| description.depth_stencil_resolve_desc(n).is_some());
+
+        let render_pass = if needs_create_render_pass2 {
+            unsafe {
+                create_render_pass2(&device,
+                                    &description,
+                                    &attachments,
+                                    &dependencies,
+                                    multiview.as_ref())?
+            }
+        } else {
+            unsafe {
+                let infos = vk::RenderPassCreateInfo {
+                    sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
+                    pNext: multiview_infos
+                        .as_ref()
+                        .map(|i| i as *const _ as *const _)
+                        .unwrap_or(ptr::null()),
+                    flags: 0, // reserved
+                    attachmentCount: attachments.len() as u32,
+                    pAttachments: if attachments.is_empty() {
+                        ptr::null()
+                    } else {
+                        attachments.as_ptr()
+                    },
+                    subpassCount: passes.len() as u32,
+                    pSubpasses: if passes.is_empty() {
+                        ptr::null()
+                    } else {
+                        passes.as_ptr()
+                    },
+                    dependencyCount: dependencies.len() as u32,
+                    pDependencies: if dependencies.is_empty() {
+                        ptr::null()
+                    } else {
+                        dependencies.as_ptr()
+                    },
+                };
+
+                let mut output = mem::uninitialized();
+                check_errors(vk.CreateRenderPass(device.internal_object(),
+                                                 &infos,
+                                                 ptr::null(),
+                                                 &mut output))?;
+                output
+            }
         };
 
         Ok(RenderPass {
@@ -356,10 +513,227 @@ impl<D> RenderPass<D>
                render_pass: render_pass,
                desc: description,
                granularity: Mutex::new(None),
+               needs_destruction: true,
            })
     }
 }
 
+// Creates a render pass with `vkCreateRenderPass2KHR`, from the already-converted attachments and
+// dependencies and from the subpasses of the description.
+//
+// The caller must have checked that the `khr_create_renderpass2` extension is enabled, and that
+// the description is valid.
+unsafe fn create_render_pass2<D>(device: &Device, description: &D,
+                                 attachments: &[vk::AttachmentDescription],
+                                 dependencies: &[vk::SubpassDependency],
+                                 multiview: Option<&LayoutMultiviewDescription>)
+                                 -> Result<vk::RenderPass, RenderPassCreationError>
+    where D: RenderPassDesc
+{
+    let vk = device.pointers();
+
+    let attachments = attachments
+        .iter()
+        .map(|a| {
+            vk::AttachmentDescription2KHR {
+                sType: vk::STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_2_KHR,
+                pNext: ptr::null(),
+                flags: a.flags,
+                format: a.format,
+                samples: a.samples,
+                loadOp: a.loadOp,
+                storeOp: a.storeOp,
+                stencilLoadOp: a.stencilLoadOp,
+                stencilStoreOp: a.stencilStoreOp,
+                initialLayout: a.initialLayout,
+                finalLayout: a.finalLayout,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let reference = |(atch_num, layout): (usize, ImageLayout)| {
+        let aspect_mask = match description.attachment_desc(atch_num).map(|a| a.format.ty()) {
+            Some(FormatTy::Depth) => vk::IMAGE_ASPECT_DEPTH_BIT,
+            Some(FormatTy::Stencil) => vk::IMAGE_ASPECT_STENCIL_BIT,
+            Some(FormatTy::DepthStencil) => {
+                vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT
+            },
+            _ => vk::IMAGE_ASPECT_COLOR_BIT,
+        };
+
+        vk::AttachmentReference2KHR {
+            sType: vk::STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2_KHR,
+            pNext: ptr::null(),
+            attachment: atch_num as u32,
+            layout: layout as u32,
+            aspectMask: aspect_mask,
+        }
+    };
+
+    // Same layout as in `RenderPass::new`: for each pass, in order, all color attachment
+    // references, then all input attachment references, then all resolve attachment references,
+    // then the depth stencil attachment reference.
+    let attachment_references = description
+        .subpass_descs()
+        .flat_map(|pass| {
+            pass.color_attachments
+                .into_iter()
+                .chain(pass.input_attachments.into_iter())
+                .chain(pass.resolve_attachments.into_iter())
+                .chain(pass.depth_stencil.into_iter())
+        })
+        .map(&reference)
+        .collect::<Vec<_>>();
+
+    let preserve_attachments_references = description
+        .subpass_descs()
+        .flat_map(|pass| pass.preserve_attachments.into_iter().map(|offset| offset as u32))
+        .collect::<Vec<_>>();
+
+    let depth_stencil_resolve_references = (0 .. description.num_subpasses())
+        .map(|n| {
+                 description
+                     .depth_stencil_resolve_desc(n)
+                     .map(|r| (reference(r.attachment), r.depth_mode, r.stencil_mode))
+             })
+        .collect::<Vec<_>>();
+
+    let depth_stencil_resolves = depth_stencil_resolve_references
+        .iter()
+        .map(|r| {
+            r.as_ref().map(|&(ref reference, depth_mode, stencil_mode)| {
+                vk::SubpassDescriptionDepthStencilResolveKHR {
+                    sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_DEPTH_STENCIL_RESOLVE_KHR,
+                    pNext: ptr::null(),
+                    depthResolveMode: depth_mode
+                        .map(|m| m as u32)
+                        .unwrap_or(vk::RESOLVE_MODE_NONE_KHR),
+                    stencilResolveMode: stencil_mode
+                        .map(|m| m as u32)
+                        .unwrap_or(vk::RESOLVE_MODE_NONE_KHR),
+                    pDepthStencilResolveAttachment: reference,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut ref_index = 0usize;
+    let mut preserve_ref_index = 0usize;
+    let mut passes = Vec::with_capacity(description.num_subpasses());
+
+    for (pass_num, pass) in description.subpass_descs().enumerate() {
+        let color_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+        ref_index += pass.color_attachments.len();
+        let input_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+        ref_index += pass.input_attachments.len();
+        let resolve_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+        ref_index += pass.resolve_attachments.len();
+        let depth_stencil = if pass.depth_stencil.is_some() {
+            let a = attachment_references.as_ptr().offset(ref_index as isize);
+            ref_index += 1;
+            a
+        } else {
+            ptr::null()
+        };
+
+        let preserve_attachments = preserve_attachments_references
+            .as_ptr()
+            .offset(preserve_ref_index as isize);
+        preserve_ref_index += pass.preserve_attachments.len();
+
+        passes.push(vk::SubpassDescription2KHR {
+                        sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_2_KHR,
+                        pNext: depth_stencil_resolves[pass_num]
+                            .as_ref()
+                            .map(|i| i as *const _ as *const _)
+                            .unwrap_or(ptr::null()),
+                        flags: 0, // reserved
+                        pipelineBindPoint: vk::PIPELINE_BIND_POINT_GRAPHICS,
+                        viewMask: multiview.map(|m| m.view_masks[pass_num]).unwrap_or(0),
+                        inputAttachmentCount: pass.input_attachments.len() as u32,
+                        pInputAttachments: if pass.input_attachments.is_empty() {
+                            ptr::null()
+                        } else {
+                            input_attachments
+                        },
+                        colorAttachmentCount: pass.color_attachments.len() as u32,
+                        pColorAttachments: if pass.color_attachments.is_empty() {
+                            ptr::null()
+                        } else {
+                            color_attachments
+                        },
+                        pResolveAttachments: if pass.resolve_attachments.is_empty() {
+                            ptr::null()
+                        } else {
+                            resolve_attachments
+                        },
+                        pDepthStencilAttachment: depth_stencil,
+                        preserveAttachmentCount: pass.preserve_attachments.len() as u32,
+                        pPreserveAttachments: if pass.preserve_attachments.is_empty() {
+                            ptr::null()
+                        } else {
+                            preserve_attachments
+                        },
+                    });
+    }
+
+    debug_assert!(ref_index == attachment_references.len());
+    debug_assert!(preserve_ref_index == preserve_attachments_references.len());
+
+    let dependencies = dependencies
+        .iter()
+        .map(|d| {
+            vk::SubpassDependency2KHR {
+                sType: vk::STRUCTURE_TYPE_SUBPASS_DEPENDENCY_2_KHR,
+                pNext: ptr::null(),
+                srcSubpass: d.srcSubpass,
+                dstSubpass: d.dstSubpass,
+                srcStageMask: d.srcStageMask,
+                dstStageMask: d.dstStageMask,
+                srcAccessMask: d.srcAccessMask,
+                dstAccessMask: d.dstAccessMask,
+                dependencyFlags: d.dependencyFlags,
+                viewOffset: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let correlation_masks = multiview.map(|m| &m.correlation_masks[..]).unwrap_or(&[]);
+
+    let infos = vk::RenderPassCreateInfo2KHR {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO_2_KHR,
+        pNext: ptr::null(),
+        flags: 0, // reserved
+        attachmentCount: attachments.len() as u32,
+        pAttachments: if attachments.is_empty() {
+            ptr::null()
+        } else {
+            attachments.as_ptr()
+        },
+        subpassCount: passes.len() as u32,
+        pSubpasses: passes.as_ptr(),
+        dependencyCount: dependencies.len() as u32,
+        pDependencies: if dependencies.is_empty() {
+            ptr::null()
+        } else {
+            dependencies.as_ptr()
+        },
+        correlatedViewMaskCount: correlation_masks.len() as u32,
+        pCorrelatedViewMasks: if correlation_masks.is_empty() {
+            ptr::null()
+        } else {
+            correlation_masks.as_ptr()
+        },
+    };
+
+    let mut output = mem::uninitialized();
+    check_errors(vk.CreateRenderPass2KHR(device.internal_object(),
+                                         &infos,
+                                         ptr::null(),
+                                         &mut output))?;
+    Ok(output)
+}
+
 impl RenderPass<EmptySinglePassRenderPassDesc> {
     /// Builds a render pass with one subpass and no attachment.
     ///
@@ -373,6 +747,29 @@ impl RenderPass<EmptySinglePassRenderPassDesc> {
 }
 
 impl<D> RenderPass<D> {
+    /// Builds a `RenderPass` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the render pass is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid render pass of `device`, and `description` must describe it
+    ///   exactly.
+    /// - If `owned` is true, the render pass must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::RenderPass, description: D,
+                           owned: bool)
+                           -> RenderPass<D> {
+        RenderPass {
+            device: device,
+            render_pass: handle,
+            desc: description,
+            granularity: Mutex::new(None),
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns the granularity of this render pass.
     ///
     /// If the render area of a render pass in a command buffer is a multiple of this granularity,
@@ -440,6 +837,17 @@ unsafe impl<D> RenderPassDesc for RenderPass<D>
     fn dependency_desc(&self, num: usize) -> Option<LayoutPassDependencyDescription> {
         self.desc.dependency_desc(num)
     }
+
+    #[inline]
+    fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+        self.desc.multiview_desc()
+    }
+
+    #[inline]
+    fn depth_stencil_resolve_desc(&self, subpass: usize)
+                                  -> Option<LayoutDepthStencilResolveDescription> {
+        self.desc.depth_stencil_resolve_desc(subpass)
+    }
 }
 
 unsafe impl<C, D> RenderPassDescClearValues<C> for RenderPass<D>
@@ -482,6 +890,10 @@ impl<D> fmt::Debug for RenderPass<D>
 impl<D> Drop for RenderPass<D> {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyRenderPass(self.device.internal_object(), self.render_pass, ptr::null());
@@ -511,6 +923,102 @@ pub enum RenderPassCreationError {
     OomError(OomError),
     /// The maximum number of color attachments has been exceeded.
     ColorAttachmentsLimitExceeded,
+    /// A subpass references an attachment that doesn't exist.
+    AttachmentOutOfRange {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the attachment that was referenced.
+        attachment: usize,
+    },
+    /// An attachment is in the list of preserved attachments of a subpass, but is also used by
+    /// this subpass.
+    PreserveAttachmentInUse {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the attachment.
+        attachment: usize,
+    },
+    /// An attachment is used as both an input attachment and a color or depth-stencil attachment
+    /// of a subpass, but with different layouts.
+    InputAttachmentLayoutMismatch {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the attachment.
+        attachment: usize,
+    },
+    /// The first use of an attachment is as an input attachment, but its load operation or its
+    /// stencil load operation is `Clear`.
+    InputAttachmentCleared {
+        /// Index of the attachment.
+        attachment: usize,
+    },
+    /// The resolve attachments of a subpass are not empty, but their number is not the same as
+    /// the number of color attachments.
+    ResolveAttachmentsCountMismatch {
+        /// Index of the subpass.
+        subpass: usize,
+    },
+    /// A resolve attachment has more than one sample.
+    ResolveAttachmentMultisampled {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the resolve attachment.
+        attachment: usize,
+    },
+    /// A color attachment that is resolved has only one sample.
+    ResolveSourceNotMultisampled {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the color attachment.
+        attachment: usize,
+    },
+    /// A resolve attachment doesn't have the same format as the color attachment it's resolved
+    /// from.
+    ResolveFormatMismatch {
+        /// Index of the subpass.
+        subpass: usize,
+        /// Index of the resolve attachment.
+        attachment: usize,
+    },
+    /// A dependency references a subpass that doesn't exist, or both of its subpasses are
+    /// `SUBPASS_EXTERNAL`.
+    DependencySubpassOutOfRange {
+        /// Index of the dependency.
+        dependency: usize,
+    },
+    /// The source subpass of a dependency comes after its destination subpass.
+    DependencyBackwards {
+        /// Index of the dependency.
+        dependency: usize,
+    },
+    /// The render pass uses multiview, but the `khr_multiview` extension is not enabled on the
+    /// device.
+    MultiviewExtensionNotEnabled,
+    /// The number of view masks is not the same as the number of subpasses.
+    MultiviewViewMasksCountMismatch {
+        /// Number of subpasses.
+        expected: usize,
+        /// Number of view masks.
+        obtained: usize,
+    },
+    /// Some view masks are zero while others are not.
+    MultiviewViewMasksInconsistent,
+    /// The render pass uses a depth/stencil resolve attachment, but the `khr_create_renderpass2`
+    /// and `khr_depth_stencil_resolve` extensions are not enabled on the device.
+    DepthStencilResolveExtensionNotEnabled,
+    /// A subpass has a depth/stencil resolve attachment but no depth/stencil attachment.
+    DepthStencilResolveMissingSource {
+        /// Index of the subpass.
+        subpass: usize,
+    },
+    /// The depth/stencil resolve attachment of a subpass resolves neither the depth nor the
+    /// stencil aspect, or resolves the stencil aspect with `ResolveMode::Average`.
+    DepthStencilResolveInvalidModes {
+        /// Index of the subpass.
+        subpass: usize,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for RenderPassCreationError {
@@ -521,6 +1029,63 @@ impl error::Error for RenderPassCreationError {
             RenderPassCreationError::ColorAttachmentsLimitExceeded => {
                 "the maximum number of color attachments has been exceeded"
             },
+            RenderPassCreationError::AttachmentOutOfRange { .. } => {
+                "a subpass references an attachment that doesn't exist"
+            },
+            RenderPassCreationError::PreserveAttachmentInUse { .. } => {
+                "an attachment is in the list of preserved attachments of a subpass, but is also \
+                 used by this subpass"
+            },
+            RenderPassCreationError::InputAttachmentLayoutMismatch { .. } => {
+                "an attachment is used as both an input attachment and a color or depth-stencil \
+                 attachment of a subpass, but with different layouts"
+            },
+            RenderPassCreationError::InputAttachmentCleared { .. } => {
+                "the first use of an attachment is as an input attachment, but its load operation \
+                 is `Clear`"
+            },
+            RenderPassCreationError::ResolveAttachmentsCountMismatch { .. } => {
+                "the number of resolve attachments of a subpass is not the same as the number of \
+                 color attachments"
+            },
+            RenderPassCreationError::ResolveAttachmentMultisampled { .. } => {
+                "a resolve attachment has more than one sample"
+            },
+            RenderPassCreationError::ResolveSourceNotMultisampled { .. } => {
+                "a color attachment that is resolved has only one sample"
+            },
+            RenderPassCreationError::ResolveFormatMismatch { .. } => {
+                "a resolve attachment doesn't have the same format as the color attachment it's \
+                 resolved from"
+            },
+            RenderPassCreationError::DependencySubpassOutOfRange { .. } => {
+                "a dependency references a subpass that doesn't exist"
+            },
+            RenderPassCreationError::DependencyBackwards { .. } => {
+                "the source subpass of a dependency comes after its destination subpass"
+            },
+            RenderPassCreationError::MultiviewExtensionNotEnabled => {
+                "the render pass uses multiview, but the `khr_multiview` extension is not enabled"
+            },
+            RenderPassCreationError::MultiviewViewMasksCountMismatch { .. } => {
+                "the number of view masks is not the same as the number of subpasses"
+            },
+            RenderPassCreationError::MultiviewViewMasksInconsistent => {
+                "some view masks are zero while others are not"
+            },
+            RenderPassCreationError::DepthStencilResolveExtensionNotEnabled => {
+                "the render pass uses a depth/stencil resolve attachment, but the \
+                 `khr_create_renderpass2` and `khr_depth_stencil_resolve` extensions are not \
+                 enabled"
+            },
+            RenderPassCreationError::DepthStencilResolveMissingSource { .. } => {
+                "a subpass has a depth/stencil resolve attachment but no depth/stencil attachment"
+            },
+            RenderPassCreationError::DepthStencilResolveInvalidModes { .. } => {
+                "the resolve modes of a depth/stencil resolve attachment are invalid"
+            },
+            RenderPassCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -533,40 +1098,28 @@ impl error::Error for RenderPassCreationError {
     }
 }
 
-impl fmt::Display for RenderPassCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for RenderPassCreationError {
-    #[inline]
-    fn from(err: OomError) -> RenderPassCreationError {
-        RenderPassCreationError::OomError(err)
-    }
-}
-
-impl From<Error> for RenderPassCreationError {
-    #[inline]
-    fn from(err: Error) -> RenderPassCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => {
-                RenderPassCreationError::OomError(OomError::from(err))
-            },
-            err @ Error::OutOfDeviceMemory => {
-                RenderPassCreationError::OomError(OomError::from(err))
-            },
-            _ => panic!("unexpected error: {:?}", err),
-        }
-    }
-}
+vk_error_conversions!(RenderPassCreationError, OomError {});
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
+    use format::ClearValue;
     use format::Format;
+    use framebuffer::EmptySinglePassRenderPassDesc;
+    use framebuffer::LayoutAttachmentDescription;
+    use framebuffer::LayoutDepthStencilResolveDescription;
+    use framebuffer::LayoutMultiviewDescription;
+    use framebuffer::LayoutPassDependencyDescription;
+    use framebuffer::LayoutPassDescription;
+    use framebuffer::LoadOp;
     use framebuffer::RenderPass;
+    use framebuffer::RenderPassAbstract;
     use framebuffer::RenderPassCreationError;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::RenderPassDescClearValues;
+    use framebuffer::ResolveMode;
+    use framebuffer::StoreOp;
+    use image::ImageLayout;
 
     #[test]
     fn empty() {
@@ -574,6 +1127,19 @@ mod tests {
         let _ = RenderPass::empty_single_pass(device).unwrap();
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let rp = RenderPass::empty_single_pass(device.clone()).unwrap();
+
+        let borrowed = unsafe {
+            RenderPass::from_raw(device, rp.inner().internal_object(),
+                                 EmptySinglePassRenderPassDesc, false)
+        };
+        assert_eq!(borrowed.inner().internal_object(), rp.inner().internal_object());
+        assert_eq!(borrowed.num_subpasses(), 1);
+    }
+
     #[test]
     fn too_many_color_atch() {
         let (device, _) = gfx_dev_and_queue!();
@@ -609,6 +1175,236 @@ mod tests {
         }
     }
 
+    #[test]
+    fn input_attachment_cleared() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = ordered_passes_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            passes: [
+                {
+                    color: [],
+                    depth_stencil: {},
+                    input: [a]
+                }
+            ]
+        };
+
+        match rp {
+            Err(RenderPassCreationError::InputAttachmentCleared { attachment: 0 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn resolve_format_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 4, },
+                b: { load: DontCare, store: Store, format: Format::B8G8R8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                resolve: [b],
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::ResolveFormatMismatch { subpass: 0, attachment: 1 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiview_extension_not_enabled() {
+        struct MultiviewDesc;
+
+        unsafe impl RenderPassDesc for MultiviewDesc {
+            fn num_attachments(&self) -> usize {
+                0
+            }
+
+            fn attachment_desc(&self, _: usize) -> Option<LayoutAttachmentDescription> {
+                None
+            }
+
+            fn num_subpasses(&self) -> usize {
+                1
+            }
+
+            fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+                EmptySinglePassRenderPassDesc.subpass_desc(num)
+            }
+
+            fn num_dependencies(&self) -> usize {
+                0
+            }
+
+            fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+                None
+            }
+
+            fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+                Some(LayoutMultiviewDescription {
+                         view_masks: vec![0b11],
+                         correlation_masks: vec![],
+                     })
+            }
+        }
+
+        unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for MultiviewDesc {
+            fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                    -> Box<Iterator<Item = ClearValue>> {
+                Box::new(values.into_iter())
+            }
+        }
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        match RenderPass::new(device, MultiviewDesc) {
+            Err(RenderPassCreationError::MultiviewExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiview_zero_view_masks() {
+        struct ZeroMasksDesc;
+
+        unsafe impl RenderPassDesc for ZeroMasksDesc {
+            fn num_attachments(&self) -> usize {
+                0
+            }
+
+            fn attachment_desc(&self, _: usize) -> Option<LayoutAttachmentDescription> {
+                None
+            }
+
+            fn num_subpasses(&self) -> usize {
+                1
+            }
+
+            fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+                EmptySinglePassRenderPassDesc.subpass_desc(num)
+            }
+
+            fn num_dependencies(&self) -> usize {
+                0
+            }
+
+            fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+                None
+            }
+
+            fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+                Some(LayoutMultiviewDescription {
+                         view_masks: vec![0],
+                         correlation_masks: vec![],
+                     })
+            }
+        }
+
+        unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for ZeroMasksDesc {
+            fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                    -> Box<Iterator<Item = ClearValue>> {
+                Box::new(values.into_iter())
+            }
+        }
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Multiview is disabled, so the extension isn't needed.
+        let _ = RenderPass::new(device, ZeroMasksDesc).unwrap();
+    }
+
+    #[test]
+    fn depth_stencil_resolve_extension_not_enabled() {
+        struct DepthResolveDesc;
+
+        unsafe impl RenderPassDesc for DepthResolveDesc {
+            fn num_attachments(&self) -> usize {
+                2
+            }
+
+            fn attachment_desc(&self, num: usize) -> Option<LayoutAttachmentDescription> {
+                if num >= 2 {
+                    return None;
+                }
+
+                Some(LayoutAttachmentDescription {
+                         format: Format::D16Unorm,
+                         samples: if num == 0 { 4 } else { 1 },
+                         load: LoadOp::Clear,
+                         store: if num == 0 { StoreOp::DontCare } else { StoreOp::Store },
+                         stencil_load: LoadOp::DontCare,
+                         stencil_store: StoreOp::DontCare,
+                         initial_layout: ImageLayout::Undefined,
+                         final_layout: ImageLayout::DepthStencilAttachmentOptimal,
+                     })
+            }
+
+            fn num_subpasses(&self) -> usize {
+                1
+            }
+
+            fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+                if num != 0 {
+                    return None;
+                }
+
+                Some(LayoutPassDescription {
+                         color_attachments: vec![],
+                         depth_stencil: Some((0, ImageLayout::DepthStencilAttachmentOptimal)),
+                         input_attachments: vec![],
+                         resolve_attachments: vec![],
+                         preserve_attachments: vec![],
+                     })
+            }
+
+            fn num_dependencies(&self) -> usize {
+                0
+            }
+
+            fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+                None
+            }
+
+            fn depth_stencil_resolve_desc(&self, subpass: usize)
+                                          -> Option<LayoutDepthStencilResolveDescription> {
+                if subpass != 0 {
+                    return None;
+                }
+
+                Some(LayoutDepthStencilResolveDescription {
+                         attachment: (1, ImageLayout::DepthStencilAttachmentOptimal),
+                         depth_mode: Some(ResolveMode::SampleZero),
+                         stencil_mode: None,
+                     })
+            }
+        }
+
+        unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for DepthResolveDesc {
+            fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                    -> Box<Iterator<Item = ClearValue>> {
+                Box::new(values.into_iter())
+            }
+        }
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        match RenderPass::new(device, DepthResolveDesc) {
+            Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn non_zero_granularity() {
         let (device, _) = gfx_dev_and_queue!();
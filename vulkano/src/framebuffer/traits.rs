@@ -7,11 +7,17 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
+use std::fmt;
+
 use device::DeviceOwned;
 use format::ClearValue;
+use format::Format;
 use framebuffer::FramebufferSys;
+use framebuffer::LayoutMultiviewDescription;
 use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassSys;
+use image::ImageLayout;
 use image::ImageViewAccess;
 use pipeline::shader::ShaderInterfaceDef;
 
@@ -126,7 +132,8 @@ pub unsafe trait RenderPassDescClearValues<C> {
     /// attachments.
     ///
     /// The format of the clear value **must** match the format of the attachment. Attachments
-    /// that are not loaded with `LoadOp::Clear` must have an entry equal to `ClearValue::None`.
+    /// that are not cleared (see `LayoutAttachmentDescription::is_cleared`) must have an entry
+    /// equal to `ClearValue::None`.
     ///
     /// # Safety
     ///
@@ -161,27 +168,36 @@ pub unsafe trait RenderPassSubpassInterface<Other: ?Sized>: RenderPassDesc
 {
     /// Returns `true` if this subpass is compatible with the fragment output definition.
     /// Also returns `false` if the subpass is out of range.
-    // TODO: return proper error
-    fn is_compatible_with(&self, subpass: u32, other: &Other) -> bool;
+    #[inline]
+    fn is_compatible_with(&self, subpass: u32, other: &Other) -> bool {
+        self.check_compatible_with(subpass, other).is_ok()
+    }
+
+    /// Same as `is_compatible_with`, but returns an error that describes the incompatibility.
+    fn check_compatible_with(&self, subpass: u32, other: &Other)
+                             -> Result<(), FragmentOutputMismatchError>;
 }
 
 unsafe impl<A, B: ?Sized> RenderPassSubpassInterface<B> for A
     where A: RenderPassDesc,
           B: ShaderInterfaceDef
 {
-    fn is_compatible_with(&self, subpass: u32, other: &B) -> bool {
+    fn check_compatible_with(&self, subpass: u32, other: &B)
+                             -> Result<(), FragmentOutputMismatchError> {
         let pass_descr = match RenderPassDesc::subpass_descs(self)
             .skip(subpass as usize)
             .next() {
             Some(s) => s,
-            None => return false,
+            None => return Err(FragmentOutputMismatchError::SubpassOutOfRange),
         };
 
         for element in other.elements() {
             for location in element.location.clone() {
                 let attachment_id = match pass_descr.color_attachments.get(location as usize) {
                     Some(a) => a.0,
-                    None => return false,
+                    None => return Err(FragmentOutputMismatchError::MissingColorAttachment {
+                                           location: location,
+                                       }),
                 };
 
                 let attachment_desc = (&self)
@@ -190,14 +206,88 @@ unsafe impl<A, B: ?Sized> RenderPassSubpassInterface<B> for A
                     .next()
                     .unwrap();
 
-                // FIXME: compare formats depending on the number of components and data type
-                /*if attachment_desc.format != element.format {
-                    return false;
-                }*/
+                // The number of components doesn't need to match, but floating-point outputs
+                // can only be written to floating-point, normalized or sRGB attachments, and
+                // integer outputs to integer attachments of the same signedness.
+                if attachment_desc.format.ty() != element.format.ty() {
+                    return Err(FragmentOutputMismatchError::TypeMismatch {
+                                   location: location,
+                                   shader_format: element.format,
+                                   attachment_format: attachment_desc.format,
+                               });
+                }
             }
         }
 
-        true
+        Ok(())
+    }
+}
+
+/// Error that can happen when the output of a fragment shader isn't compatible with a subpass.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FragmentOutputMismatchError {
+    /// The subpass doesn't exist in the render pass.
+    SubpassOutOfRange,
+
+    /// The fragment shader writes to a location that doesn't correspond to any color attachment
+    /// of the subpass.
+    MissingColorAttachment {
+        /// Location of the output.
+        location: u32,
+    },
+
+    /// The numeric type of an output doesn't match the format of the color attachment.
+    TypeMismatch {
+        /// Location of the output.
+        location: u32,
+        /// Format of the output in the fragment shader.
+        shader_format: Format,
+        /// Format of the color attachment.
+        attachment_format: Format,
+    },
+}
+
+impl error::Error for FragmentOutputMismatchError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            FragmentOutputMismatchError::SubpassOutOfRange => {
+                "the subpass doesn't exist in the render pass"
+            },
+            FragmentOutputMismatchError::MissingColorAttachment { .. } => {
+                "the fragment shader writes to a location that doesn't correspond to any color \
+                 attachment of the subpass"
+            },
+            FragmentOutputMismatchError::TypeMismatch { .. } => {
+                "the numeric type of an output doesn't match the format of the color attachment"
+            },
+        }
+    }
+}
+
+impl fmt::Display for FragmentOutputMismatchError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            FragmentOutputMismatchError::SubpassOutOfRange => {
+                write!(fmt, "{}", error::Error::description(self))
+            },
+            FragmentOutputMismatchError::MissingColorAttachment { location } => {
+                write!(fmt, "{} (location {})", error::Error::description(self), location)
+            },
+            FragmentOutputMismatchError::TypeMismatch {
+                location,
+                shader_format,
+                attachment_format,
+            } => {
+                write!(fmt,
+                       "{} (location {}, shader output {:?}, attachment {:?})",
+                       error::Error::description(self),
+                       location,
+                       shader_format,
+                       attachment_format)
+            },
+        }
     }
 }
 
@@ -215,6 +305,10 @@ pub unsafe trait RenderPassCompatible<Other: ?Sized>: RenderPassDesc
 {
     /// Returns `true` if this layout is compatible with the other layout, as defined in the
     /// `Render Pass Compatibility` section of the Vulkan specs.
+    ///
+    /// Two render passes are compatible if their attachments have the same formats and number of
+    /// samples, if their subpasses reference compatible attachments, and if they are otherwise
+    /// identical except for the load and store operations and the image layouts.
     // TODO: return proper error
     fn is_compatible_with(&self, other: &Other) -> bool;
 }
@@ -237,9 +331,90 @@ unsafe impl<A: ?Sized, B: ?Sized> RenderPassCompatible<B> for A
             }
         }
 
-        return true;
+        if self.num_subpasses() != other.num_subpasses() {
+            return false;
+        }
+
+        // Two attachment references are compatible if they reference attachments with the same
+        // format and number of samples.
+        let refs_compatible = |mine: &[(usize, ImageLayout)], theirs: &[(usize, ImageLayout)]| {
+            mine.len() == theirs.len() &&
+                mine.iter().zip(theirs.iter()).all(|(&(a, _), &(b, _))| {
+                    match (self.attachment_desc(a), other.attachment_desc(b)) {
+                        (Some(a), Some(b)) => a.is_compatible_with(&b),
+                        _ => false,
+                    }
+                })
+        };
+
+        for subpass in 0 .. self.num_subpasses() {
+            let my_pass = self.subpass_desc(subpass).unwrap();
+            let other_pass = other.subpass_desc(subpass).unwrap();
+            let my_depth = my_pass.depth_stencil.into_iter().collect::<Vec<_>>();
+            let other_depth = other_pass.depth_stencil.into_iter().collect::<Vec<_>>();
+
+            if !refs_compatible(&my_pass.color_attachments, &other_pass.color_attachments) ||
+                !refs_compatible(&my_pass.input_attachments, &other_pass.input_attachments) ||
+                !refs_compatible(&my_pass.resolve_attachments, &other_pass.resolve_attachments) ||
+                !refs_compatible(&my_depth, &other_depth)
+            {
+                return false;
+            }
+
+            if my_pass.preserve_attachments != other_pass.preserve_attachments {
+                return false;
+            }
+        }
+
+        for subpass in 0 .. self.num_subpasses() {
+            let mine = self.depth_stencil_resolve_desc(subpass);
+            let theirs = other.depth_stencil_resolve_desc(subpass);
+
+            match (mine, theirs) {
+                (None, None) => (),
+                (Some(mine), Some(theirs)) => {
+                    if mine.depth_mode != theirs.depth_mode ||
+                        mine.stencil_mode != theirs.stencil_mode ||
+                        !refs_compatible(&[mine.attachment], &[theirs.attachment])
+                    {
+                        return false;
+                    }
+                },
+                _ => return false,
+            }
+        }
+
+        // The view masks of each subpass must be identical. A render pass whose view masks are
+        // all zero doesn't use multiview.
+        let view_masks = |desc: Option<LayoutMultiviewDescription>| {
+            desc.filter(|m| m.is_enabled()).map(|m| m.view_masks)
+        };
+        if view_masks(self.multiview_desc()) != view_masks(other.multiview_desc()) {
+            return false;
+        }
+
+        if self.num_dependencies() != other.num_dependencies() {
+            return false;
+        }
+
+        for dependency in 0 .. self.num_dependencies() {
+            let my_dep = self.dependency_desc(dependency).unwrap();
+            let other_dep = other.dependency_desc(dependency).unwrap();
+            if my_dep.source_subpass != other_dep.source_subpass ||
+                my_dep.destination_subpass != other_dep.destination_subpass ||
+                my_dep.source_stages != other_dep.source_stages ||
+                my_dep.destination_stages != other_dep.destination_stages ||
+                my_dep.source_access.into_vulkan_bits() !=
+                    other_dep.source_access.into_vulkan_bits() ||
+                my_dep.destination_access.into_vulkan_bits() !=
+                    other_dep.destination_access.into_vulkan_bits() ||
+                my_dep.by_region != other_dep.by_region
+            {
+                return false;
+            }
+        }
 
-        // FIXME: finish
+        true
     }
 }
 
@@ -347,3 +522,177 @@ impl<L> Into<(L, u32)> for Subpass<L> {
         (self.render_pass, self.subpass_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use format::ClearValue;
+    use format::Format;
+    use framebuffer::FragmentOutputMismatchError;
+    use framebuffer::EmptySinglePassRenderPassDesc;
+    use framebuffer::LayoutAttachmentDescription;
+    use framebuffer::LayoutMultiviewDescription;
+    use framebuffer::LayoutPassDependencyDescription;
+    use framebuffer::LayoutPassDescription;
+    use framebuffer::RenderPassCompatible;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::RenderPassDescClearValues;
+    use framebuffer::RenderPassSubpassInterface;
+    use pipeline::shader::RuntimeShaderInterfaceDef;
+    use pipeline::shader::ShaderInterfaceDefEntry;
+
+    #[test]
+    fn compatible_different_load_ops() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp1 = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: { load: Clear, store: Store, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let rp2 = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: { load: Load, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        assert!(RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+    }
+
+    struct MultiviewDesc(Option<Vec<u32>>);
+
+    unsafe impl RenderPassDesc for MultiviewDesc {
+        fn num_attachments(&self) -> usize {
+            0
+        }
+
+        fn attachment_desc(&self, _: usize) -> Option<LayoutAttachmentDescription> {
+            None
+        }
+
+        fn num_subpasses(&self) -> usize {
+            1
+        }
+
+        fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+            EmptySinglePassRenderPassDesc.subpass_desc(num)
+        }
+
+        fn num_dependencies(&self) -> usize {
+            0
+        }
+
+        fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+            None
+        }
+
+        fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+            self.0.clone().map(|view_masks| {
+                LayoutMultiviewDescription {
+                    view_masks: view_masks,
+                    correlation_masks: vec![],
+                }
+            })
+        }
+    }
+
+    unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for MultiviewDesc {
+        fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                -> Box<Iterator<Item = ClearValue>> {
+            Box::new(values.into_iter())
+        }
+    }
+
+    #[test]
+    fn compatible_view_masks() {
+        let rp1 = MultiviewDesc(Some(vec![0b11]));
+        let rp2 = MultiviewDesc(Some(vec![0b11]));
+        assert!(RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+
+        // All-zero view masks are the same as not using multiview.
+        let rp1 = MultiviewDesc(Some(vec![0]));
+        let rp2 = MultiviewDesc(None);
+        assert!(RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+    }
+
+    #[test]
+    fn incompatible_view_masks() {
+        let rp1 = MultiviewDesc(Some(vec![0b01]));
+        let rp2 = MultiviewDesc(Some(vec![0b11]));
+        assert!(!RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+
+        let rp1 = MultiviewDesc(Some(vec![0b11]));
+        let rp2 = MultiviewDesc(None);
+        assert!(!RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+    }
+
+    #[test]
+    fn incompatible_subpasses() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp1 = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: { load: Clear, store: Store, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let rp2 = ordered_passes_renderpass!(device.clone(),
+            attachments: {
+                color: { load: Clear, store: Store, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            passes: [
+                { color: [color], depth_stencil: {}, input: [] },
+                { color: [color], depth_stencil: {}, input: [] }
+            ]
+        ).unwrap();
+
+        assert!(!RenderPassCompatible::is_compatible_with(&rp1, &rp2));
+    }
+
+    #[test]
+    fn fragment_output_type_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: { load: Clear, store: Store, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let output = |format, location| {
+            RuntimeShaderInterfaceDef::new(Some(ShaderInterfaceDefEntry {
+                                                    location: location .. location + 1,
+                                                    format: format,
+                                                    name: None,
+                                                }))
+        };
+
+        rp.check_compatible_with(0, &output(Format::R32G32B32A32Sfloat, 0)).unwrap();
+
+        assert_eq!(rp.check_compatible_with(0, &output(Format::R32G32B32A32Uint, 0)),
+                   Err(FragmentOutputMismatchError::TypeMismatch {
+                           location: 0,
+                           shader_format: Format::R32G32B32A32Uint,
+                           attachment_format: Format::R8G8B8A8Unorm,
+                       }));
+
+        assert_eq!(rp.check_compatible_with(0, &output(Format::R32G32B32A32Sfloat, 1)),
+                   Err(FragmentOutputMismatchError::MissingColorAttachment { location: 1 }));
+    }
+}
@@ -41,6 +41,12 @@
 //! - An `ImmutableImage` stores data which never need be changed after the initial upload,
 //!   like a texture.
 //!
+//! # Rendering offscreen
+//!
+//! Rendering doesn't require a surface or a swapchain. You can create a framebuffer that contains
+//! an `AttachmentImage` with the `transfer_source` usage, draw on it, and then read its pixels
+//! back with `read_image`. This is useful for tests or for generating images on a server.
+//!
 //! # Low-level informations
 //!
 //! To be written.
@@ -48,12 +54,17 @@
 
 use std::cmp;
 
+use vk;
+
 pub use self::attachment::AttachmentImage;
 pub use self::immutable::ImmutableImage;
 pub use self::layout::ImageLayout;
+pub use self::readback::ReadImageError;
+pub use self::readback::read_image;
 pub use self::storage::StorageImage;
 pub use self::swapchain::SwapchainImage;
 pub use self::sys::ImageCreationError;
+pub use self::sys::ImageViewCreationError;
 pub use self::traits::ImageAccess;
 pub use self::traits::ImageInner;
 pub use self::traits::ImageViewAccess;
@@ -62,6 +73,7 @@ pub use self::usage::ImageUsage;
 pub mod attachment; // TODO: make private
 pub mod immutable; // TODO: make private
 mod layout;
+mod readback;
 mod storage;
 pub mod swapchain; // TODO: make private
 pub mod sys;
@@ -135,6 +147,33 @@ pub enum ComponentSwizzle {
     Alpha,
 }
 
+impl Swizzle {
+    #[inline]
+    pub(crate) fn into_vulkan_mapping(self) -> vk::ComponentMapping {
+        vk::ComponentMapping {
+            r: self.r.into_vulkan(),
+            g: self.g.into_vulkan(),
+            b: self.b.into_vulkan(),
+            a: self.a.into_vulkan(),
+        }
+    }
+}
+
+impl ComponentSwizzle {
+    #[inline]
+    fn into_vulkan(self) -> vk::ComponentSwizzle {
+        match self {
+            ComponentSwizzle::Identity => vk::COMPONENT_SWIZZLE_IDENTITY,
+            ComponentSwizzle::Zero => vk::COMPONENT_SWIZZLE_ZERO,
+            ComponentSwizzle::One => vk::COMPONENT_SWIZZLE_ONE,
+            ComponentSwizzle::Red => vk::COMPONENT_SWIZZLE_R,
+            ComponentSwizzle::Green => vk::COMPONENT_SWIZZLE_G,
+            ComponentSwizzle::Blue => vk::COMPONENT_SWIZZLE_B,
+            ComponentSwizzle::Alpha => vk::COMPONENT_SWIZZLE_A,
+        }
+    }
+}
+
 impl Default for ComponentSwizzle {
     #[inline]
     fn default() -> ComponentSwizzle {
@@ -0,0 +1,236 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use buffer::BufferUsage;
+use buffer::CpuAccessibleBuffer;
+use command_buffer::AutoCommandBuffer;
+use command_buffer::CopyBufferImageError;
+use command_buffer::OneTimeSubmitError;
+use device::Queue;
+use format::AcceptsPixels;
+use format::Format;
+use format::IncompatiblePixelsType;
+use image::ImageAccess;
+use memory::DeviceMemoryAllocError;
+use sync::FlushError;
+
+/// Copies the content of an image to a CPU-accessible buffer, waits for the copy to be finished,
+/// and returns the pixels.
+///
+/// Only the first mipmap level and the first array layer are read. The pixels are returned row
+/// by row, with `Px` being the type of one pixel (or of one component, see `AcceptsPixels`).
+///
+/// This is a blocking operation that is mostly useful for rendering offscreen, for example in
+/// tests or to generate images on a server. For this, draw into an `AttachmentImage` that was
+/// created with the `transfer_source` usage, then call this function once the drawing commands
+/// have been submitted to the same queue.
+///
+/// # Example
+///
+/// ```no_run
+/// use vulkano::format::Format;
+/// use vulkano::image::AttachmentImage;
+/// use vulkano::image::ImageUsage;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+///
+/// let usage = ImageUsage {
+///     transfer_source: true,
+///     .. ImageUsage::none()
+/// };
+///
+/// let image = AttachmentImage::with_usage(device.clone(), [1024, 1024],
+///                                         Format::R8G8B8A8Unorm, usage).unwrap();
+///
+/// // ... draw on the image ...
+///
+/// let pixels: Vec<[u8; 4]> = vulkano::image::read_image(queue.clone(), image.clone()).unwrap();
+/// assert_eq!(pixels.len(), 1024 * 1024);
+/// ```
+pub fn read_image<I, Px>(queue: Arc<Queue>, image: I) -> Result<Vec<Px>, ReadImageError>
+    where I: ImageAccess + Send + Sync + 'static,
+          Px: Clone + Send + Sync + 'static,
+          Format: AcceptsPixels<Px>
+{
+    image.format().ensure_accepts()?;
+
+    let dimensions = image.dimensions().width_height_depth();
+    let len = (dimensions[0] * dimensions[1] * dimensions[2] * image.format().rate()) as usize;
+
+    // Safe because the content of the buffer is entirely written by the copy before we read it.
+    let buffer = unsafe {
+        CpuAccessibleBuffer::<[Px]>::uninitialized_array(queue.device().clone(),
+                                                         len,
+                                                         BufferUsage::transfer_destination())?
+    };
+
+    let future = {
+        let buffer = buffer.clone();
+        AutoCommandBuffer::one_time_submit(queue, move |builder| {
+            builder.copy_image_to_buffer(image, buffer)
+        })?
+    };
+    future.wait(None)?;
+
+    // The only other user of the buffer was the command buffer, which has finished executing.
+    let content = buffer.read().expect("the buffer is still locked after the copy has finished");
+    Ok(content.to_vec())
+}
+
+/// Error that can happen when reading the content of an image with `read_image`.
+#[derive(Debug, Clone)]
+pub enum ReadImageError {
+    /// Failed to allocate the buffer that receives the pixels.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+    /// The type of pixels isn't compatible with the format of the image.
+    WrongPixelType(IncompatiblePixelsType),
+    /// Failed to record or submit the copy.
+    SubmitError(OneTimeSubmitError<CopyBufferImageError>),
+    /// Error while waiting for the copy to finish.
+    FlushError(FlushError),
+}
+
+impl error::Error for ReadImageError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ReadImageError::DeviceMemoryAllocError(_) => {
+                "failed to allocate the buffer that receives the pixels"
+            },
+            ReadImageError::WrongPixelType(_) => {
+                "the type of pixels isn't compatible with the format of the image"
+            },
+            ReadImageError::SubmitError(_) => {
+                "failed to record or submit the copy"
+            },
+            ReadImageError::FlushError(_) => {
+                "error while waiting for the copy to finish"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ReadImageError::DeviceMemoryAllocError(ref err) => Some(err),
+            ReadImageError::WrongPixelType(ref err) => Some(err),
+            ReadImageError::SubmitError(ref err) => Some(err),
+            ReadImageError::FlushError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for ReadImageError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<DeviceMemoryAllocError> for ReadImageError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> ReadImageError {
+        ReadImageError::DeviceMemoryAllocError(err)
+    }
+}
+
+impl From<IncompatiblePixelsType> for ReadImageError {
+    #[inline]
+    fn from(err: IncompatiblePixelsType) -> ReadImageError {
+        ReadImageError::WrongPixelType(err)
+    }
+}
+
+impl From<OneTimeSubmitError<CopyBufferImageError>> for ReadImageError {
+    #[inline]
+    fn from(err: OneTimeSubmitError<CopyBufferImageError>) -> ReadImageError {
+        ReadImageError::SubmitError(err)
+    }
+}
+
+impl From<FlushError> for ReadImageError {
+    #[inline]
+    fn from(err: FlushError) -> ReadImageError {
+        ReadImageError::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use format::Format;
+    use framebuffer::Framebuffer;
+    use image::AttachmentImage;
+    use image::ImageUsage;
+    use image::read_image;
+    use sync::GpuFuture;
+
+    #[test]
+    fn read_back_cleared_image() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            color_attachment: true,
+            transfer_source: true,
+            ..ImageUsage::none()
+        };
+        let image = AttachmentImage::with_usage(device.clone(), [4, 4],
+                                                Format::R8G8B8A8Unorm, usage).unwrap();
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let framebuffer = Arc::new(Framebuffer::start(rp)
+                                       .add(image.clone())
+                                       .unwrap()
+                                       .build()
+                                       .unwrap());
+
+        let command_buffer =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap()
+                .begin_render_pass(framebuffer, false, vec![[0.0, 0.0, 1.0, 1.0].into()])
+                .unwrap()
+                .end_render_pass()
+                .unwrap()
+                .build()
+                .unwrap();
+
+        command_buffer
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let pixels: Vec<[u8; 4]> = read_image(queue, image).unwrap();
+        assert_eq!(pixels.len(), 16);
+        assert!(pixels.iter().all(|&p| p == [0, 0, 255, 255]));
+    }
+}
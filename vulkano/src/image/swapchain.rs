@@ -16,6 +16,7 @@ use format::FormatDesc;
 use image::Dimensions;
 use image::ImageInner;
 use image::ImageLayout;
+use image::ImageViewCreationError;
 use image::ViewType;
 use image::sys::UnsafeImageView;
 use image::traits::ImageAccess;
@@ -25,8 +26,6 @@ use image::traits::ImageViewAccess;
 use swapchain::Swapchain;
 use sync::AccessError;
 
-use OomError;
-
 /// An image that is part of a swapchain.
 ///
 /// Creating a `SwapchainImage` is automatically done when creating a swapchain.
@@ -52,7 +51,7 @@ impl<W> SwapchainImage<W> {
     ///
     /// This is an internal method that you shouldn't call.
     pub unsafe fn from_raw(swapchain: Arc<Swapchain<W>>, id: usize)
-                           -> Result<Arc<SwapchainImage<W>>, OomError> {
+                           -> Result<Arc<SwapchainImage<W>>, ImageViewCreationError> {
         let image = swapchain.raw_image(id).unwrap();
         let view = UnsafeImageView::raw(&image.image, ViewType::Dim2d, 0 .. 1, 0 .. 1)?;
 
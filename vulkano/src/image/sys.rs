@@ -22,7 +22,9 @@ use std::ptr;
 use std::sync::Arc;
 
 use device::Device;
+use device::DeviceOwned;
 use format::Format;
+use format::FormatFeatures;
 use format::FormatTy;
 use image::ImageDimensions;
 use image::ImageUsage;
@@ -31,6 +33,7 @@ use image::ViewType;
 use memory::DeviceMemory;
 use memory::DeviceMemoryAllocError;
 use memory::MemoryRequirements;
+use sampler::SamplerYcbcrConversion;
 use sync::Sharing;
 
 use Error;
@@ -63,10 +66,13 @@ pub struct UnsafeImage {
     mipmaps: u32,
 
     // Features that are supported for this particular format.
-    format_features: vk::FormatFeatureFlagBits,
+    format_features: FormatFeatures,
 
     // `vkDestroyImage` is called only if `needs_destruction` is true.
     needs_destruction: bool,
+
+    // True if the image was created with `Sharing::Concurrent`.
+    concurrent_sharing: bool,
 }
 
 impl UnsafeImage {
@@ -116,51 +122,40 @@ impl UnsafeImage {
 
         // Checking if image usage conforms to what is supported.
         let format_features = {
-            let physical_device = device.physical_device().internal_object();
-
-            let mut output = mem::uninitialized();
-            vk_i.GetPhysicalDeviceFormatProperties(physical_device, format as u32, &mut output);
+            let properties = device.physical_device().format_properties(format);
 
             let features = if linear_tiling {
-                output.linearTilingFeatures
+                properties.linear_tiling_features
             } else {
-                output.optimalTilingFeatures
+                properties.optimal_tiling_features
             };
 
-            if features == 0 {
+            if features == FormatFeatures::none() {
                 return Err(ImageCreationError::FormatNotSupported);
             }
 
-            if usage.sampled && (features & vk::FORMAT_FEATURE_SAMPLED_IMAGE_BIT == 0) {
+            if usage.sampled && !features.sampled_image {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
-            if usage.storage && (features & vk::FORMAT_FEATURE_STORAGE_IMAGE_BIT == 0) {
+            if usage.storage && !features.storage_image {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
-            if usage.color_attachment && (features & vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BIT == 0) {
+            if usage.color_attachment && !features.color_attachment {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
-            if usage.depth_stencil_attachment &&
-                (features & vk::FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT == 0)
-            {
+            if usage.depth_stencil_attachment && !features.depth_stencil_attachment {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
             if usage.input_attachment &&
-                (features &
-                     (vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BIT |
-                          vk::FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT) == 0)
+                !(features.color_attachment || features.depth_stencil_attachment)
             {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
             if device.loaded_extensions().khr_maintenance1 {
-                if usage.transfer_source &&
-                    (features & vk::FORMAT_FEATURE_TRANSFER_SRC_BIT_KHR == 0)
-                {
+                if usage.transfer_source && !features.transfer_src {
                     return Err(ImageCreationError::UnsupportedUsage);
                 }
-                if usage.transfer_destination &&
-                    (features & vk::FORMAT_FEATURE_TRANSFER_DST_BIT_KHR == 0)
-                {
+                if usage.transfer_destination && !features.transfer_dst {
                     return Err(ImageCreationError::UnsupportedUsage);
                 }
             }
@@ -497,14 +492,14 @@ impl UnsafeImage {
             output
         };
 
-        let mem_reqs = if device.loaded_extensions().khr_get_memory_requirements2 {
+        let mem_reqs = if device.supports_memory_requirements2() {
             let infos = vk::ImageMemoryRequirementsInfo2KHR {
                 sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR,
                 pNext: ptr::null_mut(),
                 image: image,
             };
 
-            let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+            let mut output2 = if device.supports_dedicated_allocation() {
                 Some(vk::MemoryDedicatedRequirementsKHR {
                          sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                          pNext: ptr::null(),
@@ -524,7 +519,7 @@ impl UnsafeImage {
                 memoryRequirements: mem::uninitialized(),
             };
 
-            vk.GetImageMemoryRequirements2KHR(device.internal_object(), &infos, &mut output);
+            device.get_image_memory_requirements2(&infos, &mut output);
             debug_assert!(output.memoryRequirements.memoryTypeBits != 0);
 
             let mut out = MemoryRequirements::from_vulkan_reqs(output.memoryRequirements);
@@ -551,24 +546,33 @@ impl UnsafeImage {
             mipmaps: mipmaps,
             format_features: format_features,
             needs_destruction: true,
+            concurrent_sharing: sh_mode == vk::SHARING_MODE_CONCURRENT,
         };
 
         Ok((image, mem_reqs))
     }
 
-    /// Creates an image from a raw handle. The image won't be destroyed.
+    /// Creates an image from a raw handle.
+    ///
+    /// This function is for example used at the swapchain's initialization, and can be used to
+    /// wrap images created outside of vulkano. If `owned` is true, the image is destroyed when
+    /// the returned object is dropped. Otherwise, the caller remains responsible for destroying
+    /// it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid image of `device`, created with the given parameters.
+    /// - `concurrent_sharing` must be true if and only if the image was created with
+    ///   `VK_SHARING_MODE_CONCURRENT`.
+    /// - If `owned` is true, the image must not be destroyed by anything else.
     ///
-    /// This function is for example used at the swapchain's initialization.
     pub unsafe fn from_raw(device: Arc<Device>, handle: u64, usage: u32, format: Format,
-                           dimensions: ImageDimensions, samples: u32, mipmaps: u32)
+                           dimensions: ImageDimensions, samples: u32, mipmaps: u32,
+                           concurrent_sharing: bool, owned: bool)
                            -> UnsafeImage {
-        let vk_i = device.instance().pointers();
-        let physical_device = device.physical_device().internal_object();
+        let properties = device.physical_device().format_properties(format);
 
-        let mut output = mem::uninitialized();
-        vk_i.GetPhysicalDeviceFormatProperties(physical_device, format as u32, &mut output);
-
-        // TODO: check that usage is correct in regard to `output`?
+        // TODO: check that usage is correct in regard to `properties`?
 
         UnsafeImage {
             device: device.clone(),
@@ -578,8 +582,9 @@ impl UnsafeImage {
             dimensions: dimensions,
             samples: samples,
             mipmaps: mipmaps,
-            format_features: output.optimalTilingFeatures,
-            needs_destruction: false, // TODO: pass as parameter
+            format_features: properties.optimal_tiling_features,
+            needs_destruction: owned,
+            concurrent_sharing: concurrent_sharing,
         }
     }
 
@@ -724,19 +729,33 @@ impl UnsafeImage {
     /// Returns true if the image can be used as a source for blits.
     #[inline]
     pub fn supports_blit_source(&self) -> bool {
-        (self.format_features & vk::FORMAT_FEATURE_BLIT_SRC_BIT) != 0
+        self.format_features.blit_src
     }
 
     /// Returns true if the image can be used as a destination for blits.
     #[inline]
     pub fn supports_blit_destination(&self) -> bool {
-        (self.format_features & vk::FORMAT_FEATURE_BLIT_DST_BIT) != 0
+        self.format_features.blit_dst
     }
 
     /// Returns true if the image can be sampled with a linear filtering.
     #[inline]
     pub fn supports_linear_filtering(&self) -> bool {
-        (self.format_features & vk::FORMAT_FEATURE_SAMPLED_IMAGE_FILTER_LINEAR_BIT) != 0
+        self.format_features.sampled_image_filter_linear
+    }
+
+    /// Returns the features that the format of the image supports, given its tiling.
+    #[inline]
+    pub fn format_features(&self) -> FormatFeatures {
+        self.format_features
+    }
+
+    /// Returns true if the image was created with `Sharing::Concurrent`.
+    ///
+    /// Images with a concurrent sharing mode don't need any queue ownership transfer.
+    #[inline]
+    pub fn concurrent_sharing(&self) -> bool {
+        self.concurrent_sharing
     }
 
     #[inline]
@@ -832,6 +851,8 @@ pub enum ImageCreationError {
     UnsupportedUsage,
     /// The `shader_storage_image_multisample` feature must be enabled to create such an image.
     ShaderStorageImageMultisampleFeatureNotEnabled,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for ImageCreationError {
@@ -854,6 +875,8 @@ impl error::Error for ImageCreationError {
                 "the `shader_storage_image_multisample` feature must be enabled to create such \
                  an image"
             },
+            ImageCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -866,19 +889,7 @@ impl error::Error for ImageCreationError {
     }
 }
 
-impl fmt::Display for ImageCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for ImageCreationError {
-    #[inline]
-    fn from(err: OomError) -> ImageCreationError {
-        ImageCreationError::AllocError(DeviceMemoryAllocError::OomError(err))
-    }
-}
+vk_error_conversions!(ImageCreationError, AllocError {});
 
 impl From<DeviceMemoryAllocError> for ImageCreationError {
     #[inline]
@@ -887,13 +898,14 @@ impl From<DeviceMemoryAllocError> for ImageCreationError {
     }
 }
 
-impl From<Error> for ImageCreationError {
+impl From<ImageViewCreationError> for ImageCreationError {
     #[inline]
-    fn from(err: Error) -> ImageCreationError {
+    fn from(err: ImageViewCreationError) -> ImageCreationError {
         match err {
-            err @ Error::OutOfHostMemory => ImageCreationError::AllocError(err.into()),
-            err @ Error::OutOfDeviceMemory => ImageCreationError::AllocError(err.into()),
-            _ => panic!("unexpected error: {:?}", err),
+            ImageViewCreationError::OomError(err) => err.into(),
+            ImageViewCreationError::UnexpectedResult(code) => {
+                ImageCreationError::UnexpectedResult(code)
+            },
         }
     }
 }
@@ -928,13 +940,48 @@ pub struct UnsafeImageView {
     usage: vk::ImageUsageFlagBits,
     identity_swizzle: bool,
     format: Format,
+    mipmap_levels: u32,
+    ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>,
+    // False if the view was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl UnsafeImageView {
     /// See the docs of new().
+    #[inline]
     pub unsafe fn raw(image: &UnsafeImage, ty: ViewType, mipmap_levels: Range<u32>,
                       array_layers: Range<u32>)
-                      -> Result<UnsafeImageView, OomError> {
+                      -> Result<UnsafeImageView, ImageViewCreationError> {
+        UnsafeImageView::raw_impl(image, ty, mipmap_levels, array_layers, None)
+    }
+
+    /// Creates a new view from an image whose values are converted from YCbCr to RGB with
+    /// `conversion` when sampled.
+    ///
+    /// The view must be sampled with a sampler that uses the same conversion.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `conversion` wasn't created with the same device as the image.
+    /// - Panics if the format of `conversion` isn't the format of the image.
+    /// - Panics for the same reasons as `new`, except for running out of memory.
+    ///
+    #[inline]
+    pub unsafe fn with_ycbcr_conversion(image: &UnsafeImage, ty: ViewType,
+                                        mipmap_levels: Range<u32>, array_layers: Range<u32>,
+                                        conversion: Arc<SamplerYcbcrConversion>)
+                                        -> Result<UnsafeImageView, ImageViewCreationError> {
+        assert_eq!(conversion.device().internal_object(),
+                   image.device.internal_object());
+        assert_eq!(conversion.format(), image.format);
+
+        UnsafeImageView::raw_impl(image, ty, mipmap_levels, array_layers, Some(conversion))
+    }
+
+    unsafe fn raw_impl(image: &UnsafeImage, ty: ViewType, mipmap_levels: Range<u32>,
+                       array_layers: Range<u32>,
+                       ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>)
+                       -> Result<UnsafeImageView, ImageViewCreationError> {
         let vk = image.device.pointers();
 
         assert!(mipmap_levels.end > mipmap_levels.start);
@@ -972,10 +1019,21 @@ impl UnsafeImageView {
             _ => panic!(),
         };
 
+        let ycbcr_conversion_infos = ycbcr_conversion.as_ref().map(|conversion| {
+            vk::SamplerYcbcrConversionInfo {
+                sType: vk::STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_INFO,
+                pNext: ptr::null(),
+                conversion: conversion.internal_object(),
+            }
+        });
+
         let view = {
             let infos = vk::ImageViewCreateInfo {
                 sType: vk::STRUCTURE_TYPE_IMAGE_VIEW_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: ycbcr_conversion_infos
+                    .as_ref()
+                    .map(|i| i as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: 0, // reserved
                 image: image.internal_object(),
                 viewType: view_type,
@@ -1009,6 +1067,9 @@ impl UnsafeImageView {
                usage: image.usage,
                identity_swizzle: true, // FIXME:
                format: image.format,
+               mipmap_levels: mipmap_levels.end - mipmap_levels.start,
+               ycbcr_conversion: ycbcr_conversion,
+               needs_destruction: true,
            })
     }
 
@@ -1034,11 +1095,49 @@ impl UnsafeImageView {
         UnsafeImageView::raw(image, ty, mipmap_levels, array_layers).unwrap()
     }
 
+    /// Builds an `UnsafeImageView` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the view is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid view of `image`, with identity swizzling, the same format as
+    ///   `image`, no YCbCr conversion, and `mipmap_levels` mipmap levels.
+    /// - If `owned` is true, the view must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(image: &UnsafeImage, handle: vk::ImageView, mipmap_levels: u32,
+                           owned: bool)
+                           -> UnsafeImageView {
+        UnsafeImageView {
+            view: handle,
+            device: image.device.clone(),
+            usage: image.usage,
+            identity_swizzle: true,
+            format: image.format,
+            mipmap_levels: mipmap_levels,
+            ycbcr_conversion: None,
+            needs_destruction: owned,
+        }
+    }
+
     #[inline]
     pub fn format(&self) -> Format {
         self.format
     }
 
+    /// Returns the number of mipmap levels that are visible through this view.
+    #[inline]
+    pub fn mipmap_levels(&self) -> u32 {
+        self.mipmap_levels
+    }
+
+    /// Returns the YCbCr conversion the view was created with, if any.
+    #[inline]
+    pub fn ycbcr_conversion(&self) -> Option<&Arc<SamplerYcbcrConversion>> {
+        self.ycbcr_conversion.as_ref()
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::IMAGE_USAGE_TRANSFER_SRC_BIT) != 0
@@ -1101,6 +1200,10 @@ impl fmt::Debug for UnsafeImageView {
 impl Drop for UnsafeImageView {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyImageView(self.device.internal_object(), self.view, ptr::null());
@@ -1108,6 +1211,37 @@ impl Drop for UnsafeImageView {
     }
 }
 
+/// Error that can happen when creating an image view.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImageViewCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for ImageViewCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ImageViewCreationError::OomError(_) => "not enough memory available",
+            ImageViewCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ImageViewCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(ImageViewCreationError, OomError {});
+
 #[cfg(test)]
 mod tests {
     use std::iter::Empty;
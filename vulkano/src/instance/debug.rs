@@ -36,10 +36,41 @@
 //! be callable. If you don't store the return value of `DebugCallback`'s constructor in a
 //! variable, it will be immediately destroyed and your callback will not work.
 //!
+//! # Debug utils messenger
+//!
+//! The `EXT_debug_utils` extension is the successor of `EXT_debug_report`. If it is enabled, a
+//! `DebugUtilsMessenger` can be registered instead of a `DebugCallback`. Messages are then
+//! filtered by severity and by type.
+//!
+//! ```no_run
+//! use vulkano::instance::Instance;
+//! use vulkano::instance::InstanceExtensions;
+//! use vulkano::instance::debug::DebugUtilsMessenger;
+//! use vulkano::instance::debug::KHRONOS_VALIDATION_LAYER;
+//! use vulkano::instance::debug::MessageSeverity;
+//! use vulkano::instance::debug::MessageType;
+//!
+//! let extensions = InstanceExtensions {
+//!     ext_debug_utils: true,
+//!     .. InstanceExtensions::none()
+//! };
+//!
+//! let layers = [KHRONOS_VALIDATION_LAYER];
+//! let instance = Instance::new(None, &extensions, &layers).unwrap();
+//!
+//! let _messenger = DebugUtilsMessenger::new(&instance, MessageSeverity::errors_and_warnings(),
+//!                                           MessageType::all(), |msg| {
+//!     println!("{:?}: {}", msg.severity, msg.description);
+//! }).unwrap();
+//! ```
+//!
+//! If the `log` feature of vulkano is enabled, `DebugCallback::with_log` and
+//! `DebugUtilsMessenger::with_log` forward the messages to the `log` crate instead of calling a
+//! closure.
+//!
 
 use std::error;
 use std::ffi::CStr;
-use std::fmt;
 use std::mem;
 use std::os::raw::{c_char, c_void};
 use std::panic;
@@ -47,8 +78,10 @@ use std::ptr;
 use std::sync::Arc;
 
 use instance::Instance;
+use instance::LayersListError;
+use instance::layers_list;
 
-use Error;
+use OomError;
 use VulkanObject;
 use check_errors;
 use vk;
@@ -61,7 +94,11 @@ use vk;
 pub struct DebugCallback {
     instance: Arc<Instance>,
     debug_report_callback: vk::DebugReportCallbackEXT,
-    user_callback: Box<Box<Fn(&Message)>>,
+    // `None` if the callback was built with `from_raw`, in which case the user data of the
+    // callback is managed by the caller.
+    user_callback: Option<Box<Box<Fn(&Message)>>>,
+    // False if the callback was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl DebugCallback {
@@ -160,10 +197,34 @@ impl DebugCallback {
         Ok(DebugCallback {
                instance: instance.clone(),
                debug_report_callback: debug_report_callback,
-               user_callback: user_callback,
+               user_callback: Some(user_callback),
+               needs_destruction: true,
            })
     }
 
+    /// Builds a `DebugCallback` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the callback is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid debug report callback of `instance`.
+    /// - The user data of the callback must outlive the returned object.
+    /// - If `owned` is true, the callback must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(instance: Arc<Instance>, handle: vk::DebugReportCallbackEXT,
+                           owned: bool)
+                           -> DebugCallback {
+        DebugCallback {
+            instance: instance,
+            debug_report_callback: handle,
+            user_callback: None,
+            needs_destruction: owned,
+        }
+    }
+
     /// Initializes a debug callback with errors and warnings.
     ///
     /// Shortcut for `new(instance, MessageTypes::errors_and_warnings(), user_callback)`.
@@ -174,11 +235,37 @@ impl DebugCallback {
     {
         DebugCallback::new(instance, MessageTypes::errors_and_warnings(), user_callback)
     }
+
+    /// Initializes a debug callback that forwards the messages to the `log` crate.
+    ///
+    /// Errors are logged with the `Error` level, warnings and performance warnings with the
+    /// `Warn` level, information with the `Info` level and debug messages with the `Debug` level.
+    #[cfg(feature = "log")]
+    pub fn with_log(instance: &Arc<Instance>, messages: MessageTypes)
+                    -> Result<DebugCallback, DebugCallbackCreationError> {
+        DebugCallback::new(instance, messages, |msg| {
+            let level = if msg.ty.error {
+                ::log::Level::Error
+            } else if msg.ty.warning || msg.ty.performance_warning {
+                ::log::Level::Warn
+            } else if msg.ty.information {
+                ::log::Level::Info
+            } else {
+                ::log::Level::Debug
+            };
+
+            log!(level, "{}: {}", msg.layer_prefix, msg.description);
+        })
+    }
 }
 
 impl Drop for DebugCallback {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.instance.pointers();
             vk.DestroyDebugReportCallbackEXT(self.instance.internal_object(),
@@ -251,30 +338,443 @@ impl MessageTypes {
 /// Error that can happen when creating a debug callback.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DebugCallbackCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
     /// The `EXT_debug_report` extension was not enabled.
     MissingExtension,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for DebugCallbackCreationError {
     #[inline]
     fn description(&self) -> &str {
         match *self {
+            DebugCallbackCreationError::OomError(_) => "not enough memory available",
             DebugCallbackCreationError::MissingExtension =>
                 "the `EXT_debug_report` extension was not enabled",
+            DebugCallbackCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DebugCallbackCreationError::OomError(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
-impl fmt::Display for DebugCallbackCreationError {
+vk_error_conversions!(DebugCallbackCreationError, OomError {});
+
+/// Name of the validation layer provided by the Khronos group as part of the Vulkan SDK.
+///
+/// Pass it to `Instance::new` to enable the validation of the API usage. The messages of the
+/// layer can be received with a `DebugCallback` or a `DebugUtilsMessenger`.
+pub const KHRONOS_VALIDATION_LAYER: &'static str = "VK_LAYER_KHRONOS_validation";
+
+/// Returns true if the layer named `KHRONOS_VALIDATION_LAYER` is available on the system.
+pub fn validation_layer_available() -> Result<bool, LayersListError> {
+    Ok(layers_list()?.any(|layer| layer.name() == KHRONOS_VALIDATION_LAYER))
+}
+
+/// Registration of a callback called with the messages of the `EXT_debug_utils` extension.
+///
+/// The callback can be called as long as this object is alive.
+#[must_use = "The DebugUtilsMessenger object must be kept alive for as long as you want your \
+              callback to be called"]
+pub struct DebugUtilsMessenger {
+    instance: Arc<Instance>,
+    messenger: vk::DebugUtilsMessengerEXT,
+    // `None` if the messenger was built with `from_raw`, in which case the user data of the
+    // callback is managed by the caller.
+    user_callback: Option<Box<Box<Fn(&DebugUtilsMessage)>>>,
+    // False if the messenger was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+impl DebugUtilsMessenger {
+    /// Initializes a debug utils messenger.
+    ///
+    /// Only the messages whose severity is in `severity` and whose type is in `ty` are passed
+    /// to `user_callback`. Panics generated by calling `user_callback` are ignored.
+    pub fn new<F>(instance: &Arc<Instance>, severity: MessageSeverity, ty: MessageType,
+                  user_callback: F)
+                  -> Result<DebugUtilsMessenger, DebugUtilsMessengerCreationError>
+        where F: Fn(&DebugUtilsMessage) + 'static + Send + panic::RefUnwindSafe
+    {
+        if !instance.loaded_extensions().ext_debug_utils {
+            return Err(DebugUtilsMessengerCreationError::MissingExtension);
+        }
+
+        // Double-boxed for the same reason as in `DebugCallback`.
+        let user_callback = Box::new(Box::new(user_callback) as Box<_>);
+
+        extern "system" fn callback(severity: vk::DebugUtilsMessageSeverityFlagBitsEXT,
+                                    ty: vk::DebugUtilsMessageTypeFlagsEXT,
+                                    data: *const vk::DebugUtilsMessengerCallbackDataEXT,
+                                    user_data: *mut c_void)
+                                    -> u32 {
+            unsafe {
+                let user_callback = user_data as *mut Box<Fn()> as *const _;
+                let user_callback: &Box<Fn(&DebugUtilsMessage)> = &*user_callback;
+                let data = &*data;
+
+                // Panicking here would unwind into the driver, therefore invalid UTF-8 is
+                // replaced instead of being rejected.
+                let message_id_name = if data.pMessageIdName.is_null() {
+                    None
+                } else {
+                    Some(CStr::from_ptr(data.pMessageIdName).to_string_lossy())
+                };
+                let description = if data.pMessage.is_null() {
+                    "".into()
+                } else {
+                    CStr::from_ptr(data.pMessage).to_string_lossy()
+                };
+
+                let message = DebugUtilsMessage {
+                    severity: MessageSeverity::from_bits(severity),
+                    ty: MessageType::from_bits(ty),
+                    message_id_name: message_id_name.as_ref().map(|name| &**name),
+                    message_id_number: data.messageIdNumber,
+                    description: &description,
+                };
+
+                let _ = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+                                                                        user_callback(&message);
+                                                                    }));
+
+                vk::FALSE
+            }
+        }
+
+        let infos = vk::DebugUtilsMessengerCreateInfoEXT {
+            sType: vk::STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CREATE_INFO_EXT,
+            pNext: ptr::null(),
+            flags: 0,
+            messageSeverity: severity.to_bits(),
+            messageType: ty.to_bits(),
+            pfnUserCallback: callback,
+            pUserData: &*user_callback as &Box<_> as *const Box<_> as *const c_void as *mut _,
+        };
+
+        let vk = instance.pointers();
+
+        let messenger = unsafe {
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateDebugUtilsMessengerEXT(instance.internal_object(),
+                                                         &infos,
+                                                         ptr::null(),
+                                                         &mut output))?;
+            output
+        };
+
+        Ok(DebugUtilsMessenger {
+               instance: instance.clone(),
+               messenger: messenger,
+               user_callback: Some(user_callback),
+               needs_destruction: true,
+           })
+    }
+
+    /// Builds a `DebugUtilsMessenger` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the messenger is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid debug utils messenger of `instance`.
+    /// - The user data of the callback must outlive the returned object.
+    /// - If `owned` is true, the messenger must not be destroyed by anything else.
+    ///
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
+    pub unsafe fn from_raw(instance: Arc<Instance>, handle: vk::DebugUtilsMessengerEXT,
+                           owned: bool)
+                           -> DebugUtilsMessenger {
+        DebugUtilsMessenger {
+            instance: instance,
+            messenger: handle,
+            user_callback: None,
+            needs_destruction: owned,
+        }
+    }
+
+    /// Initializes a debug utils messenger with errors and warnings of all types.
+    ///
+    /// Shortcut for
+    /// `new(instance, MessageSeverity::errors_and_warnings(), MessageType::all(), user_callback)`.
+    #[inline]
+    pub fn errors_and_warnings<F>(instance: &Arc<Instance>, user_callback: F)
+                                  -> Result<DebugUtilsMessenger, DebugUtilsMessengerCreationError>
+        where F: Fn(&DebugUtilsMessage) + Send + 'static + panic::RefUnwindSafe
+    {
+        DebugUtilsMessenger::new(instance,
+                                 MessageSeverity::errors_and_warnings(),
+                                 MessageType::all(),
+                                 user_callback)
+    }
+
+    /// Initializes a debug utils messenger that forwards the messages to the `log` crate.
+    ///
+    /// Errors are logged with the `Error` level, warnings with the `Warn` level, information with
+    /// the `Info` level and verbose messages with the `Trace` level.
+    #[cfg(feature = "log")]
+    pub fn with_log(instance: &Arc<Instance>, severity: MessageSeverity, ty: MessageType)
+                    -> Result<DebugUtilsMessenger, DebugUtilsMessengerCreationError> {
+        DebugUtilsMessenger::new(instance, severity, ty, |msg| {
+            let level = if msg.severity.error {
+                ::log::Level::Error
+            } else if msg.severity.warning {
+                ::log::Level::Warn
+            } else if msg.severity.information {
+                ::log::Level::Info
+            } else {
+                ::log::Level::Trace
+            };
+
+            match msg.message_id_name {
+                Some(name) => log!(level, "{}: {}", name, msg.description),
+                None => log!(level, "{}", msg.description),
+            }
+        })
     }
 }
 
-impl From<Error> for DebugCallbackCreationError {
+impl Drop for DebugUtilsMessenger {
     #[inline]
-    fn from(err: Error) -> DebugCallbackCreationError {
-        panic!("unexpected error: {:?}", err)
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.instance.pointers();
+            vk.DestroyDebugUtilsMessengerEXT(self.instance.internal_object(),
+                                             self.messenger,
+                                             ptr::null());
+        }
+    }
+}
+
+/// A message received by the callback of a `DebugUtilsMessenger`.
+pub struct DebugUtilsMessage<'a> {
+    /// Severity of the message. Exactly one field is set to `true`.
+    pub severity: MessageSeverity,
+    /// Type of the message.
+    pub ty: MessageType,
+    /// Name that identifies the kind of message, for example the validation rule that has been
+    /// violated.
+    pub message_id_name: Option<&'a str>,
+    /// Number that identifies the kind of message.
+    pub message_id_number: i32,
+    /// Description of the message.
+    pub description: &'a str,
+}
+
+/// Severities of the messages of a `DebugUtilsMessenger`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MessageSeverity {
+    /// An error that may cause undefined results, including an application crash.
+    pub error: bool,
+    /// An unexpected use, or a use that may be non-optimal.
+    pub warning: bool,
+    /// An informational message, such as the creation of a resource.
+    pub information: bool,
+    /// Diagnostic information from the loader, the layers and the driver.
+    pub verbose: bool,
+}
+
+impl MessageSeverity {
+    /// Builds a `MessageSeverity` with all fields set to `false` except `error`.
+    #[inline]
+    pub fn errors() -> MessageSeverity {
+        MessageSeverity {
+            error: true,
+            ..MessageSeverity::none()
+        }
+    }
+
+    /// Builds a `MessageSeverity` with all fields set to `false` except `error` and `warning`.
+    #[inline]
+    pub fn errors_and_warnings() -> MessageSeverity {
+        MessageSeverity {
+            error: true,
+            warning: true,
+            ..MessageSeverity::none()
+        }
+    }
+
+    /// Builds a `MessageSeverity` with all fields set to `true`.
+    #[inline]
+    pub fn all() -> MessageSeverity {
+        MessageSeverity {
+            error: true,
+            warning: true,
+            information: true,
+            verbose: true,
+        }
+    }
+
+    /// Builds a `MessageSeverity` with all fields set to `false`.
+    #[inline]
+    pub fn none() -> MessageSeverity {
+        MessageSeverity {
+            error: false,
+            warning: false,
+            information: false,
+            verbose: false,
+        }
+    }
+
+    #[inline]
+    fn from_bits(bits: vk::DebugUtilsMessageSeverityFlagsEXT) -> MessageSeverity {
+        MessageSeverity {
+            error: (bits & vk::DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT) != 0,
+            warning: (bits & vk::DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT) != 0,
+            information: (bits & vk::DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT) != 0,
+            verbose: (bits & vk::DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT) != 0,
+        }
+    }
+
+    #[inline]
+    fn to_bits(&self) -> vk::DebugUtilsMessageSeverityFlagsEXT {
+        let mut bits = 0;
+        if self.error {
+            bits |= vk::DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT;
+        }
+        if self.warning {
+            bits |= vk::DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT;
+        }
+        if self.information {
+            bits |= vk::DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT;
+        }
+        if self.verbose {
+            bits |= vk::DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT;
+        }
+        bits
+    }
+}
+
+/// Types of the messages of a `DebugUtilsMessenger`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct MessageType {
+    /// An event that is unrelated to the specification or to performances.
+    pub general: bool,
+    /// A violation of the specification, or a possible mistake.
+    pub validation: bool,
+    /// A potential non-optimal use.
+    pub performance: bool,
+}
+
+impl MessageType {
+    /// Builds a `MessageType` with all fields set to `true`.
+    #[inline]
+    pub fn all() -> MessageType {
+        MessageType {
+            general: true,
+            validation: true,
+            performance: true,
+        }
+    }
+
+    /// Builds a `MessageType` with all fields set to `false`.
+    #[inline]
+    pub fn none() -> MessageType {
+        MessageType {
+            general: false,
+            validation: false,
+            performance: false,
+        }
+    }
+
+    #[inline]
+    fn from_bits(bits: vk::DebugUtilsMessageTypeFlagsEXT) -> MessageType {
+        MessageType {
+            general: (bits & vk::DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT) != 0,
+            validation: (bits & vk::DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT) != 0,
+            performance: (bits & vk::DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT) != 0,
+        }
+    }
+
+    #[inline]
+    fn to_bits(&self) -> vk::DebugUtilsMessageTypeFlagsEXT {
+        let mut bits = 0;
+        if self.general {
+            bits |= vk::DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT;
+        }
+        if self.validation {
+            bits |= vk::DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT;
+        }
+        if self.performance {
+            bits |= vk::DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT;
+        }
+        bits
+    }
+}
+
+/// Error that can happen when creating a debug utils messenger.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugUtilsMessengerCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The `EXT_debug_utils` extension was not enabled.
+    MissingExtension,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for DebugUtilsMessengerCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DebugUtilsMessengerCreationError::OomError(_) => "not enough memory available",
+            DebugUtilsMessengerCreationError::MissingExtension =>
+                "the `EXT_debug_utils` extension was not enabled",
+            DebugUtilsMessengerCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DebugUtilsMessengerCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(DebugUtilsMessengerCreationError, OomError {});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messenger_missing_extension() {
+        let instance = instance!();
+
+        match DebugUtilsMessenger::errors_and_warnings(&instance, |_| {}) {
+            Err(DebugUtilsMessengerCreationError::MissingExtension) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn severity_bits() {
+        let severity = MessageSeverity::errors_and_warnings();
+        assert_eq!(MessageSeverity::from_bits(severity.to_bits()), severity);
+        assert_eq!(MessageSeverity::from_bits(MessageSeverity::all().to_bits()),
+                   MessageSeverity::all());
+        assert_eq!(MessageType::from_bits(MessageType::all().to_bits()), MessageType::all());
+        assert_eq!(MessageType::none().to_bits(), 0);
     }
 }
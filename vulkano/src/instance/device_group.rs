@@ -0,0 +1,203 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use instance::Instance;
+use instance::PhysicalDevice;
+use instance::Version;
+
+use OomError;
+use VulkanObject;
+use check_errors;
+use vk;
+
+/// A group of physical devices that can be used together by a single logical device.
+///
+/// The physical devices of a group are usually multiple identical GPUs linked together, for
+/// example with SLI or CrossFire. A physical device that isn't linked to anything forms a group
+/// of its own.
+///
+/// Use `Device::with_group` to create a device that spans all the physical devices of a group.
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceGroup<'a> {
+    instance: &'a Arc<Instance>,
+    devices: SmallVec<[usize; 4]>,
+    subset_allocation: bool,
+}
+
+impl<'a> PhysicalDeviceGroup<'a> {
+    /// Returns true if device groups can be enumerated on `instance`.
+    ///
+    /// This is the case if the instance uses Vulkan 1.1 or above, or if the
+    /// `khr_device_group_creation` extension has been enabled.
+    #[inline]
+    pub fn is_supported(instance: &Instance) -> bool {
+        instance.api_version() >= Version::V1_1 ||
+            instance.loaded_extensions().khr_device_group_creation
+    }
+
+    /// Enumerates the device groups of an instance.
+    ///
+    /// Each physical device of the instance belongs to exactly one group.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `is_supported` returns false for this instance.
+    ///
+    pub fn enumerate(instance: &'a Arc<Instance>)
+                     -> Result<Vec<PhysicalDeviceGroup<'a>>, OomError> {
+        assert!(PhysicalDeviceGroup::is_supported(instance),
+                "Device groups require Vulkan 1.1 or the `khr_device_group_creation` extension");
+
+        let vk = instance.pointers();
+
+        let groups: Vec<vk::PhysicalDeviceGroupProperties> = unsafe {
+            let enumerate = if instance.api_version() >= Version::V1_1 {
+                vk.EnumeratePhysicalDeviceGroups
+            } else {
+                vk.EnumeratePhysicalDeviceGroupsKHR
+            };
+
+            let mut num = 0;
+            check_errors(enumerate(instance.internal_object(), &mut num, ptr::null_mut()))?;
+
+            let mut groups = Vec::with_capacity(num as usize);
+            for _ in 0 .. num {
+                let mut group: vk::PhysicalDeviceGroupProperties = mem::zeroed();
+                group.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_GROUP_PROPERTIES;
+                groups.push(group);
+            }
+            check_errors(enumerate(instance.internal_object(), &mut num, groups.as_mut_ptr()))?;
+            groups.truncate(num as usize);
+            groups
+        };
+
+        let output = groups
+            .iter()
+            .map(|group| {
+                let devices = group.physicalDevices[.. group.physicalDeviceCount as usize]
+                    .iter()
+                    .map(|&handle| {
+                        PhysicalDevice::enumerate(instance)
+                            .find(|p| p.internal_object() == handle)
+                            .expect("device group contains an unknown physical device")
+                            .index()
+                    })
+                    .collect();
+
+                PhysicalDeviceGroup {
+                    instance: instance,
+                    devices: devices,
+                    subset_allocation: group.subsetAllocation != 0,
+                }
+            })
+            .collect();
+
+        Ok(output)
+    }
+
+    /// Returns the instance the group belongs to.
+    #[inline]
+    pub fn instance(&self) -> &'a Arc<Instance> {
+        self.instance
+    }
+
+    /// Returns the number of physical devices in the group.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// Returns the physical device of the group at the given index, or `None` if out of range.
+    ///
+    /// The index of a physical device within its group is the index of its bit in the device
+    /// masks.
+    #[inline]
+    pub fn physical_device(&self, index: usize) -> Option<PhysicalDevice<'a>> {
+        self.devices
+            .get(index)
+            .map(|&d| PhysicalDevice::from_index(self.instance, d).unwrap())
+    }
+
+    /// Returns an iterator to the physical devices of the group.
+    #[inline]
+    pub fn physical_devices<'s>(&'s self) -> PhysicalDeviceGroupIter<'a, 's> {
+        PhysicalDeviceGroupIter {
+            instance: self.instance,
+            devices: self.devices.iter(),
+        }
+    }
+
+    /// Returns true if memory allocations can be made on a subset of the devices of the group.
+    ///
+    /// If false, the device mask passed to `DeviceMemory::alloc_with_device_mask` must contain
+    /// all the devices of the group.
+    #[inline]
+    pub fn supports_subset_allocation(&self) -> bool {
+        self.subset_allocation
+    }
+}
+
+/// Iterator to the physical devices of a `PhysicalDeviceGroup`.
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceGroupIter<'a, 's> {
+    instance: &'a Arc<Instance>,
+    devices: slice::Iter<'s, usize>,
+}
+
+impl<'a, 's> Iterator for PhysicalDeviceGroupIter<'a, 's> {
+    type Item = PhysicalDevice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<PhysicalDevice<'a>> {
+        let instance = self.instance;
+        self.devices
+            .next()
+            .map(|&d| PhysicalDevice::from_index(instance, d).unwrap())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.devices.size_hint()
+    }
+}
+
+impl<'a, 's> ExactSizeIterator for PhysicalDeviceGroupIter<'a, 's> {
+}
+
+#[cfg(test)]
+mod tests {
+    use instance::PhysicalDevice;
+    use instance::PhysicalDeviceGroup;
+
+    #[test]
+    fn groups_cover_all_devices() {
+        let instance = instance!();
+        if !PhysicalDeviceGroup::is_supported(&instance) {
+            return;
+        }
+
+        let groups = PhysicalDeviceGroup::enumerate(&instance).unwrap();
+        let total: usize = groups.iter().map(|g| g.len()).sum();
+        assert_eq!(total, PhysicalDevice::enumerate(&instance).len());
+
+        for physical in PhysicalDevice::enumerate(&instance) {
+            let count = groups
+                .iter()
+                .filter(|g| g.physical_devices().any(|p| p.index() == physical.index()))
+                .count();
+            assert_eq!(count, 1);
+        }
+    }
+}
@@ -72,6 +72,35 @@ macro_rules! extensions {
                     _unbuildable: Unbuildable(())
                 }
             }
+
+            /// Returns the union of this list and another list.
+            #[inline]
+            pub fn union(&self, other: &$sname) -> $sname {
+                $sname {
+                    $(
+                        $ext: self.$ext || other.$ext,
+                    )*
+                    _unbuildable: Unbuildable(())
+                }
+            }
+
+            /// Returns true if all the extensions of `other` are also in this list.
+            #[inline]
+            pub fn superset_of(&self, other: &$sname) -> bool {
+                $((self.$ext || !other.$ext))&&*
+            }
+
+            /// Returns the Vulkan names of the extensions of this list.
+            pub fn names(&self) -> Vec<&'static str> {
+                let mut names = Vec::new();
+                $(
+                    if self.$ext {
+                        // The names are ASCII literals, therefore this can't fail.
+                        names.push(::std::str::from_utf8($s).unwrap());
+                    }
+                )*
+                names
+            }
         }
 
         impl fmt::Debug for $sname {
@@ -189,17 +218,35 @@ macro_rules! instance_extensions {
             pub fn supported_by_core_raw_with_loader<L>(ptrs: &loader::FunctionPointers<L>)
                         -> Result<Self, SupportedExtensionsError>
                 where L: loader::Loader
+            {
+                $rawname::supported_by_layer_ptr(ptrs, ptr::null())
+            }
+
+            /// Returns the extensions provided by the layer with the given name, in addition to
+            /// the ones supported by the core driver.
+            pub fn supported_by_layer_raw_with_loader<L>(ptrs: &loader::FunctionPointers<L>,
+                                                         layer: &CStr)
+                        -> Result<Self, SupportedExtensionsError>
+                where L: loader::Loader
+            {
+                $rawname::supported_by_layer_ptr(ptrs, layer.as_ptr())
+            }
+
+            fn supported_by_layer_ptr<L>(ptrs: &loader::FunctionPointers<L>,
+                                         layer: *const ::std::os::raw::c_char)
+                        -> Result<Self, SupportedExtensionsError>
+                where L: loader::Loader
             {
                 let entry_points = ptrs.entry_points();
 
                 let properties: Vec<vk::ExtensionProperties> = unsafe {
                     let mut num = 0;
                     try!(check_errors(entry_points.EnumerateInstanceExtensionProperties(
-                        ptr::null(), &mut num, ptr::null_mut())));
+                        layer, &mut num, ptr::null_mut())));
 
                     let mut properties = Vec::with_capacity(num as usize);
                     try!(check_errors(entry_points.EnumerateInstanceExtensionProperties(
-                        ptr::null(), &mut num, properties.as_mut_ptr())));
+                        layer, &mut num, properties.as_mut_ptr())));
                     properties.set_len(num as usize);
                     properties
                 };
@@ -381,12 +428,16 @@ instance_extensions! {
     khr_android_surface => b"VK_KHR_android_surface",
     khr_win32_surface => b"VK_KHR_win32_surface",
     ext_debug_report => b"VK_EXT_debug_report",
+    ext_debug_utils => b"VK_EXT_debug_utils",
     mvk_ios_surface => b"VK_MVK_ios_surface",
     mvk_macos_surface => b"VK_MVK_macos_surface",
     mvk_moltenvk => b"VK_MVK_moltenvk",     // TODO: confirm that it's an instance extension
     nn_vi_surface => b"VK_NN_vi_surface",
     ext_swapchain_colorspace => b"VK_EXT_swapchain_colorspace",
     khr_get_physical_device_properties2 => b"VK_KHR_get_physical_device_properties2",
+    khr_device_group_creation => b"VK_KHR_device_group_creation",
+    khr_external_semaphore_capabilities => b"VK_KHR_external_semaphore_capabilities",
+    khr_external_fence_capabilities => b"VK_KHR_external_fence_capabilities",
 }
 
 device_extensions! {
@@ -396,10 +447,50 @@ device_extensions! {
     khr_display_swapchain => b"VK_KHR_display_swapchain",
     khr_sampler_mirror_clamp_to_edge => b"VK_KHR_sampler_mirror_clamp_to_edge",
     khr_maintenance1 => b"VK_KHR_maintenance1",
+    khr_maintenance2 => b"VK_KHR_maintenance2",
+    khr_maintenance3 => b"VK_KHR_maintenance3",
     khr_get_memory_requirements2 => b"VK_KHR_get_memory_requirements2",
+    khr_bind_memory2 => b"VK_KHR_bind_memory2",
+    khr_device_group => b"VK_KHR_device_group",
+    khr_image_format_list => b"VK_KHR_image_format_list",
     khr_dedicated_allocation => b"VK_KHR_dedicated_allocation",
     khr_incremental_present => b"VK_KHR_incremental_present",
+    khr_push_descriptor => b"VK_KHR_push_descriptor",
+    khr_shader_draw_parameters => b"VK_KHR_shader_draw_parameters",
+    khr_draw_indirect_count => b"VK_KHR_draw_indirect_count",
+    khr_storage_buffer_storage_class => b"VK_KHR_storage_buffer_storage_class",
+    khr_16bit_storage => b"VK_KHR_16bit_storage",
+    khr_relaxed_block_layout => b"VK_KHR_relaxed_block_layout",
+    khr_descriptor_update_template => b"VK_KHR_descriptor_update_template",
+    khr_multiview => b"VK_KHR_multiview",
+    khr_create_renderpass2 => b"VK_KHR_create_renderpass2",
+    khr_depth_stencil_resolve => b"VK_KHR_depth_stencil_resolve",
+    khr_timeline_semaphore => b"VK_KHR_timeline_semaphore",
+    khr_external_semaphore => b"VK_KHR_external_semaphore",
+    khr_external_semaphore_fd => b"VK_KHR_external_semaphore_fd",
+    khr_external_semaphore_win32 => b"VK_KHR_external_semaphore_win32",
+    khr_external_fence => b"VK_KHR_external_fence",
+    khr_external_fence_fd => b"VK_KHR_external_fence_fd",
+    khr_external_fence_win32 => b"VK_KHR_external_fence_win32",
+    khr_sampler_ycbcr_conversion => b"VK_KHR_sampler_ycbcr_conversion",
+    ext_descriptor_indexing => b"VK_EXT_descriptor_indexing",
     ext_debug_marker => b"VK_EXT_debug_marker",
+    ext_conservative_rasterization => b"VK_EXT_conservative_rasterization",
+    ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive",
+    ext_hdr_metadata => b"VK_EXT_hdr_metadata",
+    ext_host_query_reset => b"VK_EXT_host_query_reset",
+    ext_memory_budget => b"VK_EXT_memory_budget",
+    ext_custom_border_color => b"VK_EXT_custom_border_color",
+    khr_buffer_device_address => b"VK_KHR_buffer_device_address",
+    khr_deferred_host_operations => b"VK_KHR_deferred_host_operations",
+    khr_acceleration_structure => b"VK_KHR_acceleration_structure",
+    khr_ray_tracing_pipeline => b"VK_KHR_ray_tracing_pipeline",
+    khr_ray_query => b"VK_KHR_ray_query",
+    khr_shader_float_controls => b"VK_KHR_shader_float_controls",
+    khr_spirv_1_4 => b"VK_KHR_spirv_1_4",
+    ext_mesh_shader => b"VK_EXT_mesh_shader",
+    ext_conditional_rendering => b"VK_EXT_conditional_rendering",
+    ext_transform_feedback => b"VK_EXT_transform_feedback",
 }
 
 /// Error that can happen when loading the list of layers.
@@ -484,4 +575,24 @@ mod tests {
         let d: RawDeviceExtensions = (&DeviceExtensions::none()).into();
         assert!(d.iter().next().is_none());
     }
+
+    #[test]
+    fn typed_set_operations() {
+        let swapchain = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        };
+        let push_descriptor = DeviceExtensions {
+            khr_push_descriptor: true,
+            ..DeviceExtensions::none()
+        };
+
+        let both = swapchain.union(&push_descriptor);
+        assert!(both.superset_of(&swapchain));
+        assert!(both.superset_of(&push_descriptor));
+        assert!(!swapchain.superset_of(&both));
+        assert!(swapchain.superset_of(&DeviceExtensions::none()));
+        assert_eq!(both.difference(&swapchain), push_descriptor);
+        assert_eq!(both.names(), vec!["VK_KHR_swapchain", "VK_KHR_push_descriptor"]);
+    }
 }
@@ -9,6 +9,7 @@
 
 use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::cmp;
 use std::error;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -23,6 +24,9 @@ use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
+use instance::extensions::SupportedExtensionsError;
+use instance::layers;
+use instance::layers::LayersListError;
 use instance::limits::Limits;
 use instance::loader;
 use instance::loader::FunctionPointers;
@@ -31,9 +35,14 @@ use instance::loader::LoadingError;
 use vk;
 
 use features::Features;
+use format::Format;
+use format::FormatProperties;
 use instance::{InstanceExtensions, RawInstanceExtensions};
 use version::Version;
 
+// Highest version of Vulkan that vulkano requests when creating an instance.
+const MAX_API_VERSION: Version = Version::V1_2;
+
 /// An instance of a Vulkan context. This is the main object that should be created by an
 /// application before everything else.
 ///
@@ -88,9 +97,13 @@ pub struct Instance {
     //alloc: Option<Box<Alloc + Send + Sync>>,
     physical_devices: Vec<PhysicalDeviceInfos>,
     vk: vk::InstancePointers,
+    api_version: Version,
     extensions: InstanceExtensions,
+    raw_extensions: RawInstanceExtensions,
     layers: SmallVec<[CString; 16]>,
     function_pointers: OwnedOrRef<FunctionPointers<Box<Loader + Send + Sync>>>,
+    // False if the instance was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 // TODO: fix the underlying cause instead
@@ -105,6 +118,11 @@ impl Instance {
     /// See the documentation of `Instance` or of [the `instance` module](index.html) for more
     /// details.
     ///
+    /// Before creating the instance, checks that all the requested layers are available and that
+    /// each requested extension is provided either by the driver or by one of the requested
+    /// layers. If that's not the case, returns `MissingLayers` or `MissingExtensions` with the
+    /// names of the missing ones.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -186,6 +204,23 @@ impl Instance {
             None
         };
 
+        // Vulkan 1.0 loaders refuse any version other than 1.0 in `apiVersion`, while newer
+        // loaders accept any version. Requesting the highest version that we know about allows
+        // using the functionalities that have been promoted to core.
+        let api_version = {
+            let supported = function_pointers.api_version()?;
+            let requested = app_infos
+                .and_then(|infos| infos.min_api_version)
+                .unwrap_or(Version::V1_0);
+            if requested > supported {
+                return Err(InstanceCreationError::UnsupportedApiVersion {
+                               requested: requested,
+                               supported: supported,
+                           });
+            }
+            cmp::min(supported, MAX_API_VERSION)
+        };
+
         // Building the `vk::ApplicationInfo` if required.
         let app_infos = if let Some(app_infos) = app_infos {
             Some(vk::ApplicationInfo {
@@ -213,18 +248,46 @@ impl Instance {
                     .engine_version
                     .map(|v| v.into_vulkan_version())
                     .unwrap_or(0),
-                apiVersion: Version {
-                    major: 1,
-                    minor: 0,
-                    patch: 0,
-                }.into_vulkan_version(), // TODO:
+                apiVersion: api_version.into_vulkan_version(),
             })
 
         } else {
             None
         };
 
-        // FIXME: check whether each layer is supported
+        // Checking that the layers and extensions are available, so that we can report the names
+        // of the missing ones. Extensions can be provided by the driver or by one of the layers.
+        {
+            let available_layers = layers::layers_list_from_loader(&*function_pointers)?
+                .map(|layer| layer.name().to_owned())
+                .collect::<Vec<_>>();
+            let missing_layers = layers
+                .iter()
+                .map(|layer| layer.to_string_lossy().into_owned())
+                .filter(|layer| !available_layers.contains(layer))
+                .collect::<Vec<_>>();
+            if !missing_layers.is_empty() {
+                return Err(InstanceCreationError::MissingLayers(missing_layers));
+            }
+
+            let mut available_extensions =
+                RawInstanceExtensions::supported_by_core_raw_with_loader(&*function_pointers)?;
+            for layer in layers.iter() {
+                let provided = RawInstanceExtensions::supported_by_layer_raw_with_loader(
+                    &*function_pointers, layer)?;
+                available_extensions = available_extensions.union(&provided);
+            }
+            let mut missing_extensions = extensions
+                .difference(&available_extensions)
+                .iter()
+                .map(|extension| extension.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            if !missing_extensions.is_empty() {
+                missing_extensions.sort();
+                return Err(InstanceCreationError::MissingExtensions(missing_extensions));
+            }
+        }
+
         let layers_ptr = layers
             .iter()
             .map(|layer| layer.as_ptr())
@@ -258,15 +321,63 @@ impl Instance {
             output
         };
 
-        // Loading the function pointers of the newly-created instance.
+        unsafe {
+            Instance::from_raw_inner(function_pointers, instance, api_version, extensions, layers,
+                                     true)
+        }
+    }
+
+    /// Builds an `Instance` from a raw handle that was created outside of vulkano, for example
+    /// by another Vulkan wrapper or by a C library.
+    ///
+    /// `api_version`, `extensions` and `layers` must be the values that were passed to
+    /// `vkCreateInstance`. The raw handle of an `Instance` can be obtained with
+    /// `VulkanObject::internal_object`.
+    ///
+    /// If `owned` is true, the instance is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it after all the vulkano objects
+    /// that use it have been dropped.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid instance created from the default Vulkan loader (see
+    ///   `loader::auto_loader`).
+    /// - The parameters must match the ones used to create the instance.
+    /// - If `owned` is true, the instance must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw<'a, L, Ext>(handle: vk::Instance, api_version: Version,
+                                       extensions: Ext, layers: L, owned: bool)
+                                       -> Result<Arc<Instance>, InstanceCreationError>
+        where L: IntoIterator<Item = &'a &'a str>,
+              Ext: Into<RawInstanceExtensions>
+    {
+        let layers = layers
+            .into_iter()
+            .map(|&layer| CString::new(layer).unwrap())
+            .collect::<SmallVec<[_; 16]>>();
+
+        Instance::from_raw_inner(OwnedOrRef::Ref(loader::auto_loader()?),
+                                 handle,
+                                 api_version,
+                                 extensions.into(),
+                                 layers,
+                                 owned)
+    }
+
+    unsafe fn from_raw_inner(
+        function_pointers: OwnedOrRef<FunctionPointers<Box<Loader + Send + Sync>>>,
+        instance: vk::Instance, api_version: Version, extensions: RawInstanceExtensions,
+        layers: SmallVec<[CString; 16]>, owned: bool)
+        -> Result<Arc<Instance>, InstanceCreationError> {
+        // Loading the function pointers of the instance.
         let vk = {
-            vk::InstancePointers::load(|name| unsafe {
+            vk::InstancePointers::load(|name| {
                 mem::transmute(function_pointers.get_instance_proc_addr(instance, name.as_ptr()))
             })
         };
 
         // Enumerating all physical devices.
-        let physical_devices: Vec<vk::PhysicalDevice> = unsafe {
+        let physical_devices: Vec<vk::PhysicalDevice> = {
             let mut num = 0;
             check_errors(vk.EnumeratePhysicalDevices(instance, &mut num, ptr::null_mut()))?;
 
@@ -277,7 +388,8 @@ impl Instance {
         };
 
         // TODO: should be Into
-        let extensions: InstanceExtensions = (&extensions).into();
+        let raw_extensions = extensions;
+        let extensions: InstanceExtensions = (&raw_extensions).into();
 
         // Getting the properties of all physical devices.
         // If possible, we use VK_KHR_get_physical_device_properties2.
@@ -292,9 +404,12 @@ impl Instance {
                         //alloc: None,
                         physical_devices: physical_devices,
                         vk: vk,
+                        api_version: api_version,
                         extensions: extensions,
+                        raw_extensions: raw_extensions,
                         layers: layers,
                         function_pointers: function_pointers,
+                        needs_destruction: owned,
                     }))
     }
 
@@ -450,6 +565,31 @@ impl Instance {
         &self.extensions
     }
 
+    /// Returns the version of Vulkan used by the instance.
+    ///
+    /// This is the highest version supported by both the Vulkan loader and vulkano. Physical
+    /// devices can support a different version, see `PhysicalDevice::api_version` and
+    /// `Device::api_version`.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
+    /// Returns the highest version of Vulkan supported by the default Vulkan loader.
+    ///
+    /// Use this to choose the value of `ApplicationInfo::min_api_version`, or to decide whether
+    /// to use another backend before creating an instance.
+    pub fn supported_api_version() -> Result<Version, SupportedApiVersionError> {
+        Ok(loader::auto_loader()?.api_version()?)
+    }
+
+    /// Returns the names of all the extensions that have been loaded, including the ones that
+    /// vulkano doesn't know about and that aren't part of `loaded_extensions()`.
+    #[inline]
+    pub fn loaded_raw_extensions(&self) -> &RawInstanceExtensions {
+        &self.raw_extensions
+    }
+
     /// Returns the list of layers requested when creating this instance.
     #[doc(hidden)]
     #[inline]
@@ -479,6 +619,10 @@ unsafe impl VulkanObject for Instance {
 impl Drop for Instance {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             self.vk.DestroyInstance(self.instance, ptr::null());
         }
@@ -514,6 +658,12 @@ pub struct ApplicationInfo<'a> {
     pub engine_name: Option<Cow<'a, str>>,
     /// An opaque number that contains the version number of the engine.
     pub engine_version: Option<Version>,
+    /// Minimum version of Vulkan that the application requires. Instance creation fails with
+    /// `UnsupportedApiVersion` if the Vulkan loader doesn't support it.
+    ///
+    /// Regardless of this value, the instance uses the highest version supported by both the
+    /// loader and vulkano. `None` is the same as version 1.0.0.
+    pub min_api_version: Option<Version>,
 }
 
 impl<'a> ApplicationInfo<'a> {
@@ -539,6 +689,7 @@ impl<'a> ApplicationInfo<'a> {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            min_api_version: None,
         }
     }
 }
@@ -566,6 +717,7 @@ macro_rules! app_info_from_cargo_toml {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            min_api_version: None,
         }
     }}
 }
@@ -577,6 +729,7 @@ impl<'a> Default for ApplicationInfo<'a> {
             application_version: None,
             engine_name: None,
             engine_version: None,
+            min_api_version: None,
         }
     }
 }
@@ -594,9 +747,24 @@ pub enum InstanceCreationError {
     LayerNotPresent,
     /// One of the requested extensions is missing.
     ExtensionNotPresent,
+    /// The Vulkan loader doesn't support the version requested with
+    /// `ApplicationInfo::min_api_version`.
+    UnsupportedApiVersion {
+        /// The version that was requested.
+        requested: Version,
+        /// The highest version supported by the loader.
+        supported: Version,
+    },
+    /// Some of the requested layers are not available. Contains their names.
+    MissingLayers(Vec<String>),
+    /// Some of the requested extensions are supported neither by the driver nor by the requested
+    /// layers. Contains their names.
+    MissingExtensions(Vec<String>),
     /// The version requested is not supported by the implementation.
     // TODO: more info about this once the question of the version has been resolved
     IncompatibleDriver,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for InstanceCreationError {
@@ -608,7 +776,18 @@ impl error::Error for InstanceCreationError {
             InstanceCreationError::InitializationFailed => "initialization failed",
             InstanceCreationError::LayerNotPresent => "layer not present",
             InstanceCreationError::ExtensionNotPresent => "extension not present",
+            InstanceCreationError::UnsupportedApiVersion { .. } => {
+                "the requested version of Vulkan is not supported by the loader"
+            },
+            InstanceCreationError::MissingLayers(_) => {
+                "some of the requested layers are missing"
+            },
+            InstanceCreationError::MissingExtensions(_) => {
+                "some of the requested extensions are missing"
+            },
             InstanceCreationError::IncompatibleDriver => "incompatible driver",
+            InstanceCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -625,7 +804,20 @@ impl error::Error for InstanceCreationError {
 impl fmt::Display for InstanceCreationError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
+        match *self {
+            InstanceCreationError::UnsupportedApiVersion { requested, supported } => {
+                write!(fmt,
+                       "{} (requested {}, supported {})",
+                       error::Error::description(self),
+                       requested,
+                       supported)
+            },
+            InstanceCreationError::MissingLayers(ref names) |
+            InstanceCreationError::MissingExtensions(ref names) => {
+                write!(fmt, "{}: {}", error::Error::description(self), names.join(", "))
+            },
+            _ => write!(fmt, "{}", error::Error::description(self)),
+        }
     }
 }
 
@@ -643,6 +835,78 @@ impl From<LoadingError> for InstanceCreationError {
     }
 }
 
+/// Error that can happen when querying the version of Vulkan supported by the loader.
+#[derive(Clone, Debug)]
+pub enum SupportedApiVersionError {
+    /// Failed to load the Vulkan shared library.
+    LoadingError(LoadingError),
+    /// Not enough memory.
+    OomError(OomError),
+}
+
+impl error::Error for SupportedApiVersionError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            SupportedApiVersionError::LoadingError(_) => {
+                "failed to load the Vulkan shared library"
+            },
+            SupportedApiVersionError::OomError(_) => "not enough memory available",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            SupportedApiVersionError::LoadingError(ref err) => Some(err),
+            SupportedApiVersionError::OomError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for SupportedApiVersionError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<LoadingError> for SupportedApiVersionError {
+    #[inline]
+    fn from(err: LoadingError) -> SupportedApiVersionError {
+        SupportedApiVersionError::LoadingError(err)
+    }
+}
+
+impl From<OomError> for SupportedApiVersionError {
+    #[inline]
+    fn from(err: OomError) -> SupportedApiVersionError {
+        SupportedApiVersionError::OomError(err)
+    }
+}
+
+impl From<LayersListError> for InstanceCreationError {
+    #[inline]
+    fn from(err: LayersListError) -> InstanceCreationError {
+        match err {
+            LayersListError::LoadingError(err) => InstanceCreationError::LoadingError(err),
+            LayersListError::OomError(err) => InstanceCreationError::OomError(err),
+        }
+    }
+}
+
+impl From<SupportedExtensionsError> for InstanceCreationError {
+    #[inline]
+    fn from(err: SupportedExtensionsError) -> InstanceCreationError {
+        match err {
+            SupportedExtensionsError::LoadingError(err) => {
+                InstanceCreationError::LoadingError(err)
+            },
+            SupportedExtensionsError::OomError(err) => InstanceCreationError::OomError(err),
+        }
+    }
+}
+
 impl From<Error> for InstanceCreationError {
     #[inline]
     fn from(err: Error) -> InstanceCreationError {
@@ -653,7 +917,7 @@ impl From<Error> for InstanceCreationError {
             Error::LayerNotPresent => InstanceCreationError::LayerNotPresent,
             Error::ExtensionNotPresent => InstanceCreationError::ExtensionNotPresent,
             Error::IncompatibleDriver => InstanceCreationError::IncompatibleDriver,
-            _ => panic!("unexpected error: {:?}", err),
+            err => InstanceCreationError::UnexpectedResult(err.result()),
         }
     }
 }
@@ -846,6 +1110,24 @@ impl<'a> PhysicalDevice<'a> {
         }
     }
 
+    /// Returns a queue family dedicated to transfer operations, or `None` if there is none.
+    ///
+    /// See `QueueFamily::is_dedicated_transfer`. Uploading data with a queue of this family lets
+    /// the graphics queues keep working during the upload.
+    #[inline]
+    pub fn dedicated_transfer_queue_family(&self) -> Option<QueueFamily<'a>> {
+        self.queue_families().find(|q| q.is_dedicated_transfer())
+    }
+
+    /// Returns a queue family that supports compute operations but not graphics operations, or
+    /// `None` if there is none.
+    ///
+    /// See `QueueFamily::is_async_compute`.
+    #[inline]
+    pub fn async_compute_queue_family(&self) -> Option<QueueFamily<'a>> {
+        self.queue_families().find(|q| q.is_async_compute())
+    }
+
     /// Builds an iterator that enumerates all the memory types on this physical device.
     #[inline]
     pub fn memory_types(&self) -> MemoryTypesIter<'a> {
@@ -901,6 +1183,88 @@ impl<'a> PhysicalDevice<'a> {
         Limits::from_vk_limits(&self.infos().properties.limits)
     }
 
+    /// Calls `vkGetPhysicalDeviceProperties2` with `next` as the `pNext` chain, in order to fill
+    /// the extension-specific property structs of the chain.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    /// # Safety
+    ///
+    /// - `next` must be null or point to a valid chain of property structs.
+    ///
+    pub(crate) unsafe fn query_properties2(&self, next: *mut ::std::os::raw::c_void) {
+        let vk_i = self.instance.pointers();
+
+        let query = if self.instance.api_version() >= Version::V1_1 {
+            vk_i.GetPhysicalDeviceProperties2
+        } else {
+            assert!(self.instance.loaded_extensions().khr_get_physical_device_properties2,
+                    "Querying extension properties requires Vulkan 1.1 or the \
+                     `khr_get_physical_device_properties2` extension");
+            vk_i.GetPhysicalDeviceProperties2KHR
+        };
+
+        let mut output = vk::PhysicalDeviceProperties2KHR {
+            sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2_KHR,
+            pNext: next as *const _,
+            properties: mem::uninitialized(),
+        };
+        query(self.internal_object(), &mut output);
+    }
+
+    /// Calls `vkGetPhysicalDeviceFeatures2` with `next` as the `pNext` chain, in order to fill
+    /// the extension-specific feature structs of the chain.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    /// # Safety
+    ///
+    /// - `next` must be null or point to a valid chain of feature structs.
+    ///
+    pub(crate) unsafe fn query_features2(&self, next: *mut ::std::os::raw::c_void) {
+        let vk_i = self.instance.pointers();
+
+        let query = if self.instance.api_version() >= Version::V1_1 {
+            vk_i.GetPhysicalDeviceFeatures2
+        } else {
+            assert!(self.instance.loaded_extensions().khr_get_physical_device_properties2,
+                    "Querying extension features requires Vulkan 1.1 or the \
+                     `khr_get_physical_device_properties2` extension");
+            vk_i.GetPhysicalDeviceFeatures2KHR
+        };
+
+        let mut output = vk::PhysicalDeviceFeatures2KHR {
+            sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2_KHR,
+            pNext: next as *const _,
+            features: mem::uninitialized(),
+        };
+        query(self.internal_object(), &mut output);
+    }
+
+    /// Queries the features that the physical device supports for `format`.
+    ///
+    /// This can be used to check whether a format can be sampled with linear filtering, used as
+    /// a storage image, or used as an attachment before creating a resource with it.
+    pub fn format_properties(&self, format: Format) -> FormatProperties {
+        let vk_i = self.instance.pointers();
+
+        let props = unsafe {
+            let mut output = mem::uninitialized();
+            vk_i.GetPhysicalDeviceFormatProperties(self.internal_object(),
+                                                   format as u32,
+                                                   &mut output);
+            output
+        };
+
+        FormatProperties::from_vulkan_properties(props)
+    }
+
     /// Returns an opaque number representing the version of the driver of this device.
     ///
     /// The meaning of this number is implementation-specific. It can be used in bug reports, for
@@ -1062,6 +1426,34 @@ impl<'a> QueueFamily<'a> {
         (self.flags() & vk::QUEUE_SPARSE_BINDING_BIT) != 0
     }
 
+    /// Returns true if queues of this family can execute transfer operations, but neither graphics
+    /// nor compute operations.
+    ///
+    /// Such families usually correspond to the DMA engines of the hardware, which can copy data
+    /// while the other queues keep working.
+    #[inline]
+    pub fn is_dedicated_transfer(&self) -> bool {
+        self.supports_transfers() && !self.supports_graphics() && !self.supports_compute()
+    }
+
+    /// Returns true if queues of this family can execute compute operations but not graphics
+    /// operations.
+    ///
+    /// Work submitted to such families can run concurrently with the work of graphics queues.
+    #[inline]
+    pub fn is_async_compute(&self) -> bool {
+        self.supports_compute() && !self.supports_graphics()
+    }
+
+    /// Returns the number of meaningful bits in the timestamps written by queues of this family,
+    /// or `None` if they don't support timestamps.
+    #[inline]
+    pub fn timestamp_valid_bits(&self) -> Option<u32> {
+        let bits = self.physical_device.infos().queue_families[self.id as usize]
+            .timestampValidBits;
+        if bits == 0 { None } else { Some(bits) }
+    }
+
     /// Internal utility function that returns the flags of this queue family.
     #[inline]
     fn flags(&self) -> u32 {
@@ -1292,13 +1684,98 @@ impl<'a> ExactSizeIterator for MemoryHeapsIter<'a> {
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
+    use format::Format;
     use instance;
+    use std::ffi::CString;
 
     #[test]
     fn create_instance() {
         let _ = instance!();
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let instance = instance!();
+
+        let borrowed = unsafe {
+            instance::Instance::from_raw(instance.internal_object(),
+                                         instance.api_version(),
+                                         &instance::InstanceExtensions::none(),
+                                         None,
+                                         false)
+                .unwrap()
+        };
+        assert_eq!(borrowed.internal_object(), instance.internal_object());
+        assert_eq!(instance::PhysicalDevice::enumerate(&borrowed).count(),
+                   instance::PhysicalDevice::enumerate(&instance).count());
+
+        // The instance must still be usable after the borrowed object is dropped.
+        drop(borrowed);
+        let _ = instance::PhysicalDevice::enumerate(&instance).count();
+    }
+
+    #[test]
+    fn missing_layer() {
+        let layers = ["VK_LAYER_VULKANO_nonexistent"];
+        match instance::Instance::new(None, &instance::InstanceExtensions::none(), &layers) {
+            Err(instance::InstanceCreationError::MissingLayers(names)) => {
+                assert_eq!(names, vec!["VK_LAYER_VULKANO_nonexistent".to_owned()]);
+            },
+            Err(instance::InstanceCreationError::LoadingError(_)) => return,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_extension() {
+        let extensions = instance::RawInstanceExtensions::new(vec![
+            CString::new("VK_VULKANO_nonexistent").unwrap(),
+        ]);
+        match instance::Instance::new(None, extensions, None) {
+            Err(instance::InstanceCreationError::MissingExtensions(names)) => {
+                assert_eq!(names, vec!["VK_VULKANO_nonexistent".to_owned()]);
+            },
+            Err(instance::InstanceCreationError::LoadingError(_)) => return,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn unsupported_api_version() {
+        let infos = instance::ApplicationInfo {
+            min_api_version: Some(instance::Version {
+                                      major: 1,
+                                      minor: 1000,
+                                      patch: 0,
+                                  }),
+            ..Default::default()
+        };
+
+        let extensions = instance::InstanceExtensions::none();
+        match instance::Instance::new(Some(&infos), &extensions, None) {
+            Err(instance::InstanceCreationError::UnsupportedApiVersion { requested, .. }) => {
+                assert_eq!(requested.minor, 1000);
+            },
+            Err(instance::InstanceCreationError::LoadingError(_)) => return,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn api_version() {
+        let instance = instance!();
+        let supported = instance::Instance::supported_api_version().unwrap();
+        assert!(instance.api_version() >= instance::Version::V1_0);
+        assert!(instance.api_version() <= supported);
+    }
+
+    #[test]
+    fn loaded_raw_extensions() {
+        let instance = instance!();
+        assert!(instance.loaded_raw_extensions().iter().next().is_none());
+    }
+
     #[test]
     fn queue_family_by_id() {
         let instance = instance!();
@@ -1316,4 +1793,23 @@ mod tests {
         let by_id = phys.queue_family_by_id(queue_family.id()).unwrap();
         assert_eq!(by_id.id(), queue_family.id());
     }
+
+    #[test]
+    fn format_properties() {
+        let instance = instance!();
+
+        let phys = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        // These features are required by the specs for this format.
+        let properties = phys.format_properties(Format::R8G8B8A8Unorm);
+        assert!(!properties.is_unsupported());
+        assert!(properties.optimal_tiling_features.sampled_image);
+        assert!(properties.optimal_tiling_features.sampled_image_filter_linear);
+        assert!(properties.optimal_tiling_features.color_attachment);
+        assert!(properties.optimal_tiling_features.blit_src);
+        assert!(properties.buffer_features.vertex_buffer);
+    }
 }
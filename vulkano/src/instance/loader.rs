@@ -20,6 +20,27 @@
 //!
 //! By default vulkano will use the `auto_loader()` function, which tries to automatically load
 //! a Vulkan implementation from the system.
+//!
+//! The Vulkan library is loaded at runtime, so that vulkano doesn't require Vulkan to be installed
+//! in order to start the application. If no Vulkan implementation can be found, `auto_loader()`
+//! and the functions that use it (such as `Instance::new`) return a `LoadingError`, which lets the
+//! application fall back to another rendering backend:
+//!
+//! ```no_run
+//! use vulkano::instance::Instance;
+//! use vulkano::instance::InstanceCreationError;
+//! use vulkano::instance::InstanceExtensions;
+//!
+//! match Instance::new(None, &InstanceExtensions::none(), None) {
+//!     Ok(instance) => {
+//!         // Render with Vulkan.
+//!     },
+//!     Err(InstanceCreationError::LoadingError(err)) => {
+//!         println!("Vulkan isn't available ({}), falling back to another backend", err);
+//!     },
+//!     Err(err) => panic!("{}", err),
+//! }
+//! ```
 
 use shared_library;
 use std::error;
@@ -30,7 +51,10 @@ use std::os::raw::c_char;
 use std::os::raw::c_void;
 use std::path::Path;
 
+use OomError;
 use SafeDeref;
+use check_errors;
+use version::Version;
 use vk;
 
 /// Implemented on objects that grant access to a Vulkan implementation.
@@ -132,6 +156,28 @@ impl<L> FunctionPointers<L> {
     {
         self.loader.get_instance_proc_addr(instance, name)
     }
+
+    /// Returns the highest version of Vulkan that instances created with this loader can use.
+    ///
+    /// Loaders that predate Vulkan 1.1 don't provide `vkEnumerateInstanceVersion`, in which case
+    /// version 1.0.0 is returned.
+    pub fn api_version(&self) -> Result<Version, OomError>
+        where L: Loader
+    {
+        unsafe {
+            let name = b"vkEnumerateInstanceVersion\0";
+            let ptr: *const c_void =
+                mem::transmute(self.get_instance_proc_addr(0, name.as_ptr() as *const c_char));
+            if ptr.is_null() {
+                return Ok(Version::V1_0);
+            }
+
+            let enumerate: extern "system" fn(*mut u32) -> vk::Result = mem::transmute(ptr);
+            let mut version = 0;
+            check_errors(enumerate(&mut version))?;
+            Ok(Version::from_vulkan_version(version))
+        }
+    }
 }
 
 /// Expression that returns a loader that assumes that Vulkan is linked to the executable you're
@@ -168,40 +214,86 @@ macro_rules! statically_linked_vulkan_loader {
 /// This function tries to auto-guess where to find the Vulkan implementation, and loads it in a
 /// `lazy_static!`. The content of the lazy_static is then returned, or an error if we failed to
 /// load Vulkan.
+///
+/// The following libraries are tried in order, and the first one that can be loaded is used:
+///
+/// - On Windows, `vulkan-1.dll`.
+/// - On Linux and the other Unix systems, `libvulkan.so.1` then `libvulkan.so`.
+/// - On Android, `libvulkan.so`.
+/// - On macOS, `libvulkan.1.dylib`, `libvulkan.dylib` then `libMoltenVK.dylib`, and finally the
+///   `MoltenVK` framework.
+///
+/// On iOS, Vulkan is assumed to be statically linked to the application.
+///
+/// An error is returned if none of the libraries can be loaded, or if the library doesn't provide
+/// `vkCreateInstance`. This function never panics in this situation.
 pub fn auto_loader(
     )
     -> Result<&'static FunctionPointers<Box<Loader + Send + Sync>>, LoadingError>
 {
-    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    #[cfg(target_os = "ios")]
     #[allow(non_snake_case)]
     fn def_loader_impl() -> Result<Box<Loader + Send + Sync>, LoadingError> {
         let loader = statically_linked_vulkan_loader!();
         Ok(Box::new(loader))
     }
 
-    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    #[cfg(not(target_os = "ios"))]
     fn def_loader_impl() -> Result<Box<Loader + Send + Sync>, LoadingError> {
         #[cfg(windows)]
-        fn get_path() -> &'static Path {
-            Path::new("vulkan-1.dll")
+        fn get_paths() -> &'static [&'static str] {
+            &["vulkan-1.dll"]
         }
         #[cfg(all(unix, not(target_os = "android"), not(target_os = "macos")))]
-        fn get_path() -> &'static Path {
-            Path::new("libvulkan.so.1")
+        fn get_paths() -> &'static [&'static str] {
+            &["libvulkan.so.1", "libvulkan.so"]
         }
         #[cfg(target_os = "android")]
-        fn get_path() -> &'static Path {
-            Path::new("libvulkan.so")
+        fn get_paths() -> &'static [&'static str] {
+            &["libvulkan.so"]
+        }
+        #[cfg(target_os = "macos")]
+        fn get_paths() -> &'static [&'static str] {
+            &[
+                "libvulkan.1.dylib",
+                "libvulkan.dylib",
+                "libMoltenVK.dylib",
+                "MoltenVK.framework/MoltenVK",
+                "/Library/Frameworks/MoltenVK.framework/MoltenVK",
+            ]
         }
 
-        let loader = unsafe { DynamicLibraryLoader::new(get_path())? };
+        let mut last_err = None;
+        for path in get_paths() {
+            match unsafe { DynamicLibraryLoader::new(path) } {
+                Ok(loader) => return Ok(Box::new(loader)),
+                Err(err) => last_err = Some(err),
+            }
+        }
 
-        Ok(Box::new(loader))
+        Err(last_err.unwrap())
+    }
+
+    fn load() -> Result<FunctionPointers<Box<Loader + Send + Sync>>, LoadingError> {
+        let ptrs = FunctionPointers::new(def_loader_impl()?);
+
+        // Some systems ship a library with the right name that isn't a working Vulkan loader. We
+        // check for `vkCreateInstance` now, otherwise its absence would only be noticed when
+        // creating the instance, by calling the panicking placeholder of `vk::EntryPoints`.
+        let create_instance: *const c_void = unsafe {
+            mem::transmute(ptrs.get_instance_proc_addr(0, b"vkCreateInstance\0".as_ptr() as
+                                                           *const c_char))
+        };
+        if create_instance.is_null() {
+            return Err(LoadingError::MissingEntryPoint("vkCreateInstance".to_owned()));
+        }
+
+        Ok(ptrs)
     }
 
     lazy_static! {
         static ref DEFAULT_LOADER: Result<FunctionPointers<Box<Loader + Send + Sync>>, LoadingError> = {
-            def_loader_impl().map(FunctionPointers::new)
+            load()
         };
     }
 
@@ -255,6 +347,21 @@ impl fmt::Display for LoadingError {
 mod tests {
     use instance::loader::DynamicLibraryLoader;
     use instance::loader::LoadingError;
+    use instance::loader::auto_loader;
+
+    #[test]
+    fn auto_loader_is_consistent() {
+        // Whether Vulkan is installed or not, the result must be the same on each call and
+        // nothing must panic.
+        match (auto_loader(), auto_loader()) {
+            (Ok(first), Ok(second)) => {
+                assert!(first as *const _ == second as *const _);
+                first.api_version().unwrap();
+            },
+            (Err(_), Err(_)) => (),
+            _ => panic!(),
+        }
+    }
 
     #[test]
     fn dl_open_error() {
@@ -98,6 +98,10 @@
 //! After you have created an instance, the next step is usually to enumerate the physical devices
 //! that are available on the system with `PhysicalDevice::enumerate()` (see above).
 //!
+//! Alternatively, a `PhysicalDeviceSelector` can choose a physical device for you. It rejects the
+//! devices that don't support the features, extensions or queue families that you require, and
+//! picks the best one among the others, preferring discrete GPUs.
+//!
 //! When choosing which physical device to use, keep in mind that physical devices may or may not
 //! be able to draw to a certain surface (ie. to a window or a monitor), or may even not be able
 //! to draw at all. See the `swapchain` module for more information about surfaces.
@@ -106,10 +110,13 @@
 //! `device` module for more info.
 //!
 
+pub use self::device_group::PhysicalDeviceGroup;
+pub use self::device_group::PhysicalDeviceGroupIter;
 pub use self::extensions::DeviceExtensions;
 pub use self::extensions::InstanceExtensions;
 pub use self::extensions::RawDeviceExtensions;
 pub use self::extensions::RawInstanceExtensions;
+pub use self::extensions::SupportedExtensionsError;
 pub use self::instance::ApplicationInfo;
 pub use self::instance::Instance;
 pub use self::instance::InstanceCreationError;
@@ -122,19 +129,24 @@ pub use self::instance::PhysicalDeviceType;
 pub use self::instance::PhysicalDevicesIter;
 pub use self::instance::QueueFamiliesIter;
 pub use self::instance::QueueFamily;
+pub use self::instance::SupportedApiVersionError;
 pub use self::layers::LayerProperties;
 pub use self::layers::LayersIterator;
 pub use self::layers::LayersListError;
 pub use self::layers::layers_list;
 pub use self::limits::Limits;
 pub use self::loader::LoadingError;
+pub use self::selection::PhysicalDeviceSelector;
 pub use features::Features;
+pub use features::FeaturesBuilder;
 pub use version::Version;
 
 pub mod debug;
 pub mod loader;
 
+mod device_group;
 mod extensions;
 mod instance;
 mod layers;
 mod limits;
+mod selection;
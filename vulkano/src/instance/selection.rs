@@ -0,0 +1,244 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+
+use instance::DeviceExtensions;
+use instance::Features;
+use instance::Instance;
+use instance::PhysicalDevice;
+use instance::PhysicalDeviceType;
+use instance::QueueFamily;
+use swapchain::Surface;
+
+/// Helper that chooses the most appropriate physical device of an instance.
+///
+/// A physical device is suitable if it supports all the required features and extensions, and if
+/// each queue family requirement is fulfilled by at least one of its queue families. Among the
+/// suitable devices, the one with the highest `score` is chosen.
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::instance::Instance;
+/// # use vulkano::swapchain::Surface;
+/// use vulkano::instance::DeviceExtensions;
+/// use vulkano::instance::PhysicalDeviceSelector;
+///
+/// # let instance: Arc<Instance> = return;
+/// # let surface: Arc<Surface<()>> = return;
+/// let extensions = DeviceExtensions {
+///     khr_swapchain: true,
+///     .. DeviceExtensions::none()
+/// };
+///
+/// let physical = PhysicalDeviceSelector::new()
+///     .extensions(&extensions)
+///     .surface(&surface)
+///     .select(&instance)
+///     .expect("no suitable physical device");
+/// ```
+pub struct PhysicalDeviceSelector<'a> {
+    features: Features,
+    extensions: DeviceExtensions,
+    queue_families: Vec<Box<Fn(QueueFamily) -> bool + 'a>>,
+}
+
+impl<'a> PhysicalDeviceSelector<'a> {
+    /// Builds a selector that accepts all the physical devices.
+    #[inline]
+    pub fn new() -> PhysicalDeviceSelector<'a> {
+        PhysicalDeviceSelector {
+            features: Features::none(),
+            extensions: DeviceExtensions::none(),
+            queue_families: Vec::new(),
+        }
+    }
+
+    /// Requires the physical device to support all the given features.
+    #[inline]
+    pub fn features(mut self, features: &Features) -> PhysicalDeviceSelector<'a> {
+        self.features = features.clone();
+        self
+    }
+
+    /// Requires the physical device to support all the given extensions.
+    #[inline]
+    pub fn extensions(mut self, extensions: &DeviceExtensions) -> PhysicalDeviceSelector<'a> {
+        self.extensions = *extensions;
+        self
+    }
+
+    /// Requires the physical device to have a queue family that supports graphics operations.
+    #[inline]
+    pub fn graphics(self) -> PhysicalDeviceSelector<'a> {
+        self.queue_family(|q| q.supports_graphics())
+    }
+
+    /// Requires the physical device to have a queue family that supports compute operations.
+    #[inline]
+    pub fn compute(self) -> PhysicalDeviceSelector<'a> {
+        self.queue_family(|q| q.supports_compute())
+    }
+
+    /// Requires the physical device to have a queue family that supports graphics operations and
+    /// that can present images to `surface`.
+    ///
+    /// Queue families for which the support can't be determined are considered as not
+    /// supporting the surface.
+    #[inline]
+    pub fn surface<W>(self, surface: &'a Surface<W>) -> PhysicalDeviceSelector<'a>
+        where W: 'a
+    {
+        self.queue_family(move |q| {
+            q.supports_graphics() && surface.is_supported(q).unwrap_or(false)
+        })
+    }
+
+    /// Requires the physical device to have a queue family for which `filter` returns true.
+    ///
+    /// Each call adds a requirement. The requirements don't need to be fulfilled by the same
+    /// queue family.
+    #[inline]
+    pub fn queue_family<F>(mut self, filter: F) -> PhysicalDeviceSelector<'a>
+        where F: Fn(QueueFamily) -> bool + 'a
+    {
+        self.queue_families.push(Box::new(filter));
+        self
+    }
+
+    /// Returns true if `device` fulfills all the requirements of the selector.
+    pub fn is_suitable(&self, device: PhysicalDevice) -> bool {
+        if !device.supported_features().superset_of(&self.features) {
+            return false;
+        }
+
+        let supported_extensions = DeviceExtensions::supported_by_device(device);
+        if self.extensions.difference(&supported_extensions) != DeviceExtensions::none() {
+            return false;
+        }
+
+        self.queue_families
+            .iter()
+            .all(|filter| device.queue_families().any(|q| filter(q)))
+    }
+
+    /// Returns the score of `device`, or `None` if it isn't suitable.
+    ///
+    /// Discrete GPUs are preferred over integrated GPUs, which are preferred over virtual GPUs,
+    /// which are preferred over CPUs. Devices of the same type are ranked by the size of their
+    /// device-local memory.
+    pub fn score(&self, device: PhysicalDevice) -> Option<u64> {
+        if !self.is_suitable(device) {
+            return None;
+        }
+
+        let type_score = match device.ty() {
+            PhysicalDeviceType::DiscreteGpu => 4,
+            PhysicalDeviceType::IntegratedGpu => 3,
+            PhysicalDeviceType::VirtualGpu => 2,
+            PhysicalDeviceType::Cpu => 1,
+            PhysicalDeviceType::Other => 0,
+        };
+
+        // The memory is counted in megabytes, so that it can never overflow into the type score.
+        let local_memory: u64 = device
+            .memory_heaps()
+            .filter(|heap| heap.is_device_local())
+            .map(|heap| heap.size() as u64 >> 20)
+            .sum();
+
+        Some((type_score << 48) | local_memory.min((1 << 48) - 1))
+    }
+
+    /// Returns the suitable physical device of `instance` with the highest score, or `None` if
+    /// no device is suitable.
+    ///
+    /// If multiple devices have the same score, the one that was enumerated first is returned.
+    pub fn select<'i>(&self, instance: &'i Arc<Instance>) -> Option<PhysicalDevice<'i>> {
+        let mut best: Option<(PhysicalDevice<'i>, u64)> = None;
+
+        for device in PhysicalDevice::enumerate(instance) {
+            let score = match self.score(device) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            match best {
+                Some((_, best_score)) if best_score >= score => (),
+                _ => best = Some((device, score)),
+            }
+        }
+
+        best.map(|(device, _)| device)
+    }
+}
+
+impl<'a> Default for PhysicalDeviceSelector<'a> {
+    #[inline]
+    fn default() -> PhysicalDeviceSelector<'a> {
+        PhysicalDeviceSelector::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instance::Features;
+    use instance::PhysicalDevice;
+    use instance::PhysicalDeviceSelector;
+
+    #[test]
+    fn accepts_any_device() {
+        let instance = instance!();
+
+        let selected = PhysicalDeviceSelector::new().select(&instance);
+        assert_eq!(selected.is_some(), PhysicalDevice::enumerate(&instance).len() != 0);
+    }
+
+    #[test]
+    fn selects_suitable_device() {
+        let instance = instance!();
+
+        let selector = PhysicalDeviceSelector::new().graphics();
+        match selector.select(&instance) {
+            Some(device) => {
+                assert!(device.queue_families().any(|q| q.supports_graphics()));
+                let best = selector.score(device).unwrap();
+                for other in PhysicalDevice::enumerate(&instance) {
+                    assert!(selector.score(other).map(|s| s <= best).unwrap_or(true));
+                }
+            },
+            None => {
+                assert!(PhysicalDevice::enumerate(&instance)
+                            .all(|p| !p.queue_families().any(|q| q.supports_graphics())));
+            },
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_features() {
+        let instance = instance!();
+
+        for device in PhysicalDevice::enumerate(&instance) {
+            // Only request the features that this device lacks.
+            let missing = Features::all().difference(device.supported_features());
+            if missing == Features::none() {
+                continue;
+            }
+
+            let selector = PhysicalDeviceSelector::new().features(&missing);
+            assert!(!selector.is_suitable(device));
+            assert_eq!(selector.score(device), None);
+            if let Some(selected) = selector.select(&instance) {
+                assert!(selected.index() != device.index());
+            }
+        }
+    }
+}
@@ -56,6 +56,18 @@
 //!   `GpuFuture`s allow you to chain multiple submissions together and are essential to performing
 //!   multiple operations on multiple different GPU queues.
 //!
+//! # Thread safety
+//!
+//! Almost all the types of vulkano implement `Send` and `Sync`, and can be shared between threads
+//! with an `Arc`. When the Vulkan specification requires an object to be externally
+//! synchronized, vulkano either takes `&mut self`, or locks the object internally. For example
+//! submissions and presents lock the `Queue`, acquiring and presenting lock the `Swapchain`, and
+//! the standard descriptor and command pools lock themselves or use one pool per thread.
+//!
+//! The few exceptions are documented. `UnsafeCommandPool` is `Send` but not `Sync`, and the
+//! `unsafe` functions that bypass the internal locks state their requirements in their
+//! documentation.
+//!
 
 //#![warn(missing_docs)]        // TODO: activate
 #![allow(dead_code)] // TODO: remove
@@ -63,19 +75,64 @@
 
 extern crate crossbeam;
 extern crate fnv;
+#[cfg(feature = "runtime-shader-compilation")]
+extern crate glsl_to_spirv;
 #[macro_use]
 extern crate lazy_static;
+#[cfg(feature = "log")]
+#[macro_use]
+extern crate log;
 extern crate shared_library;
 extern crate smallvec;
-extern crate vk_sys as vk;
+pub extern crate vk_sys as vk;
 pub extern crate half;
 
+// Implements `Display`, `From<OomError>` and `From<Error>` for the error type of a function that
+// calls Vulkan.
+//
+// `OomError`s are converted into the variant passed after the name of the type, and the memory
+// errors returned by Vulkan are converted as `OomError`s. The error codes listed between the
+// braces are converted into the given variants, and the other codes into the `UnexpectedResult`
+// variant, which keeps the original code.
+macro_rules! vk_error_conversions {
+    ($name:ident, $oom:ident { $($code:ident => $variant:ident,)* }) => (
+        impl ::std::fmt::Display for $name {
+            #[inline]
+            fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> Result<(), ::std::fmt::Error> {
+                write!(fmt, "{}", ::std::error::Error::description(self))
+            }
+        }
+
+        impl From<::OomError> for $name {
+            #[inline]
+            fn from(err: ::OomError) -> $name {
+                $name::$oom(From::from(err))
+            }
+        }
+
+        impl From<::Error> for $name {
+            #[inline]
+            fn from(err: ::Error) -> $name {
+                match err {
+                    err @ ::Error::OutOfHostMemory |
+                    err @ ::Error::OutOfDeviceMemory => ::OomError::from(err).into(),
+                    $(
+                        ::Error::$code => $name::$variant,
+                    )*
+                    err => $name::UnexpectedResult(err.result()),
+                }
+            }
+        }
+    );
+}
+
 #[macro_use]
 mod tests;
 
 mod features;
 mod version;
 
+pub mod acceleration_structure;
 pub mod buffer;
 pub mod command_buffer;
 pub mod descriptor;
@@ -142,7 +199,12 @@ pub unsafe trait SynchronizedVulkanObject {
     fn internal_object_guard(&self) -> MutexGuard<Self::Object>;
 }
 
-/// Error type returned by most Vulkan functions.
+/// Error type returned by the Vulkan functions that can only fail by running out of memory.
+///
+/// Functions that can fail for other reasons, for example because the device has been lost,
+/// return an error type of their own that wraps `OomError`. This is also the case of all the
+/// functions that create Vulkan objects, whose errors keep the raw result code if the
+/// implementation returns a code that vulkano doesn't expect.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum OomError {
     /// There is no memory available on the host (ie. the CPU, RAM, etc.).
@@ -194,29 +256,66 @@ enum Success {
 
 /// All possible errors returned by any Vulkan function.
 ///
-/// This type is not public. Instead all public error types should implement `From<Error>` and
-/// panic for error code that arent supposed to happen.
+/// This type is not public. Instead all public error types should implement `From<Error>`. The
+/// errors of creation functions keep the codes that aren't supposed to happen with `result()`,
+/// while `OomError` panics on them.
 #[derive(Debug, Copy, Clone)]
-#[repr(u32)]
 // TODO: being pub is necessary because of the weird visibility rules in rustc
 pub(crate) enum Error {
-    OutOfHostMemory = vk::ERROR_OUT_OF_HOST_MEMORY,
-    OutOfDeviceMemory = vk::ERROR_OUT_OF_DEVICE_MEMORY,
-    InitializationFailed = vk::ERROR_INITIALIZATION_FAILED,
-    DeviceLost = vk::ERROR_DEVICE_LOST,
-    MemoryMapFailed = vk::ERROR_MEMORY_MAP_FAILED,
-    LayerNotPresent = vk::ERROR_LAYER_NOT_PRESENT,
-    ExtensionNotPresent = vk::ERROR_EXTENSION_NOT_PRESENT,
-    FeatureNotPresent = vk::ERROR_FEATURE_NOT_PRESENT,
-    IncompatibleDriver = vk::ERROR_INCOMPATIBLE_DRIVER,
-    TooManyObjects = vk::ERROR_TOO_MANY_OBJECTS,
-    FormatNotSupported = vk::ERROR_FORMAT_NOT_SUPPORTED,
-    SurfaceLost = vk::ERROR_SURFACE_LOST_KHR,
-    NativeWindowInUse = vk::ERROR_NATIVE_WINDOW_IN_USE_KHR,
-    OutOfDate = vk::ERROR_OUT_OF_DATE_KHR,
-    IncompatibleDisplay = vk::ERROR_INCOMPATIBLE_DISPLAY_KHR,
-    ValidationFailed = vk::ERROR_VALIDATION_FAILED_EXT,
-    OutOfPoolMemory = vk::ERROR_OUT_OF_POOL_MEMORY_KHR,
+    OutOfHostMemory,
+    OutOfDeviceMemory,
+    InitializationFailed,
+    DeviceLost,
+    MemoryMapFailed,
+    LayerNotPresent,
+    ExtensionNotPresent,
+    FeatureNotPresent,
+    IncompatibleDriver,
+    TooManyObjects,
+    FormatNotSupported,
+    SurfaceLost,
+    NativeWindowInUse,
+    OutOfDate,
+    IncompatibleDisplay,
+    ValidationFailed,
+    OutOfPoolMemory,
+    FullscreenExclusiveLost,
+    InvalidExternalHandle,
+    InvalidShader,
+    Fragmentation,
+    /// A code that vulkano doesn't know, for example one that was added by a newer version of
+    /// Vulkan or by an extension.
+    Unknown(vk::Result),
+}
+
+impl Error {
+    /// Returns the Vulkan code of the error.
+    pub(crate) fn result(&self) -> vk::Result {
+        match *self {
+            Error::OutOfHostMemory => vk::ERROR_OUT_OF_HOST_MEMORY,
+            Error::OutOfDeviceMemory => vk::ERROR_OUT_OF_DEVICE_MEMORY,
+            Error::InitializationFailed => vk::ERROR_INITIALIZATION_FAILED,
+            Error::DeviceLost => vk::ERROR_DEVICE_LOST,
+            Error::MemoryMapFailed => vk::ERROR_MEMORY_MAP_FAILED,
+            Error::LayerNotPresent => vk::ERROR_LAYER_NOT_PRESENT,
+            Error::ExtensionNotPresent => vk::ERROR_EXTENSION_NOT_PRESENT,
+            Error::FeatureNotPresent => vk::ERROR_FEATURE_NOT_PRESENT,
+            Error::IncompatibleDriver => vk::ERROR_INCOMPATIBLE_DRIVER,
+            Error::TooManyObjects => vk::ERROR_TOO_MANY_OBJECTS,
+            Error::FormatNotSupported => vk::ERROR_FORMAT_NOT_SUPPORTED,
+            Error::SurfaceLost => vk::ERROR_SURFACE_LOST_KHR,
+            Error::NativeWindowInUse => vk::ERROR_NATIVE_WINDOW_IN_USE_KHR,
+            Error::OutOfDate => vk::ERROR_OUT_OF_DATE_KHR,
+            Error::IncompatibleDisplay => vk::ERROR_INCOMPATIBLE_DISPLAY_KHR,
+            Error::ValidationFailed => vk::ERROR_VALIDATION_FAILED_EXT,
+            Error::OutOfPoolMemory => vk::ERROR_OUT_OF_POOL_MEMORY_KHR,
+            Error::FullscreenExclusiveLost => vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
+            Error::InvalidExternalHandle => vk::ERROR_INVALID_EXTERNAL_HANDLE_KHR,
+            Error::InvalidShader => vk::ERROR_INVALID_SHADER_NV,
+            Error::Fragmentation => vk::ERROR_FRAGMENTATION_EXT,
+            Error::Unknown(code) => code,
+        }
+    }
 }
 
 /// Checks whether the result returned correctly.
@@ -246,8 +345,51 @@ fn check_errors(result: vk::Result) -> Result<Success, Error> {
         vk::ERROR_INCOMPATIBLE_DISPLAY_KHR => Err(Error::IncompatibleDisplay),
         vk::ERROR_VALIDATION_FAILED_EXT => Err(Error::ValidationFailed),
         vk::ERROR_OUT_OF_POOL_MEMORY_KHR => Err(Error::OutOfPoolMemory),
-        vk::ERROR_INVALID_SHADER_NV => panic!("Vulkan function returned \
-                                               VK_ERROR_INVALID_SHADER_NV"),
-        c => unreachable!("Unexpected error code returned by Vulkan: {}", c),
+        vk::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => Err(Error::FullscreenExclusiveLost),
+        vk::ERROR_INVALID_EXTERNAL_HANDLE_KHR => Err(Error::InvalidExternalHandle),
+        vk::ERROR_INVALID_SHADER_NV => Err(Error::InvalidShader),
+        vk::ERROR_FRAGMENTATION_EXT => Err(Error::Fragmentation),
+        c => Err(Error::Unknown(c)),
+    }
+}
+
+#[cfg(test)]
+mod thread_safety_tests {
+    fn assert_send_sync<T: Send + Sync>() {
+    }
+
+    fn assert_send<T: Send>() {
+    }
+
+    #[test]
+    fn wrappers_are_send_sync() {
+        assert_send_sync::<::instance::Instance>();
+        assert_send_sync::<::device::Device>();
+        assert_send_sync::<::device::Queue>();
+        assert_send_sync::<::swapchain::Surface<()>>();
+        assert_send_sync::<::swapchain::Swapchain<()>>();
+        assert_send_sync::<::buffer::sys::UnsafeBuffer>();
+        assert_send_sync::<::acceleration_structure::AccelerationStructure>();
+        assert_send_sync::<::image::sys::UnsafeImage>();
+        assert_send_sync::<::image::sys::UnsafeImageView>();
+        assert_send_sync::<::sampler::Sampler>();
+        assert_send_sync::<::sampler::SamplerYcbcrConversion>();
+        assert_send_sync::<::memory::DeviceMemory>();
+        assert_send_sync::<::memory::MappedDeviceMemory>();
+        assert_send_sync::<::memory::pool::StdMemoryPool>();
+        assert_send_sync::<::sync::Fence>();
+        assert_send_sync::<::sync::Semaphore>();
+        assert_send_sync::<::sync::Event>();
+        assert_send_sync::<::sync::TimelineSemaphore>();
+        assert_send_sync::<::pipeline::cache::PipelineCache>();
+        assert_send_sync::<::pipeline::shader::ShaderModule>();
+        assert_send_sync::<::descriptor::descriptor_set::UnsafeDescriptorSetLayout>();
+        assert_send_sync::<::descriptor::descriptor_set::StdDescriptorPool>();
+        assert_send_sync::<::command_buffer::pool::StandardCommandPool>();
+        assert_send_sync::<::command_buffer::AutoCommandBuffer>();
+        assert_send_sync::<::query::UnsafeQueryPool>();
+
+        // Command pools must be externally synchronized and are only `Send`.
+        assert_send::<::command_buffer::pool::UnsafeCommandPool>();
     }
 }
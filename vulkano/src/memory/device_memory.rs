@@ -48,6 +48,8 @@ pub struct DeviceMemory {
     device: Arc<Device>,
     size: usize,
     memory_type_index: u32,
+    // False if the memory was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl DeviceMemory {
@@ -78,6 +80,47 @@ impl DeviceMemory {
     pub fn dedicated_alloc(device: Arc<Device>, memory_type: MemoryType, size: usize,
                            resource: DedicatedAlloc)
                            -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        DeviceMemory::alloc_impl(device, memory_type, size, resource, None)
+    }
+
+    /// Same as `alloc`, but only allocates the memory on the physical devices of the device group
+    /// whose bit is set in `device_mask`.
+    ///
+    /// Bit `n` of the mask corresponds to the physical device at index `n` of the group that was
+    /// passed to `Device::with_group`. If the group doesn't support subset allocations, the mask
+    /// must contain all the devices of the group.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `size` is 0.
+    /// - Panics if `memory_type` doesn't belong to the same physical device as `device`.
+    /// - Panics if the device doesn't support device masks. See `Device::supports_device_masks`.
+    /// - Panics if `device_mask` is 0 or contains bits outside of `0 .. device_group_size()`.
+    ///
+    /// Returns `SubsetAllocationNotSupported` if `device_mask` doesn't contain all the devices
+    /// of the group and the device doesn't support subset allocations.
+    ///
+    #[inline]
+    pub fn alloc_with_device_mask(device: Arc<Device>, memory_type: MemoryType, size: usize,
+                                  device_mask: u32)
+                                  -> Result<DeviceMemory, DeviceMemoryAllocError> {
+        assert!(device.supports_device_masks(),
+                "Device masks require Vulkan 1.1 or the `khr_device_group` extension");
+        assert_ne!(device_mask, 0);
+        assert_eq!((device_mask as u64) >> device.device_group_size(), 0);
+
+        let full_mask = ((1u64 << device.device_group_size()) - 1) as u32;
+        if device_mask != full_mask && !device.supports_subset_allocation() {
+            return Err(DeviceMemoryAllocError::SubsetAllocationNotSupported);
+        }
+
+        DeviceMemory::alloc_impl(device, memory_type, size, DedicatedAlloc::None,
+                                 Some(device_mask))
+    }
+
+    fn alloc_impl(device: Arc<Device>, memory_type: MemoryType, size: usize,
+                  resource: DedicatedAlloc, device_mask: Option<u32>)
+                  -> Result<DeviceMemory, DeviceMemoryAllocError> {
         assert!(size >= 1);
         assert_eq!(device.physical_device().internal_object(),
                    memory_type.physical_device().internal_object());
@@ -98,7 +141,7 @@ impl DeviceMemory {
             let vk = device.pointers();
 
             // Decide whether we are going to pass a `vkMemoryDedicatedAllocateInfoKHR`.
-            let dedicated_alloc_info = if device.loaded_extensions().khr_dedicated_allocation {
+            let dedicated_alloc_info = if device.supports_dedicated_allocation() {
                 match resource {
                     DedicatedAlloc::Buffer(buffer) => {
                         Some(vk::MemoryDedicatedAllocateInfoKHR {
@@ -124,12 +167,41 @@ impl DeviceMemory {
                 None
             };
 
+            // When `VK_KHR_buffer_device_address` is enabled, all the allocations are made with
+            // the `DEVICE_ADDRESS` flag, so that any buffer can be given a device address
+            // regardless of the memory it is bound to.
+            let device_address = device.loaded_extensions().khr_buffer_device_address;
+            let flags_info = if device_mask.is_some() || device_address {
+                let mut flags = 0;
+                if device_mask.is_some() {
+                    flags |= vk::MEMORY_ALLOCATE_DEVICE_MASK_BIT;
+                }
+                if device_address {
+                    flags |= vk::MEMORY_ALLOCATE_DEVICE_ADDRESS_BIT;
+                }
+
+                Some(vk::MemoryAllocateFlagsInfo {
+                         sType: vk::STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO,
+                         pNext: dedicated_alloc_info
+                             .as_ref()
+                             .map(|i| i as *const vk::MemoryDedicatedAllocateInfoKHR)
+                             .unwrap_or(ptr::null()) as *const _,
+                         flags: flags,
+                         deviceMask: device_mask.unwrap_or(0),
+                     })
+            } else {
+                None
+            };
+
             let infos = vk::MemoryAllocateInfo {
                 sType: vk::STRUCTURE_TYPE_MEMORY_ALLOCATE_INFO,
-                pNext: dedicated_alloc_info
+                pNext: flags_info
                     .as_ref()
-                    .map(|i| i as *const vk::MemoryDedicatedAllocateInfoKHR)
-                    .unwrap_or(ptr::null()) as *const _,
+                    .map(|i| i as *const vk::MemoryAllocateFlagsInfo as *const _)
+                    .or(dedicated_alloc_info
+                            .as_ref()
+                            .map(|i| i as *const vk::MemoryDedicatedAllocateInfoKHR as *const _))
+                    .unwrap_or(ptr::null()),
                 allocationSize: size as u64,
                 memoryTypeIndex: memory_type.id(),
             };
@@ -148,9 +220,45 @@ impl DeviceMemory {
                device: device,
                size: size,
                memory_type_index: memory_type.id(),
+               needs_destruction: true,
            })
     }
 
+    /// Builds a `DeviceMemory` from a raw handle that was allocated outside of vulkano.
+    ///
+    /// If `owned` is true, the memory is freed when the returned object is dropped, and it counts
+    /// towards the `max_memory_allocation_count` limit checked by vulkano. Otherwise, the caller
+    /// remains responsible for freeing it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid memory allocation of `device`, of `size` bytes and allocated
+    ///   from `memory_type`.
+    /// - If `owned` is true, the memory must not be freed by anything else.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `memory_type` doesn't belong to the same physical device as `device`.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::DeviceMemory, memory_type: MemoryType,
+                           size: usize, owned: bool)
+                           -> DeviceMemory {
+        assert_eq!(device.physical_device().internal_object(),
+                   memory_type.physical_device().internal_object());
+
+        if owned {
+            *device.allocation_count().lock().expect("Poisoned mutex") += 1;
+        }
+
+        DeviceMemory {
+            memory: handle,
+            device: device,
+            size: size,
+            memory_type_index: memory_type.id(),
+            needs_destruction: owned,
+        }
+    }
+
     /// Allocates a chunk of memory and maps it.
     ///
     /// # Panic
@@ -240,6 +348,10 @@ unsafe impl VulkanObject for DeviceMemory {
 impl Drop for DeviceMemory {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.FreeMemory(self.device.internal_object(), self.memory, ptr::null());
@@ -468,6 +580,11 @@ pub enum DeviceMemoryAllocError {
     TooManyObjects,
     /// Memory map failed.
     MemoryMapFailed,
+    /// The device mask doesn't contain all the devices of the group, and the device doesn't
+    /// support subset allocations.
+    SubsetAllocationNotSupported,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for DeviceMemoryAllocError {
@@ -478,6 +595,11 @@ impl error::Error for DeviceMemoryAllocError {
             DeviceMemoryAllocError::TooManyObjects =>
                 "the maximum number of allocations has been exceeded",
             DeviceMemoryAllocError::MemoryMapFailed => "memory map failed",
+            DeviceMemoryAllocError::SubsetAllocationNotSupported =>
+                "the device mask doesn't contain all the devices of the group, and the device \
+                 doesn't support subset allocations",
+            DeviceMemoryAllocError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -505,7 +627,7 @@ impl From<Error> for DeviceMemoryAllocError {
             e @ Error::OutOfDeviceMemory => DeviceMemoryAllocError::OomError(e.into()),
             Error::TooManyObjects => DeviceMemoryAllocError::TooManyObjects,
             Error::MemoryMapFailed => DeviceMemoryAllocError::MemoryMapFailed,
-            _ => panic!("unexpected error: {:?}", err),
+            err => DeviceMemoryAllocError::UnexpectedResult(err.result()),
         }
     }
 }
@@ -520,6 +642,7 @@ impl From<OomError> for DeviceMemoryAllocError {
 #[cfg(test)]
 mod tests {
     use OomError;
+    use VulkanObject;
     use memory::DeviceMemory;
     use memory::DeviceMemoryAllocError;
 
@@ -530,6 +653,42 @@ mod tests {
         let _ = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        let memory = DeviceMemory::alloc(device.clone(), mem_ty, 256).unwrap();
+
+        let count = *device.allocation_count().lock().unwrap();
+        unsafe {
+            let borrowed = DeviceMemory::from_raw(device.clone(), memory.internal_object(),
+                                                  mem_ty, 256, false);
+            assert_eq!(borrowed.size(), 256);
+            assert_eq!(borrowed.memory_type().id(), mem_ty.id());
+        }
+        assert_eq!(*device.allocation_count().lock().unwrap(), count);
+    }
+
+    #[test]
+    fn device_mask() {
+        let (device, _) = gfx_dev_and_queue!();
+        if !device.supports_device_masks() {
+            return;
+        }
+
+        let mem_ty = device.physical_device().memory_types().next().unwrap();
+        let _ = DeviceMemory::alloc_with_device_mask(device.clone(), mem_ty, 256, 1).unwrap();
+        assert_should_panic!({
+                                 let _ = DeviceMemory::alloc_with_device_mask(device.clone(),
+                                                                              mem_ty, 256, 0b10);
+                             });
+        assert_should_panic!({
+                                 let _ = DeviceMemory::alloc_with_device_mask(device.clone(),
+                                                                              mem_ty, 256,
+                                                                              1 << 31);
+                             });
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
@@ -118,7 +118,7 @@ pub unsafe trait MemoryPool: DeviceOwned {
 
         // Redirect to `self.alloc_generic` if we don't perform a dedicated allocation.
         if !requirements.prefer_dedicated ||
-            !self.device().loaded_extensions().khr_dedicated_allocation
+            !self.device().supports_dedicated_allocation()
         {
             let alloc = self.alloc_generic(mem_ty,
                                            requirements.size,
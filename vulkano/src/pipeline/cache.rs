@@ -13,8 +13,9 @@
 //! pipelines on the disk.
 //!
 //! You can create either an empty cache or a cache from some initial data. Whenever you create a
-//! graphics or compute pipeline, you have the possibility to pass a reference to that cache.
-//! TODO: ^ that's not the case yet
+//! graphics pipeline, you have the possibility to pass a reference to that cache with
+//! `GraphicsPipelineBuilder::pipeline_cache`. Compute pipelines take the cache as a parameter of
+//! `ComputePipeline::new`.
 //! The Vulkan implementation will then look in the cache for an existing entry, or add one if it
 //! doesn't exist.
 //!
@@ -23,11 +24,14 @@
 //! on the disk, and [`with_data`](struct.PipelineCache.html#method.with_data) for how to reload it.
 //!
 
+use std::error;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use device::Device;
+use device::DeviceOwned;
 
 use OomError;
 use VulkanObject;
@@ -40,6 +44,10 @@ use vk;
 pub struct PipelineCache {
     device: Arc<Device>,
     cache: vk::PipelineCache,
+    // `vkMergePipelineCaches` requires the destination cache to be externally synchronized.
+    merge_lock: Mutex<()>,
+    // False if the cache was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl PipelineCache {
@@ -83,7 +91,7 @@ impl PipelineCache {
     /// ```
     #[inline]
     pub unsafe fn with_data(device: Arc<Device>, initial_data: &[u8])
-                            -> Result<Arc<PipelineCache>, OomError> {
+                            -> Result<Arc<PipelineCache>, PipelineCacheCreationError> {
         PipelineCache::new_impl(device, Some(initial_data))
     }
 
@@ -99,13 +107,13 @@ impl PipelineCache {
     /// let cache = PipelineCache::empty(device.clone()).unwrap();
     /// ```
     #[inline]
-    pub fn empty(device: Arc<Device>) -> Result<Arc<PipelineCache>, OomError> {
+    pub fn empty(device: Arc<Device>) -> Result<Arc<PipelineCache>, PipelineCacheCreationError> {
         unsafe { PipelineCache::new_impl(device, None) }
     }
 
     // Actual implementation of the constructor.
     unsafe fn new_impl(device: Arc<Device>, initial_data: Option<&[u8]>)
-                       -> Result<Arc<PipelineCache>, OomError> {
+                       -> Result<Arc<PipelineCache>, PipelineCacheCreationError> {
         let vk = device.pointers();
 
         let cache = {
@@ -130,9 +138,33 @@ impl PipelineCache {
         Ok(Arc::new(PipelineCache {
                         device: device.clone(),
                         cache: cache,
+                        merge_lock: Mutex::new(()),
+                        needs_destruction: true,
                     }))
     }
 
+    /// Builds a `PipelineCache` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the cache is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid pipeline cache of `device`.
+    /// - The cache must not be used by anything else while vulkano merges other caches into it.
+    /// - If `owned` is true, the cache must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::PipelineCache, owned: bool)
+                           -> Arc<PipelineCache> {
+        Arc::new(PipelineCache {
+                     device: device,
+                     cache: handle,
+                     merge_lock: Mutex::new(()),
+                     needs_destruction: owned,
+                 })
+    }
+
     /// Merges other pipeline caches into this one.
     ///
     /// It is `self` that is modified here. The pipeline caches passed as parameter are untouched.
@@ -141,13 +173,13 @@ impl PipelineCache {
     ///
     /// - Panics if `self` is included in the list of other pipelines.
     ///
-    // FIXME: vkMergePipelineCaches is not thread safe for the destination cache
     // TODO: write example
     pub fn merge<'a, I>(&self, pipelines: I) -> Result<(), OomError>
         where I: IntoIterator<Item = &'a &'a Arc<PipelineCache>>
     {
         unsafe {
             let vk = self.device.pointers();
+            let _lock = self.merge_lock.lock().unwrap();
 
             let pipelines = pipelines
                 .into_iter()
@@ -216,6 +248,13 @@ impl PipelineCache {
     }
 }
 
+unsafe impl DeviceOwned for PipelineCache {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
 unsafe impl VulkanObject for PipelineCache {
     type Object = vk::PipelineCache;
 
@@ -230,6 +269,10 @@ unsafe impl VulkanObject for PipelineCache {
 impl Drop for PipelineCache {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyPipelineCache(self.device.internal_object(), self.cache, ptr::null());
@@ -237,9 +280,46 @@ impl Drop for PipelineCache {
     }
 }
 
+/// Error that can happen when creating a pipeline cache.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PipelineCacheCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for PipelineCacheCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            PipelineCacheCreationError::OomError(_) => "not enough memory available",
+            PipelineCacheCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            PipelineCacheCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(PipelineCacheCreationError, OomError {});
+
 #[cfg(test)]
 mod tests {
+    use Error;
+    use OomError;
+    use VulkanObject;
+    use check_errors;
     use pipeline::cache::PipelineCache;
+    use pipeline::cache::PipelineCacheCreationError;
+    use vk;
 
     #[test]
     fn merge_self_forbidden() {
@@ -249,4 +329,33 @@ mod tests {
                                  pipeline.merge(&[&pipeline]).unwrap();
                              });
     }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+
+        let borrowed = unsafe { PipelineCache::from_raw(device, cache.internal_object(), false) };
+        assert_eq!(borrowed.internal_object(), cache.internal_object());
+        borrowed.get_data().unwrap();
+        drop(borrowed);
+        cache.get_data().unwrap();
+    }
+
+    #[test]
+    fn creation_error_keeps_result_code() {
+        assert_eq!(PipelineCacheCreationError::from(Error::OutOfHostMemory),
+                   PipelineCacheCreationError::OomError(OomError::OutOfHostMemory));
+        assert_eq!(PipelineCacheCreationError::from(Error::DeviceLost),
+                   PipelineCacheCreationError::UnexpectedResult(vk::ERROR_DEVICE_LOST));
+    }
+
+    #[test]
+    fn creation_error_keeps_unknown_result_code() {
+        // A code that vulkano doesn't know about, for example one added by a newer extension.
+        let code = -1000999000i32 as vk::Result;
+        let err = check_errors(code).unwrap_err();
+        assert_eq!(PipelineCacheCreationError::from(err),
+                   PipelineCacheCreationError::UnexpectedResult(code));
+    }
 }
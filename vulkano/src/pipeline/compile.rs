@@ -0,0 +1,183 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Compiling GLSL and HLSL shaders at runtime.
+//!
+//! This module is only available if the `runtime-shader-compilation` feature is enabled. It uses
+//! the same compiler as `vulkano-shaders`, but at runtime, which is useful for editors or for
+//! reloading shaders while the application is running.
+//!
+//! Contrary to `vulkano-shaders`, no code is generated for the descriptors and the interface of
+//! the shader. Use the `reflect` module to obtain them.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::Device;
+//! use vulkano::pipeline::compile;
+//! use vulkano::pipeline::compile::CompileOptions;
+//! use vulkano::pipeline::compile::ShaderType;
+//!
+//! # let device: Arc<Device> = return;
+//! let source = "
+//!     #version 450
+//!     layout(location = 0) out vec4 f_color;
+//!     void main() { f_color = COLOR; }
+//! ";
+//!
+//! let options = CompileOptions {
+//!     macros: vec![("COLOR".to_owned(), Some("vec4(1.0)".to_owned()))],
+//!     .. CompileOptions::default()
+//! };
+//!
+//! let module = compile::compile_module(device.clone(), source, ShaderType::Fragment, &options)
+//!     .unwrap();
+//! ```
+
+use std::error;
+use std::fmt;
+use std::io::Read;
+use std::ptr;
+use std::sync::Arc;
+
+use glsl_to_spirv;
+
+use device::Device;
+use pipeline::shader::ShaderModule;
+use pipeline::shader::ShaderModuleCreationError;
+
+pub use glsl_to_spirv::CompileOptions;
+pub use glsl_to_spirv::ShaderType;
+pub use glsl_to_spirv::SourceLanguage;
+
+/// Compiles the source code of a shader to SPIR-V.
+pub fn compile(source: &str, ty: ShaderType, options: &CompileOptions)
+               -> Result<Vec<u32>, ShaderCompilationError> {
+    let bytes = compile_bytes(source, ty, options)?;
+
+    if bytes.len() % 4 != 0 {
+        return Err(ShaderCompilationError::ModuleCreationError(
+            ShaderModuleCreationError::UnalignedLength));
+    }
+
+    // The compiler writes the code in the endianness of the host.
+    let mut words: Vec<u32> = Vec::with_capacity(bytes.len() / 4);
+    unsafe {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), words.as_mut_ptr() as *mut u8, bytes.len());
+        words.set_len(bytes.len() / 4);
+    }
+    Ok(words)
+}
+
+/// Compiles the source code of a shader and creates a shader module from the result.
+pub fn compile_module(device: Arc<Device>, source: &str, ty: ShaderType,
+                      options: &CompileOptions)
+                      -> Result<Arc<ShaderModule>, ShaderCompilationError> {
+    let bytes = compile_bytes(source, ty, options)?;
+
+    // Safe because the code has been produced by the official compiler, and is checked to
+    // contain an entry point.
+    unsafe { Ok(ShaderModule::new_checked(device, &bytes)?) }
+}
+
+fn compile_bytes(source: &str, ty: ShaderType, options: &CompileOptions)
+                 -> Result<Vec<u8>, ShaderCompilationError> {
+    let mut output = match glsl_to_spirv::compile_with_options(source, ty, options) {
+        Ok(output) => output,
+        Err(log) => return Err(ShaderCompilationError::CompilationFailed(log)),
+    };
+
+    let mut bytes = Vec::new();
+    if let Err(err) = output.read_to_end(&mut bytes) {
+        return Err(ShaderCompilationError::CompilationFailed(err.to_string()));
+    }
+
+    Ok(bytes)
+}
+
+/// Error that can happen when compiling a shader at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderCompilationError {
+    /// The compiler rejected the source code. Contains the log of the compiler.
+    CompilationFailed(String),
+    /// The compiled code couldn't be turned into a shader module.
+    ModuleCreationError(ShaderModuleCreationError),
+}
+
+impl error::Error for ShaderCompilationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderCompilationError::CompilationFailed(_) => {
+                "the compiler rejected the source code"
+            },
+            ShaderCompilationError::ModuleCreationError(_) => {
+                "the compiled code couldn't be turned into a shader module"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ShaderCompilationError::ModuleCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ShaderCompilationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ShaderCompilationError::CompilationFailed(ref log) => {
+                write!(fmt, "{}:\n{}", error::Error::description(self), log)
+            },
+            _ => write!(fmt, "{}", error::Error::description(self)),
+        }
+    }
+}
+
+impl From<ShaderModuleCreationError> for ShaderCompilationError {
+    #[inline]
+    fn from(err: ShaderModuleCreationError) -> ShaderCompilationError {
+        ShaderCompilationError::ModuleCreationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compile_error() {
+        match compile("not glsl", ShaderType::Vertex, &CompileOptions::default()) {
+            Err(ShaderCompilationError::CompilationFailed(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn compile_with_macro() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let source = "
+            #version 450
+            layout(local_size_x = SIZE) in;
+            void main() {}
+        ";
+
+        let options = CompileOptions {
+            macros: vec![("SIZE".to_owned(), Some("64".to_owned()))],
+            ..CompileOptions::default()
+        };
+
+        let module = compile_module(device, source, ShaderType::Compute, &options).unwrap();
+        assert_eq!(module.entry_points().len(), 1);
+    }
+}
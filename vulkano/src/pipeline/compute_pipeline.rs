@@ -24,10 +24,11 @@ use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
 use descriptor::pipeline_layout::PipelineLayoutSuperset;
 use descriptor::pipeline_layout::PipelineLayoutSys;
+use pipeline::cache::PipelineCache;
 use pipeline::shader::EntryPointAbstract;
+use pipeline::shader::ShaderCapabilitiesError;
 use pipeline::shader::SpecializationConstants;
 
-use Error;
 use OomError;
 use SafeDeref;
 use VulkanObject;
@@ -51,12 +52,23 @@ pub struct ComputePipeline<Pl> {
 struct Inner {
     pipeline: vk::Pipeline,
     device: Arc<Device>,
+    // False if the pipeline was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl ComputePipeline<()> {
     /// Builds a new `ComputePipeline`.
+    ///
+    /// If `cache` is `Some`, the implementation can reuse the data of the cache to build the
+    /// pipeline faster, and stores the result of the compilation in the cache.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the shader module or the pipeline cache weren't created with `device`.
+    ///
     pub fn new<Cs>(
-        device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants)
+        device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants,
+        cache: Option<Arc<PipelineCache>>)
         -> Result<ComputePipeline<PipelineLayout<Cs::PipelineLayout>>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
               Cs: EntryPointAbstract
@@ -66,7 +78,8 @@ impl ComputePipeline<()> {
             ComputePipeline::with_unchecked_pipeline_layout(device,
                                                             shader,
                                                             specialization,
-                                                            pipeline_layout)
+                                                            pipeline_layout,
+                                                            cache)
         }
     }
 }
@@ -75,10 +88,16 @@ impl<Pl> ComputePipeline<Pl> {
     /// Builds a new `ComputePipeline` with a specific pipeline layout.
     ///
     /// An error will be returned if the pipeline layout isn't a superset of what the shader
-    /// uses.
+    /// uses. `cache` is used the same way as in `new`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the shader module, the pipeline layout or the pipeline cache weren't created
+    ///   with `device`.
+    ///
     pub fn with_pipeline_layout<Cs>(device: Arc<Device>, shader: &Cs,
                                     specialization: &Cs::SpecializationConstants,
-                                    pipeline_layout: Pl)
+                                    pipeline_layout: Pl, cache: Option<Arc<PipelineCache>>)
                                     -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
               Cs: EntryPointAbstract,
@@ -89,20 +108,40 @@ impl<Pl> ComputePipeline<Pl> {
             ComputePipeline::with_unchecked_pipeline_layout(device,
                                                             shader,
                                                             specialization,
-                                                            pipeline_layout)
+                                                            pipeline_layout,
+                                                            cache)
         }
     }
 
     /// Same as `with_pipeline_layout`, but doesn't check whether the pipeline layout is a
     /// superset of what the shader expects.
+    ///
+    /// The features required by the capabilities of the shader are still checked.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the shader module, the pipeline layout or the pipeline cache weren't created
+    ///   with `device`.
+    ///
     pub unsafe fn with_unchecked_pipeline_layout<Cs>(
         device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants,
-        pipeline_layout: Pl)
+        pipeline_layout: Pl, cache: Option<Arc<PipelineCache>>)
         -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
               Cs: EntryPointAbstract,
               Pl: PipelineLayoutAbstract
     {
+        assert_eq!(shader.module().device().internal_object(),
+                   device.internal_object());
+        assert_eq!(pipeline_layout.device().internal_object(),
+                   device.internal_object());
+        if let Some(ref cache) = cache {
+            assert_eq!(cache.device().internal_object(), device.internal_object());
+        }
+
+        shader.module().check_features(device.enabled_features())?;
+        shader.module().check_extensions(device.loaded_extensions())?;
+
         let vk = device.pointers();
 
         let pipeline = {
@@ -140,7 +179,10 @@ impl<Pl> ComputePipeline<Pl> {
 
             let mut output = mem::uninitialized();
             check_errors(vk.CreateComputePipelines(device.internal_object(),
-                                                   0,
+                                                   cache
+                                                       .as_ref()
+                                                       .map(|c| c.internal_object())
+                                                       .unwrap_or(0),
                                                    1,
                                                    &infos,
                                                    ptr::null(),
@@ -152,6 +194,7 @@ impl<Pl> ComputePipeline<Pl> {
                inner: Inner {
                    device: device.clone(),
                    pipeline: pipeline,
+                   needs_destruction: true,
                },
                pipeline_layout: pipeline_layout,
            })
@@ -166,6 +209,31 @@ impl<Pl> fmt::Debug for ComputePipeline<Pl> {
 }
 
 impl<Pl> ComputePipeline<Pl> {
+    /// Builds a `ComputePipeline` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the pipeline is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid compute pipeline of the device of `pipeline_layout`, created
+    ///   with a layout that is identical to `pipeline_layout`.
+    /// - If `owned` is true, the pipeline must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(pipeline_layout: Pl, handle: vk::Pipeline, owned: bool)
+                           -> ComputePipeline<Pl>
+        where Pl: PipelineLayoutAbstract
+    {
+        ComputePipeline {
+            inner: Inner {
+                device: pipeline_layout.device().clone(),
+                pipeline: handle,
+                needs_destruction: owned,
+            },
+            pipeline_layout: pipeline_layout,
+        }
+    }
+
     /// Returns the `Device` this compute pipeline was created with.
     #[inline]
     pub fn device(&self) -> &Arc<Device> {
@@ -285,6 +353,10 @@ unsafe impl<Pl> VulkanObject for ComputePipeline<Pl> {
 impl Drop for Inner {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyPipeline(self.device.internal_object(), self.pipeline, ptr::null());
@@ -301,6 +373,10 @@ pub enum ComputePipelineCreationError {
     PipelineLayoutCreationError(PipelineLayoutCreationError),
     /// The pipeline layout is not compatible with what the shader expects.
     IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
+    /// The shader requires a feature that is not enabled on the device.
+    ShaderFeatureNotEnabled(ShaderCapabilitiesError),
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -312,6 +388,10 @@ impl error::Error for ComputePipelineCreationError {
                 "error while creating the pipeline layout object",
             ComputePipelineCreationError::IncompatiblePipelineLayout(_) =>
                 "the pipeline layout is not compatible with what the shader expects",
+            ComputePipelineCreationError::ShaderFeatureNotEnabled(_) =>
+                "the shader requires a feature that is not enabled on the device",
+            ComputePipelineCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -321,23 +401,13 @@ impl error::Error for ComputePipelineCreationError {
             ComputePipelineCreationError::OomError(ref err) => Some(err),
             ComputePipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             ComputePipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            ComputePipelineCreationError::ShaderFeatureNotEnabled(ref err) => Some(err),
+            ComputePipelineCreationError::UnexpectedResult(_) => None,
         }
     }
 }
 
-impl fmt::Display for ComputePipelineCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for ComputePipelineCreationError {
-    #[inline]
-    fn from(err: OomError) -> ComputePipelineCreationError {
-        ComputePipelineCreationError::OomError(err)
-    }
-}
+vk_error_conversions!(ComputePipelineCreationError, OomError {});
 
 impl From<PipelineLayoutCreationError> for ComputePipelineCreationError {
     #[inline]
@@ -353,18 +423,10 @@ impl From<PipelineLayoutNotSupersetError> for ComputePipelineCreationError {
     }
 }
 
-impl From<Error> for ComputePipelineCreationError {
+impl From<ShaderCapabilitiesError> for ComputePipelineCreationError {
     #[inline]
-    fn from(err: Error) -> ComputePipelineCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => {
-                ComputePipelineCreationError::OomError(OomError::from(err))
-            },
-            err @ Error::OutOfDeviceMemory => {
-                ComputePipelineCreationError::OomError(OomError::from(err))
-            },
-            _ => panic!("unexpected error: {:?}", err),
-        }
+    fn from(err: ShaderCapabilitiesError) -> ComputePipelineCreationError {
+        ComputePipelineCreationError::ShaderFeatureNotEnabled(err)
     }
 }
 
@@ -378,9 +440,16 @@ mod tests {
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
     use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::EmptyPipelineDesc;
     use descriptor::pipeline_layout::PipelineLayoutDesc;
     use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use device::Device;
+    use VulkanObject;
     use pipeline::ComputePipeline;
+    use pipeline::ComputePipelineAbstract;
+    use pipeline::ComputePipelineCreationError;
+    use pipeline::cache::PipelineCache;
+    use pipeline::shader::ComputeEntryPoint;
     use pipeline::shader::ShaderModule;
     use pipeline::shader::SpecializationConstants;
     use pipeline::shader::SpecializationMapEntry;
@@ -389,8 +458,126 @@ mod tests {
     use sync::GpuFuture;
     use sync::now;
 
-    // TODO: test for basic creation
-    // TODO: test for pipeline layout error
+    #[test]
+    fn basic_create() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let _ = ComputePipeline::new(device.clone(), &shader, &SpecConsts { VALUE: 0 }, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let pipeline = ComputePipeline::new(device.clone(), &shader, &SpecConsts { VALUE: 0 }, None)
+            .unwrap();
+        let borrowed = unsafe {
+            ComputePipeline::from_raw(pipeline.layout().clone(),
+                                      pipeline.inner().internal_object(),
+                                      false)
+        };
+        assert_eq!(borrowed.inner().internal_object(), pipeline.inner().internal_object());
+    }
+
+    #[test]
+    fn pipeline_layout_not_superset() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        // The shader uses a storage buffer but the layout is empty.
+        let layout = EmptyPipelineDesc.build(device.clone()).unwrap();
+
+        match ComputePipeline::with_pipeline_layout(device.clone(),
+                                                    &shader,
+                                                    &SpecConsts { VALUE: 0 },
+                                                    layout,
+                                                    None) {
+            Err(ComputePipelineCreationError::IncompatiblePipelineLayout(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wrong_device() {
+        let (device1, _) = gfx_dev_and_queue!();
+        let (device2, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device1.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        assert_should_panic!({
+                                 let _ = ComputePipeline::new(device2.clone(),
+                                                              &shader,
+                                                              &SpecConsts { VALUE: 0 },
+                                                              None);
+                             });
+    }
+
+    #[test]
+    fn wrong_device_pipeline_layout() {
+        let (device1, _) = gfx_dev_and_queue!();
+        let (device2, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device1.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let layout = Layout.build(device2.clone()).unwrap();
+        let spec_consts = SpecConsts { VALUE: 0 };
+
+        assert_should_panic!({
+                                 let _ = ComputePipeline::with_pipeline_layout(device1.clone(),
+                                                                               &shader,
+                                                                               &spec_consts,
+                                                                               layout,
+                                                                               None);
+                             });
+    }
+
+    #[test]
+    fn pipeline_cache() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+
+        // Building twice with the same cache lets the implementation reuse the first compilation.
+        for _ in 0 .. 2 {
+            let _ = ComputePipeline::new(device.clone(),
+                                         &shader,
+                                         &SpecConsts { VALUE: 0 },
+                                         Some(cache.clone()))
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn pipeline_cache_wrong_device() {
+        let (device1, _) = gfx_dev_and_queue!();
+        let (device2, _) = gfx_dev_and_queue!();
+
+        let shader_module = shader_module(device1.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let cache = PipelineCache::empty(device2).unwrap();
+
+        assert_should_panic!({
+                                 let _ = ComputePipeline::new(device1.clone(),
+                                                              &shader,
+                                                              &SpecConsts { VALUE: 0 },
+                                                              Some(cache.clone()));
+                             });
+    }
 
     #[test]
     fn spec_constants() {
@@ -400,7 +587,109 @@ mod tests {
 
         let (device, queue) = gfx_dev_and_queue!();
 
-        let module = unsafe {
+        let shader_module = shader_module(device.clone());
+        let shader = unsafe { compute_entry_point(&shader_module) };
+
+        let pipeline = Arc::new(ComputePipeline::new(device.clone(),
+                                                     &shader,
+                                                     &SpecConsts { VALUE: 0x12345678 },
+                                                     None)
+                                    .unwrap());
+
+        let data_buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0)
+            .unwrap();
+        let set = PersistentDescriptorSet::start(pipeline.clone(), 0)
+            .add_buffer(data_buffer.clone())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                               queue.family())
+            .unwrap()
+            .dispatch([1, 1, 1], pipeline, set, ())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let future = now(device.clone())
+            .then_execute(queue.clone(), command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let data_buffer_content = data_buffer.read().unwrap();
+        assert_eq!(*data_buffer_content, 0x12345678);
+    }
+
+    #[derive(Debug, Copy, Clone)]
+    #[allow(non_snake_case)]
+    #[repr(C)]
+    struct SpecConsts {
+        VALUE: i32,
+    }
+
+    unsafe impl SpecializationConstants for SpecConsts {
+        fn descriptors() -> &'static [SpecializationMapEntry] {
+            static DESCRIPTORS: [SpecializationMapEntry; 1] = [
+                SpecializationMapEntry {
+                    constant_id: 83,
+                    offset: 0,
+                    size: 4,
+                },
+            ];
+            &DESCRIPTORS
+        }
+    }
+
+    #[derive(Debug, Copy, Clone)]
+    struct Layout;
+    unsafe impl PipelineLayoutDesc for Layout {
+        fn num_sets(&self) -> usize {
+            1
+        }
+        fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
+            match set {
+                0 => Some(1),
+                _ => None,
+            }
+        }
+        fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
+            match (set, binding) {
+                (0, 0) => Some(DescriptorDesc {
+                                   ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                                    dynamic: Some(false),
+                                                                    storage: true,
+                                                                }),
+                                   array_count: 1,
+                                   runtime_array: false,
+                                   stages: ShaderStages {
+                                       compute: true,
+                                       ..ShaderStages::none()
+                                   },
+                                   readonly: true,
+                               }),
+                _ => None,
+            }
+        }
+        fn num_push_constants_ranges(&self) -> usize {
+            0
+        }
+        fn push_constants_range(&self, num: usize) -> Option<PipelineLayoutDescPcRange> {
+            None
+        }
+    }
+
+    // Safety: the shader module must be the one returned by `shader_module`.
+    unsafe fn compute_entry_point(module: &ShaderModule)
+                                  -> ComputeEntryPoint<SpecConsts, Layout> {
+        static NAME: [u8; 5] = [109, 97, 105, 110, 0]; // "main"
+        module.compute_entry_point(CStr::from_ptr(NAME.as_ptr() as *const _), Layout)
+    }
+
+    fn shader_module(device: Arc<Device>) -> Arc<ShaderModule> {
+        unsafe {
             /*
             #version 450
 
@@ -899,98 +1188,6 @@ mod tests {
                 0,
             ];
             ShaderModule::new(device.clone(), &MODULE).unwrap()
-        };
-
-        let shader = unsafe {
-            #[derive(Debug, Copy, Clone)]
-            struct Layout;
-            unsafe impl PipelineLayoutDesc for Layout {
-                fn num_sets(&self) -> usize {
-                    1
-                }
-                fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
-                    match set {
-                        0 => Some(1),
-                        _ => None,
-                    }
-                }
-                fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
-                    match (set, binding) {
-                        (0, 0) => Some(DescriptorDesc {
-                                           ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
-                                                                            dynamic: Some(false),
-                                                                            storage: true,
-                                                                        }),
-                                           array_count: 1,
-                                           stages: ShaderStages {
-                                               compute: true,
-                                               ..ShaderStages::none()
-                                           },
-                                           readonly: true,
-                                       }),
-                        _ => None,
-                    }
-                }
-                fn num_push_constants_ranges(&self) -> usize {
-                    0
-                }
-                fn push_constants_range(&self, num: usize) -> Option<PipelineLayoutDescPcRange> {
-                    None
-                }
-            }
-
-            static NAME: [u8; 5] = [109, 97, 105, 110, 0]; // "main"
-            module.compute_entry_point(CStr::from_ptr(NAME.as_ptr() as *const _), Layout)
-        };
-
-        #[derive(Debug, Copy, Clone)]
-        #[allow(non_snake_case)]
-        #[repr(C)]
-        struct SpecConsts {
-            VALUE: i32,
         }
-        unsafe impl SpecializationConstants for SpecConsts {
-            fn descriptors() -> &'static [SpecializationMapEntry] {
-                static DESCRIPTORS: [SpecializationMapEntry; 1] = [
-                    SpecializationMapEntry {
-                        constant_id: 83,
-                        offset: 0,
-                        size: 4,
-                    },
-                ];
-                &DESCRIPTORS
-            }
-        }
-
-        let pipeline = Arc::new(ComputePipeline::new(device.clone(),
-                                                     &shader,
-                                                     &SpecConsts { VALUE: 0x12345678 })
-                                    .unwrap());
-
-        let data_buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0)
-            .unwrap();
-        let set = PersistentDescriptorSet::start(pipeline.clone(), 0)
-            .add_buffer(data_buffer.clone())
-            .unwrap()
-            .build()
-            .unwrap();
-
-        let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
-                                                                               queue.family())
-            .unwrap()
-            .dispatch([1, 1, 1], pipeline, set, ())
-            .unwrap()
-            .build()
-            .unwrap();
-
-        let future = now(device.clone())
-            .then_execute(queue.clone(), command_buffer)
-            .unwrap()
-            .then_signal_fence_and_flush()
-            .unwrap();
-        future.wait(None).unwrap();
-
-        let data_buffer_content = data_buffer.read().unwrap();
-        assert_eq!(*data_buffer_content, 0x12345678);
     }
 }
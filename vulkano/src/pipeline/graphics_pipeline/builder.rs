@@ -13,26 +13,36 @@
 
 use smallvec::SmallVec;
 use std::mem;
+use std::ops::Range;
 use std::ptr;
 use std::sync::Arc;
 use std::u32;
 
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
+use device::DeviceOwned;
 use framebuffer::RenderPassAbstract;
 use framebuffer::Subpass;
 use pipeline::blend::AttachmentBlend;
 use pipeline::blend::AttachmentsBlend;
 use pipeline::blend::Blend;
 use pipeline::blend::LogicOp;
+use pipeline::cache::PipelineCache;
 use pipeline::depth_stencil::Compare;
 use pipeline::depth_stencil::DepthBounds;
 use pipeline::depth_stencil::DepthStencil;
+use pipeline::depth_stencil::Stencil;
 use pipeline::graphics_pipeline::GraphicsPipeline;
+use pipeline::graphics_pipeline::GraphicsPipelineAbstract;
 use pipeline::graphics_pipeline::GraphicsPipelineCreationError;
 use pipeline::graphics_pipeline::Inner as GraphicsPipelineInner;
 use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::ConservativeRasterizationMode;
+use pipeline::raster::ConservativeRasterizationProperties;
 use pipeline::raster::CullMode;
+use pipeline::raster::DepthBias;
 use pipeline::raster::DepthBiasControl;
 use pipeline::raster::FrontFace;
 use pipeline::raster::PolygonMode;
@@ -59,7 +69,8 @@ use vk;
 
 /// Prototype for a `GraphicsPipeline`.
 // TODO: we can optimize this by filling directly the raw vk structs
-pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> {
+pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                   Fs, Fss, Rp> {
     vertex_input: Vdef,
     vertex_shader: Option<(Vs, Vss)>,
     input_assembly: vk::PipelineInputAssemblyStateCreateInfo,
@@ -68,13 +79,19 @@ pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss,
     input_assembly_topology: PrimitiveTopology,
     tessellation: Option<TessInfo<Tcs, Tcss, Tes, Tess>>,
     geometry_shader: Option<(Gs, Gss)>,
+    mesh: Option<MeshInfo<Ts, Tss, Ms, Mss>>,
     viewport: Option<ViewportsState>,
     raster: Rasterization,
     multisample: vk::PipelineMultisampleStateCreateInfo,
+    // Storage for the `pSampleMask` member of `multisample`, which is only set when building.
+    sample_mask: [u32; 2],
     fragment_shader: Option<(Fs, Fss)>,
     depth_stencil: DepthStencil,
     blend: Blend,
     render_pass: Option<Subpass<Rp>>,
+    allow_derivatives: bool,
+    base_pipeline: Option<Arc<GraphicsPipelineAbstract + Send + Sync>>,
+    cache: Option<Arc<PipelineCache>>,
 }
 
 // Additional parameters if tessellation is used.
@@ -84,6 +101,13 @@ struct TessInfo<Tcs, Tcss, Tes, Tess> {
     tessellation_evaluation_shader: (Tes, Tess),
 }
 
+// Additional parameters if mesh shading is used.
+#[derive(Copy, Clone)]
+struct MeshInfo<Ts, Tss, Ms, Mss> {
+    task_shader: Option<(Ts, Tss)>,
+    mesh_shader: (Ms, Mss),
+}
+
 impl
     GraphicsPipelineBuilder<BufferlessDefinition,
                             EmptyEntryPointDummy,
@@ -96,6 +120,10 @@ impl
                             (),
                             EmptyEntryPointDummy,
                             (),
+                            EmptyEntryPointDummy,
+                            (),
+                            EmptyEntryPointDummy,
+                            (),
                             ()> {
     /// Builds a new empty builder.
     pub(super) fn new() -> Self {
@@ -111,48 +139,88 @@ impl
                 input_assembly_topology: PrimitiveTopology::TriangleList,
                 tessellation: None,
                 geometry_shader: None,
+                mesh: None,
                 viewport: None,
                 raster: Default::default(),
                 multisample: vk::PipelineMultisampleStateCreateInfo {
                     sType: vk::STRUCTURE_TYPE_PIPELINE_MULTISAMPLE_STATE_CREATE_INFO,
                     ..mem::zeroed()
                 },
+                sample_mask: [0xffffffff; 2],
                 fragment_shader: None,
                 depth_stencil: DepthStencil::disabled(),
                 blend: Blend::pass_through(),
                 render_pass: None,
+                allow_derivatives: false,
+                base_pipeline: None,
+                cache: None,
             }
         }
     }
 }
 
-impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
-    GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
+impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss, Fs, Fss, Rp>
+    GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                            Fs, Fss, Rp>
     where Vdef: VertexDefinition<Vs::InputDefinition>,
           Vs: GraphicsEntryPointAbstract,
           Fs: GraphicsEntryPointAbstract,
           Gs: GraphicsEntryPointAbstract,
           Tcs: GraphicsEntryPointAbstract,
           Tes: GraphicsEntryPointAbstract,
+          Ts: GraphicsEntryPointAbstract,
+          Ms: GraphicsEntryPointAbstract,
           Vss: SpecializationConstants,
           Tcss: SpecializationConstants,
           Tess: SpecializationConstants,
           Gss: SpecializationConstants,
+          Tss: SpecializationConstants,
+          Mss: SpecializationConstants,
           Fss: SpecializationConstants,
           Vs::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
           Fs::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
           Tcs::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
           Tes::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
           Gs::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
+          Ts::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
+          Ms::PipelineLayout: Clone + 'static + Send + Sync, // TODO: shouldn't be required
           Tcs::InputDefinition: ShaderInterfaceDefMatch<Vs::OutputDefinition>,
           Tes::InputDefinition: ShaderInterfaceDefMatch<Tcs::OutputDefinition>,
           Gs::InputDefinition: ShaderInterfaceDefMatch<Tes::OutputDefinition>
                                    + ShaderInterfaceDefMatch<Vs::OutputDefinition>,
           Fs::InputDefinition: ShaderInterfaceDefMatch<Gs::OutputDefinition>
                                    + ShaderInterfaceDefMatch<Tes::OutputDefinition>
-                                   + ShaderInterfaceDefMatch<Vs::OutputDefinition>,
+                                   + ShaderInterfaceDefMatch<Vs::OutputDefinition>
+                                   + ShaderInterfaceDefMatch<Ms::OutputDefinition>,
           Rp: RenderPassAbstract + RenderPassSubpassInterface<Fs::OutputDefinition>
 {
+    // Returns an error if one of the mandatory parameters hasn't been set.
+    fn check_missing_parameters(&self) -> Result<(), GraphicsPipelineCreationError> {
+        if self.mesh.is_some() {
+            if self.vertex_shader.is_some() || self.tessellation.is_some() ||
+                self.geometry_shader.is_some()
+            {
+                return Err(GraphicsPipelineCreationError::MeshShaderWithVertexStages);
+            }
+        } else if self.vertex_shader.is_none() {
+            return Err(GraphicsPipelineCreationError::NoVertexShader);
+        }
+
+        if self.fragment_shader.is_none() {
+            return Err(GraphicsPipelineCreationError::NoFragmentShader);
+        }
+
+        if self.viewport.is_none() {
+            return Err(GraphicsPipelineCreationError::NoViewportState);
+        }
+
+        if self.render_pass.is_none() {
+            return Err(GraphicsPipelineCreationError::NoRenderPass);
+        }
+
+        Ok(())
+    }
+
     /// Builds the graphics pipeline, using an inferred a pipeline layout.
     // TODO: replace Box<PipelineLayoutAbstract> with a PipelineUnion struct without template params
     pub fn build(self, device: Arc<Device>)
@@ -169,9 +237,152 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                             -> Result<GraphicsPipeline<Vdef, Box<PipelineLayoutAbstract + Send + Sync>, Rp>,
                                       GraphicsPipelineCreationError>
     {
+        self.check_missing_parameters()?;
+        let pipeline_layout = self.auto_layout(&device, dynamic_buffers)?;
+        self.with_pipeline_layout(device, pipeline_layout)
+    }
+
+    /// Builds several graphics pipelines in a single `vkCreateGraphicsPipelines` call, using an
+    /// inferred pipeline layout for each of them.
+    ///
+    /// This lets the implementation share work between pipelines, for example the permutations
+    /// of a shader. The pipelines are returned in the same order as `builders`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the builders don't all use the same pipeline cache.
+    ///
+    pub fn build_many(device: Arc<Device>, builders: Vec<Self>)
+                      -> Result<Vec<GraphicsPipeline<Vdef,
+                                                     Box<PipelineLayoutAbstract + Send + Sync>,
+                                                     Rp>>,
+                                GraphicsPipelineCreationError> {
+        let builders = builders
+            .into_iter()
+            .map(|builder| {
+                     builder.check_missing_parameters()?;
+                     let pipeline_layout = builder.auto_layout(&device, &[])?;
+                     Ok((builder, pipeline_layout))
+                 })
+            .collect::<Result<Vec<_>, GraphicsPipelineCreationError>>()?;
+
+        GraphicsPipelineBuilder::build_many_with_pipeline_layouts(device, builders)
+    }
+
+    /// Builds several graphics pipelines in a single `vkCreateGraphicsPipelines` call.
+    ///
+    /// Does the same as `build_many`, except that the pipeline layout of each pipeline is
+    /// specified alongside its builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the builders don't all use the same pipeline cache.
+    ///
+    pub fn build_many_with_pipeline_layouts<Pl>(device: Arc<Device>, mut builders: Vec<(Self, Pl)>)
+                                                -> Result<Vec<GraphicsPipeline<Vdef, Pl, Rp>>,
+                                                          GraphicsPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
+    {
+        let cache = match builders.first() {
+            Some(&(ref builder, _)) => builder.cache.as_ref().map(|c| c.internal_object()),
+            None => return Ok(Vec::new()),
+        };
+        assert!(builders
+                    .iter()
+                    .all(|&(ref b, _)| b.cache.as_ref().map(|c| c.internal_object()) == cache));
+
+        for &mut (ref mut builder, _) in builders.iter_mut() {
+            builder.check_missing_parameters()?;
+        }
+
+        let mut infos = Vec::with_capacity(builders.len());
+        let pipelines = GraphicsPipelineBuilder::create_many(&device,
+                                                             cache.unwrap_or(0),
+                                                             &mut builders,
+                                                             &mut infos)?;
+
+        Ok(builders
+               .into_iter()
+               .zip(pipelines.into_iter())
+               .map(|((builder, layout), pipeline)| {
+                        builder.into_pipeline(device.clone(), layout, pipeline)
+                    })
+               .collect())
+    }
+
+    // Fills the create infos of `builders` one after the other, then creates all the pipelines
+    // at once. Each recursion keeps alive the structs that the create info of its builder
+    // points to.
+    fn create_many<Pl>(device: &Arc<Device>, cache: vk::PipelineCache,
+                       builders: &mut [(Self, Pl)],
+                       infos: &mut Vec<vk::GraphicsPipelineCreateInfo>)
+                       -> Result<Vec<vk::Pipeline>, GraphicsPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
+    {
+        match builders.split_first_mut() {
+            Some((&mut (ref mut builder, ref layout), rest)) => {
+                builder.create_info(device, layout, |info| {
+                    infos.push(info);
+                    GraphicsPipelineBuilder::create_many(device, cache, rest, infos)
+                })
+            },
+            None => unsafe {
+                let vk = device.pointers();
+                let mut output = vec![0; infos.len()];
+                let result = check_errors(vk.CreateGraphicsPipelines(device.internal_object(),
+                                                                     cache,
+                                                                     infos.len() as u32,
+                                                                     infos.as_ptr(),
+                                                                     ptr::null(),
+                                                                     output.as_mut_ptr()));
+
+                if let Err(err) = result {
+                    // The pipelines that could be created must still be destroyed.
+                    for &pipeline in output.iter().filter(|&&p| p != 0) {
+                        vk.DestroyPipeline(device.internal_object(), pipeline, ptr::null());
+                    }
+                    return Err(err.into());
+                }
+
+                Ok(output)
+            },
+        }
+    }
+
+    // Checks the shader interfaces and builds the pipeline layout corresponding to the union of
+    // the shaders.
+    fn auto_layout(&self, device: &Arc<Device>, dynamic_buffers: &[(usize, usize)])
+                   -> Result<Box<PipelineLayoutAbstract + Send + Sync>,
+                             GraphicsPipelineCreationError> {
         let pipeline_layout;
 
-        if let Some(ref tess) = self.tessellation {
+        if let Some(ref mesh) = self.mesh {
+            if let Err(err) = self.fragment_shader
+                .as_ref()
+                .unwrap()
+                .0
+                .input()
+                .matches(mesh.mesh_shader.0.output())
+            {
+                return Err(GraphicsPipelineCreationError::MeshFragmentStagesMismatch(err));
+            }
+
+            if let Some(ref task) = mesh.task_shader {
+                pipeline_layout = Box::new(
+                    PipelineLayoutDescTweaks::new(mesh.mesh_shader.0.layout().clone()
+                          .union(self.fragment_shader.as_ref().unwrap().0.layout().clone())
+                          .union(task.0.layout().clone()),
+                          dynamic_buffers.into_iter().cloned()
+                    ).build(device.clone()).unwrap()) as Box<_>; // TODO: error
+            } else {
+                pipeline_layout = Box::new(
+                    PipelineLayoutDescTweaks::new(mesh.mesh_shader.0.layout().clone()
+                          .union(self.fragment_shader.as_ref().unwrap().0.layout().clone()),
+                          dynamic_buffers.into_iter().cloned()
+                    ).build(device.clone()).unwrap()) as Box<_>; // TODO: error
+            }
+
+        } else if let Some(ref tess) = self.tessellation {
             if let Some(ref gs) = self.geometry_shader {
                 if let Err(err) = tess.tessellation_control_shader
                     .0
@@ -297,7 +508,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             }
         }
 
-        self.with_pipeline_layout(device, pipeline_layout)
+        Ok(pipeline_layout)
     }
 
     /// Builds the graphics pipeline.
@@ -310,70 +521,128 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                                               GraphicsPipelineCreationError>
         where Pl: PipelineLayoutAbstract
     {
-        // TODO: return errors instead of panicking if missing param
+        self.check_missing_parameters()?;
 
         let vk = device.pointers();
+        let cache = self.cache.as_ref().map(|c| c.internal_object()).unwrap_or(0);
+
+        let pipeline = self.create_info(&device, &pipeline_layout, |infos| unsafe {
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateGraphicsPipelines(device.internal_object(),
+                                                    cache,
+                                                    1,
+                                                    &infos,
+                                                    ptr::null(),
+                                                    &mut output))?;
+            Ok(output)
+        })?;
+
+        Ok(self.into_pipeline(device, pipeline_layout, pipeline))
+    }
+
+    // Checks the parameters of the builder, fills the create info of the pipeline and passes it
+    // to `then`. The structs that the create info points to are only valid during that call.
+    fn create_info<Pl, R, F>(&mut self, device: &Arc<Device>, pipeline_layout: &Pl, then: F)
+                             -> Result<R, GraphicsPipelineCreationError>
+        where Pl: PipelineLayoutAbstract,
+              F: FnOnce(vk::GraphicsPipelineCreateInfo) -> Result<R, GraphicsPipelineCreationError>
+    {
 
         // Checking that the pipeline layout matches the shader stages.
         // TODO: more details in the errors
-        PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
-                                                   self.vertex_shader
-                                                       .as_ref()
-                                                       .unwrap()
-                                                       .0
-                                                       .layout())?;
-        PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
+        if let Some(ref vertex_shader) = self.vertex_shader {
+            PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
+                                                       vertex_shader.0.layout())?;
+        }
+        PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
                                                    self.fragment_shader
                                                        .as_ref()
                                                        .unwrap()
                                                        .0
                                                        .layout())?;
         if let Some(ref geometry_shader) = self.geometry_shader {
-            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
+            PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
                                                        geometry_shader.0.layout())?;
         }
         if let Some(ref tess) = self.tessellation {
-            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
+            PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
                                                        tess.tessellation_control_shader
                                                            .0
                                                            .layout())?;
-            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
+            PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
                                                        tess.tessellation_evaluation_shader
                                                            .0
                                                            .layout())?;
         }
+        if let Some(ref mesh) = self.mesh {
+            PipelineLayoutSuperset::ensure_superset_of(pipeline_layout,
+                                                       mesh.mesh_shader.0.layout())?;
+            if let Some(ref task) = mesh.task_shader {
+                PipelineLayoutSuperset::ensure_superset_of(pipeline_layout, task.0.layout())?;
+            }
+        }
 
         // Check that the subpass can accept the output of the fragment shader.
-        if !RenderPassSubpassInterface::is_compatible_with(&self.render_pass
-                                                               .as_ref()
-                                                               .unwrap()
-                                                               .render_pass(),
-                                                           self.render_pass
-                                                               .as_ref()
-                                                               .unwrap()
-                                                               .index(),
-                                                           self.fragment_shader
-                                                               .as_ref()
-                                                               .unwrap()
-                                                               .0
-                                                               .output())
         {
-            return Err(GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible);
+            let subpass = self.render_pass.as_ref().unwrap();
+            let output = self.fragment_shader.as_ref().unwrap().0.output();
+            RenderPassSubpassInterface::check_compatible_with(&subpass.render_pass(),
+                                                              subpass.index(),
+                                                              output)
+                .map_err(GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible)?;
+        }
+
+        // Check that the input attachments read by the fragment shader are provided by the
+        // subpass, with a matching number of samples.
+        {
+            let subpass = self.render_pass.as_ref().unwrap();
+            let pass_desc = subpass
+                .render_pass()
+                .subpass_desc(subpass.index() as usize)
+                .expect("Wrong RenderPassDesc implementation");
+            let fs_layout = self.fragment_shader.as_ref().unwrap().0.layout();
+
+            for set in 0 .. fs_layout.num_sets() {
+                let num_bindings = fs_layout.num_bindings_in_set(set).unwrap_or(0);
+                for binding in 0 .. num_bindings {
+                    let multisampled = match fs_layout.descriptor(set, binding) {
+                        Some(DescriptorDesc {
+                                 ty: DescriptorDescTy::InputAttachment { multisampled, .. },
+                                 ..
+                             }) => multisampled,
+                        _ => continue,
+                    };
+
+                    let compatible = pass_desc
+                        .input_attachments
+                        .iter()
+                        .filter_map(|&(a, _)| subpass.render_pass().attachment_desc(a))
+                        .any(|a| (a.samples > 1) == multisampled);
+                    if !compatible {
+                        return Err(GraphicsPipelineCreationError::FragmentShaderInputAttachmentsIncompatible {
+                                       set: set,
+                                       binding: binding,
+                                   });
+                    }
+                }
+            }
         }
 
         // Will contain the list of dynamic states. Filled throughout this function.
         let mut dynamic_states: SmallVec<[vk::DynamicState; 8]> = SmallVec::new();
 
         // Creating the specialization constants of the various stages.
-        let vertex_shader_specialization = {
+        let vertex_shader_specialization = if let Some(ref vs) = self.vertex_shader {
             let spec_descriptors = Vss::descriptors();
-            let constants = &self.vertex_shader.as_ref().unwrap().1;
-            vk::SpecializationInfo {
-                mapEntryCount: spec_descriptors.len() as u32,
-                pMapEntries: spec_descriptors.as_ptr() as *const _,
-                dataSize: mem::size_of_val(constants),
-                pData: constants as *const Vss as *const _,
-            }
+            let constants = &vs.1;
+            Some(vk::SpecializationInfo {
+                     mapEntryCount: spec_descriptors.len() as u32,
+                     pMapEntries: spec_descriptors.as_ptr() as *const _,
+                     dataSize: mem::size_of_val(constants),
+                     pData: constants as *const Vss as *const _,
+                 })
+        } else {
+            None
         };
         let tess_shader_specialization = if let Some(ref tess) = self.tessellation {
             let tcs_spec = {
@@ -412,6 +681,31 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         } else {
             None
         };
+        let mesh_shader_specialization = if let Some(ref mesh) = self.mesh {
+            let ts_spec = mesh.task_shader.as_ref().map(|ts| {
+                let spec_descriptors = Tss::descriptors();
+                let constants = &ts.1;
+                vk::SpecializationInfo {
+                    mapEntryCount: spec_descriptors.len() as u32,
+                    pMapEntries: spec_descriptors.as_ptr() as *const _,
+                    dataSize: mem::size_of_val(constants),
+                    pData: constants as *const Tss as *const _,
+                }
+            });
+            let ms_spec = {
+                let spec_descriptors = Mss::descriptors();
+                let constants = &mesh.mesh_shader.1;
+                vk::SpecializationInfo {
+                    mapEntryCount: spec_descriptors.len() as u32,
+                    pMapEntries: spec_descriptors.as_ptr() as *const _,
+                    dataSize: mem::size_of_val(constants),
+                    pData: constants as *const Mss as *const _,
+                }
+            };
+            Some((ts_spec, ms_spec))
+        } else {
+            None
+        };
         let fragment_shader_specialization = {
             let spec_descriptors = Fss::descriptors();
             let constants = &self.fragment_shader.as_ref().unwrap().1;
@@ -427,25 +721,25 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         let stages = {
             let mut stages = SmallVec::<[_; 5]>::new();
 
-            match self.vertex_shader.as_ref().unwrap().0.ty() {
-                GraphicsShaderType::Vertex => {},
-                _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
-            };
+            if let Some(ref vs) = self.vertex_shader {
+                match vs.0.ty() {
+                    GraphicsShaderType::Vertex => {},
+                    _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
+                };
 
-            stages.push(vk::PipelineShaderStageCreateInfo {
-                            sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
-                            pNext: ptr::null(),
-                            flags: 0, // reserved
-                            stage: vk::SHADER_STAGE_VERTEX_BIT,
-                            module: self.vertex_shader
-                                .as_ref()
-                                .unwrap()
-                                .0
-                                .module()
-                                .internal_object(),
-                            pName: self.vertex_shader.as_ref().unwrap().0.name().as_ptr(),
-                            pSpecializationInfo: &vertex_shader_specialization as *const _,
-                        });
+                stages.push(vk::PipelineShaderStageCreateInfo {
+                                sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
+                                pNext: ptr::null(),
+                                flags: 0, // reserved
+                                stage: vk::SHADER_STAGE_VERTEX_BIT,
+                                module: vs.0.module().internal_object(),
+                                pName: vs.0.name().as_ptr(),
+                                pSpecializationInfo: vertex_shader_specialization
+                                    .as_ref()
+                                    .unwrap() as
+                                    *const _,
+                            });
+            }
 
             match self.fragment_shader.as_ref().unwrap().0.ty() {
                 GraphicsShaderType::Fragment => {},
@@ -499,7 +793,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                 };
 
                 match tess.tessellation_evaluation_shader.0.ty() {
-                    GraphicsShaderType::TessellationControl => {},
+                    GraphicsShaderType::TessellationEvaluation => {},
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 };
 
@@ -538,17 +832,101 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                             });
             }
 
+            if let Some(ref mesh) = self.mesh {
+                if !device.loaded_extensions().ext_mesh_shader {
+                    return Err(GraphicsPipelineCreationError::MeshShaderExtensionNotEnabled);
+                }
+
+                match mesh.mesh_shader.0.ty() {
+                    GraphicsShaderType::Mesh => {},
+                    _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
+                };
+
+                stages.push(vk::PipelineShaderStageCreateInfo {
+                                sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
+                                pNext: ptr::null(),
+                                flags: 0, // reserved
+                                stage: vk::SHADER_STAGE_MESH_BIT_EXT,
+                                module: mesh.mesh_shader.0.module().internal_object(),
+                                pName: mesh.mesh_shader.0.name().as_ptr(),
+                                pSpecializationInfo: &mesh_shader_specialization
+                                    .as_ref()
+                                    .unwrap()
+                                    .1 as
+                                    *const _,
+                            });
+
+                if let Some(ref ts) = mesh.task_shader {
+                    if !device.supports_task_shaders() {
+                        return Err(GraphicsPipelineCreationError::TaskShaderFeatureNotEnabled);
+                    }
+
+                    match ts.0.ty() {
+                        GraphicsShaderType::Task => {},
+                        _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
+                    };
+
+                    stages.push(vk::PipelineShaderStageCreateInfo {
+                                    sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
+                                    pNext: ptr::null(),
+                                    flags: 0, // reserved
+                                    stage: vk::SHADER_STAGE_TASK_BIT_EXT,
+                                    module: ts.0.module().internal_object(),
+                                    pName: ts.0.name().as_ptr(),
+                                    pSpecializationInfo: mesh_shader_specialization
+                                        .as_ref()
+                                        .unwrap()
+                                        .0
+                                        .as_ref()
+                                        .unwrap() as
+                                        *const _,
+                                });
+                }
+            }
+
             stages
         };
 
+        // Check that the features and extensions required by the capabilities of the shaders are
+        // enabled.
+        {
+            let mut modules = SmallVec::<[_; 5]>::new();
+            if let Some(ref vs) = self.vertex_shader {
+                modules.push(vs.0.module());
+            }
+            modules.push(self.fragment_shader.as_ref().unwrap().0.module());
+            if let Some(ref gs) = self.geometry_shader {
+                modules.push(gs.0.module());
+            }
+            if let Some(ref tess) = self.tessellation {
+                modules.push(tess.tessellation_control_shader.0.module());
+                modules.push(tess.tessellation_evaluation_shader.0.module());
+            }
+            if let Some(ref mesh) = self.mesh {
+                modules.push(mesh.mesh_shader.0.module());
+                if let Some(ref ts) = mesh.task_shader {
+                    modules.push(ts.0.module());
+                }
+            }
+
+            for module in modules {
+                module.check_features(device.enabled_features())?;
+                module.check_extensions(device.loaded_extensions())?;
+            }
+        }
+
         // Vertex bindings.
-        let (binding_descriptions, attribute_descriptions) = {
-            let (buffers_iter, attribs_iter) =
-                self.vertex_input
-                    .definition(self.vertex_shader.as_ref().unwrap().0.input())?;
+        let (buffers, attribs): (Vec<_>, Vec<_>) = if let Some(ref vs) = self.vertex_shader {
+            let (buffers_iter, attribs_iter) = self.vertex_input.definition(vs.0.input())?;
+            (buffers_iter.collect(), attribs_iter.collect())
+        } else {
+            // Mesh pipelines have no vertex input.
+            (Vec::new(), Vec::new())
+        };
 
+        let (binding_descriptions, attribute_descriptions) = {
             let mut binding_descriptions = SmallVec::<[_; 8]>::new();
-            for (num, stride, rate) in buffers_iter {
+            for (num, stride, rate) in buffers {
                 if stride >
                     device
                         .physical_device()
@@ -570,7 +948,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             }
 
             let mut attribute_descriptions = SmallVec::<[_; 8]>::new();
-            for (loc, binding, info) in attribs_iter {
+            for (loc, binding, info) in attribs {
                 // TODO: check attribute format support
 
                 if info.offset >
@@ -651,11 +1029,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                        });
         }
 
-        // TODO: should check from the tess eval shader instead of the input assembly
+        if self.input_assembly_topology.has_adjacency() &&
+            !device.enabled_features().geometry_shader
+        {
+            return Err(GraphicsPipelineCreationError::GeometryShaderFeatureNotEnabled);
+        }
+
         if let Some(ref gs) = self.geometry_shader {
             match gs.0.ty() {
                 GraphicsShaderType::Geometry(primitives) => {
-                    if !primitives.matches(self.input_assembly_topology) {
+                    // If tessellation is enabled, the geometry shader receives the primitives
+                    // generated by the tessellation evaluation shader and not the ones of the
+                    // input assembly, which are always patches.
+                    // TODO: should check from the tess eval shader instead
+                    if self.tessellation.is_none() &&
+                        !primitives.matches(self.input_assembly_topology)
+                    {
                         return Err(GraphicsPipelineCreationError::TopologyNotMatchingGeometryShader);
                     }
                 },
@@ -665,7 +1054,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
 
         let tessellation = match self.input_assembly_topology {
             PrimitiveTopology::PatchList { vertices_per_patch } => {
-                if self.tessellation.is_none() {
+                if self.tessellation.is_none() || vertices_per_patch == 0 {
                     return Err(GraphicsPipelineCreationError::InvalidPrimitiveTopology);
                 }
                 if vertices_per_patch >
@@ -726,6 +1115,10 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             },
         };
 
+        if vp_num == 0 {
+            return Err(GraphicsPipelineCreationError::ZeroViewports);
+        }
+
         if vp_num > 1 && !device.enabled_features().multi_viewport {
             return Err(GraphicsPipelineCreationError::MultiViewportFeatureNotEnabled);
         }
@@ -806,9 +1199,47 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             return Err(GraphicsPipelineCreationError::FillModeNonSolidFeatureNotEnabled);
         }
 
+        let conservative = if self.raster.conservative_mode !=
+            ConservativeRasterizationMode::Disabled
+        {
+            if !device.loaded_extensions().ext_conservative_rasterization {
+                return Err(GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled);
+            }
+
+            let properties =
+                ConservativeRasterizationProperties::from_physical_device(device.physical_device());
+            // Geometry and tessellation shaders can change the kind of primitives, so only the
+            // input topology is checked when there are none.
+            let points_or_lines = self.raster.polygon_mode != PolygonMode::Fill ||
+                (self.geometry_shader.is_none() && self.tessellation.is_none() &&
+                     match self.input_assembly_topology {
+                         PrimitiveTopology::PointList |
+                         PrimitiveTopology::LineList |
+                         PrimitiveTopology::LineStrip |
+                         PrimitiveTopology::LineListWithAdjacency |
+                         PrimitiveTopology::LineStripWithAdjacency => true,
+                         _ => false,
+                     });
+            check_conservative_rasterization(&self.raster, points_or_lines, &properties)?;
+
+            Some(vk::PipelineRasterizationConservativeStateCreateInfoEXT {
+                     sType: vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_CONSERVATIVE_STATE_CREATE_INFO_EXT,
+                     pNext: ptr::null(),
+                     flags: 0, // reserved
+                     conservativeRasterizationMode: self.raster.conservative_mode as u32,
+                     extraPrimitiveOverestimationSize: self.raster
+                         .extra_primitive_overestimation_size,
+                 })
+        } else {
+            None
+        };
+
         let rasterization = vk::PipelineRasterizationStateCreateInfo {
             sType: vk::STRUCTURE_TYPE_PIPELINE_RASTERIZATION_STATE_CREATE_INFO,
-            pNext: ptr::null(),
+            pNext: conservative
+                .as_ref()
+                .map(|c| c as *const _ as *const _)
+                .unwrap_or(ptr::null()),
             flags: 0, // reserved
             depthClampEnable: if self.raster.depth_clamp {
                 vk::TRUE
@@ -835,6 +1266,23 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             .unwrap()
             .num_samples()
             .unwrap_or(1);
+        {
+            let subpass = self.render_pass.as_ref().unwrap();
+            let pass_desc = subpass
+                .render_pass()
+                .subpass_desc(subpass.index() as usize)
+                .expect("Wrong RenderPassDesc implementation");
+            let mismatch = pass_desc
+                .color_attachments
+                .iter()
+                .chain(pass_desc.depth_stencil.iter())
+                .filter_map(|&(a, _)| subpass.render_pass().attachment_desc(a))
+                .any(|a| a.samples != self.multisample.rasterizationSamples);
+            if mismatch {
+                return Err(GraphicsPipelineCreationError::MismatchedAttachmentSamples);
+            }
+        }
+        self.multisample.pSampleMask = self.sample_mask.as_ptr();
         if self.multisample.sampleShadingEnable != vk::FALSE {
             debug_assert!(self.multisample.minSampleShading >= 0.0 &&
                               self.multisample.minSampleShading <= 1.0);
@@ -856,6 +1304,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                         return Err(GraphicsPipelineCreationError::DepthBoundsFeatureNotEnabled);
                     }
 
+                    debug_assert!(range.start >= 0.0 && range.start <= 1.0);
+                    debug_assert!(range.end >= 0.0 && range.end <= 1.0);
+
                     (vk::TRUE, range.start, range.end)
                 },
                 DepthBounds::Dynamic => {
@@ -908,6 +1359,12 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                 return Err(GraphicsPipelineCreationError::NoDepthAttachment);
             }
 
+            if self.depth_stencil.depth_bounds_test != DepthBounds::Disabled &&
+                !self.render_pass.as_ref().unwrap().has_depth()
+            {
+                return Err(GraphicsPipelineCreationError::NoDepthAttachment);
+            }
+
             if (!self.depth_stencil.stencil_front.always_keep() ||
                     !self.depth_stencil.stencil_back.always_keep()) &&
                 !self.render_pass.as_ref().unwrap().has_stencil()
@@ -915,7 +1372,16 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                 return Err(GraphicsPipelineCreationError::NoStencilAttachment);
             }
 
-            // FIXME: stencil writability
+            let stencil_writes = |stencil: &Stencil| {
+                !stencil.always_keep() && stencil.write_mask != Some(0)
+            };
+
+            if (stencil_writes(&self.depth_stencil.stencil_front) ||
+                    stencil_writes(&self.depth_stencil.stencil_back)) &&
+                !self.render_pass.as_ref().unwrap().has_writable_stencil()
+            {
+                return Err(GraphicsPipelineCreationError::NoStencilAttachment);
+            }
 
             vk::PipelineDepthStencilStateCreateInfo {
                 sType: vk::STRUCTURE_TYPE_PIPELINE_DEPTH_STENCIL_STATE_CREATE_INFO,
@@ -981,12 +1447,12 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             let num_atch = self.render_pass.as_ref().unwrap().num_color_attachments();
 
             match self.blend.attachments {
-                AttachmentsBlend::Collective(blend) => {
+                AttachmentsBlend::Collective(ref blend) => {
                     (0 .. num_atch)
                         .map(|_| blend.clone().into_vulkan_state())
                         .collect()
                 },
-                AttachmentsBlend::Individual(blend) => {
+                AttachmentsBlend::Individual(ref blend) => {
                     if blend.len() != num_atch as usize {
                         return Err(GraphicsPipelineCreationError::MismatchBlendingAttachmentsCount);
                     }
@@ -1026,6 +1492,19 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             },
         };
 
+        if let Some(ref cache) = self.cache {
+            assert_eq!(cache.device().internal_object(), device.internal_object());
+        }
+
+        if let Some(ref base_pipeline) = self.base_pipeline {
+            assert_eq!(base_pipeline.device().internal_object(),
+                       device.internal_object());
+
+            if !base_pipeline.allows_derivatives() {
+                return Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed);
+            }
+        }
+
         let dynamic_states = if !dynamic_states.is_empty() {
             Some(vk::PipelineDynamicStateCreateInfo {
                      sType: vk::STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO,
@@ -1038,15 +1517,31 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             None
         };
 
-        let pipeline = unsafe {
+        {
+            let mut flags = 0;
+            if self.allow_derivatives {
+                flags |= vk::PIPELINE_CREATE_ALLOW_DERIVATIVES_BIT;
+            }
+            if self.base_pipeline.is_some() {
+                flags |= vk::PIPELINE_CREATE_DERIVATIVE_BIT;
+            }
+
             let infos = vk::GraphicsPipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: 0, // TODO: some flags are available but none are critical
+                flags: flags,
                 stageCount: stages.len() as u32,
                 pStages: stages.as_ptr(),
-                pVertexInputState: &vertex_input_state,
-                pInputAssemblyState: &self.input_assembly,
+                pVertexInputState: if self.mesh.is_some() {
+                    ptr::null()
+                } else {
+                    &vertex_input_state
+                },
+                pInputAssemblyState: if self.mesh.is_some() {
+                    ptr::null()
+                } else {
+                    &self.input_assembly
+                },
                 pTessellationState: tessellation
                     .as_ref()
                     .map(|t| t as *const _)
@@ -1060,7 +1555,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                     .as_ref()
                     .map(|s| s as *const _)
                     .unwrap_or(ptr::null()),
-                layout: PipelineLayoutAbstract::sys(&pipeline_layout).internal_object(),
+                layout: PipelineLayoutAbstract::sys(pipeline_layout).internal_object(),
                 renderPass: self.render_pass
                     .as_ref()
                     .unwrap()
@@ -1068,26 +1563,28 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                     .inner()
                     .internal_object(),
                 subpass: self.render_pass.as_ref().unwrap().index(),
-                basePipelineHandle: 0, // TODO:
-                basePipelineIndex: -1, // TODO:
+                basePipelineHandle: self.base_pipeline
+                    .as_ref()
+                    .map(|p| GraphicsPipelineAbstract::inner(&**p).internal_object())
+                    .unwrap_or(0),
+                basePipelineIndex: -1,
             };
 
-            let mut output = mem::uninitialized();
-            check_errors(vk.CreateGraphicsPipelines(device.internal_object(),
-                                                    0,
-                                                    1,
-                                                    &infos,
-                                                    ptr::null(),
-                                                    &mut output))?;
-            output
-        };
+            then(infos)
+        }
+    }
 
+    // Wraps a pipeline created from the create info of this builder.
+    fn into_pipeline<Pl>(mut self, device: Arc<Device>, pipeline_layout: Pl,
+                         pipeline: vk::Pipeline)
+                         -> GraphicsPipeline<Vdef, Pl, Rp> {
         let (render_pass, render_pass_subpass) = self.render_pass.take().unwrap().into();
 
-        Ok(GraphicsPipeline {
+        GraphicsPipeline {
                inner: GraphicsPipelineInner {
-                   device: device.clone(),
+                   device: device,
                    pipeline: pipeline,
+                   needs_destruction: true,
                },
                layout: pipeline_layout,
 
@@ -1107,35 +1604,82 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                dynamic_blend_constants: self.blend.blend_constants.is_none(),
 
                num_viewports: self.viewport.as_ref().unwrap().num_viewports(),
-           })
-    }
 
-    // TODO: add build_with_cache method
+               allow_derivatives: self.allow_derivatives,
+
+               mesh_shading: self.mesh.is_some(),
+               task_shader: self.mesh.as_ref().map(|m| m.task_shader.is_some()).unwrap_or(false),
+           }
+    }
 }
 
-impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
-    GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> {
-    // TODO: add pipeline derivate system
+impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss, Fs, Fss, Rp>
+    GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                            Fs, Fss, Rp> {
+    /// Allows other pipelines to be derived from the pipeline that is going to be built.
+    ///
+    /// Creating a pipeline that allows derivatives may be slightly slower, but creating the
+    /// derivatives of this pipeline is then faster.
+    #[inline]
+    pub fn allow_derivatives(mut self) -> Self {
+        self.allow_derivatives = true;
+        self
+    }
 
-    /// Sets the vertex input.
+    /// Creates the pipeline as a derivative of `parent`. The implementation can then reuse the
+    /// work done when creating `parent`, which is useful if both pipelines share most of their
+    /// state.
+    ///
+    /// `parent` must have been created with `allow_derivatives`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics when building if `parent` doesn't belong to the same device as the pipeline.
+    ///
     #[inline]
-    pub fn vertex_input<T>(
-        self, vertex_input: T)
-        -> GraphicsPipelineBuilder<T, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> {
-        GraphicsPipelineBuilder {
-            vertex_input: vertex_input,
-            vertex_shader: self.vertex_shader,
-            input_assembly: self.input_assembly,
-            input_assembly_topology: self.input_assembly_topology,
+    pub fn derive_from(mut self, parent: Arc<GraphicsPipelineAbstract + Send + Sync>) -> Self {
+        self.base_pipeline = Some(parent);
+        self
+    }
+
+    /// Uses `cache` when building the pipeline. The implementation can reuse the data of the
+    /// cache to build the pipeline faster, and stores the result of the compilation in the cache.
+    ///
+    /// # Panic
+    ///
+    /// - Panics when building if `cache` doesn't belong to the same device as the pipeline.
+    ///
+    #[inline]
+    pub fn pipeline_cache(mut self, cache: Arc<PipelineCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Sets the vertex input.
+    #[inline]
+    pub fn vertex_input<T>(
+        self, vertex_input: T)
+        -> GraphicsPipelineBuilder<T, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                   Fs, Fss, Rp> {
+        GraphicsPipelineBuilder {
+            vertex_input: vertex_input,
+            vertex_shader: self.vertex_shader,
+            input_assembly: self.input_assembly,
+            input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation,
             geometry_shader: self.geometry_shader,
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 
@@ -1154,6 +1698,10 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                                                                     Tess,
                                                                     Gs,
                                                                     Gss,
+                                                                    Ts,
+                                                                    Tss,
+                                                                    Ms,
+                                                                    Mss,
                                                                     Fs,
                                                                     Fss,
                                                                     Rp> {
@@ -1165,7 +1713,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     #[inline]
     pub fn vertex_shader<Vs2, Vss2>(
         self, shader: Vs2, specialization_constants: Vss2)
-        -> GraphicsPipelineBuilder<Vdef, Vs2, Vss2, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
+        -> GraphicsPipelineBuilder<Vdef, Vs2, Vss2, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                   Fs, Fss, Rp>
         where Vs2: GraphicsEntryPointAbstract<SpecializationConstants = Vss2>,
               Vss2: SpecializationConstants
     {
@@ -1176,17 +1725,26 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation,
             geometry_shader: self.geometry_shader,
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 
     /// Sets whether primitive restart if enabled.
+    ///
+    /// When enabled, the special index value `0xffff` or `0xffffffff` ends the current primitive
+    /// during indexed draws and a new primitive starts at the next index. Only strip and fan
+    /// topologies support primitive restart.
     #[inline]
     pub fn primitive_restart(mut self, enabled: bool) -> Self {
         self.input_assembly.primitiveRestartEnable = if enabled {
@@ -1299,6 +1857,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     /// Sets the topology of the primitives to a list of patches. Can only be used and must be used
     /// with a tessellation shader.
     ///
+    /// `vertices_per_patch` must be superior to 0 and inferior or equal to the
+    /// `max_tessellation_patch_size` limit of the physical device.
+    ///
     /// > **Note**: This is equivalent to
     /// > `self.primitive_topology(PrimitiveTopology::PatchList { vertices_per_patch })`.
     #[inline]
@@ -1313,7 +1874,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self, tessellation_control_shader: Tcs2,
         tessellation_control_shader_spec_constants: Tcss2, tessellation_evaluation_shader: Tes2,
         tessellation_evaluation_shader_spec_constants: Tess2)
-        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs2, Tcss2, Tes2, Tess2, Gs, Gss, Fs, Fss, Rp>
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs2, Tcss2, Tes2, Tess2, Gs, Gss, Ts, Tss, Ms,
+                                   Mss, Fs, Fss, Rp>
         where Tcs2: GraphicsEntryPointAbstract<SpecializationConstants = Tcss2>,
               Tes2: GraphicsEntryPointAbstract<SpecializationConstants = Tess2>,
               Tcss2: SpecializationConstants,
@@ -1333,13 +1895,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                                         tessellation_evaluation_shader_spec_constants),
                                }),
             geometry_shader: self.geometry_shader,
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 
@@ -1355,7 +1922,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     #[inline]
     pub fn geometry_shader<Gs2, Gss2>(
         self, shader: Gs2, specialization_constants: Gss2)
-        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs2, Gss2, Fs, Fss, Rp>
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs2, Gss2, Ts, Tss, Ms, Mss,
+                                   Fs, Fss, Rp>
         where Gs2: GraphicsEntryPointAbstract<SpecializationConstants = Gss2>,
               Gss2: SpecializationConstants
     {
@@ -1366,13 +1934,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation,
             geometry_shader: Some((shader, specialization_constants)),
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 
@@ -1383,6 +1956,94 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the mesh shader to use, without any task shader.
+    ///
+    /// The mesh shader replaces the vertex input, vertex, tessellation and geometry stages, which
+    /// must then be left disabled. The pipeline can only be used with `draw_mesh_tasks`.
+    ///
+    /// Requires the `ext_mesh_shader` extension to be enabled on the device.
+    // TODO: correct specialization constants
+    #[inline]
+    pub fn mesh_shader<Ms2, Mss2>(
+        self, shader: Ms2, specialization_constants: Mss2)
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss,
+                                   EmptyEntryPointDummy, (), Ms2, Mss2, Fs, Fss, Rp>
+        where Ms2: GraphicsEntryPointAbstract<SpecializationConstants = Mss2>,
+              Mss2: SpecializationConstants
+    {
+        GraphicsPipelineBuilder {
+            vertex_input: self.vertex_input,
+            vertex_shader: self.vertex_shader,
+            input_assembly: self.input_assembly,
+            input_assembly_topology: self.input_assembly_topology,
+            tessellation: self.tessellation,
+            geometry_shader: self.geometry_shader,
+            mesh: Some(MeshInfo {
+                           task_shader: None,
+                           mesh_shader: (shader, specialization_constants),
+                       }),
+            viewport: self.viewport,
+            raster: self.raster,
+            multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shader: self.fragment_shader,
+            depth_stencil: self.depth_stencil,
+            blend: self.blend,
+            render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
+        }
+    }
+
+    /// Sets the task shader and the mesh shader to use.
+    ///
+    /// Same as `mesh_shader`, except that the task shader runs first and decides how many mesh
+    /// shader workgroups are launched.
+    // TODO: correct specialization constants
+    #[inline]
+    pub fn task_and_mesh_shaders<Ts2, Tss2, Ms2, Mss2>(
+        self, task_shader: Ts2, task_shader_spec_constants: Tss2, shader: Ms2,
+        specialization_constants: Mss2)
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts2, Tss2, Ms2,
+                                   Mss2, Fs, Fss, Rp>
+        where Ts2: GraphicsEntryPointAbstract<SpecializationConstants = Tss2>,
+              Tss2: SpecializationConstants,
+              Ms2: GraphicsEntryPointAbstract<SpecializationConstants = Mss2>,
+              Mss2: SpecializationConstants
+    {
+        GraphicsPipelineBuilder {
+            vertex_input: self.vertex_input,
+            vertex_shader: self.vertex_shader,
+            input_assembly: self.input_assembly,
+            input_assembly_topology: self.input_assembly_topology,
+            tessellation: self.tessellation,
+            geometry_shader: self.geometry_shader,
+            mesh: Some(MeshInfo {
+                           task_shader: Some((task_shader, task_shader_spec_constants)),
+                           mesh_shader: (shader, specialization_constants),
+                       }),
+            viewport: self.viewport,
+            raster: self.raster,
+            multisample: self.multisample,
+            sample_mask: self.sample_mask,
+            fragment_shader: self.fragment_shader,
+            depth_stencil: self.depth_stencil,
+            blend: self.blend,
+            render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
+        }
+    }
+
+    /// Sets the task and mesh shader stages as disabled. This is the default.
+    #[inline]
+    pub fn mesh_shaders_disabled(mut self) -> Self {
+        self.mesh = None;
+        self
+    }
+
     /// Sets the viewports to some value, and the scissor boxes to boxes that always cover the
     /// whole viewport.
     #[inline]
@@ -1393,6 +2054,10 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     }
 
     /// Sets the characteristics of viewports and scissor boxes in advance.
+    ///
+    /// Using more than one viewport requires the `multi_viewport` feature to be enabled on the
+    /// device, and the number of viewports can't exceed the `max_viewports` limit. The geometry
+    /// shader chooses the viewport of each primitive by writing to `gl_ViewportIndex`.
     #[inline]
     pub fn viewports_scissors<I>(mut self, viewports: I) -> Self
         where I: IntoIterator<Item = (Viewport, Scissor)>
@@ -1444,6 +2109,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     /// If true, then the depth value of the vertices will be clamped to the range `[0.0 ; 1.0]`.
     /// If false, fragments whose depth is outside of this range will be discarded before the
     /// fragment shader even runs.
+    ///
+    /// Enabling depth clamping requires the `depth_clamp` feature to be enabled on the device.
     #[inline]
     pub fn depth_clamp(mut self, clamp: bool) -> Self {
         self.raster.depth_clamp = clamp;
@@ -1519,6 +2186,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     }
 
     /// Sets the polygon mode to "line". Triangles will each be turned into three lines.
+    ///
+    /// Requires the `fill_mode_non_solid` feature to be enabled on the device.
     #[inline]
     pub fn polygon_mode_line(mut self) -> Self {
         self.raster.polygon_mode = PolygonMode::Line;
@@ -1526,6 +2195,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     }
 
     /// Sets the polygon mode to "point". Triangles and lines will each be turned into three points.
+    ///
+    /// Requires the `fill_mode_non_solid` feature to be enabled on the device.
     #[inline]
     pub fn polygon_mode_point(mut self) -> Self {
         self.raster.polygon_mode = PolygonMode::Point;
@@ -1533,6 +2204,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     }
 
     /// Sets the width of the lines, if the GPU needs to draw lines. The default is `1.0`.
+    ///
+    /// Any value other than `1.0` requires the `wide_lines` feature to be enabled on the device.
     #[inline]
     pub fn line_width(mut self, value: f32) -> Self {
         self.raster.line_width = Some(value);
@@ -1547,7 +2220,66 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: missing DepthBiasControl
+    /// Enables conservative rasterization in overestimate mode. A fragment is generated for every
+    /// pixel that is even partially covered by a primitive, after enlarging the primitive by
+    /// `extra_size` pixels.
+    ///
+    /// Requires the `ext_conservative_rasterization` extension to be enabled on the device.
+    #[inline]
+    pub fn conservative_rasterization_overestimate(mut self, extra_size: f32) -> Self {
+        self.raster.conservative_mode = ConservativeRasterizationMode::Overestimate;
+        self.raster.extra_primitive_overestimation_size = extra_size;
+        self
+    }
+
+    /// Enables conservative rasterization in underestimate mode. Fragments are only generated for
+    /// pixels that are fully covered by a primitive.
+    ///
+    /// Requires the `ext_conservative_rasterization` extension to be enabled on the device.
+    #[inline]
+    pub fn conservative_rasterization_underestimate(mut self) -> Self {
+        self.raster.conservative_mode = ConservativeRasterizationMode::Underestimate;
+        self
+    }
+
+    /// Disables conservative rasterization. This is the default.
+    #[inline]
+    pub fn conservative_rasterization_disabled(mut self) -> Self {
+        self.raster.conservative_mode = ConservativeRasterizationMode::Disabled;
+        self
+    }
+
+    /// Enables depth bias with fixed values.
+    ///
+    /// The depth of each fragment is offset by `constant_factor` (in units of the smallest
+    /// representable depth difference) plus `slope_factor` multiplied by the maximum depth slope
+    /// of the polygon. If `clamp` is not `0.0`, the bias is then clamped to this value, which
+    /// requires the `depth_bias_clamp` feature to be enabled on the device.
+    ///
+    /// Depth bias only applies to polygons, and not to points or lines.
+    #[inline]
+    pub fn depth_bias(mut self, constant_factor: f32, clamp: f32, slope_factor: f32) -> Self {
+        self.raster.depth_bias = DepthBiasControl::Static(DepthBias {
+                                                              constant_factor: constant_factor,
+                                                              clamp: clamp,
+                                                              slope_factor: slope_factor,
+                                                          });
+        self
+    }
+
+    /// Enables depth bias, with the values provided when drawing.
+    #[inline]
+    pub fn depth_bias_dynamic(mut self) -> Self {
+        self.raster.depth_bias = DepthBiasControl::Dynamic;
+        self
+    }
+
+    /// Disables depth bias. This is the default.
+    #[inline]
+    pub fn depth_bias_disabled(mut self) -> Self {
+        self.raster.depth_bias = DepthBiasControl::Disabled;
+        self
+    }
 
     /// Disables sample shading. The fragment shader will only be run once per fragment (ie. per
     /// pixel) and not once by sample. The output will then be copied in all of the covered
@@ -1584,13 +2316,20 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: doc
+    /// Disables alpha-to-coverage.
+    ///
+    /// Alpha-to-coverage is disabled by default.
+    #[inline]
     pub fn alpha_to_coverage_disabled(mut self) -> Self {
         self.multisample.alphaToCoverageEnable = vk::FALSE;
         self
     }
 
-    // TODO: doc
+    /// Enables alpha-to-coverage. The alpha component of the first color output of the fragment
+    /// shader is used to determine which samples of the fragment are covered.
+    ///
+    /// Alpha-to-coverage is disabled by default.
+    #[inline]
     pub fn alpha_to_coverage_enabled(mut self) -> Self {
         self.multisample.alphaToCoverageEnable = vk::TRUE;
         self
@@ -1617,7 +2356,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: rasterizationSamples and pSampleMask
+    /// Sets the sample mask. Bit `n` of `mask` corresponds to sample `n` of each fragment, and
+    /// samples whose bit is 0 are considered as not covered. Bits beyond the number of samples of
+    /// the subpass are ignored.
+    ///
+    /// The number of samples is automatically determined from the attachments of the subpass.
+    ///
+    /// All the samples are enabled by default.
+    #[inline]
+    pub fn sample_mask(mut self, mask: u64) -> Self {
+        self.sample_mask = [mask as u32, (mask >> 32) as u32];
+        self
+    }
 
     /// Sets the fragment shader to use.
     ///
@@ -1626,7 +2376,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     #[inline]
     pub fn fragment_shader<Fs2, Fss2>(
         self, shader: Fs2, specialization_constants: Fss2)
-        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs2, Fss2, Rp>
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                   Fs2, Fss2, Rp>
         where Fs2: GraphicsEntryPointAbstract<SpecializationConstants = Fss2>,
               Fss2: SpecializationConstants
     {
@@ -1637,13 +2388,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation,
             geometry_shader: self.geometry_shader,
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: Some((shader, specialization_constants)),
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 
@@ -1681,8 +2437,66 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
-    // TODO: missing tons of depth-stencil stuff
+    /// Sets the comparison operator of the depth test.
+    ///
+    /// Passing `Compare::Always` together with `depth_write(false)` disables the depth test.
+    #[inline]
+    pub fn depth_compare(mut self, compare: Compare) -> Self {
+        self.depth_stencil.depth_compare = compare;
+        self
+    }
+
+    /// Enables the depth bounds test with a fixed range of values.
+    ///
+    /// Fragments whose value in the depth buffer is outside of `range` are discarded. Both bounds
+    /// must be between 0.0 and 1.0.
+    ///
+    /// The `depth_bounds` feature must be enabled on the device.
+    #[inline]
+    pub fn depth_bounds(mut self, range: Range<f32>) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Fixed(range);
+        self
+    }
+
+    /// Enables the depth bounds test, with the range of values provided at each draw command.
+    ///
+    /// The `depth_bounds` feature must be enabled on the device.
+    #[inline]
+    pub fn depth_bounds_dynamic(mut self) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Dynamic;
+        self
+    }
+
+    /// Disables the depth bounds test. This is the default.
+    #[inline]
+    pub fn depth_bounds_disabled(mut self) -> Self {
+        self.depth_stencil.depth_bounds_test = DepthBounds::Disabled;
+        self
+    }
+
+    /// Sets the stencil test and operations for both front-facing and back-facing polygons.
+    #[inline]
+    pub fn stencil(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_front = stencil;
+        self.depth_stencil.stencil_back = stencil;
+        self
+    }
 
+    /// Sets the stencil test and operations for front-facing polygons.
+    #[inline]
+    pub fn stencil_front(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_front = stencil;
+        self
+    }
+
+    /// Sets the stencil test and operations for back-facing polygons.
+    ///
+    /// Points and lines are always considered front-facing.
+    #[inline]
+    pub fn stencil_back(mut self, stencil: Stencil) -> Self {
+        self.depth_stencil.stencil_back = stencil;
+        self
+    }
 
     #[inline]
     pub fn blend_collective(mut self, blend: AttachmentBlend) -> Self {
@@ -1745,7 +2559,8 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     #[inline]
     pub fn render_pass<Rp2>(
         self, subpass: Subpass<Rp2>)
-        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp2> {
+        -> GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                   Fs, Fss, Rp2> {
         GraphicsPipelineBuilder {
             vertex_input: self.vertex_input,
             vertex_shader: self.vertex_shader,
@@ -1753,19 +2568,25 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation,
             geometry_shader: self.geometry_shader,
+            mesh: self.mesh,
             viewport: self.viewport,
             raster: self.raster,
             multisample: self.multisample,
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader,
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: Some(subpass),
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
+            cache: self.cache,
         }
     }
 }
 
-impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> Clone
-    for GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
+impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss, Fs, Fss, Rp> Clone
+    for GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Ts, Tss, Ms, Mss,
+                                Fs, Fss, Rp>
     where Vdef: Clone,
           Vs: Clone,
           Vss: Clone,
@@ -1775,6 +2596,10 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> Clone
           Tess: Clone,
           Gs: Clone,
           Gss: Clone,
+          Ts: Clone,
+          Tss: Clone,
+          Ms: Clone,
+          Mss: Clone,
           Fs: Clone,
           Fss: Clone,
           Rp: Clone
@@ -1787,6 +2612,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> Clone
             input_assembly_topology: self.input_assembly_topology,
             tessellation: self.tessellation.clone(),
             geometry_shader: self.geometry_shader.clone(),
+            mesh: self.mesh.clone(),
             viewport: self.viewport.clone(),
             raster: self.raster.clone(),
             multisample: vk::PipelineMultisampleStateCreateInfo {
@@ -1800,10 +2626,1063 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> Clone
                 alphaToCoverageEnable: self.multisample.alphaToCoverageEnable,
                 alphaToOneEnable: self.multisample.alphaToOneEnable,
             },
+            sample_mask: self.sample_mask,
             fragment_shader: self.fragment_shader.clone(),
             depth_stencil: self.depth_stencil.clone(),
             blend: self.blend.clone(),
             render_pass: self.render_pass.clone(),
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+/// Checks the conservative rasterization state against the properties of the device.
+fn check_conservative_rasterization(raster: &Rasterization, points_or_lines: bool,
+                                    properties: &ConservativeRasterizationProperties)
+                                    -> Result<(), GraphicsPipelineCreationError> {
+    match raster.conservative_mode {
+        ConservativeRasterizationMode::Disabled => return Ok(()),
+        ConservativeRasterizationMode::Overestimate => {
+            let extra = raster.extra_primitive_overestimation_size;
+            if !(extra >= 0.0 && extra <= properties.max_extra_primitive_overestimation_size) {
+                return Err(
+                    GraphicsPipelineCreationError::ExtraPrimitiveOverestimationSizeOutOfRange,
+                );
+            }
+        },
+        ConservativeRasterizationMode::Underestimate if !properties.primitive_underestimation => {
+            return Err(
+                GraphicsPipelineCreationError::ConservativeRasterizationUnderestimateNotSupported,
+            );
+        },
+        ConservativeRasterizationMode::Underestimate => (),
+    }
+
+    if points_or_lines && !properties.conservative_point_and_line_rasterization {
+        return Err(
+            GraphicsPipelineCreationError::ConservativePointAndLineRasterizationNotSupported,
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_conservative_rasterization;
+    use VulkanObject;
+    use format::ClearValue;
+    use format::Format;
+    use framebuffer::LayoutAttachmentDescription;
+    use framebuffer::LayoutPassDependencyDescription;
+    use framebuffer::LayoutPassDescription;
+    use framebuffer::LoadOp;
+    use framebuffer::RenderPass;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::RenderPassDescClearValues;
+    use framebuffer::StoreOp;
+    use framebuffer::Subpass;
+    use image::ImageLayout;
+    use pipeline::GraphicsPipeline;
+    use pipeline::GraphicsPipelineAbstract;
+    use pipeline::GraphicsPipelineCreationError;
+    use pipeline::GraphicsPipelineDynamicStates;
+    use pipeline::cache::PipelineCache;
+    use pipeline::depth_stencil::Compare;
+    use pipeline::depth_stencil::DepthBounds;
+    use pipeline::depth_stencil::Stencil;
+    use pipeline::depth_stencil::StencilOp;
+    use pipeline::graphics_pipeline::GraphicsPipelineBuilder;
+    use pipeline::raster::ConservativeRasterizationMode;
+    use pipeline::raster::ConservativeRasterizationProperties;
+    use pipeline::raster::DepthBiasControl;
+    use pipeline::raster::Rasterization;
+    use pipeline::shader::GeometryShaderExecutionMode;
+    use pipeline::shader::GraphicsShaderType;
+    use pipeline::shader::ShaderModule;
+    use pipeline::vertex::BufferlessDefinition;
+    use std::sync::Arc;
+    use tests::FRAGMENT_MAIN;
+    use tests::MESH_MAIN;
+    use tests::VERTEX_MAIN;
+    use tests::main_entry_point;
+
+    #[test]
+    fn mesh_shader_with_vertex_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let ms = unsafe { ShaderModule::from_words(device.clone(), &MESH_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .mesh_shader(main_entry_point(&ms, GraphicsShaderType::Mesh), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::MeshShaderWithVertexStages) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn mesh_shader_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let ms = unsafe { ShaderModule::from_words(device.clone(), &MESH_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .mesh_shader(main_entry_point(&ms, GraphicsShaderType::Mesh), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::MeshShaderExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn adjacency_without_geometry_shader_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .triangle_list_with_adjacency()
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::GeometryShaderFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn zero_viewports() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(0)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::ZeroViewports) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn build_many_and_derive() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let builder = || {
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        let pipelines = GraphicsPipelineBuilder::build_many(device.clone(),
+                                                            vec![builder().allow_derivatives(),
+                                                                 builder().point_list()])
+            .unwrap();
+        assert_eq!(pipelines.len(), 2);
+        assert!(pipelines[0].allows_derivatives());
+        assert!(!pipelines[1].allows_derivatives());
+
+        let mut pipelines = pipelines.into_iter();
+        let parent = Arc::new(pipelines.next().unwrap());
+        let without_derivatives = Arc::new(pipelines.next().unwrap());
+
+        builder()
+            .derive_from(parent as Arc<GraphicsPipelineAbstract + Send + Sync>)
+            .build(device.clone())
+            .unwrap();
+
+        let result = builder()
+            .derive_from(without_derivatives as Arc<GraphicsPipelineAbstract + Send + Sync>)
+            .build(device.clone());
+        match result {
+            Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed) => (),
+            _ => panic!(),
         }
     }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let pipeline = Arc::new(GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+            .build(device.clone())
+            .unwrap());
+
+        let dynamic_states = GraphicsPipelineDynamicStates {
+            viewport: true,
+            ..GraphicsPipelineDynamicStates::none()
+        };
+        let borrowed = unsafe {
+            GraphicsPipeline::from_raw(BufferlessDefinition,
+                                       pipeline.clone(),
+                                       Subpass::from(rp, 0).unwrap(),
+                                       pipeline.internal_object(),
+                                       dynamic_states,
+                                       1,
+                                       false,
+                                       false)
+        };
+        assert_eq!(borrowed.internal_object(), pipeline.internal_object());
+        assert_eq!(borrowed.has_dynamic_viewports(), pipeline.has_dynamic_viewports());
+        assert_eq!(borrowed.has_dynamic_scissors(), pipeline.has_dynamic_scissors());
+        assert_eq!(borrowed.num_viewports(), 1);
+    }
+
+    #[test]
+    fn sample_mask() {
+        let builder = GraphicsPipeline::start();
+        assert_eq!(builder.sample_mask, [0xffffffff; 2]);
+
+        let builder = builder.sample_mask(0x1_0000_0003);
+        assert_eq!(builder.sample_mask, [0x3, 0x1]);
+    }
+
+    #[test]
+    fn sample_mask_build() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 4,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let pipeline = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .sample_mask(0b0101)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+        assert!(pipeline.is_ok());
+    }
+
+    #[test]
+    fn mismatched_attachment_samples() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 4,
+                },
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {depth}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::MismatchedAttachmentSamples) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn pipeline_cache() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+
+        // The cache is set before the calls that change the type of the builder, and must be
+        // kept by them.
+        let builder = GraphicsPipeline::start()
+            .pipeline_cache(cache.clone())
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap());
+        assert!(Arc::ptr_eq(builder.cache.as_ref().unwrap(), &cache));
+        builder.build(device.clone()).unwrap();
+    }
+
+    #[test]
+    fn pipeline_cache_wrong_device() {
+        let (device1, _) = gfx_dev_and_queue!();
+        let (device2, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device1.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device1.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device1.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let cache = PipelineCache::empty(device2).unwrap();
+
+        assert_should_panic!({
+            let _ = GraphicsPipeline::start()
+                .pipeline_cache(cache)
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp, 0).unwrap())
+                .build(device1.clone());
+        });
+    }
+
+    #[test]
+    fn build_many_reports_missing_parameters() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let builder = || {
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        // The second builder has no viewport state.
+        let builders = vec![builder().viewports_dynamic_scissors_irrelevant(1), builder()];
+        let result = GraphicsPipelineBuilder::build_many(device.clone(), builders);
+        match result {
+            Err(GraphicsPipelineCreationError::NoViewportState) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_fragment_shader() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::NoFragmentShader) => (),
+            _ => panic!(),
+        }
+    }
+
+    // Render pass with a color attachment and a depth-stencil attachment that is only used in
+    // the read-only layout.
+    struct ReadOnlyStencilDesc;
+
+    unsafe impl RenderPassDesc for ReadOnlyStencilDesc {
+        fn num_attachments(&self) -> usize {
+            2
+        }
+
+        fn attachment_desc(&self, num: usize) -> Option<LayoutAttachmentDescription> {
+            let (format, layout) = match num {
+                0 => (Format::R8G8B8A8Unorm, ImageLayout::ColorAttachmentOptimal),
+                1 => (Format::D24Unorm_S8Uint, ImageLayout::DepthStencilReadOnlyOptimal),
+                _ => return None,
+            };
+
+            Some(LayoutAttachmentDescription {
+                     format: format,
+                     samples: 1,
+                     load: LoadOp::Load,
+                     store: StoreOp::Store,
+                     stencil_load: LoadOp::Load,
+                     stencil_store: StoreOp::Store,
+                     initial_layout: layout,
+                     final_layout: layout,
+                 })
+        }
+
+        fn num_subpasses(&self) -> usize {
+            1
+        }
+
+        fn subpass_desc(&self, num: usize) -> Option<LayoutPassDescription> {
+            if num != 0 {
+                return None;
+            }
+
+            Some(LayoutPassDescription {
+                     color_attachments: vec![(0, ImageLayout::ColorAttachmentOptimal)],
+                     depth_stencil: Some((1, ImageLayout::DepthStencilReadOnlyOptimal)),
+                     input_attachments: vec![],
+                     resolve_attachments: vec![],
+                     preserve_attachments: vec![],
+                 })
+        }
+
+        fn num_dependencies(&self) -> usize {
+            0
+        }
+
+        fn dependency_desc(&self, _: usize) -> Option<LayoutPassDependencyDescription> {
+            None
+        }
+    }
+
+    unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for ReadOnlyStencilDesc {
+        fn convert_clear_values(&self, values: Vec<ClearValue>)
+                                -> Box<Iterator<Item = ClearValue>> {
+            Box::new(values.into_iter())
+        }
+    }
+
+    #[test]
+    fn stencil_writes_read_only_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(RenderPass::new(device.clone(), ReadOnlyStencilDesc).unwrap());
+
+        let builder = || {
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        // Testing the stencil without modifying it is allowed.
+        let read_only = Stencil {
+            compare: Compare::Equal,
+            ..Stencil::default()
+        };
+        assert!(builder().stencil(read_only).build(device.clone()).is_ok());
+
+        let writes = Stencil {
+            compare: Compare::Always,
+            pass_op: StencilOp::Replace,
+            ..Stencil::default()
+        };
+        match builder().stencil_front(writes).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::NoStencilAttachment) => (),
+            _ => panic!(),
+        }
+
+        // Operations that can't change the stencil buffer because of the write mask are allowed.
+        let masked = Stencil {
+            write_mask: Some(0),
+            ..writes
+        };
+        assert!(builder().stencil(masked).build(device.clone()).is_ok());
+    }
+
+    #[test]
+    fn tessellation_shader_types() {
+        let (device, _) = gfx_dev_and_queue!(tessellation_shader);
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let builder = |tes_ty| {
+            let tcs = main_entry_point(&vs, GraphicsShaderType::TessellationControl);
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .tessellation_shaders(tcs, (), main_entry_point(&vs, tes_ty), ())
+                .patch_list(0)
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        // The shader types are accepted, so the pipeline is only rejected because of the patches
+        // without vertices.
+        match builder(GraphicsShaderType::TessellationEvaluation).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::InvalidPrimitiveTopology) => (),
+            _ => panic!(),
+        }
+
+        match builder(GraphicsShaderType::TessellationControl).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::WrongShaderType) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn tessellation_with_geometry_shader() {
+        let (device, _) = gfx_dev_and_queue!(tessellation_shader, geometry_shader);
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let tcs = main_entry_point(&vs, GraphicsShaderType::TessellationControl);
+        let tes = main_entry_point(&vs, GraphicsShaderType::TessellationEvaluation);
+        let gs_ty = GraphicsShaderType::Geometry(GeometryShaderExecutionMode::Triangles);
+
+        // The input assembly produces patches, which the geometry shader doesn't receive when
+        // tessellation is enabled. The pipeline is only rejected because of the patches without
+        // vertices.
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .tessellation_shaders(tcs, (), tes, ())
+            .geometry_shader(main_entry_point(&vs, gs_ty), ())
+            .patch_list(0)
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::InvalidPrimitiveTopology) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_stencil_setters() {
+        let builder = GraphicsPipeline::start().depth_compare(Compare::Less);
+        assert_eq!(builder.depth_stencil.depth_compare, Compare::Less);
+
+        let builder = builder.depth_bounds(0.25 .. 0.75);
+        assert_eq!(builder.depth_stencil.depth_bounds_test, DepthBounds::Fixed(0.25 .. 0.75));
+        let builder = builder.depth_bounds_dynamic();
+        assert_eq!(builder.depth_stencil.depth_bounds_test, DepthBounds::Dynamic);
+        let builder = builder.depth_bounds_disabled();
+        assert_eq!(builder.depth_stencil.depth_bounds_test, DepthBounds::Disabled);
+
+        let replace = Stencil {
+            compare: Compare::Always,
+            pass_op: StencilOp::Replace,
+            ..Stencil::default()
+        };
+        let builder = builder.stencil_front(replace);
+        assert_eq!(builder.depth_stencil.stencil_front.pass_op, StencilOp::Replace);
+        assert_eq!(builder.depth_stencil.stencil_back.pass_op, StencilOp::Keep);
+
+        let invert = Stencil {
+            pass_op: StencilOp::Invert,
+            ..replace
+        };
+        let builder = builder.stencil_back(invert);
+        assert_eq!(builder.depth_stencil.stencil_front.pass_op, StencilOp::Replace);
+        assert_eq!(builder.depth_stencil.stencil_back.pass_op, StencilOp::Invert);
+
+        let builder = builder.stencil(replace);
+        assert_eq!(builder.depth_stencil.stencil_front.pass_op, StencilOp::Replace);
+        assert_eq!(builder.depth_stencil.stencil_back.pass_op, StencilOp::Replace);
+    }
+
+    #[test]
+    fn depth_bounds_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                },
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {depth}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .depth_bounds_dynamic()
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::DepthBoundsFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_tests_without_depth_attachment() {
+        let (device, _) = gfx_dev_and_queue!(depth_bounds);
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let builder = || {
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        match builder().depth_compare(Compare::Less).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::NoDepthAttachment) => (),
+            _ => panic!(),
+        }
+
+        match builder().depth_bounds(0.0 .. 0.5).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::NoDepthAttachment) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_bias_setters() {
+        let builder = GraphicsPipeline::start().depth_bias(1.5, 0.25, 2.0);
+        match builder.raster.depth_bias {
+            DepthBiasControl::Static(bias) => {
+                assert_eq!(bias.constant_factor, 1.5);
+                assert_eq!(bias.clamp, 0.25);
+                assert_eq!(bias.slope_factor, 2.0);
+            },
+            _ => panic!(),
+        }
+
+        let builder = builder.depth_bias_dynamic();
+        assert!(builder.raster.depth_bias.is_dynamic());
+
+        let builder = builder.depth_bias_disabled();
+        match builder.raster.depth_bias {
+            DepthBiasControl::Disabled => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn rasterization_features_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let builder = || {
+            GraphicsPipeline::start()
+                .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+                .viewports_dynamic_scissors_irrelevant(1)
+                .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+                .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+        };
+
+        match builder().depth_bias(1.0, 0.5, 1.0).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::DepthBiasClampFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        match builder().depth_clamp(true).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::DepthClampFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        match builder().polygon_mode_line().build(device.clone()) {
+            Err(GraphicsPipelineCreationError::FillModeNonSolidFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        match builder().line_width(2.0).build(device.clone()) {
+            Err(GraphicsPipelineCreationError::WideLinesFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    fn conservative_properties() -> ConservativeRasterizationProperties {
+        ConservativeRasterizationProperties {
+            primitive_overestimation_size: 0.5,
+            max_extra_primitive_overestimation_size: 0.75,
+            extra_primitive_overestimation_size_granularity: 0.25,
+            primitive_underestimation: false,
+            conservative_point_and_line_rasterization: false,
+            degenerate_triangles_rasterized: false,
+            degenerate_lines_rasterized: false,
+            fully_covered_fragment_shader_input_variable: false,
+            conservative_rasterization_post_depth_coverage: false,
+        }
+    }
+
+    #[test]
+    fn conservative_rasterization_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &VERTEX_MAIN).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &FRAGMENT_MAIN).unwrap() };
+
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap();
+
+        let result = GraphicsPipeline::start()
+            .vertex_input(BufferlessDefinition)
+            .vertex_shader(main_entry_point(&vs, GraphicsShaderType::Vertex), ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .conservative_rasterization_overestimate(0.0)
+            .fragment_shader(main_entry_point(&fs, GraphicsShaderType::Fragment), ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .build(device.clone());
+
+        match result {
+            Err(GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn conservative_rasterization_setters() {
+        let builder = GraphicsPipeline::start().conservative_rasterization_overestimate(0.5);
+        assert_eq!(builder.raster.conservative_mode, ConservativeRasterizationMode::Overestimate);
+        assert_eq!(builder.raster.extra_primitive_overestimation_size, 0.5);
+
+        let builder = builder.conservative_rasterization_underestimate();
+        assert_eq!(builder.raster.conservative_mode, ConservativeRasterizationMode::Underestimate);
+
+        let builder = builder.conservative_rasterization_disabled();
+        assert_eq!(builder.raster.conservative_mode, ConservativeRasterizationMode::Disabled);
+    }
+
+    #[test]
+    fn conservative_rasterization_extra_size_out_of_range() {
+        let properties = conservative_properties();
+        let mut raster = Rasterization::default();
+        raster.conservative_mode = ConservativeRasterizationMode::Overestimate;
+
+        raster.extra_primitive_overestimation_size = 0.75;
+        assert!(check_conservative_rasterization(&raster, false, &properties).is_ok());
+
+        for &extra in &[-0.25, 1.0] {
+            raster.extra_primitive_overestimation_size = extra;
+            let err = check_conservative_rasterization(&raster, false, &properties).unwrap_err();
+            assert_eq!(
+                err,
+                GraphicsPipelineCreationError::ExtraPrimitiveOverestimationSizeOutOfRange
+            );
+        }
+    }
+
+    #[test]
+    fn conservative_rasterization_underestimate_not_supported() {
+        let mut properties = conservative_properties();
+        let mut raster = Rasterization::default();
+        raster.conservative_mode = ConservativeRasterizationMode::Underestimate;
+
+        let err = check_conservative_rasterization(&raster, false, &properties).unwrap_err();
+        assert_eq!(
+            err,
+            GraphicsPipelineCreationError::ConservativeRasterizationUnderestimateNotSupported
+        );
+
+        properties.primitive_underestimation = true;
+        assert!(check_conservative_rasterization(&raster, false, &properties).is_ok());
+    }
+
+    #[test]
+    fn conservative_point_and_line_rasterization_not_supported() {
+        let mut properties = conservative_properties();
+        let mut raster = Rasterization::default();
+
+        // Disabled conservative rasterization is never checked against the properties.
+        assert!(check_conservative_rasterization(&raster, true, &properties).is_ok());
+
+        raster.conservative_mode = ConservativeRasterizationMode::Overestimate;
+        let err = check_conservative_rasterization(&raster, true, &properties).unwrap_err();
+        assert_eq!(
+            err,
+            GraphicsPipelineCreationError::ConservativePointAndLineRasterizationNotSupported
+        );
+
+        properties.conservative_point_and_line_rasterization = true;
+        assert!(check_conservative_rasterization(&raster, true, &properties).is_ok());
+    }
 }
@@ -8,15 +8,16 @@
 // according to those terms.
 
 use std::error;
-use std::fmt;
 use std::u32;
 
-use Error;
 use OomError;
 use descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
+use framebuffer::FragmentOutputMismatchError;
 use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::shader::ShaderCapabilitiesError;
 use pipeline::shader::ShaderInterfaceMismatchError;
 use pipeline::vertex::IncompatibleVertexDefinitionError;
+use vk;
 
 /// Error that can happen when creating a graphics pipeline.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -51,9 +52,21 @@ pub enum GraphicsPipelineCreationError {
     /// The interface between the geometry shader and the fragment shader mismatches.
     GeometryFragmentStagesMismatch(ShaderInterfaceMismatchError),
 
+    /// The interface between the mesh shader and the fragment shader mismatches.
+    MeshFragmentStagesMismatch(ShaderInterfaceMismatchError),
+
     /// The output of the fragment shader is not compatible with what the render pass subpass
     /// expects.
-    FragmentShaderRenderPassIncompatible,
+    FragmentShaderRenderPassIncompatible(FragmentOutputMismatchError),
+
+    /// The fragment shader reads from an input attachment descriptor, but the subpass doesn't
+    /// have any input attachment with a matching number of samples.
+    FragmentShaderInputAttachmentsIncompatible {
+        /// Set of the faulty descriptor.
+        set: usize,
+        /// Binding of the faulty descriptor.
+        binding: usize,
+    },
 
     /// The vertex definition is not compatible with the input of the vertex shader.
     IncompatibleVertexDefinition(IncompatibleVertexDefinitionError),
@@ -103,6 +116,9 @@ pub enum GraphicsPipelineCreationError {
     /// The `multi_viewport` feature must be enabled in order to use multiple viewports at once.
     MultiViewportFeatureNotEnabled,
 
+    /// The pipeline must have at least one viewport and scissor box.
+    ZeroViewports,
+
     /// The maximum number of viewports has been exceeded.
     MaxViewportsExceeded {
         /// Maximum allowed value.
@@ -120,6 +136,20 @@ pub enum GraphicsPipelineCreationError {
     /// The `wide_lines` feature must be enabled in order to use a line width superior to 1.0.
     WideLinesFeatureNotEnabled,
 
+    /// The `ext_conservative_rasterization` extension must be enabled in order to use
+    /// conservative rasterization.
+    ConservativeRasterizationExtensionNotEnabled,
+
+    /// The device doesn't support conservative rasterization in underestimate mode.
+    ConservativeRasterizationUnderestimateNotSupported,
+
+    /// The device doesn't support conservative rasterization of points and lines.
+    ConservativePointAndLineRasterizationNotSupported,
+
+    /// The extra primitive overestimation size is negative or greater than the maximum supported
+    /// by the device.
+    ExtraPrimitiveOverestimationSizeOutOfRange,
+
     /// The `depth_clamp` feature must be enabled in order to use depth clamping.
     DepthClampFeatureNotEnabled,
 
@@ -140,12 +170,24 @@ pub enum GraphicsPipelineCreationError {
     /// The primitives topology does not match what the geometry shader expects.
     TopologyNotMatchingGeometryShader,
 
-    /// The `geometry_shader` feature must be enabled in order to use geometry shaders.
+    /// The `geometry_shader` feature must be enabled in order to use geometry shaders or
+    /// primitive topologies with adjacency.
     GeometryShaderFeatureNotEnabled,
 
     /// The `tessellation_shader` feature must be enabled in order to use tessellation shaders.
     TessellationShaderFeatureNotEnabled,
 
+    /// The `ext_mesh_shader` extension must be enabled in order to use task and mesh shaders.
+    MeshShaderExtensionNotEnabled,
+
+    /// The `task_shader` feature of the `ext_mesh_shader` extension must be supported in order to
+    /// use task shaders.
+    TaskShaderFeatureNotEnabled,
+
+    /// A mesh shader was passed to the builder along with a vertex, tessellation or geometry
+    /// shader.
+    MeshShaderWithVertexStages,
+
     /// The number of attachments specified in the blending does not match the number of
     /// attachments in the subpass.
     MismatchBlendingAttachmentsCount,
@@ -165,8 +207,8 @@ pub enum GraphicsPipelineCreationError {
     /// stencil writing is enabled and the stencil attachment is read-only.
     NoStencilAttachment,
 
-    /// Tried to use a patch list without a tessellation shader, or a non-patch-list with a
-    /// tessellation shader.
+    /// Tried to use a patch list without a tessellation shader, a non-patch-list with a
+    /// tessellation shader, or a patch list with zero vertices per patch.
     InvalidPrimitiveTopology,
 
     /// The `maxTessellationPatchSize` limit was exceeded.
@@ -182,6 +224,30 @@ pub enum GraphicsPipelineCreationError {
 
     /// The `alpha_to_one` feature must be enabled in order to use alpha-to-one.
     AlphaToOneFeatureNotEnabled,
+
+    /// One of the shaders requires a feature that is not enabled on the device.
+    ShaderFeatureNotEnabled(ShaderCapabilitiesError),
+
+    /// The attachments of the subpass don't all have the same number of samples.
+    MismatchedAttachmentSamples,
+
+    /// The pipeline passed to `derive_from` wasn't created with `allow_derivatives`.
+    BasePipelineDerivativesNotAllowed,
+
+    /// Neither a vertex shader nor a mesh shader has been passed to the builder.
+    NoVertexShader,
+
+    /// No fragment shader has been passed to the builder.
+    NoFragmentShader,
+
+    /// The viewports and scissor boxes haven't been specified.
+    NoViewportState,
+
+    /// No render pass subpass has been passed to the builder.
+    NoRenderPass,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for GraphicsPipelineCreationError {
@@ -215,13 +281,20 @@ impl error::Error for GraphicsPipelineCreationError {
             GraphicsPipelineCreationError::GeometryFragmentStagesMismatch(_) => {
                 "the interface between the geometry shader and the fragment shader mismatches"
             },
+            GraphicsPipelineCreationError::MeshFragmentStagesMismatch(_) => {
+                "the interface between the mesh shader and the fragment shader mismatches"
+            },
             GraphicsPipelineCreationError::IncompatiblePipelineLayout(_) => {
                 "the pipeline layout is not compatible with what the shaders expect"
             },
-            GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible => {
+            GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible(_) => {
                 "the output of the fragment shader is not compatible with what the render pass \
                  subpass expects"
             },
+            GraphicsPipelineCreationError::FragmentShaderInputAttachmentsIncompatible { .. } => {
+                "the fragment shader reads from an input attachment that the render pass subpass \
+                 doesn't provide"
+            },
             GraphicsPipelineCreationError::IncompatibleVertexDefinition(_) => {
                 "the vertex definition is not compatible with the input of the vertex shader"
             },
@@ -246,6 +319,9 @@ impl error::Error for GraphicsPipelineCreationError {
                 "the `multi_viewport` feature must be enabled in order to use multiple viewports \
                  at once"
             },
+            GraphicsPipelineCreationError::ZeroViewports => {
+                "the pipeline must have at least one viewport and scissor box"
+            },
             GraphicsPipelineCreationError::MaxViewportsExceeded { .. } => {
                 "the maximum number of viewports has been exceeded"
             },
@@ -259,6 +335,20 @@ impl error::Error for GraphicsPipelineCreationError {
                 "the `wide_lines` feature must be enabled in order to use a line width \
                  superior to 1.0"
             },
+            GraphicsPipelineCreationError::ConservativeRasterizationExtensionNotEnabled => {
+                "the `ext_conservative_rasterization` extension must be enabled in order to use \
+                 conservative rasterization"
+            },
+            GraphicsPipelineCreationError::ConservativeRasterizationUnderestimateNotSupported => {
+                "the device doesn't support conservative rasterization in underestimate mode"
+            },
+            GraphicsPipelineCreationError::ConservativePointAndLineRasterizationNotSupported => {
+                "the device doesn't support conservative rasterization of points and lines"
+            },
+            GraphicsPipelineCreationError::ExtraPrimitiveOverestimationSizeOutOfRange => {
+                "the extra primitive overestimation size is negative or greater than the maximum \
+                 supported by the device"
+            },
             GraphicsPipelineCreationError::DepthClampFeatureNotEnabled => {
                 "the `depth_clamp` feature must be enabled in order to use depth clamping"
             },
@@ -280,12 +370,23 @@ impl error::Error for GraphicsPipelineCreationError {
                 "the primitives topology does not match what the geometry shader expects"
             },
             GraphicsPipelineCreationError::GeometryShaderFeatureNotEnabled => {
-                "the `geometry_shader` feature must be enabled in order to use geometry shaders"
+                "the `geometry_shader` feature must be enabled in order to use geometry shaders or \
+                 primitive topologies with adjacency"
             },
             GraphicsPipelineCreationError::TessellationShaderFeatureNotEnabled => {
                 "the `tessellation_shader` feature must be enabled in order to use tessellation \
                  shaders"
             },
+            GraphicsPipelineCreationError::MeshShaderExtensionNotEnabled => {
+                "the `ext_mesh_shader` extension must be enabled in order to use task and mesh \
+                 shaders"
+            },
+            GraphicsPipelineCreationError::TaskShaderFeatureNotEnabled => {
+                "the `task_shader` feature must be supported in order to use task shaders"
+            },
+            GraphicsPipelineCreationError::MeshShaderWithVertexStages => {
+                "a mesh shader was passed along with a vertex, tessellation or geometry shader"
+            },
             GraphicsPipelineCreationError::MismatchBlendingAttachmentsCount => {
                 "the number of attachments specified in the blending does not match the number of \
                  attachments in the subpass"
@@ -304,8 +405,8 @@ impl error::Error for GraphicsPipelineCreationError {
                 "the stencil attachment of the render pass does not match the stencil test"
             },
             GraphicsPipelineCreationError::InvalidPrimitiveTopology => {
-                "trying to use a patch list without a tessellation shader, or a non-patch-list \
-                 with a tessellation shader"
+                "trying to use a patch list without a tessellation shader, a non-patch-list \
+                 with a tessellation shader, or a patch list with zero vertices per patch"
             },
             GraphicsPipelineCreationError::MaxTessellationPatchSizeExceeded => {
                 "the maximum tessellation patch size was exceeded"
@@ -319,6 +420,29 @@ impl error::Error for GraphicsPipelineCreationError {
             GraphicsPipelineCreationError::AlphaToOneFeatureNotEnabled => {
                 "the `alpha_to_one` feature must be enabled in order to use alpha-to-one"
             },
+            GraphicsPipelineCreationError::ShaderFeatureNotEnabled(_) => {
+                "one of the shaders requires a feature that is not enabled on the device"
+            },
+            GraphicsPipelineCreationError::MismatchedAttachmentSamples => {
+                "the attachments of the subpass don't all have the same number of samples"
+            },
+            GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed => {
+                "the base pipeline wasn't created with `allow_derivatives`"
+            },
+            GraphicsPipelineCreationError::NoVertexShader => {
+                "neither a vertex shader nor a mesh shader has been passed to the builder"
+            },
+            GraphicsPipelineCreationError::NoFragmentShader => {
+                "no fragment shader has been passed to the builder"
+            },
+            GraphicsPipelineCreationError::NoViewportState => {
+                "the viewports and scissor boxes haven't been specified"
+            },
+            GraphicsPipelineCreationError::NoRenderPass => {
+                "no render pass subpass has been passed to the builder"
+            },
+            GraphicsPipelineCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -334,25 +458,18 @@ impl error::Error for GraphicsPipelineCreationError {
             GraphicsPipelineCreationError::TessEvalGeometryStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::TessEvalFragmentStagesMismatch(ref err) => Some(err),
             GraphicsPipelineCreationError::GeometryFragmentStagesMismatch(ref err) => Some(err),
+            GraphicsPipelineCreationError::MeshFragmentStagesMismatch(ref err) => Some(err),
+            GraphicsPipelineCreationError::FragmentShaderRenderPassIncompatible(ref err) => {
+                Some(err)
+            },
             GraphicsPipelineCreationError::IncompatibleVertexDefinition(ref err) => Some(err),
+            GraphicsPipelineCreationError::ShaderFeatureNotEnabled(ref err) => Some(err),
             _ => None,
         }
     }
 }
 
-impl fmt::Display for GraphicsPipelineCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for GraphicsPipelineCreationError {
-    #[inline]
-    fn from(err: OomError) -> GraphicsPipelineCreationError {
-        GraphicsPipelineCreationError::OomError(err)
-    }
-}
+vk_error_conversions!(GraphicsPipelineCreationError, OomError {});
 
 impl From<PipelineLayoutNotSupersetError> for GraphicsPipelineCreationError {
     #[inline]
@@ -361,24 +478,16 @@ impl From<PipelineLayoutNotSupersetError> for GraphicsPipelineCreationError {
     }
 }
 
-impl From<IncompatibleVertexDefinitionError> for GraphicsPipelineCreationError {
+impl From<ShaderCapabilitiesError> for GraphicsPipelineCreationError {
     #[inline]
-    fn from(err: IncompatibleVertexDefinitionError) -> GraphicsPipelineCreationError {
-        GraphicsPipelineCreationError::IncompatibleVertexDefinition(err)
+    fn from(err: ShaderCapabilitiesError) -> GraphicsPipelineCreationError {
+        GraphicsPipelineCreationError::ShaderFeatureNotEnabled(err)
     }
 }
 
-impl From<Error> for GraphicsPipelineCreationError {
+impl From<IncompatibleVertexDefinitionError> for GraphicsPipelineCreationError {
     #[inline]
-    fn from(err: Error) -> GraphicsPipelineCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => {
-                GraphicsPipelineCreationError::OomError(OomError::from(err))
-            },
-            err @ Error::OutOfDeviceMemory => {
-                GraphicsPipelineCreationError::OomError(OomError::from(err))
-            },
-            _ => panic!("unexpected error: {:?}", err),
-        }
+    fn from(err: IncompatibleVertexDefinitionError) -> GraphicsPipelineCreationError {
+        GraphicsPipelineCreationError::IncompatibleVertexDefinition(err)
     }
 }
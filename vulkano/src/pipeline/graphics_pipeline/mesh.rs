@@ -0,0 +1,59 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::mem;
+
+use instance::PhysicalDevice;
+
+use vk;
+
+/// Limits of the physical device regarding mesh shading.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MeshShaderProperties {
+    /// Maximum total number of task shader workgroups that a single draw command can launch.
+    pub max_task_work_group_total_count: u32,
+    /// Maximum number of task shader workgroups in each dimension of a single draw command.
+    pub max_task_work_group_count: [u32; 3],
+    /// Maximum total number of mesh shader workgroups that a single draw command or task shader
+    /// workgroup can launch.
+    pub max_mesh_work_group_total_count: u32,
+    /// Maximum number of mesh shader workgroups in each dimension of a single draw command or
+    /// task shader workgroup.
+    pub max_mesh_work_group_count: [u32; 3],
+    /// Maximum number of vertices that a mesh shader workgroup can output.
+    pub max_mesh_output_vertices: u32,
+    /// Maximum number of primitives that a mesh shader workgroup can output.
+    pub max_mesh_output_primitives: u32,
+}
+
+impl MeshShaderProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> MeshShaderProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceMeshShaderPropertiesEXT = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MESH_SHADER_PROPERTIES_EXT;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            MeshShaderProperties {
+                max_task_work_group_total_count: output.maxTaskWorkGroupTotalCount,
+                max_task_work_group_count: output.maxTaskWorkGroupCount,
+                max_mesh_work_group_total_count: output.maxMeshWorkGroupTotalCount,
+                max_mesh_work_group_count: output.maxMeshWorkGroupCount,
+                max_mesh_output_vertices: output.maxMeshOutputVertices,
+                max_mesh_output_primitives: output.maxMeshOutputPrimitives,
+            }
+        }
+    }
+}
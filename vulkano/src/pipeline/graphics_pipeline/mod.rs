@@ -26,6 +26,8 @@ use device::Device;
 use device::DeviceOwned;
 use format::ClearValue;
 use framebuffer::LayoutAttachmentDescription;
+use framebuffer::LayoutDepthStencilResolveDescription;
+use framebuffer::LayoutMultiviewDescription;
 use framebuffer::LayoutPassDependencyDescription;
 use framebuffer::LayoutPassDescription;
 use framebuffer::RenderPassAbstract;
@@ -42,9 +44,13 @@ use vk;
 
 pub use self::builder::GraphicsPipelineBuilder;
 pub use self::creation_error::GraphicsPipelineCreationError;
+pub use self::mesh::MeshShaderProperties;
+pub use self::transform_feedback::TransformFeedbackProperties;
 
 mod builder;
 mod creation_error;
+mod mesh;
+mod transform_feedback;
 // FIXME: restore
 //mod tests;
 
@@ -72,11 +78,41 @@ pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
     dynamic_blend_constants: bool,
 
     num_viewports: u32,
+
+    allow_derivatives: bool,
+
+    mesh_shading: bool,
+    task_shader: bool,
 }
 
 struct Inner {
     pipeline: vk::Pipeline,
     device: Arc<Device>,
+    // False if the pipeline was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+/// List of the states of a graphics pipeline that are dynamic. Passed to
+/// `GraphicsPipeline::from_raw`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct GraphicsPipelineDynamicStates {
+    pub line_width: bool,
+    pub viewport: bool,
+    pub scissor: bool,
+    pub depth_bias: bool,
+    pub depth_bounds: bool,
+    pub stencil_compare_mask: bool,
+    pub stencil_write_mask: bool,
+    pub stencil_reference: bool,
+    pub blend_constants: bool,
+}
+
+impl GraphicsPipelineDynamicStates {
+    /// Builds a `GraphicsPipelineDynamicStates` with all states set to false.
+    #[inline]
+    pub fn none() -> GraphicsPipelineDynamicStates {
+        GraphicsPipelineDynamicStates::default()
+    }
 }
 
 impl GraphicsPipeline<(), (), ()> {
@@ -94,6 +130,10 @@ impl GraphicsPipeline<(), (), ()> {
                                    (),
                                    EmptyEntryPointDummy,
                                    (),
+                                   EmptyEntryPointDummy,
+                                   (),
+                                   EmptyEntryPointDummy,
+                                   (),
                                    ()>
     {
         GraphicsPipelineBuilder::new()
@@ -101,6 +141,62 @@ impl GraphicsPipeline<(), (), ()> {
 }
 
 impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
+    /// Builds a `GraphicsPipeline` from a raw handle that was created outside of vulkano.
+    ///
+    /// `dynamic_states` and `num_viewports` are used to check the dynamic state passed to the
+    /// draw commands.
+    /// If `owned` is true, the pipeline is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid graphics pipeline of the device of `layout`, created with a
+    ///   layout that is identical to `layout`, in the subpass `subpass`, with vertex input that
+    ///   matches `vertex_definition` and without mesh shaders.
+    /// - `dynamic_states`, `num_viewports` and `allow_derivatives` must match the parameters
+    ///   that were used to create the pipeline.
+    /// - If `owned` is true, the pipeline must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(vertex_definition: Mv, layout: L, subpass: Subpass<Rp>,
+                           handle: vk::Pipeline, dynamic_states: GraphicsPipelineDynamicStates,
+                           num_viewports: u32, allow_derivatives: bool, owned: bool)
+                           -> GraphicsPipeline<Mv, L, Rp>
+        where L: PipelineLayoutAbstract
+    {
+        let (render_pass, render_pass_subpass) = subpass.into();
+
+        GraphicsPipeline {
+            inner: Inner {
+                device: layout.device().clone(),
+                pipeline: handle,
+                needs_destruction: owned,
+            },
+            layout: layout,
+
+            render_pass: render_pass,
+            render_pass_subpass: render_pass_subpass,
+
+            vertex_definition: vertex_definition,
+
+            dynamic_line_width: dynamic_states.line_width,
+            dynamic_viewport: dynamic_states.viewport,
+            dynamic_scissor: dynamic_states.scissor,
+            dynamic_depth_bias: dynamic_states.depth_bias,
+            dynamic_depth_bounds: dynamic_states.depth_bounds,
+            dynamic_stencil_compare_mask: dynamic_states.stencil_compare_mask,
+            dynamic_stencil_write_mask: dynamic_states.stencil_write_mask,
+            dynamic_stencil_reference: dynamic_states.stencil_reference,
+            dynamic_blend_constants: dynamic_states.blend_constants,
+
+            num_viewports: num_viewports,
+
+            allow_derivatives: allow_derivatives,
+
+            mesh_shading: false,
+            task_shader: false,
+        }
+    }
+
     /// Returns the vertex definition used in the constructor.
     #[inline]
     pub fn vertex_definition(&self) -> &Mv {
@@ -188,6 +284,24 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    /// Returns true if other pipelines can be derived from this one.
+    #[inline]
+    pub fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
+
+    /// Returns true if this pipeline uses a mesh shader instead of the vertex input stage.
+    #[inline]
+    pub fn uses_mesh_shading(&self) -> bool {
+        self.mesh_shading
+    }
+
+    /// Returns true if this pipeline has a task shader.
+    #[inline]
+    pub fn has_task_shader(&self) -> bool {
+        self.task_shader
+    }
 }
 
 unsafe impl<Mv, L, Rp> PipelineLayoutAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -288,6 +402,17 @@ unsafe impl<Mv, L, Rp> RenderPassDesc for GraphicsPipeline<Mv, L, Rp>
     fn dependency_desc(&self, num: usize) -> Option<LayoutPassDependencyDescription> {
         self.render_pass.dependency_desc(num)
     }
+
+    #[inline]
+    fn multiview_desc(&self) -> Option<LayoutMultiviewDescription> {
+        self.render_pass.multiview_desc()
+    }
+
+    #[inline]
+    fn depth_stencil_resolve_desc(&self, subpass: usize)
+                                  -> Option<LayoutDepthStencilResolveDescription> {
+        self.render_pass.depth_stencil_resolve_desc(subpass)
+    }
 }
 
 unsafe impl<C, Mv, L, Rp> RenderPassDescClearValues<C> for GraphicsPipeline<Mv, L, Rp>
@@ -313,6 +438,10 @@ unsafe impl<Mv, L, Rp> VulkanObject for GraphicsPipeline<Mv, L, Rp> {
 impl Drop for Inner {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyPipeline(self.device.internal_object(), self.pipeline, ptr::null());
@@ -359,6 +488,18 @@ pub unsafe trait GraphicsPipelineAbstract: PipelineLayoutAbstract + RenderPassAb
 
 /// Returns true if the stencil references used by this pipeline are dynamic.
     fn has_dynamic_stencil_reference(&self) -> bool;
+
+/// Returns true if other pipelines can be derived from this one.
+    fn allows_derivatives(&self) -> bool;
+
+/// Returns true if this pipeline uses a mesh shader instead of the vertex input stage.
+///
+/// Such a pipeline can only be used with `draw_mesh_tasks`, and the other pipelines can't be
+/// used with `draw_mesh_tasks`.
+    fn uses_mesh_shading(&self) -> bool;
+
+/// Returns true if this pipeline has a task shader.
+    fn has_task_shader(&self) -> bool;
 }
 
 unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -415,6 +556,21 @@ unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
     fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
+
+    #[inline]
+    fn uses_mesh_shading(&self) -> bool {
+        self.mesh_shading
+    }
+
+    #[inline]
+    fn has_task_shader(&self) -> bool {
+        self.task_shader
+    }
 }
 
 unsafe impl<T> GraphicsPipelineAbstract for T
@@ -470,6 +626,21 @@ unsafe impl<T> GraphicsPipelineAbstract for T
     fn has_dynamic_stencil_reference(&self) -> bool {
         (**self).has_dynamic_stencil_reference()
     }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        (**self).allows_derivatives()
+    }
+
+    #[inline]
+    fn uses_mesh_shading(&self) -> bool {
+        (**self).uses_mesh_shading()
+    }
+
+    #[inline]
+    fn has_task_shader(&self) -> bool {
+        (**self).has_task_shader()
+    }
 }
 
 /// Opaque object that represents the inside of the graphics pipeline.
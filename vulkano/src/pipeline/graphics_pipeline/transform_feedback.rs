@@ -0,0 +1,55 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::mem;
+
+use instance::PhysicalDevice;
+
+use vk;
+
+/// Limits of the physical device regarding transform feedback.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TransformFeedbackProperties {
+    /// Maximum number of vertex streams that can be output by a geometry shader.
+    pub max_transform_feedback_streams: u32,
+    /// Maximum number of transform feedback buffers that can be bound at the same time.
+    pub max_transform_feedback_buffers: u32,
+    /// Maximum size in bytes of the range of a buffer that can be bound for transform feedback.
+    pub max_transform_feedback_buffer_size: u64,
+    /// Maximum stride in bytes between two vertices written to a transform feedback buffer.
+    pub max_transform_feedback_buffer_data_stride: u32,
+    /// True if `draw_indirect_byte_count` is supported.
+    pub transform_feedback_draw: bool,
+}
+
+impl TransformFeedbackProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> TransformFeedbackProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceTransformFeedbackPropertiesEXT = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_TRANSFORM_FEEDBACK_PROPERTIES_EXT;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            TransformFeedbackProperties {
+                max_transform_feedback_streams: output.maxTransformFeedbackStreams,
+                max_transform_feedback_buffers: output.maxTransformFeedbackBuffers,
+                max_transform_feedback_buffer_size: output.maxTransformFeedbackBufferSize,
+                max_transform_feedback_buffer_data_stride: output
+                    .maxTransformFeedbackBufferDataStride,
+                transform_feedback_draw: output.transformFeedbackDraw != 0,
+            }
+        }
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Rebuilding pipelines when their shader files change.
+//!
+//! A `HotReloadPipeline` holds a pipeline built from shader files and a closure that builds the
+//! pipeline from the shader modules. Calling `poll` once per frame checks whether the files have
+//! been modified. If so, the shader modules and the pipeline are rebuilt in a background thread,
+//! and the new pipeline replaces the old one during a later call to `poll`. Until then, and if
+//! the rebuild fails, the old pipeline keeps being used.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::Device;
+//! # use vulkano::pipeline::GraphicsPipelineAbstract;
+//! use vulkano::pipeline::hot_reload::HotReloadPipeline;
+//! use vulkano::pipeline::hot_reload::ShaderSource;
+//!
+//! # let device: Arc<Device> = return;
+//! # fn build_pipeline(_: &[Arc<vulkano::pipeline::shader::ShaderModule>])
+//! #     -> Arc<GraphicsPipelineAbstract + Send + Sync> { unimplemented!() }
+//! let sources = vec![
+//!     ShaderSource::Spirv("shaders/vert.spv".into()),
+//!     ShaderSource::Spirv("shaders/frag.spv".into()),
+//! ];
+//!
+//! let mut pipeline = unsafe {
+//!     HotReloadPipeline::new(device.clone(), None, sources, |_device, _cache, modules| {
+//!         Ok(build_pipeline(modules))
+//!     }).unwrap()
+//! };
+//!
+//! loop {
+//!     // At the start of each frame.
+//!     if let Err(err) = pipeline.poll() {
+//!         println!("Failed to reload the shaders: {}", err);
+//!     }
+//!
+//!     // Record the commands of the frame with `pipeline.pipeline().clone()`.
+//! #   break;
+//! }
+//! ```
+//!
+//! Command buffers keep the pipelines they use alive, so replacing a pipeline never destroys it
+//! while it is in use by the GPU.
+
+use std::error;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::SystemTime;
+
+use VulkanObject;
+use device::Device;
+use device::DeviceOwned;
+use pipeline::cache::PipelineCache;
+use pipeline::cache::PipelineCacheCreationError;
+#[cfg(feature = "runtime-shader-compilation")]
+use pipeline::compile;
+#[cfg(feature = "runtime-shader-compilation")]
+use pipeline::compile::CompileOptions;
+#[cfg(feature = "runtime-shader-compilation")]
+use pipeline::compile::ShaderCompilationError;
+#[cfg(feature = "runtime-shader-compilation")]
+use pipeline::compile::ShaderType;
+use pipeline::shader::ShaderModule;
+use pipeline::shader::ShaderModuleCreationError;
+
+/// File that a shader module is loaded from.
+#[derive(Debug, Clone)]
+pub enum ShaderSource {
+    /// File that contains SPIR-V code.
+    Spirv(PathBuf),
+
+    /// File that contains GLSL or HLSL source code, compiled with the `compile` module.
+    ///
+    /// Only available if the `runtime-shader-compilation` feature is enabled.
+    #[cfg(feature = "runtime-shader-compilation")]
+    Source {
+        /// Path to the source code.
+        path: PathBuf,
+        /// Stage of the shader.
+        ty: ShaderType,
+        /// Options passed to the compiler.
+        options: CompileOptions,
+    },
+}
+
+impl ShaderSource {
+    /// Returns the path to the file.
+    #[inline]
+    pub fn path(&self) -> &PathBuf {
+        match *self {
+            ShaderSource::Spirv(ref path) => path,
+            #[cfg(feature = "runtime-shader-compilation")]
+            ShaderSource::Source { ref path, .. } => path,
+        }
+    }
+
+    // Returns the last modification time of the file, or `None` if it can't be determined.
+    fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(self.path())
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    // Loads the file and creates the shader module.
+    unsafe fn load(&self, device: &Arc<Device>) -> Result<Arc<ShaderModule>, HotReloadError> {
+        let mut bytes = Vec::new();
+        if let Err(err) = File::open(self.path()).and_then(|mut f| f.read_to_end(&mut bytes)) {
+            return Err(HotReloadError::IoError {
+                           path: self.path().clone(),
+                           error: err,
+                       });
+        }
+
+        match *self {
+            ShaderSource::Spirv(_) => Ok(ShaderModule::new_checked(device.clone(), &bytes)?),
+            #[cfg(feature = "runtime-shader-compilation")]
+            ShaderSource::Source {
+                ref ty,
+                ref options,
+                ..
+            } => {
+                let source = String::from_utf8_lossy(&bytes);
+                Ok(compile::compile_module(device.clone(), &source, ty.clone(), options)?)
+            },
+        }
+    }
+}
+
+/// Closure that builds a pipeline from the shader modules, in the same order as the sources.
+pub type PipelineBuilderFn<P> = Fn(&Arc<Device>, &Arc<PipelineCache>, &[Arc<ShaderModule>])
+                                   -> Result<P, Box<error::Error + Send + Sync>> +
+    Send + Sync;
+
+/// Pipeline that is rebuilt whenever one of its shader files is modified.
+///
+/// See [the documentation of the module](index.html) for more info.
+pub struct HotReloadPipeline<P> {
+    device: Arc<Device>,
+    cache: Arc<PipelineCache>,
+    sources: Arc<Vec<ShaderSource>>,
+    modified: Vec<Option<SystemTime>>,
+    builder: Arc<PipelineBuilderFn<P>>,
+    current: Arc<P>,
+    // Receives the result of the rebuild in progress, if any.
+    pending: Option<mpsc::Receiver<Result<P, HotReloadError>>>,
+}
+
+impl<P> HotReloadPipeline<P>
+    where P: Send + Sync + 'static
+{
+    /// Loads the shader modules and builds the pipeline a first time, on the current thread.
+    ///
+    /// The `builder` closure receives the modules in the same order as `sources`, and must pass
+    /// the cache to the pipeline it builds with `GraphicsPipelineBuilder::pipeline_cache`. If
+    /// `cache` is `None`, an empty cache is created.
+    ///
+    /// # Safety
+    ///
+    /// The `Spirv` sources must contain valid SPIR-V code, now and whenever they are modified.
+    /// See `ShaderModule::new_checked`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `cache` doesn't belong to `device`.
+    ///
+    pub unsafe fn new<F>(device: Arc<Device>, cache: Option<Arc<PipelineCache>>,
+                         sources: Vec<ShaderSource>, builder: F)
+                         -> Result<HotReloadPipeline<P>, HotReloadError>
+        where F: Fn(&Arc<Device>, &Arc<PipelineCache>, &[Arc<ShaderModule>])
+                    -> Result<P, Box<error::Error + Send + Sync>> + Send + Sync + 'static
+    {
+        let cache = match cache {
+            Some(cache) => {
+                assert_eq!(cache.device().internal_object(), device.internal_object());
+                cache
+            },
+            None => PipelineCache::empty(device.clone())?,
+        };
+
+        let modified = sources.iter().map(|s| s.modified()).collect();
+        let builder = Arc::new(builder) as Arc<PipelineBuilderFn<P>>;
+        let current = build(&device, &cache, &sources, &*builder)?;
+
+        Ok(HotReloadPipeline {
+               device: device,
+               cache: cache,
+               sources: Arc::new(sources),
+               modified: modified,
+               builder: builder,
+               current: Arc::new(current),
+               pending: None,
+           })
+    }
+
+    /// Returns the current pipeline.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<P> {
+        &self.current
+    }
+
+    /// Returns the pipeline cache used when rebuilding the pipeline.
+    #[inline]
+    pub fn cache(&self) -> &Arc<PipelineCache> {
+        &self.cache
+    }
+
+    /// Returns true if the pipeline is being rebuilt in the background.
+    #[inline]
+    pub fn is_rebuilding(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Replaces the current pipeline if a rebuild has finished, and starts a rebuild in the
+    /// background if one of the shader files has been modified. Never blocks.
+    ///
+    /// This function should be called at a frame boundary, for example before recording the
+    /// command buffer of each frame. Returns `Ok(true)` if the pipeline has been replaced.
+    ///
+    /// If the rebuild failed, the error is returned and the current pipeline stays the same. The
+    /// pipeline is rebuilt again the next time one of the files is modified.
+    pub fn poll(&mut self) -> Result<bool, HotReloadError> {
+        let result = match self.pending.as_ref().map(|p| p.try_recv()) {
+            Some(Ok(result)) => Some(result),
+            Some(Err(mpsc::TryRecvError::Empty)) => return Ok(false),
+            Some(Err(mpsc::TryRecvError::Disconnected)) => {
+                Some(Err(HotReloadError::BuilderPanicked))
+            },
+            None => None,
+        };
+
+        if let Some(result) = result {
+            self.pending = None;
+            self.current = Arc::new(result?);
+            return Ok(true);
+        }
+
+        let modified: Vec<_> = self.sources.iter().map(|s| s.modified()).collect();
+        if modified != self.modified {
+            self.modified = modified;
+            self.rebuild();
+        }
+
+        Ok(false)
+    }
+
+    /// Starts rebuilding the pipeline in the background, even if no file has been modified.
+    ///
+    /// Does nothing if a rebuild is already in progress.
+    pub fn rebuild(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let device = self.device.clone();
+        let cache = self.cache.clone();
+        let sources = self.sources.clone();
+        let builder = self.builder.clone();
+
+        thread::spawn(move || {
+            // Safe because the caller of `new` guarantees that the files are valid.
+            let result = unsafe { build(&device, &cache, &sources, &*builder) };
+            let _ = sender.send(result);
+        });
+
+        self.pending = Some(receiver);
+    }
+}
+
+// Loads the shader modules and builds the pipeline.
+unsafe fn build<P>(device: &Arc<Device>, cache: &Arc<PipelineCache>, sources: &[ShaderSource],
+                   builder: &PipelineBuilderFn<P>)
+                   -> Result<P, HotReloadError> {
+    let mut modules = Vec::with_capacity(sources.len());
+    for source in sources {
+        modules.push(source.load(device)?);
+    }
+
+    match builder(device, cache, &modules) {
+        Ok(pipeline) => Ok(pipeline),
+        Err(err) => Err(HotReloadError::BuildFailed(err)),
+    }
+}
+
+/// Error that can happen when loading the shaders or building the pipeline.
+#[derive(Debug)]
+pub enum HotReloadError {
+    /// The pipeline cache couldn't be created.
+    CacheCreationError(PipelineCacheCreationError),
+    /// A shader file couldn't be read.
+    IoError {
+        /// Path to the file.
+        path: PathBuf,
+        /// The error that happened.
+        error: io::Error,
+    },
+    /// A SPIR-V file doesn't contain valid code.
+    ModuleCreationError(ShaderModuleCreationError),
+    /// A shader couldn't be compiled.
+    #[cfg(feature = "runtime-shader-compilation")]
+    CompilationError(ShaderCompilationError),
+    /// The closure that builds the pipeline returned an error.
+    BuildFailed(Box<error::Error + Send + Sync>),
+    /// The closure that builds the pipeline panicked.
+    BuilderPanicked,
+}
+
+impl error::Error for HotReloadError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            HotReloadError::CacheCreationError(_) => "the pipeline cache couldn't be created",
+            HotReloadError::IoError { .. } => "a shader file couldn't be read",
+            HotReloadError::ModuleCreationError(_) => {
+                "a SPIR-V file doesn't contain valid code"
+            },
+            #[cfg(feature = "runtime-shader-compilation")]
+            HotReloadError::CompilationError(_) => "a shader couldn't be compiled",
+            HotReloadError::BuildFailed(_) => {
+                "the closure that builds the pipeline returned an error"
+            },
+            HotReloadError::BuilderPanicked => "the closure that builds the pipeline panicked",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            HotReloadError::CacheCreationError(ref err) => Some(err),
+            HotReloadError::IoError { ref error, .. } => Some(error),
+            HotReloadError::ModuleCreationError(ref err) => Some(err),
+            #[cfg(feature = "runtime-shader-compilation")]
+            HotReloadError::CompilationError(ref err) => Some(err),
+            HotReloadError::BuildFailed(ref err) => Some(&**err),
+            HotReloadError::BuilderPanicked => None,
+        }
+    }
+}
+
+impl fmt::Display for HotReloadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match error::Error::cause(self) {
+            Some(cause) => write!(fmt, "{}: {}", error::Error::description(self), cause),
+            None => write!(fmt, "{}", error::Error::description(self)),
+        }
+    }
+}
+
+impl From<PipelineCacheCreationError> for HotReloadError {
+    #[inline]
+    fn from(err: PipelineCacheCreationError) -> HotReloadError {
+        HotReloadError::CacheCreationError(err)
+    }
+}
+
+impl From<ShaderModuleCreationError> for HotReloadError {
+    #[inline]
+    fn from(err: ShaderModuleCreationError) -> HotReloadError {
+        HotReloadError::ModuleCreationError(err)
+    }
+}
+
+#[cfg(feature = "runtime-shader-compilation")]
+impl From<ShaderCompilationError> for HotReloadError {
+    #[inline]
+    fn from(err: ShaderCompilationError) -> HotReloadError {
+        HotReloadError::CompilationError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_file() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let sources = vec![ShaderSource::Spirv("this file doesn't exist.spv".into())];
+        let result = unsafe { HotReloadPipeline::new(device, None, sources, |_, _, _| Ok(())) };
+
+        match result {
+            Err(HotReloadError::IoError { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn builder_error() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = unsafe {
+            HotReloadPipeline::<()>::new(device, None, Vec::new(), |_, _, _| {
+                Err("failed".into())
+            })
+        };
+
+        match result {
+            Err(HotReloadError::BuildFailed(_)) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn rebuild() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let mut pipeline = unsafe {
+            HotReloadPipeline::new(device, None, Vec::new(), |_, _, modules| Ok(modules.len()))
+                .unwrap()
+        };
+        let first = pipeline.pipeline().clone();
+
+        pipeline.rebuild();
+        assert!(pipeline.is_rebuilding());
+        while !pipeline.poll().unwrap() {}
+
+        assert!(!pipeline.is_rebuilding());
+        assert!(!Arc::ptr_eq(&first, pipeline.pipeline()));
+    }
+}
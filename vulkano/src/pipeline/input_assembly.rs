@@ -46,16 +46,40 @@ impl InputAssembly {
 /// Note that some topologies don't support primitive restart.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum PrimitiveTopology {
+    /// Each vertex is a separate point.
     PointList,
+    /// Each pair of vertices is a separate line.
     LineList,
+    /// Each vertex forms a line with the previous vertex.
     LineStrip,
+    /// Each group of three vertices is a separate triangle.
     TriangleList,
+    /// Each vertex forms a triangle with the two previous vertices.
     TriangleStrip,
+    /// Each vertex forms a triangle with the previous vertex and the first vertex.
     TriangleFan,
+    /// Same as `LineList`, but each line is accompanied by two adjacent vertices that are only
+    /// visible from the geometry shader.
+    ///
+    /// Requires the `geometry_shader` feature to be enabled on the device.
     LineListWithAdjacency,
+    /// Same as `LineStrip`, but with adjacent vertices that are only visible from the geometry
+    /// shader.
+    ///
+    /// Requires the `geometry_shader` feature to be enabled on the device.
     LineStripWithAdjacency,
+    /// Same as `TriangleList`, but each triangle is accompanied by three adjacent vertices that
+    /// are only visible from the geometry shader.
+    ///
+    /// Requires the `geometry_shader` feature to be enabled on the device.
     TriangleListWithAdjacency,
+    /// Same as `TriangleStrip`, but with adjacent vertices that are only visible from the
+    /// geometry shader.
+    ///
+    /// Requires the `geometry_shader` feature to be enabled on the device.
     TriangleStripWithAdjacency,
+    /// Each group of `vertices_per_patch` vertices is a patch that is processed by the
+    /// tessellation shaders.
     PatchList { vertices_per_patch: u32 },
 }
 
@@ -95,6 +119,18 @@ impl PrimitiveTopology {
             _ => false,
         }
     }
+
+    /// Returns true if this primitive topology includes adjacency information.
+    #[inline]
+    pub fn has_adjacency(&self) -> bool {
+        match *self {
+            PrimitiveTopology::LineListWithAdjacency => true,
+            PrimitiveTopology::LineStripWithAdjacency => true,
+            PrimitiveTopology::TriangleListWithAdjacency => true,
+            PrimitiveTopology::TriangleStripWithAdjacency => true,
+            _ => false,
+        }
+    }
 }
 
 /// Trait for types that can be used as indices by the GPU.
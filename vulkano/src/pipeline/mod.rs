@@ -83,17 +83,46 @@ pub use self::graphics_pipeline::GraphicsPipeline;
 pub use self::graphics_pipeline::GraphicsPipelineAbstract;
 pub use self::graphics_pipeline::GraphicsPipelineBuilder;
 pub use self::graphics_pipeline::GraphicsPipelineCreationError;
+pub use self::graphics_pipeline::GraphicsPipelineDynamicStates;
 pub use self::graphics_pipeline::GraphicsPipelineSys;
+pub use self::graphics_pipeline::MeshShaderProperties;
+pub use self::graphics_pipeline::TransformFeedbackProperties;
+pub use self::ray_tracing_pipeline::RayTracingPipeline;
+pub use self::ray_tracing_pipeline::RayTracingPipelineAbstract;
+pub use self::ray_tracing_pipeline::RayTracingPipelineBuilder;
+pub use self::ray_tracing_pipeline::RayTracingPipelineCreationError;
+pub use self::ray_tracing_pipeline::RayTracingPipelineProperties;
+pub use self::ray_tracing_pipeline::RayTracingPipelineSys;
+pub use self::ray_tracing_pipeline::ShaderGroup;
+pub use self::shader_binding_table::ShaderBindingTable;
+pub use self::shader_binding_table::ShaderBindingTableCreationError;
+
+use vk;
 
 mod compute_pipeline;
 mod graphics_pipeline;
+mod ray_tracing_pipeline;
+mod shader_binding_table;
 
 pub mod blend;
 pub mod cache;
+#[cfg(feature = "runtime-shader-compilation")]
+pub mod compile;
 pub mod depth_stencil;
+pub mod hot_reload;
 pub mod input_assembly;
 pub mod multisample;
 pub mod raster;
+pub mod reflect;
 pub mod shader;
 pub mod vertex;
 pub mod viewport;
+
+/// The type of pipeline that descriptor sets and push descriptors are bound to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum PipelineBindPoint {
+    Graphics = vk::PIPELINE_BIND_POINT_GRAPHICS,
+    Compute = vk::PIPELINE_BIND_POINT_COMPUTE,
+    RayTracing = vk::PIPELINE_BIND_POINT_RAY_TRACING_KHR,
+}
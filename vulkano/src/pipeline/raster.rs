@@ -13,6 +13,9 @@
 //! of pixels or samples.
 //!
 
+use std::mem;
+
+use instance::PhysicalDevice;
 use vk;
 
 /// State of the rasterizer.
@@ -43,6 +46,14 @@ pub struct Rasterization {
     pub line_width: Option<f32>,
 
     pub depth_bias: DepthBiasControl,
+
+    /// Conservative rasterization mode. Anything other than `Disabled` requires the
+    /// `ext_conservative_rasterization` extension to be enabled on the device.
+    pub conservative_mode: ConservativeRasterizationMode,
+
+    /// Extra size in pixels by which primitives are enlarged when `conservative_mode` is
+    /// `Overestimate`. Ignored otherwise.
+    pub extra_primitive_overestimation_size: f32,
 }
 
 impl Default for Rasterization {
@@ -56,6 +67,8 @@ impl Default for Rasterization {
             front_face: Default::default(),
             line_width: Some(1.0),
             depth_bias: DepthBiasControl::Disabled,
+            conservative_mode: ConservativeRasterizationMode::Disabled,
+            extra_primitive_overestimation_size: 0.0,
         }
     }
 }
@@ -85,6 +98,92 @@ pub struct DepthBias {
     pub slope_factor: f32,
 }
 
+/// Specifies how conservative rasterization is performed.
+///
+/// With conservative rasterization, the coverage of the fragments no longer depends on whether
+/// the center of the samples is inside the primitive. This is for example useful for voxelization.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ConservativeRasterizationMode {
+    /// Conservative rasterization is disabled. This is the default.
+    Disabled = vk::CONSERVATIVE_RASTERIZATION_MODE_DISABLED_EXT,
+    /// A fragment is generated for every pixel that is even partially covered by the primitive.
+    Overestimate = vk::CONSERVATIVE_RASTERIZATION_MODE_OVERESTIMATE_EXT,
+    /// A fragment is only generated for pixels that are fully covered by the primitive.
+    Underestimate = vk::CONSERVATIVE_RASTERIZATION_MODE_UNDERESTIMATE_EXT,
+}
+
+impl Default for ConservativeRasterizationMode {
+    #[inline]
+    fn default() -> ConservativeRasterizationMode {
+        ConservativeRasterizationMode::Disabled
+    }
+}
+
+/// Properties of a physical device related to conservative rasterization.
+///
+/// Only meaningful if the physical device supports the `ext_conservative_rasterization`
+/// extension.
+#[derive(Copy, Clone, Debug)]
+pub struct ConservativeRasterizationProperties {
+    /// Size in pixels by which primitives are enlarged in overestimate mode, before any extra
+    /// overestimation is applied.
+    pub primitive_overestimation_size: f32,
+    /// Maximum value of `extra_primitive_overestimation_size`.
+    pub max_extra_primitive_overestimation_size: f32,
+    /// Granularity of `extra_primitive_overestimation_size`.
+    pub extra_primitive_overestimation_size_granularity: f32,
+    /// True if the underestimate mode is supported.
+    pub primitive_underestimation: bool,
+    /// True if conservative rasterization can be used with points and lines.
+    pub conservative_point_and_line_rasterization: bool,
+    /// True if degenerate triangles are rasterized in overestimate mode.
+    pub degenerate_triangles_rasterized: bool,
+    /// True if degenerate lines are rasterized.
+    pub degenerate_lines_rasterized: bool,
+    /// True if the `FullyCoveredEXT` fragment shader input variable is supported.
+    pub fully_covered_fragment_shader_input_variable: bool,
+    /// True if conservative rasterization is supported together with post-depth coverage.
+    pub conservative_rasterization_post_depth_coverage: bool,
+}
+
+impl ConservativeRasterizationProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice)
+                                -> ConservativeRasterizationProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceConservativeRasterizationPropertiesEXT =
+                mem::zeroed();
+            output.sType =
+                vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CONSERVATIVE_RASTERIZATION_PROPERTIES_EXT;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            ConservativeRasterizationProperties {
+                primitive_overestimation_size: output.primitiveOverestimationSize,
+                max_extra_primitive_overestimation_size: output
+                    .maxExtraPrimitiveOverestimationSize,
+                extra_primitive_overestimation_size_granularity: output
+                    .extraPrimitiveOverestimationSizeGranularity,
+                primitive_underestimation: output.primitiveUnderestimation != 0,
+                conservative_point_and_line_rasterization: output
+                    .conservativePointAndLineRasterization != 0,
+                degenerate_triangles_rasterized: output.degenerateTrianglesRasterized != 0,
+                degenerate_lines_rasterized: output.degenerateLinesRasterized != 0,
+                fully_covered_fragment_shader_input_variable: output
+                    .fullyCoveredFragmentShaderInputVariable != 0,
+                conservative_rasterization_post_depth_coverage: output
+                    .conservativeRasterizationPostDepthCoverage != 0,
+            }
+        }
+    }
+}
+
 /// Specifies the culling mode.
 ///
 /// This setting works in pair with `front_face`. The `front_face` setting tells the GPU whether
@@ -0,0 +1,758 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::ffi::CStr;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use descriptor::pipeline_layout::PipelineLayoutAbstract;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
+use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+use descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
+use descriptor::pipeline_layout::PipelineLayoutSuperset;
+use descriptor::pipeline_layout::PipelineLayoutSys;
+use instance::PhysicalDevice;
+use pipeline::cache::PipelineCache;
+use pipeline::shader::RayTracingEntryPointAbstract;
+use pipeline::shader::RayTracingShaderType;
+use pipeline::shader::ShaderCapabilitiesError;
+use pipeline::shader::ShaderModule;
+use pipeline::shader::SpecializationConstants;
+use pipeline::shader::SpecializationMapEntry;
+
+use Error;
+use OomError;
+use SafeDeref;
+use VulkanObject;
+use check_errors;
+use device::Device;
+use device::DeviceOwned;
+use vk;
+
+/// A pipeline object that describes to the Vulkan implementation how it should trace rays.
+///
+/// A ray tracing pipeline is made of shader stages, which are grouped into *shader groups*. The
+/// shader binding table passed to `AutoCommandBufferBuilder::trace_rays` references these groups
+/// in order to decide which shaders are invoked when a ray is generated, misses or hits some
+/// geometry.
+///
+/// Requires the `khr_ray_tracing_pipeline` extension to be enabled on the device.
+pub struct RayTracingPipeline<Pl> {
+    inner: Inner,
+    pipeline_layout: Pl,
+    groups: Vec<ShaderGroup>,
+    group_handles: Vec<u8>,
+    properties: RayTracingPipelineProperties,
+}
+
+struct Inner {
+    pipeline: vk::Pipeline,
+    device: Arc<Device>,
+    // False if the pipeline was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+impl RayTracingPipeline<()> {
+    /// Starts the building process of a ray tracing pipeline.
+    #[inline]
+    pub fn start<'a>() -> RayTracingPipelineBuilder<'a> {
+        RayTracingPipelineBuilder {
+            stages: Vec::new(),
+            groups: Vec::new(),
+            max_recursion_depth: 1,
+            cache: None,
+        }
+    }
+}
+
+impl<Pl> RayTracingPipeline<Pl> {
+    /// Builds a `RayTracingPipeline` from a raw handle that was created outside of vulkano.
+    ///
+    /// The handles of the shader groups are queried from the pipeline.
+    /// If `owned` is true, the pipeline is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid ray tracing pipeline of the device of `pipeline_layout`,
+    ///   created with a layout that is identical to `pipeline_layout`, and with the shader
+    ///   groups `groups` in the same order.
+    /// - If `owned` is true, the pipeline must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(pipeline_layout: Pl, handle: vk::Pipeline, groups: Vec<ShaderGroup>,
+                           owned: bool)
+                           -> Result<RayTracingPipeline<Pl>, RayTracingPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
+    {
+        let device = pipeline_layout.device().clone();
+        let properties =
+            RayTracingPipelineProperties::from_physical_device(device.physical_device());
+
+        let inner = Inner {
+            pipeline: handle,
+            device: device,
+            needs_destruction: owned,
+        };
+
+        let group_handles = group_handles(&inner, groups.len() as u32, &properties)?;
+
+        Ok(RayTracingPipeline {
+               inner: inner,
+               pipeline_layout: pipeline_layout,
+               groups: groups,
+               group_handles: group_handles,
+               properties: properties,
+           })
+    }
+
+    /// Returns the `Device` this pipeline was created with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.inner.device
+    }
+
+    /// Returns the pipeline layout used in this pipeline.
+    #[inline]
+    pub fn layout(&self) -> &Pl {
+        &self.pipeline_layout
+    }
+
+    /// Returns the number of shader groups of the pipeline.
+    #[inline]
+    pub fn num_groups(&self) -> u32 {
+        self.groups.len() as u32
+    }
+
+    /// Returns the opaque handle of a shader group, which is what the shader binding table
+    /// contains. Returns `None` if the group doesn't exist.
+    #[inline]
+    pub fn group_handle(&self, group: u32) -> Option<&[u8]> {
+        let size = self.properties.shader_group_handle_size as usize;
+        let start = group as usize * size;
+        self.group_handles.get(start .. start + size)
+    }
+
+    /// Returns the properties of the physical device that were used to create the pipeline.
+    #[inline]
+    pub fn properties(&self) -> &RayTracingPipelineProperties {
+        &self.properties
+    }
+
+    #[inline]
+    pub(crate) fn group(&self, group: u32) -> Option<&ShaderGroup> {
+        self.groups.get(group as usize)
+    }
+}
+
+impl<Pl> fmt::Debug for RayTracingPipeline<Pl> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan ray tracing pipeline {:?}>", self.inner.pipeline)
+    }
+}
+
+/// Trait implemented on all ray tracing pipelines.
+pub unsafe trait RayTracingPipelineAbstract: PipelineLayoutAbstract {
+    /// Returns an opaque object that represents the inside of the ray tracing pipeline.
+    fn inner(&self) -> RayTracingPipelineSys;
+}
+
+unsafe impl<Pl> RayTracingPipelineAbstract for RayTracingPipeline<Pl>
+    where Pl: PipelineLayoutAbstract
+{
+    #[inline]
+    fn inner(&self) -> RayTracingPipelineSys {
+        RayTracingPipelineSys(self.inner.pipeline, PhantomData)
+    }
+}
+
+unsafe impl<T> RayTracingPipelineAbstract for T
+    where T: SafeDeref,
+          T::Target: RayTracingPipelineAbstract
+{
+    #[inline]
+    fn inner(&self) -> RayTracingPipelineSys {
+        (**self).inner()
+    }
+}
+
+/// Opaque object that represents the inside of the ray tracing pipeline. Can be made into a
+/// trait object.
+#[derive(Debug, Copy, Clone)]
+pub struct RayTracingPipelineSys<'a>(vk::Pipeline, PhantomData<&'a ()>);
+
+unsafe impl<'a> VulkanObject for RayTracingPipelineSys<'a> {
+    type Object = vk::Pipeline;
+
+    const TYPE: vk::DebugReportObjectTypeEXT = vk::DEBUG_REPORT_OBJECT_TYPE_PIPELINE_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Pipeline {
+        self.0
+    }
+}
+
+unsafe impl<Pl> PipelineLayoutAbstract for RayTracingPipeline<Pl>
+    where Pl: PipelineLayoutAbstract
+{
+    #[inline]
+    fn sys(&self) -> PipelineLayoutSys {
+        self.layout().sys()
+    }
+
+    #[inline]
+    fn descriptor_set_layout(&self, index: usize) -> Option<&Arc<UnsafeDescriptorSetLayout>> {
+        self.layout().descriptor_set_layout(index)
+    }
+}
+
+unsafe impl<Pl> PipelineLayoutDesc for RayTracingPipeline<Pl>
+    where Pl: PipelineLayoutDesc
+{
+    #[inline]
+    fn num_sets(&self) -> usize {
+        self.pipeline_layout.num_sets()
+    }
+
+    #[inline]
+    fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
+        self.pipeline_layout.num_bindings_in_set(set)
+    }
+
+    #[inline]
+    fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
+        self.pipeline_layout.descriptor(set, binding)
+    }
+
+    #[inline]
+    fn num_push_constants_ranges(&self) -> usize {
+        self.pipeline_layout.num_push_constants_ranges()
+    }
+
+    #[inline]
+    fn push_constants_range(&self, num: usize) -> Option<PipelineLayoutDescPcRange> {
+        self.pipeline_layout.push_constants_range(num)
+    }
+}
+
+unsafe impl<Pl> DeviceOwned for RayTracingPipeline<Pl> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device()
+    }
+}
+
+unsafe impl<Pl> VulkanObject for RayTracingPipeline<Pl> {
+    type Object = vk::Pipeline;
+
+    const TYPE: vk::DebugReportObjectTypeEXT = vk::DEBUG_REPORT_OBJECT_TYPE_PIPELINE_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Pipeline {
+        self.inner.pipeline
+    }
+}
+
+impl Drop for Inner {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyPipeline(self.device.internal_object(), self.pipeline, ptr::null());
+        }
+    }
+}
+
+/// Limits of the physical device regarding ray tracing pipelines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RayTracingPipelineProperties {
+    /// Size in bytes of the handle of a shader group.
+    pub shader_group_handle_size: u32,
+    /// Maximum value of the maximum recursion depth of a pipeline.
+    pub max_ray_recursion_depth: u32,
+    /// Maximum stride between the shader groups of a shader binding table.
+    pub max_shader_group_stride: u32,
+    /// Required alignment of the start of each region of a shader binding table.
+    pub shader_group_base_alignment: u32,
+    /// Maximum number of rays that a single `trace_rays` command can generate.
+    pub max_ray_dispatch_invocation_count: u32,
+    /// Required alignment of each shader group handle within a shader binding table.
+    pub shader_group_handle_alignment: u32,
+    /// Maximum size in bytes of the hit attributes.
+    pub max_ray_hit_attribute_size: u32,
+}
+
+impl RayTracingPipelineProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> RayTracingPipelineProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceRayTracingPipelinePropertiesKHR = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_RAY_TRACING_PIPELINE_PROPERTIES_KHR;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            RayTracingPipelineProperties {
+                shader_group_handle_size: output.shaderGroupHandleSize,
+                max_ray_recursion_depth: output.maxRayRecursionDepth,
+                max_shader_group_stride: output.maxShaderGroupStride,
+                shader_group_base_alignment: output.shaderGroupBaseAlignment,
+                max_ray_dispatch_invocation_count: output.maxRayDispatchInvocationCount,
+                shader_group_handle_alignment: output.shaderGroupHandleAlignment,
+                max_ray_hit_attribute_size: output.maxRayHitAttributeSize,
+            }
+        }
+    }
+}
+
+/// A shader group of a ray tracing pipeline. The values are indices within the stages of the
+/// pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderGroup {
+    /// A ray generation, miss or callable shader.
+    General(u32),
+    /// A hit group for triangle geometries.
+    TrianglesHit {
+        closest_hit: Option<u32>,
+        any_hit: Option<u32>,
+    },
+    /// A hit group for procedural geometries.
+    ProceduralHit {
+        intersection: u32,
+        closest_hit: Option<u32>,
+        any_hit: Option<u32>,
+    },
+}
+
+// Queries the opaque handles of the first `num_groups` shader groups of a pipeline.
+fn group_handles(inner: &Inner, num_groups: u32, properties: &RayTracingPipelineProperties)
+                 -> Result<Vec<u8>, Error> {
+    unsafe {
+        let vk = inner.device.pointers();
+        let size = num_groups as usize * properties.shader_group_handle_size as usize;
+        let mut handles: Vec<u8> = Vec::with_capacity(size);
+        check_errors(vk.GetRayTracingShaderGroupHandlesKHR(inner.device.internal_object(),
+                                                           inner.pipeline,
+                                                           0,
+                                                           num_groups,
+                                                           size,
+                                                           handles.as_mut_ptr() as *mut _))?;
+        handles.set_len(size);
+        Ok(handles)
+    }
+}
+
+struct Stage<'a> {
+    module: &'a ShaderModule,
+    name: &'a CStr,
+    ty: RayTracingShaderType,
+    layout: &'a PipelineLayoutDesc,
+    spec_descriptors: &'static [SpecializationMapEntry],
+    spec_data: &'a [u8],
+}
+
+/// Prototype for a `RayTracingPipeline`.
+///
+/// Created with `RayTracingPipeline::start`. Stages are numbered in the order they are added,
+/// starting from 0, and shader groups are numbered in the same way.
+pub struct RayTracingPipelineBuilder<'a> {
+    stages: Vec<Stage<'a>>,
+    groups: Vec<ShaderGroup>,
+    max_recursion_depth: u32,
+    cache: Option<Arc<PipelineCache>>,
+}
+
+impl<'a> RayTracingPipelineBuilder<'a> {
+    /// Adds a shader stage to the pipeline. Its index is the number of stages added before.
+    #[inline]
+    pub fn stage<E>(mut self, entry_point: &'a E,
+                    specialization: &'a E::SpecializationConstants)
+                    -> RayTracingPipelineBuilder<'a>
+        where E: RayTracingEntryPointAbstract,
+              E::PipelineLayout: 'a
+    {
+        let spec_data = unsafe {
+            slice::from_raw_parts(specialization as *const E::SpecializationConstants as *const u8,
+                                  mem::size_of_val(specialization))
+        };
+
+        self.stages.push(Stage {
+                             module: entry_point.module(),
+                             name: entry_point.name(),
+                             ty: entry_point.ty(),
+                             layout: entry_point.layout(),
+                             spec_descriptors: E::SpecializationConstants::descriptors(),
+                             spec_data: spec_data,
+                         });
+        self
+    }
+
+    /// Adds a group that contains a single ray generation, miss or callable stage.
+    #[inline]
+    pub fn general_group(mut self, stage: u32) -> RayTracingPipelineBuilder<'a> {
+        self.groups.push(ShaderGroup::General(stage));
+        self
+    }
+
+    /// Adds a hit group for triangle geometry, with an optional closest-hit stage and an optional
+    /// any-hit stage.
+    #[inline]
+    pub fn triangles_hit_group(mut self, closest_hit: Option<u32>, any_hit: Option<u32>)
+                               -> RayTracingPipelineBuilder<'a> {
+        self.groups.push(ShaderGroup::TrianglesHit {
+                             closest_hit: closest_hit,
+                             any_hit: any_hit,
+                         });
+        self
+    }
+
+    /// Adds a hit group for bounding box geometry, with an intersection stage, an optional
+    /// closest-hit stage and an optional any-hit stage.
+    #[inline]
+    pub fn procedural_hit_group(mut self, intersection: u32, closest_hit: Option<u32>,
+                                any_hit: Option<u32>)
+                                -> RayTracingPipelineBuilder<'a> {
+        self.groups.push(ShaderGroup::ProceduralHit {
+                             intersection: intersection,
+                             closest_hit: closest_hit,
+                             any_hit: any_hit,
+                         });
+        self
+    }
+
+    /// Sets the maximum depth of recursive calls to `traceRayEXT`. The default is 1, which means
+    /// that only the ray generation shader can trace rays.
+    #[inline]
+    pub fn max_recursion_depth(mut self, depth: u32) -> RayTracingPipelineBuilder<'a> {
+        self.max_recursion_depth = depth;
+        self
+    }
+
+    /// Uses `cache` when building the pipeline. The implementation can reuse the data of the
+    /// cache to build the pipeline faster, and stores the result of the compilation in the cache.
+    ///
+    /// # Panic
+    ///
+    /// - Panics when building if `cache` doesn't belong to the same device as the pipeline.
+    ///
+    #[inline]
+    pub fn pipeline_cache(mut self, cache: Arc<PipelineCache>) -> RayTracingPipelineBuilder<'a> {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Builds the pipeline with `pipeline_layout`, which must be a superset of the layouts of
+    /// all the stages.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the shader modules, the pipeline layout or the pipeline cache weren't created
+    ///   with `device`.
+    ///
+    pub fn build<Pl>(self, device: Arc<Device>, pipeline_layout: Pl)
+                     -> Result<RayTracingPipeline<Pl>, RayTracingPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
+    {
+        if !device.loaded_extensions().khr_ray_tracing_pipeline {
+            return Err(RayTracingPipelineCreationError::ExtensionNotEnabled);
+        }
+
+        assert_eq!(pipeline_layout.device().internal_object(),
+                   device.internal_object());
+        if let Some(ref cache) = self.cache {
+            assert_eq!(cache.device().internal_object(), device.internal_object());
+        }
+
+        let physical_device = device.physical_device();
+        let properties = RayTracingPipelineProperties::from_physical_device(physical_device);
+        if self.max_recursion_depth > properties.max_ray_recursion_depth {
+            return Err(RayTracingPipelineCreationError::MaxRecursionDepthExceeded {
+                           max: properties.max_ray_recursion_depth,
+                           requested: self.max_recursion_depth,
+                       });
+        }
+
+        for stage in self.stages.iter() {
+            assert_eq!(stage.module.device().internal_object(),
+                       device.internal_object());
+            stage.module.check_features(device.enabled_features())?;
+            stage.module.check_extensions(device.loaded_extensions())?;
+            PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout, stage.layout)?;
+        }
+
+        // Checks that each stage referenced by a group exists and has the right type.
+        let raw_groups = {
+            let stage_ty = |index: Option<u32>, group: usize, expected: &[RayTracingShaderType]| {
+                let index = match index {
+                    Some(i) => i,
+                    None => return Ok(vk::SHADER_UNUSED_KHR),
+                };
+
+                match self.stages.get(index as usize) {
+                    Some(stage) if expected.contains(&stage.ty) => Ok(index),
+                    _ => Err(RayTracingPipelineCreationError::InvalidShaderGroup {
+                                 group: group as u32,
+                             }),
+                }
+            };
+
+            let mut raw_groups = SmallVec::<[_; 8]>::new();
+            for (num, group) in self.groups.iter().enumerate() {
+                let (ty, general, closest_hit, any_hit, intersection) = match *group {
+                    ShaderGroup::General(stage) => {
+                        let general = stage_ty(Some(stage),
+                                               num,
+                                               &[RayTracingShaderType::RayGeneration,
+                                                 RayTracingShaderType::Miss,
+                                                 RayTracingShaderType::Callable])?;
+                        (vk::RAY_TRACING_SHADER_GROUP_TYPE_GENERAL_KHR,
+                         general,
+                         vk::SHADER_UNUSED_KHR,
+                         vk::SHADER_UNUSED_KHR,
+                         vk::SHADER_UNUSED_KHR)
+                    },
+                    ShaderGroup::TrianglesHit { closest_hit, any_hit } => {
+                        (vk::RAY_TRACING_SHADER_GROUP_TYPE_TRIANGLES_HIT_GROUP_KHR,
+                         vk::SHADER_UNUSED_KHR,
+                         stage_ty(closest_hit, num, &[RayTracingShaderType::ClosestHit])?,
+                         stage_ty(any_hit, num, &[RayTracingShaderType::AnyHit])?,
+                         vk::SHADER_UNUSED_KHR)
+                    },
+                    ShaderGroup::ProceduralHit {
+                        intersection,
+                        closest_hit,
+                        any_hit,
+                    } => {
+                        (vk::RAY_TRACING_SHADER_GROUP_TYPE_PROCEDURAL_HIT_GROUP_KHR,
+                         vk::SHADER_UNUSED_KHR,
+                         stage_ty(closest_hit, num, &[RayTracingShaderType::ClosestHit])?,
+                         stage_ty(any_hit, num, &[RayTracingShaderType::AnyHit])?,
+                         stage_ty(Some(intersection), num, &[RayTracingShaderType::Intersection])?)
+                    },
+                };
+
+                raw_groups.push(vk::RayTracingShaderGroupCreateInfoKHR {
+                                    sType: vk::STRUCTURE_TYPE_RAY_TRACING_SHADER_GROUP_CREATE_INFO_KHR,
+                                    pNext: ptr::null(),
+                                    ty: ty,
+                                    generalShader: general,
+                                    closestHitShader: closest_hit,
+                                    anyHitShader: any_hit,
+                                    intersectionShader: intersection,
+                                    pShaderGroupCaptureReplayHandle: ptr::null(),
+                                });
+            }
+            raw_groups
+        };
+
+        let vk = device.pointers();
+
+        let pipeline = unsafe {
+            let specializations = self.stages
+                .iter()
+                .map(|stage| {
+                         vk::SpecializationInfo {
+                             mapEntryCount: stage.spec_descriptors.len() as u32,
+                             pMapEntries: stage.spec_descriptors.as_ptr() as *const _,
+                             dataSize: stage.spec_data.len(),
+                             pData: stage.spec_data.as_ptr() as *const _,
+                         }
+                     })
+                .collect::<SmallVec<[_; 8]>>();
+
+            let stages = self.stages
+                .iter()
+                .zip(specializations.iter())
+                .map(|(stage, specialization)| {
+                    vk::PipelineShaderStageCreateInfo {
+                        sType: vk::STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO,
+                        pNext: ptr::null(),
+                        flags: 0,
+                        stage: stage.ty.into_vulkan_bits(),
+                        module: stage.module.internal_object(),
+                        pName: stage.name.as_ptr(),
+                        pSpecializationInfo: if specialization.dataSize == 0 {
+                            ptr::null()
+                        } else {
+                            specialization
+                        },
+                    }
+                })
+                .collect::<SmallVec<[_; 8]>>();
+
+            let infos = vk::RayTracingPipelineCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_RAY_TRACING_PIPELINE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                flags: 0,
+                stageCount: stages.len() as u32,
+                pStages: stages.as_ptr(),
+                groupCount: raw_groups.len() as u32,
+                pGroups: raw_groups.as_ptr(),
+                maxPipelineRayRecursionDepth: self.max_recursion_depth,
+                pLibraryInfo: ptr::null(),
+                pLibraryInterface: ptr::null(),
+                pDynamicState: ptr::null(),
+                layout: PipelineLayoutAbstract::sys(&pipeline_layout).internal_object(),
+                basePipelineHandle: 0,
+                basePipelineIndex: -1,
+            };
+
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateRayTracingPipelinesKHR(device.internal_object(),
+                                                         0,
+                                                         self.cache
+                                                             .as_ref()
+                                                             .map(|c| c.internal_object())
+                                                             .unwrap_or(0),
+                                                         1,
+                                                         &infos,
+                                                         ptr::null(),
+                                                         &mut output))?;
+            output
+        };
+
+        // Wrapping the pipeline immediately so that it's destroyed if querying the handles fails.
+        let inner = Inner {
+            pipeline: pipeline,
+            device: device.clone(),
+            needs_destruction: true,
+        };
+
+        let group_handles = group_handles(&inner, raw_groups.len() as u32, &properties)?;
+
+        Ok(RayTracingPipeline {
+               inner: inner,
+               pipeline_layout: pipeline_layout,
+               groups: self.groups,
+               group_handles: group_handles,
+               properties: properties,
+           })
+    }
+}
+
+impl<'a> fmt::Debug for RayTracingPipelineBuilder<'a> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("RayTracingPipelineBuilder")
+            .field("num_stages", &self.stages.len())
+            .field("groups", &self.groups)
+            .field("max_recursion_depth", &self.max_recursion_depth)
+            .finish()
+    }
+}
+
+/// Error that can happen when creating a ray tracing pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RayTracingPipelineCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `VK_KHR_ray_tracing_pipeline` extension must be enabled when creating the device.
+    ExtensionNotEnabled,
+    /// The pipeline layout is not compatible with what the shaders expect.
+    IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
+    /// A shader requires a feature that is not enabled on the device.
+    ShaderFeatureNotEnabled(ShaderCapabilitiesError),
+    /// A shader group references a stage that doesn't exist or whose type isn't allowed in the
+    /// group.
+    InvalidShaderGroup {
+        /// Index of the group.
+        group: u32,
+    },
+    /// The maximum recursion depth exceeds the limit of the device.
+    MaxRecursionDepthExceeded {
+        /// The limit of the device.
+        max: u32,
+        /// The recursion depth that was requested.
+        requested: u32,
+    },
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for RayTracingPipelineCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            RayTracingPipelineCreationError::OomError(_) => "not enough memory available",
+            RayTracingPipelineCreationError::ExtensionNotEnabled =>
+                "the device extension `VK_KHR_ray_tracing_pipeline` is not enabled",
+            RayTracingPipelineCreationError::IncompatiblePipelineLayout(_) =>
+                "the pipeline layout is not compatible with what the shaders expect",
+            RayTracingPipelineCreationError::ShaderFeatureNotEnabled(_) =>
+                "a shader requires a feature that is not enabled on the device",
+            RayTracingPipelineCreationError::InvalidShaderGroup { .. } =>
+                "a shader group references a stage that doesn't exist or has the wrong type",
+            RayTracingPipelineCreationError::MaxRecursionDepthExceeded { .. } =>
+                "the maximum recursion depth exceeds the limit of the device",
+            RayTracingPipelineCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            RayTracingPipelineCreationError::OomError(ref err) => Some(err),
+            RayTracingPipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            RayTracingPipelineCreationError::ShaderFeatureNotEnabled(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(RayTracingPipelineCreationError, OomError {});
+
+impl From<PipelineLayoutNotSupersetError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: PipelineLayoutNotSupersetError) -> RayTracingPipelineCreationError {
+        RayTracingPipelineCreationError::IncompatiblePipelineLayout(err)
+    }
+}
+
+impl From<ShaderCapabilitiesError> for RayTracingPipelineCreationError {
+    #[inline]
+    fn from(err: ShaderCapabilitiesError) -> RayTracingPipelineCreationError {
+        RayTracingPipelineCreationError::ShaderFeatureNotEnabled(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::pipeline_layout::EmptyPipelineDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use pipeline::RayTracingPipeline;
+    use pipeline::RayTracingPipelineCreationError;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        let layout = EmptyPipelineDesc.build(device.clone()).unwrap();
+
+        match RayTracingPipeline::start().build(device, layout) {
+            Err(RayTracingPipelineCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
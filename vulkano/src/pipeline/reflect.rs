@@ -0,0 +1,1262 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reflection over SPIR-V code at runtime.
+//!
+//! The `vulkano-shaders` crate generates the descriptions of the layout and of the interface of
+//! a shader at compile time. When shaders are only known at runtime, for example because they are
+//! loaded from files, the same information can be obtained with a `ShaderReflection`.
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::Device;
+//! use vulkano::descriptor::pipeline_layout::PipelineLayoutDesc;
+//! use vulkano::pipeline::reflect::ShaderReflection;
+//!
+//! # let device: Arc<Device> = return;
+//! # let words: Vec<u32> = return;
+//! let reflection = ShaderReflection::new(&words).unwrap();
+//! let entry_point = reflection.entry_points().first().unwrap();
+//!
+//! // Builds the descriptor set layouts and the pipeline layout of the shader.
+//! let layout = reflection.layout_desc(entry_point.stages()).build(device.clone()).unwrap();
+//! ```
+//!
+//! The layouts of the different stages of a graphics pipeline can be combined with
+//! `PipelineLayoutDesc::union`.
+//!
+//! The inputs and outputs of a stage can be scalars, vectors, matrices or arrays of 8, 16, 32 or
+//! 64-bit numbers, or IO blocks whose members are of these types. Each member of an IO block is
+//! reported as a separate element of the interface. Boolean types and structures nested inside
+//! an IO block are not supported, and `ShaderReflection::new` returns
+//! `ShaderReflectionError::UnsupportedType` for them.
+
+use fnv::FnvHasher;
+use std::borrow::Cow;
+use std::cmp;
+use std::collections::HashMap;
+use std::error;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fmt;
+use std::hash::BuildHasherDefault;
+
+use descriptor::descriptor::DescriptorBufferDesc;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor::DescriptorImageDesc;
+use descriptor::descriptor::DescriptorImageDescArray;
+use descriptor::descriptor::DescriptorImageDescDimensions;
+use descriptor::descriptor::ShaderStages;
+use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+use descriptor::pipeline_layout::RuntimePipelineDesc;
+use format::Format;
+use pipeline::shader::ExecutionModel;
+use pipeline::shader::RuntimeShaderInterfaceDef;
+use pipeline::shader::SPIRV_MAGIC_NUMBER;
+use pipeline::shader::ShaderInterfaceDefEntry;
+
+// Opcodes.
+const OP_NAME: u32 = 5;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_SAMPLER: u32 = 26;
+const OP_TYPE_SAMPLED_IMAGE: u32 = 27;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u32 = 29;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+const OP_TYPE_ACCELERATION_STRUCTURE_KHR: u32 = 5341;
+
+// Decorations.
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_NON_WRITABLE: u32 = 24;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+const DECORATION_OFFSET: u32 = 35;
+
+// Storage classes.
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_OUTPUT: u32 = 3;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+// Image dimensions.
+const DIM_1D: u32 = 0;
+const DIM_2D: u32 = 1;
+const DIM_3D: u32 = 2;
+const DIM_CUBE: u32 = 3;
+const DIM_BUFFER: u32 = 5;
+const DIM_SUBPASS_DATA: u32 = 6;
+
+/// Information about the descriptors, push constants and entry points of a SPIR-V module,
+/// obtained by parsing its code.
+#[derive(Debug, Clone)]
+pub struct ShaderReflection {
+    entry_points: Vec<EntryPointReflection>,
+    descriptors: Vec<ReflectedDescriptor>,
+    push_constants_size: usize,
+}
+
+impl ShaderReflection {
+    /// Parses the SPIR-V code of a shader module.
+    ///
+    /// Returns an error if the code is malformed, or if it uses types that can't be described
+    /// with a `DescriptorDesc` or a `ShaderInterfaceDefEntry`.
+    pub fn new(spirv: &[u32]) -> Result<ShaderReflection, ShaderReflectionError> {
+        let module = Module::parse(spirv)?;
+
+        // Descriptors are declared at the module level. Variables that share the same set and
+        // binding are merged.
+        let mut descriptors: Vec<ReflectedDescriptor> = Vec::new();
+        for &(id, ty, storage_class) in &module.variables {
+            let set = match module.decoration(id, DECORATION_DESCRIPTOR_SET) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let binding = match module.decoration(id, DECORATION_BINDING) {
+                Some(b) => b,
+                None => return Err(ShaderReflectionError::MissingBinding { variable: id }),
+            };
+
+            let desc = module.descriptor_desc(id, module.pointee(ty)?, storage_class)?;
+
+            if let Some(existing) = descriptors
+                .iter_mut()
+                .find(|d| d.set == set && d.binding == binding)
+            {
+                match existing.desc.union(&desc) {
+                    Some(union) => existing.desc = union,
+                    None => return Err(ShaderReflectionError::ConflictingDescriptors {
+                                           set: set,
+                                           binding: binding,
+                                       }),
+                }
+                continue;
+            }
+
+            descriptors.push(ReflectedDescriptor {
+                                 set: set,
+                                 binding: binding,
+                                 name: module.names.get(&id).cloned(),
+                                 desc: desc,
+                             });
+        }
+
+        let mut push_constants_size = 0;
+        for &(_, ty, storage_class) in &module.variables {
+            if storage_class != STORAGE_CLASS_PUSH_CONSTANT {
+                continue;
+            }
+
+            let size = module.size_of(module.pointee(ty)?)?;
+            push_constants_size = cmp::max(push_constants_size, size);
+        }
+
+        let mut entry_points = Vec::with_capacity(module.entry_points.len());
+        for &(execution_model, ref name, ref interface) in &module.entry_points {
+            entry_points.push(module.entry_point(execution_model, name, interface)?);
+        }
+
+        Ok(ShaderReflection {
+               entry_points: entry_points,
+               descriptors: descriptors,
+               push_constants_size: push_constants_size,
+           })
+    }
+
+    /// Returns the entry points declared in the module.
+    #[inline]
+    pub fn entry_points(&self) -> &[EntryPointReflection] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point with the given name, if it is declared in the module.
+    #[inline]
+    pub fn entry_point(&self, name: &CStr) -> Option<&EntryPointReflection> {
+        self.entry_points
+            .iter()
+            .find(|e| e.name.as_c_str() == name)
+    }
+
+    /// Returns the descriptors declared in the module.
+    ///
+    /// The `stages` of each descriptor are empty. They are filled by `layout_desc`.
+    #[inline]
+    pub fn descriptors(&self) -> &[ReflectedDescriptor] {
+        &self.descriptors
+    }
+
+    /// Returns the size in bytes of the push constants of the module, or 0 if the module doesn't
+    /// use push constants.
+    #[inline]
+    pub fn push_constants_size(&self) -> usize {
+        self.push_constants_size
+    }
+
+    /// Builds the description of a pipeline layout that contains all the descriptors and the push
+    /// constants of the module, accessed by the given stages.
+    ///
+    /// The stages are usually the ones returned by `EntryPointReflection::stages`. Calling `build`
+    /// on the description creates the corresponding descriptor set layouts and pipeline layout.
+    pub fn layout_desc(&self, stages: ShaderStages) -> RuntimePipelineDesc {
+        let num_sets = self.descriptors
+            .iter()
+            .map(|d| d.set as usize + 1)
+            .max()
+            .unwrap_or(0);
+
+        let mut sets: Vec<Vec<Option<DescriptorDesc>>> = (0 .. num_sets).map(|_| Vec::new())
+            .collect();
+        for descriptor in &self.descriptors {
+            let set = &mut sets[descriptor.set as usize];
+            let binding = descriptor.binding as usize;
+            if set.len() <= binding {
+                set.resize(binding + 1, None);
+            }
+
+            set[binding] = Some(DescriptorDesc {
+                                    stages: stages,
+                                    ..descriptor.desc.clone()
+                                });
+        }
+
+        let push_constants = if self.push_constants_size != 0 {
+            Some(PipelineLayoutDescPcRange {
+                     offset: 0,
+                     size: self.push_constants_size,
+                     stages: stages,
+                 })
+        } else {
+            None
+        };
+
+        // There is at most one range of push constants, so there can't be any conflict.
+        RuntimePipelineDesc::new(sets, push_constants).unwrap()
+    }
+}
+
+/// Descriptor declared in a SPIR-V module.
+#[derive(Debug, Clone)]
+pub struct ReflectedDescriptor {
+    /// Index of the descriptor set.
+    pub set: u32,
+    /// Binding of the descriptor within its set.
+    pub binding: u32,
+    /// Name of the variable, if the module contains debug information.
+    pub name: Option<String>,
+    /// Description of the descriptor.
+    pub desc: DescriptorDesc,
+}
+
+/// Entry point declared in a SPIR-V module, with the description of its inputs and outputs.
+#[derive(Debug, Clone)]
+pub struct EntryPointReflection {
+    name: CString,
+    execution_model: ExecutionModel,
+    input: RuntimeShaderInterfaceDef,
+    output: RuntimeShaderInterfaceDef,
+}
+
+impl EntryPointReflection {
+    /// Returns the name of the entry point.
+    #[inline]
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the stage the entry point is written for.
+    #[inline]
+    pub fn execution_model(&self) -> ExecutionModel {
+        self.execution_model
+    }
+
+    /// Returns the shader stages that correspond to the execution model of the entry point.
+    #[inline]
+    pub fn stages(&self) -> ShaderStages {
+        match self.execution_model {
+            ExecutionModel::Vertex => ShaderStages { vertex: true, ..ShaderStages::none() },
+            ExecutionModel::TessellationControl => {
+                ShaderStages { tessellation_control: true, ..ShaderStages::none() }
+            },
+            ExecutionModel::TessellationEvaluation => {
+                ShaderStages { tessellation_evaluation: true, ..ShaderStages::none() }
+            },
+            ExecutionModel::Geometry => ShaderStages { geometry: true, ..ShaderStages::none() },
+            ExecutionModel::Fragment => ShaderStages { fragment: true, ..ShaderStages::none() },
+            ExecutionModel::GLCompute => ShaderStages { compute: true, ..ShaderStages::none() },
+            ExecutionModel::Kernel => ShaderStages::none(),
+            ExecutionModel::RayGeneration => ShaderStages { raygen: true, ..ShaderStages::none() },
+            ExecutionModel::Intersection => {
+                ShaderStages { intersection: true, ..ShaderStages::none() }
+            },
+            ExecutionModel::AnyHit => ShaderStages { any_hit: true, ..ShaderStages::none() },
+            ExecutionModel::ClosestHit => {
+                ShaderStages { closest_hit: true, ..ShaderStages::none() }
+            },
+            ExecutionModel::Miss => ShaderStages { miss: true, ..ShaderStages::none() },
+            ExecutionModel::Callable => ShaderStages { callable: true, ..ShaderStages::none() },
+            ExecutionModel::Task => ShaderStages { task: true, ..ShaderStages::none() },
+            ExecutionModel::Mesh => ShaderStages { mesh: true, ..ShaderStages::none() },
+        }
+    }
+
+    /// Returns the description of the inputs of the entry point. Built-in variables are ignored.
+    #[inline]
+    pub fn input(&self) -> &RuntimeShaderInterfaceDef {
+        &self.input
+    }
+
+    /// Returns the description of the outputs of the entry point. Built-in variables are ignored.
+    #[inline]
+    pub fn output(&self) -> &RuntimeShaderInterfaceDef {
+        &self.output
+    }
+}
+
+// A type declared in the SPIR-V code. Types that are irrelevant for the reflection are omitted.
+#[derive(Debug, Clone)]
+enum Type {
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+    Vector { component: u32, count: u32 },
+    Matrix { column: u32, count: u32 },
+    Image {
+        dim: u32,
+        arrayed: bool,
+        multisampled: bool,
+        sampled: u32,
+    },
+    Sampler,
+    SampledImage { image: u32 },
+    Array { element: u32, length: u32 },
+    RuntimeArray { element: u32 },
+    Struct { members: Vec<u32> },
+    Pointer { pointee: u32 },
+    AccelerationStructure,
+}
+
+// The instructions of a SPIR-V module that are relevant for the reflection.
+struct Module {
+    names: HashMap<u32, String, BuildHasherDefault<FnvHasher>>,
+    // Target, decoration, first operand or 0.
+    decorations: Vec<(u32, u32, u32)>,
+    // Structure, member, decoration, first operand or 0.
+    member_decorations: Vec<(u32, u32, u32, u32)>,
+    types: HashMap<u32, Type, BuildHasherDefault<FnvHasher>>,
+    constants: HashMap<u32, u64, BuildHasherDefault<FnvHasher>>,
+    // Id, pointer type, storage class.
+    variables: Vec<(u32, u32, u32)>,
+    // Execution model, name, interface ids.
+    entry_points: Vec<(ExecutionModel, CString, Vec<u32>)>,
+}
+
+impl Module {
+    fn parse(spirv: &[u32]) -> Result<Module, ShaderReflectionError> {
+        if spirv.len() < 5 {
+            return Err(ShaderReflectionError::MissingHeader);
+        }
+
+        if spirv[0] != SPIRV_MAGIC_NUMBER {
+            if spirv[0] == SPIRV_MAGIC_NUMBER.swap_bytes() {
+                return Err(ShaderReflectionError::WrongEndianness);
+            }
+            return Err(ShaderReflectionError::WrongMagicNumber);
+        }
+
+        let mut module = Module {
+            names: HashMap::default(),
+            decorations: Vec::new(),
+            member_decorations: Vec::new(),
+            types: HashMap::default(),
+            constants: HashMap::default(),
+            variables: Vec::new(),
+            entry_points: Vec::new(),
+        };
+
+        let mut instructions = &spirv[5 ..];
+
+        while !instructions.is_empty() {
+            let word_count = (instructions[0] >> 16) as usize;
+            let opcode = instructions[0] & 0xffff;
+
+            if word_count == 0 || word_count > instructions.len() {
+                return Err(ShaderReflectionError::InvalidInstruction);
+            }
+
+            let operands = &instructions[1 .. word_count];
+            instructions = &instructions[word_count ..];
+
+            // Minimum number of operands of each instruction.
+            let min_operands = match opcode {
+                OP_NAME => 2,
+                OP_ENTRY_POINT => 3,
+                OP_TYPE_INT => 3,
+                OP_TYPE_FLOAT => 2,
+                OP_TYPE_VECTOR | OP_TYPE_MATRIX => 3,
+                OP_TYPE_IMAGE => 8,
+                OP_TYPE_SAMPLER => 1,
+                OP_TYPE_SAMPLED_IMAGE => 2,
+                OP_TYPE_ACCELERATION_STRUCTURE_KHR => 1,
+                OP_TYPE_ARRAY => 3,
+                OP_TYPE_RUNTIME_ARRAY => 2,
+                OP_TYPE_STRUCT => 1,
+                OP_TYPE_POINTER => 3,
+                OP_CONSTANT | OP_SPEC_CONSTANT => 3,
+                OP_VARIABLE => 3,
+                OP_DECORATE => 2,
+                OP_MEMBER_DECORATE => 3,
+                _ => continue,
+            };
+
+            if operands.len() < min_operands {
+                return Err(ShaderReflectionError::InvalidInstruction);
+            }
+
+            match opcode {
+                OP_NAME => {
+                    let (name, _) = parse_string(&operands[1 ..])?;
+                    if let Ok(name) = String::from_utf8(name) {
+                        if !name.is_empty() {
+                            module.names.insert(operands[0], name);
+                        }
+                    }
+                },
+                OP_ENTRY_POINT => {
+                    let execution_model = match ExecutionModel::from_num(operands[0]) {
+                        Some(m) => m,
+                        None => return Err(ShaderReflectionError::InvalidInstruction),
+                    };
+                    let (name, len) = parse_string(&operands[2 ..])?;
+                    let name = match CString::new(name) {
+                        Ok(n) => n,
+                        Err(_) => return Err(ShaderReflectionError::InvalidInstruction),
+                    };
+                    let interface = operands[2 + len ..].to_vec();
+                    module.entry_points.push((execution_model, name, interface));
+                },
+                OP_TYPE_INT => {
+                    module.types.insert(operands[0],
+                                        Type::Int {
+                                            width: operands[1],
+                                            signed: operands[2] != 0,
+                                        });
+                },
+                OP_TYPE_FLOAT => {
+                    module.types.insert(operands[0], Type::Float { width: operands[1] });
+                },
+                OP_TYPE_VECTOR => {
+                    module.types.insert(operands[0],
+                                        Type::Vector {
+                                            component: operands[1],
+                                            count: operands[2],
+                                        });
+                },
+                OP_TYPE_MATRIX => {
+                    module.types.insert(operands[0],
+                                        Type::Matrix {
+                                            column: operands[1],
+                                            count: operands[2],
+                                        });
+                },
+                OP_TYPE_IMAGE => {
+                    module.types.insert(operands[0],
+                                        Type::Image {
+                                            dim: operands[2],
+                                            arrayed: operands[4] != 0,
+                                            multisampled: operands[5] != 0,
+                                            sampled: operands[6],
+                                        });
+                },
+                OP_TYPE_SAMPLER => {
+                    module.types.insert(operands[0], Type::Sampler);
+                },
+                OP_TYPE_SAMPLED_IMAGE => {
+                    module.types.insert(operands[0], Type::SampledImage { image: operands[1] });
+                },
+                OP_TYPE_ACCELERATION_STRUCTURE_KHR => {
+                    module.types.insert(operands[0], Type::AccelerationStructure);
+                },
+                OP_TYPE_ARRAY => {
+                    module.types.insert(operands[0],
+                                        Type::Array {
+                                            element: operands[1],
+                                            length: operands[2],
+                                        });
+                },
+                OP_TYPE_RUNTIME_ARRAY => {
+                    module.types.insert(operands[0], Type::RuntimeArray { element: operands[1] });
+                },
+                OP_TYPE_STRUCT => {
+                    module.types.insert(operands[0],
+                                        Type::Struct { members: operands[1 ..].to_vec() });
+                },
+                OP_TYPE_POINTER => {
+                    module.types.insert(operands[0], Type::Pointer { pointee: operands[2] });
+                },
+                OP_CONSTANT | OP_SPEC_CONSTANT => {
+                    // Literals are stored low-order word first.
+                    let value = operands[2 ..]
+                        .iter()
+                        .take(2)
+                        .rev()
+                        .fold(0u64, |a, &b| (a << 32) | b as u64);
+                    module.constants.insert(operands[1], value);
+                },
+                OP_VARIABLE => {
+                    module.variables.push((operands[1], operands[0], operands[2]));
+                },
+                OP_DECORATE => {
+                    let param = operands.get(2).cloned().unwrap_or(0);
+                    module.decorations.push((operands[0], operands[1], param));
+                },
+                OP_MEMBER_DECORATE => {
+                    let param = operands.get(3).cloned().unwrap_or(0);
+                    module
+                        .member_decorations
+                        .push((operands[0], operands[1], operands[2], param));
+                },
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(module)
+    }
+
+    // Returns the first operand of the given decoration of `target`, or 0 if the decoration
+    // has no operand. Returns `None` if `target` doesn't have the decoration.
+    fn decoration(&self, target: u32, decoration: u32) -> Option<u32> {
+        self.decorations
+            .iter()
+            .find(|&&(t, d, _)| t == target && d == decoration)
+            .map(|&(_, _, p)| p)
+    }
+
+    // Same as `decoration`, but for a member of a structure.
+    fn member_decoration(&self, target: u32, member: u32, decoration: u32) -> Option<u32> {
+        self.member_decorations
+            .iter()
+            .find(|&&(t, m, d, _)| t == target && m == member && d == decoration)
+            .map(|&(_, _, _, p)| p)
+    }
+
+    fn ty(&self, id: u32) -> Result<&Type, ShaderReflectionError> {
+        match self.types.get(&id) {
+            Some(ty) => Ok(ty),
+            None => Err(ShaderReflectionError::UnsupportedType { id: id }),
+        }
+    }
+
+    fn pointee(&self, pointer: u32) -> Result<u32, ShaderReflectionError> {
+        match *self.ty(pointer)? {
+            Type::Pointer { pointee } => Ok(pointee),
+            _ => Err(ShaderReflectionError::InvalidInstruction),
+        }
+    }
+
+    fn constant(&self, id: u32) -> Result<u64, ShaderReflectionError> {
+        match self.constants.get(&id) {
+            Some(&value) => Ok(value),
+            None => Err(ShaderReflectionError::InvalidInstruction),
+        }
+    }
+
+    // Builds the description of the descriptor that corresponds to `variable`, whose type is
+    // `ty`.
+    fn descriptor_desc(&self, variable: u32, ty: u32, storage_class: u32)
+                       -> Result<DescriptorDesc, ShaderReflectionError> {
+        let (ty, array_count, runtime_array) = match *self.ty(ty)? {
+            Type::Array { element, length } => (element, self.constant(length)? as u32, false),
+            // The size of a runtime-sized array is only known when the layout is created. The
+            // layout must contain at least one element.
+            Type::RuntimeArray { element } => (element, 1, true),
+            _ => (ty, 1, false),
+        };
+
+        let (desc_ty, writable) = match *self.ty(ty)? {
+            Type::Struct { ref members } => {
+                let storage = storage_class == STORAGE_CLASS_STORAGE_BUFFER ||
+                    self.decoration(ty, DECORATION_BUFFER_BLOCK).is_some();
+                let non_writable = (0 .. members.len() as u32).all(|m| {
+                    self.member_decoration(ty, m, DECORATION_NON_WRITABLE).is_some()
+                });
+                let desc_ty = DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                           dynamic: Some(false),
+                                                           storage: storage,
+                                                       });
+                (desc_ty, storage && !non_writable)
+            },
+            Type::Image { .. } => self.image_desc_ty(ty, false)?,
+            Type::SampledImage { image } => self.image_desc_ty(image, true)?,
+            Type::Sampler => (DescriptorDescTy::Sampler, false),
+            Type::AccelerationStructure => (DescriptorDescTy::AccelerationStructure, false),
+            _ => return Err(ShaderReflectionError::UnsupportedType { id: ty }),
+        };
+
+        let readonly = !writable || self.decoration(variable, DECORATION_NON_WRITABLE).is_some();
+
+        Ok(DescriptorDesc {
+               ty: desc_ty,
+               array_count: array_count,
+               runtime_array: runtime_array,
+               stages: ShaderStages::none(),
+               readonly: readonly,
+           })
+    }
+
+    // Returns the descriptor type of the image `id`, and whether the shader can write to it.
+    fn image_desc_ty(&self, id: u32, combined_sampler: bool)
+                     -> Result<(DescriptorDescTy, bool), ShaderReflectionError> {
+        let (dim, arrayed, multisampled, sampled) = match *self.ty(id)? {
+            Type::Image {
+                dim,
+                arrayed,
+                multisampled,
+                sampled,
+            } => (dim, arrayed, multisampled, sampled),
+            _ => return Err(ShaderReflectionError::UnsupportedType { id: id }),
+        };
+
+        // Vulkan requires a `Sampled` operand of 1 (sampled) or 2 (storage).
+        let storage = match sampled {
+            1 => false,
+            2 => true,
+            _ => return Err(ShaderReflectionError::UnsupportedType { id: id }),
+        };
+
+        let array_layers = if arrayed {
+            DescriptorImageDescArray::Arrayed { max_layers: None }
+        } else {
+            DescriptorImageDescArray::NonArrayed
+        };
+
+        let dimensions = match dim {
+            DIM_SUBPASS_DATA if !combined_sampler => {
+                let desc_ty = DescriptorDescTy::InputAttachment {
+                    multisampled: multisampled,
+                    array_layers: array_layers,
+                };
+                return Ok((desc_ty, false));
+            },
+            DIM_BUFFER if !combined_sampler => {
+                let desc_ty = DescriptorDescTy::TexelBuffer {
+                    storage: storage,
+                    format: None, // TODO: specify format if known
+                };
+                return Ok((desc_ty, storage));
+            },
+            DIM_1D => DescriptorImageDescDimensions::OneDimensional,
+            DIM_2D => DescriptorImageDescDimensions::TwoDimensional,
+            DIM_3D => DescriptorImageDescDimensions::ThreeDimensional,
+            DIM_CUBE => DescriptorImageDescDimensions::Cube,
+            _ => return Err(ShaderReflectionError::UnsupportedType { id: id }),
+        };
+
+        let desc = DescriptorImageDesc {
+            sampled: !storage,
+            dimensions: dimensions,
+            format: None, // TODO: specify format if known
+            multisampled: multisampled,
+            array_layers: array_layers,
+        };
+
+        if combined_sampler {
+            Ok((DescriptorDescTy::CombinedImageSampler(desc), false))
+        } else {
+            Ok((DescriptorDescTy::Image(desc), storage))
+        }
+    }
+
+    // Returns the size in bytes of a type, taking the explicit layout decorations into account.
+    fn size_of(&self, id: u32) -> Result<usize, ShaderReflectionError> {
+        match *self.ty(id)? {
+            Type::Int { width, .. } |
+            Type::Float { width } => Ok(width as usize / 8),
+            Type::Vector { component, count } => Ok(self.size_of(component)? * count as usize),
+            Type::Matrix { column, count } => Ok(self.size_of(column)? * count as usize),
+            Type::Array { element, length } => {
+                let stride = match self.decoration(id, DECORATION_ARRAY_STRIDE) {
+                    Some(stride) => stride as usize,
+                    None => self.size_of(element)?,
+                };
+                Ok(stride * self.constant(length)? as usize)
+            },
+            Type::RuntimeArray { .. } => Err(ShaderReflectionError::RuntimeSizedPushConstants),
+            Type::Struct { ref members } => {
+                let mut end = 0;
+                for (num, &member) in members.iter().enumerate() {
+                    let num = num as u32;
+                    let offset = match self.member_decoration(id, num, DECORATION_OFFSET) {
+                        Some(offset) => offset as usize,
+                        None => end,
+                    };
+
+                    let stride = self.member_decoration(id, num, DECORATION_MATRIX_STRIDE);
+                    let size = match (self.ty(member)?, stride) {
+                        (&Type::Matrix { count, .. }, Some(stride)) => {
+                            stride as usize * count as usize
+                        },
+                        _ => self.size_of(member)?,
+                    };
+
+                    end = cmp::max(end, offset + size);
+                }
+                Ok(end)
+            },
+            _ => Err(ShaderReflectionError::UnsupportedType { id: id }),
+        }
+    }
+
+    // Returns the format of an input or output of type `id` and the number of locations it
+    // covers. If `ignore_first_array` is true, the outermost array is considered to be the
+    // per-vertex array of a tessellation or geometry shader.
+    //
+    // Scalars, vectors, matrices and arrays of 8, 16, 32 and 64-bit numbers are supported. Booleans
+    // and structures (other than the IO blocks handled by `entry_point`) are not, and return
+    // `UnsupportedType`.
+    fn interface_format(&self, id: u32, ignore_first_array: bool)
+                        -> Result<(Format, u32), ShaderReflectionError> {
+        let unsupported = ShaderReflectionError::UnsupportedType { id: id };
+
+        match *self.ty(id)? {
+            Type::Array { element, .. } if ignore_first_array => {
+                self.interface_format(element, false)
+            },
+            Type::Int { width, signed } => {
+                let format = match (width, signed) {
+                    (8, true) => Format::R8Sint,
+                    (8, false) => Format::R8Uint,
+                    (16, true) => Format::R16Sint,
+                    (16, false) => Format::R16Uint,
+                    (32, true) => Format::R32Sint,
+                    (32, false) => Format::R32Uint,
+                    (64, true) => Format::R64Sint,
+                    (64, false) => Format::R64Uint,
+                    _ => return Err(unsupported),
+                };
+                Ok((format, 1))
+            },
+            Type::Float { width } => {
+                let format = match width {
+                    16 => Format::R16Sfloat,
+                    32 => Format::R32Sfloat,
+                    64 => Format::R64Sfloat,
+                    _ => return Err(unsupported),
+                };
+                Ok((format, 1))
+            },
+            Type::Vector { component, count } => {
+                let (scalar, _) = self.interface_format(component, false)?;
+                let format = match vector_format(scalar, count) {
+                    Some(f) => f,
+                    None => return Err(unsupported),
+                };
+                // A location holds 16 bytes, so vectors of three or four 64-bit components use
+                // two locations.
+                let locations = if format.size().unwrap_or(0) > 16 { 2 } else { 1 };
+                Ok((format, locations))
+            },
+            Type::Matrix { column, count } => {
+                let (format, locations) = self.interface_format(column, false)?;
+                Ok((format, locations * count))
+            },
+            Type::Array { element, length } => {
+                let (format, locations) = self.interface_format(element, false)?;
+                Ok((format, locations * self.constant(length)? as u32))
+            },
+            _ => Err(unsupported),
+        }
+    }
+
+    // If the input or output of type `id` is an IO block, returns the id of the structure and the
+    // types of its members.
+    fn io_block(&self, id: u32, ignore_first_array: bool)
+                -> Result<Option<(u32, &[u32])>, ShaderReflectionError> {
+        let id = match *self.ty(id)? {
+            Type::Array { element, .. } if ignore_first_array => element,
+            _ => id,
+        };
+
+        match *self.ty(id)? {
+            Type::Struct { ref members } => Ok(Some((id, &members[..]))),
+            _ => Ok(None),
+        }
+    }
+
+    // Returns true if the variable is a built-in, or a block of built-ins such as `gl_PerVertex`.
+    fn is_builtin(&self, variable: u32, pointee: u32) -> bool {
+        if self.decoration(variable, DECORATION_BUILT_IN).is_some() {
+            return true;
+        }
+
+        let pointee = match self.types.get(&pointee) {
+            Some(&Type::Array { element, .. }) => element,
+            _ => pointee,
+        };
+
+        self.member_decoration(pointee, 0, DECORATION_BUILT_IN)
+            .is_some()
+    }
+
+    fn entry_point(&self, execution_model: ExecutionModel, name: &CString, interface: &[u32])
+                   -> Result<EntryPointReflection, ShaderReflectionError> {
+        // The inputs of tessellation and geometry shaders, and the outputs of tessellation control
+        // shaders, are arrays with one element per vertex.
+        let (ignore_first_array_in, ignore_first_array_out) = match execution_model {
+            ExecutionModel::TessellationControl => (true, true),
+            ExecutionModel::TessellationEvaluation => (true, false),
+            ExecutionModel::Geometry => (true, false),
+            _ => (false, false),
+        };
+
+        let mut input = Vec::new();
+        let mut output = Vec::new();
+
+        for &id in interface {
+            let (ty, storage_class) = match self.variables.iter().find(|v| v.0 == id) {
+                Some(&(_, ty, storage_class)) => (ty, storage_class),
+                None => continue,
+            };
+
+            let (elements, ignore_first_array) = match storage_class {
+                STORAGE_CLASS_INPUT => (&mut input, ignore_first_array_in),
+                STORAGE_CLASS_OUTPUT => (&mut output, ignore_first_array_out),
+                _ => continue,
+            };
+
+            let pointee = self.pointee(ty)?;
+            if self.is_builtin(id, pointee) {
+                continue;
+            }
+
+            let location = self.decoration(id, DECORATION_LOCATION);
+
+            // The members of an IO block use consecutive locations, starting at the location of
+            // the block, unless they have their own `Location` decoration.
+            if let Some((block, members)) = self.io_block(pointee, ignore_first_array)? {
+                let mut next_location = location;
+                for (num, &member) in members.iter().enumerate() {
+                    let start = match self.member_decoration(block, num as u32,
+                                                             DECORATION_LOCATION)
+                        .or(next_location)
+                    {
+                        Some(l) => l,
+                        None => return Err(ShaderReflectionError::MissingLocation { variable: id }),
+                    };
+
+                    let (format, locations) = self.interface_format(member, false)?;
+                    elements.push(ShaderInterfaceDefEntry {
+                                      location: start .. start + locations,
+                                      format: format,
+                                      name: None,
+                                  });
+                    next_location = Some(start + locations);
+                }
+                continue;
+            }
+
+            let location = match location {
+                Some(l) => l,
+                None => return Err(ShaderReflectionError::MissingLocation { variable: id }),
+            };
+
+            let (format, locations) = self.interface_format(pointee, ignore_first_array)?;
+
+            elements.push(ShaderInterfaceDefEntry {
+                              location: location .. location + locations,
+                              format: format,
+                              name: self.names.get(&id).map(|n| Cow::Owned(n.clone())),
+                          });
+        }
+
+        input.sort_by_key(|e| e.location.start);
+        output.sort_by_key(|e| e.location.start);
+
+        Ok(EntryPointReflection {
+               name: name.clone(),
+               execution_model: execution_model,
+               input: RuntimeShaderInterfaceDef::new(input),
+               output: RuntimeShaderInterfaceDef::new(output),
+           })
+    }
+}
+
+// Returns the format of a vector of `count` components whose format is `scalar`.
+fn vector_format(scalar: Format, count: u32) -> Option<Format> {
+    let format = match (scalar, count) {
+        (scalar, 1) => scalar,
+        (Format::R8Sint, 2) => Format::R8G8Sint,
+        (Format::R8Sint, 3) => Format::R8G8B8Sint,
+        (Format::R8Sint, 4) => Format::R8G8B8A8Sint,
+        (Format::R16Sint, 2) => Format::R16G16Sint,
+        (Format::R16Sint, 3) => Format::R16G16B16Sint,
+        (Format::R16Sint, 4) => Format::R16G16B16A16Sint,
+        (Format::R32Sint, 2) => Format::R32G32Sint,
+        (Format::R32Sint, 3) => Format::R32G32B32Sint,
+        (Format::R32Sint, 4) => Format::R32G32B32A32Sint,
+        (Format::R64Sint, 2) => Format::R64G64Sint,
+        (Format::R64Sint, 3) => Format::R64G64B64Sint,
+        (Format::R64Sint, 4) => Format::R64G64B64A64Sint,
+        (Format::R8Uint, 2) => Format::R8G8Uint,
+        (Format::R8Uint, 3) => Format::R8G8B8Uint,
+        (Format::R8Uint, 4) => Format::R8G8B8A8Uint,
+        (Format::R16Uint, 2) => Format::R16G16Uint,
+        (Format::R16Uint, 3) => Format::R16G16B16Uint,
+        (Format::R16Uint, 4) => Format::R16G16B16A16Uint,
+        (Format::R32Uint, 2) => Format::R32G32Uint,
+        (Format::R32Uint, 3) => Format::R32G32B32Uint,
+        (Format::R32Uint, 4) => Format::R32G32B32A32Uint,
+        (Format::R64Uint, 2) => Format::R64G64Uint,
+        (Format::R64Uint, 3) => Format::R64G64B64Uint,
+        (Format::R64Uint, 4) => Format::R64G64B64A64Uint,
+        (Format::R16Sfloat, 2) => Format::R16G16Sfloat,
+        (Format::R16Sfloat, 3) => Format::R16G16B16Sfloat,
+        (Format::R16Sfloat, 4) => Format::R16G16B16A16Sfloat,
+        (Format::R32Sfloat, 2) => Format::R32G32Sfloat,
+        (Format::R32Sfloat, 3) => Format::R32G32B32Sfloat,
+        (Format::R32Sfloat, 4) => Format::R32G32B32A32Sfloat,
+        (Format::R64Sfloat, 2) => Format::R64G64Sfloat,
+        (Format::R64Sfloat, 3) => Format::R64G64B64Sfloat,
+        (Format::R64Sfloat, 4) => Format::R64G64B64A64Sfloat,
+        _ => return None,
+    };
+    Some(format)
+}
+
+// Parses a nul-terminated literal string, packed in little-endian order. Returns the bytes of the
+// string and the number of words it occupies.
+fn parse_string(words: &[u32]) -> Result<(Vec<u8>, usize), ShaderReflectionError> {
+    let mut bytes = Vec::new();
+
+    for (num, &word) in words.iter().enumerate() {
+        for n in 0 .. 4 {
+            let byte = ((word >> (n * 8)) & 0xff) as u8;
+            if byte == 0 {
+                return Ok((bytes, num + 1));
+            }
+            bytes.push(byte);
+        }
+    }
+
+    Err(ShaderReflectionError::InvalidInstruction)
+}
+
+/// Error that can happen when parsing SPIR-V code for reflection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderReflectionError {
+    /// The code is too short to contain the SPIR-V header.
+    MissingHeader,
+    /// The code doesn't start with the SPIR-V magic number.
+    WrongMagicNumber,
+    /// The code starts with the byte-swapped SPIR-V magic number, which means that it was
+    /// written with the wrong endianness.
+    WrongEndianness,
+    /// An instruction is malformed or refers to an id that doesn't exist.
+    InvalidInstruction,
+    /// A variable has a `DescriptorSet` decoration but no `Binding` decoration.
+    MissingBinding {
+        /// Id of the variable.
+        variable: u32,
+    },
+    /// An input or output variable that isn't a built-in has no `Location` decoration.
+    MissingLocation {
+        /// Id of the variable.
+        variable: u32,
+    },
+    /// Two variables with incompatible types use the same binding.
+    ConflictingDescriptors {
+        /// Index of the descriptor set.
+        set: u32,
+        /// Binding within the set.
+        binding: u32,
+    },
+    /// The push constants contain a runtime-sized array.
+    RuntimeSizedPushConstants,
+    /// A descriptor, push constant or interface variable has a type that isn't supported.
+    UnsupportedType {
+        /// Id of the type.
+        id: u32,
+    },
+}
+
+impl error::Error for ShaderReflectionError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderReflectionError::MissingHeader => {
+                "the code is too short to contain the SPIR-V header"
+            },
+            ShaderReflectionError::WrongMagicNumber => {
+                "the code doesn't start with the SPIR-V magic number"
+            },
+            ShaderReflectionError::WrongEndianness => {
+                "the code was written with the wrong endianness"
+            },
+            ShaderReflectionError::InvalidInstruction => {
+                "an instruction is malformed or refers to an id that doesn't exist"
+            },
+            ShaderReflectionError::MissingBinding { .. } => {
+                "a variable has a `DescriptorSet` decoration but no `Binding` decoration"
+            },
+            ShaderReflectionError::MissingLocation { .. } => {
+                "an input or output variable that isn't a built-in has no `Location` decoration"
+            },
+            ShaderReflectionError::ConflictingDescriptors { .. } => {
+                "two variables with incompatible types use the same binding"
+            },
+            ShaderReflectionError::RuntimeSizedPushConstants => {
+                "the push constants contain a runtime-sized array"
+            },
+            ShaderReflectionError::UnsupportedType { .. } => {
+                "a descriptor, push constant or interface variable has a type that isn't supported"
+            },
+        }
+    }
+}
+
+impl fmt::Display for ShaderReflectionError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use pipeline::shader::ShaderInterfaceDef;
+
+    fn instruction(opcode: u32, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | opcode];
+        words.extend_from_slice(operands);
+        words
+    }
+
+    // Fragment shader with a uniform buffer at set 1 binding 0, a combined image sampler at set
+    // 0 binding 2, push constants made of a `vec4` and a `float`, one `vec2` input at location
+    // 0 and one `vec4` output at location 0.
+    fn fragment_shader() -> Vec<u32> {
+        let mut words = vec![SPIRV_MAGIC_NUMBER, 0x00010000, 0, 100, 0];
+        // OpEntryPoint Fragment %1 "main" %20 %21
+        words.extend(instruction(OP_ENTRY_POINT, &[4, 1, 0x6e69616d, 0, 20, 21]));
+        words.extend(instruction(OP_DECORATE, &[10, 2])); // Block
+        words.extend(instruction(OP_DECORATE, &[11, DECORATION_DESCRIPTOR_SET, 1]));
+        words.extend(instruction(OP_DECORATE, &[11, DECORATION_BINDING, 0]));
+        words.extend(instruction(OP_DECORATE, &[13, DECORATION_DESCRIPTOR_SET, 0]));
+        words.extend(instruction(OP_DECORATE, &[13, DECORATION_BINDING, 2]));
+        words.extend(instruction(OP_MEMBER_DECORATE, &[14, 0, DECORATION_OFFSET, 0]));
+        words.extend(instruction(OP_MEMBER_DECORATE, &[14, 1, DECORATION_OFFSET, 16]));
+        words.extend(instruction(OP_DECORATE, &[20, DECORATION_LOCATION, 0]));
+        words.extend(instruction(OP_DECORATE, &[21, DECORATION_LOCATION, 0]));
+        // %2 = float, %3 = vec4, %4 = vec2
+        words.extend(instruction(OP_TYPE_FLOAT, &[2, 32]));
+        words.extend(instruction(OP_TYPE_VECTOR, &[3, 2, 4]));
+        words.extend(instruction(OP_TYPE_VECTOR, &[4, 2, 2]));
+        // Uniform buffer.
+        words.extend(instruction(OP_TYPE_STRUCT, &[10, 3]));
+        words.extend(instruction(OP_TYPE_POINTER, &[30, 2, 10]));
+        words.extend(instruction(OP_VARIABLE, &[30, 11, 2]));
+        // Combined image sampler.
+        words.extend(instruction(OP_TYPE_IMAGE, &[5, 2, DIM_2D, 0, 0, 0, 1, 0]));
+        words.extend(instruction(OP_TYPE_SAMPLED_IMAGE, &[6, 5]));
+        words.extend(instruction(OP_TYPE_POINTER, &[31, 0, 6]));
+        words.extend(instruction(OP_VARIABLE, &[31, 13, 0]));
+        // Push constants.
+        words.extend(instruction(OP_TYPE_STRUCT, &[14, 3, 2]));
+        words.extend(instruction(OP_TYPE_POINTER, &[32, STORAGE_CLASS_PUSH_CONSTANT, 14]));
+        words.extend(instruction(OP_VARIABLE, &[32, 15, STORAGE_CLASS_PUSH_CONSTANT]));
+        // Input and output.
+        words.extend(instruction(OP_TYPE_POINTER, &[33, STORAGE_CLASS_INPUT, 4]));
+        words.extend(instruction(OP_TYPE_POINTER, &[34, STORAGE_CLASS_OUTPUT, 3]));
+        words.extend(instruction(OP_VARIABLE, &[33, 20, STORAGE_CLASS_INPUT]));
+        words.extend(instruction(OP_VARIABLE, &[34, 21, STORAGE_CLASS_OUTPUT]));
+        words.extend(instruction(OP_NAME, &[20, 0x00007675])); // "uv"
+        words
+    }
+
+    #[test]
+    fn descriptors_and_push_constants() {
+        let reflection = ShaderReflection::new(&fragment_shader()).unwrap();
+
+        assert_eq!(reflection.descriptors().len(), 2);
+        assert_eq!(reflection.push_constants_size(), 20);
+
+        let stages = reflection.entry_points()[0].stages();
+        assert_eq!(stages, ShaderStages { fragment: true, ..ShaderStages::none() });
+
+        let layout = reflection.layout_desc(stages);
+        assert_eq!(layout.num_sets(), 2);
+        assert_eq!(layout.num_bindings_in_set(0), Some(3));
+        assert_eq!(layout.num_bindings_in_set(1), Some(1));
+        assert!(layout.descriptor(0, 0).is_none());
+
+        match layout.descriptor(1, 0).unwrap().ty {
+            DescriptorDescTy::Buffer(DescriptorBufferDesc { storage: false, .. }) => (),
+            _ => panic!(),
+        }
+
+        let sampler = layout.descriptor(0, 2).unwrap();
+        assert_eq!(sampler.stages, stages);
+        match sampler.ty {
+            DescriptorDescTy::CombinedImageSampler(ref desc) => {
+                assert_eq!(desc.dimensions, DescriptorImageDescDimensions::TwoDimensional);
+            },
+            _ => panic!(),
+        }
+
+        assert_eq!(layout.num_push_constants_ranges(), 1);
+        assert_eq!(layout.push_constants_range(0).unwrap().size, 20);
+    }
+
+    #[test]
+    fn stage_interface() {
+        let reflection = ShaderReflection::new(&fragment_shader()).unwrap();
+        let entry_point = reflection.entry_point(CStr::from_bytes_with_nul(b"main\0").unwrap())
+            .unwrap();
+        assert_eq!(entry_point.execution_model(), ExecutionModel::Fragment);
+
+        let input = entry_point.input().elements().collect::<Vec<_>>();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].location, 0 .. 1);
+        assert_eq!(input[0].format, Format::R32G32Sfloat);
+        assert_eq!(input[0].name.as_ref().map(|n| &**n), Some("uv"));
+
+        let output = entry_point.output().elements().collect::<Vec<_>>();
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].format, Format::R32G32B32A32Sfloat);
+        assert!(output[0].name.is_none());
+    }
+
+    #[test]
+    fn io_block_and_wide_types() {
+        let mut words = vec![SPIRV_MAGIC_NUMBER, 0x00010000, 0, 100, 0];
+        // OpEntryPoint Vertex %1 "main" %20 %21
+        words.extend(instruction(OP_ENTRY_POINT, &[0, 1, 0x6e69616d, 0, 20, 21]));
+        words.extend(instruction(OP_DECORATE, &[10, 2])); // Block
+        words.extend(instruction(OP_MEMBER_DECORATE, &[10, 2, DECORATION_LOCATION, 7]));
+        words.extend(instruction(OP_DECORATE, &[20, DECORATION_LOCATION, 0]));
+        words.extend(instruction(OP_DECORATE, &[21, DECORATION_LOCATION, 2]));
+        // %2 = float, %3 = vec4, %4 = double, %5 = dvec3, %6 = float16_t, %7 = f16vec2
+        words.extend(instruction(OP_TYPE_FLOAT, &[2, 32]));
+        words.extend(instruction(OP_TYPE_VECTOR, &[3, 2, 4]));
+        words.extend(instruction(OP_TYPE_FLOAT, &[4, 64]));
+        words.extend(instruction(OP_TYPE_VECTOR, &[5, 4, 3]));
+        words.extend(instruction(OP_TYPE_FLOAT, &[6, 16]));
+        words.extend(instruction(OP_TYPE_VECTOR, &[7, 6, 2]));
+        // Output block { vec4; dvec3; layout(location = 7) float16_t; }
+        words.extend(instruction(OP_TYPE_STRUCT, &[10, 3, 5, 6]));
+        words.extend(instruction(OP_TYPE_POINTER, &[33, STORAGE_CLASS_INPUT, 7]));
+        words.extend(instruction(OP_TYPE_POINTER, &[34, STORAGE_CLASS_OUTPUT, 10]));
+        words.extend(instruction(OP_VARIABLE, &[33, 20, STORAGE_CLASS_INPUT]));
+        words.extend(instruction(OP_VARIABLE, &[34, 21, STORAGE_CLASS_OUTPUT]));
+
+        let reflection = ShaderReflection::new(&words).unwrap();
+        let entry_point = &reflection.entry_points()[0];
+
+        let input = entry_point.input().elements().collect::<Vec<_>>();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].location, 0 .. 1);
+        assert_eq!(input[0].format, Format::R16G16Sfloat);
+
+        let output = entry_point.output().elements().collect::<Vec<_>>();
+        assert_eq!(output.len(), 3);
+        assert_eq!(output[0].location, 2 .. 3);
+        assert_eq!(output[0].format, Format::R32G32B32A32Sfloat);
+        assert_eq!(output[1].location, 3 .. 5);
+        assert_eq!(output[1].format, Format::R64G64B64Sfloat);
+        assert_eq!(output[2].location, 7 .. 8);
+        assert_eq!(output[2].format, Format::R16Sfloat);
+    }
+
+    #[test]
+    fn nested_struct_in_io_block() {
+        let mut words = vec![SPIRV_MAGIC_NUMBER, 0x00010000, 0, 100, 0];
+        // OpEntryPoint Vertex %1 "main" %21
+        words.extend(instruction(OP_ENTRY_POINT, &[0, 1, 0x6e69616d, 0, 21]));
+        words.extend(instruction(OP_DECORATE, &[10, 2])); // Block
+        words.extend(instruction(OP_DECORATE, &[21, DECORATION_LOCATION, 0]));
+        words.extend(instruction(OP_TYPE_FLOAT, &[2, 32]));
+        words.extend(instruction(OP_TYPE_STRUCT, &[3, 2]));
+        words.extend(instruction(OP_TYPE_STRUCT, &[10, 3]));
+        words.extend(instruction(OP_TYPE_POINTER, &[34, STORAGE_CLASS_OUTPUT, 10]));
+        words.extend(instruction(OP_VARIABLE, &[34, 21, STORAGE_CLASS_OUTPUT]));
+
+        assert_eq!(ShaderReflection::new(&words).unwrap_err(),
+                   ShaderReflectionError::UnsupportedType { id: 3 });
+    }
+
+    #[test]
+    fn acceleration_structure() {
+        let mut words = vec![SPIRV_MAGIC_NUMBER, 0x00010400, 0, 100, 0];
+        // OpEntryPoint GLCompute %1 "main"
+        words.extend(instruction(OP_ENTRY_POINT, &[5, 1, 0x6e69616d, 0]));
+        words.extend(instruction(OP_DECORATE, &[11, DECORATION_DESCRIPTOR_SET, 0]));
+        words.extend(instruction(OP_DECORATE, &[11, DECORATION_BINDING, 1]));
+        words.extend(instruction(OP_TYPE_ACCELERATION_STRUCTURE_KHR, &[2]));
+        words.extend(instruction(OP_TYPE_POINTER, &[30, 0, 2]));
+        words.extend(instruction(OP_VARIABLE, &[30, 11, 0]));
+
+        let reflection = ShaderReflection::new(&words).unwrap();
+        let stages = reflection.entry_points()[0].stages();
+        assert_eq!(stages, ShaderStages { compute: true, ..ShaderStages::none() });
+
+        let layout = reflection.layout_desc(stages);
+        assert_eq!(layout.num_bindings_in_set(0), Some(2));
+        let tlas = layout.descriptor(0, 1).unwrap();
+        assert_eq!(tlas.array_count, 1);
+        assert_eq!(tlas.stages, stages);
+        match tlas.ty {
+            DescriptorDescTy::AccelerationStructure => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn missing_binding() {
+        let mut words = fragment_shader();
+        words.extend(instruction(OP_DECORATE, &[40, DECORATION_DESCRIPTOR_SET, 0]));
+        words.extend(instruction(OP_VARIABLE, &[31, 40, 0]));
+
+        assert_eq!(ShaderReflection::new(&words).unwrap_err(),
+                   ShaderReflectionError::MissingBinding { variable: 40 });
+    }
+
+    #[test]
+    fn wrong_endianness() {
+        let mut words = fragment_shader();
+        words[0] = SPIRV_MAGIC_NUMBER.swap_bytes();
+
+        assert_eq!(ShaderReflection::new(&words).unwrap_err(),
+                   ShaderReflectionError::WrongEndianness);
+    }
+
+    #[test]
+    fn build_layout() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let reflection = ShaderReflection::new(&fragment_shader()).unwrap();
+        let stages = reflection.entry_points()[0].stages();
+        reflection.layout_desc(stages).build(device).unwrap();
+    }
+}
@@ -13,13 +13,16 @@
 //! code and can contain one or more entry points. Note that for the moment the official
 //! GLSL-to-SPIR-V compiler does not support multiple entry points.
 //!
-//! The vulkano library does not provide any functionnality that checks and introspects the SPIR-V
-//! code, therefore the whole shader-related API is unsafe. You are encouraged to use the
-//! `vulkano-shaders` crate that will generate Rust code that wraps around vulkano's shaders API.
+//! The vulkano library only performs basic checks on the SPIR-V code (see
+//! `ShaderModule::from_words_checked`) and lists its entry points, but doesn't introspect the
+//! interfaces and the layouts of the shaders. Therefore the whole shader-related API is unsafe.
+//! You are encouraged to use the `vulkano-shaders` crate that will generate Rust code that wraps
+//! around vulkano's shaders API.
 
 use std::borrow::Cow;
 use std::error;
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt;
 use std::iter;
 use std::iter::Empty as EmptyIter;
@@ -27,10 +30,14 @@ use std::marker::PhantomData;
 use std::mem;
 use std::ops::Range;
 use std::ptr;
+use std::slice;
 use std::sync::Arc;
+use std::vec::IntoIter as VecIntoIter;
 
+use descriptor::descriptor::ShaderStages;
 use descriptor::pipeline_layout::EmptyPipelineDesc;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
+use features::Features;
 use format::Format;
 use pipeline::input_assembly::PrimitiveTopology;
 
@@ -38,6 +45,8 @@ use OomError;
 use VulkanObject;
 use check_errors;
 use device::Device;
+use device::DeviceExtensions;
+use device::DeviceOwned;
 use vk;
 
 /// Contains SPIR-V code with one or more entry points.
@@ -50,8 +59,18 @@ pub struct ShaderModule {
     module: vk::ShaderModule,
     // Pointer to the device.
     device: Arc<Device>,
+    // Entry points found in the SPIR-V code. Empty if the code couldn't be parsed.
+    entry_points: Vec<EntryPointInfo>,
+    // Values of the `OpCapability` instructions of the SPIR-V code. Empty if the code couldn't
+    // be parsed.
+    capabilities: Vec<u32>,
+    // False if the module was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
+/// Magic number found in the first word of SPIR-V code.
+pub const SPIRV_MAGIC_NUMBER: u32 = 0x07230203;
+
 impl ShaderModule {
     /// Builds a new shader module from SPIR-V bytes.
     ///
@@ -61,9 +80,40 @@ impl ShaderModule {
     /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
     ///   this function either.
     ///
-    pub unsafe fn new(device: Arc<Device>, spirv: &[u8]) -> Result<Arc<ShaderModule>, OomError> {
+    pub unsafe fn new(device: Arc<Device>, spirv: &[u8])
+                      -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
         debug_assert!((spirv.len() % 4) == 0);
-        Self::from_ptr(device, spirv.as_ptr() as *const _, spirv.len())
+
+        // The code can only be parsed if the bytes are correctly aligned.
+        let info = if spirv.as_ptr() as usize % mem::align_of::<u32>() == 0 {
+            let words = slice::from_raw_parts(spirv.as_ptr() as *const u32, spirv.len() / 4);
+            parse_spirv(words).unwrap_or(SpirvInfo::default())
+        } else {
+            SpirvInfo::default()
+        };
+
+        Self::from_ptr(device, spirv.as_ptr() as *const _, spirv.len(), info)
+    }
+
+    /// Builds a new shader module from SPIR-V bytes, after checking that the length is a multiple
+    /// of 4 and that the code is well-formed.
+    ///
+    /// The bytes don't need to be aligned. See `from_words_checked` for more information.
+    ///
+    /// # Safety
+    ///
+    /// Same as `from_words_checked`.
+    ///
+    pub unsafe fn new_checked(device: Arc<Device>, spirv: &[u8])
+                              -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        if spirv.len() % 4 != 0 {
+            return Err(ShaderModuleCreationError::UnalignedLength);
+        }
+
+        let mut words: Vec<u32> = Vec::with_capacity(spirv.len() / 4);
+        ptr::copy_nonoverlapping(spirv.as_ptr(), words.as_mut_ptr() as *mut u8, spirv.len());
+        words.set_len(spirv.len() / 4);
+        ShaderModule::from_words_checked(device, &words)
     }
 
     /// Builds a new shader module from SPIR-V 32-bit words.
@@ -75,8 +125,38 @@ impl ShaderModule {
     ///   this function either.
     ///
     pub unsafe fn from_words(device: Arc<Device>, spirv: &[u32])
-                             -> Result<Arc<ShaderModule>, OomError> {
-        Self::from_ptr(device, spirv.as_ptr(), spirv.len() * mem::size_of::<u32>())
+                             -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let info = parse_spirv(spirv).unwrap_or(SpirvInfo::default());
+        Self::from_ptr(device,
+                       spirv.as_ptr(),
+                       spirv.len() * mem::size_of::<u32>(),
+                       info)
+    }
+
+    /// Builds a new shader module from SPIR-V 32-bit words, after checking that the code is
+    /// well-formed.
+    ///
+    /// The checks are the presence of the SPIR-V header and of its magic number, and the
+    /// consistency of the lengths of the instructions. The code must contain at least one entry
+    /// point.
+    ///
+    /// # Safety
+    ///
+    /// - The semantics of the SPIR-V code are not validated.
+    /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
+    ///   this function either.
+    ///
+    pub unsafe fn from_words_checked(device: Arc<Device>, spirv: &[u32])
+                                     -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
+        let info = parse_spirv(spirv)?;
+        if info.entry_points.is_empty() {
+            return Err(ShaderModuleCreationError::NoEntryPoint);
+        }
+
+        Self::from_ptr(device,
+                       spirv.as_ptr(),
+                       spirv.len() * mem::size_of::<u32>(),
+                       info)
     }
 
     /// Builds a new shader module from SPIR-V.
@@ -87,8 +167,9 @@ impl ShaderModule {
     /// - The SPIR-V code may require some features that are not enabled. This isn't checked by
     ///   this function either.
     ///
-    unsafe fn from_ptr(device: Arc<Device>, spirv: *const u32, spirv_len: usize)
-                       -> Result<Arc<ShaderModule>, OomError> {
+    unsafe fn from_ptr(device: Arc<Device>, spirv: *const u32, spirv_len: usize,
+                       info: SpirvInfo)
+                       -> Result<Arc<ShaderModule>, ShaderModuleCreationError> {
         let module = {
             let infos = vk::ShaderModuleCreateInfo {
                 sType: vk::STRUCTURE_TYPE_SHADER_MODULE_CREATE_INFO,
@@ -110,9 +191,117 @@ impl ShaderModule {
         Ok(Arc::new(ShaderModule {
                         module: module,
                         device: device,
+                        entry_points: info.entry_points,
+                        capabilities: info.capabilities,
+                        needs_destruction: true,
                     }))
     }
 
+    /// Builds a `ShaderModule` from a raw handle that was created outside of vulkano.
+    ///
+    /// The SPIR-V code isn't available, so `entry_points` returns an empty list.
+    /// If `owned` is true, the module is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid shader module of `device`.
+    /// - If `owned` is true, the module must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::ShaderModule, owned: bool)
+                           -> Arc<ShaderModule> {
+        Arc::new(ShaderModule {
+                     module: handle,
+                     device: device,
+                     entry_points: Vec::new(),
+                     capabilities: Vec::new(),
+                     needs_destruction: owned,
+                 })
+    }
+
+    /// Returns the entry points declared in the SPIR-V code.
+    ///
+    /// Always empty if the module was built with `new` or `from_words` and the code couldn't be
+    /// parsed.
+    #[inline]
+    pub fn entry_points(&self) -> &[EntryPointInfo] {
+        &self.entry_points
+    }
+
+    /// Returns the entry point with the given name, if it is declared in the SPIR-V code.
+    #[inline]
+    pub fn entry_point_info(&self, name: &CStr) -> Option<&EntryPointInfo> {
+        self.entry_points
+            .iter()
+            .find(|e| e.name.as_c_str() == name)
+    }
+
+    /// Checks that the device features required by the SPIR-V capabilities declared in the code
+    /// are enabled in `features`.
+    ///
+    /// Capabilities that depend on an extension rather than on a feature are ignored. Always
+    /// succeeds if the module was built with `new` or `from_words` and the code couldn't be
+    /// parsed.
+    pub fn check_features(&self, features: &Features) -> Result<(), ShaderCapabilitiesError> {
+        for &capability in &self.capabilities {
+            let (capability_name, feature_name, enabled) =
+                match capability_feature(capability, features) {
+                    Some(c) => c,
+                    None => continue,
+                };
+
+            if !enabled {
+                return Err(ShaderCapabilitiesError::FeatureNotEnabled {
+                               capability: capability_name,
+                               feature: feature_name,
+                           });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the device extensions required by the SPIR-V capabilities declared in the code
+    /// are enabled in `extensions`.
+    ///
+    /// Only the capabilities related to ray tracing, mesh shading and transform feedback are
+    /// currently checked. Always succeeds if the module was built with `new` or `from_words` and
+    /// the code couldn't be parsed.
+    pub fn check_extensions(&self, extensions: &DeviceExtensions)
+                            -> Result<(), ShaderCapabilitiesError> {
+        for &capability in &self.capabilities {
+            let (capability_name, extension_name, enabled) =
+                match capability_extension(capability, extensions) {
+                    Some(c) => c,
+                    None => continue,
+                };
+
+            if !enabled {
+                return Err(ShaderCapabilitiesError::ExtensionNotEnabled {
+                               capability: capability_name,
+                               extension: extension_name,
+                           });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Checks that the entry point exists with the given execution model, if the entry points are
+    // known.
+    fn debug_check_entry_point(&self, name: &CStr, model: ExecutionModel) {
+        if self.entry_points.is_empty() {
+            return;
+        }
+
+        debug_assert!(self.entry_points
+                          .iter()
+                          .any(|e| e.name.as_c_str() == name && e.execution_model == model),
+                      "The shader module doesn't contain a {:?} entry point named {:?}",
+                      model,
+                      name);
+    }
+
     /// Gets access to an entry point contained in this module.
     ///
     /// This is purely a *logical* operation. It returns a struct that *represents* the entry
@@ -121,7 +310,7 @@ impl ShaderModule {
     /// # Safety
     ///
     /// - The user must check that the entry point exists in the module, as this is not checked
-    ///   by Vulkan.
+    ///   by Vulkan. If the entry points of the module are known, this is checked in debug mode.
     /// - The input, output and layout must correctly describe the input, output and layout used
     ///   by this stage.
     ///
@@ -129,6 +318,8 @@ impl ShaderModule {
                                                        output: O, layout: L,
                                                        ty: GraphicsShaderType)
                                                        -> GraphicsEntryPoint<'a, S, I, O, L> {
+        self.debug_check_entry_point(name, ty.execution_model());
+
         GraphicsEntryPoint {
             module: self,
             name: name,
@@ -148,12 +339,14 @@ impl ShaderModule {
     /// # Safety
     ///
     /// - The user must check that the entry point exists in the module, as this is not checked
-    ///   by Vulkan.
+    ///   by Vulkan. If the entry points of the module are known, this is checked in debug mode.
     /// - The layout must correctly describe the layout used by this stage.
     ///
     #[inline]
     pub unsafe fn compute_entry_point<'a, S, L>(&'a self, name: &'a CStr, layout: L)
                                                 -> ComputeEntryPoint<'a, S, L> {
+        self.debug_check_entry_point(name, ExecutionModel::GLCompute);
+
         ComputeEntryPoint {
             module: self,
             name: name,
@@ -161,6 +354,357 @@ impl ShaderModule {
             marker: PhantomData,
         }
     }
+
+    /// Gets access to a ray tracing entry point contained in this module.
+    ///
+    /// This is purely a *logical* operation. It returns a struct that *represents* the entry
+    /// point but doesn't actually do anything.
+    ///
+    /// # Safety
+    ///
+    /// - The user must check that the entry point exists in the module, as this is not checked
+    ///   by Vulkan. If the entry points of the module are known, this is checked in debug mode.
+    /// - The layout must correctly describe the layout used by this stage.
+    ///
+    #[inline]
+    pub unsafe fn ray_tracing_entry_point<'a, S, L>(&'a self, name: &'a CStr, layout: L,
+                                                    ty: RayTracingShaderType)
+                                                    -> RayTracingEntryPoint<'a, S, L> {
+        self.debug_check_entry_point(name, ty.execution_model());
+
+        RayTracingEntryPoint {
+            module: self,
+            name: name,
+            layout: layout,
+            ty: ty,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Description of an entry point declared in SPIR-V code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPointInfo {
+    /// Name of the entry point.
+    pub name: CString,
+    /// Stage the entry point is written for.
+    pub execution_model: ExecutionModel,
+}
+
+/// Stage an entry point is written for, as declared in the SPIR-V code.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ExecutionModel {
+    Vertex = 0,
+    TessellationControl = 1,
+    TessellationEvaluation = 2,
+    Geometry = 3,
+    Fragment = 4,
+    GLCompute = 5,
+    Kernel = 6,
+    RayGeneration = 5313,
+    Intersection = 5314,
+    AnyHit = 5315,
+    ClosestHit = 5316,
+    Miss = 5317,
+    Callable = 5318,
+    Task = 5364,
+    Mesh = 5365,
+}
+
+impl ExecutionModel {
+    #[inline]
+    pub(crate) fn from_num(num: u32) -> Option<ExecutionModel> {
+        match num {
+            0 => Some(ExecutionModel::Vertex),
+            1 => Some(ExecutionModel::TessellationControl),
+            2 => Some(ExecutionModel::TessellationEvaluation),
+            3 => Some(ExecutionModel::Geometry),
+            4 => Some(ExecutionModel::Fragment),
+            5 => Some(ExecutionModel::GLCompute),
+            6 => Some(ExecutionModel::Kernel),
+            5313 => Some(ExecutionModel::RayGeneration),
+            5314 => Some(ExecutionModel::Intersection),
+            5315 => Some(ExecutionModel::AnyHit),
+            5316 => Some(ExecutionModel::ClosestHit),
+            5317 => Some(ExecutionModel::Miss),
+            5318 => Some(ExecutionModel::Callable),
+            5364 => Some(ExecutionModel::Task),
+            5365 => Some(ExecutionModel::Mesh),
+            _ => None,
+        }
+    }
+}
+
+// Returns the name of a SPIR-V capability, the name of the device feature that it requires, and
+// whether this feature is enabled in `features`. Returns `None` if the capability doesn't require
+// any feature.
+fn capability_feature(capability: u32, features: &Features)
+                      -> Option<(&'static str, &'static str, bool)> {
+    macro_rules! feature {
+        ($capability:expr, $feature:ident) => {
+            Some(($capability, stringify!($feature), features.$feature))
+        };
+    }
+
+    match capability {
+        2 => feature!("Geometry", geometry_shader),
+        3 => feature!("Tessellation", tessellation_shader),
+        10 => feature!("Float64", shader_f3264),
+        11 => feature!("Int64", shader_int64),
+        22 => feature!("Int16", shader_int16),
+        23 => feature!("TessellationPointSize", shader_tessellation_and_geometry_point_size),
+        24 => feature!("GeometryPointSize", shader_tessellation_and_geometry_point_size),
+        25 => feature!("ImageGatherExtended", shader_image_gather_extended),
+        27 => feature!("StorageImageMultisample", shader_storage_image_multisample),
+        28 => feature!("UniformBufferArrayDynamicIndexing",
+                       shader_uniform_buffer_array_dynamic_indexing),
+        29 => feature!("SampledImageArrayDynamicIndexing",
+                       shader_sampled_image_array_dynamic_indexing),
+        30 => feature!("StorageBufferArrayDynamicIndexing",
+                       shader_storage_buffer_array_dynamic_indexing),
+        31 => feature!("StorageImageArrayDynamicIndexing",
+                       shader_storage_image_array_dynamic_indexing),
+        32 => feature!("ClipDistance", shader_clip_distance),
+        33 => feature!("CullDistance", shader_cull_distance),
+        34 => feature!("ImageCubeArray", image_cube_array),
+        35 => feature!("SampleRateShading", sample_rate_shading),
+        41 => feature!("SparseResidency", shader_resource_residency),
+        42 => feature!("MinLod", shader_resource_min_lod),
+        45 => feature!("SampledCubeArray", image_cube_array),
+        48 => feature!("ImageMSArray", shader_storage_image_multisample),
+        49 => feature!("StorageImageExtendedFormats", shader_storage_image_extended_formats),
+        52 => feature!("InterpolationFunction", sample_rate_shading),
+        55 => feature!("StorageImageReadWithoutFormat", shader_storage_image_read_without_format),
+        56 => feature!("StorageImageWriteWithoutFormat",
+                       shader_storage_image_write_without_format),
+        57 => feature!("MultiViewport", multi_viewport),
+        _ => None,
+    }
+}
+
+// Same as `capability_feature`, but for the capabilities that require a device extension.
+fn capability_extension(capability: u32, extensions: &DeviceExtensions)
+                        -> Option<(&'static str, &'static str, bool)> {
+    macro_rules! extension {
+        ($capability:expr, $extension:ident) => {
+            Some(($capability, stringify!($extension), extensions.$extension))
+        };
+    }
+
+    match capability {
+        4472 => extension!("RayQueryKHR", khr_ray_query),
+        4479 => extension!("RayTracingKHR", khr_ray_tracing_pipeline),
+        4992 => extension!("TransformFeedback", ext_transform_feedback),
+        5283 => extension!("MeshShadingEXT", ext_mesh_shader),
+        5353 => extension!("RayTraversalPrimitiveCullingKHR", khr_ray_tracing_pipeline),
+        _ => None,
+    }
+}
+
+// Information extracted from SPIR-V code.
+#[derive(Debug, Clone, Default)]
+struct SpirvInfo {
+    entry_points: Vec<EntryPointInfo>,
+    capabilities: Vec<u32>,
+}
+
+// Checks the structure of the SPIR-V code and returns the list of entry points and capabilities.
+fn parse_spirv(spirv: &[u32]) -> Result<SpirvInfo, ShaderModuleCreationError> {
+    // Opcode of `OpCapability`.
+    const OP_CAPABILITY: u32 = 17;
+    // Opcode of `OpEntryPoint`.
+    const OP_ENTRY_POINT: u32 = 15;
+
+    if spirv.len() < 5 {
+        return Err(ShaderModuleCreationError::MissingHeader);
+    }
+
+    if spirv[0] != SPIRV_MAGIC_NUMBER {
+        if spirv[0] == SPIRV_MAGIC_NUMBER.swap_bytes() {
+            return Err(ShaderModuleCreationError::WrongEndianness);
+        }
+        return Err(ShaderModuleCreationError::WrongMagicNumber);
+    }
+
+    let mut entry_points = Vec::new();
+    let mut capabilities = Vec::new();
+    let mut instructions = &spirv[5 ..];
+
+    while !instructions.is_empty() {
+        let word_count = (instructions[0] >> 16) as usize;
+        let opcode = instructions[0] & 0xffff;
+
+        if word_count == 0 || word_count > instructions.len() {
+            return Err(ShaderModuleCreationError::InvalidInstruction);
+        }
+
+        if opcode == OP_CAPABILITY {
+            if word_count != 2 {
+                return Err(ShaderModuleCreationError::InvalidInstruction);
+            }
+
+            capabilities.push(instructions[1]);
+        }
+
+        if opcode == OP_ENTRY_POINT {
+            // Words are: opcode, execution model, function id, name, interface ids.
+            if word_count < 4 {
+                return Err(ShaderModuleCreationError::InvalidInstruction);
+            }
+
+            let execution_model = match ExecutionModel::from_num(instructions[1]) {
+                Some(m) => m,
+                None => return Err(ShaderModuleCreationError::InvalidInstruction),
+            };
+
+            // Literal strings are nul-terminated and packed in little-endian order.
+            let mut name = Vec::new();
+            'words: for &word in &instructions[3 .. word_count] {
+                for n in 0 .. 4 {
+                    let byte = ((word >> (n * 8)) & 0xff) as u8;
+                    if byte == 0 {
+                        break 'words;
+                    }
+                    name.push(byte);
+                }
+            }
+
+            let name = match CString::new(name) {
+                Ok(n) => n,
+                Err(_) => return Err(ShaderModuleCreationError::InvalidInstruction),
+            };
+
+            entry_points.push(EntryPointInfo {
+                                  name: name,
+                                  execution_model: execution_model,
+                              });
+        }
+
+        instructions = &instructions[word_count ..];
+    }
+
+    Ok(SpirvInfo {
+           entry_points: entry_points,
+           capabilities: capabilities,
+       })
+}
+
+/// Error that can happen when creating a shader module.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderModuleCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The length in bytes of the code is not a multiple of 4.
+    UnalignedLength,
+    /// The code is too short to contain the SPIR-V header.
+    MissingHeader,
+    /// The first word of the code is not the SPIR-V magic number.
+    WrongMagicNumber,
+    /// The code is SPIR-V but has the wrong endianness.
+    WrongEndianness,
+    /// The length of an instruction is invalid, or an `OpEntryPoint` is malformed.
+    InvalidInstruction,
+    /// The code doesn't declare any entry point.
+    NoEntryPoint,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for ShaderModuleCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderModuleCreationError::OomError(_) => "not enough memory available",
+            ShaderModuleCreationError::UnalignedLength => {
+                "the length in bytes of the code is not a multiple of 4"
+            },
+            ShaderModuleCreationError::MissingHeader => {
+                "the code is too short to contain the SPIR-V header"
+            },
+            ShaderModuleCreationError::WrongMagicNumber => {
+                "the first word of the code is not the SPIR-V magic number"
+            },
+            ShaderModuleCreationError::WrongEndianness => {
+                "the code is SPIR-V but has the wrong endianness"
+            },
+            ShaderModuleCreationError::InvalidInstruction => {
+                "the length of an instruction is invalid, or an `OpEntryPoint` is malformed"
+            },
+            ShaderModuleCreationError::NoEntryPoint => "the code doesn't declare any entry point",
+            ShaderModuleCreationError::UnexpectedResult(_) => {
+                "the implementation returned an unexpected error code"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ShaderModuleCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(ShaderModuleCreationError, OomError {});
+
+/// Error that can happen when a shader module requires device features that are not enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderCapabilitiesError {
+    /// The code declares a SPIR-V capability whose corresponding feature is not enabled on the
+    /// device.
+    FeatureNotEnabled {
+        /// Name of the SPIR-V capability, for example `Float64`.
+        capability: &'static str,
+        /// Name of the field of `Features` that must be enabled, for example `shader_f3264`.
+        feature: &'static str,
+    },
+    /// The code declares a SPIR-V capability whose corresponding extension is not enabled on the
+    /// device.
+    ExtensionNotEnabled {
+        /// Name of the SPIR-V capability, for example `RayQueryKHR`.
+        capability: &'static str,
+        /// Name of the field of `DeviceExtensions` that must be enabled, for example
+        /// `khr_ray_query`.
+        extension: &'static str,
+    },
+}
+
+impl error::Error for ShaderCapabilitiesError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderCapabilitiesError::FeatureNotEnabled { .. } => {
+                "the shader declares a capability whose corresponding feature is not enabled"
+            },
+            ShaderCapabilitiesError::ExtensionNotEnabled { .. } => {
+                "the shader declares a capability whose corresponding extension is not enabled"
+            },
+        }
+    }
+}
+
+impl fmt::Display for ShaderCapabilitiesError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ShaderCapabilitiesError::FeatureNotEnabled { capability, feature } => {
+                write!(fmt,
+                       "the shader declares the `{}` capability, which requires the `{}` \
+                        feature to be enabled",
+                       capability,
+                       feature)
+            },
+            ShaderCapabilitiesError::ExtensionNotEnabled { capability, extension } => {
+                write!(fmt,
+                       "the shader declares the `{}` capability, which requires the `{}` \
+                        extension to be enabled",
+                       capability,
+                       extension)
+            },
+        }
+    }
 }
 
 unsafe impl VulkanObject for ShaderModule {
@@ -174,9 +718,20 @@ unsafe impl VulkanObject for ShaderModule {
     }
 }
 
+unsafe impl DeviceOwned for ShaderModule {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
 impl Drop for ShaderModule {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyShaderModule(self.device.internal_object(), self.module, ptr::null());
@@ -269,6 +824,26 @@ pub enum GraphicsShaderType {
     TessellationEvaluation,
     Geometry(GeometryShaderExecutionMode),
     Fragment,
+    /// Task shader. Requires the `ext_mesh_shader` extension.
+    Task,
+    /// Mesh shader. Requires the `ext_mesh_shader` extension.
+    Mesh,
+}
+
+impl GraphicsShaderType {
+    /// Returns the SPIR-V execution model that corresponds to this type of shader.
+    #[inline]
+    pub fn execution_model(&self) -> ExecutionModel {
+        match *self {
+            GraphicsShaderType::Vertex => ExecutionModel::Vertex,
+            GraphicsShaderType::TessellationControl => ExecutionModel::TessellationControl,
+            GraphicsShaderType::TessellationEvaluation => ExecutionModel::TessellationEvaluation,
+            GraphicsShaderType::Geometry(_) => ExecutionModel::Geometry,
+            GraphicsShaderType::Fragment => ExecutionModel::Fragment,
+            GraphicsShaderType::Task => ExecutionModel::Task,
+            GraphicsShaderType::Mesh => ExecutionModel::Mesh,
+        }
+    }
 }
 
 /// Declares which type of primitives are expected by the geometry shader.
@@ -353,6 +928,108 @@ unsafe impl<'a, S, L> EntryPointAbstract for ComputeEntryPoint<'a, S, L>
     }
 }
 
+pub unsafe trait RayTracingEntryPointAbstract: EntryPointAbstract {
+    /// Returns the type of shader.
+    fn ty(&self) -> RayTracingShaderType;
+}
+
+/// Represents the entry point of a ray tracing shader in a shader module.
+///
+/// Can be obtained by calling `ray_tracing_entry_point()` on the shader module.
+#[derive(Debug, Copy, Clone)]
+pub struct RayTracingEntryPoint<'a, S, L> {
+    module: &'a ShaderModule,
+    name: &'a CStr,
+    layout: L,
+    ty: RayTracingShaderType,
+    marker: PhantomData<S>,
+}
+
+unsafe impl<'a, S, L> EntryPointAbstract for RayTracingEntryPoint<'a, S, L>
+    where L: PipelineLayoutDesc,
+          S: SpecializationConstants
+{
+    type PipelineLayout = L;
+    type SpecializationConstants = S;
+
+    #[inline]
+    fn module(&self) -> &ShaderModule {
+        self.module
+    }
+
+    #[inline]
+    fn name(&self) -> &CStr {
+        self.name
+    }
+
+    #[inline]
+    fn layout(&self) -> &L {
+        &self.layout
+    }
+}
+
+unsafe impl<'a, S, L> RayTracingEntryPointAbstract for RayTracingEntryPoint<'a, S, L>
+    where L: PipelineLayoutDesc,
+          S: SpecializationConstants
+{
+    #[inline]
+    fn ty(&self) -> RayTracingShaderType {
+        self.ty
+    }
+}
+
+/// Stage of a ray tracing pipeline.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RayTracingShaderType {
+    RayGeneration,
+    AnyHit,
+    ClosestHit,
+    Miss,
+    Intersection,
+    Callable,
+}
+
+impl RayTracingShaderType {
+    /// Returns the SPIR-V execution model that corresponds to this type of shader.
+    #[inline]
+    pub fn execution_model(&self) -> ExecutionModel {
+        match *self {
+            RayTracingShaderType::RayGeneration => ExecutionModel::RayGeneration,
+            RayTracingShaderType::AnyHit => ExecutionModel::AnyHit,
+            RayTracingShaderType::ClosestHit => ExecutionModel::ClosestHit,
+            RayTracingShaderType::Miss => ExecutionModel::Miss,
+            RayTracingShaderType::Intersection => ExecutionModel::Intersection,
+            RayTracingShaderType::Callable => ExecutionModel::Callable,
+        }
+    }
+
+    /// Returns the shader stage that corresponds to this type of shader.
+    #[inline]
+    pub fn stages(&self) -> ShaderStages {
+        match *self {
+            RayTracingShaderType::RayGeneration => {
+                ShaderStages { raygen: true, ..ShaderStages::none() }
+            },
+            RayTracingShaderType::AnyHit => ShaderStages { any_hit: true, ..ShaderStages::none() },
+            RayTracingShaderType::ClosestHit => {
+                ShaderStages { closest_hit: true, ..ShaderStages::none() }
+            },
+            RayTracingShaderType::Miss => ShaderStages { miss: true, ..ShaderStages::none() },
+            RayTracingShaderType::Intersection => {
+                ShaderStages { intersection: true, ..ShaderStages::none() }
+            },
+            RayTracingShaderType::Callable => {
+                ShaderStages { callable: true, ..ShaderStages::none() }
+            },
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::ShaderStageFlags {
+        self.stages().into_vulkan_bits()
+    }
+}
+
 /// A dummy that implements `GraphicsEntryPointAbstract` and `EntryPointAbstract`.
 ///
 /// When a function has a signature like: `fn foo<S: EntryPointAbstract>(shader: Option<S>)`, you
@@ -444,12 +1121,41 @@ unsafe impl ShaderInterfaceDef for EmptyShaderInterfaceDef {
     }
 }
 
+/// Description of a shader interface whose elements are only known at runtime, for example
+/// because they have been obtained by reflection over SPIR-V code.
+#[derive(Debug, Clone)]
+pub struct RuntimeShaderInterfaceDef {
+    elements: Vec<ShaderInterfaceDefEntry>,
+}
+
+impl RuntimeShaderInterfaceDef {
+    /// Builds a new `RuntimeShaderInterfaceDef` from a list of elements.
+    #[inline]
+    pub fn new<I>(elements: I) -> RuntimeShaderInterfaceDef
+        where I: IntoIterator<Item = ShaderInterfaceDefEntry>
+    {
+        RuntimeShaderInterfaceDef { elements: elements.into_iter().collect() }
+    }
+}
+
+unsafe impl ShaderInterfaceDef for RuntimeShaderInterfaceDef {
+    type Iter = VecIntoIter<ShaderInterfaceDefEntry>;
+
+    #[inline]
+    fn elements(&self) -> Self::Iter {
+        self.elements.clone().into_iter()
+    }
+}
+
 /// Extension trait for `ShaderInterfaceDef` that specifies that the interface is potentially
 /// compatible with another one.
 pub unsafe trait ShaderInterfaceDefMatch<I>: ShaderInterfaceDef
     where I: ShaderInterfaceDef
 {
     /// Returns `Ok` if the two definitions match.
+    ///
+    /// `self` is the input interface of a shader stage, and `other` is the output interface of
+    /// the previous stage.
     fn matches(&self, other: &I) -> Result<(), ShaderInterfaceMismatchError>;
 }
 
@@ -459,13 +1165,8 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
           I: ShaderInterfaceDef
 {
     fn matches(&self, other: &I) -> Result<(), ShaderInterfaceMismatchError> {
-        if self.elements().len() != other.elements().len() {
-            return Err(ShaderInterfaceMismatchError::ElementsCountMismatch {
-                self_elements: self.elements().len() as u32,
-                other_elements: other.elements().len() as u32,
-            });
-        }
-
+        // Outputs of the previous stage that aren't consumed by this stage are allowed, so we
+        // only need to iterate over the inputs.
         for a in self.elements() {
             for loc in a.location.clone() {
                 let b = match other
@@ -477,7 +1178,21 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
                     Some(b) => b,
                 };
 
-                if a.format != b.format {
+                // The output is allowed to have more components than the input, as long as the
+                // components have the same numeric type and width.
+                let compatible = a.format == b.format || {
+                    match (a.format.components(), b.format.components(),
+                           a.format.size(), b.format.size())
+                    {
+                        (Some(a_cmp), Some(b_cmp), Some(a_sz), Some(b_sz)) => {
+                            a.format.ty() == b.format.ty() &&
+                                a_sz / a_cmp as usize == b_sz / b_cmp as usize &&
+                                a_cmp <= b_cmp
+                        },
+                        _ => false,
+                    }
+                };
+                if !compatible {
                     return Err(ShaderInterfaceMismatchError::FormatMismatch {
                         location: loc,
                         self_format: a.format,
@@ -493,9 +1208,6 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
             }
         }
 
-        // Note: since we check that the number of elements is the same, we don't need to iterate
-        // over b's elements.
-
         Ok(())
     }
 }
@@ -504,6 +1216,9 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ShaderInterfaceMismatchError {
     /// The number of elements is not the same between the two shader interfaces.
+    ///
+    /// Never returned by the default implementation of `ShaderInterfaceDefMatch`, as the previous
+    /// stage is allowed to have more outputs than the next stage has inputs.
     ElementsCountMismatch {
         /// Number of elements in the first interface.
         self_elements: u32,
@@ -511,7 +1226,7 @@ pub enum ShaderInterfaceMismatchError {
         other_elements: u32,
     },
 
-    /// An element is missing from one of the interfaces.
+    /// An input of the first interface has no corresponding output in the second interface.
     MissingElement {
         /// Location of the missing element.
         location: u32,
@@ -548,7 +1263,33 @@ impl error::Error for ShaderInterfaceMismatchError {
 impl fmt::Display for ShaderInterfaceMismatchError {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
+        match *self {
+            ShaderInterfaceMismatchError::ElementsCountMismatch {
+                self_elements,
+                other_elements,
+            } => {
+                write!(fmt,
+                       "{} ({} and {})",
+                       error::Error::description(self),
+                       self_elements,
+                       other_elements)
+            },
+            ShaderInterfaceMismatchError::MissingElement { location } => {
+                write!(fmt, "{} (location {})", error::Error::description(self), location)
+            },
+            ShaderInterfaceMismatchError::FormatMismatch {
+                location,
+                self_format,
+                other_format,
+            } => {
+                write!(fmt,
+                       "{} (location {}, input {:?}, output {:?})",
+                       error::Error::description(self),
+                       location,
+                       self_format,
+                       other_format)
+            },
+        }
     }
 }
 
@@ -646,3 +1387,137 @@ pub struct SpecializationMapEntry {
     /// Size of the data in bytes. Must match the size of the constant (`4` for booleans).
     pub size: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Header followed by `OpEntryPoint GLCompute %1 "main"`.
+    const COMPUTE_MAIN: [u32; 9] = [SPIRV_MAGIC_NUMBER, 0x00010000, 0, 2, 0,
+                                    (4 << 16) | 15, 5, 1, 0x6e69616d];
+
+    #[test]
+    fn entry_points() {
+        let mut words = COMPUTE_MAIN.to_vec();
+        words.push(0); // nul terminator of "main"
+        words[5] = (5 << 16) | 15;
+
+        let entry_points = parse_spirv(&words).map(|s| s.entry_points).unwrap();
+        assert_eq!(entry_points.len(), 1);
+        assert_eq!(entry_points[0].name.to_bytes(), b"main");
+        assert_eq!(entry_points[0].execution_model, ExecutionModel::GLCompute);
+    }
+
+    #[test]
+    fn wrong_magic_number() {
+        let mut words = COMPUTE_MAIN.to_vec();
+        words[0] = 0x12345678;
+        assert_eq!(parse_spirv(&words).map(|s| s.entry_points),
+                   Err(ShaderModuleCreationError::WrongMagicNumber));
+
+        words[0] = SPIRV_MAGIC_NUMBER.swap_bytes();
+        assert_eq!(parse_spirv(&words).map(|s| s.entry_points),
+                   Err(ShaderModuleCreationError::WrongEndianness));
+    }
+
+    #[test]
+    fn invalid_instruction_length() {
+        let mut words = COMPUTE_MAIN.to_vec();
+        words[5] = (12 << 16) | 15;
+        assert_eq!(parse_spirv(&words).map(|s| s.entry_points),
+                   Err(ShaderModuleCreationError::InvalidInstruction));
+    }
+
+    #[test]
+    fn capabilities() {
+        let mut words = COMPUTE_MAIN[.. 5].to_vec();
+        words.extend_from_slice(&[(2 << 16) | 17, 1, (2 << 16) | 17, 10]);
+
+        let info = parse_spirv(&words).unwrap();
+        assert_eq!(info.capabilities, vec![1, 10]);
+
+        let mut features = Features::none();
+        assert_eq!(capability_feature(1, &features), None);
+        assert_eq!(capability_feature(10, &features), Some(("Float64", "shader_f3264", false)));
+        features.shader_f3264 = true;
+        assert_eq!(capability_feature(10, &features), Some(("Float64", "shader_f3264", true)));
+
+        let mut extensions = DeviceExtensions::none();
+        assert_eq!(capability_extension(10, &extensions), None);
+        assert_eq!(capability_extension(4472, &extensions),
+                   Some(("RayQueryKHR", "khr_ray_query", false)));
+        extensions.khr_ray_query = true;
+        assert_eq!(capability_extension(4472, &extensions),
+                   Some(("RayQueryKHR", "khr_ray_query", true)));
+    }
+
+    #[test]
+    fn unaligned_length() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match unsafe { ShaderModule::new_checked(device, &[0x03, 0x02, 0x23]) } {
+            Err(ShaderModuleCreationError::UnalignedLength) => (),
+            _ => panic!(),
+        }
+    }
+
+    fn interface(elements: &[(u32, Format)]) -> RuntimeShaderInterfaceDef {
+        RuntimeShaderInterfaceDef::new(elements.iter().map(|&(location, format)| {
+            ShaderInterfaceDefEntry {
+                location: location .. location + 1,
+                format: format,
+                name: None,
+            }
+        }))
+    }
+
+    #[test]
+    fn interface_unused_outputs() {
+        let input = interface(&[(0, Format::R32G32Sfloat)]);
+        let output = interface(&[(0, Format::R32G32B32A32Sfloat), (1, Format::R32Uint)]);
+        assert_eq!(input.matches(&output), Ok(()));
+    }
+
+    #[test]
+    fn interface_missing_output() {
+        let input = interface(&[(0, Format::R32Sfloat), (2, Format::R32Sfloat)]);
+        let output = interface(&[(0, Format::R32Sfloat)]);
+        assert_eq!(input.matches(&output),
+                   Err(ShaderInterfaceMismatchError::MissingElement { location: 2 }));
+    }
+
+    #[test]
+    fn invalid_shader_result_code() {
+        let err = check_errors(vk::ERROR_INVALID_SHADER_NV).unwrap_err();
+        assert_eq!(ShaderModuleCreationError::from(err),
+                   ShaderModuleCreationError::UnexpectedResult(vk::ERROR_INVALID_SHADER_NV));
+    }
+
+    #[test]
+    fn interface_component_width_mismatch() {
+        let input = interface(&[(0, Format::R32G32B32A32Sfloat)]);
+        let output = interface(&[(0, Format::R64G64Sfloat)]);
+        assert_eq!(input.matches(&output),
+                   Err(ShaderInterfaceMismatchError::FormatMismatch {
+                           location: 0,
+                           self_format: Format::R32G32B32A32Sfloat,
+                           other_format: Format::R64G64Sfloat,
+                       }));
+
+        let input = interface(&[(0, Format::R32G32Sfloat)]);
+        let output = interface(&[(0, Format::R64Sfloat)]);
+        assert!(input.matches(&output).is_err());
+    }
+
+    #[test]
+    fn interface_type_mismatch() {
+        let input = interface(&[(0, Format::R32G32Sint)]);
+        let output = interface(&[(0, Format::R32G32Sfloat)]);
+        assert_eq!(input.matches(&output),
+                   Err(ShaderInterfaceMismatchError::FormatMismatch {
+                           location: 0,
+                           self_format: Format::R32G32Sint,
+                           other_format: Format::R32G32Sfloat,
+                       }));
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::iter;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use buffer::BufferUsage;
+use buffer::CpuAccessibleBuffer;
+use memory::DeviceMemoryAllocError;
+use pipeline::RayTracingPipeline;
+use pipeline::ray_tracing_pipeline::ShaderGroup;
+
+use vk;
+
+/// Buffer containing the handles of the shader groups of a ray tracing pipeline, arranged in the
+/// way `trace_rays` expects.
+///
+/// The table contains four regions: a region with the ray generation group, and regions with the
+/// miss, hit and callable groups. When a ray misses or hits some geometry, the index of the group
+/// that is invoked is computed from the parameters of `traceRayEXT` and, for hits, from the
+/// shader binding table offset of the instance that was hit.
+///
+/// The table is only valid for the pipeline it was created from.
+pub struct ShaderBindingTable {
+    buffer: Arc<CpuAccessibleBuffer<[u8]>>,
+    // Raygen, miss, hit and callable regions, in this order.
+    regions: [vk::StridedDeviceAddressRegionKHR; 4],
+}
+
+impl ShaderBindingTable {
+    /// Builds a shader binding table for `pipeline`.
+    ///
+    /// `raygen` is the index of the group to use for generating rays. `miss`, `hit` and
+    /// `callable` are lists of group indices that are stored in this order in their region.
+    pub fn new<Pl>(pipeline: &RayTracingPipeline<Pl>, raygen: u32, miss: &[u32], hit: &[u32],
+                   callable: &[u32])
+                   -> Result<ShaderBindingTable, ShaderBindingTableCreationError> {
+        // Checks that the type of each group matches the region it is stored in.
+        let is_general = |group: u32| match pipeline.group(group) {
+            Some(&ShaderGroup::General(_)) => true,
+            _ => false,
+        };
+        let is_hit = |group: u32| match pipeline.group(group) {
+            Some(&ShaderGroup::TrianglesHit { .. }) |
+            Some(&ShaderGroup::ProceduralHit { .. }) => true,
+            _ => false,
+        };
+
+        for &group in iter::once(&raygen).chain(miss.iter()).chain(callable.iter()) {
+            if !is_general(group) {
+                return Err(ShaderBindingTableCreationError::InvalidGroup { group: group });
+            }
+        }
+        for &group in hit.iter() {
+            if !is_hit(group) {
+                return Err(ShaderBindingTableCreationError::InvalidGroup { group: group });
+            }
+        }
+
+        let properties = pipeline.properties();
+        let handle_size = properties.shader_group_handle_size as u64;
+        let base_alignment = properties.shader_group_base_alignment as u64;
+        let stride = align(handle_size, properties.shader_group_handle_alignment as u64);
+
+        // Offsets of the regions relative to the first one, which is aligned later.
+        let lists: [&[u32]; 4] = [&[raygen], miss, hit, callable];
+        let mut offsets = [0; 4];
+        let mut total_size = 0;
+        for (offset, list) in offsets.iter_mut().zip(lists.iter()) {
+            *offset = total_size;
+            total_size = align(total_size + list.len() as u64 * stride, base_alignment);
+        }
+
+        // The buffer isn't necessarily aligned to `shader_group_base_alignment`, so we allocate
+        // enough room to skip the start of the buffer.
+        let usage = BufferUsage {
+            shader_binding_table: true,
+            shader_device_address: true,
+            ..BufferUsage::none()
+        };
+        let len = (total_size + base_alignment) as usize;
+        let buffer = CpuAccessibleBuffer::from_iter(pipeline.device().clone(),
+                                                    usage,
+                                                    (0 .. len).map(|_| 0u8))?;
+
+        let buffer_address = {
+            let inner = buffer.inner();
+            inner.buffer.device_address() + inner.offset as u64
+        };
+        let base_address = align(buffer_address, base_alignment);
+        let base_offset = base_address - buffer_address;
+
+        {
+            let mut content = buffer.write().unwrap();
+            for (offset, list) in offsets.iter().zip(lists.iter()) {
+                for (num, &group) in list.iter().enumerate() {
+                    let handle = pipeline.group_handle(group).unwrap();
+                    let start = (base_offset + offset + num as u64 * stride) as usize;
+                    content[start .. start + handle.len()].copy_from_slice(handle);
+                }
+            }
+        }
+
+        let region = |num: usize| {
+            let count = lists[num].len() as u64;
+            vk::StridedDeviceAddressRegionKHR {
+                deviceAddress: if count == 0 {
+                    0
+                } else {
+                    base_address + offsets[num]
+                },
+                stride: if count == 0 { 0 } else { stride },
+                size: count * stride,
+            }
+        };
+
+        Ok(ShaderBindingTable {
+               buffer: buffer,
+               regions: [region(0), region(1), region(2), region(3)],
+           })
+    }
+
+    /// Returns the buffer that contains the table.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<CpuAccessibleBuffer<[u8]>> {
+        &self.buffer
+    }
+
+    #[inline]
+    pub(crate) fn raw_regions(&self) -> &[vk::StridedDeviceAddressRegionKHR; 4] {
+        &self.regions
+    }
+}
+
+impl fmt::Debug for ShaderBindingTable {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt,
+               "<Shader binding table with {} miss, {} hit and {} callable entries>",
+               self.regions[1].size / self.regions[1].stride.max(1),
+               self.regions[2].size / self.regions[2].stride.max(1),
+               self.regions[3].size / self.regions[3].stride.max(1))
+    }
+}
+
+// Rounds `value` up to a multiple of `alignment`.
+#[inline]
+fn align(value: u64, alignment: u64) -> u64 {
+    if alignment == 0 {
+        return value;
+    }
+
+    (value + alignment - 1) / alignment * alignment
+}
+
+/// Error that can happen when creating a shader binding table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderBindingTableCreationError {
+    /// Error while allocating the buffer of the table.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+    /// A group doesn't exist in the pipeline, or can't be stored in the region it was passed for.
+    InvalidGroup {
+        /// Index of the group.
+        group: u32,
+    },
+}
+
+impl error::Error for ShaderBindingTableCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderBindingTableCreationError::DeviceMemoryAllocError(_) => {
+                "error while allocating the buffer of the table"
+            },
+            ShaderBindingTableCreationError::InvalidGroup { .. } => {
+                "a group doesn't exist or can't be stored in the region it was passed for"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ShaderBindingTableCreationError::DeviceMemoryAllocError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ShaderBindingTableCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<DeviceMemoryAllocError> for ShaderBindingTableCreationError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> ShaderBindingTableCreationError {
+        ShaderBindingTableCreationError::DeviceMemoryAllocError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::align;
+
+    #[test]
+    fn align_values() {
+        assert_eq!(align(0, 64), 0);
+        assert_eq!(align(1, 64), 64);
+        assert_eq!(align(64, 64), 64);
+        assert_eq!(align(65, 32), 96);
+        assert_eq!(align(12, 0), 12);
+    }
+}
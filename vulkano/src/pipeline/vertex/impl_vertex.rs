@@ -10,7 +10,30 @@
 use pipeline::vertex::VertexMemberTy;
 
 /// Implements the `Vertex` trait on a struct.
-// TODO: add example
+///
+/// The first parameter is the struct, and the following ones are the names of the members that
+/// can be read by the vertex shader. The name of each member must match the name of an input of
+/// the vertex shader. The struct should be `#[repr(C)]` so that the offsets of its members are
+/// stable.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate vulkano;
+/// # fn main() {
+/// #[derive(Debug, Copy, Clone)]
+/// #[repr(C)]
+/// struct Vertex {
+///     position: [f32; 3],
+///     color: [f32; 4],
+/// }
+///
+/// impl_vertex!(Vertex, position, color);
+/// # }
+/// ```
+///
+/// Per-instance data is described the same way. Use `OneVertexOneInstanceDefinition` or
+/// `TwoBuffersDefinition` to combine several structs in a pipeline.
 #[macro_export]
 macro_rules! impl_vertex {
     ($out:ty $(, $member:ident)*) => (
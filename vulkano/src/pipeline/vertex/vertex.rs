@@ -8,6 +8,7 @@
 // according to those terms.
 
 use format::Format;
+use format::FormatTy;
 
 /// Describes an individual `Vertex`. In other words a collection of attributes that can be read
 /// from a vertex shader.
@@ -52,9 +53,12 @@ pub enum VertexMemberTy {
 
 impl VertexMemberTy {
     /// Returns true if a combination of `(type, array_size)` matches a format.
+    ///
+    /// The total size must be the same, and the type of the member must be compatible with the
+    /// numeric type of the format. For example `[f32; 2]` matches `R32G32Sfloat` but `[i32; 2]`
+    /// doesn't. 8 and 16 bits integers also match normalized and scaled formats.
     #[inline]
     pub fn matches(&self, array_size: usize, format: Format, num_locs: u32) -> bool {
-        // TODO: implement correctly
         let my_size = match *self {
             VertexMemberTy::I8 => 1,
             VertexMemberTy::U8 => 1,
@@ -66,6 +70,27 @@ impl VertexMemberTy {
             VertexMemberTy::F64 => 8,
         };
 
+        let ty_matches = match (*self, format.ty()) {
+            (VertexMemberTy::F32, FormatTy::Float) |
+            (VertexMemberTy::F64, FormatTy::Float) => true,
+            (VertexMemberTy::I8, FormatTy::Sint) |
+            (VertexMemberTy::I16, FormatTy::Sint) |
+            (VertexMemberTy::I32, FormatTy::Sint) => true,
+            (VertexMemberTy::U8, FormatTy::Uint) |
+            (VertexMemberTy::U16, FormatTy::Uint) |
+            (VertexMemberTy::U32, FormatTy::Uint) => true,
+            // Normalized and scaled formats.
+            (VertexMemberTy::I8, FormatTy::Float) |
+            (VertexMemberTy::U8, FormatTy::Float) |
+            (VertexMemberTy::I16, FormatTy::Float) |
+            (VertexMemberTy::U16, FormatTy::Float) => true,
+            _ => false,
+        };
+
+        if !ty_matches {
+            return false;
+        }
+
         let format_size = match format.size() {
             None => return false,
             Some(s) => s,
@@ -74,3 +99,35 @@ impl VertexMemberTy {
         array_size * my_size == format_size * num_locs as usize
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use pipeline::vertex::VertexMemberTy;
+
+    #[test]
+    fn matches_same_type() {
+        assert!(VertexMemberTy::F32.matches(2, Format::R32G32Sfloat, 1));
+        assert!(VertexMemberTy::I32.matches(2, Format::R32G32Sint, 1));
+        assert!(VertexMemberTy::U32.matches(2, Format::R32G32Uint, 1));
+    }
+
+    #[test]
+    fn wrong_size() {
+        assert!(!VertexMemberTy::F32.matches(3, Format::R32G32Sfloat, 1));
+        assert!(!VertexMemberTy::F32.matches(2, Format::R32G32Sfloat, 2));
+    }
+
+    #[test]
+    fn wrong_type() {
+        assert!(!VertexMemberTy::I32.matches(2, Format::R32G32Sfloat, 1));
+        assert!(!VertexMemberTy::F32.matches(2, Format::R32G32Uint, 1));
+        assert!(!VertexMemberTy::U32.matches(2, Format::R32G32Sint, 1));
+    }
+
+    #[test]
+    fn multiple_locations() {
+        // A `mat2` uses two locations of `vec2` each.
+        assert!(VertexMemberTy::F32.matches(4, Format::R32G32Sfloat, 2));
+    }
+}
@@ -12,26 +12,40 @@
 //! In Vulkan, queries are not created individually. Instead you manipulate **query pools**, which
 //! represent a collection of queries. Whenever you use a query, you have to specify both the query
 //! pool and the slot id within that query pool.
+//!
+//! Queries are begun and ended, reset, or written to with the corresponding commands of
+//! `AutoCommandBufferBuilder`. Once the commands have been executed, the results can be read
+//! either from the host with `UnsafeQueriesRange::get_results`, or on the device with the
+//! `copy_query_pool_results` command. If the `ext_host_query_reset` extension is enabled, queries
+//! can also be reset from the host with `UnsafeQueriesRange::reset`.
 
 use std::error;
 use std::fmt;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use device::Device;
 use device::DeviceOwned;
+use instance::QueueFamily;
 
 use Error;
 use OomError;
+use Success;
 use VulkanObject;
 use check_errors;
 use vk;
 
+pub mod profiler;
+
 pub struct UnsafeQueryPool {
     pool: vk::QueryPool,
     device: Arc<Device>,
     num_slots: u32,
+    ty: QueryType,
+    // False if the pool was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl UnsafeQueryPool {
@@ -73,15 +87,47 @@ impl UnsafeQueryPool {
                pool: pool,
                device: device,
                num_slots: num_slots,
+               ty: ty,
+               needs_destruction: true,
            })
     }
 
+    /// Builds an `UnsafeQueryPool` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the pool is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid query pool of `device`, created with the type `ty` and with
+    ///   `num_slots` queries.
+    /// - If `owned` is true, the pool must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::QueryPool, ty: QueryType,
+                           num_slots: u32, owned: bool)
+                           -> UnsafeQueryPool {
+        UnsafeQueryPool {
+            pool: handle,
+            device: device,
+            num_slots: num_slots,
+            ty: ty,
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns the number of slots of that query pool.
     #[inline]
     pub fn num_slots(&self) -> u32 {
         self.num_slots
     }
 
+    /// Returns the type of queries of that query pool.
+    #[inline]
+    pub fn ty(&self) -> QueryType {
+        self.ty
+    }
+
     #[inline]
     pub fn query(&self, index: u32) -> Option<UnsafeQuery> {
         if index < self.num_slots() {
@@ -99,7 +145,7 @@ impl UnsafeQueryPool {
     pub fn queries_range(&self, first_index: u32, count: u32) -> Option<UnsafeQueriesRange> {
         assert!(count >= 1);
 
-        if first_index + count < self.num_slots() {
+        if first_index.checked_add(count).map_or(false, |end| end <= self.num_slots()) {
             Some(UnsafeQueriesRange {
                      pool: self,
                      first: first_index,
@@ -167,6 +213,83 @@ impl<'a> UnsafeQueriesRange<'a> {
     pub fn count(&self) -> u32 {
         self.count
     }
+
+    /// Returns the number of elements of type `T` that `get_results` writes for these queries
+    /// with the given flags.
+    #[inline]
+    pub fn results_len(&self, flags: QueryResultFlags) -> usize {
+        let per_query = self.pool.ty().result_len() +
+            if flags.with_availability { 1 } else { 0 };
+        per_query * self.count as usize
+    }
+
+    /// Resets the queries from the host, instead of with the `reset_query_pool` command.
+    ///
+    /// # Safety
+    ///
+    /// - The queries must not be used by a command buffer that is being executed or that is
+    ///   waiting to be executed.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_host_query_reset` extension is not enabled on the device.
+    ///
+    #[inline]
+    pub unsafe fn reset(&self) {
+        assert!(self.pool.device.loaded_extensions().ext_host_query_reset);
+
+        let vk = self.pool.device.pointers();
+        vk.ResetQueryPoolEXT(self.pool.device.internal_object(),
+                             self.pool.pool,
+                             self.first,
+                             self.count);
+    }
+
+    /// Copies the results of the queries from the device to `destination`.
+    ///
+    /// For each query, `ty().result_len()` values are written, followed by the availability of
+    /// the result if `flags.with_availability` is set. `destination` must be large enough to
+    /// contain `results_len(flags)` elements.
+    ///
+    /// Returns `Ok(false)` if the results of some queries were not available yet, which can only
+    /// happen if `flags.wait` is not set. In that case, the values of these queries are only
+    /// written if `flags.partial` is set, and their availability is written as zero.
+    ///
+    /// > **Note**: If `flags.wait` is set, the queries must have been begun and ended or written
+    /// > to by a command buffer that has been submitted, otherwise this function never returns.
+    pub fn get_results<T>(&self, destination: &mut [T], flags: QueryResultFlags)
+                          -> Result<bool, GetResultsError>
+        where T: QueryResultElement
+    {
+        let required = self.results_len(flags);
+        if destination.len() < required {
+            return Err(GetResultsError::BufferTooSmall {
+                           required: required,
+                           actual: destination.len(),
+                       });
+        }
+
+        let per_query = required / self.count as usize;
+
+        let result = unsafe {
+            let vk = self.pool.device.pointers();
+            check_errors(vk.GetQueryPoolResults(self.pool.device.internal_object(),
+                                                self.pool.pool,
+                                                self.first,
+                                                self.count,
+                                                required * mem::size_of::<T>(),
+                                                destination.as_mut_ptr() as *mut _,
+                                                (per_query * mem::size_of::<T>()) as
+                                                    vk::DeviceSize,
+                                                flags.into_vulkan_bits() | T::FLAG))?
+        };
+
+        match result {
+            Success::Success => Ok(true),
+            Success::NotReady => Ok(false),
+            s => panic!("unexpected success value: {:?}", s),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -176,6 +299,178 @@ pub enum QueryType {
     Timestamp,
 }
 
+impl QueryType {
+    /// Returns the number of values that are written for the result of one query of this type,
+    /// not counting the availability value.
+    ///
+    /// This is 1 for occlusion and timestamp queries, and the number of enabled statistics for
+    /// pipeline statistics queries.
+    #[inline]
+    pub fn result_len(&self) -> usize {
+        match *self {
+            QueryType::Occlusion | QueryType::Timestamp => 1,
+            QueryType::PipelineStatistics(flags) => {
+                let bits: vk::QueryPipelineStatisticFlags = flags.into();
+                bits.count_ones() as usize
+            },
+        }
+    }
+}
+
+/// Converts the values written by timestamp queries into durations.
+///
+/// Timestamps are expressed in device-specific ticks, whose duration is given by the
+/// `timestamp_period` limit of the physical device. Only the lower `timestamp_valid_bits()` bits
+/// of the timestamps written by the queues of a family are meaningful.
+///
+/// ```no_run
+/// # use vulkano::device::Queue;
+/// # use vulkano::query::QueryResultFlags;
+/// # use vulkano::query::QueryType;
+/// # use vulkano::query::TimestampConverter;
+/// # use vulkano::query::UnsafeQueryPool;
+/// # let queue: Queue = return;
+/// let pool = UnsafeQueryPool::new(queue.device().clone(), QueryType::Timestamp, 2).unwrap();
+/// // ... write timestamps to the two queries and submit the commands ...
+///
+/// let mut timestamps = [0u64; 2];
+/// let flags = QueryResultFlags { wait: true, .. QueryResultFlags::none() };
+/// pool.queries_range(0, 2).unwrap().get_results(&mut timestamps, flags).unwrap();
+///
+/// let converter = TimestampConverter::new(queue.family()).unwrap();
+/// println!("GPU time: {:?}", converter.elapsed(timestamps[0], timestamps[1]));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimestampConverter {
+    period: f32,
+    valid_bits: u32,
+}
+
+impl TimestampConverter {
+    /// Builds a converter for the timestamps written by queues of the given family.
+    ///
+    /// Returns `None` if the queues of this family don't support timestamps.
+    #[inline]
+    pub fn new(queue_family: QueueFamily) -> Option<TimestampConverter> {
+        let valid_bits = match queue_family.timestamp_valid_bits() {
+            Some(bits) => bits,
+            None => return None,
+        };
+
+        Some(TimestampConverter::from_parameters(queue_family
+                                                     .physical_device()
+                                                     .limits()
+                                                     .timestamp_period(),
+                                                 valid_bits))
+    }
+
+    /// Builds a converter from the number of nanoseconds per tick and the number of meaningful
+    /// bits of the timestamps.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `valid_bits` is 0 or greater than 64.
+    ///
+    #[inline]
+    pub fn from_parameters(period: f32, valid_bits: u32) -> TimestampConverter {
+        assert!(valid_bits >= 1 && valid_bits <= 64);
+
+        TimestampConverter {
+            period: period,
+            valid_bits: valid_bits,
+        }
+    }
+
+    /// Returns the number of nanoseconds per tick.
+    #[inline]
+    pub fn period(&self) -> f32 {
+        self.period
+    }
+
+    /// Returns the number of meaningful bits of the timestamps.
+    #[inline]
+    pub fn valid_bits(&self) -> u32 {
+        self.valid_bits
+    }
+
+    /// Converts a number of ticks into a duration.
+    #[inline]
+    pub fn to_duration(&self, ticks: u64) -> Duration {
+        let nanos = (ticks as f64 * self.period as f64) as u64;
+        Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+    }
+
+    /// Returns the duration between two timestamps, where `end` was written after `start`.
+    ///
+    /// The bits of the timestamps that aren't meaningful are ignored, and a timestamp counter
+    /// that wrapped around between `start` and `end` is handled correctly.
+    #[inline]
+    pub fn elapsed(&self, start: u64, end: u64) -> Duration {
+        let mask = if self.valid_bits == 64 {
+            !0
+        } else {
+            (1u64 << self.valid_bits) - 1
+        };
+
+        self.to_duration(end.wrapping_sub(start) & mask)
+    }
+}
+
+/// Flags that control how the results of queries are retrieved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct QueryResultFlags {
+    /// Wait for the results of all the queries to become available before writing them.
+    pub wait: bool,
+    /// Write an additional value after each result, which is non-zero if the result is available.
+    pub with_availability: bool,
+    /// Allow writing an intermediate result if the final result isn't available yet.
+    pub partial: bool,
+}
+
+impl QueryResultFlags {
+    /// Builds a `QueryResultFlags` with none of the flags set.
+    #[inline]
+    pub fn none() -> QueryResultFlags {
+        QueryResultFlags {
+            wait: false,
+            with_availability: false,
+            partial: false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::QueryResultFlags {
+        let mut result = 0;
+        if self.wait {
+            result |= vk::QUERY_RESULT_WAIT_BIT;
+        }
+        if self.with_availability {
+            result |= vk::QUERY_RESULT_WITH_AVAILABILITY_BIT;
+        }
+        if self.partial {
+            result |= vk::QUERY_RESULT_PARTIAL_BIT;
+        }
+        result
+    }
+}
+
+/// Integer type in which the results of queries can be written.
+///
+/// This trait is implemented on `u32` and `u64`, and is unsafe because the flags must match the
+/// size of the type.
+pub unsafe trait QueryResultElement: Copy + Send + Sync + 'static {
+    /// Flags to pass to Vulkan to request results of this size.
+    const FLAG: vk::QueryResultFlags;
+}
+
+unsafe impl QueryResultElement for u32 {
+    const FLAG: vk::QueryResultFlags = 0;
+}
+
+unsafe impl QueryResultElement for u64 {
+    const FLAG: vk::QueryResultFlags = vk::QUERY_RESULT_64_BIT;
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct QueryPipelineStatisticFlags {
     pub input_assembly_vertices: bool,
@@ -253,6 +548,10 @@ impl Into<vk::QueryPipelineStatisticFlags> for QueryPipelineStatisticFlags {
 impl Drop for UnsafeQueryPool {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyQueryPool(self.device.internal_object(), self.pool, ptr::null());
@@ -267,6 +566,8 @@ pub enum QueryPoolCreationError {
     OomError(OomError),
     /// A pipeline statistics pool was requested but the corresponding feature wasn't enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for QueryPoolCreationError {
@@ -278,6 +579,8 @@ impl error::Error for QueryPoolCreationError {
                 "a pipeline statistics pool was requested but the corresponding feature \
                  wasn't enabled"
             },
+            QueryPoolCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -290,26 +593,59 @@ impl error::Error for QueryPoolCreationError {
     }
 }
 
-impl fmt::Display for QueryPoolCreationError {
+vk_error_conversions!(QueryPoolCreationError, OomError {});
+
+/// Error that can happen when reading the results of queries from the host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GetResultsError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The connection to the device has been lost.
+    DeviceLost,
+    /// The destination slice is too small to contain the results.
+    BufferTooSmall {
+        /// Required number of elements.
+        required: usize,
+        /// Actual number of elements.
+        actual: usize,
+    },
+}
+
+impl error::Error for GetResultsError {
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
+    fn description(&self) -> &str {
+        match *self {
+            GetResultsError::OomError(_) => "not enough memory available",
+            GetResultsError::DeviceLost => "the connection to the device has been lost",
+            GetResultsError::BufferTooSmall { .. } => {
+                "the destination slice is too small to contain the results"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            GetResultsError::OomError(ref err) => Some(err),
+            _ => None,
+        }
     }
 }
 
-impl From<OomError> for QueryPoolCreationError {
+impl fmt::Display for GetResultsError {
     #[inline]
-    fn from(err: OomError) -> QueryPoolCreationError {
-        QueryPoolCreationError::OomError(err)
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
     }
 }
 
-impl From<Error> for QueryPoolCreationError {
+impl From<Error> for GetResultsError {
     #[inline]
-    fn from(err: Error) -> QueryPoolCreationError {
+    fn from(err: Error) -> GetResultsError {
         match err {
-            err @ Error::OutOfHostMemory => QueryPoolCreationError::OomError(OomError::from(err)),
-            err @ Error::OutOfDeviceMemory => QueryPoolCreationError::OomError(OomError::from(err)),
+            err @ Error::OutOfHostMemory => GetResultsError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => GetResultsError::OomError(OomError::from(err)),
+            Error::DeviceLost => GetResultsError::DeviceLost,
             _ => panic!("unexpected error: {:?}", err),
         }
     }
@@ -321,15 +657,10 @@ pub struct OcclusionQueriesPool {
 
 impl OcclusionQueriesPool {
     /// See the docs of new().
-    pub fn raw(device: Arc<Device>, num_slots: u32) -> Result<OcclusionQueriesPool, OomError> {
+    pub fn raw(device: Arc<Device>, num_slots: u32)
+               -> Result<OcclusionQueriesPool, QueryPoolCreationError> {
         Ok(OcclusionQueriesPool {
-               inner: match UnsafeQueryPool::new(device, QueryType::Occlusion, num_slots) {
-                   Ok(q) => q,
-                   Err(QueryPoolCreationError::OomError(err)) => return Err(err),
-                   Err(QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled) => {
-                       unreachable!()
-                   },
-               },
+               inner: UnsafeQueryPool::new(device, QueryType::Occlusion, num_slots)?,
            })
     }
 
@@ -360,11 +691,20 @@ unsafe impl DeviceOwned for OcclusionQueriesPool {
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use query::GetResultsError;
     use query::OcclusionQueriesPool;
     use query::QueryPipelineStatisticFlags;
     use query::QueryPoolCreationError;
+    use query::QueryResultFlags;
     use query::QueryType;
+    use query::TimestampConverter;
     use query::UnsafeQueryPool;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sync::GpuFuture;
 
     #[test]
     fn occlusion_create() {
@@ -372,6 +712,19 @@ mod tests {
         let _ = OcclusionQueriesPool::new(device, 256);
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, 4).unwrap();
+
+        let borrowed = unsafe {
+            UnsafeQueryPool::from_raw(device, pool.internal_object(), QueryType::Timestamp, 4,
+                                      false)
+        };
+        assert_eq!(borrowed.internal_object(), pool.internal_object());
+        assert_eq!(borrowed.num_slots(), 4);
+    }
+
     #[test]
     fn pipeline_statistics_feature() {
         let (device, _) = gfx_dev_and_queue!();
@@ -382,4 +735,76 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn host_reset_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        let pool = UnsafeQueryPool::new(device, QueryType::Occlusion, 4).unwrap();
+        assert_should_panic!({
+                                 unsafe {
+                                     pool.queries_range(0, 4).unwrap().reset();
+                                 }
+                             });
+    }
+
+    #[test]
+    fn timestamp_conversion() {
+        let converter = TimestampConverter::from_parameters(2.5, 8);
+        assert_eq!(converter.to_duration(4), Duration::new(0, 10));
+        assert_eq!(converter.to_duration(800_000_000), Duration::new(2, 0));
+        assert_eq!(converter.elapsed(0x1fe, 0x302), Duration::new(0, 10));
+        assert_eq!(converter.elapsed(0xfe, 0x02), Duration::new(0, 10));
+    }
+
+    #[test]
+    fn occlusion_results() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = UnsafeQueryPool::new(device.clone(), QueryType::Occlusion, 2).unwrap();
+        let pool = Arc::new(pool);
+
+        let cb = unsafe {
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+                .unwrap()
+                .reset_query_pool(pool.clone(), 0, 2)
+                .unwrap()
+                .begin_query(pool.clone(), 0, false)
+                .unwrap()
+                .end_query(pool.clone(), 0)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let flags = QueryResultFlags {
+            wait: true,
+            with_availability: true,
+            ..QueryResultFlags::none()
+        };
+
+        let range = pool.queries_range(0, 1).unwrap();
+        assert_eq!(range.results_len(flags), 2);
+
+        let mut results = [0xffu64; 2];
+        assert_eq!(range.get_results(&mut results, flags), Ok(true));
+        assert_eq!(results[0], 0);
+        assert!(results[1] != 0);
+
+        // The second query was reset but never used, so its result is not available.
+        let mut results = [0u32; 2];
+        let range = pool.queries_range(1, 1).unwrap();
+        assert_eq!(range.get_results(&mut results, QueryResultFlags::none()), Ok(false));
+
+        match range.get_results(&mut [0u32; 0], QueryResultFlags::none()) {
+            Err(GetResultsError::BufferTooSmall { required: 1, actual: 0 }) => (),
+            _ => panic!(),
+        }
+    }
 }
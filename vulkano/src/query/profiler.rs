@@ -0,0 +1,483 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Measuring the time spent by the GPU on parts of a frame.
+//!
+//! A `GpuProfiler` writes a timestamp at the beginning and at the end of each named scope of the
+//! command buffers of a frame, and reports the duration of each scope once the frame has
+//! finished executing.
+//!
+//! As several frames are usually in flight at the same time, the profiler owns one query pool
+//! per frame in flight and uses them in turn. The results of a frame are read when its query
+//! pool is reused, in other words when `begin_frame` is called `frames_in_flight` frames later.
+//!
+//! ```no_run
+//! # use vulkano::command_buffer::AutoCommandBufferBuilder;
+//! # use vulkano::device::Queue;
+//! # use vulkano::query::profiler::GpuProfiler;
+//! # let queue: Queue = return;
+//! let mut profiler = GpuProfiler::new(&queue, 2, 16).unwrap();
+//!
+//! loop {
+//!     let builder = AutoCommandBufferBuilder::primary_one_time_submit(queue.device().clone(),
+//!                                                                     queue.family()).unwrap();
+//!     let builder = profiler.begin_frame(builder).unwrap();
+//!     let builder = profiler.begin_scope("shadows", builder).unwrap();
+//!     // ... draw the shadows ...
+//!     let builder = profiler.end_scope(builder).unwrap();
+//!     // ... submit the command buffer ...
+//!
+//!     for scope in profiler.results() {
+//!         println!("{}{}: {:?}", "  ".repeat(scope.depth as usize), scope.name, scope.duration);
+//!     }
+//! #   break;
+//! }
+//! ```
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::ResetQueryPoolError;
+use command_buffer::WriteTimestampError;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use query::GetResultsError;
+use query::QueryPoolCreationError;
+use query::QueryResultFlags;
+use query::QueryType;
+use query::TimestampConverter;
+use query::UnsafeQueryPool;
+use sync::PipelineStages;
+
+/// Measures the time spent by the GPU on named scopes of each frame.
+///
+/// See the documentation of the `profiler` module.
+pub struct GpuProfiler {
+    device: Arc<Device>,
+    converter: TimestampConverter,
+    max_scopes: u32,
+    // One entry per frame in flight.
+    frames: Vec<Frame>,
+    // Index within `frames` of the current frame. Only meaningful if `began` is true.
+    current: usize,
+    // True if `begin_frame` has been called at least once.
+    began: bool,
+    // Durations of the scopes of the most recent frame whose results were available.
+    results: Vec<ScopeTiming>,
+}
+
+struct Frame {
+    pool: Arc<UnsafeQueryPool>,
+    // Scopes recorded during the frame, in the order in which they were begun. The timestamps of
+    // scope `n` are written to queries `2 * n` and `2 * n + 1`.
+    scopes: Vec<(String, u32)>,
+    // Indices within `scopes` of the scopes that have been begun but not ended.
+    open: Vec<usize>,
+}
+
+/// Time spent by the GPU on a scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeTiming {
+    /// Name passed to `begin_scope`.
+    pub name: String,
+    /// Number of scopes that were open when this scope was begun.
+    pub depth: u32,
+    /// Time between the beginning and the end of the scope.
+    pub duration: Duration,
+}
+
+impl GpuProfiler {
+    /// Builds a new profiler for command buffers that are executed on `queue`.
+    ///
+    /// `frames_in_flight` is the number of frames whose command buffers can be executing at the
+    /// same time, and `max_scopes` the maximum number of scopes per frame.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `frames_in_flight` or `max_scopes` is 0.
+    ///
+    pub fn new(queue: &Queue, frames_in_flight: usize, max_scopes: u32)
+               -> Result<GpuProfiler, GpuProfilerCreationError> {
+        assert!(frames_in_flight >= 1);
+        assert!(max_scopes >= 1);
+
+        let converter = match TimestampConverter::new(queue.family()) {
+            Some(c) => c,
+            None => return Err(GpuProfilerCreationError::TimestampsNotSupported),
+        };
+
+        let device = queue.device().clone();
+        let mut frames = Vec::with_capacity(frames_in_flight);
+        for _ in 0 .. frames_in_flight {
+            let pool = UnsafeQueryPool::new(device.clone(), QueryType::Timestamp, max_scopes * 2)?;
+            frames.push(Frame {
+                            pool: Arc::new(pool),
+                            scopes: Vec::new(),
+                            open: Vec::new(),
+                        });
+        }
+
+        Ok(GpuProfiler {
+               device: device,
+               converter: converter,
+               max_scopes: max_scopes,
+               frames: frames,
+               current: 0,
+               began: false,
+               results: Vec::new(),
+           })
+    }
+
+    /// Starts a new frame, and adds a command that resets its queries to `builder`.
+    ///
+    /// The results of the frame that previously used the same query pool, in other words of the
+    /// frame that began `frames_in_flight` frames ago, are read and become available through
+    /// `results`. That frame must have finished executing, for example by waiting for its fence.
+    /// If some of its results aren't available, the results of the previous frames are kept.
+    ///
+    /// `builder` must be executed before the command buffers that contain the scopes of the
+    /// frame.
+    pub fn begin_frame<P>(&mut self, builder: AutoCommandBufferBuilder<P>)
+                          -> Result<AutoCommandBufferBuilder<P>, GpuProfilerError> {
+        if self.began {
+            self.current = (self.current + 1) % self.frames.len();
+        }
+        self.began = true;
+
+        self.collect_results()?;
+
+        let frame = &mut self.frames[self.current];
+        frame.scopes.clear();
+        frame.open.clear();
+        Ok(builder.reset_query_pool(frame.pool.clone(), 0, self.max_scopes * 2)?)
+    }
+
+    /// Adds a command that writes the timestamp of the beginning of a scope to `builder`.
+    ///
+    /// Scopes can be nested, and must be ended with `end_scope` in the reverse order.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `begin_frame` hasn't been called yet.
+    ///
+    pub fn begin_scope<P>(&mut self, name: &str, builder: AutoCommandBufferBuilder<P>)
+                          -> Result<AutoCommandBufferBuilder<P>, GpuProfilerError> {
+        assert!(self.began, "begin_frame must be called before begin_scope");
+
+        let frame = &mut self.frames[self.current];
+        if frame.scopes.len() >= self.max_scopes as usize {
+            return Err(GpuProfilerError::TooManyScopes);
+        }
+
+        let index = frame.scopes.len();
+        let builder = unsafe {
+            // Safe because the query has been reset by `begin_frame` and is only written once.
+            builder.write_timestamp(frame.pool.clone(),
+                                    index as u32 * 2,
+                                    PipelineStages {
+                                        top_of_pipe: true,
+                                        ..PipelineStages::none()
+                                    })?
+        };
+
+        frame.scopes.push((name.to_owned(), frame.open.len() as u32));
+        frame.open.push(index);
+        Ok(builder)
+    }
+
+    /// Adds a command that writes the timestamp of the end of the innermost open scope to
+    /// `builder`.
+    pub fn end_scope<P>(&mut self, builder: AutoCommandBufferBuilder<P>)
+                        -> Result<AutoCommandBufferBuilder<P>, GpuProfilerError> {
+        let frame = &mut self.frames[self.current];
+        let index = match frame.open.last() {
+            Some(&index) => index,
+            None => return Err(GpuProfilerError::NoOpenScope),
+        };
+
+        let builder = unsafe {
+            // Safe because the query has been reset by `begin_frame` and is only written once.
+            builder.write_timestamp(frame.pool.clone(),
+                                    index as u32 * 2 + 1,
+                                    PipelineStages {
+                                        bottom_of_pipe: true,
+                                        ..PipelineStages::none()
+                                    })?
+        };
+
+        frame.open.pop();
+        Ok(builder)
+    }
+
+    /// Returns the duration of each scope of the most recent frame whose results are available,
+    /// in the order in which the scopes were begun.
+    ///
+    /// Scopes that weren't ended are omitted.
+    #[inline]
+    pub fn results(&self) -> &[ScopeTiming] {
+        &self.results
+    }
+
+    /// Returns the number of frames in flight.
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    // Reads the results of the current frame, if it contains any scope.
+    fn collect_results(&mut self) -> Result<(), GetResultsError> {
+        let frame = &self.frames[self.current];
+        if frame.scopes.is_empty() {
+            return Ok(());
+        }
+
+        let flags = QueryResultFlags {
+            with_availability: true,
+            ..QueryResultFlags::none()
+        };
+
+        let range = frame
+            .pool
+            .queries_range(0, frame.scopes.len() as u32 * 2)
+            .unwrap();
+        let mut values = vec![0u64; range.results_len(flags)];
+        range.get_results(&mut values, flags)?;
+
+        // Each query writes its timestamp followed by its availability.
+        let mut results = Vec::with_capacity(frame.scopes.len());
+        for (n, &(ref name, depth)) in frame.scopes.iter().enumerate() {
+            let (start, start_available) = (values[n * 4], values[n * 4 + 1] != 0);
+            let (end, end_available) = (values[n * 4 + 2], values[n * 4 + 3] != 0);
+
+            if frame.open.contains(&n) {
+                continue;
+            }
+
+            if !start_available || !end_available {
+                return Ok(());
+            }
+
+            results.push(ScopeTiming {
+                             name: name.clone(),
+                             depth: depth,
+                             duration: self.converter.elapsed(start, end),
+                         });
+        }
+
+        self.results = results;
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for GpuProfiler {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Error that can happen when creating a `GpuProfiler`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GpuProfilerCreationError {
+    /// The queue family doesn't support timestamps.
+    TimestampsNotSupported,
+    /// Error while creating a query pool.
+    QueryPoolCreationError(QueryPoolCreationError),
+}
+
+impl error::Error for GpuProfilerCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            GpuProfilerCreationError::TimestampsNotSupported => {
+                "the queue family doesn't support timestamps"
+            },
+            GpuProfilerCreationError::QueryPoolCreationError(_) => {
+                "error while creating a query pool"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            GpuProfilerCreationError::QueryPoolCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GpuProfilerCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<QueryPoolCreationError> for GpuProfilerCreationError {
+    #[inline]
+    fn from(err: QueryPoolCreationError) -> GpuProfilerCreationError {
+        GpuProfilerCreationError::QueryPoolCreationError(err)
+    }
+}
+
+/// Error that can happen when recording the scopes of a `GpuProfiler`.
+#[derive(Debug, Clone)]
+pub enum GpuProfilerError {
+    /// The maximum number of scopes per frame has been reached.
+    TooManyScopes,
+    /// `end_scope` was called while no scope was open.
+    NoOpenScope,
+    /// Error while reading the results of a previous frame.
+    GetResultsError(GetResultsError),
+    /// Error while adding the command that resets the queries.
+    ResetQueryPoolError(ResetQueryPoolError),
+    /// Error while adding a command that writes a timestamp.
+    WriteTimestampError(WriteTimestampError),
+}
+
+impl error::Error for GpuProfilerError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            GpuProfilerError::TooManyScopes => {
+                "the maximum number of scopes per frame has been reached"
+            },
+            GpuProfilerError::NoOpenScope => "`end_scope` was called while no scope was open",
+            GpuProfilerError::GetResultsError(_) => {
+                "error while reading the results of a previous frame"
+            },
+            GpuProfilerError::ResetQueryPoolError(_) => {
+                "error while adding the command that resets the queries"
+            },
+            GpuProfilerError::WriteTimestampError(_) => {
+                "error while adding a command that writes a timestamp"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            GpuProfilerError::GetResultsError(ref err) => Some(err),
+            GpuProfilerError::ResetQueryPoolError(ref err) => Some(err),
+            GpuProfilerError::WriteTimestampError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GpuProfilerError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<GetResultsError> for GpuProfilerError {
+    #[inline]
+    fn from(err: GetResultsError) -> GpuProfilerError {
+        GpuProfilerError::GetResultsError(err)
+    }
+}
+
+impl From<ResetQueryPoolError> for GpuProfilerError {
+    #[inline]
+    fn from(err: ResetQueryPoolError) -> GpuProfilerError {
+        GpuProfilerError::ResetQueryPoolError(err)
+    }
+}
+
+impl From<WriteTimestampError> for GpuProfilerError {
+    #[inline]
+    fn from(err: WriteTimestampError) -> GpuProfilerError {
+        GpuProfilerError::WriteTimestampError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use query::profiler::GpuProfiler;
+    use query::profiler::GpuProfilerError;
+    use sync::GpuFuture;
+
+    #[test]
+    fn scope_errors() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut profiler = match GpuProfiler::new(&queue, 1, 1) {
+            Ok(p) => p,
+            Err(_) => return, // Timestamps not supported.
+        };
+
+        let builder = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                        queue.family())
+            .unwrap();
+        let builder = profiler.begin_frame(builder).unwrap();
+        let builder = profiler.begin_scope("scope", builder).unwrap();
+        let builder = profiler.end_scope(builder).unwrap();
+        let builder = match profiler.end_scope(builder) {
+            Err(GpuProfilerError::NoOpenScope) => {
+                AutoCommandBufferBuilder::primary(device.clone(), queue.family()).unwrap()
+            },
+            _ => panic!(),
+        };
+        match profiler.begin_scope("too many", builder) {
+            Err(GpuProfilerError::TooManyScopes) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn results_after_frame() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut profiler = match GpuProfiler::new(&queue, 1, 4) {
+            Ok(p) => p,
+            Err(_) => return, // Timestamps not supported.
+        };
+
+        let builder = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                        queue.family())
+            .unwrap();
+        let builder = profiler.begin_frame(builder).unwrap();
+        let builder = profiler.begin_scope("outer", builder).unwrap();
+        let builder = profiler.begin_scope("inner", builder).unwrap();
+        let builder = profiler.end_scope(builder).unwrap();
+        let builder = profiler.end_scope(builder).unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let builder = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                        queue.family())
+            .unwrap();
+        let _ = profiler.begin_frame(builder).unwrap();
+
+        let results = profiler.results();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "outer");
+        assert_eq!(results[0].depth, 0);
+        assert_eq!(results[1].name, "inner");
+        assert_eq!(results[1].depth, 1);
+    }
+}
@@ -60,20 +60,57 @@
 //!
 //! Samplers that don't use `ClampToBorder` are not concerned by these restrictions.
 //!
+//! If the `ext_custom_border_color` extension is enabled on the device, you can also use
+//! `BorderColor::FloatCustom` or `BorderColor::IntCustom` and pass an arbitrary value to
+//! `SamplerBuilder::custom_border_color`.
+//!
+//! # Sampler builder
+//!
+//! `Sampler::start` returns a `SamplerBuilder`, which gives access to all the parameters of a
+//! sampler with default values for the ones you don't care about:
+//!
+//! ```
+//! use vulkano::sampler::Filter;
+//! use vulkano::sampler::MipmapMode;
+//! use vulkano::sampler::Sampler;
+//! use vulkano::sampler::SamplerAddressMode;
+//!
+//! # let device: std::sync::Arc<vulkano::device::Device> = return;
+//! let _sampler = Sampler::start(device.clone())
+//!     .filter(Filter::Linear)
+//!     .mipmap_mode(MipmapMode::Linear)
+//!     .address_mode(SamplerAddressMode::Repeat)
+//!     .lod(0.0, 8.0)
+//!     .build()
+//!     .unwrap();
+//! ```
+//!
+//! # YCbCr conversions
+//!
+//! If the `khr_sampler_ycbcr_conversion` extension is enabled on the device, a
+//! `SamplerYcbcrConversion` can be passed to `SamplerBuilder::ycbcr_conversion` in order to
+//! convert the values of the sampled image from YCbCr to RGB. The image view must be created
+//! with the same conversion, and the sampler must be an immutable sampler of a combined image
+//! sampler descriptor.
+//!
 // FIXME: restrictions aren't checked yet
 
 use std::error;
 use std::fmt;
 use std::mem;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 
-use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use format::Format;
+use format::FormatFeatures;
+use image::Swizzle;
+use instance::PhysicalDevice;
 use vk;
 
 pub use pipeline::depth_stencil::Compare;
@@ -84,12 +121,82 @@ pub struct Sampler {
     device: Arc<Device>,
     compare_mode: bool,
     unnormalized: bool,
+    ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>,
     usable_with_float_formats: bool,
     usable_with_int_formats: bool,
     usable_with_swizzling: bool,
+    // True if the sampler uses a custom border color and is counted in the
+    // `custom_border_color_sampler_count` of the device.
+    custom_border_color: bool,
+    // False if the sampler was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl Sampler {
+    /// Builds a `Sampler` from a raw handle that was created outside of vulkano.
+    ///
+    /// `compare_mode`, `unnormalized` and `border_color` must match the parameters that were
+    /// used to create the sampler. `border_color` must be `None` if none of the address modes
+    /// is `ClampToBorder`.
+    /// If `owned` is true, the sampler is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid sampler of `device`, created without a YCbCr conversion.
+    /// - If `owned` is true, the sampler must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::Sampler, compare_mode: bool,
+                           unnormalized: bool, border_color: Option<BorderColor>, owned: bool)
+                           -> Arc<Sampler> {
+        Arc::new(Sampler::from_parts(device,
+                                     handle,
+                                     compare_mode,
+                                     unnormalized,
+                                     border_color,
+                                     None,
+                                     owned))
+    }
+
+    // Builds the `Sampler` struct, and determines from the border color the image views the
+    // sampler can be used with.
+    fn from_parts(device: Arc<Device>, sampler: vk::Sampler, compare_mode: bool,
+                  unnormalized: bool, border_color: Option<BorderColor>,
+                  ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>, needs_destruction: bool)
+                  -> Sampler {
+        Sampler {
+            sampler: sampler,
+            device: device,
+            compare_mode: compare_mode,
+            unnormalized: unnormalized,
+            ycbcr_conversion: ycbcr_conversion,
+            usable_with_float_formats: match border_color {
+                Some(BorderColor::FloatTransparentBlack) => true,
+                Some(BorderColor::FloatOpaqueBlack) => true,
+                Some(BorderColor::FloatOpaqueWhite) => true,
+                Some(BorderColor::FloatCustom) => true,
+                Some(_) => false,
+                None => true,
+            },
+            usable_with_int_formats: !compare_mode &&
+                match border_color {
+                    Some(BorderColor::IntTransparentBlack) => true,
+                    Some(BorderColor::IntOpaqueBlack) => true,
+                    Some(BorderColor::IntOpaqueWhite) => true,
+                    Some(BorderColor::IntCustom) => true,
+                    Some(_) => false,
+                    None => true,
+                },
+            usable_with_swizzling: match border_color {
+                Some(BorderColor::FloatOpaqueBlack) => false,
+                Some(BorderColor::IntOpaqueBlack) => false,
+                _ => true,
+            },
+            custom_border_color: false,
+            needs_destruction: needs_destruction,
+        }
+    }
+
     /// Shortcut for creating a sampler with linear sampling, linear mipmaps, and with the repeat
     /// mode for borders.
     ///
@@ -165,24 +272,23 @@ impl Sampler {
     /// - Panics if `max_anisotropy < 1.0`.
     /// - Panics if `min_lod > max_lod`.
     ///
-    #[inline(always)]
+    #[inline]
     pub fn new(device: Arc<Device>, mag_filter: Filter, min_filter: Filter,
                mipmap_mode: MipmapMode, address_u: SamplerAddressMode,
                address_v: SamplerAddressMode, address_w: SamplerAddressMode, mip_lod_bias: f32,
                max_anisotropy: f32, min_lod: f32, max_lod: f32)
                -> Result<Arc<Sampler>, SamplerCreationError> {
-        Sampler::new_impl(device,
-                          mag_filter,
-                          min_filter,
-                          mipmap_mode,
-                          address_u,
-                          address_v,
-                          address_w,
-                          mip_lod_bias,
-                          max_anisotropy,
-                          min_lod,
-                          max_lod,
-                          None)
+        Sampler::start(device)
+            .mag_filter(mag_filter)
+            .min_filter(min_filter)
+            .mipmap_mode(mipmap_mode)
+            .address_mode_u(address_u)
+            .address_mode_v(address_v)
+            .address_mode_w(address_w)
+            .mip_lod_bias(mip_lod_bias)
+            .max_anisotropy(max_anisotropy)
+            .lod(min_lod, max_lod)
+            .build()
     }
 
     /// Creates a new `Sampler` with the given behavior.
@@ -201,45 +307,363 @@ impl Sampler {
     ///
     /// Same panic reasons as `new`.
     ///
-    #[inline(always)]
+    #[inline]
     pub fn compare(device: Arc<Device>, mag_filter: Filter, min_filter: Filter,
                    mipmap_mode: MipmapMode, address_u: SamplerAddressMode,
                    address_v: SamplerAddressMode, address_w: SamplerAddressMode,
                    mip_lod_bias: f32, max_anisotropy: f32, min_lod: f32, max_lod: f32,
                    compare: Compare)
                    -> Result<Arc<Sampler>, SamplerCreationError> {
-        Sampler::new_impl(device,
-                          mag_filter,
-                          min_filter,
-                          mipmap_mode,
-                          address_u,
-                          address_v,
-                          address_w,
-                          mip_lod_bias,
-                          max_anisotropy,
-                          min_lod,
-                          max_lod,
-                          Some(compare))
-    }
-
-    fn new_impl(device: Arc<Device>, mag_filter: Filter, min_filter: Filter,
-                mipmap_mode: MipmapMode, address_u: SamplerAddressMode,
-                address_v: SamplerAddressMode, address_w: SamplerAddressMode, mip_lod_bias: f32,
-                max_anisotropy: f32, min_lod: f32, max_lod: f32, compare: Option<Compare>)
-                -> Result<Arc<Sampler>, SamplerCreationError> {
-        assert!(max_anisotropy >= 1.0);
-        assert!(min_lod <= max_lod);
+        Sampler::start(device)
+            .mag_filter(mag_filter)
+            .min_filter(min_filter)
+            .mipmap_mode(mipmap_mode)
+            .address_mode_u(address_u)
+            .address_mode_v(address_v)
+            .address_mode_w(address_w)
+            .mip_lod_bias(mip_lod_bias)
+            .max_anisotropy(max_anisotropy)
+            .lod(min_lod, max_lod)
+            .compare(compare)
+            .build()
+    }
+
+    /// Creates a sampler with unnormalized coordinates. This means that texture coordinates won't
+    /// range between `0.0` and `1.0` but use plain pixel offsets.
+    ///
+    /// Using an unnormalized sampler adds a few restrictions:
+    ///
+    /// - It can only be used with non-array 1D or 2D images.
+    /// - It can only be used with images with a single mipmap.
+    /// - Projection and offsets can't be used by shaders. Only the first mipmap can be accessed.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if multiple `ClampToBorder` values are passed and the border color is different.
+    ///
+    pub fn unnormalized(device: Arc<Device>, filter: Filter,
+                        address_u: UnnormalizedSamplerAddressMode,
+                        address_v: UnnormalizedSamplerAddressMode)
+                        -> Result<Arc<Sampler>, SamplerCreationError> {
+        Sampler::start(device)
+            .filter(filter)
+            .address_mode_u(address_u.to_normalized())
+            .address_mode_v(address_v.to_normalized())
+            .unnormalized_coordinates()
+            .build()
+    }
+
+    /// Starts building a sampler.
+    ///
+    /// By default the sampler uses nearest filtering, the `ClampToEdge` address mode, no
+    /// anisotropy and no comparison, and can access all the mipmaps of an image.
+    #[inline]
+    pub fn start(device: Arc<Device>) -> SamplerBuilder {
+        SamplerBuilder {
+            device: device,
+            mag_filter: Filter::Nearest,
+            min_filter: Filter::Nearest,
+            mipmap_mode: MipmapMode::Nearest,
+            address_u: SamplerAddressMode::ClampToEdge,
+            address_v: SamplerAddressMode::ClampToEdge,
+            address_w: SamplerAddressMode::ClampToEdge,
+            mip_lod_bias: 0.0,
+            max_anisotropy: 1.0,
+            min_lod: 0.0,
+            max_lod: 1_000.0,
+            compare: None,
+            unnormalized: false,
+            custom_border_color: None,
+            ycbcr_conversion: None,
+        }
+    }
+
+    /// Returns true if the sampler is a compare-mode sampler.
+    #[inline]
+    pub fn compare_mode(&self) -> bool {
+        self.compare_mode
+    }
+
+    /// Returns true if the sampler is unnormalized.
+    #[inline]
+    pub fn is_unnormalized(&self) -> bool {
+        self.unnormalized
+    }
+
+    /// Returns the YCbCr conversion of the sampler, if any.
+    #[inline]
+    pub fn ycbcr_conversion(&self) -> Option<&Arc<SamplerYcbcrConversion>> {
+        self.ycbcr_conversion.as_ref()
+    }
+
+    /// Returns true if the sampler can be used with floating-point image views. See the
+    /// documentation of the `sampler` module for more info.
+    #[inline]
+    pub fn usable_with_float_formats(&self) -> bool {
+        self.usable_with_float_formats
+    }
+
+    /// Returns true if the sampler can be used with integer image views. See the documentation of
+    /// the `sampler` module for more info.
+    #[inline]
+    pub fn usable_with_int_formats(&self) -> bool {
+        self.usable_with_int_formats
+    }
+
+    /// Returns true if the sampler can be used with image views that have non-identity swizzling.
+    /// See the documentation of the `sampler` module for more info.
+    #[inline]
+    pub fn usable_with_swizzling(&self) -> bool {
+        self.usable_with_swizzling
+    }
+}
+
+unsafe impl DeviceOwned for Sampler {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for Sampler {
+    type Object = vk::Sampler;
+
+    const TYPE: vk::DebugReportObjectTypeEXT = vk::DEBUG_REPORT_OBJECT_TYPE_SAMPLER_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Sampler {
+        self.sampler
+    }
+}
+
+impl fmt::Debug for Sampler {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan sampler {:?}>", self.sampler)
+    }
+}
+
+impl Drop for Sampler {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySampler(self.device.internal_object(), self.sampler, ptr::null());
+        }
+
+        if self.custom_border_color {
+            let mut count = self.device
+                .custom_border_color_sampler_count()
+                .lock()
+                .expect("Poisoned mutex");
+            *count -= 1;
+        }
+    }
+}
+
+/// Prototype of a sampler.
+///
+/// Created with `Sampler::start`.
+///
+/// # Example
+///
+/// ```
+/// use vulkano::sampler::Compare;
+/// use vulkano::sampler::Filter;
+/// use vulkano::sampler::Sampler;
+/// use vulkano::sampler::SamplerAddressMode;
+///
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let shadow_sampler = Sampler::start(device.clone())
+///     .filter(Filter::Linear)
+///     .address_mode(SamplerAddressMode::ClampToEdge)
+///     .compare(Compare::LessOrEqual)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct SamplerBuilder {
+    device: Arc<Device>,
+    mag_filter: Filter,
+    min_filter: Filter,
+    mipmap_mode: MipmapMode,
+    address_u: SamplerAddressMode,
+    address_v: SamplerAddressMode,
+    address_w: SamplerAddressMode,
+    mip_lod_bias: f32,
+    max_anisotropy: f32,
+    min_lod: f32,
+    max_lod: f32,
+    compare: Option<Compare>,
+    unnormalized: bool,
+    custom_border_color: Option<(CustomBorderColor, Format)>,
+    ycbcr_conversion: Option<Arc<SamplerYcbcrConversion>>,
+}
+
+impl SamplerBuilder {
+    /// Sets how the image is sampled when it is larger than the original.
+    #[inline]
+    pub fn mag_filter(mut self, filter: Filter) -> SamplerBuilder {
+        self.mag_filter = filter;
+        self
+    }
+
+    /// Sets how the image is sampled when it is smaller than the original.
+    #[inline]
+    pub fn min_filter(mut self, filter: Filter) -> SamplerBuilder {
+        self.min_filter = filter;
+        self
+    }
+
+    /// Sets both the magnification and the minification filters.
+    #[inline]
+    pub fn filter(self, filter: Filter) -> SamplerBuilder {
+        self.mag_filter(filter).min_filter(filter)
+    }
+
+    /// Sets how the mipmap to sample from is chosen.
+    #[inline]
+    pub fn mipmap_mode(mut self, mode: MipmapMode) -> SamplerBuilder {
+        self.mipmap_mode = mode;
+        self
+    }
+
+    /// Sets the behavior when sampling outside of the range `[0.0, 1.0]` on the U axis.
+    #[inline]
+    pub fn address_mode_u(mut self, mode: SamplerAddressMode) -> SamplerBuilder {
+        self.address_u = mode;
+        self
+    }
+
+    /// Sets the behavior when sampling outside of the range `[0.0, 1.0]` on the V axis.
+    #[inline]
+    pub fn address_mode_v(mut self, mode: SamplerAddressMode) -> SamplerBuilder {
+        self.address_v = mode;
+        self
+    }
+
+    /// Sets the behavior when sampling outside of the range `[0.0, 1.0]` on the W axis.
+    #[inline]
+    pub fn address_mode_w(mut self, mode: SamplerAddressMode) -> SamplerBuilder {
+        self.address_w = mode;
+        self
+    }
+
+    /// Sets the same address mode on all three axes.
+    #[inline]
+    pub fn address_mode(self, mode: SamplerAddressMode) -> SamplerBuilder {
+        self.address_mode_u(mode).address_mode_v(mode).address_mode_w(mode)
+    }
+
+    /// Sets the value added to the mipmap level of detail computed by the implementation.
+    ///
+    /// The absolute value must not exceed the `max_sampler_lod_bias` limit.
+    #[inline]
+    pub fn mip_lod_bias(mut self, bias: f32) -> SamplerBuilder {
+        self.mip_lod_bias = bias;
+        self
+    }
+
+    /// Sets the maximum anisotropy. A value of 1.0 disables anisotropic filtering.
+    ///
+    /// Using a value superior to 1.0 requires the `sampler_anisotropy` feature to be enabled
+    /// when creating the device, and the value must not exceed the `max_sampler_anisotropy`
+    /// limit.
+    #[inline]
+    pub fn max_anisotropy(mut self, max_anisotropy: f32) -> SamplerBuilder {
+        self.max_anisotropy = max_anisotropy;
+        self
+    }
+
+    /// Sets the range of levels of detail that the computed level is clamped to.
+    ///
+    /// Use a `max_lod` of `1000.0` to not clamp the upper end of the range.
+    #[inline]
+    pub fn lod(mut self, min_lod: f32, max_lod: f32) -> SamplerBuilder {
+        self.min_lod = min_lod;
+        self.max_lod = max_lod;
+        self
+    }
+
+    /// Turns the sampler into a compare-mode sampler that compares the reference value passed
+    /// by the shader to the value of the texture with `compare`.
+    ///
+    /// See the documentation of `Sampler::compare` for more info.
+    #[inline]
+    pub fn compare(mut self, compare: Compare) -> SamplerBuilder {
+        self.compare = Some(compare);
+        self
+    }
+
+    /// Makes the sampler use unnormalized coordinates.
+    ///
+    /// This also resets the mipmap mode and the level of detail range to `MipmapMode::Nearest`
+    /// and `0.0`, which are the only values allowed with unnormalized coordinates. See the
+    /// documentation of `Sampler::unnormalized` for the other restrictions.
+    #[inline]
+    pub fn unnormalized_coordinates(mut self) -> SamplerBuilder {
+        self.unnormalized = true;
+        self.mipmap_mode = MipmapMode::Nearest;
+        self.min_lod = 0.0;
+        self.max_lod = 0.0;
+        self
+    }
+
+    /// Sets the value of `BorderColor::FloatCustom` or `BorderColor::IntCustom`, depending on the
+    /// kind of `color`.
+    ///
+    /// `format` is the format of the image views that the sampler will be used with. The value
+    /// is ignored if none of the address modes uses a custom border color.
+    #[inline]
+    pub fn custom_border_color(mut self, color: CustomBorderColor, format: Format)
+                               -> SamplerBuilder {
+        self.custom_border_color = Some((color, format));
+        self
+    }
+
+    /// Makes the sampler convert the values of the image from YCbCr to RGB with `conversion`.
+    ///
+    /// The address modes must be `ClampToEdge`, the minification and magnification filters must
+    /// be the chroma filter of the conversion, and anisotropic filtering and unnormalized
+    /// coordinates can't be used.
+    #[inline]
+    pub fn ycbcr_conversion(mut self, conversion: Arc<SamplerYcbcrConversion>)
+                            -> SamplerBuilder {
+        self.ycbcr_conversion = Some(conversion);
+        self
+    }
+
+    /// Builds the sampler.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the YCbCr conversion wasn't created with the same device.
+    /// - Panics if the address modes use `ClampToBorder` with different border colors.
+    /// - Panics if the address modes use a custom border color and `custom_border_color` wasn't
+    ///   called with a value of the same kind.
+    /// - Panics if `max_anisotropy < 1.0`.
+    /// - Panics if `min_lod > max_lod`.
+    ///
+    pub fn build(self) -> Result<Arc<Sampler>, SamplerCreationError> {
+        let device = self.device;
+
+        assert!(self.max_anisotropy >= 1.0);
+        assert!(self.min_lod <= self.max_lod);
 
         // Check max anisotropy.
-        if max_anisotropy > 1.0 {
+        if self.max_anisotropy > 1.0 {
             if !device.enabled_features().sampler_anisotropy {
                 return Err(SamplerCreationError::SamplerAnisotropyFeatureNotEnabled);
             }
 
             let limit = device.physical_device().limits().max_sampler_anisotropy();
-            if max_anisotropy > limit {
+            if self.max_anisotropy > limit {
                 return Err(SamplerCreationError::AnisotropyLimitExceeded {
-                               requested: max_anisotropy,
+                               requested: self.max_anisotropy,
                                maximum: limit,
                            });
             }
@@ -248,16 +672,16 @@ impl Sampler {
         // Check mip_lod_bias value.
         {
             let limit = device.physical_device().limits().max_sampler_lod_bias();
-            if mip_lod_bias > limit {
+            if self.mip_lod_bias.abs() > limit {
                 return Err(SamplerCreationError::MipLodBiasLimitExceeded {
-                               requested: mip_lod_bias,
+                               requested: self.mip_lod_bias,
                                maximum: limit,
                            });
             }
         }
 
         // Check MirrorClampToEdge extension support
-        if [address_u, address_v, address_w]
+        if [self.address_u, self.address_v, self.address_w]
             .iter()
             .any(|&mode| mode == SamplerAddressMode::MirrorClampToEdge)
         {
@@ -266,9 +690,47 @@ impl Sampler {
             }
         }
 
+        // Check the restrictions of unnormalized coordinates. The W axis is ignored.
+        if self.unnormalized {
+            let address_mode_ok = |mode: SamplerAddressMode| match mode {
+                SamplerAddressMode::ClampToEdge |
+                SamplerAddressMode::ClampToBorder(_) => true,
+                _ => false,
+            };
+
+            if self.mag_filter != self.min_filter || self.mipmap_mode != MipmapMode::Nearest ||
+                self.min_lod != 0.0 || self.max_lod != 0.0 ||
+                !address_mode_ok(self.address_u) ||
+                !address_mode_ok(self.address_v) ||
+                self.max_anisotropy > 1.0 || self.compare.is_some()
+            {
+                return Err(SamplerCreationError::UnnormalizedCoordinatesRestrictionsViolated);
+            }
+        }
+
+        // Check the restrictions of YCbCr conversions.
+        if let Some(ref conversion) = self.ycbcr_conversion {
+            assert_eq!(conversion.device().internal_object(), device.internal_object());
+
+            if [self.address_u, self.address_v, self.address_w]
+                .iter()
+                .any(|&mode| mode != SamplerAddressMode::ClampToEdge) ||
+                self.max_anisotropy > 1.0 || self.unnormalized ||
+                self.min_filter != conversion.chroma_filter() ||
+                self.mag_filter != conversion.chroma_filter()
+            {
+                return Err(SamplerCreationError::YcbcrConversionRestrictionsViolated);
+            }
+        }
+
         // Handling border color.
-        let border_color = address_u.border_color();
-        let border_color = match (border_color, address_v.border_color()) {
+        let address_w = if self.unnormalized {
+            SamplerAddressMode::ClampToEdge // unused by the impl
+        } else {
+            self.address_w
+        };
+        let border_color = self.address_u.border_color();
+        let border_color = match (border_color, self.address_v.border_color()) {
             (Some(b1), Some(b2)) => {
                 assert_eq!(b1, b2);
                 Some(b1)
@@ -285,35 +747,100 @@ impl Sampler {
             (b, None) => b,
         };
 
+        let custom_border_color_infos = match (border_color, self.custom_border_color) {
+            (Some(BorderColor::FloatCustom), Some((CustomBorderColor::Float(value), format))) => {
+                Some((vk::ClearColorValue { float32: value }, format))
+            },
+            (Some(BorderColor::IntCustom), Some((CustomBorderColor::Int(value), format))) => {
+                Some((vk::ClearColorValue { int32: value }, format))
+            },
+            (Some(BorderColor::FloatCustom), _) | (Some(BorderColor::IntCustom), _) => {
+                panic!("No custom border color of the right kind was provided")
+            },
+            _ => None,
+        };
+
+        let custom_border_color_infos = match custom_border_color_infos {
+            Some((value, format)) => {
+                if !device.loaded_extensions().ext_custom_border_color {
+                    return Err(SamplerCreationError::CustomBorderColorExtensionNotEnabled);
+                }
+
+                Some(vk::SamplerCustomBorderColorCreateInfoEXT {
+                         sType: vk::STRUCTURE_TYPE_SAMPLER_CUSTOM_BORDER_COLOR_CREATE_INFO_EXT,
+                         pNext: ptr::null(),
+                         customBorderColor: value,
+                         format: format as u32,
+                     })
+            },
+            None => None,
+        };
+
+        let mut next: *const c_void = custom_border_color_infos
+            .as_ref()
+            .map(|i| i as *const _ as *const _)
+            .unwrap_or(ptr::null());
+
+        let ycbcr_conversion_infos = self.ycbcr_conversion.as_ref().map(|conversion| {
+            vk::SamplerYcbcrConversionInfo {
+                sType: vk::STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_INFO,
+                pNext: next,
+                conversion: conversion.internal_object(),
+            }
+        });
+        if let Some(ref infos) = ycbcr_conversion_infos {
+            next = infos as *const _ as *const _;
+        }
+
+        // Held until the sampler is created, so that other threads can't exceed the limit.
+        let mut custom_border_color_count = if custom_border_color_infos.is_some() {
+            let max = CustomBorderColorProperties::from_physical_device(device.physical_device())
+                .max_custom_border_color_samplers;
+            let count = device
+                .custom_border_color_sampler_count()
+                .lock()
+                .expect("Poisoned mutex");
+            if *count >= max {
+                return Err(SamplerCreationError::TooManyCustomBorderColorSamplers { max: max });
+            }
+            Some(count)
+        } else {
+            None
+        };
+
         let vk = device.pointers();
         let sampler = unsafe {
             let infos = vk::SamplerCreateInfo {
                 sType: vk::STRUCTURE_TYPE_SAMPLER_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: next,
                 flags: 0, // reserved
-                magFilter: mag_filter as u32,
-                minFilter: min_filter as u32,
-                mipmapMode: mipmap_mode as u32,
-                addressModeU: address_u.to_vk(),
-                addressModeV: address_v.to_vk(),
+                magFilter: self.mag_filter as u32,
+                minFilter: self.min_filter as u32,
+                mipmapMode: self.mipmap_mode as u32,
+                addressModeU: self.address_u.to_vk(),
+                addressModeV: self.address_v.to_vk(),
                 addressModeW: address_w.to_vk(),
-                mipLodBias: mip_lod_bias,
-                anisotropyEnable: if max_anisotropy > 1.0 {
+                mipLodBias: self.mip_lod_bias,
+                anisotropyEnable: if self.max_anisotropy > 1.0 {
                     vk::TRUE
                 } else {
                     vk::FALSE
                 },
-                maxAnisotropy: max_anisotropy,
-                compareEnable: if compare.is_some() {
+                maxAnisotropy: self.max_anisotropy,
+                compareEnable: if self.compare.is_some() {
                     vk::TRUE
                 } else {
                     vk::FALSE
                 },
-                compareOp: compare.map(|c| c as u32).unwrap_or(0),
-                minLod: min_lod,
-                maxLod: max_lod,
+                compareOp: self.compare.map(|c| c as u32).unwrap_or(vk::COMPARE_OP_NEVER),
+                minLod: self.min_lod,
+                maxLod: self.max_lod,
                 borderColor: border_color.map(|b| b as u32).unwrap_or(0),
-                unnormalizedCoordinates: vk::FALSE,
+                unnormalizedCoordinates: if self.unnormalized {
+                    vk::TRUE
+                } else {
+                    vk::FALSE
+                },
             };
 
             let mut output = mem::uninitialized();
@@ -324,189 +851,366 @@ impl Sampler {
             output
         };
 
-        Ok(Arc::new(Sampler {
-                        sampler: sampler,
-                        device: device.clone(),
-                        compare_mode: compare.is_some(),
-                        unnormalized: false,
-                        usable_with_float_formats: match border_color {
-                            Some(BorderColor::FloatTransparentBlack) => true,
-                            Some(BorderColor::FloatOpaqueBlack) => true,
-                            Some(BorderColor::FloatOpaqueWhite) => true,
-                            Some(_) => false,
-                            None => true,
-                        },
-                        usable_with_int_formats: compare.is_none() &&
-                            match border_color {
-                                Some(BorderColor::IntTransparentBlack) => true,
-                                Some(BorderColor::IntOpaqueBlack) => true,
-                                Some(BorderColor::IntOpaqueWhite) => true,
-                                Some(_) => false,
-                                None => true,
-                            },
-                        usable_with_swizzling: match border_color {
-                            Some(BorderColor::FloatOpaqueBlack) => false,
-                            Some(BorderColor::IntOpaqueBlack) => false,
-                            _ => true,
-                        },
-                    }))
+        let mut sampler = Sampler::from_parts(device.clone(),
+                                              sampler,
+                                              self.compare.is_some(),
+                                              self.unnormalized,
+                                              border_color,
+                                              self.ycbcr_conversion,
+                                              true);
+
+        if let Some(ref mut count) = custom_border_color_count {
+            **count += 1;
+            sampler.custom_border_color = true;
+        }
+
+        Ok(Arc::new(sampler))
     }
+}
 
-    /// Creates a sampler with unnormalized coordinates. This means that texture coordinates won't
-    /// range between `0.0` and `1.0` but use plain pixel offsets.
+/// Describes how to convert the YCbCr values of an image into RGB values when sampling it.
+///
+/// A sampler that uses a conversion can only be used as an immutable sampler of a combined image
+/// sampler descriptor, and the image views it samples must be created with the same conversion
+/// with `UnsafeImageView::with_ycbcr_conversion`.
+///
+/// Requires the `khr_sampler_ycbcr_conversion` extension to be enabled on the device.
+pub struct SamplerYcbcrConversion {
+    conversion: vk::SamplerYcbcrConversion,
+    device: Arc<Device>,
+    format: Format,
+    chroma_filter: Filter,
+    // False if the conversion was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+}
+
+impl SamplerYcbcrConversion {
+    /// Starts building a conversion for images of the given format.
     ///
-    /// Using an unnormalized sampler adds a few restrictions:
+    /// By default the conversion uses the BT.709 model with the narrow range, identity
+    /// swizzling, cosited chroma samples and nearest chroma filtering.
+    #[inline]
+    pub fn start(device: Arc<Device>, format: Format) -> SamplerYcbcrConversionBuilder {
+        SamplerYcbcrConversionBuilder {
+            device: device,
+            format: format,
+            model: YcbcrModel::Ycbcr709,
+            range: YcbcrRange::ItuNarrow,
+            components: Swizzle::default(),
+            chroma_offset: [ChromaLocation::CositedEven, ChromaLocation::CositedEven],
+            chroma_filter: Filter::Nearest,
+            force_explicit_reconstruction: false,
+        }
+    }
+
+    /// Builds a `SamplerYcbcrConversion` from a raw handle that was created outside of vulkano.
     ///
-    /// - It can only be used with non-array 1D or 2D images.
-    /// - It can only be used with images with a single mipmap.
-    /// - Projection and offsets can't be used by shaders. Only the first mipmap can be accessed.
+    /// If `owned` is true, the conversion is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
     ///
-    /// # Panic
+    /// # Safety
     ///
-    /// - Panics if multiple `ClampToBorder` values are passed and the border color is different.
+    /// - `handle` must be a valid YCbCr conversion of `device`, created for `format` and with
+    ///   `chroma_filter` as chroma filter.
+    /// - If `owned` is true, the conversion must not be destroyed by anything else.
     ///
-    pub fn unnormalized(device: Arc<Device>, filter: Filter,
-                        address_u: UnnormalizedSamplerAddressMode,
-                        address_v: UnnormalizedSamplerAddressMode)
-                        -> Result<Arc<Sampler>, SamplerCreationError> {
-        let vk = device.pointers();
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::SamplerYcbcrConversion,
+                           format: Format, chroma_filter: Filter, owned: bool)
+                           -> Arc<SamplerYcbcrConversion> {
+        Arc::new(SamplerYcbcrConversion {
+                     conversion: handle,
+                     device: device,
+                     format: format,
+                     chroma_filter: chroma_filter,
+                     needs_destruction: owned,
+                 })
+    }
 
-        let border_color = address_u.border_color();
-        let border_color = match (border_color, address_v.border_color()) {
-            (Some(b1), Some(b2)) => {
-                assert_eq!(b1, b2);
-                Some(b1)
-            },
-            (None, b) => b,
-            (b, None) => b,
-        };
+    /// Returns the format of the images that the conversion applies to.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
 
-        let sampler = unsafe {
-            let infos = vk::SamplerCreateInfo {
-                sType: vk::STRUCTURE_TYPE_SAMPLER_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
-                magFilter: filter as u32,
-                minFilter: filter as u32,
-                mipmapMode: vk::SAMPLER_MIPMAP_MODE_NEAREST,
-                addressModeU: address_u.to_vk(),
-                addressModeV: address_v.to_vk(),
-                addressModeW: vk::SAMPLER_ADDRESS_MODE_CLAMP_TO_EDGE, // unused by the impl
-                mipLodBias: 0.0,
-                anisotropyEnable: vk::FALSE,
-                maxAnisotropy: 1.0,
-                compareEnable: vk::FALSE,
-                compareOp: vk::COMPARE_OP_NEVER,
-                minLod: 0.0,
-                maxLod: 0.0,
-                borderColor: border_color.map(|b| b as u32).unwrap_or(0),
-                unnormalizedCoordinates: vk::TRUE,
-            };
+    /// Returns the filter used to reconstruct the chroma components.
+    #[inline]
+    pub fn chroma_filter(&self) -> Filter {
+        self.chroma_filter
+    }
+}
 
-            let mut output = mem::uninitialized();
-            check_errors(vk.CreateSampler(device.internal_object(),
-                                          &infos,
-                                          ptr::null(),
-                                          &mut output))?;
-            output
-        };
+unsafe impl DeviceOwned for SamplerYcbcrConversion {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for SamplerYcbcrConversion {
+    type Object = vk::SamplerYcbcrConversion;
+
+    const TYPE: vk::DebugReportObjectTypeEXT =
+        vk::DEBUG_REPORT_OBJECT_TYPE_SAMPLER_YCBCR_CONVERSION_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::SamplerYcbcrConversion {
+        self.conversion
+    }
+}
+
+impl fmt::Debug for SamplerYcbcrConversion {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "<Vulkan sampler YCbCr conversion {:?}>", self.conversion)
+    }
+}
+
+impl Drop for SamplerYcbcrConversion {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
 
-        Ok(Arc::new(Sampler {
-                        sampler: sampler,
-                        device: device.clone(),
-                        compare_mode: false,
-                        unnormalized: true,
-                        usable_with_float_formats: match border_color {
-                            Some(BorderColor::FloatTransparentBlack) => true,
-                            Some(BorderColor::FloatOpaqueBlack) => true,
-                            Some(BorderColor::FloatOpaqueWhite) => true,
-                            Some(_) => false,
-                            None => true,
-                        },
-                        usable_with_int_formats: match border_color {
-                            Some(BorderColor::IntTransparentBlack) => true,
-                            Some(BorderColor::IntOpaqueBlack) => true,
-                            Some(BorderColor::IntOpaqueWhite) => true,
-                            Some(_) => false,
-                            None => true,
-                        },
-                        usable_with_swizzling: match border_color {
-                            Some(BorderColor::FloatOpaqueBlack) => false,
-                            Some(BorderColor::IntOpaqueBlack) => false,
-                            _ => true,
-                        },
-                    }))
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySamplerYcbcrConversionKHR(self.device.internal_object(),
+                                                self.conversion,
+                                                ptr::null());
+        }
     }
+}
 
-    /// Returns true if the sampler is a compare-mode sampler.
+/// Prototype of a `SamplerYcbcrConversion`.
+///
+/// Created with `SamplerYcbcrConversion::start`.
+#[derive(Debug, Clone)]
+pub struct SamplerYcbcrConversionBuilder {
+    device: Arc<Device>,
+    format: Format,
+    model: YcbcrModel,
+    range: YcbcrRange,
+    components: Swizzle,
+    chroma_offset: [ChromaLocation; 2],
+    chroma_filter: Filter,
+    force_explicit_reconstruction: bool,
+}
+
+impl SamplerYcbcrConversionBuilder {
+    /// Sets the color model of the source values.
     #[inline]
-    pub fn compare_mode(&self) -> bool {
-        self.compare_mode
+    pub fn model(mut self, model: YcbcrModel) -> SamplerYcbcrConversionBuilder {
+        self.model = model;
+        self
     }
 
-    /// Returns true if the sampler is unnormalized.
+    /// Sets the range of the encoded values.
     #[inline]
-    pub fn is_unnormalized(&self) -> bool {
-        self.unnormalized
+    pub fn range(mut self, range: YcbcrRange) -> SamplerYcbcrConversionBuilder {
+        self.range = range;
+        self
     }
 
-    /// Returns true if the sampler can be used with floating-point image views. See the
-    /// documentation of the `sampler` module for more info.
+    /// Sets the swizzling applied to the components before the conversion.
     #[inline]
-    pub fn usable_with_float_formats(&self) -> bool {
-        self.usable_with_float_formats
+    pub fn components(mut self, components: Swizzle) -> SamplerYcbcrConversionBuilder {
+        self.components = components;
+        self
     }
 
-    /// Returns true if the sampler can be used with integer image views. See the documentation of
-    /// the `sampler` module for more info.
+    /// Sets the location of the downsampled chroma samples on the X and Y axes.
     #[inline]
-    pub fn usable_with_int_formats(&self) -> bool {
-        self.usable_with_int_formats
+    pub fn chroma_offset(mut self, x: ChromaLocation, y: ChromaLocation)
+                         -> SamplerYcbcrConversionBuilder {
+        self.chroma_offset = [x, y];
+        self
     }
 
-    /// Returns true if the sampler can be used with image views that have non-identity swizzling.
-    /// See the documentation of the `sampler` module for more info.
+    /// Sets the filter used to reconstruct the chroma components.
     #[inline]
-    pub fn usable_with_swizzling(&self) -> bool {
-        self.usable_with_swizzling
+    pub fn chroma_filter(mut self, filter: Filter) -> SamplerYcbcrConversionBuilder {
+        self.chroma_filter = filter;
+        self
     }
-}
 
-unsafe impl DeviceOwned for Sampler {
+    /// If true, the chroma reconstruction is always done explicitly by the implementation.
     #[inline]
-    fn device(&self) -> &Arc<Device> {
-        &self.device
+    pub fn force_explicit_reconstruction(mut self, force: bool)
+                                         -> SamplerYcbcrConversionBuilder {
+        self.force_explicit_reconstruction = force;
+        self
     }
-}
 
-unsafe impl VulkanObject for Sampler {
-    type Object = vk::Sampler;
+    /// Builds the conversion.
+    pub fn build(self)
+                 -> Result<Arc<SamplerYcbcrConversion>, SamplerYcbcrConversionCreationError> {
+        let device = self.device;
 
-    const TYPE: vk::DebugReportObjectTypeEXT = vk::DEBUG_REPORT_OBJECT_TYPE_SAMPLER_EXT;
+        if !device.loaded_extensions().khr_sampler_ycbcr_conversion {
+            return Err(SamplerYcbcrConversionCreationError::ExtensionNotEnabled);
+        }
 
-    #[inline]
-    fn internal_object(&self) -> vk::Sampler {
-        self.sampler
+        // The features of both tilings are accepted, since the conversion doesn't know the
+        // tiling of the images it will be used with.
+        let features = {
+            let properties = device.physical_device().format_properties(self.format);
+            let linear = properties.linear_tiling_features;
+            let optimal = properties.optimal_tiling_features;
+            FormatFeatures {
+                midpoint_chroma_samples: linear.midpoint_chroma_samples ||
+                    optimal.midpoint_chroma_samples,
+                cosited_chroma_samples: linear.cosited_chroma_samples ||
+                    optimal.cosited_chroma_samples,
+                ycbcr_conversion_linear_filter: linear.ycbcr_conversion_linear_filter ||
+                    optimal.ycbcr_conversion_linear_filter,
+                ..FormatFeatures::none()
+            }
+        };
+
+        if !features.midpoint_chroma_samples && !features.cosited_chroma_samples {
+            return Err(SamplerYcbcrConversionCreationError::FormatNotSupported);
+        }
+
+        for &location in self.chroma_offset.iter() {
+            let supported = match location {
+                ChromaLocation::CositedEven => features.cosited_chroma_samples,
+                ChromaLocation::Midpoint => features.midpoint_chroma_samples,
+            };
+
+            if !supported {
+                return Err(SamplerYcbcrConversionCreationError::ChromaLocationNotSupported);
+            }
+        }
+
+        if self.chroma_filter == Filter::Linear && !features.ycbcr_conversion_linear_filter {
+            return Err(SamplerYcbcrConversionCreationError::LinearChromaFilterNotSupported);
+        }
+
+        let vk = device.pointers();
+        let conversion = unsafe {
+            let infos = vk::SamplerYcbcrConversionCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_CREATE_INFO,
+                pNext: ptr::null(),
+                format: self.format as u32,
+                ycbcrModel: self.model as u32,
+                ycbcrRange: self.range as u32,
+                components: self.components.into_vulkan_mapping(),
+                xChromaOffset: self.chroma_offset[0] as u32,
+                yChromaOffset: self.chroma_offset[1] as u32,
+                chromaFilter: self.chroma_filter as u32,
+                forceExplicitReconstruction: if self.force_explicit_reconstruction {
+                    vk::TRUE
+                } else {
+                    vk::FALSE
+                },
+            };
+
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateSamplerYcbcrConversionKHR(device.internal_object(),
+                                                            &infos,
+                                                            ptr::null(),
+                                                            &mut output))?;
+            output
+        };
+
+        Ok(Arc::new(SamplerYcbcrConversion {
+                        conversion: conversion,
+                        device: device.clone(),
+                        format: self.format,
+                        chroma_filter: self.chroma_filter,
+                        needs_destruction: true,
+                    }))
     }
 }
 
-impl fmt::Debug for Sampler {
+/// The color model of the values of an image that uses a `SamplerYcbcrConversion`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum YcbcrModel {
+    /// The values are already RGB and are only range-expanded.
+    RgbIdentity = vk::SAMPLER_YCBCR_MODEL_CONVERSION_RGB_IDENTITY,
+    /// The values are YCbCr and are only range-expanded, without any color model conversion.
+    YcbcrIdentity = vk::SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_IDENTITY,
+    /// The values are converted from the BT.709 color model.
+    Ycbcr709 = vk::SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_709,
+    /// The values are converted from the BT.601 color model.
+    Ycbcr601 = vk::SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_601,
+    /// The values are converted from the BT.2020 color model.
+    Ycbcr2020 = vk::SAMPLER_YCBCR_MODEL_CONVERSION_YCBCR_2020,
+}
+
+/// The range of the encoded values of an image that uses a `SamplerYcbcrConversion`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum YcbcrRange {
+    /// The values use the full range of the encoding.
+    ItuFull = vk::SAMPLER_YCBCR_RANGE_ITU_FULL,
+    /// The values use the headroom and footroom reserved by the ITU standards.
+    ItuNarrow = vk::SAMPLER_YCBCR_RANGE_ITU_NARROW,
+}
+
+/// Location of the downsampled chroma samples relative to the luma samples.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ChromaLocation {
+    /// The chroma samples are aligned with the luma samples with an even coordinate.
+    CositedEven = vk::CHROMA_LOCATION_COSITED_EVEN,
+    /// The chroma samples are located halfway between the luma samples.
+    Midpoint = vk::CHROMA_LOCATION_MIDPOINT,
+}
+
+/// Error that can happen when creating a `SamplerYcbcrConversion`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SamplerYcbcrConversionCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The `VK_KHR_sampler_ycbcr_conversion` extension must be enabled when creating the device.
+    ExtensionNotEnabled,
+
+    /// The format doesn't support YCbCr conversions.
+    FormatNotSupported,
+
+    /// One of the chroma offsets isn't supported by the format.
+    ChromaLocationNotSupported,
+
+    /// The format doesn't support linear filtering of the chroma components.
+    LinearChromaFilterNotSupported,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for SamplerYcbcrConversionCreationError {
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "<Vulkan sampler {:?}>", self.sampler)
+    fn description(&self) -> &str {
+        match *self {
+            SamplerYcbcrConversionCreationError::OomError(_) => "not enough memory available",
+            SamplerYcbcrConversionCreationError::ExtensionNotEnabled =>
+                "the device extension `VK_KHR_sampler_ycbcr_conversion` is not enabled",
+            SamplerYcbcrConversionCreationError::FormatNotSupported =>
+                "the format doesn't support YCbCr conversions",
+            SamplerYcbcrConversionCreationError::ChromaLocationNotSupported =>
+                "one of the chroma offsets isn't supported by the format",
+            SamplerYcbcrConversionCreationError::LinearChromaFilterNotSupported =>
+                "the format doesn't support linear filtering of the chroma components",
+            SamplerYcbcrConversionCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
     }
-}
 
-impl Drop for Sampler {
     #[inline]
-    fn drop(&mut self) {
-        unsafe {
-            let vk = self.device.pointers();
-            vk.DestroySampler(self.device.internal_object(), self.sampler, ptr::null());
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            SamplerYcbcrConversionCreationError::OomError(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
+vk_error_conversions!(SamplerYcbcrConversionCreationError, OomError {});
+
 /// Describes how the color of each pixel should be determined.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -611,10 +1315,12 @@ impl UnnormalizedSamplerAddressMode {
     }
 
     #[inline]
-    fn border_color(self) -> Option<BorderColor> {
+    fn to_normalized(self) -> SamplerAddressMode {
         match self {
-            UnnormalizedSamplerAddressMode::ClampToEdge => None,
-            UnnormalizedSamplerAddressMode::ClampToBorder(c) => Some(c),
+            UnnormalizedSamplerAddressMode::ClampToEdge => SamplerAddressMode::ClampToEdge,
+            UnnormalizedSamplerAddressMode::ClampToBorder(c) => {
+                SamplerAddressMode::ClampToBorder(c)
+            },
         }
     }
 }
@@ -646,6 +1352,60 @@ pub enum BorderColor {
 
     /// The value `(1, 1, 1, 1)`. Can only be used with integer images.
     IntOpaqueWhite = vk::BORDER_COLOR_INT_OPAQUE_WHITE,
+
+    /// The floating-point value passed to `SamplerBuilder::custom_border_color`. Can only be used
+    /// with floating-point images.
+    ///
+    /// Requires the `ext_custom_border_color` extension to be enabled on the device.
+    FloatCustom = vk::BORDER_COLOR_FLOAT_CUSTOM_EXT,
+
+    /// The integer value passed to `SamplerBuilder::custom_border_color`. Can only be used with
+    /// integer images.
+    ///
+    /// Requires the `ext_custom_border_color` extension to be enabled on the device.
+    IntCustom = vk::BORDER_COLOR_INT_CUSTOM_EXT,
+}
+
+/// Properties of a physical device related to custom border colors.
+///
+/// Only meaningful if the physical device supports the `ext_custom_border_color` extension.
+#[derive(Copy, Clone, Debug)]
+pub struct CustomBorderColorProperties {
+    /// Maximum number of samplers with a custom border color that can exist simultaneously on a
+    /// device.
+    pub max_custom_border_color_samplers: u32,
+}
+
+impl CustomBorderColorProperties {
+    /// Queries the properties of a physical device.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the instance uses Vulkan 1.0 and the `khr_get_physical_device_properties2`
+    ///   extension isn't loaded.
+    ///
+    pub fn from_physical_device(physical_device: PhysicalDevice) -> CustomBorderColorProperties {
+        unsafe {
+            let mut output: vk::PhysicalDeviceCustomBorderColorPropertiesEXT = mem::zeroed();
+            output.sType = vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_CUSTOM_BORDER_COLOR_PROPERTIES_EXT;
+            physical_device.query_properties2(&mut output as *mut _ as *mut _);
+
+            CustomBorderColorProperties {
+                max_custom_border_color_samplers: output.maxCustomBorderColorSamplers,
+            }
+        }
+    }
+}
+
+/// The value of a custom border color.
+///
+/// Used with `BorderColor::FloatCustom` and `BorderColor::IntCustom`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CustomBorderColor {
+    /// Value for `BorderColor::FloatCustom`.
+    Float([f32; 4]),
+    /// Value for `BorderColor::IntCustom`.
+    Int([i32; 4]),
 }
 
 /// Error that can happen when creating an instance.
@@ -681,6 +1441,30 @@ pub enum SamplerCreationError {
     /// Using `MirrorClampToEdge` requires enabling the `VK_KHR_sampler_mirror_clamp_to_edge`
     /// extension when creating the device.
     SamplerMirrorClampToEdgeExtensionNotEnabled,
+
+    /// A sampler with unnormalized coordinates must use the same magnification and minification
+    /// filters, the nearest mipmap mode, a level of detail of 0, the `ClampToEdge` or
+    /// `ClampToBorder` address modes, and neither anisotropy nor comparison.
+    UnnormalizedCoordinatesRestrictionsViolated,
+
+    /// Using a custom border color requires enabling the `VK_EXT_custom_border_color` extension
+    /// when creating the device.
+    CustomBorderColorExtensionNotEnabled,
+
+    /// There are already `maxCustomBorderColorSamplers` samplers with a custom border color alive
+    /// on the device.
+    TooManyCustomBorderColorSamplers {
+        /// The value of `maxCustomBorderColorSamplers`.
+        max: u32,
+    },
+
+    /// Samplers that use a YCbCr conversion must use the `ClampToEdge` address mode, normalized
+    /// coordinates, no anisotropic filtering, and minification and magnification filters equal
+    /// to the chroma filter of the conversion.
+    YcbcrConversionRestrictionsViolated,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for SamplerCreationError {
@@ -695,6 +1479,16 @@ impl error::Error for SamplerCreationError {
             SamplerCreationError::MipLodBiasLimitExceeded { .. } => "mip lod bias limit exceeded",
             SamplerCreationError::SamplerMirrorClampToEdgeExtensionNotEnabled =>
                 "the device extension `VK_KHR_sampler_mirror_clamp_to_edge` is not enabled",
+            SamplerCreationError::UnnormalizedCoordinatesRestrictionsViolated =>
+                "the parameters of the sampler are incompatible with unnormalized coordinates",
+            SamplerCreationError::CustomBorderColorExtensionNotEnabled =>
+                "the device extension `VK_EXT_custom_border_color` is not enabled",
+            SamplerCreationError::TooManyCustomBorderColorSamplers { .. } =>
+                "too many simultaneous samplers with a custom border color",
+            SamplerCreationError::YcbcrConversionRestrictionsViolated =>
+                "the sampler doesn't respect the restrictions of YCbCr conversions",
+            SamplerCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -707,34 +1501,14 @@ impl error::Error for SamplerCreationError {
     }
 }
 
-impl fmt::Display for SamplerCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for SamplerCreationError {
-    #[inline]
-    fn from(err: OomError) -> SamplerCreationError {
-        SamplerCreationError::OomError(err)
-    }
-}
-
-impl From<Error> for SamplerCreationError {
-    #[inline]
-    fn from(err: Error) -> SamplerCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => SamplerCreationError::OomError(OomError::from(err)),
-            err @ Error::OutOfDeviceMemory => SamplerCreationError::OomError(OomError::from(err)),
-            Error::TooManyObjects => SamplerCreationError::TooManyObjects,
-            _ => panic!("unexpected error: {:?}", err),
-        }
-    }
-}
+vk_error_conversions!(SamplerCreationError, OomError {
+    TooManyObjects => TooManyObjects,
+});
 
 #[cfg(test)]
 mod tests {
+    use VulkanObject;
+    use format::Format;
     use sampler;
 
     #[test]
@@ -953,4 +1727,242 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn builder_defaults() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let s = sampler::Sampler::start(device).build().unwrap();
+        assert!(!s.compare_mode());
+        assert!(!s.is_unnormalized());
+    }
+
+    #[test]
+    fn builder_compare() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let s = sampler::Sampler::start(device)
+            .filter(sampler::Filter::Linear)
+            .compare(sampler::Compare::LessOrEqual)
+            .build()
+            .unwrap();
+        assert!(s.compare_mode());
+        assert!(!s.usable_with_int_formats());
+    }
+
+    #[test]
+    fn builder_unnormalized_restrictions() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let s = sampler::Sampler::start(device.clone())
+            .unnormalized_coordinates()
+            .build()
+            .unwrap();
+        assert!(s.is_unnormalized());
+
+        let r = sampler::Sampler::start(device)
+            .unnormalized_coordinates()
+            .address_mode(sampler::SamplerAddressMode::Repeat)
+            .build();
+        match r {
+            Err(sampler::SamplerCreationError::UnnormalizedCoordinatesRestrictionsViolated) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn builder_checks() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let r = sampler::Sampler::start(device.clone())
+            .max_anisotropy(2.0)
+            .build();
+        match r {
+            Err(sampler::SamplerCreationError::SamplerAnisotropyFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        let r = sampler::Sampler::start(device.clone())
+            .address_mode_v(sampler::SamplerAddressMode::MirrorClampToEdge)
+            .build();
+        match r {
+            Err(sampler::SamplerCreationError::SamplerMirrorClampToEdgeExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        assert_should_panic!({
+            let _ = sampler::Sampler::start(device).lod(2.0, 1.0).build();
+        });
+    }
+
+    #[test]
+    fn custom_border_color_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let border = sampler::BorderColor::FloatCustom;
+        let r = sampler::Sampler::start(device.clone())
+            .address_mode(sampler::SamplerAddressMode::ClampToBorder(border))
+            .custom_border_color(sampler::CustomBorderColor::Float([0.5, 0.0, 0.5, 1.0]),
+                                 Format::R8G8B8A8Unorm)
+            .build();
+
+        match r {
+            Err(sampler::SamplerCreationError::CustomBorderColorExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+
+        assert_eq!(*device.custom_border_color_sampler_count().lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn custom_border_color_ignored() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // The value is ignored if no address mode uses a custom border color, so the extension
+        // isn't required.
+        let border = sampler::BorderColor::FloatOpaqueBlack;
+        let s = sampler::Sampler::start(device.clone())
+            .address_mode(sampler::SamplerAddressMode::ClampToBorder(border))
+            .custom_border_color(sampler::CustomBorderColor::Float([0.5, 0.0, 0.5, 1.0]),
+                                 Format::R8G8B8A8Unorm)
+            .build()
+            .unwrap();
+
+        assert!(s.usable_with_float_formats());
+        assert_eq!(*device.custom_border_color_sampler_count().lock().unwrap(), 0);
+    }
+
+    #[test]
+    fn custom_border_color_missing() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let border = sampler::BorderColor::IntCustom;
+        assert_should_panic!("No custom border color of the right kind was provided", {
+            let _ = sampler::Sampler::start(device)
+                .address_mode(sampler::SamplerAddressMode::ClampToBorder(border))
+                .build();
+        });
+    }
+
+    #[test]
+    fn custom_border_color_wrong_kind() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let border = sampler::BorderColor::IntCustom;
+        assert_should_panic!("No custom border color of the right kind was provided", {
+            let _ = sampler::Sampler::start(device)
+                .address_mode(sampler::SamplerAddressMode::ClampToBorder(border))
+                .custom_border_color(sampler::CustomBorderColor::Float([0.5, 0.0, 0.5, 1.0]),
+                                     Format::R8G8B8A8Unorm)
+                .build();
+        });
+    }
+
+    #[test]
+    fn ycbcr_conversion_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let r = sampler::SamplerYcbcrConversion::start(device, Format::R8G8B8A8Unorm)
+            .build();
+
+        match r {
+            Err(sampler::SamplerYcbcrConversionCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn ycbcr_conversion_format_not_supported() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_sampler_ycbcr_conversion]);
+
+        let r = sampler::SamplerYcbcrConversion::start(device, Format::R8G8B8A8Unorm)
+            .build();
+
+        match r {
+            Err(sampler::SamplerYcbcrConversionCreationError::FormatNotSupported) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn ycbcr_conversion_sampler_restrictions() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_sampler_ycbcr_conversion]);
+
+        let format = Format::G8_B8_R8_3Plane420Unorm;
+        let conversion = match sampler::SamplerYcbcrConversion::start(device.clone(), format)
+            .build()
+        {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let s = sampler::Sampler::start(device.clone())
+            .address_mode(sampler::SamplerAddressMode::ClampToEdge)
+            .ycbcr_conversion(conversion.clone())
+            .build()
+            .unwrap();
+        assert!(s.ycbcr_conversion().is_some());
+
+        let r = sampler::Sampler::start(device.clone())
+            .address_mode(sampler::SamplerAddressMode::Repeat)
+            .ycbcr_conversion(conversion.clone())
+            .build();
+        match r {
+            Err(sampler::SamplerCreationError::YcbcrConversionRestrictionsViolated) => (),
+            _ => panic!(),
+        }
+
+        let r = sampler::Sampler::start(device)
+            .address_mode(sampler::SamplerAddressMode::ClampToEdge)
+            .filter(sampler::Filter::Linear)
+            .ycbcr_conversion(conversion)
+            .build();
+        match r {
+            Err(sampler::SamplerCreationError::YcbcrConversionRestrictionsViolated) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let border = sampler::BorderColor::IntOpaqueBlack;
+        let s = sampler::Sampler::start(device.clone())
+            .address_mode(sampler::SamplerAddressMode::ClampToBorder(border))
+            .build()
+            .unwrap();
+
+        let borrowed = unsafe {
+            sampler::Sampler::from_raw(device, s.internal_object(), false, false, Some(border),
+                                       false)
+        };
+        assert_eq!(borrowed.internal_object(), s.internal_object());
+        assert!(!borrowed.usable_with_float_formats());
+        assert!(borrowed.usable_with_int_formats());
+        assert!(!borrowed.usable_with_swizzling());
+    }
+
+    #[test]
+    fn ycbcr_conversion_from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_sampler_ycbcr_conversion]);
+
+        let format = Format::G8_B8_R8_3Plane420Unorm;
+        let conversion = match sampler::SamplerYcbcrConversion::start(device.clone(), format)
+            .build()
+        {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let borrowed = unsafe {
+            sampler::SamplerYcbcrConversion::from_raw(device,
+                                                      conversion.internal_object(),
+                                                      format,
+                                                      conversion.chroma_filter(),
+                                                      false)
+        };
+        assert_eq!(borrowed.internal_object(), conversion.internal_object());
+        assert_eq!(borrowed.format(), format);
+    }
 }
@@ -57,6 +57,104 @@ pub struct Capabilities {
     pub present_modes: SupportedPresentModes,
 }
 
+impl Capabilities {
+    /// Returns the dimensions that a swapchain should have, given the dimensions that you would
+    /// like it to have (for example the dimensions of the window).
+    ///
+    /// If the surface has a current extent, it is returned. Otherwise the desired dimensions are
+    /// clamped between `min_image_extent` and `max_image_extent`.
+    #[inline]
+    pub fn clamp_extent(&self, desired: [u32; 2]) -> [u32; 2] {
+        if let Some(current) = self.current_extent {
+            return current;
+        }
+
+        [
+            clamp(desired[0], self.min_image_extent[0], self.max_image_extent[0]),
+            clamp(desired[1], self.min_image_extent[1], self.max_image_extent[1]),
+        ]
+    }
+
+    /// Returns the number of images that a swapchain should have, given the number of images
+    /// that you would like it to have.
+    ///
+    /// The desired number is clamped between `min_image_count` and `max_image_count`.
+    #[inline]
+    pub fn clamp_image_count(&self, desired: u32) -> u32 {
+        let max = self.max_image_count.unwrap_or(u32::max_value());
+        clamp(desired, self.min_image_count, max)
+    }
+
+    /// Chooses a format and a color space among `supported_formats`.
+    ///
+    /// Only the formats in the `SrgbNonLinear` color space are considered, as it is the only one
+    /// that is always supported. If `srgb` is true, `B8G8R8A8Srgb` is preferred, then any other
+    /// sRGB format. If `srgb` is false, `B8G8R8A8Unorm` is preferred, then any other non-sRGB
+    /// format. If no format matches, the first supported format is returned.
+    ///
+    /// Returns `None` only if `supported_formats` is empty.
+    pub fn choose_format(&self, srgb: bool) -> Option<(Format, ColorSpace)> {
+        let preferred = if srgb {
+            Format::B8G8R8A8Srgb
+        } else {
+            Format::B8G8R8A8Unorm
+        };
+
+        let candidates = || {
+            self.supported_formats
+                .iter()
+                .cloned()
+                .filter(|&(_, c)| c == ColorSpace::SrgbNonLinear)
+        };
+
+        candidates()
+            .find(|&(f, _)| f == preferred)
+            .or_else(|| candidates().find(|&(f, _)| is_srgb(f) == srgb))
+            .or_else(|| self.supported_formats.first().cloned())
+    }
+
+    /// Returns the first transform of `preferred` that is supported, or `current_transform` if
+    /// none of them is.
+    ///
+    /// Using the current transform avoids an additional transformation by the presentation
+    /// engine, but you have to take it into account when rendering. For example on a rotated
+    /// display you have to rotate what you draw. Pass `SurfaceTransform::Identity` in `preferred`
+    /// to let the presentation engine handle the rotation instead, if it supports it.
+    pub fn choose_transform<I>(&self, preferred: I) -> SurfaceTransform
+        where I: IntoIterator<Item = SurfaceTransform>
+    {
+        self.supported_transforms
+            .choose(preferred)
+            .unwrap_or(self.current_transform)
+    }
+}
+
+// Returns true if `format` is one of the uncompressed sRGB formats.
+#[inline]
+fn is_srgb(format: Format) -> bool {
+    match format {
+        Format::R8Srgb |
+        Format::R8G8Srgb |
+        Format::R8G8B8Srgb |
+        Format::B8G8R8Srgb |
+        Format::R8G8B8A8Srgb |
+        Format::B8G8R8A8Srgb |
+        Format::A8B8G8R8SrgbPack32 => true,
+        _ => false,
+    }
+}
+
+#[inline]
+fn clamp(value: u32, min: u32, max: u32) -> u32 {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
 /// The way presenting a swapchain is accomplished.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
@@ -132,6 +230,32 @@ impl SupportedPresentModes {
         }
     }
 
+    /// Returns the first mode of `preferred` that is supported, or `PresentMode::Fifo` if none
+    /// of them is.
+    ///
+    /// Since `Fifo` is always supported, the returned mode can always be passed when creating a
+    /// swapchain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::swapchain::PresentMode;
+    /// # use vulkano::swapchain::SupportedPresentModes;
+    /// # let modes = SupportedPresentModes { fifo: true, .. SupportedPresentModes::none() };
+    ///
+    /// // Low latency if possible, without tearing.
+    /// let mode = modes.choose(vec![PresentMode::Mailbox]);
+    /// # assert_eq!(mode, PresentMode::Fifo);
+    /// ```
+    pub fn choose<I>(&self, preferred: I) -> PresentMode
+        where I: IntoIterator<Item = PresentMode>
+    {
+        preferred
+            .into_iter()
+            .find(|&mode| self.supports(mode))
+            .unwrap_or(PresentMode::Fifo)
+    }
+
     /// Returns an iterator to the list of supported present modes.
     #[inline]
     pub fn iter(&self) -> SupportedPresentModesIter {
@@ -264,6 +388,31 @@ impl SupportedCompositeAlpha {
         }
     }
 
+    /// Returns the first mode of `preferred` that is supported, or the first supported mode if
+    /// none of them is.
+    ///
+    /// Returns `None` only if the list is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use vulkano::swapchain::CompositeAlpha;
+    /// # use vulkano::swapchain::SupportedCompositeAlpha;
+    /// # let alpha = SupportedCompositeAlpha { opaque: true, .. SupportedCompositeAlpha::none() };
+    ///
+    /// // Transparent window if possible.
+    /// let alpha = alpha.choose(vec![CompositeAlpha::PreMultiplied, CompositeAlpha::PostMultiplied]);
+    /// # assert_eq!(alpha, Some(CompositeAlpha::Opaque));
+    /// ```
+    pub fn choose<I>(&self, preferred: I) -> Option<CompositeAlpha>
+        where I: IntoIterator<Item = CompositeAlpha>
+    {
+        preferred
+            .into_iter()
+            .find(|&alpha| self.supports(alpha))
+            .or_else(|| self.iter().next())
+    }
+
     /// Returns an iterator to the list of supported composite alpha.
     #[inline]
     pub fn iter(&self) -> SupportedCompositeAlphaIter {
@@ -392,6 +541,17 @@ impl SupportedSurfaceTransforms {
         }
     }
 
+    /// Returns the first transform of `preferred` that is supported, or `None` if none of them
+    /// is.
+    ///
+    /// See also `Capabilities::choose_transform`, which falls back to the current transform.
+    #[inline]
+    pub fn choose<I>(&self, preferred: I) -> Option<SurfaceTransform>
+        where I: IntoIterator<Item = SurfaceTransform>
+    {
+        preferred.into_iter().find(|&transform| self.supports(transform))
+    }
+
     /// Returns an iterator to the list of supported composite alpha.
     #[inline]
     pub fn iter(&self) -> SupportedSurfaceTransformsIter {
@@ -542,6 +702,17 @@ impl Default for SurfaceTransform {
 /// Additionally you can try detect whether the implementation supports any additional color space
 /// and perform a manual conversion to that color space from inside your shader.
 ///
+/// ## HDR
+///
+/// The color spaces other than `SrgbNonLinear` are only reported by the surface if the
+/// `ext_swapchain_colorspace` instance extension is enabled. Among them, the color spaces for
+/// which `is_hdr` returns true can represent colors brighter than the sRGB white. For example
+/// `Hdr10St2084` is usually used with the `A2B10G10R10UnormPack32` format, and
+/// `ExtendedSrgbLinear` (also known as scRGB) with the `R16G16B16A16Sfloat` format.
+///
+/// When using an HDR color space, you can describe the content of the images to the display with
+/// `Swapchain::set_hdr_metadata`.
+///
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum ColorSpace {
@@ -559,11 +730,29 @@ pub enum ColorSpace {
     AdobeRgbLinear = vk::COLOR_SPACE_ADOBERGB_LINEAR_EXT,
     AdobeRgbNonLinear = vk::COLOR_SPACE_ADOBERGB_NONLINEAR_EXT,
     PassThrough = vk::COLOR_SPACE_PASS_THROUGH_EXT,
+    ExtendedSrgbNonLinear = vk::COLOR_SPACE_EXTENDED_SRGB_NONLINEAR_EXT,
+}
+
+impl ColorSpace {
+    /// Returns true if the color space can represent colors outside of the range of sRGB, in
+    /// other words if it is meant to be used with HDR displays.
+    #[inline]
+    pub fn is_hdr(&self) -> bool {
+        match *self {
+            ColorSpace::ExtendedSrgbLinear |
+            ColorSpace::ExtendedSrgbNonLinear |
+            ColorSpace::Hdr10St2084 |
+            ColorSpace::Hdr10Hlg |
+            ColorSpace::DolbyVision => true,
+            _ => false,
+        }
+    }
 }
 
+/// Returns `None` if the color space is unknown to vulkano.
 #[inline]
-pub fn color_space_from_num(val: u32) -> ColorSpace {
-    match val {
+pub fn color_space_from_num(val: u32) -> Option<ColorSpace> {
+    Some(match val {
         vk::COLOR_SPACE_SRGB_NONLINEAR_KHR => ColorSpace::SrgbNonLinear,
         vk::COLOR_SPACE_DISPLAY_P3_NONLINEAR_EXT => ColorSpace::DisplayP3NonLinear,
         vk::COLOR_SPACE_EXTENDED_SRGB_LINEAR_EXT => ColorSpace::ExtendedSrgbLinear,
@@ -578,6 +767,135 @@ pub fn color_space_from_num(val: u32) -> ColorSpace {
         vk::COLOR_SPACE_ADOBERGB_LINEAR_EXT => ColorSpace::AdobeRgbLinear,
         vk::COLOR_SPACE_ADOBERGB_NONLINEAR_EXT => ColorSpace::AdobeRgbNonLinear,
         vk::COLOR_SPACE_PASS_THROUGH_EXT => ColorSpace::PassThrough,
-        _ => panic!("Wrong value for color space enum"),
+        vk::COLOR_SPACE_EXTENDED_SRGB_NONLINEAR_EXT => ColorSpace::ExtendedSrgbNonLinear,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use image::ImageUsage;
+    use swapchain::Capabilities;
+    use swapchain::ColorSpace;
+    use swapchain::CompositeAlpha;
+    use swapchain::PresentMode;
+    use swapchain::SupportedCompositeAlpha;
+    use swapchain::SupportedPresentModes;
+    use swapchain::SupportedSurfaceTransforms;
+    use swapchain::SurfaceTransform;
+
+    fn capabilities(current_extent: Option<[u32; 2]>) -> Capabilities {
+        Capabilities {
+            min_image_count: 2,
+            max_image_count: Some(4),
+            current_extent: current_extent,
+            min_image_extent: [16, 16],
+            max_image_extent: [4096, 4096],
+            max_image_array_layers: 1,
+            supported_transforms: SupportedSurfaceTransforms::none(),
+            current_transform: SurfaceTransform::Identity,
+            supported_composite_alpha: SupportedCompositeAlpha::none(),
+            supported_usage_flags: ImageUsage::none(),
+            supported_formats: vec![(Format::B8G8R8A8Srgb, ColorSpace::SrgbNonLinear)],
+            present_modes: SupportedPresentModes::none(),
+        }
+    }
+
+    #[test]
+    fn clamp_extent() {
+        let caps = capabilities(None);
+        assert_eq!(caps.clamp_extent([8, 800]), [16, 800]);
+        assert_eq!(caps.clamp_extent([8000, 600]), [4096, 600]);
+
+        let caps = capabilities(Some([1024, 768]));
+        assert_eq!(caps.clamp_extent([800, 600]), [1024, 768]);
+    }
+
+    #[test]
+    fn choose_present_mode() {
+        let modes = SupportedPresentModes {
+            immediate: true,
+            fifo: true,
+            ..SupportedPresentModes::none()
+        };
+
+        assert_eq!(modes.choose(vec![PresentMode::Mailbox, PresentMode::Immediate]),
+                   PresentMode::Immediate);
+        assert_eq!(modes.choose(vec![PresentMode::Relaxed]), PresentMode::Fifo);
+        assert_eq!(modes.choose(None), PresentMode::Fifo);
+    }
+
+    #[test]
+    fn clamp_image_count() {
+        let mut caps = capabilities(None);
+        assert_eq!(caps.clamp_image_count(1), 2);
+        assert_eq!(caps.clamp_image_count(3), 3);
+        assert_eq!(caps.clamp_image_count(8), 4);
+
+        caps.max_image_count = None;
+        assert_eq!(caps.clamp_image_count(8), 8);
+    }
+
+    #[test]
+    fn choose_format() {
+        let mut caps = capabilities(None);
+        caps.supported_formats = vec![
+            (Format::R8G8B8A8Unorm, ColorSpace::SrgbNonLinear),
+            (Format::B8G8R8A8Srgb, ColorSpace::DisplayP3NonLinear),
+            (Format::R8G8B8A8Srgb, ColorSpace::SrgbNonLinear),
+        ];
+
+        assert_eq!(caps.choose_format(true),
+                   Some((Format::R8G8B8A8Srgb, ColorSpace::SrgbNonLinear)));
+        assert_eq!(caps.choose_format(false),
+                   Some((Format::R8G8B8A8Unorm, ColorSpace::SrgbNonLinear)));
+
+        caps.supported_formats = vec![(Format::B8G8R8A8Srgb, ColorSpace::DisplayP3NonLinear)];
+        assert_eq!(caps.choose_format(true),
+                   Some((Format::B8G8R8A8Srgb, ColorSpace::DisplayP3NonLinear)));
+
+        caps.supported_formats = vec![];
+        assert_eq!(caps.choose_format(true), None);
+    }
+
+    #[test]
+    fn hdr_color_spaces() {
+        assert!(!ColorSpace::SrgbNonLinear.is_hdr());
+        assert!(!ColorSpace::DisplayP3NonLinear.is_hdr());
+        assert!(ColorSpace::Hdr10St2084.is_hdr());
+        assert!(ColorSpace::ExtendedSrgbLinear.is_hdr());
+    }
+
+    #[test]
+    fn choose_transform() {
+        let mut caps = capabilities(None);
+        caps.current_transform = SurfaceTransform::Rotate90;
+        caps.supported_transforms = SupportedSurfaceTransforms {
+            identity: true,
+            rotate90: true,
+            ..SupportedSurfaceTransforms::none()
+        };
+
+        assert_eq!(caps.choose_transform(Some(SurfaceTransform::Identity)),
+                   SurfaceTransform::Identity);
+        assert_eq!(caps.choose_transform(Some(SurfaceTransform::Rotate180)),
+                   SurfaceTransform::Rotate90);
+        assert_eq!(caps.choose_transform(None), SurfaceTransform::Rotate90);
+    }
+
+    #[test]
+    fn choose_composite_alpha() {
+        let alpha = SupportedCompositeAlpha {
+            opaque: true,
+            pre_multiplied: true,
+            ..SupportedCompositeAlpha::none()
+        };
+
+        assert_eq!(alpha.choose(vec![CompositeAlpha::PostMultiplied,
+                                     CompositeAlpha::PreMultiplied]),
+                   Some(CompositeAlpha::PreMultiplied));
+        assert_eq!(alpha.choose(Some(CompositeAlpha::Inherit)), Some(CompositeAlpha::Opaque));
+        assert_eq!(SupportedCompositeAlpha::none().choose(None), None);
     }
 }
@@ -9,8 +9,9 @@
 
 //! Allows you to create surfaces that fill a whole display, outside of the windowing system.
 //!
-//! **As far as the author knows, no existing device supports these features. Therefore the code
-//! here is mostly a draft and needs rework in both the API and the implementation.**
+//! This is useful on systems that don't have a windowing system, for example embedded devices or
+//! headless machines that are connected to a monitor. The `VK_KHR_display` instance extension
+//! must be enabled.
 //!
 //! The purpose of the objects in this module is to let you create a `Surface` object that
 //! represents a location on the screen. This is done in four steps:
@@ -20,15 +21,38 @@
 //!   `Display::enumerate`.
 //! - Choose a `DisplayMode`, which is the combination of a display, a resolution and a refresh
 //!   rate. You can enumerate the modes available on a display with `Display::display_modes`, or
-//!   attempt to create your own mode with `TODO`.
-//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion.
-//! - Create a `Surface` object with `Surface::from_display_mode` and pass the chosen `DisplayMode`
-//!   and `DisplayPlane`.
+//!   attempt to create your own mode with `DisplayMode::new`.
+//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion. Use
+//!   `DisplayPlane::supports` to check whether a plane can be used with a display, and
+//!   `DisplayPlane::capabilities` to query what the plane supports with a given mode.
+//! - Create a `Surface` object with `Surface::from_display_mode` or
+//!   `Surface::from_display_plane` and pass the chosen `DisplayMode` and `DisplayPlane`.
+//!
+//! The surface can then be used to create a swapchain like any other surface.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use vulkano::swapchain::Surface;
+//! use vulkano::swapchain::display::Display;
+//! use vulkano::swapchain::display::DisplayPlane;
+//! # let physical: vulkano::instance::PhysicalDevice = return;
+//!
+//! let display = Display::enumerate(physical).next().expect("no display available");
+//! let mode = display.display_modes().next().expect("no display mode available");
+//! let plane = DisplayPlane::enumerate(physical)
+//!     .find(|plane| plane.supports(&display))
+//!     .expect("no plane supports the display");
+//!
+//! let surface = Surface::<()>::from_display_mode(&mode, &plane).unwrap();
+//! ```
 
 #![allow(dead_code)] // TODO: this module isn't finished
 #![allow(unused_variables)] // TODO: this module isn't finished
 
+use std::error;
 use std::ffi::CStr;
+use std::mem;
 use std::ptr;
 use std::sync::Arc;
 use std::vec::IntoIter;
@@ -45,8 +69,8 @@ use vk;
 
 // TODO: extract this to a `display` module and solve the visibility problems
 
-/// ?
-// TODO: plane capabilities
+/// A plane of a physical device. The content of multiple planes can be shown on the same
+/// display, stacked on top of each other.
 // TODO: store properties in the instance?
 pub struct DisplayPlane {
     instance: Arc<Instance>,
@@ -133,6 +157,49 @@ impl DisplayPlane {
         self.index
     }
 
+    /// Returns the index of this plane in the stack of planes of the display it is currently
+    /// associated with.
+    #[inline]
+    pub fn current_stack_index(&self) -> u32 {
+        self.properties.currentStackIndex
+    }
+
+    /// Queries the capabilities of this plane when it is used with the given mode.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `mode` doesn't belong to the same physical device as this plane.
+    ///
+    pub fn capabilities(&self, mode: &DisplayMode)
+                        -> Result<DisplayPlaneCapabilities, OomError> {
+        assert_eq!(self.physical_device().internal_object(),
+                   mode.display().physical_device().internal_object());
+
+        let vk = self.instance.pointers();
+
+        let caps = unsafe {
+            let mut output = mem::uninitialized();
+            check_errors(vk.GetDisplayPlaneCapabilitiesKHR(self.physical_device()
+                                                               .internal_object(),
+                                                           mode.internal_object(),
+                                                           self.index,
+                                                           &mut output))?;
+            output
+        };
+
+        Ok(DisplayPlaneCapabilities {
+               supported_alpha: supported_display_plane_alpha_from_bits(caps.supportedAlpha),
+               min_src_position: [caps.minSrcPosition.x, caps.minSrcPosition.y],
+               max_src_position: [caps.maxSrcPosition.x, caps.maxSrcPosition.y],
+               min_src_extent: [caps.minSrcExtent.width, caps.minSrcExtent.height],
+               max_src_extent: [caps.maxSrcExtent.width, caps.maxSrcExtent.height],
+               min_dst_position: [caps.minDstPosition.x, caps.minDstPosition.y],
+               max_dst_position: [caps.maxDstPosition.x, caps.maxDstPosition.y],
+               min_dst_extent: [caps.minDstExtent.width, caps.minDstExtent.height],
+               max_dst_extent: [caps.maxDstExtent.width, caps.maxDstExtent.height],
+           })
+    }
+
     /// Returns true if this plane supports the given display.
     #[inline]
     pub fn supports(&self, display: &Display) -> bool {
@@ -326,36 +393,44 @@ pub struct DisplayMode {
 }
 
 impl DisplayMode {
-    /*pub fn new(display: &Display) -> Result<Arc<DisplayMode>, OomError> {
-        let vk = instance.pointers();
-        assert!(device.instance().loaded_extensions().khr_display);     // TODO: return error instead
-
-        let parameters = vk::DisplayModeParametersKHR {
-            visibleRegion: vk::Extent2D { width: , height:  },
-            refreshRate: ,
+    /// Creates a new mode on a display, with the given visible region and refresh rate.
+    ///
+    /// The refresh rate is expressed in millihertz, like the one returned by `refresh_rate`.
+    ///
+    /// The mode is destroyed at the same time as the display.
+    pub fn new(display: &Display, visible_region: [u32; 2], refresh_rate: u32)
+               -> Result<DisplayMode, DisplayModeCreationError> {
+        let vk = display.instance.pointers();
+
+        let infos = vk::DisplayModeCreateInfoKHR {
+            sType: vk::STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR,
+            pNext: ptr::null(),
+            flags: 0, // reserved
+            parameters: vk::DisplayModeParametersKHR {
+                visibleRegion: vk::Extent2D {
+                    width: visible_region[0],
+                    height: visible_region[1],
+                },
+                refreshRate: refresh_rate,
+            },
         };
 
-        let display_mode = {
-            let infos = vk::DisplayModeCreateInfoKHR {
-                sType: vk::STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR,
-                pNext: ptr::null(),
-                flags: 0,   // reserved
-                parameters: parameters,
-            };
-
+        let display_mode = unsafe {
             let mut output = mem::uninitialized();
-            try!(check_errors(vk.CreateDisplayModeKHR(display.device.internal_object(),
-                                                      display.display, &infos, ptr::null(),
-                                                      &mut output)));
+            check_errors(vk.CreateDisplayModeKHR(display.physical_device().internal_object(),
+                                                 display.internal_object(),
+                                                 &infos,
+                                                 ptr::null(),
+                                                 &mut output))?;
             output
         };
 
-        Ok(Arc::new(DisplayMode {
-            instance: display.device.instance().clone(),
-            display_mode: display_mode,
-            parameters: ,
-        }))
-    }*/
+        Ok(DisplayMode {
+               display: display.clone(),
+               display_mode: display_mode,
+               parameters: infos.parameters,
+           })
+    }
 
     /// Returns the display corresponding to this mode.
     #[inline]
@@ -370,7 +445,7 @@ impl DisplayMode {
         [d.width, d.height]
     }
 
-    /// Returns the refresh rate of this mode.
+    /// Returns the refresh rate of this mode, in millihertz.
     #[inline]
     pub fn refresh_rate(&self) -> u32 {
         self.parameters.refreshRate
@@ -387,3 +462,190 @@ unsafe impl VulkanObject for DisplayMode {
         self.display_mode
     }
 }
+
+/// Error that can happen when creating a display mode.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayModeCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The display doesn't support the requested visible region and refresh rate.
+    InvalidParameters,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for DisplayModeCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DisplayModeCreationError::OomError(_) => "not enough memory available",
+            DisplayModeCreationError::InvalidParameters =>
+                "the display doesn't support the requested visible region and refresh rate",
+            DisplayModeCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            DisplayModeCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(DisplayModeCreationError, OomError {
+    InitializationFailed => InvalidParameters,
+});
+
+/// The capabilities of a display plane when it is used with a specific display mode.
+///
+/// The source region is the region of the images of the swapchain that is shown, and the
+/// destination region is the region of the display where it is shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DisplayPlaneCapabilities {
+    /// The alpha modes that are supported by the plane.
+    pub supported_alpha: SupportedDisplayPlaneAlpha,
+    /// Minimum position of the source region.
+    pub min_src_position: [i32; 2],
+    /// Maximum position of the source region.
+    pub max_src_position: [i32; 2],
+    /// Minimum dimensions of the source region.
+    pub min_src_extent: [u32; 2],
+    /// Maximum dimensions of the source region.
+    pub max_src_extent: [u32; 2],
+    /// Minimum position of the destination region.
+    pub min_dst_position: [i32; 2],
+    /// Maximum position of the destination region.
+    pub max_dst_position: [i32; 2],
+    /// Minimum dimensions of the destination region.
+    pub min_dst_extent: [u32; 2],
+    /// Maximum dimensions of the destination region.
+    pub max_dst_extent: [u32; 2],
+}
+
+/// How the alpha values of the pixels of a display plane are treated when it is blended with the
+/// planes below it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum DisplayPlaneAlpha {
+    /// The alpha channel of the image is ignored. The plane is considered opaque.
+    Opaque = vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR,
+
+    /// The alpha value passed when creating the surface is used for all the pixels.
+    Global = vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR,
+
+    /// The alpha channel of the image is respected.
+    PerPixel = vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_BIT_KHR,
+
+    /// The alpha channel of the image is respected. The color channels are expected to have
+    /// already been multiplied by the alpha value.
+    PerPixelPremultiplied = vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR,
+}
+
+/// List of supported display plane alpha modes.
+///
+/// See the docs of `DisplayPlaneAlpha`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub struct SupportedDisplayPlaneAlpha {
+    pub opaque: bool,
+    pub global: bool,
+    pub per_pixel: bool,
+    pub per_pixel_premultiplied: bool,
+}
+
+fn supported_display_plane_alpha_from_bits(val: u32) -> SupportedDisplayPlaneAlpha {
+    SupportedDisplayPlaneAlpha {
+        opaque: (val & vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR) != 0,
+        global: (val & vk::DISPLAY_PLANE_ALPHA_GLOBAL_BIT_KHR) != 0,
+        per_pixel: (val & vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_BIT_KHR) != 0,
+        per_pixel_premultiplied: (val & vk::DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR) !=
+            0,
+    }
+}
+
+impl SupportedDisplayPlaneAlpha {
+    /// Builds a `SupportedDisplayPlaneAlpha` with all fields set to false.
+    #[inline]
+    pub fn none() -> SupportedDisplayPlaneAlpha {
+        SupportedDisplayPlaneAlpha {
+            opaque: false,
+            global: false,
+            per_pixel: false,
+            per_pixel_premultiplied: false,
+        }
+    }
+
+    /// Returns true if the given `DisplayPlaneAlpha` is in this list.
+    #[inline]
+    pub fn supports(&self, value: DisplayPlaneAlpha) -> bool {
+        match value {
+            DisplayPlaneAlpha::Opaque => self.opaque,
+            DisplayPlaneAlpha::Global => self.global,
+            DisplayPlaneAlpha::PerPixel => self.per_pixel,
+            DisplayPlaneAlpha::PerPixelPremultiplied => self.per_pixel_premultiplied,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instance::PhysicalDevice;
+    use swapchain::display::Display;
+    use swapchain::display::DisplayPlane;
+
+    use VulkanObject;
+
+    #[test]
+    fn enumerate_displays() {
+        let instance = instance!(extensions: [khr_display]);
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        for display in Display::enumerate(physical) {
+            assert_eq!(display.physical_device().index(), physical.index());
+
+            for mode in display.display_modes() {
+                assert_eq!(mode.display().internal_object(), display.internal_object());
+            }
+        }
+    }
+
+    #[test]
+    fn enumerate_planes() {
+        let instance = instance!(extensions: [khr_display]);
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let planes = DisplayPlane::enumerate(physical).collect::<Vec<_>>();
+        for (index, plane) in planes.iter().enumerate() {
+            assert_eq!(plane.index(), index as u32);
+            assert_eq!(plane.physical_device().index(), physical.index());
+        }
+    }
+
+    #[test]
+    fn plane_capabilities() {
+        let instance = instance!(extensions: [khr_display]);
+        let physical = match PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        for plane in DisplayPlane::enumerate(physical) {
+            for display in Display::enumerate(physical).filter(|d| plane.supports(d)) {
+                for mode in display.display_modes() {
+                    plane.capabilities(&mode).unwrap();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ptr;
+
+use vk;
+
+/// Describes the content of the images of a swapchain to an HDR display.
+///
+/// The chromaticity coordinates are `[x, y]` pairs in the CIE 1931 color space. The luminances
+/// are in nits (candelas per square meter).
+///
+/// See `Swapchain::set_hdr_metadata`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HdrMetadata {
+    /// Chromaticity of the red primary of the display used to master the content.
+    pub display_primary_red: [f32; 2],
+    /// Chromaticity of the green primary of the display used to master the content.
+    pub display_primary_green: [f32; 2],
+    /// Chromaticity of the blue primary of the display used to master the content.
+    pub display_primary_blue: [f32; 2],
+    /// Chromaticity of the white point of the display used to master the content.
+    pub white_point: [f32; 2],
+    /// Maximum luminance of the display used to master the content.
+    pub max_luminance: f32,
+    /// Minimum luminance of the display used to master the content.
+    pub min_luminance: f32,
+    /// Maximum luminance of any pixel of the content.
+    pub max_content_light_level: f32,
+    /// Maximum average luminance of a frame of the content.
+    pub max_frame_average_light_level: f32,
+}
+
+impl HdrMetadata {
+    pub(crate) fn to_vk(&self) -> vk::HdrMetadataEXT {
+        #[inline]
+        fn xy(v: [f32; 2]) -> vk::XYColorEXT {
+            vk::XYColorEXT { x: v[0], y: v[1] }
+        }
+
+        vk::HdrMetadataEXT {
+            sType: vk::STRUCTURE_TYPE_HDR_METADATA_EXT,
+            pNext: ptr::null(),
+            displayPrimaryRed: xy(self.display_primary_red),
+            displayPrimaryGreen: xy(self.display_primary_green),
+            displayPrimaryBlue: xy(self.display_primary_blue),
+            whitePoint: xy(self.white_point),
+            maxLuminance: self.max_luminance,
+            minLuminance: self.min_luminance,
+            maxContentLightLevel: self.max_content_light_level,
+            maxFrameAverageLightLevel: self.max_frame_average_light_level,
+        }
+    }
+}
@@ -89,8 +89,8 @@
 //!
 //! ## Creating a surface from a monitor
 //!
-//! Currently no system provides the `VK_KHR_display` extension that contains this feature.
-//! This feature is still a work-in-progress in vulkano and will reside in the `display` module.
+//! On systems without a windowing system, a surface can cover a whole monitor instead. This
+//! requires the `VK_KHR_display` extension. See the `display` module for more information.
 //!
 //! # Swapchains
 //!
@@ -164,9 +164,10 @@
 //! # use vulkano::sync::SharingMode;
 //! # use vulkano::format::Format;
 //! # use vulkano::swapchain::{Surface, Swapchain, SurfaceTransform, PresentMode, CompositeAlpha};
+//! # use vulkano::swapchain::{ColorSpace, FullscreenExclusive};
 //! # fn create_swapchain(
 //! #     device: Arc<Device>, surface: Arc<Surface<()>>, present_queue: Arc<Queue>,
-//! #     buffers_count: u32, format: Format, dimensions: [u32; 2],
+//! #     buffers_count: u32, format: Format, color_space: ColorSpace, dimensions: [u32; 2],
 //! #     surface_transform: SurfaceTransform, composite_alpha: CompositeAlpha, present_mode: PresentMode
 //! # ) -> Result<(), Box<std::error::Error>> {
 //! // The created swapchain will be used as a color attachment for rendering.
@@ -187,6 +188,8 @@
 //!     buffers_count,
 //!     // The format of the images.
 //!     format,
+//!     // How the content of the images is interpreted by the presentation engine.
+//!     color_space,
 //!     // The size of each image.
 //!     dimensions,
 //!     // How many layers each image has.
@@ -201,6 +204,8 @@
 //!     composite_alpha,
 //!     // How to present images.
 //!     present_mode,
+//!     // Let the implementation decide whether to use fullscreen exclusivity.
+//!     FullscreenExclusive::Default,
 //!     // Clip the parts of the buffer which aren't visible.
 //!     true,
 //!     // No previous swapchain.
@@ -274,6 +279,10 @@
 //!         Err(err) => panic!("{:?}", err)
 //!     };
 //!
+//!     if acq_future.is_suboptimal() {
+//!         recreate_swapchain = true;
+//!     }
+//!
 //!     // ...
 //!
 //!     let final_future = acq_future
@@ -285,6 +294,35 @@
 //! }
 //! ```
 //!
+//! ## Presenting only a part of an image
+//!
+//! If the `VK_KHR_incremental_present` device extension is enabled, you can tell the
+//! presentation engine which regions of the image have changed since the last time it was
+//! presented, by using `then_swapchain_present_incremental` (or `present_incremental`) with a
+//! `PresentRegion`. This can reduce the power consumption of applications that only redraw small
+//! parts of the screen, like user interfaces. The region is only a hint, and it is ignored if
+//! the extension isn't enabled.
+//!
+//! ```
+//! use vulkano::swapchain::PresentRegion;
+//! use vulkano::swapchain::RectangleLayer;
+//! use vulkano::sync::GpuFuture;
+//! # let swapchain: ::std::sync::Arc<::vulkano::swapchain::Swapchain<()>> = return;
+//! # let queue: ::std::sync::Arc<::vulkano::device::Queue> = return;
+//! # let acq_future: ::vulkano::swapchain::SwapchainAcquireFuture<()> = return;
+//! # let index = 0;
+//!
+//! // Only the top-left 64x64 corner of the image has been redrawn.
+//! let region = PresentRegion {
+//!     rectangles: vec![RectangleLayer { offset: [0, 0], extent: [64, 64], layer: 0 }],
+//! };
+//!
+//! let final_future = acq_future
+//!     // .then_execute(...)
+//!     .then_swapchain_present_incremental(queue.clone(), swapchain.clone(), index, region)
+//!     .then_signal_fence_and_flush();
+//! ```
+//!
 
 use std::sync::atomic::AtomicBool;
 
@@ -299,6 +337,7 @@ pub use self::capabilities::SupportedPresentModesIter;
 pub use self::capabilities::SupportedSurfaceTransforms;
 pub use self::capabilities::SupportedSurfaceTransformsIter;
 pub use self::capabilities::SurfaceTransform;
+pub use self::hdr_metadata::HdrMetadata;
 pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
 pub use self::surface::CapabilitiesError;
@@ -306,10 +345,13 @@ pub use self::surface::Surface;
 pub use self::surface::SurfaceCreationError;
 pub use self::swapchain::AcquireError;
 pub use self::swapchain::AcquiredImage;
+pub use self::swapchain::FullscreenExclusive;
+pub use self::swapchain::FullscreenExclusiveError;
 pub use self::swapchain::PresentFuture;
 pub use self::swapchain::Swapchain;
 pub use self::swapchain::SwapchainAcquireFuture;
 pub use self::swapchain::SwapchainCreationError;
+pub use self::swapchain::Win32Monitor;
 pub use self::swapchain::acquire_next_image;
 pub use self::swapchain::acquire_next_image_raw;
 pub use self::swapchain::present;
@@ -317,6 +359,7 @@ pub use self::swapchain::present_incremental;
 
 mod capabilities;
 pub mod display;
+mod hdr_metadata;
 mod present_region;
 mod surface;
 mod swapchain;
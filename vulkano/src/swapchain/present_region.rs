@@ -42,12 +42,25 @@ pub struct RectangleLayer {
 
 impl RectangleLayer {
     /// Returns true if this rectangle layer is compatible with swapchain.
+    ///
+    /// The rectangle must be entirely inside of the images of the swapchain.
     pub fn is_compatible_with<W>(&self, swapchain: &Swapchain<W>) -> bool {
-        // FIXME negative offset is not disallowed by spec, but semantically should not be possible
-        debug_assert!(self.offset[0] >= 0);
-        debug_assert!(self.offset[1] >= 0);
-        self.offset[0] as u32 + self.extent[0] <= swapchain.dimensions()[0] &&
-            self.offset[1] as u32 + self.extent[1] <= swapchain.dimensions()[1] &&
+        // Negative offsets are not disallowed by the specs, but a rectangle that starts outside
+        // of the image doesn't make sense.
+        if self.offset[0] < 0 || self.offset[1] < 0 {
+            return false;
+        }
+
+        let dimensions = swapchain.dimensions();
+        let fits = |offset: i32, extent: u32, max: u32| {
+            (offset as u32)
+                .checked_add(extent)
+                .map(|end| end <= max)
+                .unwrap_or(false)
+        };
+
+        fits(self.offset[0], self.extent[0], dimensions[0]) &&
+            fits(self.offset[1], self.extent[1], dimensions[1]) &&
             self.layer < swapchain.layers()
     }
 
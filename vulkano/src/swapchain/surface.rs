@@ -22,9 +22,11 @@ use instance::PhysicalDevice;
 use instance::QueueFamily;
 use swapchain::Capabilities;
 use swapchain::SurfaceSwapchainLock;
+use swapchain::SurfaceTransform;
 use swapchain::capabilities;
 use swapchain::display::DisplayMode;
 use swapchain::display::DisplayPlane;
+use swapchain::display::DisplayPlaneAlpha;
 
 use Error;
 use OomError;
@@ -63,13 +65,43 @@ impl<W> Surface<W> {
 
     /// Creates a `Surface` that covers a display mode.
     ///
+    /// The surface isn't transformed, is opaque, and its dimensions are the visible region of
+    /// the mode. Use `from_display_plane` to choose these parameters.
+    ///
     /// # Panic
     ///
     /// - Panics if `display_mode` and `plane` don't belong to the same physical device.
     /// - Panics if `plane` doesn't support the display of `display_mode`.
     ///
+    #[inline]
     pub fn from_display_mode(display_mode: &DisplayMode, plane: &DisplayPlane)
                              -> Result<Arc<Surface<()>>, SurfaceCreationError> {
+        Surface::<()>::from_display_plane(display_mode,
+                                          plane,
+                                          SurfaceTransform::Identity,
+                                          DisplayPlaneAlpha::Opaque,
+                                          1.0,
+                                          display_mode.visible_region())
+    }
+
+    /// Creates a `Surface` that covers a display mode, using the given plane.
+    ///
+    /// `transform` is applied to the images when they are shown on the display. `global_alpha`
+    /// is only used if `alpha` is `DisplayPlaneAlpha::Global`, and must be between 0.0 and 1.0.
+    /// `image_extent` is the dimensions of the images of the swapchains that will be created for
+    /// this surface. See also `DisplayPlane::capabilities`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `display_mode` and `plane` don't belong to the same physical device.
+    /// - Panics if `plane` doesn't support the display of `display_mode`.
+    /// - Panics if the display doesn't support `transform`.
+    /// - Panics if `global_alpha` is not between 0.0 and 1.0.
+    ///
+    pub fn from_display_plane(display_mode: &DisplayMode, plane: &DisplayPlane,
+                              transform: SurfaceTransform, alpha: DisplayPlaneAlpha,
+                              global_alpha: f32, image_extent: [u32; 2])
+                              -> Result<Arc<Surface<()>>, SurfaceCreationError> {
         if !display_mode
             .display()
             .physical_device()
@@ -83,6 +115,8 @@ impl<W> Surface<W> {
         assert_eq!(display_mode.display().physical_device().internal_object(),
                    plane.physical_device().internal_object());
         assert!(plane.supports(display_mode.display()));
+        assert!(display_mode.display().supported_transforms().supports(transform));
+        assert!(global_alpha >= 0.0 && global_alpha <= 1.0);
 
         let instance = display_mode.display().physical_device().instance();
         let vk = instance.pointers();
@@ -94,14 +128,13 @@ impl<W> Surface<W> {
                 flags: 0, // reserved
                 displayMode: display_mode.internal_object(),
                 planeIndex: plane.index(),
-                planeStackIndex: 0, // FIXME: plane.properties.currentStackIndex,
-                transform: vk::SURFACE_TRANSFORM_IDENTITY_BIT_KHR, // TODO: let user choose
-                globalAlpha: 0.0, // TODO: let user choose
-                alphaMode: vk::DISPLAY_PLANE_ALPHA_OPAQUE_BIT_KHR, // TODO: let user choose
+                planeStackIndex: plane.current_stack_index(),
+                transform: transform as u32,
+                globalAlpha: global_alpha,
+                alphaMode: alpha as u32,
                 imageExtent: vk::Extent2D {
-                    // TODO: let user choose
-                    width: display_mode.visible_region()[0],
-                    height: display_mode.visible_region()[1],
+                    width: image_extent[0],
+                    height: image_extent[1],
                 },
             };
 
@@ -493,10 +526,21 @@ impl<W> Surface<W> {
                     }))
     }
 
-    /// Returns true if the given queue family can draw on this surface.
-    // FIXME: vulkano doesn't check this for the moment!
+    /// Returns true if the given queue family can present images to this surface.
+    ///
+    /// Creating a swapchain returns an error if none of the queue families of the device
+    /// support the surface.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the queue family and the surface don't belong to the same instance.
+    ///
     pub fn is_supported(&self, queue: QueueFamily) -> Result<bool, CapabilitiesError> {
         unsafe {
+            assert_eq!(&*self.instance as *const _,
+                       &**queue.physical_device().instance() as *const _,
+                       "Instance mismatch in Surface::is_supported");
+
             let vk = self.instance.pointers();
 
             let mut output = mem::uninitialized();
@@ -591,8 +635,12 @@ impl<W> Surface<W> {
                     debug_assert!(usage.color_attachment);  // specs say that this must be true
                     usage
                 },
-                supported_formats: formats.into_iter().map(|f| {
-                    (Format::from_vulkan_num(f.format).unwrap(), capabilities::color_space_from_num(f.colorSpace))
+                // Formats and color spaces that vulkano doesn't know about are skipped.
+                supported_formats: formats.into_iter().filter_map(|f| {
+                    Format::from_vulkan_num(f.format).and_then(|format| {
+                        capabilities::color_space_from_num(f.colorSpace)
+                            .map(|color_space| (format, color_space))
+                    })
                 }).collect(),
                 present_modes: modes,
             })
@@ -658,6 +706,9 @@ pub enum SurfaceCreationError {
         /// Name of the missing extension.
         name: &'static str,
     },
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for SurfaceCreationError {
@@ -667,6 +718,8 @@ impl error::Error for SurfaceCreationError {
             SurfaceCreationError::OomError(_) => "not enough memory available",
             SurfaceCreationError::MissingExtension { .. } =>
                 "the extension required for this function was not enabled",
+            SurfaceCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -679,30 +732,7 @@ impl error::Error for SurfaceCreationError {
     }
 }
 
-impl fmt::Display for SurfaceCreationError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<OomError> for SurfaceCreationError {
-    #[inline]
-    fn from(err: OomError) -> SurfaceCreationError {
-        SurfaceCreationError::OomError(err)
-    }
-}
-
-impl From<Error> for SurfaceCreationError {
-    #[inline]
-    fn from(err: Error) -> SurfaceCreationError {
-        match err {
-            err @ Error::OutOfHostMemory => SurfaceCreationError::OomError(OomError::from(err)),
-            err @ Error::OutOfDeviceMemory => SurfaceCreationError::OomError(OomError::from(err)),
-            _ => panic!("unexpected error: {:?}", err),
-        }
-    }
-}
+vk_error_conversions!(SurfaceCreationError, OomError {});
 
 /// Error that can happen when retreiving a surface's capabilities.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
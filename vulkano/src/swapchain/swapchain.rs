@@ -10,6 +10,7 @@
 use std::error;
 use std::fmt;
 use std::mem;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -31,11 +32,13 @@ use image::ImageDimensions;
 use image::ImageInner;
 use image::ImageLayout;
 use image::ImageUsage;
+use image::ImageViewCreationError;
 use image::swapchain::SwapchainImage;
 use image::sys::UnsafeImage;
 use swapchain::CapabilitiesError;
 use swapchain::ColorSpace;
 use swapchain::CompositeAlpha;
+use swapchain::HdrMetadata;
 use swapchain::PresentMode;
 use swapchain::PresentRegion;
 use swapchain::Surface;
@@ -45,10 +48,12 @@ use sync::AccessCheckError;
 use sync::AccessError;
 use sync::AccessFlagBits;
 use sync::Fence;
+use sync::FenceCreationError;
 use sync::FlushError;
 use sync::GpuFuture;
 use sync::PipelineStages;
 use sync::Semaphore;
+use sync::SemaphoreCreationError;
 use sync::SharingMode;
 
 use Error;
@@ -64,6 +69,11 @@ use vk;
 /// when creating the swapchain, plus a future that represents the moment when the image will
 /// become available from the GPU (which may not be *immediately*).
 ///
+/// If the swapchain can still be used but no longer matches the surface exactly, the image is
+/// acquired anyway and `is_suboptimal()` returns true on the future. You should recreate the
+/// swapchain when you get the chance. If the swapchain can't be used anymore,
+/// `AcquireError::OutOfDate` is returned instead.
+///
 /// If you try to draw on an image without acquiring it first, the execution will block. (TODO
 /// behavior may change).
 pub fn acquire_next_image<W>(swapchain: Arc<Swapchain<W>>, timeout: Option<Duration>)
@@ -71,17 +81,29 @@ pub fn acquire_next_image<W>(swapchain: Arc<Swapchain<W>>, timeout: Option<Durat
     let semaphore = Semaphore::from_pool(swapchain.device.clone())?;
     let fence = Fence::from_pool(swapchain.device.clone())?;
 
-    // TODO: propagate `suboptimal` to the user
-    let AcquiredImage { id, suboptimal } = {
+    let AcquiredImage { id: id, suboptimal: suboptimal } = {
+        // Acquisitions are serialized by a mutex of their own, so that waiting for an image
+        // doesn't prevent the other threads from presenting in the meantime.
+        let _acquire_lock = swapchain.acquire_lock.lock().unwrap();
+
         // Check that this is not an old swapchain. From specs:
         // > swapchain must not have been replaced by being passed as the
         // > VkSwapchainCreateInfoKHR::oldSwapchain value to vkCreateSwapchainKHR
-        let stale = swapchain.stale.lock().unwrap();
-        if *stale {
+        if *swapchain.stale.lock().unwrap() {
             return Err(AcquireError::OutOfDate);
         }
 
-        unsafe { acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence)) }?
+        match unsafe {
+            acquire_next_image_raw(&swapchain, timeout, Some(&semaphore), Some(&fence))
+        } {
+            Err(AcquireError::FullscreenExclusiveLost) => {
+                swapchain
+                    .fullscreen_exclusive_held
+                    .store(false, Ordering::SeqCst);
+                return Err(AcquireError::FullscreenExclusiveLost);
+            },
+            result => result?,
+        }
     };
 
     Ok((id,
@@ -90,6 +112,7 @@ pub fn acquire_next_image<W>(swapchain: Arc<Swapchain<W>>, timeout: Option<Durat
             semaphore: Some(semaphore),
             fence: Some(fence),
             image_id: id,
+            suboptimal: suboptimal,
             finished: AtomicBool::new(false),
         }))
 }
@@ -131,6 +154,12 @@ pub fn present<F, W>(swapchain: Arc<Swapchain<W>>, before: F, queue: Arc<Queue>,
 /// This is just an optimizaion hint, as the vulkan driver is free to ignore the given present region.
 ///
 /// If `VK_KHR_incremental_present` is not enabled on the device, the parameter will be ignored.
+///
+/// # Panic
+///
+/// - Flushing the returned future panics if a rectangle of `present_region` isn't entirely
+///   inside of the images of the swapchain. See `PresentRegion::is_compatible_with`.
+///
 pub fn present_incremental<F, W>(swapchain: Arc<Swapchain<W>>, before: F, queue: Arc<Queue>,
                               index: usize, present_region: PresentRegion)
                               -> PresentFuture<F, W>
@@ -171,10 +200,15 @@ pub struct Swapchain<W> {
     // If true, that means we have tried to use this swapchain to recreate a new swapchain. The current
     // swapchain can no longer be used for anything except presenting already-acquired images.
     //
-    // We use a `Mutex` instead of an `AtomicBool` because we want to keep that locked while
-    // we acquire the image.
+    // We use a `Mutex` instead of an `AtomicBool` because the mutex also provides the host
+    // synchronization of the swapchain that Vulkan requires for presenting and the other
+    // swapchain commands.
     stale: Mutex<bool>,
 
+    // Locked while an image is being acquired. Acquiring doesn't lock `stale`, so that the
+    // other threads can present while we wait for an image to be available.
+    acquire_lock: Mutex<()>,
+
     // Parameters passed to the constructor.
     num_images: u32,
     format: Format,
@@ -186,7 +220,15 @@ pub struct Swapchain<W> {
     transform: SurfaceTransform,
     alpha: CompositeAlpha,
     mode: PresentMode,
+    fullscreen_exclusive: FullscreenExclusive,
     clipped: bool,
+
+    // True if fullscreen exclusivity is currently held. Only used with
+    // `FullscreenExclusive::AppControlled`.
+    fullscreen_exclusive_held: AtomicBool,
+
+    // False if the swapchain was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 struct ImageEntry {
@@ -203,6 +245,14 @@ impl <W> Swapchain<W> {
     /// supported by the implementation. All the parameters that you pass to `Swapchain::new`
     /// must be supported.
     ///
+    /// The `format` and `color_space` parameters must correspond to one of the entries of
+    /// `supported_formats` in the capabilities of the surface. See also
+    /// `Capabilities::choose_format`.
+    ///
+    /// The `fullscreen_exclusive` parameter controls whether the swapchain can get exclusive
+    /// access to the display when its surface covers it entirely. Anything other than
+    /// `FullscreenExclusive::Default` requires the `ext_full_screen_exclusive` extension.
+    ///
     /// The `clipped` parameter indicates whether the implementation is allowed to discard
     /// rendering operations that affect regions of the surface which aren't visible. This is
     /// important to take into account if your fragment shader has side-effects or if you want to
@@ -218,13 +268,13 @@ impl <W> Swapchain<W> {
     /// - Panics if `usage` is empty.
     ///
     // TODO: remove `old_swapchain` parameter and add another function `with_old_swapchain`.
-    // TODO: add `ColorSpace` parameter
     // TODO: isn't it unsafe to take the surface through an Arc when it comes to vulkano-win?
     #[inline]
     pub fn new<F, S>(
         device: Arc<Device>, surface: Arc<Surface<W>>, num_images: u32, format: F,
-        dimensions: [u32; 2], layers: u32, usage: ImageUsage, sharing: S,
-        transform: SurfaceTransform, alpha: CompositeAlpha, mode: PresentMode, clipped: bool,
+        color_space: ColorSpace, dimensions: [u32; 2], layers: u32, usage: ImageUsage, sharing: S,
+        transform: SurfaceTransform, alpha: CompositeAlpha, mode: PresentMode,
+        fullscreen_exclusive: FullscreenExclusive, clipped: bool,
         old_swapchain: Option<&Arc<Swapchain<W>>>)
         -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError>
         where F: FormatDesc,
@@ -234,7 +284,7 @@ impl <W> Swapchain<W> {
                              surface,
                              num_images,
                              format.format(),
-                             ColorSpace::SrgbNonLinear,
+                             color_space,
                              dimensions,
                              layers,
                              usage,
@@ -242,11 +292,76 @@ impl <W> Swapchain<W> {
                              transform,
                              alpha,
                              mode,
+                             fullscreen_exclusive,
                              clipped,
                              old_swapchain.map(|s| &**s))
     }
 
+    /// Builds a `Swapchain` from a raw handle that was created outside of vulkano, and returns
+    /// it with its images, like `new`.
+    ///
+    /// The parameters must be the ones that were used to create the swapchain, and are
+    /// documented in `new`.
+    /// If `owned` is true, the swapchain is destroyed when the returned object is dropped, and
+    /// vulkano considers that the surface has a swapchain. Otherwise, the caller remains
+    /// responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid swapchain of `device`, created for `surface` with the given
+    ///   parameters.
+    /// - If `owned` is true, the swapchain must not be destroyed by anything else.
+    ///
+    pub unsafe fn from_raw<F, S>(
+        device: Arc<Device>, surface: Arc<Surface<W>>, handle: vk::SwapchainKHR, num_images: u32,
+        format: F, color_space: ColorSpace, dimensions: [u32; 2], layers: u32, usage: ImageUsage,
+        sharing: S, transform: SurfaceTransform, alpha: CompositeAlpha, mode: PresentMode,
+        fullscreen_exclusive: FullscreenExclusive, clipped: bool, owned: bool)
+        -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError>
+        where F: FormatDesc,
+              S: Into<SharingMode>
+    {
+        if owned {
+            surface.flag().store(true, Ordering::Release);
+        }
+
+        Swapchain::wrap(device,
+                        surface,
+                        handle,
+                        num_images,
+                        format.format(),
+                        color_space,
+                        dimensions,
+                        layers,
+                        usage,
+                        sharing.into(),
+                        transform,
+                        alpha,
+                        mode,
+                        fullscreen_exclusive,
+                        clipped,
+                        owned)
+    }
+
+    /// Recreates the swapchain with the current dimensions of the surface.
+    ///
+    /// If the dimensions of the surface depend on the swapchain, the dimensions of this swapchain
+    /// are reused, clamped to what the surface currently supports.
+    ///
+    /// See `recreate_with_dimension` for what happens to this swapchain.
+    pub fn recreate(&self)
+                    -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>),
+                              SwapchainCreationError> {
+        let capabilities = self.surface.capabilities(self.device.physical_device())?;
+        self.recreate_with_dimension(capabilities.clamp_extent(self.dimensions))
+    }
+
     /// Recreates the swapchain with new dimensions.
+    ///
+    /// This swapchain is passed as the old swapchain, which lets the implementation reuse its
+    /// resources. Afterwards, it can only be used to present the images that were already
+    /// acquired, and `acquire_next_image` returns `AcquireError::OutOfDate`. Its images are
+    /// destroyed once they are no longer in use.
     pub fn recreate_with_dimension(
         &self, dimensions: [u32; 2])
         -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
@@ -262,6 +377,7 @@ impl <W> Swapchain<W> {
                              self.transform,
                              self.alpha,
                              self.mode,
+                             self.fullscreen_exclusive,
                              self.clipped,
                              Some(self))
     }
@@ -269,11 +385,24 @@ impl <W> Swapchain<W> {
     fn new_inner(device: Arc<Device>, surface: Arc<Surface<W>>, num_images: u32, format: Format,
                  color_space: ColorSpace, dimensions: [u32; 2], layers: u32, usage: ImageUsage,
                  sharing: SharingMode, transform: SurfaceTransform, alpha: CompositeAlpha,
-                 mode: PresentMode, clipped: bool, old_swapchain: Option<&Swapchain<W>>)
+                 mode: PresentMode, fullscreen_exclusive: FullscreenExclusive, clipped: bool,
+                 old_swapchain: Option<&Swapchain<W>>)
                  -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
         assert_eq!(device.instance().internal_object(),
                    surface.instance().internal_object());
 
+        // The surface must be supported by at least one of the queue families of the device.
+        let mut surface_supported = false;
+        for family in device.active_queue_families() {
+            if surface.is_supported(family)? {
+                surface_supported = true;
+                break;
+            }
+        }
+        if !surface_supported {
+            return Err(SwapchainCreationError::UnsupportedSurface);
+        }
+
         // Checking that the requested parameters match the capabilities.
         let capabilities = surface.capabilities(device.physical_device())?;
         if num_images < capabilities.min_image_count {
@@ -340,9 +469,18 @@ impl <W> Swapchain<W> {
             return Err(SwapchainCreationError::MissingExtension);
         }
 
+        if fullscreen_exclusive != FullscreenExclusive::Default &&
+            !device.loaded_extensions().ext_full_screen_exclusive
+        {
+            return Err(SwapchainCreationError::FullscreenExclusiveExtensionNotEnabled);
+        }
+
         // Required by the specs.
         assert_ne!(usage, ImageUsage::none());
 
+        // Waits for the acquisitions in progress on the old swapchain to finish.
+        let _old_acquire_lock = old_swapchain.map(|sc| sc.acquire_lock.lock().unwrap());
+
         if let Some(ref old_swapchain) = old_swapchain {
             let mut stale = old_swapchain.stale.lock().unwrap();
 
@@ -371,9 +509,38 @@ impl <W> Swapchain<W> {
                                                      ids.as_ptr()),
             };
 
+            let win32_monitor = match fullscreen_exclusive {
+                FullscreenExclusive::AppControlled(Some(monitor)) => {
+                    Some(vk::SurfaceFullScreenExclusiveWin32InfoEXT {
+                             sType: vk::STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT,
+                             pNext: ptr::null(),
+                             hmonitor: monitor.0 as *mut _,
+                         })
+                },
+                _ => None,
+            };
+
+            let fullscreen_exclusive_infos = if fullscreen_exclusive !=
+                FullscreenExclusive::Default
+            {
+                Some(vk::SurfaceFullScreenExclusiveInfoEXT {
+                         sType: vk::STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT,
+                         pNext: win32_monitor
+                             .as_ref()
+                             .map(|i| i as *const _ as *mut _)
+                             .unwrap_or(ptr::null_mut()),
+                         fullScreenExclusive: fullscreen_exclusive.to_vk(),
+                     })
+            } else {
+                None
+            };
+
             let infos = vk::SwapchainCreateInfoKHR {
                 sType: vk::STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR,
-                pNext: ptr::null(),
+                pNext: fullscreen_exclusive_infos
+                    .as_ref()
+                    .map(|i| i as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
                 flags: 0, // reserved
                 surface: surface.internal_object(),
                 minImageCount: num_images,
@@ -407,6 +574,33 @@ impl <W> Swapchain<W> {
             output
         };
 
+        Swapchain::wrap(device,
+                        surface,
+                        swapchain,
+                        num_images,
+                        format,
+                        color_space,
+                        dimensions,
+                        layers,
+                        usage,
+                        sharing,
+                        transform,
+                        alpha,
+                        mode,
+                        fullscreen_exclusive,
+                        clipped,
+                        true)
+    }
+
+    // Builds the `Swapchain` and its images around a swapchain that has already been created.
+    fn wrap(device: Arc<Device>, surface: Arc<Surface<W>>, swapchain: vk::SwapchainKHR,
+            num_images: u32, format: Format, color_space: ColorSpace, dimensions: [u32; 2],
+            layers: u32, usage: ImageUsage, sharing: SharingMode, transform: SurfaceTransform,
+            alpha: CompositeAlpha, mode: PresentMode, fullscreen_exclusive: FullscreenExclusive,
+            clipped: bool, needs_destruction: bool)
+            -> Result<(Arc<Swapchain<W>>, Vec<Arc<SwapchainImage<W>>>), SwapchainCreationError> {
+        let vk = device.pointers();
+
         let image_handles = unsafe {
             let mut num = 0;
             check_errors(vk.GetSwapchainImagesKHR(device.internal_object(),
@@ -423,6 +617,11 @@ impl <W> Swapchain<W> {
             images
         };
 
+        let concurrent_sharing = match sharing {
+            SharingMode::Exclusive(_) => false,
+            SharingMode::Concurrent(_) => true,
+        };
+
         let images = image_handles
             .into_iter()
             .map(|image| unsafe {
@@ -439,7 +638,9 @@ impl <W> Swapchain<W> {
                                                 format,
                                                 dims,
                                                 1,
-                                                1);
+                                                1,
+                                                concurrent_sharing,
+                                                false);
 
                 ImageEntry {
                     image: img,
@@ -454,6 +655,7 @@ impl <W> Swapchain<W> {
                                      swapchain: swapchain,
                                      images: images,
                                      stale: Mutex::new(false),
+                                     acquire_lock: Mutex::new(()),
                                      num_images: num_images,
                                      format: format,
                                      color_space: color_space,
@@ -464,7 +666,10 @@ impl <W> Swapchain<W> {
                                      transform: transform,
                                      alpha: alpha,
                                      mode: mode,
+                                     fullscreen_exclusive: fullscreen_exclusive,
                                      clipped: clipped,
+                                     fullscreen_exclusive_held: AtomicBool::new(false),
+                                     needs_destruction: needs_destruction,
                                  });
 
         let swapchain_images = unsafe {
@@ -492,6 +697,20 @@ impl <W> Swapchain<W> {
         })
     }
 
+    /// Returns the mutex that must be locked while the swapchain is used by a Vulkan command that
+    /// requires host synchronization.
+    #[inline]
+    pub(crate) fn host_sync(&self) -> &Mutex<bool> {
+        &self.stale
+    }
+
+    /// Returns the flag that indicates whether fullscreen exclusivity is held. Presenting clears
+    /// it if the swapchain loses exclusivity.
+    #[inline]
+    pub(crate) fn fullscreen_exclusive_held(&self) -> &AtomicBool {
+        &self.fullscreen_exclusive_held
+    }
+
     /// Returns the number of images of the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
@@ -508,6 +727,14 @@ impl <W> Swapchain<W> {
         self.format
     }
 
+    /// Returns the color space of the images of the swapchain.
+    ///
+    /// See the documentation of `Swapchain::new`.
+    #[inline]
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
     /// Returns the dimensions of the images of the swapchain.
     ///
     /// See the documentation of `Swapchain::new`.
@@ -555,6 +782,98 @@ impl <W> Swapchain<W> {
     pub fn clipped(&self) -> bool {
         self.clipped
     }
+
+    /// Returns the fullscreen exclusivity mode that was passed when creating the swapchain.
+    ///
+    /// See the documentation of `Swapchain::new`.
+    #[inline]
+    pub fn fullscreen_exclusive(&self) -> FullscreenExclusive {
+        self.fullscreen_exclusive
+    }
+
+    /// Returns true if fullscreen exclusivity is currently held by this swapchain.
+    ///
+    /// Always returns false if the swapchain wasn't created with
+    /// `FullscreenExclusive::AppControlled`.
+    #[inline]
+    pub fn is_fullscreen_exclusive(&self) -> bool {
+        self.fullscreen_exclusive_held.load(Ordering::SeqCst)
+    }
+
+    /// Sets the HDR metadata of the swapchain, which describes its content to the display.
+    ///
+    /// The metadata is used by the presentation engine starting with the next present operation.
+    /// It is only meaningful if the color space of the swapchain is an HDR color space.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_hdr_metadata` extension is not enabled on the device.
+    ///
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) {
+        assert!(self.device.loaded_extensions().ext_hdr_metadata);
+
+        let _lock = self.stale.lock().unwrap();
+        unsafe {
+            let vk = self.device.pointers();
+            let metadata = metadata.to_vk();
+            vk.SetHdrMetadataEXT(self.device.internal_object(), 1, &self.swapchain, &metadata);
+        }
+    }
+
+    /// Acquires fullscreen exclusivity.
+    ///
+    /// The swapchain must have been created with `FullscreenExclusive::AppControlled`, and must
+    /// not already hold fullscreen exclusivity. Exclusivity can be lost at any time, in which
+    /// case `acquire_next_image` or presenting returns a `FullscreenExclusiveLost` error and this
+    /// function must be called again.
+    pub fn acquire_fullscreen_exclusive(&self) -> Result<(), FullscreenExclusiveError> {
+        match self.fullscreen_exclusive {
+            FullscreenExclusive::AppControlled(_) => (),
+            _ => return Err(FullscreenExclusiveError::NotAppControlled),
+        }
+
+        if self.fullscreen_exclusive_held.swap(true, Ordering::SeqCst) {
+            return Err(FullscreenExclusiveError::DoubleAcquire);
+        }
+
+        let result = unsafe {
+            let _lock = self.stale.lock().unwrap();
+            let vk = self.device.pointers();
+            check_errors(vk.AcquireFullScreenExclusiveModeEXT(self.device.internal_object(),
+                                                              self.swapchain))
+        };
+
+        if let Err(err) = result {
+            self.fullscreen_exclusive_held.store(false, Ordering::SeqCst);
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    /// Releases fullscreen exclusivity.
+    ///
+    /// The swapchain must have been created with `FullscreenExclusive::AppControlled`, and must
+    /// currently hold fullscreen exclusivity.
+    pub fn release_fullscreen_exclusive(&self) -> Result<(), FullscreenExclusiveError> {
+        match self.fullscreen_exclusive {
+            FullscreenExclusive::AppControlled(_) => (),
+            _ => return Err(FullscreenExclusiveError::NotAppControlled),
+        }
+
+        if !self.fullscreen_exclusive_held.swap(false, Ordering::SeqCst) {
+            return Err(FullscreenExclusiveError::DoubleRelease);
+        }
+
+        unsafe {
+            let _lock = self.stale.lock().unwrap();
+            let vk = self.device.pointers();
+            check_errors(vk.ReleaseFullScreenExclusiveModeEXT(self.device.internal_object(),
+                                                              self.swapchain))?;
+        }
+
+        Ok(())
+    }
 }
 
 unsafe impl<W> VulkanObject for Swapchain<W> {
@@ -584,6 +903,10 @@ impl<W> fmt::Debug for Swapchain<W> {
 impl<W> Drop for Swapchain<W> {
     #[inline]
     fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroySwapchainKHR(self.device.internal_object(), self.swapchain, ptr::null());
@@ -592,6 +915,65 @@ impl<W> Drop for Swapchain<W> {
     }
 }
 
+/// Whether a swapchain can get exclusive access to the display when it is fullscreen.
+///
+/// Exclusive access lets the presentation engine bypass the compositor, which can reduce the
+/// latency. Using anything other than `Default` requires the `ext_full_screen_exclusive`
+/// extension.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FullscreenExclusive {
+    /// Let the implementation decide.
+    Default,
+
+    /// The implementation can get exclusive access whenever it thinks it is appropriate.
+    Allowed,
+
+    /// The implementation must never get exclusive access.
+    Disallowed,
+
+    /// Exclusive access is controlled with `Swapchain::acquire_fullscreen_exclusive` and
+    /// `Swapchain::release_fullscreen_exclusive`.
+    ///
+    /// On Windows, the monitor that the surface is on must be given.
+    AppControlled(Option<Win32Monitor>),
+}
+
+impl FullscreenExclusive {
+    #[inline]
+    fn to_vk(&self) -> vk::FullScreenExclusiveEXT {
+        match *self {
+            FullscreenExclusive::Default => vk::FULL_SCREEN_EXCLUSIVE_DEFAULT_EXT,
+            FullscreenExclusive::Allowed => vk::FULL_SCREEN_EXCLUSIVE_ALLOWED_EXT,
+            FullscreenExclusive::Disallowed => vk::FULL_SCREEN_EXCLUSIVE_DISALLOWED_EXT,
+            FullscreenExclusive::AppControlled(_) => {
+                vk::FULL_SCREEN_EXCLUSIVE_APPLICATION_CONTROLLED_EXT
+            },
+        }
+    }
+}
+
+/// A Windows `HMONITOR` handle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Win32Monitor(*const c_void);
+
+impl Win32Monitor {
+    /// Wraps a Windows `HMONITOR` handle.
+    ///
+    /// # Safety
+    ///
+    /// - `hmonitor` must be a valid handle as returned by the Win32 API.
+    #[inline]
+    pub unsafe fn new<T>(hmonitor: *const T) -> Win32Monitor {
+        Win32Monitor(hmonitor as *const c_void)
+    }
+}
+
+// The handle is only passed to the implementation and never dereferenced by us.
+unsafe impl Send for Win32Monitor {
+}
+unsafe impl Sync for Win32Monitor {
+}
+
 /// Error that can happen when creation a swapchain.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SwapchainCreationError {
@@ -629,6 +1011,13 @@ pub enum SwapchainCreationError {
     UnsupportedCompositeAlpha,
     /// The requested present mode is not supported by the surface.
     UnsupportedPresentMode,
+    /// None of the queue families of the device can present to the surface.
+    UnsupportedSurface,
+    /// A fullscreen exclusivity mode other than `Default` was requested, but the
+    /// `ext_full_screen_exclusive` extension was not enabled.
+    FullscreenExclusiveExtensionNotEnabled,
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for SwapchainCreationError {
@@ -686,6 +1075,15 @@ impl error::Error for SwapchainCreationError {
             SwapchainCreationError::UnsupportedPresentMode => {
                 "the requested present mode is not supported by the surface"
             },
+            SwapchainCreationError::UnsupportedSurface => {
+                "none of the queue families of the device can present to the surface"
+            },
+            SwapchainCreationError::FullscreenExclusiveExtensionNotEnabled => {
+                "a fullscreen exclusivity mode was requested, but the `ext_full_screen_exclusive` \
+                 extension was not enabled"
+            },
+            SwapchainCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -698,50 +1096,108 @@ impl error::Error for SwapchainCreationError {
     }
 }
 
-impl fmt::Display for SwapchainCreationError {
+vk_error_conversions!(SwapchainCreationError, OomError {
+    DeviceLost => DeviceLost,
+    SurfaceLost => SurfaceLost,
+    NativeWindowInUse => NativeWindowInUse,
+});
+
+impl From<ImageViewCreationError> for SwapchainCreationError {
     #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
+    fn from(err: ImageViewCreationError) -> SwapchainCreationError {
+        match err {
+            ImageViewCreationError::OomError(err) => SwapchainCreationError::OomError(err),
+            ImageViewCreationError::UnexpectedResult(code) => {
+                SwapchainCreationError::UnexpectedResult(code)
+            },
+        }
     }
 }
 
-impl From<Error> for SwapchainCreationError {
+impl From<CapabilitiesError> for SwapchainCreationError {
     #[inline]
-    fn from(err: Error) -> SwapchainCreationError {
+    fn from(err: CapabilitiesError) -> SwapchainCreationError {
         match err {
-            err @ Error::OutOfHostMemory => {
-                SwapchainCreationError::OomError(OomError::from(err))
+            CapabilitiesError::OomError(err) => SwapchainCreationError::OomError(err),
+            CapabilitiesError::SurfaceLost => SwapchainCreationError::SurfaceLost,
+        }
+    }
+}
+
+/// Error that can happen when acquiring or releasing fullscreen exclusivity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FullscreenExclusiveError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    /// Fullscreen exclusivity couldn't be acquired for implementation-specific reasons.
+    InitializationFailed,
+
+    /// The swapchain wasn't created with `FullscreenExclusive::AppControlled`.
+    NotAppControlled,
+
+    /// Fullscreen exclusivity is already held by the swapchain.
+    DoubleAcquire,
+
+    /// Fullscreen exclusivity isn't held by the swapchain.
+    DoubleRelease,
+}
+
+impl error::Error for FullscreenExclusiveError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            FullscreenExclusiveError::OomError(_) => "not enough memory",
+            FullscreenExclusiveError::SurfaceLost => {
+                "the surface of this swapchain is no longer valid"
             },
-            err @ Error::OutOfDeviceMemory => {
-                SwapchainCreationError::OomError(OomError::from(err))
+            FullscreenExclusiveError::InitializationFailed => {
+                "fullscreen exclusivity couldn't be acquired"
             },
-            Error::DeviceLost => {
-                SwapchainCreationError::DeviceLost
+            FullscreenExclusiveError::NotAppControlled => {
+                "the swapchain wasn't created with application-controlled fullscreen exclusivity"
             },
-            Error::SurfaceLost => {
-                SwapchainCreationError::SurfaceLost
+            FullscreenExclusiveError::DoubleAcquire => {
+                "fullscreen exclusivity is already held by the swapchain"
             },
-            Error::NativeWindowInUse => {
-                SwapchainCreationError::NativeWindowInUse
+            FullscreenExclusiveError::DoubleRelease => {
+                "fullscreen exclusivity isn't held by the swapchain"
             },
-            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            FullscreenExclusiveError::OomError(ref err) => Some(err),
+            _ => None,
         }
     }
 }
 
-impl From<OomError> for SwapchainCreationError {
+impl fmt::Display for FullscreenExclusiveError {
     #[inline]
-    fn from(err: OomError) -> SwapchainCreationError {
-        SwapchainCreationError::OomError(err)
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
     }
 }
 
-impl From<CapabilitiesError> for SwapchainCreationError {
+impl From<Error> for FullscreenExclusiveError {
     #[inline]
-    fn from(err: CapabilitiesError) -> SwapchainCreationError {
+    fn from(err: Error) -> FullscreenExclusiveError {
         match err {
-            CapabilitiesError::OomError(err) => SwapchainCreationError::OomError(err),
-            CapabilitiesError::SurfaceLost => SwapchainCreationError::SurfaceLost,
+            err @ Error::OutOfHostMemory => {
+                FullscreenExclusiveError::OomError(OomError::from(err))
+            },
+            err @ Error::OutOfDeviceMemory => {
+                FullscreenExclusiveError::OomError(OomError::from(err))
+            },
+            Error::SurfaceLost => FullscreenExclusiveError::SurfaceLost,
+            Error::InitializationFailed => FullscreenExclusiveError::InitializationFailed,
+            _ => panic!("unexpected error: {:?}", err),
         }
     }
 }
@@ -751,6 +1207,8 @@ impl From<CapabilitiesError> for SwapchainCreationError {
 pub struct SwapchainAcquireFuture<W> {
     swapchain: Arc<Swapchain<W>>,
     image_id: usize,
+    // True if the implementation returned `VK_SUBOPTIMAL_KHR` when acquiring the image.
+    suboptimal: bool,
     // Semaphore that is signalled when the acquire is complete. Empty if the acquire has already
     // happened.
     semaphore: Option<Semaphore>,
@@ -772,6 +1230,15 @@ impl<W> SwapchainAcquireFuture<W> {
     pub fn swapchain(&self) -> &Arc<Swapchain<W>> {
         &self.swapchain
     }
+
+    /// Returns true if the swapchain no longer matches the surface exactly.
+    ///
+    /// The image can still be drawn on and presented, but you should recreate the swapchain
+    /// (for example with `recreate_with_dimension`) for optimal presentation.
+    #[inline]
+    pub fn is_suboptimal(&self) -> bool {
+        self.suboptimal
+    }
 }
 
 unsafe impl<W> GpuFuture for SwapchainAcquireFuture<W> {
@@ -897,6 +1364,13 @@ pub enum AcquireError {
     /// The surface has changed in a way that makes the swapchain unusable. You must query the
     /// surface's new properties and recreate a new swapchain if you want to continue drawing.
     OutOfDate,
+
+    /// The swapchain has lost or doesn't have fullscreen exclusivity, possibly for
+    /// implementation-specific reasons outside of the application's control.
+    FullscreenExclusiveLost,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for AcquireError {
@@ -908,6 +1382,11 @@ impl error::Error for AcquireError {
             AcquireError::Timeout => "no image is available for acquiring yet",
             AcquireError::SurfaceLost => "the surface of this swapchain is no longer valid",
             AcquireError::OutOfDate => "the swapchain needs to be recreated",
+            AcquireError::FullscreenExclusiveLost => {
+                "the swapchain no longer has fullscreen exclusivity"
+            },
+            AcquireError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -920,30 +1399,29 @@ impl error::Error for AcquireError {
     }
 }
 
-impl fmt::Display for AcquireError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
+vk_error_conversions!(AcquireError, OomError {
+    DeviceLost => DeviceLost,
+    SurfaceLost => SurfaceLost,
+    OutOfDate => OutOfDate,
+    FullscreenExclusiveLost => FullscreenExclusiveLost,
+});
 
-impl From<OomError> for AcquireError {
+impl From<FenceCreationError> for AcquireError {
     #[inline]
-    fn from(err: OomError) -> AcquireError {
-        AcquireError::OomError(err)
+    fn from(err: FenceCreationError) -> AcquireError {
+        match err {
+            FenceCreationError::OomError(err) => AcquireError::OomError(err),
+            FenceCreationError::UnexpectedResult(code) => AcquireError::UnexpectedResult(code),
+        }
     }
 }
 
-impl From<Error> for AcquireError {
+impl From<SemaphoreCreationError> for AcquireError {
     #[inline]
-    fn from(err: Error) -> AcquireError {
+    fn from(err: SemaphoreCreationError) -> AcquireError {
         match err {
-            err @ Error::OutOfHostMemory => AcquireError::OomError(OomError::from(err)),
-            err @ Error::OutOfDeviceMemory => AcquireError::OomError(OomError::from(err)),
-            Error::DeviceLost => AcquireError::DeviceLost,
-            Error::SurfaceLost => AcquireError::SurfaceLost,
-            Error::OutOfDate => AcquireError::OutOfDate,
-            _ => panic!("unexpected error: {:?}", err),
+            SemaphoreCreationError::OomError(err) => AcquireError::OomError(err),
+            SemaphoreCreationError::UnexpectedResult(code) => AcquireError::UnexpectedResult(code),
         }
     }
 }
@@ -1131,7 +1609,7 @@ impl<P, W> Drop for PresentFuture<P, W>
                 match self.flush() {
                     Ok(()) => {
                         // Block until the queue finished.
-                        self.queue().unwrap().wait().unwrap();
+                        self.queue().unwrap().wait_idle().unwrap();
                         self.previous.signal_finished();
                     },
                     Err(_) => {
@@ -1159,6 +1637,10 @@ pub struct AcquiredImage {
 pub unsafe fn acquire_next_image_raw<W>(swapchain: &Swapchain<W>, timeout: Option<Duration>,
                                      semaphore: Option<&Semaphore>, fence: Option<&Fence>)
                                               -> Result<AcquiredImage, AcquireError> {
+    if swapchain.device.is_lost() {
+        return Err(AcquireError::DeviceLost);
+    }
+
     let vk = swapchain.device.pointers();
 
     let timeout_ns = if let Some(timeout) = timeout {
@@ -1177,7 +1659,8 @@ pub unsafe fn acquire_next_image_raw<W>(swapchain: &Swapchain<W>, timeout: Optio
                                             timeout_ns,
                                             semaphore.map(|s| s.internal_object()).unwrap_or(0),
                                             fence.map(|f| f.internal_object()).unwrap_or(0),
-                                            &mut out))?;
+                                            &mut out));
+    let r = swapchain.device.track_lost(r)?;
 
     let (id, suboptimal) = match r {
         Success::Success => (out as usize, false),
@@ -1187,5 +1670,8 @@ pub unsafe fn acquire_next_image_raw<W>(swapchain: &Swapchain<W>, timeout: Optio
         s => panic!("unexpected success value: {:?}", s),
     };
 
-    Ok(AcquiredImage { id, suboptimal })
+    Ok(AcquiredImage {
+           id: id,
+           suboptimal: suboptimal,
+       })
 }
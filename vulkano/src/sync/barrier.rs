@@ -0,0 +1,133 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::ops::Range;
+
+use buffer::BufferAccess;
+use image::ImageAccess;
+use image::ImageLayout;
+use sync::AccessFlagBits;
+use sync::PipelineStages;
+
+/// Memory barrier that applies to all the resources.
+#[derive(Debug, Copy, Clone)]
+pub struct MemoryBarrier {
+    /// Stages of the previous commands that must finish before the barrier.
+    pub source_stages: PipelineStages,
+    /// Memory accesses of the previous commands that must be made available.
+    pub source_access: AccessFlagBits,
+    /// Stages of the following commands that must wait for the barrier.
+    pub destination_stages: PipelineStages,
+    /// Memory accesses of the following commands that must see the previous accesses.
+    pub destination_access: AccessFlagBits,
+}
+
+/// Memory barrier that applies to a range of a buffer.
+///
+/// Also allows transferring the ownership of the buffer between queue families.
+#[derive(Debug, Clone)]
+pub struct BufferMemoryBarrier<B> {
+    /// The buffer the barrier applies to.
+    pub buffer: B,
+    /// Offset in bytes of the range of the buffer.
+    pub offset: usize,
+    /// Size in bytes of the range of the buffer.
+    pub size: usize,
+    /// Stages of the previous commands that must finish before the barrier.
+    pub source_stages: PipelineStages,
+    /// Memory accesses of the previous commands that must be made available.
+    pub source_access: AccessFlagBits,
+    /// Stages of the following commands that must wait for the barrier.
+    pub destination_stages: PipelineStages,
+    /// Memory accesses of the following commands that must see the previous accesses.
+    pub destination_access: AccessFlagBits,
+    /// If `Some`, the ids of the source and destination queue families of an ownership transfer.
+    /// One of them must be the queue family of the command buffer.
+    pub queue_family_transfer: Option<(u32, u32)>,
+}
+
+impl<B> BufferMemoryBarrier<B>
+    where B: BufferAccess
+{
+    /// Builds a barrier that applies to the whole buffer, without any ownership transfer.
+    #[inline]
+    pub fn new(buffer: B, source_stages: PipelineStages, source_access: AccessFlagBits,
+               destination_stages: PipelineStages, destination_access: AccessFlagBits)
+               -> BufferMemoryBarrier<B> {
+        let size = buffer.size();
+
+        BufferMemoryBarrier {
+            buffer: buffer,
+            offset: 0,
+            size: size,
+            source_stages: source_stages,
+            source_access: source_access,
+            destination_stages: destination_stages,
+            destination_access: destination_access,
+            queue_family_transfer: None,
+        }
+    }
+}
+
+/// Memory barrier that applies to a range of mipmap levels and array layers of an image.
+///
+/// Also allows transferring the ownership of the image between queue families.
+///
+/// The layouts of the image are handled automatically. The image is transitioned to `layout`
+/// before the barrier if necessary, and stays in this layout afterwards.
+#[derive(Debug, Clone)]
+pub struct ImageMemoryBarrier<I> {
+    /// The image the barrier applies to.
+    pub image: I,
+    /// Range of mipmap levels the barrier applies to.
+    pub mipmaps: Range<u32>,
+    /// Range of array layers the barrier applies to.
+    pub layers: Range<u32>,
+    /// Layout of the image during the barrier. Can't be `Undefined` or `Preinitialized`.
+    pub layout: ImageLayout,
+    /// Stages of the previous commands that must finish before the barrier.
+    pub source_stages: PipelineStages,
+    /// Memory accesses of the previous commands that must be made available.
+    pub source_access: AccessFlagBits,
+    /// Stages of the following commands that must wait for the barrier.
+    pub destination_stages: PipelineStages,
+    /// Memory accesses of the following commands that must see the previous accesses.
+    pub destination_access: AccessFlagBits,
+    /// If `Some`, the ids of the source and destination queue families of an ownership transfer.
+    /// One of them must be the queue family of the command buffer.
+    pub queue_family_transfer: Option<(u32, u32)>,
+}
+
+impl<I> ImageMemoryBarrier<I>
+    where I: ImageAccess
+{
+    /// Builds a barrier that applies to all the mipmap levels and array layers of the image,
+    /// without any ownership transfer. The layout is the one returned by
+    /// `initial_layout_requirement()`.
+    #[inline]
+    pub fn new(image: I, source_stages: PipelineStages, source_access: AccessFlagBits,
+               destination_stages: PipelineStages, destination_access: AccessFlagBits)
+               -> ImageMemoryBarrier<I> {
+        let mipmaps = 0 .. image.mipmap_levels();
+        let layers = 0 .. image.dimensions().array_layers();
+        let layout = image.initial_layout_requirement();
+
+        ImageMemoryBarrier {
+            image: image,
+            mipmaps: mipmaps,
+            layers: layers,
+            layout: layout,
+            source_stages: source_stages,
+            source_access: source_access,
+            destination_stages: destination_stages,
+            destination_access: destination_access,
+            queue_family_transfer: None,
+        }
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::any::Any;
+use std::mem;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use device::Device;
+use device::DeviceOwned;
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+
+use VulkanObject;
+
+/// Holds objects that are still used by work in flight, and destroys them once the fence that
+/// marks the end of that work is signaled.
+///
+/// Normally, dropping a `FenceSignalFuture` whose submission isn't finished blocks the current
+/// thread until the GPU is done. Instead, the future can be handed to a deletion queue with
+/// `push_future`, in which case it is destroyed later without blocking. Any other object that
+/// must outlive a submission, for example a buffer that the application no longer needs, can be
+/// handed to the queue with `push_after`.
+///
+/// A deletion queue is created with `DeletionQueue::new` and registered to its device. The
+/// finished entries are destroyed whenever a new fence signal future is created on the device,
+/// which typically happens once per submission, and by `Device::wait_idle` and
+/// `Queue::wait_idle`. They can also be destroyed explicitly with `cleanup`.
+///
+/// The device only keeps a weak reference to its deletion queues. Dropping the last `Arc` to a
+/// queue blocks until all of its entries are finished.
+pub struct DeletionQueue {
+    device: Arc<Device>,
+    entries: Mutex<Vec<Entry>>,
+}
+
+// An object and the future whose fence must be signaled before the object can be destroyed.
+struct Entry {
+    // Declared first so that it is dropped before `future`.
+    object: Option<Box<Any + Send + Sync>>,
+    future: Box<PendingFuture>,
+}
+
+// Type-erased version of `Arc<FenceSignalFuture<_>>`.
+trait PendingFuture: Send + Sync {
+    // Returns true if the fence of the future is signaled. Never blocks.
+    fn is_signaled(&self) -> bool;
+}
+
+impl<F> PendingFuture for Arc<FenceSignalFuture<F>>
+    where F: GpuFuture + Send + Sync
+{
+    #[inline]
+    fn is_signaled(&self) -> bool {
+        FenceSignalFuture::is_signaled(self)
+    }
+}
+
+impl DeletionQueue {
+    /// Builds an empty queue and registers it to `device`.
+    pub fn new(device: Arc<Device>) -> Arc<DeletionQueue> {
+        let queue = Arc::new(DeletionQueue {
+                                 device: device,
+                                 entries: Mutex::new(Vec::new()),
+                             });
+        queue.device.register_deletion_queue(&queue);
+        queue
+    }
+
+    /// Flushes `future` if necessary, then keeps it until its fence is signaled.
+    ///
+    /// Contrary to dropping the future, this never blocks. The resources used by the submissions
+    /// of the future are released once the fence is signaled.
+    ///
+    /// Returns an error if flushing failed, in which case the future is dropped immediately.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the future doesn't belong to the device of the queue.
+    ///
+    pub fn push_future<F>(&self, future: FenceSignalFuture<F>) -> Result<(), FlushError>
+        where F: GpuFuture + Send + Sync + 'static
+    {
+        assert_eq!(future.device().internal_object(),
+                   self.device.internal_object());
+
+        let future = Arc::new(future);
+        future.flush()?;
+        self.entries
+            .lock()
+            .unwrap()
+            .push(Entry {
+                      object: None,
+                      future: Box::new(future) as Box<_>,
+                  });
+        Ok(())
+    }
+
+    /// Keeps `object` alive until the fence of `future` is signaled, then destroys it.
+    ///
+    /// `future` is flushed if necessary. Returns an error if flushing failed, in which case
+    /// `object` is dropped immediately.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the future doesn't belong to the device of the queue.
+    ///
+    pub fn push_after<F, T>(&self, future: Arc<FenceSignalFuture<F>>, object: T)
+                            -> Result<(), FlushError>
+        where F: GpuFuture + Send + Sync + 'static,
+              T: Send + Sync + 'static
+    {
+        assert_eq!(future.device().internal_object(),
+                   self.device.internal_object());
+
+        future.flush()?;
+        self.entries
+            .lock()
+            .unwrap()
+            .push(Entry {
+                      object: Some(Box::new(object) as Box<_>),
+                      future: Box::new(future) as Box<_>,
+                  });
+        Ok(())
+    }
+
+    /// Destroys the entries whose fence is signaled. Never blocks.
+    ///
+    /// Returns the number of entries that have been destroyed.
+    pub fn cleanup(&self) -> usize {
+        // The entries are destroyed after the lock is released, in case their destructor
+        // accesses the queue.
+        let finished = {
+            let mut entries = self.entries.lock().unwrap();
+            let (finished, pending): (Vec<_>, Vec<_>) =
+                mem::replace(&mut *entries, Vec::new())
+                    .into_iter()
+                    .partition(|e| e.future.is_signaled());
+            *entries = pending;
+            finished
+        };
+
+        finished.len()
+    }
+
+    /// Returns the number of entries that are waiting to be destroyed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns true if no entry is waiting to be destroyed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+unsafe impl DeviceOwned for DeletionQueue {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::AutoCommandBuffer;
+    use command_buffer::AutoCommandBufferBuilder;
+    use command_buffer::CommandBuffer;
+    use device::Device;
+    use device::Queue;
+    use std::sync::Arc;
+    use sync::DeletionQueue;
+    use sync::GpuFuture;
+
+    fn fill_command_buffer(device: &Arc<Device>, queue: &Arc<Queue>,
+                           buffer: Arc<CpuAccessibleBuffer<u32>>)
+                           -> AutoCommandBuffer {
+        AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), queue.family())
+            .unwrap()
+            .fill_buffer(buffer, 12)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn future_released_after_wait_idle() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let deletion_queue = DeletionQueue::new(device.clone());
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let cb = fill_command_buffer(&device, &queue, buffer.clone());
+
+        let future = cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        deletion_queue.push_future(future).unwrap();
+        assert_eq!(deletion_queue.len(), 1);
+
+        device.wait_idle().unwrap();
+        assert!(deletion_queue.is_empty());
+        assert_eq!(Arc::strong_count(&buffer), 1);
+    }
+
+    #[test]
+    fn object_released_after_queue_wait_idle() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let deletion_queue = DeletionQueue::new(device.clone());
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let weak = Arc::downgrade(&buffer);
+        let cb = fill_command_buffer(&device, &queue, buffer.clone());
+
+        let future = Arc::new(cb.execute(queue.clone())
+                                  .unwrap()
+                                  .then_signal_fence_and_flush()
+                                  .unwrap());
+        deletion_queue.push_after(future.clone(), buffer).unwrap();
+
+        queue.wait_idle().unwrap();
+        assert!(deletion_queue.is_empty());
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn object_released_after_cleanup() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let deletion_queue = DeletionQueue::new(device.clone());
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let weak = Arc::downgrade(&buffer);
+        let cb = fill_command_buffer(&device, &queue, buffer.clone());
+
+        let future = Arc::new(cb.execute(queue.clone())
+                                  .unwrap()
+                                  .then_signal_fence_and_flush()
+                                  .unwrap());
+        deletion_queue.push_after(future.clone(), buffer).unwrap();
+
+        future.wait(None).unwrap();
+        assert_eq!(deletion_queue.cleanup(), 1);
+        assert!(deletion_queue.is_empty());
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn object_released_on_submit() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let deletion_queue = DeletionQueue::new(device.clone());
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let weak = Arc::downgrade(&buffer);
+        let cb = fill_command_buffer(&device, &queue, buffer.clone());
+
+        let future = Arc::new(cb.execute(queue.clone())
+                                  .unwrap()
+                                  .then_signal_fence_and_flush()
+                                  .unwrap());
+        deletion_queue.push_after(future.clone(), buffer).unwrap();
+        future.wait(None).unwrap();
+
+        // Signaling a new fence on the device destroys the finished entries.
+        let other_buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+        let cb = fill_command_buffer(&device, &queue, other_buffer);
+        let other_future = cb.execute(queue.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        assert!(deletion_queue.is_empty());
+        assert!(weak.upgrade().is_none());
+
+        other_future.wait(None).unwrap();
+    }
+
+    #[test]
+    fn wrong_device() {
+        let (device1, queue1) = gfx_dev_and_queue!();
+        let (device2, _) = gfx_dev_and_queue!();
+        let deletion_queue = DeletionQueue::new(device2);
+
+        let buffer =
+            CpuAccessibleBuffer::from_data(device1.clone(), BufferUsage::all(), 0u32).unwrap();
+        let cb = fill_command_buffer(&device1, &queue1, buffer);
+        let future = cb.execute(queue1.clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+
+        assert_should_panic!({
+                                 let _ = deletion_queue.push_future(future);
+                             });
+    }
+
+    #[test]
+    fn device_freed() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let weak_device = Arc::downgrade(&device);
+
+        {
+            let deletion_queue = DeletionQueue::new(device.clone());
+            let buffer =
+                CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), 0u32).unwrap();
+            let cb = fill_command_buffer(&device, &queue, buffer);
+
+            let future = cb.execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap();
+            deletion_queue.push_future(future).unwrap();
+        }
+
+        drop(queue);
+        drop(device);
+        assert!(weak_device.upgrade().is_none());
+    }
+}
@@ -7,6 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
 use std::mem;
 use std::ptr;
 use std::sync::Arc;
@@ -32,6 +33,8 @@ pub struct Event {
     // The device.
     device: Arc<Device>,
     must_put_in_pool: bool,
+    // False if the event was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl Event {
@@ -41,7 +44,7 @@ impl Event {
     ///
     /// For most applications, using the event pool should be preferred,
     /// in order to avoid creating new events every frame.
-    pub fn from_pool(device: Arc<Device>) -> Result<Event, OomError> {
+    pub fn from_pool(device: Arc<Device>) -> Result<Event, EventCreationError> {
         let maybe_raw_event = device.event_pool().lock().unwrap().pop();
         match maybe_raw_event {
             Some(raw_event) => {
@@ -54,6 +57,7 @@ impl Event {
                        event: raw_event,
                        device: device,
                        must_put_in_pool: true,
+                       needs_destruction: true,
                    })
             },
             None => {
@@ -65,11 +69,12 @@ impl Event {
 
     /// Builds a new event.
     #[inline]
-    pub fn alloc(device: Arc<Device>) -> Result<Event, OomError> {
+    pub fn alloc(device: Arc<Device>) -> Result<Event, EventCreationError> {
         Event::alloc_impl(device, false)
     }
 
-    fn alloc_impl(device: Arc<Device>, must_put_in_pool: bool) -> Result<Event, OomError> {
+    fn alloc_impl(device: Arc<Device>, must_put_in_pool: bool)
+                  -> Result<Event, EventCreationError> {
         let event = unsafe {
             // since the creation is constant, we use a `static` instead of a struct on the stack
             static mut INFOS: vk::EventCreateInfo = vk::EventCreateInfo {
@@ -91,12 +96,34 @@ impl Event {
                device: device,
                event: event,
                must_put_in_pool: must_put_in_pool,
+               needs_destruction: true,
            })
     }
 
+    /// Builds an `Event` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the event is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it. The event is never put in
+    /// the event pool.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid event of `device`.
+    /// - If `owned` is true, the event must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::Event, owned: bool) -> Event {
+        Event {
+            event: handle,
+            device: device,
+            must_put_in_pool: false,
+            needs_destruction: owned,
+        }
+    }
+
     /// Returns true if the event is signaled.
     #[inline]
-    pub fn signaled(&self) -> Result<bool, OomError> {
+    pub fn signaled(&self) -> Result<bool, EventStatusError> {
         unsafe {
             let vk = self.device.pointers();
             let result = check_errors(vk.GetEventStatus(self.device.internal_object(),
@@ -179,7 +206,7 @@ impl Drop for Event {
             if self.must_put_in_pool {
                 let raw_event = self.event;
                 self.device.event_pool().lock().unwrap().push(raw_event);
-            } else {
+            } else if self.needs_destruction {
                 let vk = self.device.pointers();
                 vk.DestroyEvent(self.device.internal_object(), self.event, ptr::null());
             }
@@ -187,6 +214,74 @@ impl Drop for Event {
     }
 }
 
+/// Error that can happen when creating an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for EventCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            EventCreationError::OomError(_) => "not enough memory available",
+            EventCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            EventCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(EventCreationError, OomError {});
+
+/// Error that can be returned when querying the status of an event.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventStatusError {
+    /// Not enough memory to query the status.
+    OomError(OomError),
+
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for EventStatusError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            EventStatusError::OomError(_) => "not enough memory",
+            EventStatusError::DeviceLost => "the connection to the device has been lost",
+            EventStatusError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            EventStatusError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(EventStatusError, OomError {
+    DeviceLost => DeviceLost,
+});
+
 #[cfg(test)]
 mod tests {
     use VulkanObject;
@@ -199,6 +294,18 @@ mod tests {
         assert!(!event.signaled().unwrap());
     }
 
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+        let mut event = Event::alloc(device.clone()).unwrap();
+        event.set();
+
+        let borrowed = unsafe { Event::from_raw(device, event.internal_object(), false) };
+        assert!(borrowed.signaled().unwrap());
+        drop(borrowed);
+        assert!(event.signaled().unwrap());
+    }
+
     #[test]
     fn event_set() {
         let (device, _) = gfx_dev_and_queue!();
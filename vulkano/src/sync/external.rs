@@ -0,0 +1,157 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+
+use OomError;
+use vk;
+
+/// Type of external handle that the payload of a semaphore can be exported to or imported from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ExternalSemaphoreHandleType {
+    /// POSIX file descriptor that is only meaningful to a Vulkan implementation that shares the
+    /// same driver and device.
+    OpaqueFd = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_FD_BIT_KHR,
+    /// NT handle that is only meaningful to a Vulkan implementation that shares the same driver
+    /// and device.
+    OpaqueWin32 = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_BIT_KHR,
+    /// Global share handle that is only meaningful to a Vulkan implementation that shares the
+    /// same driver and device.
+    OpaqueWin32Kmt = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT_KHR,
+    /// NT handle to a Direct3D 12 fence.
+    D3D12Fence = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_D3D12_FENCE_BIT_KHR,
+    /// Linux sync file descriptor. Can only be imported temporarily.
+    SyncFd = vk::EXTERNAL_SEMAPHORE_HANDLE_TYPE_SYNC_FD_BIT_KHR,
+}
+
+impl ExternalSemaphoreHandleType {
+    /// Returns true if the handle is a file descriptor, and false if it is a Win32 handle.
+    #[inline]
+    pub fn is_fd(&self) -> bool {
+        match *self {
+            ExternalSemaphoreHandleType::OpaqueFd |
+            ExternalSemaphoreHandleType::SyncFd => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the device extension that is needed to export or import this type of
+    /// handle.
+    #[inline]
+    pub(crate) fn extension_name(&self) -> &'static str {
+        if self.is_fd() {
+            "VK_KHR_external_semaphore_fd"
+        } else {
+            "VK_KHR_external_semaphore_win32"
+        }
+    }
+}
+
+/// Type of external handle that the payload of a fence can be exported to or imported from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ExternalFenceHandleType {
+    /// POSIX file descriptor that is only meaningful to a Vulkan implementation that shares the
+    /// same driver and device.
+    OpaqueFd = vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_FD_BIT_KHR,
+    /// NT handle that is only meaningful to a Vulkan implementation that shares the same driver
+    /// and device.
+    OpaqueWin32 = vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_BIT_KHR,
+    /// Global share handle that is only meaningful to a Vulkan implementation that shares the
+    /// same driver and device.
+    OpaqueWin32Kmt = vk::EXTERNAL_FENCE_HANDLE_TYPE_OPAQUE_WIN32_KMT_BIT_KHR,
+    /// Linux sync file descriptor. Can only be imported temporarily.
+    SyncFd = vk::EXTERNAL_FENCE_HANDLE_TYPE_SYNC_FD_BIT_KHR,
+}
+
+impl ExternalFenceHandleType {
+    /// Returns true if the handle is a file descriptor, and false if it is a Win32 handle.
+    #[inline]
+    pub fn is_fd(&self) -> bool {
+        match *self {
+            ExternalFenceHandleType::OpaqueFd |
+            ExternalFenceHandleType::SyncFd => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the name of the device extension that is needed to export or import this type of
+    /// handle.
+    #[inline]
+    pub(crate) fn extension_name(&self) -> &'static str {
+        if self.is_fd() {
+            "VK_KHR_external_fence_fd"
+        } else {
+            "VK_KHR_external_fence_win32"
+        }
+    }
+}
+
+/// Error that can be returned when creating, exporting or importing an external semaphore or
+/// fence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExternalHandleError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// An extension that is needed for this operation is not enabled on the device.
+    MissingExtension {
+        /// Name of the missing extension.
+        name: &'static str,
+    },
+
+    /// The object wasn't created as exportable to this type of handle.
+    NotExportable,
+
+    /// The implementation couldn't create a new handle.
+    TooManyObjects,
+
+    /// The handle to import is not valid or is not of the correct type.
+    InvalidExternalHandle,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for ExternalHandleError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ExternalHandleError::OomError(_) => "not enough memory",
+            ExternalHandleError::MissingExtension { .. } => {
+                "an extension that is needed for this operation is not enabled on the device"
+            },
+            ExternalHandleError::NotExportable => {
+                "the object wasn't created as exportable to this type of handle"
+            },
+            ExternalHandleError::TooManyObjects => {
+                "the implementation couldn't create a new handle"
+            },
+            ExternalHandleError::InvalidExternalHandle => {
+                "the handle to import is not valid or is not of the correct type"
+            },
+            ExternalHandleError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            ExternalHandleError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(ExternalHandleError, OomError {
+    TooManyObjects => TooManyObjects,
+    InvalidExternalHandle => InvalidExternalHandle,
+});
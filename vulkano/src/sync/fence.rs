@@ -9,15 +9,15 @@
 
 use smallvec::SmallVec;
 use std::error;
-use std::fmt;
 use std::mem;
+use std::os::raw::c_int;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
-use Error;
 use OomError;
 use SafeDeref;
 use Success;
@@ -25,6 +25,8 @@ use VulkanObject;
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use sync::ExternalFenceHandleType;
+use sync::ExternalHandleError;
 use vk;
 
 /// A fence is used to know when a command buffer submission has finished its execution.
@@ -32,6 +34,16 @@ use vk;
 /// When a command buffer accesses a ressource, you have to ensure that the CPU doesn't access
 /// the same ressource simultaneously (except for concurrent reads). Therefore in order to know
 /// when the CPU can access a ressource again, a fence has to be used.
+///
+/// # External fences
+///
+/// Similarly to semaphores, a fence created with `alloc_exportable` can share its payload with
+/// other APIs or other processes by exporting it as a file descriptor or a Win32 handle, and the
+/// payload of a handle created elsewhere can be imported with `import_fd` or
+/// `import_win32_handle`.
+///
+/// This requires the `khr_external_fence` extension, plus `khr_external_fence_fd` or
+/// `khr_external_fence_win32` depending on the type of handle.
 #[derive(Debug)]
 pub struct Fence<D = Arc<Device>>
     where D: SafeDeref<Target = Device>
@@ -48,6 +60,12 @@ pub struct Fence<D = Arc<Device>>
     // Indicates whether this fence was taken from the fence pool.
     // If true, will be put back into fence pool on drop.
     must_put_in_pool: bool,
+
+    // Type of handle the fence can be exported to, if any.
+    export_handle_type: Option<ExternalFenceHandleType>,
+
+    // False if the fence was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<D> Fence<D>
@@ -59,7 +77,7 @@ impl<D> Fence<D>
     ///
     /// For most applications, using the fence pool should be preferred,
     /// in order to avoid creating new fences every frame.
-    pub fn from_pool(device: D) -> Result<Fence<D>, OomError> {
+    pub fn from_pool(device: D) -> Result<Fence<D>, FenceCreationError> {
         let maybe_raw_fence = device.fence_pool().lock().unwrap().pop();
         match maybe_raw_fence {
             Some(raw_fence) => {
@@ -73,6 +91,8 @@ impl<D> Fence<D>
                        device: device,
                        signaled: AtomicBool::new(false),
                        must_put_in_pool: true,
+                       export_handle_type: None,
+                       needs_destruction: true,
                    })
             },
             None => {
@@ -82,19 +102,42 @@ impl<D> Fence<D>
         }
     }
 
+    /// Builds a `Fence` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the fence is destroyed when the returned object is dropped. Otherwise,
+    /// the caller remains responsible for destroying it. The fence is never put in the fence pool.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid fence of `device`.
+    /// - If `owned` is true, the fence must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: D, handle: vk::Fence, owned: bool) -> Fence<D> {
+        Fence {
+            fence: handle,
+            device: device,
+            signaled: AtomicBool::new(false),
+            must_put_in_pool: false,
+            export_handle_type: None,
+            needs_destruction: owned,
+        }
+    }
+
     /// Builds a new fence.
     #[inline]
-    pub fn alloc(device: D) -> Result<Fence<D>, OomError> {
+    pub fn alloc(device: D) -> Result<Fence<D>, FenceCreationError> {
         Fence::alloc_impl(device, false, false)
     }
 
     /// Builds a new fence in signaled state.
     #[inline]
-    pub fn alloc_signaled(device: D) -> Result<Fence<D>, OomError> {
+    pub fn alloc_signaled(device: D) -> Result<Fence<D>, FenceCreationError> {
         Fence::alloc_impl(device, true, false)
     }
 
-    fn alloc_impl(device: D, signaled: bool, must_put_in_pool: bool) -> Result<Fence<D>, OomError> {
+    fn alloc_impl(device: D, signaled: bool, must_put_in_pool: bool)
+                  -> Result<Fence<D>, FenceCreationError> {
         let fence = unsafe {
             let infos = vk::FenceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_FENCE_CREATE_INFO,
@@ -120,20 +163,238 @@ impl<D> Fence<D>
                device: device,
                signaled: AtomicBool::new(signaled),
                must_put_in_pool: must_put_in_pool,
+               export_handle_type: None,
+               needs_destruction: true,
            })
     }
 
+    /// Builds a new unsignaled fence whose payload can be exported to a handle of type
+    /// `handle_type`.
+    ///
+    /// The fence is never put in the fence pool.
+    pub fn alloc_exportable(device: D, handle_type: ExternalFenceHandleType)
+                            -> Result<Fence<D>, ExternalHandleError> {
+        if !device.loaded_extensions().khr_external_fence {
+            return Err(ExternalHandleError::MissingExtension { name: "VK_KHR_external_fence" });
+        }
+
+        let fence = unsafe {
+            let export_infos = vk::ExportFenceCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_EXPORT_FENCE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                handleTypes: handle_type as u32,
+            };
+
+            let infos = vk::FenceCreateInfo {
+                sType: vk::STRUCTURE_TYPE_FENCE_CREATE_INFO,
+                pNext: &export_infos as *const _ as *const _,
+                flags: 0,
+            };
+
+            let vk = device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateFence(device.internal_object(),
+                                        &infos,
+                                        ptr::null(),
+                                        &mut output))?;
+            output
+        };
+
+        Ok(Fence {
+               fence: fence,
+               device: device,
+               signaled: AtomicBool::new(false),
+               must_put_in_pool: false,
+               export_handle_type: Some(handle_type),
+               needs_destruction: true,
+           })
+    }
+
+    /// Exports the payload of the fence as a file descriptor. The ownership of the file
+    /// descriptor is transferred to the caller, who is responsible for closing it.
+    ///
+    /// Exporting a `SyncFd` requires the fence to be signaled or to have a pending signal
+    /// operation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the fence was created as exportable to a Win32 handle.
+    ///
+    pub fn export_fd(&self) -> Result<c_int, ExternalHandleError> {
+        let handle_type = self.export_handle_type.ok_or(ExternalHandleError::NotExportable)?;
+        assert!(handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        unsafe {
+            let infos = vk::FenceGetFdInfoKHR {
+                sType: vk::STRUCTURE_TYPE_FENCE_GET_FD_INFO_KHR,
+                pNext: ptr::null(),
+                fence: self.fence,
+                handleType: handle_type as u32,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.GetFenceFdKHR(self.device.internal_object(), &infos, &mut output))?;
+            Ok(output)
+        }
+    }
+
+    /// Exports the payload of the fence as a Win32 handle. For `OpaqueWin32` handles, the caller
+    /// is responsible for closing the handle.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the fence was created as exportable to a file descriptor.
+    ///
+    pub fn export_win32_handle(&self) -> Result<*mut c_void, ExternalHandleError> {
+        let handle_type = self.export_handle_type.ok_or(ExternalHandleError::NotExportable)?;
+        assert!(!handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        unsafe {
+            let infos = vk::FenceGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_FENCE_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                fence: self.fence,
+                handleType: handle_type as u32,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.GetFenceWin32HandleKHR(self.device.internal_object(),
+                                                   &infos,
+                                                   &mut output))?;
+            Ok(output)
+        }
+    }
+
+    /// Replaces the payload of the fence with the payload of a file descriptor. On success, the
+    /// ownership of the file descriptor is transferred to the Vulkan implementation.
+    ///
+    /// If `temporary` is true, the imported payload is only used until the next reset of the
+    /// fence, after which the fence gets its previous payload back. `SyncFd` handles can only be
+    /// imported temporarily.
+    ///
+    /// The fence is not going to be put back in the fence pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is not a file descriptor type.
+    ///
+    /// # Safety
+    ///
+    /// - The fence must not be in use by the GPU.
+    /// - `fd` must be a valid file descriptor of type `handle_type`, that was exported from a
+    ///   fence created with the same driver and device.
+    ///
+    pub unsafe fn import_fd(&mut self, handle_type: ExternalFenceHandleType, fd: c_int,
+                            temporary: bool)
+                            -> Result<(), ExternalHandleError> {
+        assert!(handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        let infos = vk::ImportFenceFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_FENCE_FD_INFO_KHR,
+            pNext: ptr::null(),
+            fence: self.fence,
+            flags: if temporary {
+                vk::FENCE_IMPORT_TEMPORARY_BIT_KHR
+            } else {
+                0
+            },
+            handleType: handle_type as u32,
+            fd: fd,
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportFenceFdKHR(self.device.internal_object(), &infos))?;
+        self.signaled.store(false, Ordering::Relaxed);
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    /// Replaces the payload of the fence with the payload of a Win32 handle. Contrary to file
+    /// descriptors, the ownership of the handle is not transferred.
+    ///
+    /// If `temporary` is true, the imported payload is only used until the next reset of the
+    /// fence, after which the fence gets its previous payload back.
+    ///
+    /// The fence is not going to be put back in the fence pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is not a Win32 handle type.
+    ///
+    /// # Safety
+    ///
+    /// - The fence must not be in use by the GPU.
+    /// - `handle` must be a valid handle of type `handle_type`.
+    ///
+    pub unsafe fn import_win32_handle(&mut self, handle_type: ExternalFenceHandleType,
+                                      handle: *mut c_void, temporary: bool)
+                                      -> Result<(), ExternalHandleError> {
+        assert!(!handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        let infos = vk::ImportFenceWin32HandleInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_FENCE_WIN32_HANDLE_INFO_KHR,
+            pNext: ptr::null(),
+            fence: self.fence,
+            flags: if temporary {
+                vk::FENCE_IMPORT_TEMPORARY_BIT_KHR
+            } else {
+                0
+            },
+            handleType: handle_type as u32,
+            handle: handle,
+            name: ptr::null(),
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportFenceWin32HandleKHR(self.device.internal_object(), &infos))?;
+        self.signaled.store(false, Ordering::Relaxed);
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    // Checks that the extension needed to export or import `handle_type` is enabled.
+    fn check_extension(&self, handle_type: ExternalFenceHandleType)
+                       -> Result<(), ExternalHandleError> {
+        let extensions = self.device.loaded_extensions();
+        let enabled = if handle_type.is_fd() {
+            extensions.khr_external_fence_fd
+        } else {
+            extensions.khr_external_fence_win32
+        };
+
+        if !enabled {
+            return Err(ExternalHandleError::MissingExtension {
+                           name: handle_type.extension_name(),
+                       });
+        }
+
+        Ok(())
+    }
+
     /// Returns true if the fence is signaled.
+    ///
+    /// This function never returns `FenceWaitError::Timeout`.
     #[inline]
-    pub fn ready(&self) -> Result<bool, OomError> {
+    pub fn ready(&self) -> Result<bool, FenceWaitError> {
         unsafe {
             if self.signaled.load(Ordering::Relaxed) {
                 return Ok(true);
             }
 
+            if self.device.is_lost() {
+                return Err(FenceWaitError::DeviceLostError);
+            }
+
             let vk = self.device.pointers();
             let result = check_errors(vk.GetFenceStatus(self.device.internal_object(),
-                                                        self.fence))?;
+                                                        self.fence));
+            let result = self.device.track_lost(result)?;
             match result {
                 Success::Success => {
                     self.signaled.store(true, Ordering::Relaxed);
@@ -165,12 +426,17 @@ impl<D> Fence<D>
                 u64::max_value()
             };
 
+            if self.device.is_lost() {
+                return Err(FenceWaitError::DeviceLostError);
+            }
+
             let vk = self.device.pointers();
             let r = check_errors(vk.WaitForFences(self.device.internal_object(),
                                                   1,
                                                   &self.fence,
                                                   vk::TRUE,
-                                                  timeout_ns))?;
+                                                  timeout_ns));
+            let r = self.device.track_lost(r)?;
 
             match r {
                 Success::Success => {
@@ -185,19 +451,54 @@ impl<D> Fence<D>
         }
     }
 
-    /// Waits for multiple fences at once.
+    /// Waits until all the fences are signaled, or at least until the timeout duration has
+    /// elapsed.
+    ///
+    /// Returns `Ok` if all the fences are now signaled. Returns `Err` if the timeout was reached
+    /// instead. If the iterator is empty, returns `Ok` immediately.
     ///
     /// # Panic
     ///
     /// Panics if not all fences belong to the same device.
+    #[inline]
     pub fn multi_wait<'a, I>(iter: I, timeout: Option<Duration>) -> Result<(), FenceWaitError>
         where I: IntoIterator<Item = &'a Fence<D>>,
               D: 'a
+    {
+        Fence::multi_wait_impl(iter, true, timeout)
+    }
+
+    /// Waits until at least one of the fences is signaled, or at least until the timeout
+    /// duration has elapsed.
+    ///
+    /// Returns `Ok` if at least one of the fences is now signaled. Returns `Err` if the timeout
+    /// was reached instead. If the iterator is empty, returns `Ok` immediately.
+    ///
+    /// Vulkan doesn't tell which fence was signaled. Use `ready()` to find out.
+    ///
+    /// # Panic
+    ///
+    /// Panics if not all fences belong to the same device.
+    #[inline]
+    pub fn multi_wait_any<'a, I>(iter: I, timeout: Option<Duration>)
+                                 -> Result<(), FenceWaitError>
+        where I: IntoIterator<Item = &'a Fence<D>>,
+              D: 'a
+    {
+        Fence::multi_wait_impl(iter, false, timeout)
+    }
+
+    fn multi_wait_impl<'a, I>(iter: I, wait_all: bool, timeout: Option<Duration>)
+                              -> Result<(), FenceWaitError>
+        where I: IntoIterator<Item = &'a Fence<D>>,
+              D: 'a
     {
         let mut device: Option<&Device> = None;
+        // True if at least one of the fences is already known to be signaled.
+        let mut any_signaled = false;
 
-        let fences: SmallVec<[vk::Fence; 8]> = iter.into_iter()
-            .filter_map(|fence| {
+        let fences: SmallVec<[&Fence<D>; 8]> = iter.into_iter()
+            .filter(|fence| {
                 match &mut device {
                     dev @ &mut None => *dev = Some(&*fence.device),
                     &mut Some(ref dev)
@@ -207,13 +508,20 @@ impl<D> Fence<D>
                 };
 
                 if fence.signaled.load(Ordering::Relaxed) {
-                    None
+                    any_signaled = true;
+                    false
                 } else {
-                    Some(fence.fence)
+                    true
                 }
             })
             .collect();
 
+        if fences.is_empty() || (!wait_all && any_signaled) {
+            return Ok(());
+        }
+
+        let raw_fences: SmallVec<[vk::Fence; 8]> = fences.iter().map(|f| f.fence).collect();
+
         let timeout_ns = if let Some(timeout) = timeout {
             timeout
                 .as_secs()
@@ -223,21 +531,30 @@ impl<D> Fence<D>
             u64::max_value()
         };
 
-        let r = if let Some(device) = device {
-            unsafe {
-                let vk = device.pointers();
-                check_errors(vk.WaitForFences(device.internal_object(),
-                                              fences.len() as u32,
-                                              fences.as_ptr(),
-                                              vk::TRUE,
-                                              timeout_ns))?
+        let r = unsafe {
+            let device = device.unwrap();
+            if device.is_lost() {
+                return Err(FenceWaitError::DeviceLostError);
             }
-        } else {
-            return Ok(());
+
+            let vk = device.pointers();
+            let r = check_errors(vk.WaitForFences(device.internal_object(),
+                                                  raw_fences.len() as u32,
+                                                  raw_fences.as_ptr(),
+                                                  if wait_all { vk::TRUE } else { vk::FALSE },
+                                                  timeout_ns));
+            device.track_lost(r)?
         };
 
         match r {
-            Success::Success => Ok(()),
+            Success::Success => {
+                if wait_all {
+                    for fence in fences.iter() {
+                        fence.signaled.store(true, Ordering::Relaxed);
+                    }
+                }
+                Ok(())
+            },
             Success::Timeout => Err(FenceWaitError::Timeout),
             _ => unreachable!(),
         }
@@ -324,7 +641,7 @@ impl<D> Drop for Fence<D>
             if self.must_put_in_pool {
                 let raw_fence = self.fence;
                 self.device.fence_pool().lock().unwrap().push(raw_fence);
-            } else {
+            } else if self.needs_destruction {
                 let vk = self.device.pointers();
                 vk.DestroyFence(self.device.internal_object(), self.fence, ptr::null());
             }
@@ -332,6 +649,37 @@ impl<D> Drop for Fence<D>
     }
 }
 
+/// Error that can happen when creating a fence.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FenceCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for FenceCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            FenceCreationError::OomError(_) => "not enough memory available",
+            FenceCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            FenceCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(FenceCreationError, OomError {});
+
 /// Error that can be returned when waiting on a fence.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FenceWaitError {
@@ -343,6 +691,9 @@ pub enum FenceWaitError {
 
     /// The device has been lost.
     DeviceLostError,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for FenceWaitError {
@@ -352,6 +703,8 @@ impl error::Error for FenceWaitError {
             FenceWaitError::OomError(_) => "no memory available",
             FenceWaitError::Timeout => "the timeout has been reached",
             FenceWaitError::DeviceLostError => "the device was lost",
+            FenceWaitError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -364,30 +717,18 @@ impl error::Error for FenceWaitError {
     }
 }
 
-impl fmt::Display for FenceWaitError {
-    #[inline]
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(fmt, "{}", error::Error::description(self))
-    }
-}
-
-impl From<Error> for FenceWaitError {
-    #[inline]
-    fn from(err: Error) -> FenceWaitError {
-        match err {
-            Error::OutOfHostMemory => FenceWaitError::OomError(From::from(err)),
-            Error::OutOfDeviceMemory => FenceWaitError::OomError(From::from(err)),
-            Error::DeviceLost => FenceWaitError::DeviceLostError,
-            _ => panic!("Unexpected error value: {}", err as i32),
-        }
-    }
-}
+vk_error_conversions!(FenceWaitError, OomError {
+    DeviceLost => DeviceLostError,
+});
 
 #[cfg(test)]
 mod tests {
     use VulkanObject;
     use std::time::Duration;
+    use sync::ExternalFenceHandleType;
+    use sync::ExternalHandleError;
     use sync::Fence;
+    use sync::FenceWaitError;
 
     #[test]
     fn fence_create() {
@@ -413,6 +754,21 @@ mod tests {
         fence.wait(Some(Duration::new(0, 10))).unwrap();
     }
 
+    #[test]
+    fn fence_from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence = Fence::alloc_signaled(device.clone()).unwrap();
+        unsafe {
+            let borrowed = Fence::from_raw(device.clone(), fence.internal_object(), false);
+            assert_eq!(borrowed.internal_object(), fence.internal_object());
+            assert!(borrowed.ready().unwrap());
+        }
+
+        // Dropping the borrowed fence must not have destroyed the original one.
+        assert!(fence.ready().unwrap());
+    }
+
     #[test]
     fn fence_reset() {
         let (device, _) = gfx_dev_and_queue!();
@@ -438,6 +794,71 @@ mod tests {
                              });
     }
 
+    #[test]
+    fn multiwait_signaled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::alloc_signaled(device.clone()).unwrap();
+        let fence2 = Fence::alloc_signaled(device.clone()).unwrap();
+        Fence::multi_wait([&fence1, &fence2].iter().cloned(), Some(Duration::new(0, 10)))
+            .unwrap();
+    }
+
+    #[test]
+    fn multiwait_any() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::alloc(device.clone()).unwrap();
+        let fence2 = Fence::alloc_signaled(device.clone()).unwrap();
+        Fence::multi_wait_any([&fence1, &fence2].iter().cloned(), Some(Duration::new(0, 10)))
+            .unwrap();
+
+        match Fence::multi_wait([&fence1, &fence2].iter().cloned(), Some(Duration::new(0, 10))) {
+            Err(FenceWaitError::Timeout) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiwait_unsignaled_timeout() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::alloc(device.clone()).unwrap();
+        let fence2 = Fence::alloc(device.clone()).unwrap();
+
+        match Fence::multi_wait([&fence1, &fence2].iter().cloned(), Some(Duration::new(0, 10))) {
+            Err(FenceWaitError::Timeout) => (),
+            _ => panic!(),
+        }
+
+        match Fence::multi_wait_any([&fence1, &fence2].iter().cloned(),
+                                    Some(Duration::new(0, 10))) {
+            Err(FenceWaitError::Timeout) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiwait_submitted() {
+        use command_buffer::submit::SubmitCommandBufferBuilder;
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::alloc(device.clone()).unwrap();
+        let fence2 = Fence::alloc_signaled(device.clone()).unwrap();
+
+        unsafe {
+            let mut builder = SubmitCommandBufferBuilder::new();
+            builder.set_fence_signal(&fence1);
+            builder.submit(&queue).unwrap();
+        }
+
+        // `fence1` is only signaled by the queue, so the wait must go through Vulkan.
+        Fence::multi_wait([&fence1, &fence2].iter().cloned(), Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(fence1.ready().unwrap());
+    }
+
     #[test]
     fn multireset_different_devices() {
         use std::iter::once;
@@ -456,6 +877,16 @@ mod tests {
                              });
     }
 
+    #[test]
+    fn exportable_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match Fence::alloc_exportable(device.clone(), ExternalFenceHandleType::OpaqueFd) {
+            Err(ExternalHandleError::MissingExtension { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn fence_pool() {
         let (device, _) = gfx_dev_and_queue!();
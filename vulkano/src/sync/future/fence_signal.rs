@@ -37,6 +37,9 @@ pub fn then_signal_fence<F>(future: F, behavior: FenceSignalFutureBehavior) -> F
 
     assert!(future.queue().is_some()); // TODO: document
 
+    // Good opportunity to destroy the objects whose submissions have finished.
+    device.cleanup_deletion_queues();
+
     let fence = Fence::from_pool(device.clone()).unwrap();
     FenceSignalFuture {
         device: device,
@@ -151,6 +154,20 @@ impl<F> FenceSignalFuture<F>
             _ => unreachable!(),
         }
     }
+
+    /// Returns true if the fence has been signaled by the GPU. Never blocks.
+    ///
+    /// Returns false if the future hasn't been flushed yet. If the fence is signaled, this
+    /// function also cleans any resource locked by previous submissions.
+    #[inline]
+    pub fn is_signaled(&self) -> bool {
+        self.cleanup_finished_impl();
+
+        match *self.state.lock().unwrap() {
+            FenceSignalFutureState::Cleaned => true,
+            _ => false,
+        }
+    }
 }
 
 impl<F> FenceSignalFuture<F>
@@ -0,0 +1,217 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+
+use device::Device;
+use device::DeviceOwned;
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::now;
+
+use VulkanObject;
+
+/// Limits the number of frames that have been submitted but not yet finished by the GPU.
+///
+/// Without throttling, the CPU can submit frames much faster than the GPU executes them, which
+/// increases the latency and the memory usage. With a `FramesInFlight` object, `begin_frame`
+/// blocks until the frame that was submitted `num_frames` frames ago has finished. The fences and
+/// semaphores are handled by the futures.
+///
+/// The index returned by `current_frame` can be used to choose between `num_frames` copies of the
+/// per-frame resources, for example uniform buffers that the CPU writes to.
+///
+/// # Example
+///
+/// ```no_run
+/// # use vulkano::sync::FramesInFlight;
+/// # use vulkano::sync::GpuFuture;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// let mut frames = FramesInFlight::new(device.clone(), 2);
+///
+/// loop {
+///     let future = frames.begin_frame().unwrap();
+///     // let future = future.join(acquire_future).then_execute(...).then_swapchain_present(...);
+///     frames.end_frame(future).unwrap();
+/// }
+/// ```
+pub struct FramesInFlight {
+    device: Arc<Device>,
+    // One entry per frame in flight. `None` if the frame hasn't been submitted or has already
+    // been waited upon.
+    frames: Vec<Option<FenceSignalFuture<Box<GpuFuture + Send + Sync>>>>,
+    current: usize,
+}
+
+impl FramesInFlight {
+    /// Builds a new `FramesInFlight` that allows `num_frames` frames to be executed by the GPU
+    /// at the same time.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `num_frames` is 0.
+    ///
+    pub fn new(device: Arc<Device>, num_frames: usize) -> FramesInFlight {
+        assert_ne!(num_frames, 0);
+
+        FramesInFlight {
+            device: device,
+            frames: (0 .. num_frames).map(|_| None).collect(),
+            current: 0,
+        }
+    }
+
+    /// Returns the maximum number of frames in flight.
+    #[inline]
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the index of the current frame, between 0 and `num_frames()`.
+    #[inline]
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    /// Starts a new frame.
+    ///
+    /// Blocks until the GPU has finished the frame that previously used the same index, and
+    /// releases the resources of the frames that are finished. Returns the future that the
+    /// commands of the new frame should be chained to.
+    ///
+    /// Returns an error if the previous frame that used this index couldn't be submitted.
+    pub fn begin_frame(&mut self) -> Result<Box<GpuFuture + Send + Sync>, FlushError> {
+        for frame in self.frames.iter_mut() {
+            if let Some(ref mut frame) = *frame {
+                frame.cleanup_finished();
+            }
+        }
+
+        if let Some(frame) = self.frames[self.current].take() {
+            frame.wait(None)?;
+        }
+
+        Ok(Box::new(now(self.device.clone())) as Box<_>)
+    }
+
+    /// Ends the current frame by signalling a fence after `future` and flushing it.
+    ///
+    /// The next call to `begin_frame` uses the next frame index, even if an error is returned.
+    pub fn end_frame<F>(&mut self, future: F) -> Result<(), FlushError>
+        where F: GpuFuture + Send + Sync + 'static
+    {
+        debug_assert_eq!(future.device().internal_object(),
+                         self.device.internal_object());
+
+        let index = self.current;
+        self.current = (self.current + 1) % self.frames.len();
+
+        let future = Box::new(future) as Box<GpuFuture + Send + Sync>;
+        self.frames[index] = Some(future.then_signal_fence_and_flush()?);
+        Ok(())
+    }
+
+    /// Blocks until all the frames in flight have finished.
+    ///
+    /// This is useful before destroying resources that the frames use, for example when
+    /// recreating a swapchain.
+    pub fn wait_idle(&mut self) -> Result<(), FlushError> {
+        for frame in self.frames.iter_mut() {
+            if let Some(frame) = frame.take() {
+                frame.wait(None)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for FramesInFlight {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use command_buffer::AutoCommandBufferBuilder;
+    use sync::FramesInFlight;
+    use sync::GpuFuture;
+
+    #[test]
+    fn zero_frames() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert_should_panic!({
+            let _ = FramesInFlight::new(device, 0);
+        });
+    }
+
+    #[test]
+    fn frame_index_cycles() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut frames = FramesInFlight::new(device.clone(), 3);
+        assert_eq!(frames.num_frames(), 3);
+
+        for n in 0 .. 7 {
+            assert_eq!(frames.current_frame(), n % 3);
+
+            let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                                   queue.family())
+                .unwrap()
+                .build()
+                .unwrap();
+
+            let future = frames
+                .begin_frame()
+                .unwrap()
+                .then_execute(queue.clone(), command_buffer)
+                .unwrap();
+            frames.end_frame(future).unwrap();
+        }
+
+        assert_eq!(frames.current_frame(), 1);
+    }
+
+    #[test]
+    fn begin_frame_waits_for_reused_index() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let mut frames = FramesInFlight::new(device.clone(), 2);
+
+        for _ in 0 .. 2 {
+            let command_buffer = AutoCommandBufferBuilder::primary_one_time_submit(device.clone(),
+                                                                                   queue.family())
+                .unwrap()
+                .build()
+                .unwrap();
+
+            let future = frames
+                .begin_frame()
+                .unwrap()
+                .then_execute(queue.clone(), command_buffer)
+                .unwrap();
+            frames.end_frame(future).unwrap();
+        }
+
+        assert!(frames.frames.iter().all(|frame| frame.is_some()));
+
+        // Index 0 is reused, so the first frame must have been waited upon.
+        let _ = frames.begin_frame().unwrap();
+        assert_eq!(frames.current_frame(), 0);
+        assert!(frames.frames[0].is_none());
+        assert!(frames.frames[1].is_some());
+
+        frames.wait_idle().unwrap();
+        assert!(frames.frames.iter().all(|frame| frame.is_none()));
+    }
+}
@@ -31,14 +31,17 @@ use swapchain::Swapchain;
 use sync::AccessFlagBits;
 use sync::FenceWaitError;
 use sync::PipelineStages;
+use vk;
 
 pub use self::fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior};
+pub use self::frames_in_flight::FramesInFlight;
 pub use self::join::JoinFuture;
 pub use self::now::{NowFuture, now};
 pub use self::semaphore_signal::SemaphoreSignalFuture;
 
 mod now;
 mod fence_signal;
+mod frames_in_flight;
 mod join;
 mod semaphore_signal;
 
@@ -255,6 +258,17 @@ pub unsafe trait GpuFuture: DeviceOwned {
     {
         swapchain::present_incremental(swapchain, self, queue, image_index, present_region)
     }
+
+    /// Turns this future into a `Box<GpuFuture>`.
+    ///
+    /// This is useful when the type of the future depends on a runtime condition, for example
+    /// when storing the future of the previous frame in a variable.
+    #[inline]
+    fn boxed(self) -> Box<GpuFuture>
+        where Self: Sized + 'static
+    {
+        Box::new(self) as Box<_>
+    }
 }
 
 unsafe impl<F: ?Sized> GpuFuture for Box<F>
@@ -344,7 +358,7 @@ impl error::Error for AccessError {
                 "the resource is already in use, and there is no tracking of concurrent usages"
             },
             AccessError::UnexpectedImageLayout { .. } => {
-                unimplemented!() // TODO: find a description
+                "the image is not in the layout that was requested"
             },
             AccessError::ImageNotInitialized { .. } => {
                 "trying to use an image without transitionning it from the undefined or \
@@ -424,8 +438,15 @@ pub enum FlushError {
     /// surface's new properties and recreate a new swapchain if you want to continue drawing.
     OutOfDate,
 
+    /// The swapchain has lost or doesn't have fullscreen exclusivity, possibly for
+    /// implementation-specific reasons outside of the application's control.
+    FullscreenExclusiveLost,
+
     /// The flush operation needed to block, but the timeout has elapsed.
     Timeout,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
 }
 
 impl error::Error for FlushError {
@@ -437,8 +458,13 @@ impl error::Error for FlushError {
             FlushError::DeviceLost => "the connection to the device has been lost",
             FlushError::SurfaceLost => "the surface of this swapchain is no longer valid",
             FlushError::OutOfDate => "the swapchain needs to be recreated",
+            FlushError::FullscreenExclusiveLost => {
+                "the swapchain no longer has fullscreen exclusivity"
+            },
             FlushError::Timeout => "the flush operation needed to block, but the timeout has \
                                     elapsed",
+            FlushError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
         }
     }
 
@@ -474,6 +500,7 @@ impl From<SubmitPresentError> for FlushError {
             SubmitPresentError::DeviceLost => FlushError::DeviceLost,
             SubmitPresentError::SurfaceLost => FlushError::SurfaceLost,
             SubmitPresentError::OutOfDate => FlushError::OutOfDate,
+            SubmitPresentError::FullscreenExclusiveLost => FlushError::FullscreenExclusiveLost,
         }
     }
 }
@@ -505,6 +532,7 @@ impl From<FenceWaitError> for FlushError {
             FenceWaitError::OomError(err) => FlushError::OomError(err),
             FenceWaitError::Timeout => FlushError::Timeout,
             FenceWaitError::DeviceLostError => FlushError::DeviceLost,
+            FenceWaitError::UnexpectedResult(code) => FlushError::UnexpectedResult(code),
         }
     }
 }
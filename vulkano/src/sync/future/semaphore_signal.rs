@@ -178,7 +178,7 @@ impl<F> Drop for SemaphoreSignalFuture<F>
                 // TODO: handle errors?
                 self.flush().unwrap();
                 // Block until the queue finished.
-                self.queue().unwrap().wait().unwrap();
+                self.queue().unwrap().wait_idle().unwrap();
                 self.previous.signal_finished();
             }
         }
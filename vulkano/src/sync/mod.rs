@@ -102,17 +102,40 @@
 //! TODO: lots of problems with how to use fences
 //! TODO: talk about fence + semaphore simultaneously
 //! TODO: talk about using fences to clean up
+//!
+//! # Pipeline barriers
+//!
+//! Within a command buffer built with `AutoCommandBufferBuilder`, the pipeline barriers between
+//! commands that access the same resources are added automatically. If you need more control,
+//! for example because the GPU accesses a resource in a way that vulkano can't track, you can add
+//! a `MemoryBarrier`, a `BufferMemoryBarrier` or an `ImageMemoryBarrier` yourself with the
+//! corresponding methods of `AutoCommandBufferBuilder`.
+//!
+//! Each barrier makes the memory accesses of type `source_access` performed by the `source_stages`
+//! of the previous commands available to the memory accesses of type `destination_access`
+//! performed by the `destination_stages` of the following commands.
 
 use device::Queue;
 use std::sync::Arc;
 
+pub use self::barrier::BufferMemoryBarrier;
+pub use self::barrier::ImageMemoryBarrier;
+pub use self::barrier::MemoryBarrier;
+pub use self::deletion_queue::DeletionQueue;
 pub use self::event::Event;
+pub use self::event::EventCreationError;
+pub use self::event::EventStatusError;
+pub use self::external::ExternalFenceHandleType;
+pub use self::external::ExternalHandleError;
+pub use self::external::ExternalSemaphoreHandleType;
 pub use self::fence::Fence;
+pub use self::fence::FenceCreationError;
 pub use self::fence::FenceWaitError;
 pub use self::future::AccessCheckError;
 pub use self::future::AccessError;
 pub use self::future::FenceSignalFuture;
 pub use self::future::FlushError;
+pub use self::future::FramesInFlight;
 pub use self::future::GpuFuture;
 pub use self::future::JoinFuture;
 pub use self::future::NowFuture;
@@ -121,12 +144,20 @@ pub use self::future::now;
 pub use self::pipeline::AccessFlagBits;
 pub use self::pipeline::PipelineStages;
 pub use self::semaphore::Semaphore;
+pub use self::semaphore::SemaphoreCreationError;
+pub use self::timeline_semaphore::TimelineSemaphore;
+pub use self::timeline_semaphore::TimelineSemaphoreCreationError;
+pub use self::timeline_semaphore::TimelineSemaphoreError;
 
+mod barrier;
+mod deletion_queue;
 mod event;
+mod external;
 mod fence;
 mod future;
 mod pipeline;
 mod semaphore;
+mod timeline_semaphore;
 
 /// Declares in which queue(s) a resource can be used.
 ///
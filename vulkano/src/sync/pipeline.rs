@@ -82,25 +82,39 @@ pipeline_stages!{
     host => vk::PIPELINE_STAGE_HOST_BIT,
     all_graphics => vk::PIPELINE_STAGE_ALL_GRAPHICS_BIT,
     all_commands => vk::PIPELINE_STAGE_ALL_COMMANDS_BIT,
+    task_shader => vk::PIPELINE_STAGE_TASK_SHADER_BIT_EXT,
+    mesh_shader => vk::PIPELINE_STAGE_MESH_SHADER_BIT_EXT,
+    ray_tracing_shader => vk::PIPELINE_STAGE_RAY_TRACING_SHADER_BIT_KHR,
+    acceleration_structure_build => vk::PIPELINE_STAGE_ACCELERATION_STRUCTURE_BUILD_BIT_KHR,
+    conditional_rendering => vk::PIPELINE_STAGE_CONDITIONAL_RENDERING_BIT_EXT,
+    transform_feedback => vk::PIPELINE_STAGE_TRANSFORM_FEEDBACK_BIT_EXT,
 }
 
+// The bits after the `;` belong to extensions, and aren't set by `AccessFlagBits::all()`.
 macro_rules! access_flags {
-    ($($elem:ident => $val:expr,)+) => (
+    ($($elem:ident => $val:expr,)+ ; $($ext_elem:ident => $ext_val:expr,)*) => (
         #[derive(Debug, Copy, Clone)]
         #[allow(missing_docs)]
         pub struct AccessFlagBits {
             $(
                 pub $elem: bool,
             )+
+            $(
+                pub $ext_elem: bool,
+            )*
         }
 
         impl AccessFlagBits {
-            /// Builds an `AccessFlagBits` struct with all bits set.
+            /// Builds an `AccessFlagBits` struct with all bits set, except for the bits that
+            /// belong to an extension.
             pub fn all() -> AccessFlagBits {
                 AccessFlagBits {
                     $(
                         $elem: true,
                     )+
+                    $(
+                        $ext_elem: false,
+                    )*
                 }
             }
 
@@ -110,6 +124,9 @@ macro_rules! access_flags {
                     $(
                         $elem: false,
                     )+
+                    $(
+                        $ext_elem: false,
+                    )*
                 }
             }
 
@@ -119,6 +136,9 @@ macro_rules! access_flags {
                 $(
                     if self.$elem { result |= $val }
                 )+
+                $(
+                    if self.$ext_elem { result |= $ext_val }
+                )*
                 result
             }
         }
@@ -132,6 +152,9 @@ macro_rules! access_flags {
                     $(
                         $elem: self.$elem || rhs.$elem,
                     )+
+                    $(
+                        $ext_elem: self.$ext_elem || rhs.$ext_elem,
+                    )*
                 }
             }
         }
@@ -142,6 +165,9 @@ macro_rules! access_flags {
                 $(
                     self.$elem = self.$elem || rhs.$elem;
                 )+
+                $(
+                    self.$ext_elem = self.$ext_elem || rhs.$ext_elem;
+                )*
             }
         }
     );
@@ -165,6 +191,13 @@ access_flags!{
     host_write => vk::ACCESS_HOST_WRITE_BIT,
     memory_read => vk::ACCESS_MEMORY_READ_BIT,
     memory_write => vk::ACCESS_MEMORY_WRITE_BIT,
+    ;
+    acceleration_structure_read => vk::ACCESS_ACCELERATION_STRUCTURE_READ_BIT_KHR,
+    acceleration_structure_write => vk::ACCESS_ACCELERATION_STRUCTURE_WRITE_BIT_KHR,
+    conditional_rendering_read => vk::ACCESS_CONDITIONAL_RENDERING_READ_BIT_EXT,
+    transform_feedback_write => vk::ACCESS_TRANSFORM_FEEDBACK_WRITE_BIT_EXT,
+    transform_feedback_counter_read => vk::ACCESS_TRANSFORM_FEEDBACK_COUNTER_READ_BIT_EXT,
+    transform_feedback_counter_write => vk::ACCESS_TRANSFORM_FEEDBACK_COUNTER_WRITE_BIT_EXT,
 }
 
 impl AccessFlagBits {
@@ -191,7 +224,39 @@ impl AccessFlagBits {
             !stages.vertex_shader && !stages.tessellation_control_shader &&
             !stages.tessellation_evaluation_shader && !stages.geometry_shader &&
             !stages.fragment_shader &&
-            !stages.compute_shader && !stages.all_graphics
+            !stages.compute_shader && !stages.task_shader && !stages.mesh_shader &&
+            !stages.ray_tracing_shader &&
+            !stages.acceleration_structure_build && !stages.all_graphics
+        {
+            return false;
+        }
+
+        // Acceleration structures can be read by ray queries from any shader stage.
+        if self.acceleration_structure_read && !stages.vertex_shader &&
+            !stages.tessellation_control_shader && !stages.tessellation_evaluation_shader &&
+            !stages.geometry_shader && !stages.fragment_shader && !stages.compute_shader &&
+            !stages.task_shader && !stages.mesh_shader && !stages.ray_tracing_shader &&
+            !stages.acceleration_structure_build && !stages.all_graphics
+        {
+            return false;
+        }
+
+        if self.acceleration_structure_write && !stages.acceleration_structure_build {
+            return false;
+        }
+
+        if self.conditional_rendering_read && !stages.conditional_rendering {
+            return false;
+        }
+
+        if (self.transform_feedback_write || self.transform_feedback_counter_write) &&
+            !stages.transform_feedback
+        {
+            return false;
+        }
+
+        if self.transform_feedback_counter_read && !stages.transform_feedback &&
+            !stages.draw_indirect
         {
             return false;
         }
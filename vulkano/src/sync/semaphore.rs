@@ -7,7 +7,10 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::error;
 use std::mem;
+use std::os::raw::c_int;
+use std::os::raw::c_void;
 use std::ptr;
 use std::sync::Arc;
 
@@ -17,12 +20,25 @@ use VulkanObject;
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use sync::ExternalHandleError;
+use sync::ExternalSemaphoreHandleType;
 use vk;
 
 /// Used to provide synchronization between command buffers during their execution.
 ///
 /// It is similar to a fence, except that it is purely on the GPU side. The CPU can't query a
 /// semaphore's status or wait for it to be signaled.
+///
+/// # External semaphores
+///
+/// A semaphore created with `alloc_exportable` can share its payload with other APIs or other
+/// processes, for example a compositor or an OpenGL context, by exporting it as a file descriptor
+/// or a Win32 handle. The other side can then wait for the semaphore or signal it. Conversely,
+/// the payload of a handle created elsewhere can be imported with `import_fd` or
+/// `import_win32_handle`.
+///
+/// This requires the `khr_external_semaphore` extension, plus `khr_external_semaphore_fd` or
+/// `khr_external_semaphore_win32` depending on the type of handle.
 #[derive(Debug)]
 pub struct Semaphore<D = Arc<Device>>
     where D: SafeDeref<Target = Device>
@@ -30,6 +46,10 @@ pub struct Semaphore<D = Arc<Device>>
     semaphore: vk::Semaphore,
     device: D,
     must_put_in_pool: bool,
+    // Type of handle the semaphore can be exported to, if any.
+    export_handle_type: Option<ExternalSemaphoreHandleType>,
+    // False if the semaphore was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
 }
 
 impl<D> Semaphore<D>
@@ -41,7 +61,7 @@ impl<D> Semaphore<D>
     ///
     /// For most applications, using the pool should be preferred,
     /// in order to avoid creating new semaphores every frame.
-    pub fn from_pool(device: D) -> Result<Semaphore<D>, OomError> {
+    pub fn from_pool(device: D) -> Result<Semaphore<D>, SemaphoreCreationError> {
         let maybe_raw_sem = device.semaphore_pool().lock().unwrap().pop();
         match maybe_raw_sem {
             Some(raw_sem) => {
@@ -49,6 +69,8 @@ impl<D> Semaphore<D>
                        device: device,
                        semaphore: raw_sem,
                        must_put_in_pool: true,
+                       export_handle_type: None,
+                       needs_destruction: true,
                    })
             },
             None => {
@@ -58,13 +80,36 @@ impl<D> Semaphore<D>
         }
     }
 
+    /// Builds a `Semaphore` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the semaphore is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it. The semaphore is never put
+    /// in the semaphore pool.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid binary semaphore of `device`.
+    /// - If `owned` is true, the semaphore must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: D, handle: vk::Semaphore, owned: bool) -> Semaphore<D> {
+        Semaphore {
+            device: device,
+            semaphore: handle,
+            must_put_in_pool: false,
+            export_handle_type: None,
+            needs_destruction: owned,
+        }
+    }
+
     /// Builds a new semaphore.
     #[inline]
-    pub fn alloc(device: D) -> Result<Semaphore<D>, OomError> {
+    pub fn alloc(device: D) -> Result<Semaphore<D>, SemaphoreCreationError> {
         Semaphore::alloc_impl(device, false)
     }
 
-    fn alloc_impl(device: D, must_put_in_pool: bool) -> Result<Semaphore<D>, OomError> {
+    fn alloc_impl(device: D, must_put_in_pool: bool)
+                  -> Result<Semaphore<D>, SemaphoreCreationError> {
         let semaphore = unsafe {
             // since the creation is constant, we use a `static` instead of a struct on the stack
             static mut INFOS: vk::SemaphoreCreateInfo = vk::SemaphoreCreateInfo {
@@ -86,8 +131,219 @@ impl<D> Semaphore<D>
                device: device,
                semaphore: semaphore,
                must_put_in_pool: must_put_in_pool,
+               export_handle_type: None,
+               needs_destruction: true,
            })
     }
+
+    /// Builds a new semaphore whose payload can be exported to a handle of type `handle_type`.
+    ///
+    /// The semaphore is never put in the semaphore pool.
+    pub fn alloc_exportable(device: D, handle_type: ExternalSemaphoreHandleType)
+                            -> Result<Semaphore<D>, ExternalHandleError> {
+        if !device.loaded_extensions().khr_external_semaphore {
+            return Err(ExternalHandleError::MissingExtension {
+                           name: "VK_KHR_external_semaphore",
+                       });
+        }
+
+        let semaphore = unsafe {
+            let export_infos = vk::ExportSemaphoreCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                handleTypes: handle_type as u32,
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &export_infos as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateSemaphore(device.internal_object(),
+                                            &infos,
+                                            ptr::null(),
+                                            &mut output))?;
+            output
+        };
+
+        Ok(Semaphore {
+               device: device,
+               semaphore: semaphore,
+               must_put_in_pool: false,
+               export_handle_type: Some(handle_type),
+               needs_destruction: true,
+           })
+    }
+
+    /// Exports the payload of the semaphore as a file descriptor. The ownership of the file
+    /// descriptor is transferred to the caller, who is responsible for closing it.
+    ///
+    /// Exporting a `SyncFd` requires the semaphore to be signaled or to have a pending signal
+    /// operation, and resets the semaphore to the unsignaled state.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the semaphore was created as exportable to a Win32 handle.
+    ///
+    pub fn export_fd(&self) -> Result<c_int, ExternalHandleError> {
+        let handle_type = self.export_handle_type.ok_or(ExternalHandleError::NotExportable)?;
+        assert!(handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        unsafe {
+            let infos = vk::SemaphoreGetFdInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_FD_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                handleType: handle_type as u32,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.GetSemaphoreFdKHR(self.device.internal_object(),
+                                              &infos,
+                                              &mut output))?;
+            Ok(output)
+        }
+    }
+
+    /// Exports the payload of the semaphore as a Win32 handle. For `OpaqueWin32` and `D3D12Fence`
+    /// handles, the caller is responsible for closing the handle.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the semaphore was created as exportable to a file descriptor.
+    ///
+    pub fn export_win32_handle(&self) -> Result<*mut c_void, ExternalHandleError> {
+        let handle_type = self.export_handle_type.ok_or(ExternalHandleError::NotExportable)?;
+        assert!(!handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        unsafe {
+            let infos = vk::SemaphoreGetWin32HandleInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_GET_WIN32_HANDLE_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                handleType: handle_type as u32,
+            };
+
+            let vk = self.device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.GetSemaphoreWin32HandleKHR(self.device.internal_object(),
+                                                       &infos,
+                                                       &mut output))?;
+            Ok(output)
+        }
+    }
+
+    /// Replaces the payload of the semaphore with the payload of a file descriptor. On success,
+    /// the ownership of the file descriptor is transferred to the Vulkan implementation.
+    ///
+    /// If `temporary` is true, the imported payload is only used until the next wait operation on
+    /// the semaphore, after which the semaphore gets its previous payload back. `SyncFd` handles
+    /// can only be imported temporarily.
+    ///
+    /// The semaphore is not going to be put back in the semaphore pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is not a file descriptor type.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must not be in use by the GPU.
+    /// - `fd` must be a valid file descriptor of type `handle_type`, that was exported from a
+    ///   semaphore created with the same driver and device.
+    ///
+    pub unsafe fn import_fd(&mut self, handle_type: ExternalSemaphoreHandleType, fd: c_int,
+                            temporary: bool)
+                            -> Result<(), ExternalHandleError> {
+        assert!(handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        let infos = vk::ImportSemaphoreFdInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_FD_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            flags: if temporary {
+                vk::SEMAPHORE_IMPORT_TEMPORARY_BIT_KHR
+            } else {
+                0
+            },
+            handleType: handle_type as u32,
+            fd: fd,
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportSemaphoreFdKHR(self.device.internal_object(), &infos))?;
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    /// Replaces the payload of the semaphore with the payload of a Win32 handle. Contrary to file
+    /// descriptors, the ownership of the handle is not transferred.
+    ///
+    /// If `temporary` is true, the imported payload is only used until the next wait operation on
+    /// the semaphore, after which the semaphore gets its previous payload back.
+    ///
+    /// The semaphore is not going to be put back in the semaphore pool.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `handle_type` is not a Win32 handle type.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphore must not be in use by the GPU.
+    /// - `handle` must be a valid handle of type `handle_type`.
+    ///
+    pub unsafe fn import_win32_handle(&mut self, handle_type: ExternalSemaphoreHandleType,
+                                      handle: *mut c_void, temporary: bool)
+                                      -> Result<(), ExternalHandleError> {
+        assert!(!handle_type.is_fd());
+        self.check_extension(handle_type)?;
+
+        let infos = vk::ImportSemaphoreWin32HandleInfoKHR {
+            sType: vk::STRUCTURE_TYPE_IMPORT_SEMAPHORE_WIN32_HANDLE_INFO_KHR,
+            pNext: ptr::null(),
+            semaphore: self.semaphore,
+            flags: if temporary {
+                vk::SEMAPHORE_IMPORT_TEMPORARY_BIT_KHR
+            } else {
+                0
+            },
+            handleType: handle_type as u32,
+            handle: handle,
+            name: ptr::null(),
+        };
+
+        let vk = self.device.pointers();
+        check_errors(vk.ImportSemaphoreWin32HandleKHR(self.device.internal_object(), &infos))?;
+        self.must_put_in_pool = false;
+        Ok(())
+    }
+
+    // Checks that the extension needed to export or import `handle_type` is enabled.
+    fn check_extension(&self, handle_type: ExternalSemaphoreHandleType)
+                       -> Result<(), ExternalHandleError> {
+        let extensions = self.device.loaded_extensions();
+        let enabled = if handle_type.is_fd() {
+            extensions.khr_external_semaphore_fd
+        } else {
+            extensions.khr_external_semaphore_win32
+        };
+
+        if !enabled {
+            return Err(ExternalHandleError::MissingExtension {
+                           name: handle_type.extension_name(),
+                       });
+        }
+
+        Ok(())
+    }
 }
 
 unsafe impl DeviceOwned for Semaphore {
@@ -119,7 +375,7 @@ impl<D> Drop for Semaphore<D>
             if self.must_put_in_pool {
                 let raw_sem = self.semaphore;
                 self.device.semaphore_pool().lock().unwrap().push(raw_sem);
-            } else {
+            } else if self.needs_destruction {
                 let vk = self.device.pointers();
                 vk.DestroySemaphore(self.device.internal_object(), self.semaphore, ptr::null());
             }
@@ -127,9 +383,42 @@ impl<D> Drop for Semaphore<D>
     }
 }
 
+/// Error that can happen when creating a semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SemaphoreCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for SemaphoreCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            SemaphoreCreationError::OomError(_) => "not enough memory available",
+            SemaphoreCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            SemaphoreCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(SemaphoreCreationError, OomError {});
+
 #[cfg(test)]
 mod tests {
     use VulkanObject;
+    use sync::ExternalHandleError;
+    use sync::ExternalSemaphoreHandleType;
     use sync::Semaphore;
 
     #[test]
@@ -154,4 +443,25 @@ mod tests {
         assert_eq!(device.semaphore_pool().lock().unwrap().len(), 0);
         assert_eq!(sem2.internal_object(), sem1_internal_obj);
     }
+
+    #[test]
+    fn exportable_missing_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match Semaphore::alloc_exportable(device.clone(), ExternalSemaphoreHandleType::OpaqueFd) {
+            Err(ExternalHandleError::MissingExtension { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn export_not_exportable() {
+        let (device, _) = gfx_dev_and_queue!();
+        let sem = Semaphore::alloc(device.clone()).unwrap();
+
+        match sem.export_fd() {
+            Err(ExternalHandleError::NotExportable) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -0,0 +1,557 @@
+// Copyright (c) 2017 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::mem;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use OomError;
+use Success;
+use VulkanObject;
+use check_errors;
+use device::Device;
+use device::DeviceOwned;
+use vk;
+
+/// Semaphore that contains a 64-bits counter instead of a signaled state.
+///
+/// The counter can be increased by the GPU as part of a queue submission, or by the CPU with
+/// `signal`. Both the GPU (as part of a queue submission) and the CPU (with `wait`) can wait until
+/// the counter reaches a certain value. Contrary to a regular `Semaphore`, the same value can be
+/// waited upon multiple times, and the CPU can query the current value.
+///
+/// Using timeline semaphores requires the `khr_timeline_semaphore` extension to be enabled on the
+/// device, or a Vulkan 1.2 device that supports the `timelineSemaphore` feature. The feature is
+/// then enabled automatically. See `Device::supports_timeline_semaphores`.
+///
+/// The value of the counter can only increase. Signaling from the CPU a value that is lower than
+/// or equal to the current value, or greater than or equal to a value that a pending submission
+/// is going to signal, returns an error.
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    semaphore: vk::Semaphore,
+    device: Arc<Device>,
+    // False if the semaphore was built with `from_raw` without taking ownership.
+    needs_destruction: bool,
+    // Values that the submissions made with `SubmitCommandBufferBuilder` are going to signal.
+    // The values that the counter has already reached are removed lazily.
+    pending_signals: Mutex<SmallVec<[u64; 4]>>,
+}
+
+impl TimelineSemaphore {
+    /// Builds a new timeline semaphore whose counter starts at `initial_value`.
+    pub fn new(device: Arc<Device>, initial_value: u64)
+               -> Result<TimelineSemaphore, TimelineSemaphoreCreationError> {
+        if !device.supports_timeline_semaphores() {
+            return Err(TimelineSemaphoreCreationError::ExtensionNotEnabled);
+        }
+
+        let semaphore = unsafe {
+            let type_infos = vk::SemaphoreTypeCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                semaphoreType: vk::SEMAPHORE_TYPE_TIMELINE_KHR,
+                initialValue: initial_value,
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &type_infos as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = mem::uninitialized();
+            check_errors(vk.CreateSemaphore(device.internal_object(),
+                                            &infos,
+                                            ptr::null(),
+                                            &mut output))?;
+            output
+        };
+
+        Ok(TimelineSemaphore {
+               semaphore: semaphore,
+               device: device,
+               needs_destruction: true,
+               pending_signals: Mutex::new(SmallVec::new()),
+           })
+    }
+
+    /// Builds a `TimelineSemaphore` from a raw handle that was created outside of vulkano.
+    ///
+    /// If `owned` is true, the semaphore is destroyed when the returned object is dropped.
+    /// Otherwise, the caller remains responsible for destroying it.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid timeline semaphore of `device`.
+    /// - If `owned` is true, the semaphore must not be destroyed by anything else.
+    ///
+    #[inline]
+    pub unsafe fn from_raw(device: Arc<Device>, handle: vk::Semaphore, owned: bool)
+                           -> TimelineSemaphore {
+        TimelineSemaphore {
+            semaphore: handle,
+            device: device,
+            needs_destruction: owned,
+            pending_signals: Mutex::new(SmallVec::new()),
+        }
+    }
+
+    /// Returns the current value of the counter.
+    #[inline]
+    pub fn value(&self) -> Result<u64, TimelineSemaphoreError> {
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output = mem::uninitialized();
+            if self.device.loaded_extensions().khr_timeline_semaphore {
+                check_errors(vk.GetSemaphoreCounterValueKHR(self.device.internal_object(),
+                                                            self.semaphore,
+                                                            &mut output))?;
+            } else {
+                check_errors(vk.GetSemaphoreCounterValue(self.device.internal_object(),
+                                                         self.semaphore,
+                                                         &mut output))?;
+            }
+            Ok(output)
+        }
+    }
+
+    /// Sets the value of the counter from the CPU.
+    ///
+    /// Returns an error if `value` is not greater than the current value of the counter, or if
+    /// it is not lower than the values that pending submissions are going to signal. Only the
+    /// submissions made with `SubmitCommandBufferBuilder` are known to vulkano.
+    pub fn signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        let current = self.value()?;
+        if value <= current {
+            return Err(TimelineSemaphoreError::SignalValueTooLow {
+                           current: current,
+                           requested: value,
+                       });
+        }
+
+        {
+            let mut pending_signals = self.pending_signals.lock().unwrap();
+            pending_signals.retain(|v| *v > current);
+            if let Some(&pending) = pending_signals.iter().min() {
+                if value >= pending {
+                    return Err(TimelineSemaphoreError::SignalValueTooHigh {
+                                   pending: pending,
+                                   requested: value,
+                               });
+                }
+            }
+        }
+
+        unsafe {
+            let infos = vk::SemaphoreSignalInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                value: value,
+            };
+
+            let vk = self.device.pointers();
+            if self.device.loaded_extensions().khr_timeline_semaphore {
+                check_errors(vk.SignalSemaphoreKHR(self.device.internal_object(), &infos))?;
+            } else {
+                check_errors(vk.SignalSemaphore(self.device.internal_object(), &infos))?;
+            }
+            Ok(())
+        }
+    }
+
+    // Called when a submission that signals `value` has been submitted.
+    pub(crate) fn add_pending_signal(&self, value: u64) {
+        self.pending_signals.lock().unwrap().push(value);
+    }
+
+    /// Waits until the counter reaches at least `value`, or at least until the timeout duration
+    /// has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached the value. Returns `Err` if the timeout was reached
+    /// instead.
+    ///
+    /// If you pass a duration of 0, then the function will return without blocking.
+    #[inline]
+    pub fn wait(&self, value: u64, timeout: Option<Duration>)
+                -> Result<(), TimelineSemaphoreError> {
+        TimelineSemaphore::multi_wait(Some((self, value)), timeout)
+    }
+
+    /// Waits until the counter of each semaphore reaches the value associated with it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if not all semaphores belong to the same device.
+    #[inline]
+    pub fn multi_wait<'a, I>(iter: I, timeout: Option<Duration>)
+                             -> Result<(), TimelineSemaphoreError>
+        where I: IntoIterator<Item = (&'a TimelineSemaphore, u64)>
+    {
+        TimelineSemaphore::multi_wait_impl(iter, true, timeout)
+    }
+
+    /// Waits until the counter of at least one of the semaphores reaches the value associated
+    /// with it.
+    ///
+    /// # Panic
+    ///
+    /// Panics if not all semaphores belong to the same device.
+    #[inline]
+    pub fn multi_wait_any<'a, I>(iter: I, timeout: Option<Duration>)
+                                 -> Result<(), TimelineSemaphoreError>
+        where I: IntoIterator<Item = (&'a TimelineSemaphore, u64)>
+    {
+        TimelineSemaphore::multi_wait_impl(iter, false, timeout)
+    }
+
+    fn multi_wait_impl<'a, I>(iter: I, wait_all: bool, timeout: Option<Duration>)
+                              -> Result<(), TimelineSemaphoreError>
+        where I: IntoIterator<Item = (&'a TimelineSemaphore, u64)>
+    {
+        let mut device: Option<&Device> = None;
+        let mut semaphores: SmallVec<[vk::Semaphore; 8]> = SmallVec::new();
+        let mut values: SmallVec<[u64; 8]> = SmallVec::new();
+
+        for (semaphore, value) in iter {
+            if let Some(dev) = device {
+                if dev as *const Device != &*semaphore.device as *const Device {
+                    panic!("Tried to wait for multiple semaphores that didn't belong to the same \
+                            device");
+                }
+            } else {
+                device = Some(&*semaphore.device);
+            }
+
+            semaphores.push(semaphore.semaphore);
+            values.push(value);
+        }
+
+        let device = match device {
+            Some(d) => d,
+            None => return Ok(()),
+        };
+
+        let timeout_ns = if let Some(timeout) = timeout {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        } else {
+            u64::max_value()
+        };
+
+        let r = unsafe {
+            let infos = vk::SemaphoreWaitInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO_KHR,
+                pNext: ptr::null(),
+                flags: if wait_all {
+                    0
+                } else {
+                    vk::SEMAPHORE_WAIT_ANY_BIT_KHR
+                },
+                semaphoreCount: semaphores.len() as u32,
+                pSemaphores: semaphores.as_ptr(),
+                pValues: values.as_ptr(),
+            };
+
+            let vk = device.pointers();
+            if device.loaded_extensions().khr_timeline_semaphore {
+                check_errors(vk.WaitSemaphoresKHR(device.internal_object(), &infos, timeout_ns))?
+            } else {
+                check_errors(vk.WaitSemaphores(device.internal_object(), &infos, timeout_ns))?
+            }
+        };
+
+        match r {
+            Success::Success => Ok(()),
+            Success::Timeout => Err(TimelineSemaphoreError::Timeout),
+            _ => unreachable!(),
+        }
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for TimelineSemaphore {
+    type Object = vk::Semaphore;
+
+    const TYPE: vk::DebugReportObjectTypeEXT = vk::DEBUG_REPORT_OBJECT_TYPE_SEMAPHORE_EXT;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Semaphore {
+        self.semaphore
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.needs_destruction {
+            return;
+        }
+
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySemaphore(self.device.internal_object(), self.semaphore, ptr::null());
+        }
+    }
+}
+
+/// Error that can be returned when creating a timeline semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The `khr_timeline_semaphore` extension is not enabled on the device, and the device
+    /// doesn't support the `timelineSemaphore` feature of Vulkan 1.2.
+    ExtensionNotEnabled,
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for TimelineSemaphoreCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            TimelineSemaphoreCreationError::OomError(_) => "not enough memory",
+            TimelineSemaphoreCreationError::ExtensionNotEnabled => {
+                "the `khr_timeline_semaphore` extension is not enabled on the device"
+            },
+            TimelineSemaphoreCreationError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            TimelineSemaphoreCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(TimelineSemaphoreCreationError, OomError {});
+
+/// Error that can be returned when querying, signaling or waiting on a timeline semaphore.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimelineSemaphoreError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The device has been lost.
+    DeviceLost,
+
+    /// The value to signal is not greater than the current value of the counter.
+    SignalValueTooLow {
+        /// Current value of the counter.
+        current: u64,
+        /// Value that was passed to `signal`.
+        requested: u64,
+    },
+
+    /// The value to signal is not lower than a value that a pending submission is going to
+    /// signal.
+    SignalValueTooHigh {
+        /// Lowest value that a pending submission is going to signal.
+        pending: u64,
+        /// Value that was passed to `signal`.
+        requested: u64,
+    },
+
+    /// The implementation returned an error code that this function isn't expected to return.
+    UnexpectedResult(vk::Result),
+}
+
+impl error::Error for TimelineSemaphoreError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            TimelineSemaphoreError::OomError(_) => "not enough memory",
+            TimelineSemaphoreError::Timeout => "the timeout has been reached",
+            TimelineSemaphoreError::DeviceLost => "the device was lost",
+            TimelineSemaphoreError::SignalValueTooLow { .. } => {
+                "the value to signal is not greater than the current value of the counter"
+            },
+            TimelineSemaphoreError::SignalValueTooHigh { .. } => {
+                "the value to signal is not lower than a value that a pending submission is going \
+                 to signal"
+            },
+            TimelineSemaphoreError::UnexpectedResult(_) =>
+                "the implementation returned an unexpected error code",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            TimelineSemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+vk_error_conversions!(TimelineSemaphoreError, OomError {
+    DeviceLost => DeviceLost,
+});
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use VulkanObject;
+    use check_errors;
+    use command_buffer::submit::SubmitCommandBufferBuilder;
+    use sync::PipelineStages;
+    use sync::TimelineSemaphore;
+    use sync::TimelineSemaphoreCreationError;
+    use sync::TimelineSemaphoreError;
+    use vk;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+        if device.supports_timeline_semaphores() {
+            // Vulkan 1.2 devices don't need the extension.
+            return;
+        }
+
+        match TimelineSemaphore::new(device.clone(), 0) {
+            Err(TimelineSemaphoreCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn value() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore]);
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 5).unwrap();
+        assert_eq!(semaphore.value().unwrap(), 5);
+    }
+
+    #[test]
+    fn signal() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore]);
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 5).unwrap();
+        semaphore.signal(7).unwrap();
+        assert_eq!(semaphore.value().unwrap(), 7);
+
+        match semaphore.signal(7) {
+            Err(TimelineSemaphoreError::SignalValueTooLow {
+                    current: 7,
+                    requested: 7,
+                }) => (),
+            _ => panic!(),
+        }
+
+        match semaphore.signal(3) {
+            Err(TimelineSemaphoreError::SignalValueTooLow { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn wait() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore]);
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 5).unwrap();
+        semaphore.wait(5, Some(Duration::new(0, 0))).unwrap();
+        semaphore.wait(3, Some(Duration::new(0, 0))).unwrap();
+
+        match semaphore.wait(6, Some(Duration::new(0, 0))) {
+            Err(TimelineSemaphoreError::Timeout) => (),
+            _ => panic!(),
+        }
+
+        semaphore.signal(6).unwrap();
+        semaphore.wait(6, None).unwrap();
+    }
+
+    #[test]
+    fn signal_below_pending_signal() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore]);
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 0).unwrap();
+
+        // The submission can't signal 10 before the CPU signals 5.
+        unsafe {
+            let mut builder = SubmitCommandBufferBuilder::new();
+            builder.add_wait_timeline_semaphore(&semaphore, 5, PipelineStages {
+                                                    all_commands: true,
+                                                    ..PipelineStages::none()
+                                                });
+            builder.add_signal_timeline_semaphore(&semaphore, 10);
+            builder.submit(&queue).unwrap();
+        }
+
+        match semaphore.signal(10) {
+            Err(TimelineSemaphoreError::SignalValueTooHigh {
+                    pending: 10,
+                    requested: 10,
+                }) => (),
+            _ => panic!(),
+        }
+
+        semaphore.signal(5).unwrap();
+        semaphore.wait(10, None).unwrap();
+        semaphore.signal(11).unwrap();
+    }
+
+    #[test]
+    fn vulkan_1_2_without_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        if !device.supports_timeline_semaphores() {
+            return;
+        }
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 1).unwrap();
+        semaphore.signal(2).unwrap();
+        assert_eq!(semaphore.value().unwrap(), 2);
+        semaphore.wait(2, None).unwrap();
+    }
+
+    #[test]
+    fn from_raw_borrowed() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore]);
+
+        let semaphore = TimelineSemaphore::new(device.clone(), 5).unwrap();
+        let borrowed =
+            unsafe { TimelineSemaphore::from_raw(device, semaphore.internal_object(), false) };
+        assert_eq!(borrowed.value().unwrap(), 5);
+        drop(borrowed);
+        assert_eq!(semaphore.value().unwrap(), 5);
+    }
+
+    #[test]
+    fn error_keeps_unknown_result_code() {
+        let code = -1000999000i32 as vk::Result;
+        let err = check_errors(code).unwrap_err();
+        assert_eq!(TimelineSemaphoreError::from(err),
+                   TimelineSemaphoreError::UnexpectedResult(code));
+    }
+}
@@ -10,11 +10,24 @@
 #![cfg(test)]
 
 /// Creates an instance or returns if initialization fails.
+///
+/// The instance extensions to enable can be passed with `extensions: [...]`.
 macro_rules! instance {
     () => ({
+        instance!(extensions: [])
+    });
+
+    (extensions: [$($extension:ident),*]) => ({
         use instance;
 
-        match instance::Instance::new(None, &instance::InstanceExtensions::none(), None) {
+        let extensions = instance::InstanceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. instance::InstanceExtensions::none()
+        };
+
+        match instance::Instance::new(None, &extensions, None) {
             Ok(i) => i,
             Err(_) => return
         }
@@ -22,8 +35,14 @@ macro_rules! instance {
 }
 
 /// Creates a device and a queue for graphics operations.
+///
+/// The device extensions to enable can be passed with `extensions: [...]` before the features.
 macro_rules! gfx_dev_and_queue {
     ($($feature:ident),*) => ({
+        gfx_dev_and_queue!(extensions: [] $(, $feature)*)
+    });
+
+    (extensions: [$($extension:ident),*] $(, $feature:ident)*) => ({
         use instance;
         use device::Device;
         use device::DeviceExtensions;
@@ -41,7 +60,17 @@ macro_rules! gfx_dev_and_queue {
             None => return
         };
 
-        let extensions = DeviceExtensions::none();
+        let extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::none()
+        };
+
+        // If the physical device doesn't support the requested extensions, just return.
+        if !DeviceExtensions::supported_by_device(physical).superset_of(&extensions) {
+            return;
+        }
 
         let features = Features {
             $(
@@ -97,3 +126,73 @@ macro_rules! assert_should_panic {
         }
     });
 }
+
+// Minimal SPIR-V modules whose `main` entry point does nothing, for the tests that need shader
+// stages but not what the shaders compute. They have no inputs, outputs or descriptors.
+
+use descriptor::pipeline_layout::EmptyPipelineDesc;
+use pipeline::shader::EmptyShaderInterfaceDef;
+use pipeline::shader::GraphicsEntryPoint;
+use pipeline::shader::GraphicsShaderType;
+use pipeline::shader::ShaderModule;
+use std::ffi::CStr;
+
+/// Returns the `main` entry point of one of the modules below.
+pub fn main_entry_point(module: &ShaderModule, ty: GraphicsShaderType)
+                        -> GraphicsEntryPoint<(), EmptyShaderInterfaceDef,
+                                              EmptyShaderInterfaceDef, EmptyPipelineDesc> {
+    let name = CStr::from_bytes_with_nul(b"main\0").unwrap();
+    unsafe {
+        module.graphics_entry_point(name, EmptyShaderInterfaceDef, EmptyShaderInterfaceDef,
+                                    EmptyPipelineDesc, ty)
+    }
+}
+
+/// Vertex shader.
+pub const VERTEX_MAIN: [u32; 29] = [
+    0x07230203, 0x00010000, 0, 5, 0,
+    (2 << 16) | 17, 1, // OpCapability Shader
+    (3 << 16) | 14, 0, 1, // OpMemoryModel Logical GLSL450
+    (5 << 16) | 15, 0, 1, 0x6e69616d, 0, // OpEntryPoint Vertex %1 "main"
+    (2 << 16) | 19, 2, // %2 = OpTypeVoid
+    (3 << 16) | 33, 3, 2, // %3 = OpTypeFunction %2
+    (5 << 16) | 54, 2, 1, 0, 3, // %1 = OpFunction %2 None %3
+    (2 << 16) | 248, 4, // %4 = OpLabel
+    (1 << 16) | 253, // OpReturn
+    (1 << 16) | 56, // OpFunctionEnd
+];
+
+/// Fragment shader.
+pub const FRAGMENT_MAIN: [u32; 32] = [
+    0x07230203, 0x00010000, 0, 5, 0,
+    (2 << 16) | 17, 1, // OpCapability Shader
+    (3 << 16) | 14, 0, 1, // OpMemoryModel Logical GLSL450
+    (5 << 16) | 15, 4, 1, 0x6e69616d, 0, // OpEntryPoint Fragment %1 "main"
+    (3 << 16) | 16, 1, 7, // OpExecutionMode %1 OriginUpperLeft
+    (2 << 16) | 19, 2, // %2 = OpTypeVoid
+    (3 << 16) | 33, 3, 2, // %3 = OpTypeFunction %2
+    (5 << 16) | 54, 2, 1, 0, 3, // %1 = OpFunction %2 None %3
+    (2 << 16) | 248, 4, // %4 = OpLabel
+    (1 << 16) | 253, // OpReturn
+    (1 << 16) | 56, // OpFunctionEnd
+];
+
+/// Mesh shader of `VK_EXT_mesh_shader` that outputs no primitive. Uses SPIR-V 1.4.
+pub const MESH_MAIN: [u32; 52] = [
+    0x07230203, 0x00010400, 0, 5, 0,
+    (2 << 16) | 17, 5283, // OpCapability MeshShadingEXT
+    // OpExtension "SPV_EXT_mesh_shader"
+    (6 << 16) | 10, 0x5f565053, 0x5f545845, 0x6873656d, 0x6168735f, 0x00726564,
+    (3 << 16) | 14, 0, 1, // OpMemoryModel Logical GLSL450
+    (5 << 16) | 15, 5365, 1, 0x6e69616d, 0, // OpEntryPoint MeshEXT %1 "main"
+    (6 << 16) | 16, 1, 17, 1, 1, 1, // OpExecutionMode %1 LocalSize 1 1 1
+    (4 << 16) | 16, 1, 26, 3, // OpExecutionMode %1 OutputVertices 3
+    (4 << 16) | 16, 1, 5270, 1, // OpExecutionMode %1 OutputPrimitivesEXT 1
+    (3 << 16) | 16, 1, 5298, // OpExecutionMode %1 OutputTrianglesEXT
+    (2 << 16) | 19, 2, // %2 = OpTypeVoid
+    (3 << 16) | 33, 3, 2, // %3 = OpTypeFunction %2
+    (5 << 16) | 54, 2, 1, 0, 3, // %1 = OpFunction %2 None %3
+    (2 << 16) | 248, 4, // %4 = OpLabel
+    (1 << 16) | 253, // OpReturn
+    (1 << 16) | 56, // OpFunctionEnd
+];
@@ -59,6 +59,13 @@ impl Ord for Version {
 }
 
 impl Version {
+    /// Version 1.0.0 of Vulkan.
+    pub const V1_0: Version = Version { major: 1, minor: 0, patch: 0 };
+    /// Version 1.1.0 of Vulkan.
+    pub const V1_1: Version = Version { major: 1, minor: 1, patch: 0 };
+    /// Version 1.2.0 of Vulkan.
+    pub const V1_2: Version = Version { major: 1, minor: 2, patch: 0 };
+
     /// Turns a version number given by Vulkan into a `Version` struct.
     #[inline]
     pub fn from_vulkan_version(value: u32) -> Version {